@@ -0,0 +1,146 @@
+//! Criterion benchmarks for the scanner hot paths.
+//!
+//! Covers the three stages every scan pass runs per entry: walker traversal,
+//! pattern classification, and `ScoringEngine::score_batch`. Inputs (and the
+//! walked tree, via `scanner::synthetic`) are generated deterministically so
+//! numbers are comparable release to release:
+//!
+//!   cargo bench --bench hot_paths
+//!   cargo bench --bench hot_paths -- --save-baseline v0.2.7
+//!   cargo bench --bench hot_paths -- --baseline v0.2.7
+
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use storage_ballast_helper::core::config::ScoringConfig;
+use storage_ballast_helper::scanner::patterns::{ArtifactPatternRegistry, StructuralSignals};
+use storage_ballast_helper::scanner::protection::ProtectionRegistry;
+use storage_ballast_helper::scanner::scoring::{CandidateInput, ScoringEngine};
+use storage_ballast_helper::scanner::synthetic::{self, SyntheticTreeSpec};
+use storage_ballast_helper::scanner::walker::{DirectoryWalker, StatMode, WalkerConfig};
+
+/// Path shapes seen on agent build hosts, cycled to build inputs.
+const PATH_TEMPLATES: &[&str] = &[
+    "/data/projects/p{i}/target",
+    "/data/projects/p{i}/target/debug/deps",
+    "/data/projects/p{i}/target/debug/incremental",
+    "/data/projects/p{i}/node_modules",
+    "/data/projects/p{i}/node_modules/.cache",
+    "/data/projects/p{i}/__pycache__",
+    "/data/projects/p{i}/build",
+    "/data/projects/p{i}/src",
+    "/tmp/cargo-target-{i}",
+    "/tmp/agent-{i}/workspace",
+    "/home/dev/.cache/pip/wheels/{i}",
+    "/home/dev/Documents/report-{i}",
+];
+
+fn sample_paths(count: usize) -> Vec<PathBuf> {
+    (0..count)
+        .map(|i| {
+            let template = PATH_TEMPLATES[i % PATH_TEMPLATES.len()];
+            PathBuf::from(template.replace("{i}", &i.to_string()))
+        })
+        .collect()
+}
+
+fn sample_signals(i: usize) -> StructuralSignals {
+    StructuralSignals {
+        has_incremental: i % 3 == 0,
+        has_deps: i % 4 == 0,
+        has_build: i % 5 == 0,
+        has_fingerprint: i % 3 == 0,
+        has_git: i % 97 == 0,
+        has_cargo_toml: i % 7 == 0,
+        mostly_object_files: i % 2 == 0,
+    }
+}
+
+fn sample_inputs(registry: &ArtifactPatternRegistry, count: usize) -> Vec<CandidateInput> {
+    sample_paths(count)
+        .into_iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let signals = sample_signals(i);
+            let i = i as u64;
+            CandidateInput {
+                classification: registry.classify(&path, signals),
+                path,
+                size_bytes: (i % 64 + 1) * 64 * 1024 * 1024,
+                disk_bytes: (i % 64 + 1) * 64 * 1024 * 1024,
+                age: Duration::from_secs((i % 72 + 1) * 3600),
+                signals,
+                is_open: false,
+                excluded: false,
+            }
+        })
+        .collect()
+}
+
+fn bench_score_batch(c: &mut Criterion) {
+    let registry = ArtifactPatternRegistry::default();
+    let engine = ScoringEngine::from_config(&ScoringConfig::default(), 30);
+    let mut group = c.benchmark_group("scoring/score_batch");
+    for count in [1_000, 10_000] {
+        let inputs = sample_inputs(&registry, count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &inputs, |b, inputs| {
+            b.iter(|| engine.score_batch(black_box(inputs), black_box(0.5)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_classify(c: &mut Criterion) {
+    let registry = ArtifactPatternRegistry::default();
+    let paths = sample_paths(1_000);
+    let mut group = c.benchmark_group("patterns/classify");
+    group.throughput(Throughput::Elements(paths.len() as u64));
+    group.bench_function("1000_paths", |b| {
+        b.iter(|| {
+            for (i, path) in paths.iter().enumerate() {
+                black_box(registry.classify(black_box(path), sample_signals(i)));
+            }
+        });
+    });
+    group.finish();
+}
+
+fn bench_walker(c: &mut Criterion) {
+    let tmp = tempfile::TempDir::new_in(synthetic::default_base_dir()).unwrap();
+    let spec = SyntheticTreeSpec {
+        projects: 40,
+        ..SyntheticTreeSpec::default()
+    };
+    synthetic::generate(tmp.path(), &spec).unwrap();
+    let config = WalkerConfig {
+        root_paths: vec![tmp.path().to_path_buf()],
+        max_depth: 10,
+        follow_symlinks: false,
+        cross_devices: false,
+        parallelism: 4,
+        excluded_paths: HashSet::new(),
+    };
+
+    let mut group = c.benchmark_group("walker/traverse");
+    group.sample_size(20);
+    for mode in [StatMode::Portable, StatMode::Reduced] {
+        group.bench_function(format!("{mode:?}").to_lowercase(), |b| {
+            b.iter(|| {
+                let walker =
+                    DirectoryWalker::new(config.clone(), ProtectionRegistry::marker_only())
+                        .with_stat_mode(mode);
+                black_box(walker.walk().unwrap().len())
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_score_batch, bench_classify, bench_walker);
+criterion_main!(benches);
@@ -52,6 +52,7 @@ fn main() {
             candidates.push(CandidateInput {
                 path: entry.path.clone(),
                 size_bytes: entry.metadata.content_size_bytes,
+                disk_bytes: entry.metadata.content_disk_bytes,
                 age,
                 classification,
                 signals: entry.structural_signals,
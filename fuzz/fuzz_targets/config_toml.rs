@@ -0,0 +1,23 @@
+//! `config.toml` contents through parsing, normalization, and validation.
+
+#![no_main]
+
+use std::path::Path;
+
+use libfuzzer_sys::fuzz_target;
+use storage_ballast_helper::core::config::Config;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(config) = Config::from_toml_str(raw) else {
+        return;
+    };
+    // A config that validates must also hash and resolve mounts cleanly.
+    let _ = config.stable_hash();
+    for mount in &config.pressure.mounts {
+        let _ = config.pressure.for_mount(&mount.path);
+    }
+    let _ = config.pressure.for_mount(Path::new("/"));
+});
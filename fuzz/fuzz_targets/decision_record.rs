@@ -0,0 +1,27 @@
+//! Stored decision records through deserialization and every explain level.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use storage_ballast_helper::scanner::decision_record::{
+    ExplainLevel, decision_summary_line, format_explain, parse_decision_from_details,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Some(record) = parse_decision_from_details(raw) else {
+        return;
+    };
+    let _ = decision_summary_line(&record);
+    for level in [
+        ExplainLevel::L0,
+        ExplainLevel::L1,
+        ExplainLevel::L2,
+        ExplainLevel::L3,
+    ] {
+        let _ = format_explain(&record, level);
+        let _ = record.to_json_at_level(level);
+    }
+});
@@ -0,0 +1,12 @@
+//! Activity-log lines through the dashboard's schema-shield parser.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use storage_ballast_helper::tui::telemetry::parse_timeline_line;
+
+fuzz_target!(|data: &[u8]| {
+    for line in String::from_utf8_lossy(data).lines() {
+        let _ = parse_timeline_line(line);
+    }
+});
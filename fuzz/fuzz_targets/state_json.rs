@@ -0,0 +1,12 @@
+//! `state.json` contents through the schema check and typed decode.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use storage_ballast_helper::core::state::parse_state;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(raw) = std::str::from_utf8(data) {
+        let _ = parse_state(raw);
+    }
+});
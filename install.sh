@@ -11,9 +11,14 @@ ARCH="$(uname -m)"
 
 case "${OS}" in
   Linux)
+    # musl-based distributions (Alpine) get the static musl build.
+    LIBC="gnu"
+    if ldd --version 2>&1 | grep -qi musl || compgen -G '/lib/ld-musl-*.so.1' >/dev/null; then
+      LIBC="musl"
+    fi
     case "${ARCH}" in
-      x86_64)  TARGET="x86_64-unknown-linux-gnu" ;;
-      aarch64) TARGET="aarch64-unknown-linux-gnu" ;;
+      x86_64)  TARGET="x86_64-unknown-linux-${LIBC}" ;;
+      aarch64) TARGET="aarch64-unknown-linux-${LIBC}" ;;
       *)       echo "Unsupported Linux architecture: ${ARCH}" >&2; exit 1 ;;
     esac
     ;;
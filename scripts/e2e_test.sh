@@ -522,9 +522,13 @@ create_offline_update_bundle_fixture() {
 
   case "$(uname -s)" in
     Linux)
+      local libc="gnu"
+      if ldd --version 2>&1 | grep -qi musl; then
+        libc="musl"
+      fi
       case "$(uname -m)" in
-        x86_64) triple="x86_64-unknown-linux-gnu" ;;
-        aarch64|arm64) triple="aarch64-unknown-linux-gnu" ;;
+        x86_64) triple="x86_64-unknown-linux-${libc}" ;;
+        aarch64|arm64) triple="aarch64-unknown-linux-${libc}" ;;
       esac
       ;;
     Darwin)
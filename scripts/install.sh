@@ -303,16 +303,26 @@ parse_args() {
   done
 }
 
+# musl-based distributions (Alpine) get the static musl build.
+linux_libc() {
+  if ldd --version 2>&1 | grep -qi musl || compgen -G '/lib/ld-musl-*.so.1' >/dev/null; then
+    echo musl
+  else
+    echo gnu
+  fi
+}
+
 resolve_target_triple() {
-  local os arch
+  local os arch libc
   os="$(uname -s | tr '[:upper:]' '[:lower:]')"
   arch="$(uname -m)"
 
   case "$os" in
     linux)
+      libc="$(linux_libc)"
       case "$arch" in
-        x86_64) TARGET_TRIPLE="x86_64-unknown-linux-gnu" ;;
-        aarch64|arm64) TARGET_TRIPLE="aarch64-unknown-linux-gnu" ;;
+        x86_64) TARGET_TRIPLE="x86_64-unknown-linux-${libc}" ;;
+        aarch64|arm64) TARGET_TRIPLE="aarch64-unknown-linux-${libc}" ;;
         *) die "Unsupported Linux architecture: $arch" ;;
       esac
       ;;
@@ -7,7 +7,7 @@
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::ballast::manager::{BallastManager, ProvisionReport, ReleaseReport, VerifyReport};
@@ -18,10 +18,11 @@ use crate::platform::pal::{MountPoint, Platform};
 // ──────────────────── constants ────────────────────
 
 /// Subdirectory name placed on each volume for ballast files.
-const BALLAST_SUBDIR: &str = ".sbh/ballast";
+pub const BALLAST_SUBDIR: &str = ".sbh/ballast";
 
-/// Filesystem types where `fallocate()` reserves real blocks.
-const FALLOCATE_FRIENDLY: &[&str] = &["ext4", "xfs", "ext3", "ext2"];
+/// Filesystem types where preallocation reserves real blocks (`fallocate()`
+/// on Linux, `F_PREALLOCATE` on macOS).
+const FALLOCATE_FRIENDLY: &[&str] = &["ext4", "xfs", "ext3", "ext2", "apfs", "hfs"];
 
 /// CoW filesystems where fallocate doesn't prevent dedup — random data required.
 const COW_FILESYSTEMS: &[&str] = &["btrfs", "zfs", "bcachefs"];
@@ -37,7 +38,7 @@ const NETWORK_FILESYSTEMS: &[&str] = &["nfs", "nfs4", "cifs", "smbfs", "fuse.ssh
 /// Provisioning strategy based on filesystem type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProvisionStrategy {
-    /// Use fallocate for instant block allocation (ext4, xfs).
+    /// Preallocate for instant block allocation (ext4, xfs, apfs).
     Fallocate,
     /// Write random data to defeat CoW dedup (btrfs, zfs).
     RandomData,
@@ -132,7 +133,9 @@ pub struct BallastPoolCoordinator {
 
 impl BallastPoolCoordinator {
     /// Discover and initialize pools for all unique mount points derived from
-    /// the given watched paths. Skips RAM-backed and read-only filesystems.
+    /// the given watched paths and any explicitly placed `[[ballast.pools]]`.
+    /// Skips RAM-backed and read-only filesystems.
+    #[allow(clippy::too_many_lines)]
     pub fn discover(
         config: &BallastConfig,
         watched_paths: &[PathBuf],
@@ -152,14 +155,48 @@ impl BallastPoolCoordinator {
             }
         }
 
+        // Explicit pools add their own mounts; one pool per filesystem.
+        let mut explicit_dirs = HashMap::<PathBuf, PathBuf>::new();
+        for pool in &config.pools {
+            let Some(mount) = find_mount(&pool.path, &mounts) else {
+                eprintln!(
+                    "[SBH-WARN] no mount found for ballast pool {}",
+                    pool.path.display()
+                );
+                continue;
+            };
+            if let Some(existing) = explicit_dirs.get(&mount.path) {
+                eprintln!(
+                    "[SBH-WARN] ballast pool {} shares {} with {}; ignoring it",
+                    pool.path.display(),
+                    mount.path.display(),
+                    existing.display()
+                );
+                continue;
+            }
+            explicit_dirs.insert(mount.path.clone(), pool.path.clone());
+            seen_mounts
+                .entry(mount.path.clone())
+                .or_insert_with(|| mount.clone());
+        }
+
         for (mount_path, mount) in &seen_mounts {
             let mount_str = mount_path.to_string_lossy();
             let strategy = provision_strategy(&mount.fs_type);
+            let explicit_dir = explicit_dirs.get(mount_path);
+            let ballast_dir = explicit_dir
+                .cloned()
+                .or_else(|| {
+                    config
+                        .effective_ballast_dir(&mount_str)
+                        .map(Path::to_path_buf)
+                })
+                .unwrap_or_else(|| mount_path.join(BALLAST_SUBDIR));
             let mut skip_with = |reason: String| {
                 skipped_pools.insert(
                     mount_path.clone(),
                     SkippedPoolInfo {
-                        ballast_dir: mount_path.join(BALLAST_SUBDIR),
+                        ballast_dir: ballast_dir.clone(),
                         fs_type: mount.fs_type.clone(),
                         strategy,
                         reason,
@@ -174,8 +211,8 @@ impl BallastPoolCoordinator {
                 );
             }
 
-            // Check override: explicitly disabled?
-            if !config.is_volume_enabled(&mount_str) {
+            // Check override: explicitly disabled? An explicit pool wins.
+            if explicit_dir.is_none() && !config.is_volume_enabled(&mount_str) {
                 skip_with("disabled via ballast volume override".to_string());
                 continue;
             }
@@ -211,19 +248,8 @@ impl BallastPoolCoordinator {
                 continue;
             }
 
-            // Determine ballast directory for this volume.
-            let ballast_dir = mount_path.join(BALLAST_SUBDIR);
-
             // Build per-volume config.
-            let file_count = config.effective_file_count(&mount_str);
-            let file_size_bytes = config.effective_file_size_bytes(&mount_str);
-            let pool_config = BallastConfig {
-                file_count,
-                file_size_bytes,
-                replenish_cooldown_minutes: config.replenish_cooldown_minutes,
-                auto_provision: config.auto_provision,
-                overrides: BTreeMap::new(),
-            };
+            let pool_config = config.pool_settings(&mount_str, &ballast_dir);
 
             let mut manager = match BallastManager::new(ballast_dir.clone(), pool_config) {
                 Ok(manager) => manager,
@@ -340,6 +366,20 @@ impl BallastPoolCoordinator {
         Ok(Some(report))
     }
 
+    /// Re-create corrupted ballast files on a specific mount point.
+    pub fn repair_for_mount(
+        &mut self,
+        mount_path: &Path,
+        free_pct_check: Option<&dyn Fn() -> f64>,
+    ) -> Result<Option<ProvisionReport>> {
+        let Some(pool) = self.pools.get_mut(mount_path) else {
+            return Ok(None);
+        };
+
+        let report = pool.manager.repair(free_pct_check)?;
+        Ok(Some(report))
+    }
+
     /// Verify integrity of all pools.
     pub fn verify_all(&mut self) -> Vec<(PathBuf, VerifyReport)> {
         self.pools
@@ -449,16 +489,7 @@ impl BallastPoolCoordinator {
     pub fn update_config(&mut self, config: &BallastConfig) {
         for (mount_path, pool) in &mut self.pools {
             let mount_str = mount_path.to_string_lossy();
-            let file_count = config.effective_file_count(&mount_str);
-            let file_size_bytes = config.effective_file_size_bytes(&mount_str);
-
-            let pool_config = BallastConfig {
-                file_count,
-                file_size_bytes,
-                replenish_cooldown_minutes: config.replenish_cooldown_minutes,
-                auto_provision: config.auto_provision,
-                overrides: std::collections::BTreeMap::new(),
-            };
+            let pool_config = config.pool_settings(&mount_str, &pool.ballast_dir);
             pool.manager.update_config(pool_config);
         }
     }
@@ -492,9 +523,9 @@ fn find_mount<'a>(path: &Path, mounts: &'a [MountPoint]) -> Option<&'a MountPoin
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::config::BallastVolumeOverride;
+    use crate::core::config::{BallastPoolConfig, BallastVolumeOverride};
     use crate::platform::pal::{FsStats, MemoryInfo, MockPlatform, PlatformPaths};
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
 
     fn tiny_ballast_config() -> BallastConfig {
         BallastConfig {
@@ -502,7 +533,10 @@ mod tests {
             file_size_bytes: 4096 + 4096, // header + 4KB data
             replenish_cooldown_minutes: 0,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides: BTreeMap::new(),
+            pools: Vec::new(),
         }
     }
 
@@ -656,6 +690,7 @@ mod tests {
                 enabled: false,
                 file_count: None,
                 file_size_bytes: None,
+                ballast_dir: None,
             },
         );
 
@@ -680,6 +715,42 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn explicit_pools_cover_unwatched_mounts_with_their_own_sizes() {
+        let dir_data = tempfile::tempdir().unwrap();
+        let dir_root = tempfile::tempdir().unwrap();
+        let platform = mock_platform_two_volumes(dir_data.path(), dir_root.path());
+
+        let pool_dir = dir_root.path().join("reserve");
+        let mut config = tiny_ballast_config();
+        config.pools.push(BallastPoolConfig {
+            path: pool_dir.clone(),
+            file_count: Some(2),
+            file_size_bytes: None,
+        });
+        // Second pool on the same filesystem is ignored.
+        config.pools.push(BallastPoolConfig {
+            path: dir_root.path().join("spare"),
+            file_count: Some(9),
+            file_size_bytes: None,
+        });
+
+        let watched = vec![dir_data.path().to_path_buf()];
+        let mut coordinator =
+            BallastPoolCoordinator::discover(&config, &watched, &platform).unwrap();
+        assert_eq!(coordinator.pool_count(), 2);
+        let pool = coordinator.pool_for_mount(dir_root.path()).unwrap();
+        assert_eq!(pool.ballast_dir, pool_dir);
+        assert_eq!(pool.manager.config().file_count, 2);
+        assert_eq!(
+            pool.manager.config().file_size_bytes,
+            config.file_size_bytes
+        );
+
+        let report = coordinator.provision_all(&platform).unwrap();
+        assert_eq!(report.total_files_created(), 5); // 3 watched + 2 explicit
+    }
+
     #[test]
     fn provision_all_creates_files_on_each_volume() {
         let dir_data = tempfile::tempdir().unwrap();
@@ -779,6 +850,7 @@ mod tests {
                 enabled: true,
                 file_count: Some(2),
                 file_size_bytes: None,
+                ballast_dir: None,
             },
         );
 
@@ -795,6 +867,31 @@ mod tests {
         assert_eq!(pool_b.available_count(), 3);
     }
 
+    #[test]
+    fn volume_override_relocates_pool_dir() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+        let platform = mock_platform_two_volumes(dir_a.path(), dir_b.path());
+
+        let mut config = tiny_ballast_config();
+        let reserve = dir_a.path().join("reserve");
+        config.overrides.insert(
+            dir_a.path().to_string_lossy().to_string(),
+            BallastVolumeOverride {
+                ballast_dir: Some(reserve.clone()),
+                ..BallastVolumeOverride::default()
+            },
+        );
+
+        let watched = vec![dir_a.path().to_path_buf(), dir_b.path().to_path_buf()];
+        let coordinator = BallastPoolCoordinator::discover(&config, &watched, &platform).unwrap();
+
+        let pool_a = coordinator.pool_for_mount(dir_a.path()).unwrap();
+        assert_eq!(pool_a.ballast_dir, reserve);
+        let pool_b = coordinator.pool_for_mount(dir_b.path()).unwrap();
+        assert_eq!(pool_b.ballast_dir, dir_b.path().join(BALLAST_SUBDIR));
+    }
+
     #[test]
     fn verify_all_reports_per_volume() {
         let dir_a = tempfile::tempdir().unwrap();
@@ -905,6 +1002,7 @@ mod tests {
     fn provision_strategy_detection() {
         assert_eq!(provision_strategy("ext4"), ProvisionStrategy::Fallocate);
         assert_eq!(provision_strategy("xfs"), ProvisionStrategy::Fallocate);
+        assert_eq!(provision_strategy("apfs"), ProvisionStrategy::Fallocate);
         assert_eq!(provision_strategy("btrfs"), ProvisionStrategy::RandomData);
         assert_eq!(provision_strategy("zfs"), ProvisionStrategy::RandomData);
         assert_eq!(provision_strategy("tmpfs"), ProvisionStrategy::Skip);
@@ -1,9 +1,11 @@
 //! Ballast file manager: create/verify/reclaim pre-allocated sacrificial space files.
 //!
 //! Ballast files are named `SBH_BALLAST_FILE_NNNNN.dat` and contain a 4096-byte
-//! JSON header followed by random data. On ext4/xfs, provisioning uses `fallocate()`
-//! for instant allocation; on CoW filesystems (btrfs, zfs), random data is written
-//! in 4 MB chunks to defeat deduplication.
+//! JSON header followed by the reserved data. Where the filesystem supports it,
+//! the data is preallocated (`fallocate()` on Linux, `F_PREALLOCATE` on macOS)
+//! and the file is checked to be space-backed rather than sparse; otherwise, and
+//! always on CoW filesystems (btrfs, zfs), random data is written in 4 MB chunks
+//! to defeat deduplication.
 //!
 //! Access to the ballast directory is serialized via `flock()` on a lockfile so
 //! concurrent daemon + CLI operations don't race.
@@ -15,7 +17,7 @@
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 #[cfg(unix)]
@@ -23,6 +25,7 @@ use std::os::unix::fs::OpenOptionsExt;
 
 use crate::core::config::BallastConfig;
 use crate::core::errors::{Result, SbhError};
+use crate::scanner::walker::allocated_bytes;
 
 // ──────────────────── constants ────────────────────
 
@@ -102,6 +105,20 @@ pub struct ReleaseReport {
     pub errors: Vec<String>,
 }
 
+/// Result of a resize operation.
+#[derive(Debug, Clone, Default)]
+pub struct ResizeReport {
+    pub files_created: usize,
+    pub files_removed: usize,
+    /// Existing files grown or truncated in place.
+    pub files_resized: usize,
+    /// Bytes newly allocated by created and grown files.
+    pub bytes_added: u64,
+    /// Bytes returned by removed and truncated files.
+    pub bytes_freed: u64,
+    pub errors: Vec<String>,
+}
+
 // ──────────────────── manager ────────────────────
 
 /// Manages the lifecycle of ballast files: creation, verification, release, replenishment.
@@ -132,6 +149,21 @@ impl BallastManager {
         Ok(mgr)
     }
 
+    /// Open an existing pool for inspection only: unlike [`Self::new`] this
+    /// neither creates the directory nor prunes files beyond the configured
+    /// count.
+    #[must_use]
+    pub fn inspect(ballast_dir: PathBuf, config: BallastConfig) -> Self {
+        let mut mgr = Self {
+            ballast_dir,
+            config,
+            inventory: Vec::new(),
+            skip_fallocate: false,
+        };
+        mgr.scan_existing();
+        mgr
+    }
+
     /// Directory containing ballast files.
     pub fn ballast_dir(&self) -> &Path {
         &self.ballast_dir
@@ -300,6 +332,114 @@ impl BallastManager {
         Ok(report)
     }
 
+    // ──────────────────── resize ────────────────────
+
+    /// Change the pool to `file_count` files of `file_size_bytes` each,
+    /// touching only the difference: files past the new count are deleted,
+    /// valid files are grown or truncated in place with their header
+    /// rewritten, and only indices past the old count are created. Released
+    /// files stay released for replenishment.
+    ///
+    /// Space is freed before any is allocated, and `free_pct_check` gates
+    /// each allocation as in [`Self::provision`].
+    pub fn resize(
+        &mut self,
+        file_count: usize,
+        file_size_bytes: u64,
+        free_pct_check: Option<&dyn Fn() -> f64>,
+    ) -> Result<ResizeReport> {
+        if file_size_bytes < HEADER_SIZE as u64 {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "file_size_bytes ({file_size_bytes}) must be >= HEADER_SIZE ({HEADER_SIZE})"
+                ),
+            });
+        }
+        let _lock = self.acquire_lock()?;
+        let mut report = ResizeReport::default();
+        let old_count = self.config.file_count;
+        let old_size = self.config.file_size_bytes;
+
+        for i in (file_count + 1..=old_count).rev() {
+            let path = self.file_path(i as u32);
+            let Ok(meta) = fs::metadata(&path) else {
+                continue;
+            };
+            match fs::remove_file(&path) {
+                Ok(()) => {
+                    report.files_removed += 1;
+                    report.bytes_freed += meta.len();
+                }
+                Err(e) => report.errors.push(format!("file {i}: {e}")),
+            }
+        }
+
+        // Files that do not verify are left for `repair` to recreate.
+        let kept: Vec<u32> = (1..=old_count.min(file_count))
+            .map(|i| i as u32)
+            .filter(|&index| {
+                let path = self.file_path(index);
+                path.exists() && self.verify_single_file(&path, index).is_ok()
+            })
+            .collect();
+        if file_size_bytes != old_size {
+            let shrinking = file_size_bytes < old_size;
+            for index in kept {
+                if !shrinking && let Some(check) = free_pct_check {
+                    let free = check();
+                    if free < MIN_FREE_PCT {
+                        report.errors.push(format!(
+                            "stopped growing at file {index}: free space {free:.1}% < {MIN_FREE_PCT}%"
+                        ));
+                        break;
+                    }
+                }
+                let path = self.file_path(index);
+                match self.resize_ballast_file(index, &path, old_size, file_size_bytes) {
+                    Ok(()) => {
+                        report.files_resized += 1;
+                        if shrinking {
+                            report.bytes_freed += old_size - file_size_bytes;
+                        } else {
+                            report.bytes_added += file_size_bytes - old_size;
+                        }
+                    }
+                    Err(e) => {
+                        // A half-grown file would fail verification; drop it
+                        // so replenishment recreates it at the new size.
+                        let _ = fs::remove_file(&path);
+                        report.errors.push(format!("file {index}: {e}"));
+                    }
+                }
+            }
+        }
+
+        self.config.file_count = file_count;
+        self.config.file_size_bytes = file_size_bytes;
+        for i in old_count + 1..=file_count {
+            let index = i as u32;
+            if let Some(check) = free_pct_check {
+                let free = check();
+                if free < MIN_FREE_PCT {
+                    report.errors.push(format!(
+                        "aborted at file {index}: free space {free:.1}% < {MIN_FREE_PCT}%"
+                    ));
+                    break;
+                }
+            }
+            match self.create_ballast_file(index) {
+                Ok(()) => {
+                    report.files_created += 1;
+                    report.bytes_added += file_size_bytes;
+                }
+                Err(e) => report.errors.push(format!("file {index}: {e}")),
+            }
+        }
+
+        self.scan_existing();
+        Ok(report)
+    }
+
     // ──────────────────── verify ────────────────────
 
     /// Verify integrity of all expected ballast files.
@@ -335,6 +475,54 @@ impl BallastManager {
         Ok(report)
     }
 
+    /// Re-create ballast files that exist but fail verification.
+    ///
+    /// Missing (released) files are left for replenishment. A corrupted file
+    /// is kept as-is when free space is below the safety floor, since
+    /// deleting it first could still leave the mount without the file.
+    pub fn repair(&mut self, free_pct_check: Option<&dyn Fn() -> f64>) -> Result<ProvisionReport> {
+        let _lock = self.acquire_lock()?;
+        let mut report = ProvisionReport {
+            files_created: 0,
+            files_skipped: 0,
+            total_bytes: 0,
+            errors: Vec::new(),
+        };
+
+        for i in 1..=self.config.file_count {
+            let index = i as u32;
+            let path = self.file_path(index);
+
+            if !path.exists() || self.verify_single_file(&path, index).is_ok() {
+                report.files_skipped += 1;
+                continue;
+            }
+
+            if let Some(check) = free_pct_check {
+                let free = check();
+                if free < MIN_FREE_PCT {
+                    report.errors.push(format!(
+                        "file {index} left corrupted: free space {free:.1}% < {MIN_FREE_PCT}%"
+                    ));
+                    continue;
+                }
+            }
+
+            let _ = fs::remove_file(&path);
+            match self.create_ballast_file(index) {
+                Ok(()) => {
+                    report.files_created += 1;
+                    report.total_bytes +=
+                        fs::metadata(&path).map_or(self.config.file_size_bytes, |m| m.len());
+                }
+                Err(e) => report.errors.push(format!("file {index}: {e}")),
+            }
+        }
+
+        self.scan_existing();
+        Ok(report)
+    }
+
     // ──────────────────── replenish ────────────────────
 
     /// Recreate released ballast files when pressure subsides.
@@ -566,35 +754,58 @@ impl BallastManager {
             opts.open(path).map_err(|e| SbhError::io(path, e))?
         };
 
-        // Write header (4096 bytes, null-padded).
-        let header = BallastHeader::new(index, size);
-        let header_json = serde_json::to_string(&header)?;
-        if header_json.len() > HEADER_SIZE {
-            return Err(SbhError::Runtime {
-                details: format!(
-                    "ballast header JSON ({} bytes) exceeds HEADER_SIZE ({HEADER_SIZE})",
-                    header_json.len()
-                ),
-            });
-        }
-        let mut header_buf = vec![0u8; HEADER_SIZE];
-        header_buf[..header_json.len()].copy_from_slice(header_json.as_bytes());
-        file.write_all(&header_buf)
-            .map_err(|e| SbhError::io(path, e))?;
+        write_header(&mut file, index, size, path)?;
 
         // Write data portion.
         let data_size = size - HEADER_SIZE as u64;
+        self.allocate_data(&mut file, HEADER_SIZE as u64, data_size, path)
+    }
+
+    /// Grow or truncate an existing ballast file to `new_size`, keeping its
+    /// data and rewriting the header.
+    fn resize_ballast_file(
+        &self,
+        index: u32,
+        path: &Path,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map_err(|e| SbhError::io(path, e))?;
+        if new_size < old_size {
+            file.set_len(new_size).map_err(|e| SbhError::io(path, e))?;
+        } else {
+            file.seek(SeekFrom::Start(old_size))
+                .map_err(|e| SbhError::io(path, e))?;
+            self.allocate_data(&mut file, old_size, new_size - old_size, path)?;
+        }
+        file.seek(SeekFrom::Start(0))
+            .map_err(|e| SbhError::io(path, e))?;
+        write_header(&mut file, index, new_size, path)?;
+        file.sync_all().map_err(|e| SbhError::io(path, e))
+    }
 
-        // Try fallocate (instant on ext4/xfs, no unsafe needed).
+    /// Allocate `len` bytes at `offset`, where the file's cursor must be, and
+    /// sync.
+    fn allocate_data(&self, file: &mut File, offset: u64, len: u64, path: &Path) -> Result<()> {
+        // Preallocation is instant and writes nothing, sparing SSDs the wear.
         // Skipped on CoW filesystems where zero-filled blocks defeat dedup.
-        #[cfg(target_os = "linux")]
-        if !self.skip_fallocate && try_fallocate_fd(&file, HEADER_SIZE as u64, data_size) {
+        if !self.skip_fallocate
+            && preallocate(file, offset, len).map_err(|e| SbhError::io(path, e))?
+        {
             file.sync_all().map_err(|e| SbhError::io(path, e))?;
-            return Ok(());
+            // A filesystem may accept the call yet leave the range sparse,
+            // which would reserve nothing; write the data in that case.
+            let meta = file.metadata().map_err(|e| SbhError::io(path, e))?;
+            if allocated_bytes(&meta) >= meta.len() {
+                return Ok(());
+            }
         }
 
         // Fallback: write random data in chunks (works on all FS including CoW).
-        self.write_random_data(&mut file, data_size, path)?;
+        self.write_random_data(file, len, path)?;
 
         file.sync_all().map_err(|e| SbhError::io(path, e))?;
         Ok(())
@@ -631,29 +842,48 @@ impl BallastManager {
     }
 }
 
-// ──────────────────── fallocate (Linux) ────────────────────
+/// Write the 4096-byte, null-padded header at the file's cursor.
+fn write_header(file: &mut File, index: u32, size: u64, path: &Path) -> Result<()> {
+    let header = BallastHeader::new(index, size);
+    let header_json = serde_json::to_string(&header)?;
+    if header_json.len() > HEADER_SIZE {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "ballast header JSON ({} bytes) exceeds HEADER_SIZE ({HEADER_SIZE})",
+                header_json.len()
+            ),
+        });
+    }
+    let mut header_buf = vec![0u8; HEADER_SIZE];
+    header_buf[..header_json.len()].copy_from_slice(header_json.as_bytes());
+    file.write_all(&header_buf)
+        .map_err(|e| SbhError::io(path, e))
+}
+
+// ──────────────────── preallocation ────────────────────
 
-/// Try to use `fallocate` for instant block allocation on ext4/xfs.
+/// Reserve `len` bytes at `offset` without writing them, growing the file to
+/// cover the range: `fallocate` on Linux, `F_PREALLOCATE` on macOS.
 ///
-/// Falls back to random data writing if the syscall is not available or fails
-/// (e.g., on CoW filesystems like btrfs/zfs where fallocate doesn't prevent dedup).
-#[cfg(target_os = "linux")]
-fn try_fallocate_fd(file: &File, offset: u64, len: u64) -> bool {
-    use nix::fcntl::{FallocateFlags, fallocate};
-    use std::os::unix::io::AsRawFd;
-
-    // fallocate(fd, mode, offset, len)
-    match fallocate(
-        file.as_raw_fd(),
-        FallocateFlags::empty(),
-        offset.cast_signed(),
-        len.cast_signed(),
-    ) {
-        Ok(()) => true,
-        Err(_) => false,
+/// `Ok(false)` when the OS or filesystem cannot preallocate. Running out of
+/// space or quota is an error, since writing the data would fail as well.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn preallocate(file: &File, offset: u64, len: u64) -> std::io::Result<bool> {
+    use rustix::fs::{FallocateFlags, fallocate};
+    use rustix::io::Errno;
+
+    match fallocate(file, FallocateFlags::empty(), offset, len) {
+        Ok(()) => Ok(true),
+        Err(e @ (Errno::NOSPC | Errno::DQUOT)) => Err(e.into()),
+        Err(_) => Ok(false),
     }
 }
 
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn preallocate(_file: &File, _offset: u64, _len: u64) -> std::io::Result<bool> {
+    Ok(false)
+}
+
 // ──────────────────── tests ────────────────────
 
 #[cfg(test)]
@@ -666,7 +896,10 @@ mod tests {
             file_size_bytes: HEADER_SIZE as u64 + 8192, // header + 8KB data
             replenish_cooldown_minutes: 0,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides: std::collections::BTreeMap::new(),
+            pools: Vec::new(),
         }
     }
 
@@ -745,6 +978,34 @@ mod tests {
         assert_eq!(report.files_corrupted, 1);
     }
 
+    #[test]
+    fn repair_recreates_only_corrupted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mgr = BallastManager::new(dir.path().to_path_buf(), small_config()).unwrap();
+        mgr.provision(None).unwrap();
+        mgr.release(1).unwrap();
+
+        let path = dir.path().join("SBH_BALLAST_FILE_00002.dat");
+        let mut data = fs::read(&path).unwrap();
+        data[0..5].copy_from_slice(b"JUNK!");
+        fs::write(&path, &data).unwrap();
+
+        // Below the safety floor the corrupted file is left in place.
+        let low = || 1.0;
+        let report = mgr.repair(Some(&low)).unwrap();
+        assert_eq!(report.files_created, 0);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(mgr.verify().unwrap().files_corrupted, 1);
+
+        let report = mgr.repair(None).unwrap();
+        assert_eq!(report.files_created, 1);
+        let verify = mgr.verify().unwrap();
+        assert_eq!(verify.files_ok, 2);
+        assert_eq!(verify.files_corrupted, 0);
+        // The released file stays released.
+        assert_eq!(verify.files_missing, 1);
+    }
+
     #[test]
     fn release_deletes_highest_index_first() {
         let dir = tempfile::tempdir().unwrap();
@@ -861,6 +1122,26 @@ mod tests {
         assert_eq!(mode, 0o600, "ballast file should be owner-only (0o600)");
     }
 
+    #[test]
+    fn provisioned_files_are_space_backed() {
+        for skip_fallocate in [false, true] {
+            let dir = tempfile::tempdir().unwrap();
+            let mut mgr = BallastManager::new(dir.path().to_path_buf(), small_config()).unwrap();
+            mgr.set_skip_fallocate(skip_fallocate);
+            mgr.provision(None).unwrap();
+
+            for file in mgr.inventory() {
+                let meta = fs::metadata(&file.path).unwrap();
+                assert_eq!(meta.len(), small_config().file_size_bytes);
+                assert!(
+                    allocated_bytes(&meta) >= meta.len(),
+                    "{} is sparse (skip_fallocate={skip_fallocate})",
+                    file.path.display()
+                );
+            }
+        }
+    }
+
     #[test]
     fn reducing_file_count_removes_orphans() {
         let dir = tempfile::tempdir().unwrap();
@@ -896,4 +1177,50 @@ mod tests {
             "Orphaned file 5 should be removed"
         );
     }
+
+    #[test]
+    fn resize_touches_only_the_difference() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mgr = BallastManager::new(dir.path().to_path_buf(), small_config()).unwrap();
+        mgr.provision(None).unwrap();
+        mgr.release(1).unwrap();
+        let kept = dir.path().join("SBH_BALLAST_FILE_00001.dat");
+        let mut tail = vec![0u8; 64];
+        File::open(&kept)
+            .and_then(|mut f| {
+                f.seek(SeekFrom::End(-64))?;
+                f.read_exact(&mut tail)
+            })
+            .unwrap();
+
+        // Grow: 3 -> 5 files, 8 KB -> 16 KB of data.
+        let grown = HEADER_SIZE as u64 + 16_384;
+        let report = mgr.resize(5, grown, None).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.files_created, 2);
+        assert_eq!(report.files_resized, 2);
+        assert_eq!(report.bytes_added, 2 * grown + 2 * 8192);
+        assert_eq!(mgr.available_count(), 4, "released file 3 stays released");
+        assert_eq!(mgr.verify().unwrap().files_ok, 4);
+
+        // The original data is kept, not rewritten.
+        let mut after = vec![0u8; 64];
+        File::open(&kept)
+            .and_then(|mut f| {
+                f.seek(SeekFrom::Start(HEADER_SIZE as u64 + 8192 - 64))?;
+                f.read_exact(&mut after)
+            })
+            .unwrap();
+        assert_eq!(tail, after);
+
+        // Shrink: 5 -> 2 files, back to 8 KB.
+        let report = mgr.resize(2, small_config().file_size_bytes, None).unwrap();
+        assert_eq!(report.files_removed, 2);
+        assert_eq!(report.files_resized, 2);
+        assert_eq!(report.bytes_freed, 2 * grown + 2 * 8192);
+        assert_eq!(mgr.verify().unwrap().files_ok, 2);
+        assert!(!dir.path().join("SBH_BALLAST_FILE_00004.dat").exists());
+
+        assert!(mgr.resize(2, 16, None).is_err(), "smaller than the header");
+    }
 }
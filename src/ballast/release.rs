@@ -9,7 +9,9 @@
 //! - 0.9..1.0: release ALL ballast (emergency)
 //!
 //! Replenishment only occurs when pressure stays Green for the configured cooldown
-//! period, and is paused if pressure rises during the process.
+//! period, and is paused if pressure rises during the process. It is also
+//! deferred while the mount's fill-rate forecast says the new files would
+//! push it below the yellow threshold within the forecast horizon.
 
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
@@ -33,6 +35,62 @@ struct MountReleaseState {
     green_since: Option<Instant>,
     /// Last time a file was replenished.
     last_replenish_time: Option<Instant>,
+    /// Set once a forecast deferral has been reported, until the next
+    /// replenishment, so a long deferral is logged once.
+    deferral_reported: bool,
+}
+
+/// Projected free space for a mount over the replenish forecast horizon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplenishForecast {
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    /// EWMA fill rate in bytes/second; a recovering mount counts as 0.
+    pub fill_rate_bps: f64,
+    pub horizon: Duration,
+    /// Free percentage the mount must keep (the yellow threshold).
+    pub floor_pct: f64,
+}
+
+impl ReplenishForecast {
+    /// Free bytes expected at the end of the horizon, before replenishing.
+    #[must_use]
+    pub fn projected_free_bytes(&self) -> f64 {
+        let growth = self.fill_rate_bps.max(0.0) * self.horizon.as_secs_f64();
+        self.free_bytes as f64 - growth
+    }
+
+    /// Projected free percentage at the horizon after adding `files` files
+    /// of `file_size` bytes.
+    #[must_use]
+    pub fn projected_free_pct(&self, files: usize, file_size: u64) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        let after = (files as f64).mul_add(-(file_size as f64), self.projected_free_bytes());
+        after / self.total_bytes as f64 * 100.0
+    }
+
+    /// How many of `wanted` files fit while keeping the floor.
+    #[must_use]
+    pub fn files_allowed(&self, wanted: usize, file_size: u64) -> usize {
+        (0..=wanted)
+            .rev()
+            .find(|&files| self.projected_free_pct(files, file_size) >= self.floor_pct)
+            .unwrap_or(0)
+    }
+}
+
+/// What the daemon should do for one mount that is ready to replenish.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplenishPlan {
+    /// Files to create now; 0 means deferred.
+    pub files: usize,
+    /// Projected free percentage at the horizon after `files` (or after one
+    /// file, when deferred).
+    pub projected_free_pct: f64,
+    /// True for the first tick of a deferral, which should be logged.
+    pub report_deferral: bool,
 }
 
 /// Tracks release/replenishment state across monitoring loop iterations.
@@ -218,6 +276,40 @@ impl BallastReleaseController {
     pub fn on_replenished(&mut self, mount_path: &Path, _count: usize) {
         let state = self.states.entry(mount_path.to_path_buf()).or_default();
         state.last_replenish_time = Some(Instant::now());
+        state.deferral_reported = false;
+    }
+
+    /// Size a replenishment for a mount that passed
+    /// [`Self::is_ready_for_replenish`]. Without a forecast (no rate yet, or
+    /// forecasting disabled) the plan is one file, or all `missing` files
+    /// when not `incremental`.
+    pub fn plan_replenish(
+        &mut self,
+        mount_path: &Path,
+        forecast: Option<&ReplenishForecast>,
+        missing: usize,
+        file_size: u64,
+        incremental: bool,
+    ) -> ReplenishPlan {
+        let wanted = if incremental { missing.min(1) } else { missing };
+        let Some(forecast) = forecast else {
+            return ReplenishPlan {
+                files: wanted,
+                projected_free_pct: f64::NAN,
+                report_deferral: false,
+            };
+        };
+        let files = forecast.files_allowed(wanted, file_size);
+        let state = self.states.entry(mount_path.to_path_buf()).or_default();
+        let report_deferral = files == 0 && wanted > 0 && !state.deferral_reported;
+        if report_deferral {
+            state.deferral_reported = true;
+        }
+        ReplenishPlan {
+            files,
+            projected_free_pct: forecast.projected_free_pct(files.max(1), file_size),
+            report_deferral,
+        }
     }
 
     /// Reset all state (e.g., after config reload).
@@ -242,7 +334,10 @@ mod tests {
             file_size_bytes: 4096 + 4096, // tiny files for tests
             replenish_cooldown_minutes: 0,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides: std::collections::BTreeMap::new(),
+            pools: Vec::new(),
         }
     }
 
@@ -259,6 +354,52 @@ mod tests {
         }
     }
 
+    /// Forecast for a 100 kB mount with a 14% floor over one hour.
+    fn forecast(free_bytes: u64, fill_rate_bps: f64) -> ReplenishForecast {
+        ReplenishForecast {
+            free_bytes,
+            total_bytes: 100_000,
+            fill_rate_bps,
+            horizon: Duration::from_secs(3_600),
+            floor_pct: 14.0,
+        }
+    }
+
+    #[test]
+    fn forecast_limits_files_to_what_keeps_the_floor() {
+        // 40% free, static: three 8% files leave 16%, a fourth would leave 8%.
+        assert_eq!(forecast(40_000, 0.0).files_allowed(5, 8_000), 3);
+        // Filling at 2 bytes/s eats 7.2% over the hour: only two files fit.
+        assert_eq!(forecast(40_000, 2.0).files_allowed(5, 8_000), 2);
+        // A recovering mount is not credited with future space.
+        assert_eq!(forecast(20_000, -50.0).files_allowed(5, 8_000), 0);
+    }
+
+    #[test]
+    fn deferral_is_reported_once_until_the_next_replenish() {
+        let mut ctrl = BallastReleaseController::new(0);
+        let mount = Path::new("/test");
+        let tight = forecast(20_000, 0.0);
+
+        let plan = ctrl.plan_replenish(mount, Some(&tight), 3, 8_000, true);
+        assert_eq!((plan.files, plan.report_deferral), (0, true));
+        assert!((plan.projected_free_pct - 12.0).abs() < 1e-9);
+        let plan = ctrl.plan_replenish(mount, Some(&tight), 3, 8_000, true);
+        assert_eq!((plan.files, plan.report_deferral), (0, false));
+
+        let roomy = forecast(60_000, 0.0);
+        let plan = ctrl.plan_replenish(mount, Some(&roomy), 3, 8_000, true);
+        assert_eq!(plan.files, 1);
+        let plan = ctrl.plan_replenish(mount, Some(&roomy), 3, 8_000, false);
+        assert_eq!(plan.files, 3);
+        ctrl.on_replenished(mount, 3);
+        let plan = ctrl.plan_replenish(mount, Some(&tight), 3, 8_000, true);
+        assert!(plan.report_deferral);
+
+        // No forecast: plain one-at-a-time behavior.
+        assert_eq!(ctrl.plan_replenish(mount, None, 3, 8_000, true).files, 1);
+    }
+
     fn one_hour_ago() -> Instant {
         Instant::now()
             .checked_sub(Duration::from_secs(3_600))
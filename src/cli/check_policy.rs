@@ -0,0 +1,183 @@
+//! Per-project space requirements for `sbh check`.
+//!
+//! A repository can ship a `.sbh-check.toml` at its root declaring what a
+//! build there needs:
+//!
+//! ```toml
+//! need = "30GB"          # free bytes required; an integer is taken as bytes
+//! predict_minutes = 45   # space must last this long at the current fill rate
+//! target_free_pct = 15   # free percentage floor
+//! ```
+//!
+//! `sbh check` run anywhere inside the repository, with no flags, enforces
+//! those values. Flags given on the command line take precedence.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::core::errors::{Result, SbhError};
+
+/// File name looked up from the checked path towards the root.
+pub const CHECK_POLICY_FILE: &str = ".sbh-check.toml";
+
+/// Requirements declared by a project.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckPolicy {
+    /// Free bytes required on the mount.
+    #[serde(default, deserialize_with = "deserialize_byte_size")]
+    pub need: Option<u64>,
+    /// Minutes the free space must last at the daemon's fill-rate estimate.
+    #[serde(default)]
+    pub predict_minutes: Option<u64>,
+    /// Minimum free percentage.
+    #[serde(default)]
+    pub target_free_pct: Option<f64>,
+}
+
+impl CheckPolicy {
+    /// Parse and validate a policy file's contents.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let policy: Self = toml::from_str(raw)?;
+        if let Some(pct) = policy.target_free_pct
+            && !(pct > 0.0 && pct <= 100.0)
+        {
+            return Err(SbhError::InvalidConfig {
+                details: format!("target_free_pct ({pct}) must be in (0, 100]"),
+            });
+        }
+        Ok(policy)
+    }
+}
+
+/// Find the nearest `.sbh-check.toml` at or above `start` and parse it.
+/// Returns the file's path with the policy, or `None` if there is none.
+pub fn find_check_policy(start: &Path) -> Result<Option<(PathBuf, CheckPolicy)>> {
+    let dir = if start.is_dir() {
+        start
+    } else {
+        start.parent().unwrap_or(start)
+    };
+    for ancestor in dir.ancestors() {
+        let candidate = ancestor.join(CHECK_POLICY_FILE);
+        let raw = match fs::read_to_string(&candidate) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(SbhError::io(&candidate, e)),
+        };
+        let policy = CheckPolicy::parse(&raw).map_err(|e| SbhError::InvalidConfig {
+            details: format!("{}: {e}", candidate.display()),
+        })?;
+        return Ok(Some((candidate, policy)));
+    }
+    Ok(None)
+}
+
+/// Parse a size such as `30GB`, `512MiB`, `2G`, or `1000`.
+///
+/// `KB`, `MB`, `GB`, and `TB` are decimal. `KiB`, `MiB`, `GiB`, `TiB` and the
+/// one-letter `K`, `M`, `G`, `T` are binary, as in `du -h`. Case is ignored.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn parse_byte_size(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        _ => return None,
+    };
+    let bytes = (number * multiplier as f64).round();
+    (bytes.is_finite() && bytes <= u64::MAX as f64).then_some(bytes as u64)
+}
+
+fn deserialize_byte_size<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Size {
+        Bytes(u64),
+        Text(String),
+    }
+
+    match Size::deserialize(deserializer)? {
+        Size::Bytes(bytes) => Ok(Some(bytes)),
+        Size::Text(text) => parse_byte_size(&text).map(Some).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "invalid size {text:?} (try \"30GB\" or \"512MiB\")"
+            ))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_parse_with_decimal_and_binary_units() {
+        assert_eq!(parse_byte_size("30GB"), Some(30_000_000_000));
+        assert_eq!(parse_byte_size("30 gb"), Some(30_000_000_000));
+        assert_eq!(parse_byte_size("512MiB"), Some(512 << 20));
+        assert_eq!(parse_byte_size("2G"), Some(2 << 30));
+        assert_eq!(parse_byte_size("1.5TB"), Some(1_500_000_000_000));
+        assert_eq!(parse_byte_size("4096"), Some(4096));
+        assert_eq!(parse_byte_size("lots"), None);
+        assert_eq!(parse_byte_size("30XB"), None);
+        assert_eq!(parse_byte_size(""), None);
+    }
+
+    #[test]
+    fn policy_is_found_from_a_nested_path() {
+        let repo = tempfile::tempdir().unwrap();
+        fs::write(
+            repo.path().join(CHECK_POLICY_FILE),
+            "need = \"30GB\"\npredict_minutes = 45\n",
+        )
+        .unwrap();
+        let nested = repo.path().join("crates/core/src");
+        fs::create_dir_all(&nested).unwrap();
+
+        let (path, policy) = find_check_policy(&nested).unwrap().unwrap();
+        assert_eq!(path, repo.path().join(CHECK_POLICY_FILE));
+        assert_eq!(policy.need, Some(30_000_000_000));
+        assert_eq!(policy.predict_minutes, Some(45));
+        assert_eq!(policy.target_free_pct, None);
+    }
+
+    #[test]
+    fn bad_policies_are_rejected_with_the_file_named() {
+        assert_eq!(
+            CheckPolicy::parse("need = 1024\n").unwrap().need,
+            Some(1024)
+        );
+        assert!(CheckPolicy::parse("need = \"lots\"\n").is_err());
+        assert!(CheckPolicy::parse("target_free_pct = 0\n").is_err());
+        assert!(CheckPolicy::parse("needs = \"1GB\"\n").is_err());
+
+        let repo = tempfile::tempdir().unwrap();
+        fs::write(repo.path().join(CHECK_POLICY_FILE), "need = true\n").unwrap();
+        let err = find_check_policy(repo.path()).unwrap_err().to_string();
+        assert!(err.contains(CHECK_POLICY_FILE), "{err}");
+    }
+}
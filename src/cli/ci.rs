@@ -0,0 +1,476 @@
+//! CI runner integration: job summaries and low-space annotations.
+//!
+//! `sbh ci begin` records the job mount's free space in a baseline file at
+//! job start; `sbh ci report` measures again at job end and renders a
+//! markdown summary (disk before/after, what sbh cleaned, and a forecast for
+//! the next run) plus workflow annotations. On GitHub Actions the summary is
+//! appended to `$GITHUB_STEP_SUMMARY` and annotations use the `::warning::`
+//! command syntax; elsewhere both go to stdout.
+//!
+//! `sbh runner-hook` uses the same provider detection to find the job's
+//! workspace when a runner lifecycle hook calls it without a path.
+
+#![allow(missing_docs)]
+#![allow(clippy::cast_precision_loss)]
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Baseline file name, written under the runner's temp directory.
+pub const BASELINE_FILE: &str = "sbh-ci-baseline.json";
+
+/// Cleaned items listed in the summary; the rest are folded into a count.
+const SUMMARY_MAX_ITEMS: usize = 15;
+
+/// CI system the command is running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    GitHubActions,
+    GitLab,
+    Buildkite,
+    Jenkins,
+    Unknown,
+}
+
+impl CiProvider {
+    /// Detect the provider from environment variables via `lookup`.
+    pub fn detect(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        if lookup("GITHUB_ACTIONS").as_deref() == Some("true") {
+            Self::GitHubActions
+        } else if lookup("GITLAB_CI").as_deref() == Some("true") {
+            Self::GitLab
+        } else if lookup("BUILDKITE").as_deref() == Some("true") {
+            Self::Buildkite
+        } else if lookup("JENKINS_URL").is_some() {
+            Self::Jenkins
+        } else {
+            Self::Unknown
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::GitHubActions => "github_actions",
+            Self::GitLab => "gitlab",
+            Self::Buildkite => "buildkite",
+            Self::Jenkins => "jenkins",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Where the job-start baseline lives: the runner's per-job temp directory
+/// when it has one, so concurrent jobs on one host don't collide.
+pub fn baseline_path(lookup: impl Fn(&str) -> Option<String>) -> PathBuf {
+    lookup("RUNNER_TEMP")
+        .or_else(|| lookup("CI_BUILDS_DIR"))
+        .map_or_else(std::env::temp_dir, PathBuf::from)
+        .join(BASELINE_FILE)
+}
+
+/// The job's checkout directory as the runner exports it, if any.
+pub fn workspace_dir(lookup: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    [
+        "GITHUB_WORKSPACE",
+        "CI_PROJECT_DIR",
+        "BUILDKITE_BUILD_CHECKOUT_PATH",
+        "WORKSPACE",
+    ]
+    .into_iter()
+    .find_map(|key| lookup(key).filter(|value| !value.is_empty()))
+    .map(PathBuf::from)
+}
+
+/// Disk state at job start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CiBaseline {
+    /// RFC 3339 timestamp; deletions logged after it count as this job's.
+    pub recorded_at: String,
+    pub mount_point: PathBuf,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+}
+
+/// One artifact sbh removed during the job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CleanedItem {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Disk state at job end, compared against the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CiReport {
+    pub mount_point: PathBuf,
+    pub total_bytes: u64,
+    /// `None` when `sbh ci begin` did not run for this job.
+    pub before_free_bytes: Option<u64>,
+    pub after_free_bytes: u64,
+    pub cleaned: Vec<CleanedItem>,
+    /// Free-space percentage below which the report warns.
+    pub warn_free_pct: f64,
+    /// Free-space percentage below which the report errors.
+    pub critical_free_pct: f64,
+}
+
+/// Workflow annotation severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationLevel {
+    Warning,
+    Error,
+}
+
+/// A low-space finding surfaced on the job page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// GitHub Actions workflow command (`::warning title=...::message`).
+    #[must_use]
+    pub fn to_workflow_command(&self) -> String {
+        let level = match self.level {
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Error => "error",
+        };
+        format!(
+            "::{level} title={}::{}",
+            escape_property(&self.title),
+            escape_data(&self.message)
+        )
+    }
+}
+
+impl CiReport {
+    #[must_use]
+    pub fn cleaned_bytes(&self) -> u64 {
+        self.cleaned.iter().map(|item| item.size_bytes).sum()
+    }
+
+    #[must_use]
+    pub fn free_pct(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.after_free_bytes as f64 * 100.0 / self.total_bytes as f64
+    }
+
+    /// Bytes the job itself wrote: the free-space drop plus whatever sbh
+    /// reclaimed along the way. `None` without a baseline.
+    #[must_use]
+    pub fn consumed_bytes(&self) -> Option<u64> {
+        self.before_free_bytes.map(|before| {
+            before
+                .saturating_add(self.cleaned_bytes())
+                .saturating_sub(self.after_free_bytes)
+        })
+    }
+
+    /// Free space left after another run that consumes as much as this one.
+    #[must_use]
+    pub fn predicted_next_free_bytes(&self) -> Option<u64> {
+        self.consumed_bytes()
+            .map(|consumed| self.after_free_bytes.saturating_sub(consumed))
+    }
+
+    /// Low-space findings: current free space against the thresholds, and
+    /// a forecast that the next run drops below the warning threshold.
+    #[must_use]
+    pub fn annotations(&self) -> Vec<Annotation> {
+        let mut annotations = Vec::new();
+        let free_pct = self.free_pct();
+        let mount = self.mount_point.display();
+        if free_pct < self.critical_free_pct {
+            annotations.push(Annotation {
+                level: AnnotationLevel::Error,
+                title: "Disk critically low".to_string(),
+                message: format!(
+                    "{mount} has {} free ({free_pct:.1}%), below {:.1}%",
+                    human_bytes(self.after_free_bytes),
+                    self.critical_free_pct
+                ),
+            });
+        } else if free_pct < self.warn_free_pct {
+            annotations.push(Annotation {
+                level: AnnotationLevel::Warning,
+                title: "Disk space low".to_string(),
+                message: format!(
+                    "{mount} has {} free ({free_pct:.1}%), below {:.1}%",
+                    human_bytes(self.after_free_bytes),
+                    self.warn_free_pct
+                ),
+            });
+        } else if let Some(next) = self.predicted_next_free_bytes() {
+            let next_pct = if self.total_bytes == 0 {
+                0.0
+            } else {
+                next as f64 * 100.0 / self.total_bytes as f64
+            };
+            if next_pct < self.warn_free_pct {
+                annotations.push(Annotation {
+                    level: AnnotationLevel::Warning,
+                    title: "Next run may run out of disk".to_string(),
+                    message: format!(
+                        "this job consumed {}; another like it leaves {} free ({next_pct:.1}%) on {mount}",
+                        human_bytes(self.consumed_bytes().unwrap_or(0)),
+                        human_bytes(next)
+                    ),
+                });
+            }
+        }
+        annotations
+    }
+
+    /// Job summary markdown.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("## sbh disk report\n\n");
+        let _ = writeln!(out, "| | Free | |");
+        let _ = writeln!(out, "| --- | ---: | --- |");
+        match self.before_free_bytes {
+            Some(before) => {
+                let _ = writeln!(out, "| Job start | {} | |", human_bytes(before));
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "| Job start | n/a | run `sbh ci begin` first to compare |"
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "| Job end | {} | {:.1}% of {} on `{}` |",
+            human_bytes(self.after_free_bytes),
+            self.free_pct(),
+            human_bytes(self.total_bytes),
+            self.mount_point.display()
+        );
+        if let Some(next) = self.predicted_next_free_bytes() {
+            let _ = writeln!(
+                out,
+                "| Next run (forecast) | {} | job consumed {} |",
+                human_bytes(next),
+                human_bytes(self.consumed_bytes().unwrap_or(0))
+            );
+        }
+
+        out.push('\n');
+        if self.cleaned.is_empty() {
+            out.push_str("sbh cleaned nothing during this job.\n");
+        } else {
+            let _ = writeln!(
+                out,
+                "### Cleaned: {} items, {}\n",
+                self.cleaned.len(),
+                human_bytes(self.cleaned_bytes())
+            );
+            let _ = writeln!(out, "| Size | Path |");
+            let _ = writeln!(out, "| ---: | --- |");
+            let mut largest: Vec<&CleanedItem> = self.cleaned.iter().collect();
+            largest.sort_by_key(|item| std::cmp::Reverse(item.size_bytes));
+            for item in largest.iter().take(SUMMARY_MAX_ITEMS) {
+                let _ = writeln!(
+                    out,
+                    "| {} | `{}` |",
+                    human_bytes(item.size_bytes),
+                    item.path
+                );
+            }
+            if largest.len() > SUMMARY_MAX_ITEMS {
+                let _ = writeln!(out, "\n…and {} more.", largest.len() - SUMMARY_MAX_ITEMS);
+            }
+        }
+
+        let annotations = self.annotations();
+        if !annotations.is_empty() {
+            out.push('\n');
+            for annotation in &annotations {
+                let _ = writeln!(out, "> **{}:** {}", annotation.title, annotation.message);
+            }
+        }
+        out
+    }
+}
+
+/// Step outputs in the `name=value` line form GitHub Actions reads from
+/// `$GITHUB_OUTPUT`. Line breaks in a value are replaced by spaces, since
+/// each output must fit on its line.
+#[must_use]
+pub fn step_output_lines(outputs: &[(&str, String)]) -> String {
+    let mut out = String::new();
+    for (name, value) in outputs {
+        let _ = writeln!(out, "{name}={}", value.replace(['\r', '\n'], " "));
+    }
+    out
+}
+
+/// Read a baseline written by `sbh ci begin`; `None` when absent or unreadable.
+#[must_use]
+pub fn read_baseline(path: &Path) -> Option<CiBaseline> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Workflow-command message escaping (`%`, CR, LF).
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Workflow-command property escaping: data escaping plus `:` and `,`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+    const TIB: f64 = GIB * 1024.0;
+    let b = bytes as f64;
+    if b >= TIB {
+        format!("{:.1} TiB", b / TIB)
+    } else if b >= GIB {
+        format!("{:.1} GiB", b / GIB)
+    } else if b >= MIB {
+        format!("{:.1} MiB", b / MIB)
+    } else if b >= KIB {
+        format!("{:.1} KiB", b / KIB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1_073_741_824;
+
+    fn report(before: Option<u64>, after: u64, cleaned: Vec<CleanedItem>) -> CiReport {
+        CiReport {
+            mount_point: PathBuf::from("/home/runner"),
+            total_bytes: 100 * GIB,
+            before_free_bytes: before,
+            after_free_bytes: after,
+            cleaned,
+            warn_free_pct: 20.0,
+            critical_free_pct: 5.0,
+        }
+    }
+
+    #[test]
+    fn provider_detection_and_baseline_path_follow_runner_env() {
+        let github = |key: &str| match key {
+            "GITHUB_ACTIONS" => Some("true".to_string()),
+            "RUNNER_TEMP" => Some("/home/runner/work/_temp".to_string()),
+            _ => None,
+        };
+        assert_eq!(CiProvider::detect(github), CiProvider::GitHubActions);
+        assert_eq!(
+            baseline_path(github),
+            PathBuf::from("/home/runner/work/_temp").join(BASELINE_FILE)
+        );
+
+        let gitlab = |key: &str| (key == "GITLAB_CI").then(|| "true".to_string());
+        assert_eq!(CiProvider::detect(gitlab), CiProvider::GitLab);
+        assert_eq!(CiProvider::detect(|_| None), CiProvider::Unknown);
+
+        let jenkins = |key: &str| match key {
+            "JENKINS_URL" => Some("https://ci.example.com/".to_string()),
+            "WORKSPACE" => Some("/var/lib/jenkins/workspace/app".to_string()),
+            _ => None,
+        };
+        assert_eq!(CiProvider::detect(jenkins), CiProvider::Jenkins);
+        assert_eq!(
+            workspace_dir(jenkins),
+            Some(PathBuf::from("/var/lib/jenkins/workspace/app"))
+        );
+        assert_eq!(
+            workspace_dir(github),
+            None,
+            "RUNNER_TEMP is not a workspace"
+        );
+        assert_eq!(workspace_dir(|_| Some(String::new())), None);
+    }
+
+    #[test]
+    fn forecast_counts_what_sbh_reclaimed_as_job_consumption() {
+        // 60 GiB free at start, 4 GiB cleaned, 50 GiB free at end:
+        // the job wrote 14 GiB, so the next run ends at 36 GiB.
+        let report = report(
+            Some(60 * GIB),
+            50 * GIB,
+            vec![CleanedItem {
+                path: "/home/runner/work/app/target".to_string(),
+                size_bytes: 4 * GIB,
+            }],
+        );
+        assert_eq!(report.consumed_bytes(), Some(14 * GIB));
+        assert_eq!(report.predicted_next_free_bytes(), Some(36 * GIB));
+        assert!(report.annotations().is_empty());
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| Job start | 60.0 GiB |"));
+        assert!(markdown.contains("Next run (forecast) | 36.0 GiB"));
+        assert!(markdown.contains("`/home/runner/work/app/target`"));
+    }
+
+    #[test]
+    fn annotations_flag_low_space_and_risky_forecasts() {
+        let critical = report(None, 3 * GIB, Vec::new());
+        let annotations = critical.annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].level, AnnotationLevel::Error);
+        assert!(
+            annotations[0]
+                .to_workflow_command()
+                .starts_with("::error title=Disk critically low::/home/runner has 3.0 GiB")
+        );
+
+        let low = report(None, 15 * GIB, Vec::new());
+        assert_eq!(low.annotations()[0].level, AnnotationLevel::Warning);
+
+        // Healthy now, but another 20 GiB job leaves 10 GiB (10%).
+        let risky = report(Some(50 * GIB), 30 * GIB, Vec::new());
+        let annotations = risky.annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].title, "Next run may run out of disk");
+        assert!(
+            risky
+                .to_markdown()
+                .contains("> **Next run may run out of disk:**")
+        );
+    }
+
+    #[test]
+    fn workflow_command_escaping() {
+        let annotation = Annotation {
+            level: AnnotationLevel::Warning,
+            title: "a:b,c".to_string(),
+            message: "50%\nfree".to_string(),
+        };
+        assert_eq!(
+            annotation.to_workflow_command(),
+            "::warning title=a%3Ab%2Cc::50%25%0Afree"
+        );
+        assert_eq!(
+            step_output_lines(&[
+                ("free_pct", "12.50".to_string()),
+                ("reason", "a\nb".to_string()),
+                ("minutes_until_full", String::new()),
+            ]),
+            "free_pct=12.50\nreason=a b\nminutes_until_full=\n"
+        );
+    }
+}
@@ -0,0 +1,458 @@
+//! Two-phase clean: `sbh clean --emit-plan` writes the plan to a file for
+//! review, and `sbh clean --execute-plan` runs that file later.
+//!
+//! Each entry keeps a stat fingerprint of its path from planning time: the
+//! device and inode, whether it is a directory, its modification time, and a
+//! file's length. Before execution every entry is re-stated. One that is
+//! gone, was replaced, was modified, or has been protected since is skipped,
+//! and the rest go through the deletion executor and its usual pre-flight
+//! checks. Entries also carry the scoring inputs they were planned from, so
+//! the audit log records the same evidence as a direct clean.
+//!
+//! A plan file is refused outright if its entries no longer hash to the
+//! recorded plan hash, i.e. it was edited after planning.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+use crate::scanner::deletion::{DeletionPlan, hash_planned};
+use crate::scanner::protection::{MARKER_FILENAME, ProtectionRegistry};
+use crate::scanner::scoring::ScoringInputs;
+
+/// Plan file format version; readers refuse other versions.
+pub const PLAN_FILE_VERSION: u32 = 1;
+
+/// Depth searched below an entry for protection markers added since planning.
+const MARKER_SEARCH_DEPTH: usize = 3;
+
+/// What a path looked like when it was planned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub is_dir: bool,
+    pub dev: u64,
+    pub ino: u64,
+    pub modified_ns: u64,
+    pub len: u64,
+}
+
+impl Fingerprint {
+    /// Stat `path` without following a final symlink. A symlink has no
+    /// fingerprint: the executor never deletes one.
+    pub fn of(path: &Path) -> std::io::Result<Option<Self>> {
+        let meta = fs::symlink_metadata(path)?;
+        if meta.file_type().is_symlink() {
+            return Ok(None);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let modified_ns = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64);
+        #[cfg(unix)]
+        let (dev, ino) = {
+            use std::os::unix::fs::MetadataExt;
+            (meta.dev(), meta.ino())
+        };
+        #[cfg(not(unix))]
+        let (dev, ino) = (0, 0);
+        Ok(Some(Self {
+            is_dir: meta.is_dir(),
+            dev,
+            ino,
+            modified_ns,
+            len: meta.len(),
+        }))
+    }
+}
+
+/// Why a planned entry is no longer executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleReason {
+    /// The path no longer exists.
+    Gone,
+    /// Something else now sits at the path: another inode, a symlink, or a
+    /// file where a directory was.
+    Replaced,
+    /// Its modification time (or a file's length) changed.
+    Modified,
+    /// It, an ancestor, or something shortly below it is now protected.
+    Protected,
+}
+
+impl StaleReason {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gone => "gone",
+            Self::Replaced => "replaced",
+            Self::Modified => "modified",
+            Self::Protected => "protected",
+        }
+    }
+}
+
+/// One planned candidate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanEntry {
+    pub path: PathBuf,
+    /// Bytes expected back, including nested candidates folded into it.
+    pub size_bytes: u64,
+    /// On-disk counterpart of `size_bytes`; zero in plans written before
+    /// on-disk sizes were tracked.
+    #[serde(default)]
+    pub disk_bytes: u64,
+    pub score: f64,
+    pub category: String,
+    pub fingerprint: Fingerprint,
+    pub inputs: ScoringInputs,
+}
+
+impl PlanEntry {
+    /// Why this entry should no longer run, or `None` if it still matches
+    /// its fingerprint and is unprotected.
+    pub fn staleness(&self, protection: &mut ProtectionRegistry) -> Option<StaleReason> {
+        let now = match Fingerprint::of(&self.path) {
+            Ok(Some(now)) => now,
+            Ok(None) => return Some(StaleReason::Replaced),
+            Err(_) => return Some(StaleReason::Gone),
+        };
+        let was = self.fingerprint;
+        if (now.dev, now.ino, now.is_dir) != (was.dev, was.ino, was.is_dir) {
+            return Some(StaleReason::Replaced);
+        }
+        if now.modified_ns != was.modified_ns || (!now.is_dir && now.len != was.len) {
+            return Some(StaleReason::Modified);
+        }
+        for dir in self.path.ancestors() {
+            if dir.join(MARKER_FILENAME).exists() {
+                protection.register_marker(dir);
+            }
+        }
+        let markers_below = if now.is_dir {
+            protection
+                .discover_markers(&self.path, MARKER_SEARCH_DEPTH)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        (markers_below > 0 || protection.is_protected(&self.path)).then_some(StaleReason::Protected)
+    }
+}
+
+/// A plan written by `--emit-plan`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanFile {
+    pub version: u32,
+    /// RFC 3339 time the plan was made.
+    pub created_at: String,
+    /// [`DeletionPlan::plan_hash`] of the entries, as `--plan-hash` prints it.
+    pub plan_hash: String,
+    pub total_bytes: u64,
+    pub entries: Vec<PlanEntry>,
+}
+
+impl PlanFile {
+    /// Record `plan`. Candidates that vanished while planning are left out.
+    #[must_use]
+    pub fn from_plan(plan: &DeletionPlan) -> Self {
+        let mut recorded = DeletionPlan {
+            candidates: Vec::new(),
+            total_reclaimable_bytes: 0,
+            total_reclaimable_disk_bytes: 0,
+            estimated_items: 0,
+            subsumed: Vec::new(),
+            deferred: 0,
+        };
+        let mut entries = Vec::new();
+        for candidate in &plan.candidates {
+            let Ok(Some(fingerprint)) = Fingerprint::of(&candidate.path) else {
+                continue;
+            };
+            entries.push(PlanEntry {
+                path: candidate.path.clone(),
+                size_bytes: candidate.size_bytes,
+                disk_bytes: candidate.disk_bytes,
+                score: candidate.total_score,
+                category: candidate.inputs.category.clone(),
+                fingerprint,
+                inputs: candidate.inputs.clone(),
+            });
+            recorded.candidates.push(candidate.clone());
+        }
+        Self {
+            version: PLAN_FILE_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            plan_hash: recorded.plan_hash(),
+            total_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+            entries,
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| SbhError::Serialization {
+            context: "clean plan",
+            details: e.to_string(),
+        })?;
+        fs::write(path, json + "\n").map_err(|e| SbhError::io(path, e))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).map_err(|e| SbhError::io(path, e))?;
+        let plan: Self = serde_json::from_str(&raw).map_err(|e| SbhError::Serialization {
+            context: "clean plan",
+            details: format!("{}: {e}", path.display()),
+        })?;
+        if plan.version != PLAN_FILE_VERSION {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "{}: plan file version {} is not supported (expected {PLAN_FILE_VERSION})",
+                    path.display(),
+                    plan.version
+                ),
+            });
+        }
+        let hash = plan.entries_hash();
+        if !hash.eq_ignore_ascii_case(&plan.plan_hash) {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "{}: entries hash to {hash}, not the recorded plan hash {}; \
+                     the file was edited after planning",
+                    path.display(),
+                    plan.plan_hash
+                ),
+            });
+        }
+        Ok(plan)
+    }
+
+    /// [`DeletionPlan::plan_hash`] recomputed from the entries.
+    #[must_use]
+    pub fn entries_hash(&self) -> String {
+        hash_planned(
+            self.entries
+                .iter()
+                .map(|entry| (entry.path.as_path(), entry.size_bytes)),
+        )
+    }
+
+    /// The first entry that is not verifiably under any of the canonical
+    /// `roots`.
+    ///
+    /// The hash only catches careless edits, since anyone who can edit the
+    /// file can recompute it, so this is what keeps a plan inside the roots:
+    /// below the root an entry may only name plain components, and its
+    /// parent must canonicalize to itself, i.e. no directory on the way is a
+    /// symlink. The executor refuses a symlink as the last component.
+    #[must_use]
+    pub fn entry_outside(&self, roots: &[PathBuf]) -> Option<&PlanEntry> {
+        self.entries
+            .iter()
+            .find(|entry| !roots.iter().any(|root| lies_under(&entry.path, root)))
+    }
+
+    /// Split the entries into those still safe to run and those that
+    /// changed since planning.
+    #[must_use]
+    pub fn check(
+        &self,
+        protection: &mut ProtectionRegistry,
+    ) -> (Vec<&PlanEntry>, Vec<(&PlanEntry, StaleReason)>) {
+        let mut current = Vec::new();
+        let mut stale = Vec::new();
+        for entry in &self.entries {
+            match entry.staleness(protection) {
+                Some(reason) => stale.push((entry, reason)),
+                None => current.push(entry),
+            }
+        }
+        (current, stale)
+    }
+}
+
+/// Whether `path` names something strictly below `root` without `..`, `.`,
+/// or a symlinked parent directory.
+fn lies_under(path: &Path, root: &Path) -> bool {
+    let Ok(rest) = path.strip_prefix(root) else {
+        return false;
+    };
+    if rest.as_os_str().is_empty()
+        || !rest
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+    path.parent()
+        .and_then(|parent| Some(parent.canonicalize().ok()? == parent))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &Path) -> PlanEntry {
+        PlanEntry {
+            path: path.to_path_buf(),
+            size_bytes: 4,
+            disk_bytes: 4096,
+            score: 0.9,
+            category: "rust_target".to_string(),
+            fingerprint: Fingerprint::of(path).unwrap().unwrap(),
+            inputs: ScoringInputs::default(),
+        }
+    }
+
+    #[test]
+    fn changed_entries_are_stale_and_the_rest_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for name in [
+            "same",
+            "gone",
+            "touched",
+            "replaced",
+            "proj/target",
+            "nested/keep",
+        ] {
+            fs::create_dir_all(root.join(name)).unwrap();
+        }
+        fs::write(root.join("grown.log"), "four").unwrap();
+        let names = [
+            "same",
+            "gone",
+            "touched",
+            "replaced",
+            "proj/target",
+            "nested",
+            "grown.log",
+        ];
+        let plan = PlanFile {
+            version: PLAN_FILE_VERSION,
+            created_at: String::new(),
+            plan_hash: String::new(),
+            total_bytes: 0,
+            entries: names.iter().map(|name| entry(&root.join(name))).collect(),
+        };
+
+        fs::remove_dir(root.join("gone")).unwrap();
+        fs::write(root.join("touched/new.o"), "").unwrap();
+        fs::remove_dir(root.join("replaced")).unwrap();
+        fs::write(root.join("replaced"), "").unwrap();
+        fs::write(root.join("proj").join(MARKER_FILENAME), "").unwrap();
+        fs::write(root.join("nested/keep").join(MARKER_FILENAME), "").unwrap();
+        fs::write(root.join("grown.log"), "four more").unwrap();
+
+        let mut protection = ProtectionRegistry::marker_only();
+        let (current, stale) = plan.check(&mut protection);
+        assert_eq!(current.len(), 1);
+        assert!(current[0].path.ends_with("same"));
+        let reasons: Vec<_> = stale
+            .iter()
+            .map(|(entry, reason)| (entry.path.strip_prefix(root).unwrap(), *reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (Path::new("gone"), StaleReason::Gone),
+                (Path::new("touched"), StaleReason::Modified),
+                (Path::new("replaced"), StaleReason::Replaced),
+                (Path::new("proj/target"), StaleReason::Protected),
+                (Path::new("nested"), StaleReason::Protected),
+                (Path::new("grown.log"), StaleReason::Modified),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_files_round_trip_and_reject_other_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        let mut plan = PlanFile {
+            version: PLAN_FILE_VERSION,
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            plan_hash: String::new(),
+            total_bytes: 4,
+            entries: vec![entry(&dir.path().join("target"))],
+        };
+        plan.plan_hash = plan.entries_hash();
+        let file = dir.path().join("plan.json");
+        plan.write(&file).unwrap();
+        assert_eq!(PlanFile::read(&file).unwrap(), plan);
+
+        let raw = fs::read_to_string(&file).unwrap();
+        fs::write(&file, raw.replace("\"version\": 1", "\"version\": 99")).unwrap();
+        assert!(PlanFile::read(&file).is_err());
+    }
+
+    #[test]
+    fn edited_plans_and_entries_outside_the_roots_are_caught() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("work/target")).unwrap();
+        fs::create_dir(dir.path().join("home")).unwrap();
+        let mut plan = PlanFile {
+            version: PLAN_FILE_VERSION,
+            created_at: String::new(),
+            plan_hash: String::new(),
+            total_bytes: 4,
+            entries: vec![entry(&dir.path().join("work/target"))],
+        };
+        plan.plan_hash = plan.entries_hash();
+        assert_eq!(plan.entry_outside(&[dir.path().join("work")]), None);
+
+        plan.entries.push(entry(&dir.path().join("home")));
+        assert_eq!(
+            plan.entry_outside(&[dir.path().join("work")]),
+            Some(&plan.entries[1])
+        );
+        let file = dir.path().join("plan.json");
+        plan.write(&file).unwrap();
+        assert!(PlanFile::read(&file).is_err());
+    }
+
+    #[test]
+    fn entries_climbing_out_of_the_root_are_outside() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("work/target")).unwrap();
+        fs::create_dir(dir.path().join("home")).unwrap();
+        let plan = PlanFile {
+            version: PLAN_FILE_VERSION,
+            created_at: String::new(),
+            plan_hash: String::new(),
+            total_bytes: 4,
+            entries: vec![entry(&dir.path().join("work/target/../../home"))],
+        };
+        assert!(plan.entries[0].path.starts_with(dir.path().join("work")));
+        assert_eq!(
+            plan.entry_outside(&[dir.path().join("work")]),
+            Some(&plan.entries[0])
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn entries_behind_a_symlinked_parent_are_outside() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("work")).unwrap();
+        fs::create_dir_all(dir.path().join("home/cache")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("home"), dir.path().join("work/link")).unwrap();
+        let plan = PlanFile {
+            version: PLAN_FILE_VERSION,
+            created_at: String::new(),
+            plan_hash: String::new(),
+            total_bytes: 4,
+            entries: vec![entry(&dir.path().join("work/link/cache"))],
+        };
+        assert_eq!(
+            plan.entry_outside(&[dir.path().join("work")]),
+            Some(&plan.entries[0])
+        );
+    }
+}
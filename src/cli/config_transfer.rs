@@ -0,0 +1,295 @@
+//! Moving a tuned config between hosts: `sbh config export` / `import`.
+//!
+//! An export is the config file as written, optionally with the keys that
+//! describe this machine's own storage layout stripped (`--portable`). An
+//! import adopts another host's settings but always keeps the local values
+//! of those host-specific keys, so a build host's pools, sockets, and state
+//! paths never leak onto the rest of the fleet.
+//!
+//! The import preview is a three-way diff against the built-in defaults:
+//! a key changed only on the other host is plainly incoming, one changed
+//! only here is local tuning the import would drop, and one changed on both
+//! sides to different values is a conflict worth a look.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::config::Config;
+use crate::core::errors::{Result, SbhError};
+
+/// Keys, as dotted paths, that describe one machine rather than a policy:
+/// state and log locations, the control socket, ballast pools and per-mount
+/// entries, per-device limits, and the archive target.
+pub const HOST_SPECIFIC_KEYS: &[&str] = &[
+    "paths",
+    "control.socket_path",
+    "update.metadata_cache_file",
+    "ballast.pools",
+    "ballast.overrides",
+    "pressure.mounts",
+    "scanner.device_parallelism",
+    "archive.path",
+];
+
+/// Whether `key` is, or sits below, one of [`HOST_SPECIFIC_KEYS`].
+#[must_use]
+pub fn is_host_specific(key: &str) -> bool {
+    HOST_SPECIFIC_KEYS.iter().any(|host_key| {
+        key == *host_key
+            || key
+                .strip_prefix(host_key)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// Remove host-specific keys from a config file's table. Returns the dotted
+/// keys that were present and removed.
+pub fn strip_host_specific(table: &mut toml::Table) -> Vec<String> {
+    let mut stripped = Vec::new();
+    for key in HOST_SPECIFIC_KEYS {
+        let (parent, leaf) = match key.rsplit_once('.') {
+            Some((section, leaf)) => (
+                table.get_mut(section).and_then(toml::Value::as_table_mut),
+                leaf,
+            ),
+            None => (Some(&mut *table), *key),
+        };
+        if parent.and_then(|parent| parent.remove(leaf)).is_some() {
+            stripped.push((*key).to_string());
+        }
+    }
+    stripped
+}
+
+/// The table to write when importing `incoming` on a host whose config file
+/// is `local`: everything from `incoming` except host-specific keys, which
+/// keep their local values (or stay unset).
+#[must_use]
+pub fn merge_import(mut incoming: toml::Table, local: &toml::Table) -> toml::Table {
+    strip_host_specific(&mut incoming);
+    for key in HOST_SPECIFIC_KEYS {
+        let Some((section, leaf)) = key.rsplit_once('.') else {
+            if let Some(value) = local.get(*key) {
+                incoming.insert((*key).to_string(), value.clone());
+            }
+            continue;
+        };
+        let Some(value) = local
+            .get(section)
+            .and_then(toml::Value::as_table)
+            .and_then(|table| table.get(leaf))
+        else {
+            continue;
+        };
+        if let Some(target) = incoming
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+        {
+            target.insert(leaf.to_string(), value.clone());
+        }
+    }
+    incoming
+}
+
+/// How a key differs between this host and an imported config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChangeKind {
+    /// Only the imported config moved it off the default; importing adopts it.
+    Incoming,
+    /// Only this host moved it off the default; importing resets it.
+    Local,
+    /// Both moved it, to different values; importing takes theirs.
+    Conflict,
+}
+
+impl ConfigChangeKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Incoming => "incoming",
+            Self::Local => "local",
+            Self::Conflict => "conflict",
+        }
+    }
+}
+
+/// One key whose value an import would change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigChange {
+    pub key: String,
+    pub kind: ConfigChangeKind,
+    pub default: Value,
+    pub local: Value,
+    pub incoming: Value,
+}
+
+/// Keys where `local` and `incoming` disagree, classified against
+/// `defaults`, in key order. Host-specific keys are left out: an import
+/// never changes them.
+pub fn three_way_diff(
+    defaults: &Config,
+    local: &Config,
+    incoming: &Config,
+) -> Result<Vec<ConfigChange>> {
+    let to_json = |config: &Config| {
+        serde_json::to_value(config).map_err(|e| SbhError::Serialization {
+            context: "config diff",
+            details: e.to_string(),
+        })
+    };
+    let mut base = BTreeMap::new();
+    let mut ours = BTreeMap::new();
+    let mut theirs = BTreeMap::new();
+    flatten("", &to_json(defaults)?, &mut base);
+    flatten("", &to_json(local)?, &mut ours);
+    flatten("", &to_json(incoming)?, &mut theirs);
+
+    let mut keys: Vec<&String> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    let mut changes = Vec::new();
+    for key in keys {
+        if is_host_specific(key) {
+            continue;
+        }
+        let value = |map: &BTreeMap<String, Value>| map.get(key).cloned().unwrap_or(Value::Null);
+        let (default, local, incoming) = (value(&base), value(&ours), value(&theirs));
+        if local == incoming {
+            continue;
+        }
+        let kind = if local == default {
+            ConfigChangeKind::Incoming
+        } else if incoming == default {
+            ConfigChangeKind::Local
+        } else {
+            ConfigChangeKind::Conflict
+        };
+        changes.push(ConfigChange {
+            key: key.clone(),
+            kind,
+            default,
+            local,
+            incoming,
+        });
+    }
+    Ok(changes)
+}
+
+/// Objects become dotted keys; arrays and scalars are compared whole.
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, child, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(raw: &str) -> toml::Table {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn portable_export_drops_host_paths_and_import_keeps_local_ones() {
+        let mut exported = table(
+            r#"
+[paths]
+state_file = "/srv/a/state.json"
+
+[pressure]
+green_min_free_pct = 25.0
+
+[[pressure.mounts]]
+path = "/scratch"
+ballast = false
+
+[control]
+socket_path = "/run/a.sock"
+enabled = true
+"#,
+        );
+        let stripped = strip_host_specific(&mut exported);
+        assert_eq!(
+            stripped,
+            ["paths", "control.socket_path", "pressure.mounts"]
+        );
+        assert!(exported["pressure"].get("mounts").is_none());
+        assert_eq!(exported["control"]["enabled"].as_bool(), Some(true));
+
+        let local = table(
+            r#"
+[paths]
+state_file = "/srv/b/state.json"
+
+[control]
+socket_path = "/run/b.sock"
+"#,
+        );
+        // Even a non-portable export cannot carry its host's paths over.
+        let mut foreign = exported.clone();
+        foreign.insert("paths".to_string(), table("state_file = \"/srv/a\"").into());
+        let merged = merge_import(foreign, &local);
+        assert_eq!(
+            merged["paths"]["state_file"].as_str(),
+            Some("/srv/b/state.json")
+        );
+        assert_eq!(
+            merged["control"]["socket_path"].as_str(),
+            Some("/run/b.sock")
+        );
+        assert_eq!(
+            merged["pressure"]["green_min_free_pct"].as_float(),
+            Some(25.0)
+        );
+    }
+
+    #[test]
+    fn three_way_diff_separates_incoming_local_and_conflicting_keys() {
+        let defaults = Config::default();
+        let mut local = Config::default();
+        local.scoring.min_score = 0.6;
+        local.pressure.poll_interval_ms = 2_000;
+        local.paths.state_file = "/srv/b/state.json".into();
+        let mut incoming = Config::default();
+        incoming.scoring.min_score = 0.8;
+        incoming.ballast.file_count = 20;
+        incoming.paths.state_file = "/srv/a/state.json".into();
+
+        let changes = three_way_diff(&defaults, &local, &incoming).unwrap();
+        let kinds: Vec<_> = changes
+            .iter()
+            .map(|change| (change.key.as_str(), change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("ballast.file_count", ConfigChangeKind::Incoming),
+                ("pressure.poll_interval_ms", ConfigChangeKind::Local),
+                ("scoring.min_score", ConfigChangeKind::Conflict),
+            ]
+        );
+        assert!(is_host_specific("paths.state_file"));
+        assert!(!is_host_specific("pressure.mounts_extra"));
+    }
+}
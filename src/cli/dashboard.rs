@@ -24,7 +24,7 @@ use crossterm::style::{Attribute, Color, SetAttribute, SetForegroundColor};
 use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{execute, queue};
 
-use crate::daemon::self_monitor::{DaemonState, SelfMonitor};
+use crate::core::state::{DaemonState, read_state};
 use crate::monitor::fs_stats::FsStatsCollector;
 use crate::platform::pal::detect_platform;
 
@@ -41,7 +41,7 @@ fn level_color(level: &str) -> Color {
         "green" => Color::Green,
         "yellow" => Color::Yellow,
         "orange" => Color::DarkYellow,
-        "red" | "critical" => Color::Red,
+        "red" | "critical" | "readonly" => Color::Red,
         _ => Color::White,
     }
 }
@@ -54,6 +54,7 @@ fn level_label(level: &str) -> &str {
         "orange" => "ORANGE",
         "red" => "RED",
         "critical" => "CRITICAL",
+        "readonly" => "READONLY",
         _ => level,
     }
 }
@@ -267,7 +268,9 @@ fn run_inner(stdout: &mut io::Stdout, config: &DashboardConfig) -> io::Result<()
         let width = cols as usize;
 
         // Try reading daemon state.
-        let state = SelfMonitor::read_state(&config.state_file).ok();
+        let state = read_state(&config.state_file)
+            .ok()
+            .map(|snapshot| snapshot.state);
 
         // Update rate histories from state.
         if let Some(ref s) = state {
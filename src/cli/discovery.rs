@@ -0,0 +1,193 @@
+//! Monitoring-system feeds for `sbh status`: Zabbix low-level discovery and
+//! flat Telegraf `exec` records.
+//!
+//! Zabbix discovers one item set per mount from `{#MACRO}` rows; Telegraf's
+//! `json` parser wants a flat array of objects with string tags and numeric
+//! fields. Daemon-wide fields come from the Prometheus metric registry so
+//! every feed reports the same counters under the same names.
+
+#![allow(missing_docs)]
+
+use serde_json::{Map, Value, json};
+
+use crate::core::metrics::{METRICS, pressure_level_code};
+use crate::core::state::DaemonState;
+
+/// Measurement name of the per-mount Telegraf records.
+pub const TELEGRAF_MOUNT_MEASUREMENT: &str = "sbh_mount";
+/// Measurement name of the daemon-wide Telegraf record.
+pub const TELEGRAF_DAEMON_MEASUREMENT: &str = "sbh_daemon";
+
+/// One monitored mount as `sbh status` measured it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredMount {
+    pub path: String,
+    pub fs_type: String,
+    pub class: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub free_pct: f64,
+    pub level: String,
+    /// Daemon EWMA fill rate for the mount, when the daemon reports one.
+    pub rate_bps: Option<f64>,
+}
+
+/// Zabbix low-level-discovery document for `mounts`.
+///
+/// Besides the mount identity, each row carries the warning and critical
+/// free-space floors so trigger prototypes can use `{#SBH.WARN_PCT}` instead
+/// of hard-coding sbh's thresholds.
+#[must_use]
+pub fn zabbix_lld(mounts: &[DiscoveredMount], warning_pct: f64, critical_pct: f64) -> Value {
+    let rows: Vec<Value> = mounts
+        .iter()
+        .map(|mount| {
+            json!({
+                "{#FSNAME}": mount.path,
+                "{#FSTYPE}": mount.fs_type,
+                "{#SBH.CLASS}": mount.class,
+                "{#SBH.WARN_PCT}": warning_pct,
+                "{#SBH.CRIT_PCT}": critical_pct,
+            })
+        })
+        .collect();
+    json!({ "data": rows })
+}
+
+/// Flat records for Telegraf's `exec` input with `data_format = "json"`.
+///
+/// One `sbh_mount` record per mount (tags `mount`, `fs_type`, `class`) plus,
+/// when daemon state is available, one `sbh_daemon` record of the registry's
+/// unlabelled metrics and ballast counts.
+#[must_use]
+pub fn telegraf_records(mounts: &[DiscoveredMount], daemon: Option<&DaemonState>) -> Value {
+    let mut records: Vec<Value> = mounts
+        .iter()
+        .map(|mount| {
+            let mut record = json!({
+                "name": TELEGRAF_MOUNT_MEASUREMENT,
+                "mount": mount.path,
+                "fs_type": mount.fs_type,
+                "class": mount.class,
+                "total_bytes": mount.total_bytes,
+                "free_bytes": mount.free_bytes,
+                "free_pct": mount.free_pct,
+            });
+            if let Some(code) = pressure_level_code(&mount.level) {
+                record["pressure_level"] = json!(code);
+            }
+            if let Some(rate) = mount.rate_bps {
+                record["fill_rate_bytes_per_second"] = json!(rate);
+            }
+            record
+        })
+        .collect();
+
+    if let Some(state) = daemon {
+        let mut fields = Map::new();
+        fields.insert("name".to_string(), json!(TELEGRAF_DAEMON_MEASUREMENT));
+        // Per-mount series are covered by the live mount records above.
+        for metric in METRICS
+            .iter()
+            .filter(|metric| !metric.labels.contains(&"mount"))
+        {
+            let base = metric.name.strip_prefix("sbh_").unwrap_or(metric.name);
+            for sample in (metric.collect)(state) {
+                let key = sample
+                    .labels
+                    .iter()
+                    .fold(base.to_string(), |key, (_, value)| format!("{key}_{value}"));
+                fields.insert(key, json!(sample.value));
+            }
+        }
+        records.push(Value::Object(fields));
+    }
+    Value::Array(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::{BallastState, Counters};
+
+    fn mount(path: &str, level: &str, rate_bps: Option<f64>) -> DiscoveredMount {
+        DiscoveredMount {
+            path: path.to_string(),
+            fs_type: "ext4".to_string(),
+            class: "permanent".to_string(),
+            total_bytes: 1_000,
+            free_bytes: 120,
+            free_pct: 12.0,
+            level: level.to_string(),
+            rate_bps,
+        }
+    }
+
+    #[test]
+    fn zabbix_rows_carry_mount_and_threshold_macros() {
+        let lld = zabbix_lld(&[mount("/data", "yellow", None)], 14.0, 6.0);
+        let row = &lld["data"][0];
+        assert_eq!(row["{#FSNAME}"], "/data");
+        assert_eq!(row["{#FSTYPE}"], "ext4");
+        assert_eq!(row["{#SBH.CLASS}"], "permanent");
+        assert_eq!(row["{#SBH.WARN_PCT}"], 14.0);
+        assert_eq!(row["{#SBH.CRIT_PCT}"], 6.0);
+        assert_eq!(zabbix_lld(&[], 14.0, 6.0), json!({ "data": [] }));
+    }
+
+    #[test]
+    fn telegraf_records_are_flat_with_numeric_fields() {
+        let state = DaemonState {
+            ballast: BallastState {
+                available: 7,
+                total: 10,
+                released: 3,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
+            },
+            counters: Counters {
+                bytes_freed: 4_096,
+                ..Counters::default()
+            },
+            policy_mode: "canary".to_string(),
+            ..DaemonState::default()
+        };
+        let records = telegraf_records(
+            &[
+                mount("/data", "orange", Some(256.0)),
+                mount("/tmp", "bogus", None),
+            ],
+            Some(&state),
+        );
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 3);
+        for record in records {
+            assert!(
+                record
+                    .as_object()
+                    .unwrap()
+                    .values()
+                    .all(|v| !v.is_object() && !v.is_array())
+            );
+        }
+
+        assert_eq!(records[0]["name"], TELEGRAF_MOUNT_MEASUREMENT);
+        assert_eq!(records[0]["pressure_level"], 2.0);
+        assert_eq!(records[0]["fill_rate_bytes_per_second"], 256.0);
+        assert!(records[1].get("pressure_level").is_none());
+
+        let daemon = &records[2];
+        assert_eq!(daemon["name"], TELEGRAF_DAEMON_MEASUREMENT);
+        assert_eq!(daemon["bytes_freed_total"], 4_096.0);
+        assert_eq!(daemon["ballast_files_available"], 7.0);
+        assert_eq!(daemon["policy_mode"], 1.0);
+        assert!(daemon.get("mount_free_percent").is_none());
+    }
+
+    #[test]
+    fn telegraf_without_daemon_state_reports_mounts_only() {
+        let records = telegraf_records(&[mount("/data", "green", None)], None);
+        assert_eq!(records.as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,787 @@
+//! `sbh doctor`: diagnose an installation and its environment.
+//!
+//! Where `sbh selftest` exercises the pipeline in a sandbox, doctor looks at
+//! the real host: the binary's ownership, the installed service unit, the
+//! config, the activity database, the ballast pools, the daemon's state
+//! file, platform support, and the mount options of every path sbh reads or
+//! writes. Each check is ok, warn, or fail, and every warning or failure
+//! carries one remediation step, so the report doubles as a checklist.
+//!
+//! Doctor only reads. It never provisions ballast, rewrites state, or
+//! checkpoints the database, so it is safe to run next to a live daemon.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::ballast::manager::BallastManager;
+use crate::core::config::Config;
+use crate::core::container;
+use crate::daemon::service::{LaunchdConfig, SystemdConfig};
+use crate::platform::pal::{detect_platform, unescape_mount_path};
+use crate::scanner::io_throttle::PSI_IO_PATH;
+
+/// A write-ahead log larger than this means checkpoints are not keeping up.
+pub const WAL_WARN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Outcome of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub remediation: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+}
+
+/// All checks, in the order they ran.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// No check failed; warnings are allowed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+
+    /// Remediation steps, failures first.
+    #[must_use]
+    pub fn checklist(&self) -> Vec<&str> {
+        let mut pending: Vec<&Check> = self
+            .checks
+            .iter()
+            .filter(|check| check.remediation.is_some())
+            .collect();
+        pending.sort_by_key(|check| std::cmp::Reverse(check.status));
+        pending
+            .into_iter()
+            .filter_map(|check| check.remediation.as_deref())
+            .collect()
+    }
+}
+
+/// Run every check against the config at `config_path` (or the default
+/// resolution). An invalid config is reported and the remaining checks use
+/// built-in defaults.
+#[must_use]
+pub fn run(config_path: Option<&Path>) -> DoctorReport {
+    let (config_check, config) = match Config::load(config_path) {
+        Ok(config) => {
+            let file = &config.paths.config_file;
+            let detail = if file.exists() {
+                format!("{} is valid", file.display())
+            } else {
+                format!("no file at {}; using built-in defaults", file.display())
+            };
+            (Check::ok("config", detail), config)
+        }
+        Err(e) => (
+            Check::fail(
+                "config",
+                e.to_string(),
+                "fix the config file, then confirm with `sbh config validate`",
+            ),
+            Config::default(),
+        ),
+    };
+    let units = installed_units();
+    let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("sbh"));
+    let mounts = fs::read_to_string("/proc/self/mounts")
+        .map(|raw| parse_mount_options(&raw))
+        .ok();
+
+    let checks = vec![
+        check_binary(&exe, units.iter().any(|unit| unit.system)),
+        check_service(&units, &exe),
+        config_check,
+        check_database(&config.paths.sqlite_db),
+        check_ballast(&config),
+        check_state(&config.paths.state_file),
+        check_platform(&config),
+        check_tools(&config),
+        check_mounts(&config, mounts.as_deref()),
+    ];
+    DoctorReport { checks }
+}
+
+// ──────────────────── binary ────────────────────
+
+/// A binary others can write to is one they can replace, and a root service
+/// would then run their code.
+#[cfg(unix)]
+fn check_binary(exe: &Path, root_service: bool) -> Check {
+    use std::os::unix::fs::MetadataExt;
+
+    let meta = match fs::metadata(exe) {
+        Ok(meta) => meta,
+        Err(e) => {
+            return Check::warn(
+                "binary",
+                format!("cannot stat {}: {e}", exe.display()),
+                "reinstall sbh with `sbh install`",
+            );
+        }
+    };
+    let mode = meta.mode() & 0o7777;
+    let detail = format!("{} (mode {mode:o}, uid {})", exe.display(), meta.uid());
+    if mode & 0o022 != 0 {
+        Check::fail(
+            "binary",
+            format!("{detail} is writable by group or others"),
+            format!("chmod go-w {}", exe.display()),
+        )
+    } else if root_service && meta.uid() != 0 {
+        Check::warn(
+            "binary",
+            format!("{detail} is not owned by root but a system service runs it"),
+            format!("sudo chown root:root {}", exe.display()),
+        )
+    } else {
+        Check::ok("binary", detail)
+    }
+}
+
+#[cfg(not(unix))]
+fn check_binary(exe: &Path, _root_service: bool) -> Check {
+    Check::ok("binary", exe.display().to_string())
+}
+
+// ──────────────────── service ────────────────────
+
+/// An installed service definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceUnit {
+    pub path: PathBuf,
+    /// System scope (runs as root) rather than user scope.
+    pub system: bool,
+}
+
+/// Service definitions present in either scope for this platform's
+/// service manager.
+#[must_use]
+pub fn installed_units() -> Vec<ServiceUnit> {
+    let candidates: Vec<(PathBuf, bool)> = if cfg!(target_os = "macos") {
+        [false, true]
+            .into_iter()
+            .map(|user_scope| {
+                let config = LaunchdConfig {
+                    user_scope,
+                    binary_path: PathBuf::new(),
+                    stdout_log: PathBuf::new(),
+                    stderr_log: PathBuf::new(),
+                };
+                (config.plist_path(), !user_scope)
+            })
+            .collect()
+    } else if cfg!(target_os = "linux") {
+        [false, true]
+            .into_iter()
+            .map(|user_scope| {
+                let config = SystemdConfig {
+                    user_scope,
+                    binary_path: PathBuf::new(),
+                    read_write_paths: Vec::new(),
+                };
+                (config.unit_path(), !user_scope)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    candidates
+        .into_iter()
+        .filter(|(path, _)| path.exists())
+        .map(|(path, system)| ServiceUnit { path, system })
+        .collect()
+}
+
+/// The binary a systemd unit starts: the first word of `ExecStart=`.
+#[must_use]
+pub fn unit_binary(unit: &str) -> Option<PathBuf> {
+    unit.lines()
+        .find_map(|line| line.trim().strip_prefix("ExecStart="))?
+        .split_whitespace()
+        .next()
+        .map(PathBuf::from)
+}
+
+fn check_service(units: &[ServiceUnit], exe: &Path) -> Check {
+    let install = if cfg!(target_os = "macos") {
+        "sbh install --launchd"
+    } else {
+        "sbh install --systemd"
+    };
+    match units {
+        [] if container::is_enabled() => {
+            Check::ok("service", "container mode; the image runs the daemon")
+        }
+        [] if !cfg!(any(target_os = "linux", target_os = "macos")) => {
+            Check::ok("service", "service units are not checked on this platform")
+        }
+        [] => Check::warn(
+            "service",
+            "no service unit installed; nothing runs the daemon",
+            format!("{install} (add --user for a per-user service)"),
+        ),
+        [unit] => {
+            let scope = if unit.system { "system" } else { "user" };
+            let detail = format!("{scope} unit {}", unit.path.display());
+            let runs = fs::read_to_string(&unit.path)
+                .ok()
+                .and_then(|raw| unit_binary(&raw));
+            match runs {
+                Some(bin) if !bin.exists() => Check::fail(
+                    "service",
+                    format!("{detail} runs {}, which does not exist", bin.display()),
+                    format!("reinstall the unit: {install}"),
+                ),
+                Some(bin) if fs::canonicalize(&bin).ok() != fs::canonicalize(exe).ok() => {
+                    Check::warn(
+                        "service",
+                        format!(
+                            "{detail} runs {}, not this binary ({})",
+                            bin.display(),
+                            exe.display()
+                        ),
+                        format!("reinstall the unit from the binary you mean to run: {install}"),
+                    )
+                }
+                _ => Check::ok("service", detail),
+            }
+        }
+        _ => Check::warn(
+            "service",
+            format!(
+                "units installed in both scopes ({}); two daemons would compete",
+                units
+                    .iter()
+                    .map(|unit| unit.path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            "disable and delete the unit for the scope you do not use",
+        ),
+    }
+}
+
+// ──────────────────── database ────────────────────
+
+#[cfg(feature = "sqlite")]
+fn check_database(db: &Path) -> Check {
+    use rusqlite::{Connection, OpenFlags};
+
+    if !db.exists() {
+        return Check::warn(
+            "database",
+            format!("no activity database at {}", db.display()),
+            "start the daemon; it creates the database on first run",
+        );
+    }
+    let conn = match Connection::open_with_flags(
+        db,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) {
+        Ok(conn) => conn,
+        Err(e) => {
+            return Check::warn(
+                "database",
+                format!("cannot open {}: {e}", db.display()),
+                "run doctor as the user the daemon runs as",
+            );
+        }
+    };
+    let integrity = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0))
+        .unwrap_or_else(|e| e.to_string());
+    if integrity != "ok" {
+        return Check::fail(
+            "database",
+            format!("{} failed its integrity check: {integrity}", db.display()),
+            format!(
+                "stop the daemon, move {} aside, and restart it to start a fresh database",
+                db.display()
+            ),
+        );
+    }
+    let mut wal = db.as_os_str().to_owned();
+    wal.push("-wal");
+    let wal_bytes = fs::metadata(&wal).map_or(0, |meta| meta.len());
+    let detail = format!(
+        "{} passes its integrity check, WAL {} KiB",
+        db.display(),
+        wal_bytes / 1024
+    );
+    if wal_bytes > WAL_WARN_BYTES {
+        Check::warn(
+            "database",
+            format!("{detail}; checkpoints are not keeping up"),
+            format!(
+                "restart the daemon, or run `sqlite3 {} 'PRAGMA wal_checkpoint(TRUNCATE)'`",
+                db.display()
+            ),
+        )
+    } else {
+        Check::ok("database", detail)
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn check_database(_db: &Path) -> Check {
+    Check::ok(
+        "database",
+        "built without SQLite; activity goes to JSONL only",
+    )
+}
+
+// ──────────────────── ballast ────────────────────
+
+fn check_ballast(config: &Config) -> Check {
+    let pools: Vec<_> = if config.ballast.pools.is_empty() {
+        vec![(config.paths.ballast_dir.clone(), config.ballast.clone())]
+    } else {
+        config
+            .ballast
+            .pools
+            .iter()
+            .map(|pool| {
+                (
+                    pool.path.clone(),
+                    config.ballast.pool_settings("", &pool.path),
+                )
+            })
+            .collect()
+    };
+    let (mut expected, mut ok, mut missing) = (0, 0, 0);
+    let mut corrupted = Vec::new();
+    for (dir, settings) in pools {
+        if settings.file_count == 0 {
+            continue;
+        }
+        expected += settings.file_count;
+        if !dir.is_dir() {
+            missing += settings.file_count;
+            continue;
+        }
+        let mut manager = BallastManager::inspect(dir.clone(), settings);
+        match manager.verify() {
+            Ok(report) => {
+                ok += report.files_ok;
+                missing += report.files_missing;
+                if report.files_corrupted > 0 {
+                    corrupted.push(format!("{}: {}", dir.display(), report.details.join("; ")));
+                }
+            }
+            Err(e) => corrupted.push(format!("{}: {e}", dir.display())),
+        }
+    }
+
+    if expected == 0 {
+        Check::ok("ballast", "ballast is disabled (file_count = 0)")
+    } else if !corrupted.is_empty() {
+        Check::fail(
+            "ballast",
+            format!("corrupted ballast: {}", corrupted.join(", ")),
+            "rebuild the corrupted files with `sbh ballast provision`",
+        )
+    } else if ok == 0 {
+        Check::warn(
+            "ballast",
+            format!("none of the {expected} ballast files exist"),
+            "provision ballast with `sbh ballast provision`",
+        )
+    } else if missing > 0 {
+        Check::warn(
+            "ballast",
+            format!(
+                "{ok} of {expected} files present; the rest were released or never provisioned"
+            ),
+            "`sbh ballast replenish` once pressure has eased, or `sbh ballast provision`",
+        )
+    } else {
+        Check::ok("ballast", format!("{ok} of {expected} files intact"))
+    }
+}
+
+// ──────────────────── state ────────────────────
+
+fn check_state(state_file: &Path) -> Check {
+    use crate::core::state::{
+        DAEMON_STATE_STALE_THRESHOLD_SECS, StateParseError, StateReadError, read_state,
+    };
+
+    match read_state(state_file) {
+        Ok(snapshot) if snapshot.is_fresh() => Check::ok(
+            "state",
+            format!(
+                "{} written {}s ago",
+                state_file.display(),
+                snapshot.age.as_secs()
+            ),
+        ),
+        Ok(snapshot) => Check::warn(
+            "state",
+            format!(
+                "{} last written {}s ago (stale after {DAEMON_STATE_STALE_THRESHOLD_SECS}s); \
+                 the daemon has stopped or stalled",
+                state_file.display(),
+                snapshot.age.as_secs()
+            ),
+            "check the daemon with `sbh status` and restart its service",
+        ),
+        Err(StateReadError::Missing) => Check::warn(
+            "state",
+            format!(
+                "no state file at {}; the daemon is not running",
+                state_file.display()
+            ),
+            "start the daemon through its service, or run `sbh daemon`",
+        ),
+        Err(StateReadError::Io(e)) => Check::warn(
+            "state",
+            format!("cannot read {}: {e}", state_file.display()),
+            "run doctor as the user the daemon runs as",
+        ),
+        Err(StateReadError::Parse(e @ StateParseError::NewerSchema(_))) => Check::warn(
+            "state",
+            e.to_string(),
+            "upgrade this binary with `sbh update`",
+        ),
+        Err(StateReadError::Parse(e)) => Check::fail(
+            "state",
+            format!("{}: {e}", state_file.display()),
+            "restart the daemon so it rewrites the state file",
+        ),
+    }
+}
+
+// ──────────────────── platform ────────────────────
+
+fn check_platform(config: &Config) -> Check {
+    if let Err(e) = detect_platform() {
+        return Check::fail(
+            "platform",
+            e.to_string(),
+            "run the daemon on Linux or Windows; elsewhere use the one-shot commands",
+        );
+    }
+    let os = std::env::consts::OS;
+    if cfg!(target_os = "linux")
+        && config.scanner.io_throttle().is_some()
+        && !Path::new(PSI_IO_PATH).exists()
+    {
+        return Check::warn(
+            "platform",
+            format!("{os}: no {PSI_IO_PATH}, so deletion is not paced on IO pressure"),
+            "boot a kernel with pressure stall information (4.20+, `psi=1`), \
+             or set scanner.io_throttle_stall_pct = 0",
+        );
+    }
+    Check::ok("platform", format!("{os} is supported"))
+}
+
+// ──────────────────── tools ────────────────────
+
+fn check_tools(config: &Config) -> Check {
+    let missing = container::missing_tools(config);
+    if missing.is_empty() {
+        return Check::ok("tools", "every enabled feature has the programs it runs");
+    }
+    let detail = missing
+        .iter()
+        .map(|tool| {
+            let fallback = tool
+                .fallback
+                .map(|fallback| format!(" (using {fallback} meanwhile)"))
+                .unwrap_or_default();
+            format!("{} need `{}`{fallback}", tool.feature, tool.program)
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    // Features with a fallback still work, just less thoroughly.
+    if missing.iter().all(|tool| tool.fallback.is_some()) {
+        return Check::ok("tools", format!("{detail}, not found on PATH"));
+    }
+    Check::warn(
+        "tools",
+        format!("{detail}, not found on PATH"),
+        "install the missing programs or turn those features off",
+    )
+}
+
+// ──────────────────── mounts ────────────────────
+
+/// A `/proc/self/mounts` entry with its options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountOptions {
+    pub path: PathBuf,
+    pub fs_type: String,
+    pub options: Vec<String>,
+}
+
+impl MountOptions {
+    #[must_use]
+    pub fn read_only(&self) -> bool {
+        self.options.iter().any(|option| option == "ro")
+    }
+
+    #[must_use]
+    pub fn ram_backed(&self) -> bool {
+        matches!(self.fs_type.as_str(), "tmpfs" | "ramfs")
+    }
+}
+
+/// Parse `/proc/self/mounts`, deepest mount points first.
+#[must_use]
+pub fn parse_mount_options(raw: &str) -> Vec<MountOptions> {
+    let mut mounts: Vec<MountOptions> = raw
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let path = unescape_mount_path(fields.next()?);
+            let fs_type = fields.next()?.to_string();
+            let options = fields.next()?.split(',').map(str::to_string).collect();
+            Some(MountOptions {
+                path,
+                fs_type,
+                options,
+            })
+        })
+        .collect();
+    // Later entries shadow earlier ones at the same mount point.
+    mounts.reverse();
+    mounts.sort_by_key(|mount| std::cmp::Reverse(mount.path.as_os_str().len()));
+    mounts
+}
+
+/// The mount holding `path`.
+#[must_use]
+pub fn mount_of<'a>(path: &Path, mounts: &'a [MountOptions]) -> Option<&'a MountOptions> {
+    mounts.iter().find(|mount| path.starts_with(&mount.path))
+}
+
+fn check_mounts(config: &Config, mounts: Option<&[MountOptions]>) -> Check {
+    let Some(mounts) = mounts else {
+        return Check::ok("mounts", "mount options are not checked on this platform");
+    };
+    let ballast_dirs: Vec<&Path> = if config.ballast.pools.is_empty() {
+        vec![&config.paths.ballast_dir]
+    } else {
+        config
+            .ballast
+            .pools
+            .iter()
+            .map(|pool| pool.path.as_path())
+            .collect()
+    };
+    let written = [
+        ("state file", config.paths.state_file.as_path()),
+        ("database", config.paths.sqlite_db.as_path()),
+    ];
+
+    let mut problems: Vec<(CheckStatus, String, String)> = Vec::new();
+    for dir in ballast_dirs {
+        let Some(mount) = nearest_existing(dir).and_then(|path| mount_of(&path, mounts)) else {
+            continue;
+        };
+        if mount.read_only() {
+            problems.push((
+                CheckStatus::Fail,
+                format!(
+                    "ballast {} is on read-only {}",
+                    dir.display(),
+                    mount.path.display()
+                ),
+                "move ballast to a writable filesystem (paths.ballast_dir)".to_string(),
+            ));
+        } else if mount.ram_backed() {
+            problems.push((
+                CheckStatus::Warn,
+                format!(
+                    "ballast {} is on {} {}; releasing it frees memory, not disk",
+                    dir.display(),
+                    mount.fs_type,
+                    mount.path.display()
+                ),
+                "move ballast to the disk it should protect (paths.ballast_dir)".to_string(),
+            ));
+        }
+    }
+    for (what, path) in written {
+        if let Some(mount) = nearest_existing(path).and_then(|path| mount_of(&path, mounts))
+            && mount.read_only()
+        {
+            problems.push((
+                CheckStatus::Fail,
+                format!(
+                    "{what} {} is on read-only {}",
+                    path.display(),
+                    mount.path.display()
+                ),
+                format!("point {what} at a writable directory in [paths]"),
+            ));
+        }
+    }
+    for root in config
+        .scanner
+        .root_paths
+        .iter()
+        .filter(|root| root.exists())
+    {
+        if let Some(mount) = mount_of(root, mounts)
+            && mount.read_only()
+        {
+            problems.push((
+                CheckStatus::Warn,
+                format!(
+                    "watched {} is on read-only {}; nothing there can be cleaned",
+                    root.display(),
+                    mount.path.display()
+                ),
+                format!("drop {} from scanner.root_paths", root.display()),
+            ));
+        }
+    }
+
+    let Some(worst) = problems.iter().map(|(status, ..)| *status).max() else {
+        return Check::ok(
+            "mounts",
+            "ballast, state, and watched paths are on suitable mounts",
+        );
+    };
+    let (details, remediations): (Vec<_>, Vec<_>) = problems
+        .into_iter()
+        .map(|(_, detail, remediation)| (detail, remediation))
+        .unzip();
+    Check {
+        name: "mounts",
+        status: worst,
+        detail: details.join("; "),
+        remediation: Some(remediations.join("; ")),
+    }
+}
+
+/// `path` or its closest existing ancestor: a path sbh has yet to create
+/// lands on its parent's mount.
+fn nearest_existing(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mount_options_are_parsed_deepest_first() {
+        let raw = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+                   tmpfs /tmp tmpfs rw,nosuid 0 0\n\
+                   /dev/sdb1 /mnt/old\\040disk ext4 ro,noatime 0 0\n\
+                   /dev/sdc1 /tmp ext4 rw 0 0\n";
+        let mounts = parse_mount_options(raw);
+
+        let old = mount_of(Path::new("/mnt/old disk/x"), &mounts).unwrap();
+        assert!(old.read_only());
+        assert_eq!(old.options, ["ro", "noatime"]);
+        // The later mount over /tmp shadows the tmpfs.
+        let tmp = mount_of(Path::new("/tmp/sbh"), &mounts).unwrap();
+        assert_eq!(tmp.fs_type, "ext4");
+        assert!(!tmp.ram_backed());
+        assert_eq!(
+            mount_of(Path::new("/var/lib/sbh"), &mounts).unwrap().path,
+            Path::new("/")
+        );
+    }
+
+    #[test]
+    fn unit_binary_is_the_first_word_of_exec_start() {
+        let unit = "[Service]\nType=simple\nExecStart=/usr/local/bin/sbh daemon\n";
+        assert_eq!(unit_binary(unit), Some(PathBuf::from("/usr/local/bin/sbh")));
+        assert_eq!(unit_binary("[Service]\n"), None);
+    }
+
+    #[test]
+    fn checklist_lists_failures_before_warnings() {
+        let report = DoctorReport {
+            checks: vec![
+                Check::warn("state", "stale", "restart"),
+                Check::ok("config", "valid"),
+                Check::fail("binary", "writable", "chmod go-w"),
+            ],
+        };
+        assert!(!report.passed());
+        assert_eq!(report.checklist(), ["chmod go-w", "restart"]);
+    }
+
+    #[test]
+    fn missing_state_and_ballast_are_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.paths.ballast_dir = dir.path().join("ballast");
+        config.ballast.file_count = 2;
+
+        let state = check_state(&dir.path().join("state.json"));
+        assert_eq!(state.status, CheckStatus::Warn);
+        let ballast = check_ballast(&config);
+        assert_eq!(ballast.status, CheckStatus::Warn);
+        assert_eq!(ballast.detail, "none of the 2 ballast files exist");
+        assert!(!config.paths.ballast_dir.exists());
+    }
+}
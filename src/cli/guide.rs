@@ -0,0 +1,293 @@
+//! Long-form `sbh help <topic>` guides.
+//!
+//! Each topic explains one part of the decision model in operator terms so
+//! the reasoning behind a scan, deletion, or ballast release can be learned
+//! from the box itself. Topics are static text; numbers quoted here are the
+//! shipped defaults and must be kept in sync with `core::config`.
+
+#![allow(missing_docs)]
+
+use std::fmt;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+
+/// A long-form help topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HelpTopic {
+    PressureModel,
+    Scoring,
+    PolicyModes,
+    Emergency,
+}
+
+impl HelpTopic {
+    /// Every topic, in display order.
+    pub const ALL: [Self; 4] = [
+        Self::PressureModel,
+        Self::Scoring,
+        Self::PolicyModes,
+        Self::Emergency,
+    ];
+
+    /// Stable kebab-case name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::PressureModel => "pressure-model",
+            Self::Scoring => "scoring",
+            Self::PolicyModes => "policy-modes",
+            Self::Emergency => "emergency",
+        }
+    }
+
+    /// One-line summary for topic listings.
+    #[must_use]
+    pub const fn summary(self) -> &'static str {
+        match self {
+            Self::PressureModel => "Free-space levels, EWMA forecasting, and the PID controller",
+            Self::Scoring => "Five-factor artifact scoring and the expected-loss decision",
+            Self::PolicyModes => "Observe, canary, enforce, and fallback-safe rollout modes",
+            Self::Emergency => "Zero-write recovery when the disk is already full",
+        }
+    }
+
+    /// Full guide text.
+    #[must_use]
+    pub const fn body(self) -> &'static str {
+        match self {
+            Self::PressureModel => PRESSURE_MODEL,
+            Self::Scoring => SCORING,
+            Self::PolicyModes => POLICY_MODES,
+            Self::Emergency => EMERGENCY,
+        }
+    }
+
+    /// Resolve a topic from its name (case-insensitive, `_` accepted for `-`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|t| t.name() == normalized)
+    }
+}
+
+impl fmt::Display for HelpTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Render the topic index shown by `sbh help --topics`.
+#[must_use]
+pub fn format_topic_list() -> String {
+    let mut out = String::from("Help topics (sbh help <topic>):\n");
+    for topic in HelpTopic::ALL {
+        let _ = writeln!(out, "  {:<16} {}", topic.name(), topic.summary());
+    }
+    out
+}
+
+/// Write `text` through the user's pager, falling back to plain stdout.
+///
+/// Uses `$PAGER` when set (an empty value disables paging), otherwise
+/// `less -R`. Any failure to spawn the pager degrades to a direct write.
+pub fn page(text: &str) -> io::Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    if let Some(program) = parts.next()
+        && let Ok(mut child) = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()
+    {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A pager quitting early closes the pipe; that is not an error.
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        child.wait()?;
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()
+}
+
+const PRESSURE_MODEL: &str = "\
+PRESSURE MODEL
+
+sbh classifies every watched mount by its free-space percentage:
+
+  Level      Free space (default)   Response
+  GREEN      > 20%                  base scan interval, no ballast release
+  YELLOW     14-20%                 scan twice as often, up to 1 ballast file
+  ORANGE     10-14%                 scan 4x as often, 1-3 ballast files
+  RED        6-10%                  scan 8x as often, 3-5 ballast files
+  CRITICAL   < 6%                   100ms polling, 10 ballast files
+
+Thresholds live under [pressure] (green_min_free_pct, yellow_min_free_pct,
+orange_min_free_pct, red_min_free_pct) and must strictly descend.
+
+FORECASTING
+  An EWMA estimator tracks bytes/sec consumed on each mount. Its smoothing
+  factor adapts to burstiness so a sudden `cargo build` is tracked within a
+  few samples. Acceleration is tracked too, giving a quadratic
+  time-to-exhaustion estimate. With fewer than 3 samples or confidence below
+  0.2 the estimator reports uncertainty instead of a number.
+
+CONTROL
+  A PID controller turns the gap between the 18% setpoint and current free
+  space into an urgency value between 0.0 and 1.0:
+
+    error   = target_free_pct - current_free_pct
+    raw     = Kp*error + Ki*integral + Kd*derivative   (0.25, 0.08, 0.02)
+    urgency = 1 - exp(-max(0, raw))
+
+  Urgency scales scan frequency, delete batch size, and ballast release.
+
+PREDICTIVE PRE-EMPTION
+  When [pressure.prediction] is enabled and exhaustion is forecast within
+  action_horizon_minutes (default 30), urgency is raised to at least 0.70
+  even if the mount is only YELLOW today. warning_horizon_minutes (default
+  60) emits early warnings without acting.
+
+See also: sbh status, sbh check --predict, sbh help scoring
+";
+
+const SCORING: &str = "\
+SCORING
+
+Every candidate gets a composite score from five weighted factors. Weights
+live under [scoring] and must sum to 1.0:
+
+  Factor      Weight   Signal
+  location    0.25     /tmp and build dirs high, documents and system paths low
+  name        0.25     known artifact patterns (target/, node_modules/, *.o, ...)
+  age         0.20     peaks at 4-10 hours; very fresh items score 0
+  size        0.15     favors 1-10 GiB with diminishing returns beyond
+  structure   0.15     .git/ forces 0; cargo fingerprints and object dirs score high
+
+Under pressure the composite is multiplied by 1.0 (calm) up to 3.0
+(critical urgency) so marginal candidates become eligible.
+
+EXPECTED-LOSS DECISION
+  The score is not a delete threshold by itself. It is converted to a
+  posterior probability that the artifact is abandoned, then two losses are
+  compared:
+
+    keep loss   = posterior       * false_negative_loss   (default 30)
+    delete loss = (1 - posterior) * false_positive_loss   (default 50)
+
+  The asymmetry encodes that deleting something useful costs more than
+  leaving something stale. A [costs] table (category or pattern name ->
+  regeneration cost, same unit) replaces false_positive_loss per artifact,
+  so cheap re-fetches like remote-cached Bazel outputs go first. Posterior entropy and calibration inflate the
+  delete loss when sbh is unsure; items too uncertain to decide land in
+  REVIEW instead of being kept or deleted silently.
+
+VETOES
+  Protected paths (.sbh-protect markers, scanner.protected_paths globs), open
+  files, .git directories, and recent writes veto deletion regardless of
+  score.
+
+See also: sbh scan, sbh protect, sbh help policy-modes
+";
+
+const POLICY_MODES: &str = "\
+POLICY MODES
+
+The daemon's policy engine decides whether scored deletions actually run:
+
+  Mode           Deletes   Purpose
+  observe        no        shadow mode; logs what would have been deleted
+  canary         capped    real deletions, at most max_canary_deletes_per_hour
+  enforce        yes       full pipeline
+  fallback_safe  no        automatic safety mode; only ballast release
+
+Set the starting mode with `sbh config set policy.initial_mode <mode>`.
+
+PROMOTION
+  observe -> canary -> enforce is always explicit; sbh never auto-promotes.
+
+DEMOTION
+  The engine drops to fallback_safe automatically on:
+    - repeated calibration breaches (policy.calibration_breach_windows)
+    - guardrail drift alarms
+    - canary budget exhaustion
+    - failure to persist daemon state
+    - the kill switch (policy.kill_switch or SBH_POLICY_KILL_SWITCH=true)
+
+RECOVERY
+  After policy.recovery_clean_windows clean windows the engine recovers. A
+  daemon that was in enforce returns to canary first and must be promoted
+  again, so it re-proves itself under capped deletions.
+
+See also: sbh status, sbh help scoring
+";
+
+const EMERGENCY: &str = "\
+EMERGENCY
+
+`sbh emergency` is for a disk that is already full, when ordinary tools fail
+because they need to write temp files, logs, or state.
+
+ZERO-WRITE GUARANTEES
+  - no SQLite or JSONL log writes
+  - no state file updates
+  - no config reads that could trigger cache writes
+  - scoring uses in-memory defaults; output goes to stdout/stderr only
+
+USAGE
+  sbh emergency /data --target-free 10        interactive: review, then confirm
+  sbh emergency /data --target-free 10 --yes  delete highest scores until 10% free
+
+Candidates are taken in score order until the target free percentage is
+reached or candidates run out. Protection markers and safety vetoes still
+apply.
+
+FIRST MOVES
+  1. sbh ballast release 3      ballast is the fastest, safest space
+  2. sbh emergency <mount>      reclaim stale artifacts without writing
+  3. sbh status                 confirm the mount is out of RED
+
+See also: sbh ballast, sbh help pressure-model
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_names_round_trip() {
+        for topic in HelpTopic::ALL {
+            assert_eq!(HelpTopic::from_name(topic.name()), Some(topic));
+        }
+        assert_eq!(
+            HelpTopic::from_name("Policy_Modes"),
+            Some(HelpTopic::PolicyModes)
+        );
+        assert_eq!(HelpTopic::from_name("nonexistent"), None);
+    }
+
+    #[test]
+    fn every_topic_has_a_titled_body() {
+        for topic in HelpTopic::ALL {
+            let title = topic.name().replace('-', " ").to_ascii_uppercase();
+            assert!(
+                topic.body().starts_with(&title),
+                "{topic} body must start with {title}"
+            );
+        }
+    }
+
+    #[test]
+    fn topic_list_mentions_every_topic() {
+        let list = format_topic_list();
+        for topic in HelpTopic::ALL {
+            assert!(list.contains(topic.name()));
+        }
+    }
+}
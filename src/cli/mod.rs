@@ -3,12 +3,22 @@
 
 pub mod assets;
 pub mod bootstrap;
+pub mod check_policy;
+pub mod ci;
+pub mod clean_plan;
+pub mod config_transfer;
 pub mod dashboard;
+pub mod discovery;
+pub mod doctor;
 pub mod from_source;
+pub mod guide;
 pub mod install;
+pub mod nagios;
+pub mod selftest;
 pub mod uninstall;
 pub mod update;
 pub mod wizard;
+pub mod workspace;
 
 use std::fmt;
 use std::fs;
@@ -36,6 +46,8 @@ pub const RELEASE_BINARY_NAME: &str = "sbh";
 pub const CI_RELEASE_TARGETS: &[&str] = &[
     "x86_64-unknown-linux-gnu",
     "aarch64-unknown-linux-gnu",
+    "x86_64-unknown-linux-musl",
+    "aarch64-unknown-linux-musl",
     "x86_64-apple-darwin",
     "aarch64-apple-darwin",
 ];
@@ -744,6 +756,14 @@ fn resolve_artifact_target(host: HostSpecifier) -> Result<ArtifactTarget> {
             triple: "aarch64-unknown-linux-gnu",
             archive: ArchiveFormat::TarXz,
         }),
+        (HostOs::Linux, HostArch::X86_64, HostAbi::Musl) => Ok(ArtifactTarget {
+            triple: "x86_64-unknown-linux-musl",
+            archive: ArchiveFormat::TarXz,
+        }),
+        (HostOs::Linux, HostArch::Aarch64, HostAbi::Musl) => Ok(ArtifactTarget {
+            triple: "aarch64-unknown-linux-musl",
+            archive: ArchiveFormat::TarXz,
+        }),
         (HostOs::MacOs, HostArch::X86_64, HostAbi::None) => Ok(ArtifactTarget {
             triple: "x86_64-apple-darwin",
             archive: ArchiveFormat::TarXz,
@@ -845,7 +865,7 @@ fn unsupported_target(host: HostSpecifier) -> SbhError {
 }
 
 fn supported_triples() -> &'static str {
-    "x86_64-unknown-linux-gnu, aarch64-unknown-linux-gnu, x86_64-apple-darwin, aarch64-apple-darwin, x86_64-pc-windows-msvc, aarch64-pc-windows-msvc"
+    "x86_64-unknown-linux-gnu, aarch64-unknown-linux-gnu, x86_64-unknown-linux-musl, aarch64-unknown-linux-musl, x86_64-apple-darwin, aarch64-apple-darwin, x86_64-pc-windows-msvc, aarch64-pc-windows-msvc"
 }
 
 impl fmt::Display for HostOs {
@@ -929,6 +949,24 @@ mod tests {
                 "aarch64-unknown-linux-gnu",
                 ArchiveFormat::TarXz,
             ),
+            (
+                HostSpecifier {
+                    os: HostOs::Linux,
+                    arch: HostArch::X86_64,
+                    abi: HostAbi::Musl,
+                },
+                "x86_64-unknown-linux-musl",
+                ArchiveFormat::TarXz,
+            ),
+            (
+                HostSpecifier {
+                    os: HostOs::Linux,
+                    arch: HostArch::Aarch64,
+                    abi: HostAbi::Musl,
+                },
+                "aarch64-unknown-linux-musl",
+                ArchiveFormat::TarXz,
+            ),
             (
                 HostSpecifier {
                     os: HostOs::MacOs,
@@ -1055,8 +1093,8 @@ mod tests {
     #[test]
     fn unsupported_targets_fail_with_actionable_remediation() {
         let host = HostSpecifier {
-            os: HostOs::Linux,
-            arch: HostArch::Aarch64,
+            os: HostOs::MacOs,
+            arch: HostArch::X86_64,
             abi: HostAbi::Musl,
         };
         let error =
@@ -1220,6 +1258,8 @@ mod tests {
             let (os, arch, abi) = match *triple {
                 "x86_64-unknown-linux-gnu" => ("linux", "x86_64", Some("gnu")),
                 "aarch64-unknown-linux-gnu" => ("linux", "aarch64", Some("gnu")),
+                "x86_64-unknown-linux-musl" => ("linux", "x86_64", Some("musl")),
+                "aarch64-unknown-linux-musl" => ("linux", "aarch64", Some("musl")),
                 "x86_64-apple-darwin" => ("macos", "x86_64", None),
                 "aarch64-apple-darwin" => ("macos", "aarch64", None),
                 other => panic!("unknown CI target: {other}"),
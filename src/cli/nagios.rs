@@ -0,0 +1,205 @@
+//! Nagios/Icinga plugin output for `sbh check --nagios`.
+//!
+//! The plugin contract is one stdout line, `SBH <STATUS> - message | perfdata`,
+//! and an exit code of 0/1/2/3 for OK/WARNING/CRITICAL/UNKNOWN. Thresholds
+//! are free-space floors, so the perfdata ranges use the `N:` form ("alert
+//! when below N").
+
+#![allow(missing_docs)]
+#![allow(clippy::cast_precision_loss)]
+
+use std::fmt::Write as _;
+
+/// Service name printed before the status.
+const SERVICE: &str = "SBH";
+
+/// Plugin result, ordered by severity (UNKNOWN sorts last but is only used
+/// when nothing could be measured).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginStatus {
+    Ok,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+impl PluginStatus {
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Measurements and thresholds for one checked mount.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckInput {
+    pub free_pct: f64,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    pub warning_pct: f64,
+    pub critical_pct: f64,
+    pub need_bytes: Option<u64>,
+    /// Daemon EWMA fill rate, when a fresh estimate exists.
+    pub rate_bps: Option<f64>,
+    /// Minutes until free space drops to the warning floor at `rate_bps`.
+    pub minutes_left: Option<f64>,
+    pub predict_minutes: Option<u64>,
+}
+
+/// Worst status across all checks, plus a reason for each failed one.
+#[must_use]
+pub fn evaluate(input: &CheckInput) -> (PluginStatus, Vec<String>) {
+    let mut status = PluginStatus::Ok;
+    let mut reasons = Vec::new();
+
+    if input.free_pct < input.critical_pct {
+        status = status.max(PluginStatus::Critical);
+        reasons.push(format!("below critical {}%", input.critical_pct));
+    } else if input.free_pct < input.warning_pct {
+        status = status.max(PluginStatus::Warning);
+        reasons.push(format!("below warning {}%", input.warning_pct));
+    }
+    if let Some(need) = input.need_bytes
+        && input.free_bytes < need
+    {
+        status = status.max(PluginStatus::Critical);
+        reasons.push(format!("{need} bytes required"));
+    }
+    if let (Some(minutes_left), Some(window)) = (input.minutes_left, input.predict_minutes)
+        && minutes_left < window as f64
+    {
+        status = status.max(PluginStatus::Warning);
+        reasons.push(format!(
+            "predicted below warning in {minutes_left:.0} min (window {window} min)"
+        ));
+    }
+    (status, reasons)
+}
+
+/// Performance data for `input`, in plugin `label=value[UOM];warn;crit;min;max` form.
+#[must_use]
+pub fn perfdata(input: &CheckInput) -> String {
+    let mut out = format!(
+        "free_pct={:.2}%;{}:;{}:;0;100 free={}B;;{};0;{}",
+        input.free_pct,
+        input.warning_pct,
+        input.critical_pct,
+        input.free_bytes,
+        input
+            .need_bytes
+            .map(|need| format!("{need}:"))
+            .unwrap_or_default(),
+        input.total_bytes,
+    );
+    if let Some(rate) = input.rate_bps {
+        let _ = write!(out, " fill_rate={rate:.0}B");
+    }
+    if let Some(minutes_left) = input.minutes_left {
+        let warn = input
+            .predict_minutes
+            .map(|window| format!("{window}:"))
+            .unwrap_or_default();
+        let _ = write!(out, " minutes_left={minutes_left:.0};{warn};;0;");
+    }
+    out
+}
+
+/// The single plugin output line.
+#[must_use]
+pub fn render_line(status: PluginStatus, message: &str, perfdata: &str) -> String {
+    if perfdata.is_empty() {
+        format!("{SERVICE} {} - {message}", status.label())
+    } else {
+        format!("{SERVICE} {} - {message} | {perfdata}", status.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(free_pct: f64) -> CheckInput {
+        CheckInput {
+            free_pct,
+            free_bytes: 40_000,
+            total_bytes: 100_000,
+            warning_pct: 14.0,
+            critical_pct: 6.0,
+            need_bytes: None,
+            rate_bps: None,
+            minutes_left: None,
+            predict_minutes: None,
+        }
+    }
+
+    #[test]
+    fn free_space_thresholds_map_to_plugin_statuses() {
+        assert_eq!(evaluate(&input(40.0)), (PluginStatus::Ok, Vec::new()));
+        assert_eq!(evaluate(&input(10.0)).0, PluginStatus::Warning);
+        assert_eq!(evaluate(&input(5.0)).0, PluginStatus::Critical);
+        assert_eq!(
+            evaluate(&input(5.0)).1,
+            vec!["below critical 6%".to_string()]
+        );
+    }
+
+    #[test]
+    fn unmet_need_is_critical_and_prediction_is_a_warning() {
+        let mut need = input(40.0);
+        need.need_bytes = Some(50_000);
+        assert_eq!(evaluate(&need).0, PluginStatus::Critical);
+
+        let mut filling = input(40.0);
+        filling.minutes_left = Some(20.0);
+        filling.predict_minutes = Some(60);
+        assert_eq!(evaluate(&filling).0, PluginStatus::Warning);
+        filling.predict_minutes = Some(10);
+        assert_eq!(evaluate(&filling).0, PluginStatus::Ok);
+
+        // Prediction never lowers a worse status.
+        let mut both = input(5.0);
+        both.minutes_left = Some(1.0);
+        both.predict_minutes = Some(60);
+        assert_eq!(evaluate(&both).0, PluginStatus::Critical);
+        assert_eq!(evaluate(&both).1.len(), 2);
+    }
+
+    #[test]
+    fn line_and_perfdata_follow_the_plugin_format() {
+        let mut check = input(40.0);
+        check.need_bytes = Some(10_000);
+        check.rate_bps = Some(512.4);
+        check.minutes_left = Some(90.2);
+        check.predict_minutes = Some(30);
+        let perf = perfdata(&check);
+        assert_eq!(
+            perf,
+            "free_pct=40.00%;14:;6:;0;100 free=40000B;;10000:;0;100000 \
+             fill_rate=512B minutes_left=90;30:;;0;"
+        );
+        assert_eq!(
+            render_line(PluginStatus::Ok, "/data 40.0% free", &perf),
+            format!("SBH OK - /data 40.0% free | {perf}")
+        );
+        assert_eq!(
+            render_line(PluginStatus::Unknown, "cannot stat /nope", ""),
+            "SBH UNKNOWN - cannot stat /nope"
+        );
+        assert_eq!(PluginStatus::Unknown.exit_code(), 3);
+    }
+}
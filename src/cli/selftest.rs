@@ -0,0 +1,491 @@
+//! `sbh selftest`: an end-to-end smoke test in a throwaway sandbox.
+//!
+//! Builds a small synthetic tree (Rust and Node projects, one project marked
+//! `.sbh-protect`) plus a two-file ballast pool, then drives the real
+//! pipeline over it: scan, score, plan, dry-run delete, and ballast
+//! provision/release/verify. Each stage reports pass, fail, or skip, so a
+//! fresh install or upgrade can be checked without touching real data.
+//!
+//! Everything runs on built-in defaults rather than the local config, so a
+//! result means the same thing on every machine. Directory ages cannot be
+//! backdated portably, so scoring uses [`SIMULATED_AGE`] instead of the
+//! entries' real (brand-new) timestamps, and [`SIMULATED_URGENCY`] because
+//! at zero pressure the decision layer rightly keeps everything this small.
+
+#![allow(missing_docs)]
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::ballast::manager::BallastManager;
+use crate::core::config::{BallastConfig, Config};
+use crate::scanner::deletion::{DeletionConfig, DeletionExecutor};
+use crate::scanner::patterns::ArtifactPatternRegistry;
+use crate::scanner::protection::{MARKER_FILENAME, ProtectionRegistry};
+use crate::scanner::scoring::{CandidacyScore, CandidateInput, DecisionAction, ScoringEngine};
+use crate::scanner::synthetic::{self, SyntheticTreeSpec};
+use crate::scanner::walker::{DirectoryWalker, WalkEntry, WalkerConfig};
+
+/// Age every sandbox entry is scored at: old enough to clear the default
+/// minimum age and count as abandoned.
+pub const SIMULATED_AGE: Duration = Duration::from_hours(7 * 24);
+
+/// Pressure urgency the sandbox is scored under: the boost the PID
+/// controller applies when red is forecast within five minutes.
+pub const SIMULATED_URGENCY: f64 = 0.9;
+
+/// Ballast mini-pool: small enough to be instant, large enough to exercise
+/// the header and payload paths.
+const BALLAST_FILES: usize = 2;
+const BALLAST_FILE_BYTES: u64 = 1024 * 1024;
+
+const PROTECTED_PROJECT: &str = "protected";
+
+/// Outcome of one stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StageStatus {
+    Pass,
+    Fail,
+    /// Not run because an earlier stage it depends on failed.
+    Skip,
+}
+
+impl StageStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StageResult {
+    pub name: &'static str,
+    pub status: StageStatus,
+    pub detail: String,
+    pub elapsed: Duration,
+}
+
+/// All stages, in the order they ran.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelftestReport {
+    pub sandbox: PathBuf,
+    pub stages: Vec<StageResult>,
+}
+
+impl SelftestReport {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| stage.status == StageStatus::Pass)
+    }
+}
+
+/// Where `sbh selftest` builds its sandbox: tmpfs when available, with the
+/// PID in the name so concurrent runs never share one.
+#[must_use]
+pub fn default_sandbox() -> PathBuf {
+    synthetic::default_base_dir().join(format!("sbh-selftest-{}", std::process::id()))
+}
+
+/// Run every stage inside `sandbox`, which must not exist yet.
+///
+/// The sandbox is left in place; callers remove it (or keep it for
+/// inspection). A failing stage is reported, not returned as an error, and
+/// every stage that needs its output is skipped.
+pub fn run(sandbox: &Path) -> SelftestReport {
+    let config = Config::default();
+    let mut stages = Stages::default();
+
+    let expected = stages.run("sandbox", Some(()), |()| build_sandbox(sandbox));
+    let entries = stages.run("scan", expected.as_ref(), |_| scan(sandbox, &config));
+    let scored = stages.run(
+        "score",
+        entries.as_ref().zip(expected.as_ref()),
+        |(entries, expected)| score(entries, &config, expected),
+    );
+    let planned = stages.run(
+        "plan",
+        scored.as_ref().zip(expected.as_ref()),
+        |(scored, expected)| plan(scored, &config, expected),
+    );
+    stages.run(
+        "dry-run delete",
+        scored.as_ref().zip(planned.as_ref()),
+        |(scored, planned)| dry_run_delete(scored, &config, planned),
+    );
+    stages.run("ballast", expected.as_ref(), |_| {
+        ballast(&sandbox.join("ballast"))
+    });
+
+    SelftestReport {
+        sandbox: sandbox.to_path_buf(),
+        stages: stages.0,
+    }
+}
+
+#[derive(Default)]
+struct Stages(Vec<StageResult>);
+
+impl Stages {
+    /// Time `stage` on `input`, or record a skip when an earlier stage did
+    /// not produce that input.
+    fn run<I, T>(
+        &mut self,
+        name: &'static str,
+        input: Option<I>,
+        stage: impl FnOnce(I) -> Result<(T, String), String>,
+    ) -> Option<T> {
+        let Some(input) = input else {
+            self.0.push(StageResult {
+                name,
+                status: StageStatus::Skip,
+                detail: "an earlier stage failed".to_string(),
+                elapsed: Duration::ZERO,
+            });
+            return None;
+        };
+        let started = Instant::now();
+        let (status, detail, output) = match stage(input) {
+            Ok((output, detail)) => (StageStatus::Pass, detail, Some(output)),
+            Err(detail) => (StageStatus::Fail, detail, None),
+        };
+        self.0.push(StageResult {
+            name,
+            status,
+            detail,
+            elapsed: started.elapsed(),
+        });
+        output
+    }
+}
+
+/// Lay out the fixture tree and return the artifact dirs a correct pipeline
+/// must plan: every `target/` and `node_modules/` outside the protected
+/// project. `src/` trees and manifests are the decoys.
+fn build_sandbox(sandbox: &Path) -> Result<(BTreeSet<PathBuf>, String), String> {
+    if sandbox.exists() {
+        return Err(format!("{} already exists", sandbox.display()));
+    }
+    let spec = SyntheticTreeSpec {
+        seed: 0x5e1f_7e57,
+        projects: 3,
+        target_probability: 1.0,
+        node_modules_probability: 1.0,
+        pycache_probability: 0.0,
+        max_files_per_dir: 4,
+        max_file_bytes: 256 * 1024,
+        max_junk_files: 0,
+    };
+    let projects = sandbox.join("projects");
+    synthetic::generate(&projects, &spec).map_err(|e| e.to_string())?;
+    let protected = projects.join(PROTECTED_PROJECT);
+    synthetic::generate(
+        &protected,
+        &SyntheticTreeSpec {
+            projects: 1,
+            ..spec
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    fs::write(protected.join(MARKER_FILENAME), "")
+        .map_err(|e| format!("{}: {e}", protected.display()))?;
+
+    let expected: BTreeSet<PathBuf> = (0..spec.projects)
+        .map(|p| projects.join(format!("project-{p:04}")))
+        .flat_map(|project| [project.join("target"), project.join("node_modules")])
+        .collect();
+    let detail = format!(
+        "{} projects, {} artifact dirs, 1 protected project at {}",
+        spec.projects,
+        expected.len(),
+        sandbox.display()
+    );
+    Ok((expected, detail))
+}
+
+fn scan(sandbox: &Path, config: &Config) -> Result<(Vec<WalkEntry>, String), String> {
+    let root = sandbox.join("projects");
+    let protection = ProtectionRegistry::new(None).map_err(|e| e.to_string())?;
+    let walker = DirectoryWalker::new(
+        WalkerConfig {
+            root_paths: vec![root.clone()],
+            max_depth: config.scanner.max_depth,
+            follow_symlinks: false,
+            cross_devices: false,
+            parallelism: config.scanner.parallelism,
+            excluded_paths: std::collections::HashSet::new(),
+        },
+        protection,
+    )
+    .with_artifact_roots(config.scanner.artifact_root_sampling());
+    let entries = walker.walk().map_err(|e| e.to_string())?;
+    if entries.is_empty() {
+        return Err(format!("walked nothing under {}", root.display()));
+    }
+    let protected = root.join(PROTECTED_PROJECT);
+    if let Some(leak) = entries.iter().find(|e| e.path.starts_with(&protected)) {
+        return Err(format!(
+            "protected project was walked: {}",
+            leak.path.display()
+        ));
+    }
+    let detail = format!(
+        "walked {} entries; protected project skipped",
+        entries.len()
+    );
+    Ok((entries, detail))
+}
+
+fn score(
+    entries: &[WalkEntry],
+    config: &Config,
+    expected: &BTreeSet<PathBuf>,
+) -> Result<(Vec<CandidacyScore>, String), String> {
+    let registry = ArtifactPatternRegistry::default();
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let scored: Vec<CandidacyScore> = entries
+        .iter()
+        .map(|entry| {
+            let classification = registry.classify_entry(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.structural_signals,
+            );
+            engine.score_candidate(
+                &CandidateInput {
+                    path: entry.path.clone(),
+                    size_bytes: entry.metadata.content_size_bytes,
+                    disk_bytes: entry.metadata.content_disk_bytes,
+                    age: SIMULATED_AGE,
+                    classification,
+                    signals: entry.structural_signals,
+                    is_open: false,
+                    excluded: false,
+                },
+                SIMULATED_URGENCY,
+            )
+        })
+        .collect();
+
+    // Same gate `DeletionExecutor::plan` applies.
+    let eligible = |c: &CandidacyScore| {
+        c.decision.action == DecisionAction::Delete
+            && !c.vetoed
+            && c.total_score >= config.scoring.min_score
+    };
+    for path in expected {
+        let Some(candidate) = scored.iter().find(|c| &c.path == path) else {
+            return Err(format!("{} was not scored", path.display()));
+        };
+        if !eligible(candidate) {
+            return Err(format!(
+                "{} not eligible: score {:.2} (min {:.2}), decision {:?}{}",
+                path.display(),
+                candidate.total_score,
+                config.scoring.min_score,
+                candidate.decision.action,
+                candidate
+                    .veto_reason
+                    .as_deref()
+                    .map_or_else(String::new, |reason| format!(", vetoed: {reason}")),
+            ));
+        }
+    }
+    if let Some(decoy) = scored
+        .iter()
+        .find(|c| c.path.file_name().is_some_and(|name| name == "src") && eligible(c))
+    {
+        return Err(format!(
+            "source dir {} would be deleted (score {:.2})",
+            decoy.path.display(),
+            decoy.total_score
+        ));
+    }
+
+    let (low, high) = expected
+        .iter()
+        .filter_map(|path| scored.iter().find(|c| &c.path == path))
+        .fold((f64::MAX, f64::MIN), |(low, high), c| {
+            (low.min(c.total_score), high.max(c.total_score))
+        });
+    let detail = format!(
+        "{} entries scored; artifact dirs {low:.2}-{high:.2}, source dirs kept",
+        scored.len()
+    );
+    Ok((scored, detail))
+}
+
+const fn deletion_config(config: &Config, dry_run: bool) -> DeletionConfig {
+    DeletionConfig {
+        max_batch_size: usize::MAX,
+        dry_run,
+        min_score: config.scoring.min_score,
+        circuit_breaker_threshold: 3,
+        circuit_breaker_cooldown: Duration::ZERO,
+        // The sandbox is private to this process, and the /proc scan can
+        // abort a batch under load, which would fail the stage spuriously.
+        check_open_files: false,
+        truncate_categories: Vec::new(),
+        relocation: None,
+        quarantine_ttl: None,
+        quarantine_offload: None,
+        io_throttle: None,
+        workers: 1,
+    }
+}
+
+fn plan(
+    candidates: &[CandidacyScore],
+    config: &Config,
+    expected: &BTreeSet<PathBuf>,
+) -> Result<(Vec<PathBuf>, String), String> {
+    let executor = DeletionExecutor::new(deletion_config(config, true), None);
+    let plan = executor.plan(candidates.to_vec());
+    let planned: BTreeSet<PathBuf> = plan.candidates.iter().map(|c| c.path.clone()).collect();
+    if &planned != expected {
+        let missing: Vec<_> = expected.difference(&planned).collect();
+        let extra: Vec<_> = planned.difference(expected).collect();
+        return Err(format!(
+            "plan mismatch: missing {missing:?}, unexpected {extra:?}"
+        ));
+    }
+    let detail = format!(
+        "{} dirs, {} bytes; {} nested candidates folded into their parents",
+        plan.candidates.len(),
+        plan.total_reclaimable_bytes,
+        plan.subsumed.len()
+    );
+    Ok((planned.into_iter().collect(), detail))
+}
+
+fn dry_run_delete(
+    candidates: &[CandidacyScore],
+    config: &Config,
+    planned: &[PathBuf],
+) -> Result<((), String), String> {
+    let executor = DeletionExecutor::new(deletion_config(config, true), None);
+    let plan = executor.plan(candidates.to_vec());
+    let report = executor.execute(&plan, None);
+    if let Some(error) = report.errors.first() {
+        return Err(format!("{}: {}", error.path.display(), error.error));
+    }
+    if report.items_deleted != planned.len() {
+        return Err(format!(
+            "dry run counted {} deletions, plan has {}",
+            report.items_deleted,
+            planned.len()
+        ));
+    }
+    if let Some(gone) = planned.iter().find(|path| !path.exists()) {
+        return Err(format!("dry run removed {}", gone.display()));
+    }
+    let detail = format!(
+        "{} dirs would free {} bytes; all still present",
+        report.items_deleted, report.bytes_freed
+    );
+    Ok(((), detail))
+}
+
+fn ballast(dir: &Path) -> Result<((), String), String> {
+    let config = BallastConfig {
+        file_count: BALLAST_FILES,
+        file_size_bytes: BALLAST_FILE_BYTES,
+        ..BallastConfig::default()
+    };
+    let mut manager = BallastManager::new(dir.to_path_buf(), config).map_err(|e| e.to_string())?;
+
+    let provisioned = manager.provision(None).map_err(|e| e.to_string())?;
+    if let Some(error) = provisioned.errors.first() {
+        return Err(format!("provision: {error}"));
+    }
+    let verified = manager.verify().map_err(|e| e.to_string())?;
+    if verified.files_ok != BALLAST_FILES {
+        return Err(format!(
+            "verify after provision: {} of {BALLAST_FILES} ok ({})",
+            verified.files_ok,
+            verified.details.join("; ")
+        ));
+    }
+
+    let released = manager.release(1).map_err(|e| e.to_string())?;
+    if released.files_released != 1 {
+        return Err(format!("release: {}", released.errors.join("; ")));
+    }
+    let verified = manager.verify().map_err(|e| e.to_string())?;
+    if verified.files_ok != BALLAST_FILES - 1 || verified.files_missing != 1 {
+        return Err(format!(
+            "verify after release: {} ok, {} missing",
+            verified.files_ok, verified.files_missing
+        ));
+    }
+
+    let replenished = manager.provision(None).map_err(|e| e.to_string())?;
+    let verified = manager.verify().map_err(|e| e.to_string())?;
+    if replenished.files_created != 1 || verified.files_ok != BALLAST_FILES {
+        return Err(format!(
+            "replenish: created {}, {} of {BALLAST_FILES} ok",
+            replenished.files_created, verified.files_ok
+        ));
+    }
+    let detail = format!(
+        "{BALLAST_FILES} x {} KiB provisioned, released 1 ({} bytes), replenished, verified",
+        BALLAST_FILE_BYTES / 1024,
+        released.bytes_freed
+    );
+    Ok(((), detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_stage_passes_in_a_fresh_sandbox() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = run(&dir.path().join("sandbox"));
+        let names: Vec<&str> = report.stages.iter().map(|stage| stage.name).collect();
+        assert_eq!(
+            names,
+            [
+                "sandbox",
+                "scan",
+                "score",
+                "plan",
+                "dry-run delete",
+                "ballast"
+            ]
+        );
+        for stage in &report.stages {
+            assert_eq!(
+                stage.status,
+                StageStatus::Pass,
+                "{}: {}",
+                stage.name,
+                stage.detail
+            );
+        }
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn existing_sandbox_fails_and_skips_the_pipeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = run(dir.path());
+        let statuses: Vec<StageStatus> = report.stages.iter().map(|stage| stage.status).collect();
+        assert_eq!(statuses[0], StageStatus::Fail);
+        assert!(statuses[1..].iter().all(|s| *s == StageStatus::Skip));
+        assert_eq!(statuses.len(), 6);
+        assert!(!report.passed());
+    }
+}
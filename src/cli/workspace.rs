@@ -0,0 +1,256 @@
+//! Agent workspaces with explicit ownership: `sbh workspace create/destroy`.
+//!
+//! A swarm orchestrator creates each agent's working directory through sbh,
+//! which drops a `.sbh-workspace` lease recording the owner, an optional
+//! byte budget, and when the lease expires. Destroying a workspace skips
+//! scoring entirely (the lease is the proof of ownership) but still refuses
+//! while any live process has its cwd or an open file inside the tree, or
+//! when a `.sbh-protect` marker sits anywhere in it.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+use crate::scanner::protection::MARKER_FILENAME;
+
+/// Lease file written at the workspace root.
+pub const LEASE_FILENAME: &str = ".sbh-workspace";
+
+/// Ownership and budget attached to a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceLease {
+    /// Agent or job that owns the workspace.
+    pub owner: String,
+    /// RFC 3339 creation time.
+    pub created_at: String,
+    /// RFC 3339 lease expiry; `None` means the lease never expires.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Bytes the workspace is expected to stay under.
+    #[serde(default)]
+    pub budget_bytes: Option<u64>,
+}
+
+impl WorkspaceLease {
+    /// Whether the lease expired before `now`. Unparseable expiries count as
+    /// expired so a corrupt lease never pins a workspace forever.
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.as_deref().is_some_and(|expiry| {
+            DateTime::parse_from_rfc3339(expiry)
+                .ok()
+                .is_none_or(|expiry| expiry < now)
+        })
+    }
+
+    /// Bytes by which `used_bytes` exceeds the budget (0 when within it or
+    /// when no budget was set).
+    #[must_use]
+    pub fn overrun_bytes(&self, used_bytes: u64) -> u64 {
+        self.budget_bytes
+            .map_or(0, |budget| used_bytes.saturating_sub(budget))
+    }
+}
+
+/// A process still using a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LiveProcess {
+    pub pid: u32,
+    pub name: String,
+}
+
+/// Create `path` (and parents) and write its lease.
+///
+/// Refuses an existing non-empty directory, leased or not: adopting it
+/// would let `destroy` delete files sbh never owned.
+pub fn create_workspace(path: &Path, lease: &WorkspaceLease) -> Result<()> {
+    if path.exists() {
+        let mut entries = fs::read_dir(path).map_err(|source| SbhError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        if entries.next().is_some() {
+            return Err(SbhError::SafetyVeto {
+                path: path.to_path_buf(),
+                reason: "directory already exists and is not empty".to_string(),
+            });
+        }
+    }
+    fs::create_dir_all(path).map_err(|source| SbhError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let lease_path = path.join(LEASE_FILENAME);
+    fs::write(&lease_path, serde_json::to_string_pretty(lease)?).map_err(|source| SbhError::Io {
+        path: lease_path,
+        source,
+    })
+}
+
+/// Read the lease at the workspace root; `Ok(None)` when there is none.
+pub fn read_lease(path: &Path) -> Result<Option<WorkspaceLease>> {
+    let lease_path = path.join(LEASE_FILENAME);
+    match fs::read_to_string(&lease_path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|e| SbhError::Runtime {
+                details: format!("invalid lease {}: {e}", lease_path.display()),
+            }),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(SbhError::Io {
+            path: lease_path,
+            source,
+        }),
+    }
+}
+
+/// First `.sbh-protect` marker found inside the workspace, if any.
+#[must_use]
+pub fn find_protected_dir(path: &Path) -> Option<PathBuf> {
+    if path.join(MARKER_FILENAME).exists() {
+        return Some(path.to_path_buf());
+    }
+    fs::read_dir(path).ok()?.flatten().find_map(|entry| {
+        entry
+            .file_type()
+            .is_ok_and(|kind| kind.is_dir())
+            .then(|| find_protected_dir(&entry.path()))
+            .flatten()
+    })
+}
+
+/// Processes whose cwd, root, or any open file lies inside `path`.
+///
+/// On Linux this scans /proc; elsewhere it returns nothing.
+#[must_use]
+pub fn live_processes(path: &Path) -> Vec<LiveProcess> {
+    #[cfg(target_os = "linux")]
+    {
+        let root = crate::core::paths::resolve_absolute_path(path);
+        live_processes_linux(&root)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn live_processes_linux(root: &Path) -> Vec<LiveProcess> {
+    let Ok(proc_dir) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let inside = |link: PathBuf| link.starts_with(root);
+
+    let mut live = Vec::new();
+    for entry in proc_dir.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let proc_path = entry.path();
+        let uses_root = ["cwd", "root"]
+            .iter()
+            .filter_map(|link| fs::read_link(proc_path.join(link)).ok())
+            .any(inside)
+            || fs::read_dir(proc_path.join("fd")).is_ok_and(|fds| {
+                fds.flatten()
+                    .filter_map(|fd| fs::read_link(fd.path()).ok())
+                    .any(inside)
+            });
+        if uses_root {
+            let name = fs::read_to_string(proc_path.join("comm"))
+                .map(|comm| comm.trim().to_string())
+                .unwrap_or_default();
+            live.push(LiveProcess { pid, name });
+        }
+    }
+    live.sort_by_key(|process| process.pid);
+    live
+}
+
+/// Remove the whole workspace tree.
+pub fn remove_workspace(path: &Path) -> Result<()> {
+    fs::remove_dir_all(path).map_err(|source| SbhError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lease(expires_at: Option<&str>, budget_bytes: Option<u64>) -> WorkspaceLease {
+        WorkspaceLease {
+            owner: "agent-7".to_string(),
+            created_at: "2026-10-16T09:00:00.000Z".to_string(),
+            expires_at: expires_at.map(str::to_string),
+            budget_bytes,
+        }
+    }
+
+    #[test]
+    fn create_writes_lease_and_refuses_foreign_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = tmp.path().join("swarm/agent-7");
+        let expected = lease(None, Some(1 << 30));
+        create_workspace(&ws, &expected).unwrap();
+        assert_eq!(read_lease(&ws).unwrap(), Some(expected.clone()));
+
+        // Leased and non-empty: a second create must not adopt it.
+        let err = create_workspace(&ws, &expected).unwrap_err();
+        assert!(matches!(err, SbhError::SafetyVeto { .. }));
+
+        let foreign = tmp.path().join("foreign");
+        fs::create_dir(&foreign).unwrap();
+        fs::write(foreign.join("notes.txt"), "mine").unwrap();
+        assert!(create_workspace(&foreign, &expected).is_err());
+        assert_eq!(read_lease(&foreign).unwrap(), None);
+    }
+
+    #[test]
+    fn lease_expiry_and_budget_overrun() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(!lease(None, None).is_expired(now));
+        assert!(!lease(Some("2026-10-16T13:00:00Z"), None).is_expired(now));
+        assert!(lease(Some("2026-10-16T11:00:00Z"), None).is_expired(now));
+        assert!(lease(Some("tomorrow"), None).is_expired(now));
+
+        assert_eq!(lease(None, None).overrun_bytes(5_000), 0);
+        assert_eq!(lease(None, Some(4_000)).overrun_bytes(5_000), 1_000);
+        assert_eq!(lease(None, Some(6_000)).overrun_bytes(5_000), 0);
+    }
+
+    #[test]
+    fn protection_markers_anywhere_in_the_tree_are_found() {
+        let tmp = tempfile::tempdir().unwrap();
+        let nested = tmp.path().join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_protected_dir(tmp.path()), None);
+        fs::write(nested.join(MARKER_FILENAME), "").unwrap();
+        assert_eq!(find_protected_dir(tmp.path()), Some(nested));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn open_file_inside_workspace_marks_this_process_live() {
+        let tmp = tempfile::tempdir().unwrap();
+        let held = fs::File::create(tmp.path().join("held.log")).unwrap();
+        let me = std::process::id();
+        assert!(live_processes(tmp.path()).iter().any(|p| p.pid == me));
+        drop(held);
+    }
+}
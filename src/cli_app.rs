@@ -1,36 +1,100 @@
 //! Top-level CLI definition and dispatch.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::env::Shells as DynamicShells;
 use clap_complete::{Shell as CompletionShell, generate};
 use colored::control;
 use serde::Serialize;
 use serde_json::{Value, json};
 use thiserror::Error;
 
+use storage_ballast_helper::ballast::coordinator::BALLAST_SUBDIR;
 use storage_ballast_helper::ballast::manager::BallastManager;
-use storage_ballast_helper::core::config::Config;
+use storage_ballast_helper::cli::check_policy::find_check_policy;
+use storage_ballast_helper::cli::clean_plan::{PlanEntry, PlanFile};
+use storage_ballast_helper::cli::config_transfer::{
+    ConfigChange, ConfigChangeKind, merge_import, strip_host_specific, three_way_diff,
+};
+use storage_ballast_helper::cli::nagios::PluginStatus;
+use storage_ballast_helper::core::config::{Config, PressureConfig};
+use storage_ballast_helper::core::container;
+use storage_ballast_helper::core::maintenance::{
+    MaintenanceWindow, active_maintenance, clear_maintenance, maintenance_path, write_maintenance,
+};
+use storage_ballast_helper::core::probation::{
+    ConfigProbation, DEFAULT_REVERT_WINDOW_MINUTES, is_enforcement_key, probation_path,
+    read_probation, write_probation,
+};
+use storage_ballast_helper::core::state::{
+    DAEMON_STATE_STALE_THRESHOLD_SECS, DaemonState, MountKey, StateParseError, StateReadError,
+    StateSnapshot, read_state,
+};
+use storage_ballast_helper::daemon::control::{
+    self as control_socket, ControlReply, ControlRequest,
+};
 use storage_ballast_helper::daemon::loop_main::{
     DaemonArgs as RuntimeDaemonArgs, MonitoringDaemon,
 };
-use storage_ballast_helper::daemon::self_monitor::DAEMON_STATE_STALE_THRESHOLD_SECS;
+use storage_ballast_helper::daemon::notifications::{
+    EVENT_TYPES, EventKey, NotificationLevel, NotificationManager, RouteOutcome,
+};
+use storage_ballast_helper::daemon::policy::{BurnIn, burn_in_path, read_burn_in};
 use storage_ballast_helper::daemon::service::{
-    LaunchdServiceManager, ServiceActionResult, SystemdServiceManager,
+    LaunchdServiceManager, ScheduledTaskServiceManager, ServiceActionResult, SystemdServiceManager,
+};
+use storage_ballast_helper::logger::audit::{
+    AuditLog, DeletionManifest, ManifestAction, ManifestItem,
+};
+use storage_ballast_helper::logger::diag::{self, DiagFormat, DiagLevel, DiagTarget};
+use storage_ballast_helper::logger::dual::ActivityEvent;
+use storage_ballast_helper::logger::sqlite::{ActivityRow, SqliteLogger};
+use storage_ballast_helper::logger::stats::{
+    StatsEngine, StatsGroupBy, UserStat, categories_json, window_label,
 };
-use storage_ballast_helper::logger::sqlite::SqliteLogger;
-use storage_ballast_helper::logger::stats::{StatsEngine, window_label};
+use storage_ballast_helper::monitor::disk_health::{self, DiskHealth};
 use storage_ballast_helper::monitor::fs_stats::FsStatsCollector;
-use storage_ballast_helper::platform::pal::{MemoryInfo, ServiceManager, detect_platform};
-use storage_ballast_helper::scanner::deletion::{DeletionConfig, DeletionExecutor, DeletionPlan};
-use storage_ballast_helper::scanner::patterns::ArtifactPatternRegistry;
-use storage_ballast_helper::scanner::protection::{self, ProtectionRegistry};
-use storage_ballast_helper::scanner::scoring::{CandidacyScore, CandidateInput, ScoringEngine};
+use storage_ballast_helper::monitor::mount_watch::{MountWatcher, classify_mount};
+use storage_ballast_helper::monitor::writers::{
+    AttributionSnapshot, ProcessIo, WriterRate, blame_processes, collect_processes,
+    compute_writer_rates,
+};
+use storage_ballast_helper::platform::pal::{
+    FsStats, MemoryInfo, MountPoint, Platform, ServiceManager, detect_platform,
+};
+use storage_ballast_helper::scanner::decision_record::{
+    ActionRecord, DecisionRecord, DecisionRecordBuilder, ExplainLevel, PolicyMode, format_explain,
+};
+use storage_ballast_helper::scanner::deletion::{
+    AuditContext, DeletionConfig, DeletionExecutor, DeletionPlan, ItemObserver, ItemOutcome,
+    ReclaimVerification, SkipReason, verify_reclaim,
+};
+use storage_ballast_helper::scanner::object_store::ObjectStore;
+use storage_ballast_helper::scanner::orphaned_fds::{self, OrphanRemedy};
+use storage_ballast_helper::scanner::patterns::{
+    ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry, StructuralSignals,
+};
+use storage_ballast_helper::scanner::preview::ContentPreview;
+use storage_ballast_helper::scanner::protection::{
+    self, CoveredProtection, GlobPattern, ProtectionCoverage, ProtectionRegistry,
+};
+use storage_ballast_helper::scanner::quarantine::{self, QuarantineEntry};
+use storage_ballast_helper::scanner::scoring::{
+    CandidacyScore, CandidateInput, SCORING_ENGINE_VERSION, ScoringEngine, ScoringInputs,
+    TopCandidates,
+};
+use storage_ballast_helper::scanner::synthetic::{self, SyntheticTreeSpec};
+use storage_ballast_helper::scanner::tool_caches::{
+    self, CacheAction, CacheCleanOutcome, CacheRoots,
+};
 use storage_ballast_helper::scanner::walker::{
-    DirectoryWalker, WalkerConfig, collect_open_path_ancestors, is_path_open_by_ancestor,
+    DirectoryWalker, RootStatus, RootWalkReport, StatMode, SyscallCounts, SyscallProfile,
+    TreeWatcher, WalkEntry, WalkerConfig, collect_open_path_ancestors, is_path_open_by_ancestor,
 };
 
 const LIVE_REFRESH_MIN_MS: u64 = 100;
@@ -45,6 +109,7 @@ const STATUS_WATCH_REFRESH_MS: u64 = 1_000;
     about = "Storage Ballast Helper - Disk Space Guardian",
     long_about = None,
     arg_required_else_help = true,
+    disable_help_subcommand = true,
     max_term_width = 100
 )]
 #[allow(clippy::struct_excessive_bools)]
@@ -64,6 +129,19 @@ pub struct Cli {
     /// Quiet mode (errors only).
     #[arg(short, long, global = true, conflicts_with = "verbose")]
     quiet: bool,
+    /// Internal diagnostic level for this run: error, warn, info, debug, trace.
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<DiagLevel>,
+    /// Write internal diagnostics to this file instead of stderr.
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+    /// Diagnostic line format: human or json.
+    #[arg(long, global = true, value_name = "FORMAT")]
+    log_format: Option<DiagFormat>,
+    /// Container defaults: /etc/sbh config, /var/lib/sbh data, watch /data,
+    /// no service manager. Also set by `SBH_CONTAINER=1`.
+    #[arg(long, global = true)]
+    container: bool,
     /// Subcommand to execute.
     #[command(subcommand)]
     command: Command,
@@ -114,6 +192,43 @@ enum Command {
     Setup(SetupArgs),
     /// View activity log entries.
     Log(LogArgs),
+    /// Show help for a command, or a long-form guide topic.
+    Help(HelpArgs),
+    /// Generate roff man pages.
+    Man(ManArgs),
+    /// Print the process exit-code contract.
+    ExitCodes,
+    /// Measure scan performance on real or synthetic trees.
+    Bench(BenchArgs),
+    /// CI runner integration: job summaries and low-space annotations.
+    Ci(CiArgs),
+    /// Entry points for runner lifecycle hooks (GitLab, Buildkite, Jenkins).
+    RunnerHook(RunnerHookArgs),
+    /// Create and destroy leased agent workspaces.
+    Workspace(WorkspaceArgs),
+    /// Export metrics and monitoring dashboards.
+    Export(ExportArgs),
+    /// List or restore artifacts moved to the cold-storage archive.
+    Restore(RestoreArgs),
+    /// List, restore, or purge artifacts staged by `clean --quarantine`.
+    Quarantine(QuarantineArgs),
+    /// Review the manifest written before every deletion batch.
+    Audit(AuditArgs),
+    /// Inspect notification routing rules.
+    Notify(NotifyArgs),
+    /// Add an operator note to the activity timeline.
+    Annotate(AnnotateArgs),
+    /// Pause all enforcement for a bounded maintenance window.
+    Pause(PauseArgs),
+    /// End a maintenance window early and resume enforcement.
+    Resume,
+    /// Smoke-test scan, scoring, deletion, and ballast in a throwaway sandbox.
+    Selftest(SelftestArgs),
+    /// Diagnose the installation and environment, with a remediation checklist.
+    Doctor,
+    /// Classify and score one path, showing why it is or is not a candidate.
+    #[command(visible_alias = "explain-path")]
+    Explain(ExplainArgs),
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
@@ -127,17 +242,27 @@ struct DaemonArgs {
     /// Systemd watchdog timeout in seconds (0 disables).
     #[arg(long, default_value_t = 0, value_name = "SECONDS")]
     watchdog_sec: u64,
+    /// Run one loop iteration (stats, prediction, policy, enforcement),
+    /// wait for the scan and deletions it queued, print a summary, and exit.
+    #[arg(long, conflicts_with = "background")]
+    once: bool,
+    /// With --once, score and log candidates without deleting anything.
+    #[arg(long, requires = "once")]
+    dry_run: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
 #[allow(clippy::struct_excessive_bools)]
 struct InstallArgs {
     /// Install systemd service units (Linux).
-    #[arg(long, conflicts_with = "launchd")]
+    #[arg(long, conflicts_with_all = ["launchd", "scheduled_task"])]
     systemd: bool,
     /// Install launchd service plist (macOS).
-    #[arg(long, conflicts_with = "systemd")]
+    #[arg(long, conflicts_with_all = ["systemd", "scheduled_task"])]
     launchd: bool,
+    /// Register a Task Scheduler task that runs the daemon (Windows).
+    #[arg(long, conflicts_with_all = ["systemd", "launchd"])]
+    scheduled_task: bool,
     /// Install in user service scope.
     #[arg(long)]
     user: bool,
@@ -174,13 +299,17 @@ struct InstallArgs {
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
+#[allow(clippy::struct_excessive_bools)]
 struct UninstallArgs {
     /// Remove systemd service units (Linux).
-    #[arg(long, conflicts_with = "launchd")]
+    #[arg(long, conflicts_with_all = ["launchd", "scheduled_task"])]
     systemd: bool,
     /// Remove launchd service plist (macOS).
-    #[arg(long, conflicts_with = "systemd")]
+    #[arg(long, conflicts_with_all = ["systemd", "scheduled_task"])]
     launchd: bool,
+    /// Remove the daemon's Task Scheduler task (Windows).
+    #[arg(long, conflicts_with_all = ["systemd", "launchd"])]
+    scheduled_task: bool,
     /// Remove all generated state and logs.
     #[arg(long)]
     purge: bool,
@@ -191,6 +320,12 @@ struct StatusArgs {
     /// Continuously refresh status output.
     #[arg(long)]
     watch: bool,
+    /// Print low-level discovery of monitored mounts for a monitoring system (zabbix).
+    #[arg(long, value_name = "SYSTEM", conflicts_with_all = ["watch", "telegraf"])]
+    discovery: Option<String>,
+    /// Print flat metric records for Telegraf's exec input (data_format = "json").
+    #[arg(long, conflicts_with = "watch")]
+    telegraf: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
@@ -207,9 +342,16 @@ struct StatsArgs {
     /// Show pressure level timeline.
     #[arg(long)]
     pressure_history: bool,
+    /// Show per-user bytes reclaimed, write contribution, and protections.
+    #[arg(long)]
+    by_user: bool,
+    /// Split the window into buckets: day, hour, category, or mount.
+    #[arg(long, value_name = "KEY")]
+    group_by: Option<StatsGroupBy>,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
+#[allow(clippy::struct_excessive_bools)]
 struct ScanArgs {
     /// Paths to scan (falls back to configured watched paths when omitted).
     #[arg(value_name = "PATH")]
@@ -220,16 +362,69 @@ struct ScanArgs {
     /// Minimum score to include in output.
     #[arg(long, default_value_t = 0.7, value_name = "SCORE")]
     min_score: f64,
+    /// Only report candidates at least this old (e.g. `7d`, `12h`).
+    #[arg(long, value_name = "AGE")]
+    older_than: Option<String>,
+    /// Only report candidates younger than this (e.g. `1h`, `30d`).
+    #[arg(long, value_name = "AGE")]
+    newer_than: Option<String>,
+    /// Only report candidates matching GLOB or inside a match (repeatable).
+    /// A pattern not starting with `/` or `**` matches at any depth.
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+    /// Leave out candidates matching GLOB, inside a match, or containing
+    /// one (repeatable).
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
     /// Include protected paths in output report.
     #[arg(long)]
     show_protected: bool,
+    /// Per-root walk time limit; a root that exceeds it is reported partial.
+    #[arg(long, value_name = "SECONDS")]
+    root_timeout: Option<u64>,
+    /// Debug: walk once more in portable stat mode and report metadata
+    /// syscalls per 1k entries for portable vs reduced mode.
+    #[arg(long, hide_short_help = true)]
+    profile_syscalls: bool,
+    /// Report unlinked files still held open on the scanned mounts, grouped
+    /// by holding process, instead of scoring artifacts.
+    #[arg(long, conflicts_with_all = ["older_than", "newer_than", "include", "exclude"])]
+    orphaned_fds: bool,
+    /// With `--orphaned-fds`: empty held log and core-dump files in place
+    /// through the holder's descriptor.
+    #[arg(long, requires = "orphaned_fds")]
+    truncate: bool,
+    /// Skip the `--truncate` confirmation prompt.
+    #[arg(long, requires = "truncate")]
+    yes: bool,
+    /// Ask the running daemon to scan its roots now (like SIGUSR1) instead
+    /// of printing a report; it may then clean what it finds. Without a
+    /// daemon the local report runs as usual.
+    #[arg(long, conflicts_with_all = [
+        "paths", "orphaned_fds", "profile_syscalls", "older_than", "newer_than", "include",
+        "exclude",
+    ])]
+    daemon: bool,
+    /// After the report, keep watching the roots (inotify/FSEvents) and
+    /// report artifacts as they appear or disappear, until interrupted.
+    #[arg(long, conflicts_with_all = ["daemon", "orphaned_fds", "profile_syscalls"])]
+    watch: bool,
+    /// Emit a JSON line for each candidate as soon as it scores, then the
+    /// usual report. Implies `--json`.
+    #[arg(long, conflicts_with_all = ["daemon", "orphaned_fds"])]
+    json_stream: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
 struct CleanArgs {
     /// Paths to clean (falls back to configured watched paths when omitted).
-    #[arg(value_name = "PATH")]
+    #[arg(value_name = "PATH", conflicts_with = "mount")]
     paths: Vec<PathBuf>,
+    /// Clean only the configured roots on this mount; `--target-free` is
+    /// measured against this mount alone.
+    #[arg(long, value_name = "MOUNT")]
+    mount: Option<PathBuf>,
     /// Target free percentage to recover.
     #[arg(long, value_name = "PERCENT")]
     target_free: Option<f64>,
@@ -239,27 +434,438 @@ struct CleanArgs {
     /// Maximum number of items to delete.
     #[arg(long, value_name = "N")]
     max_items: Option<usize>,
+    /// Only clean candidates at least this old (e.g. `7d`, `12h`).
+    #[arg(long, value_name = "AGE", conflicts_with = "caches")]
+    older_than: Option<String>,
+    /// Only clean candidates younger than this (e.g. `1h`, `30d`).
+    #[arg(long, value_name = "AGE", conflicts_with = "caches")]
+    newer_than: Option<String>,
+    /// Only clean candidates matching GLOB or inside a match (repeatable).
+    /// A pattern not starting with `/` or `**` matches at any depth.
+    #[arg(long, value_name = "GLOB", conflicts_with = "caches")]
+    include: Vec<String>,
+    /// Leave out candidates matching GLOB, inside a match, or containing
+    /// one (repeatable), e.g. `--exclude '**/release/**'`.
+    #[arg(long, value_name = "GLOB", conflicts_with = "caches")]
+    exclude: Vec<String>,
     /// Print candidates and planned actions without deleting.
     #[arg(long)]
     dry_run: bool,
     /// Skip interactive confirmation prompt.
     #[arg(long)]
     yes: bool,
+    /// Clean curated tool caches in the home directory (pip, yarn, pnpm,
+    /// Playwright, Hugging Face, cargo) instead of workspace artifacts.
+    #[arg(long, conflicts_with_all = ["paths", "mount", "target_free"])]
+    caches: bool,
+    /// Move candidates into their mount's `.sbh-quarantine/` instead of
+    /// deleting them; they are purged after `quarantine.ttl_hours`.
+    #[arg(long, conflicts_with = "caches")]
+    quarantine: bool,
+    /// Emit a JSON line for each candidate as it scores and for each item
+    /// as it is deleted, then the usual report. Implies `--json`; needs
+    /// `--yes` or `--dry-run`.
+    #[arg(long, conflicts_with = "caches")]
+    json_stream: bool,
+    /// Print a hash of the plan (paths, sizes, order) to approve and pass
+    /// back with `--expect-plan-hash`.
+    #[arg(long, requires = "dry_run", conflicts_with = "caches")]
+    plan_hash: bool,
+    /// Abort without deleting anything unless the plan still hashes to this
+    /// value from `--dry-run --plan-hash` (or `--emit-plan`).
+    #[arg(long, value_name = "HASH", conflicts_with = "caches")]
+    expect_plan_hash: Option<String>,
+    /// Write the plan to FILE for review instead of deleting anything; run
+    /// it later with `--execute-plan`.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["caches", "yes", "json_stream", "plan_hash", "expect_plan_hash"]
+    )]
+    emit_plan: Option<PathBuf>,
+    /// Run a plan written by `--emit-plan`. Entries changed since planning
+    /// are skipped; a plan that was edited or reaches outside the configured
+    /// scan roots is refused. Needs `--yes` or `--dry-run`.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "paths", "mount", "target_free", "max_items", "older_than", "newer_than",
+            "include", "exclude", "caches", "json_stream", "plan_hash", "emit_plan",
+        ]
+    )]
+    execute_plan: Option<PathBuf>,
 }
 
 impl Default for CleanArgs {
     fn default() -> Self {
         Self {
             paths: Vec::new(),
+            mount: None,
             target_free: None,
             min_score: 0.7,
             max_items: None,
+            older_than: None,
+            newer_than: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
             dry_run: false,
             yes: false,
+            caches: false,
+            quarantine: false,
+            json_stream: false,
+            plan_hash: false,
+            expect_plan_hash: None,
+            emit_plan: None,
+            execute_plan: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Args, Serialize)]
+struct BenchArgs {
+    /// Benchmark to run.
+    #[command(subcommand)]
+    command: BenchCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum BenchCommand {
+    /// Time walk and scoring over paths or a generated synthetic tree.
+    Scan(BenchScanArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct BenchScanArgs {
+    /// Paths to scan (falls back to configured watched paths when omitted).
+    #[arg(value_name = "PATH", conflicts_with = "synthetic")]
+    paths: Vec<PathBuf>,
+    /// Generate a seeded synthetic tree (on tmpfs when available) and scan it.
+    #[arg(long)]
+    synthetic: bool,
+    /// Synthetic tree size in projects.
+    #[arg(long, default_value_t = 200, value_name = "N", requires = "synthetic")]
+    projects: usize,
+    /// Synthetic tree seed (same seed, same tree on every machine).
+    #[arg(long, value_name = "SEED", requires = "synthetic")]
+    seed: Option<u64>,
+    /// Number of timed scan passes.
+    #[arg(long, default_value_t = 3, value_name = "N")]
+    iterations: usize,
+    /// Keep the generated synthetic tree instead of removing it.
+    #[arg(long, requires = "synthetic")]
+    keep: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct CiArgs {
+    /// CI step to run.
+    #[command(subcommand)]
+    command: CiCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum CiCommand {
+    /// Record free space at job start.
+    Begin(CiPathArgs),
+    /// Compare against the job-start baseline and emit a summary and annotations.
+    Report(CiPathArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct CiPathArgs {
+    /// Path on the job's build mount (defaults to cwd).
+    #[arg(value_name = "PATH", add = ArgValueCandidates::new(mount_candidates))]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct RunnerHookArgs {
+    /// Lifecycle phase the hook runs in.
+    #[command(subcommand)]
+    command: RunnerHookCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum RunnerHookCommand {
+    /// Check the workspace mount and reclaim space before the job starts.
+    PreJob(PreJobArgs),
+    /// Score, and with `--clean` delete, the finished job's workspace artifacts.
+    PostJob(PostJobArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct PreJobArgs {
+    /// Job workspace (defaults to the runner's workspace variable, then cwd).
+    #[arg(value_name = "WORKSPACE")]
+    workspace: Option<PathBuf>,
+    /// Free percentage to restore on the workspace mount (defaults to the yellow threshold).
+    #[arg(long, value_name = "PERCENT")]
+    target_free: Option<f64>,
+    /// Fail the hook when fewer than this many bytes are free after reclaiming.
+    #[arg(long, value_name = "BYTES")]
+    need: Option<u64>,
+    /// Plan the reclaim without deleting.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct PostJobArgs {
+    /// Finished job's workspace (defaults to the runner's workspace variable, then cwd).
+    #[arg(value_name = "WORKSPACE")]
+    workspace: Option<PathBuf>,
+    /// Delete the scored candidates instead of only reporting them.
+    #[arg(long)]
+    clean: bool,
+    /// Minimum score to include in deletion candidates.
+    #[arg(long, default_value_t = 0.7, value_name = "SCORE")]
+    min_score: f64,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct WorkspaceArgs {
+    /// Workspace operation to run.
+    #[command(subcommand)]
+    command: WorkspaceCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum WorkspaceCommand {
+    /// Create a workspace directory with a lease and optional byte budget.
+    Create(WorkspaceCreateArgs),
+    /// Delete a leased workspace once no process is using it.
+    Destroy(WorkspaceDestroyArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct WorkspaceCreateArgs {
+    /// Directory to create (must not exist, or be empty).
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+    /// Agent or job that owns the workspace.
+    #[arg(long, value_name = "NAME")]
+    owner: String,
+    /// Byte budget for the workspace; must fit in the mount's free space.
+    #[arg(long, value_name = "BYTES")]
+    budget: Option<u64>,
+    /// Lease duration (e.g. 90m, 8h, 2d); without it the lease never expires.
+    #[arg(long, value_name = "DURATION")]
+    lease: Option<String>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct WorkspaceDestroyArgs {
+    /// Workspace created by `sbh workspace create`.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+    /// Lease owner; required until the lease expires.
+    #[arg(long, value_name = "NAME")]
+    owner: Option<String>,
+    /// Run the safety checks and report the size without deleting.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct ExportArgs {
+    /// What to export.
+    #[command(subcommand)]
+    command: ExportCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum ExportCommand {
+    /// Daemon metrics in the Prometheus text format (for the node_exporter textfile collector).
+    Prometheus(ExportPrometheusArgs),
+    /// Ready-to-import Grafana dashboard over the exported metrics.
+    GrafanaDashboard(ExportGrafanaArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct ExportPrometheusArgs {
+    /// Write to this file (atomically) instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct ExportGrafanaArgs {
+    /// Prometheus datasource UID; without it Grafana asks on import.
+    #[arg(long, value_name = "UID")]
+    datasource: Option<String>,
+    /// Write to this file (atomically) instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct RestoreArgs {
+    /// Archive entry id, or the path the artifact was relocated from.
+    #[arg(value_name = "ID|PATH", required_unless_present = "list")]
+    target: Option<String>,
+    /// List archived artifacts instead of restoring one.
+    #[arg(long, conflicts_with = "to")]
+    list: bool,
+    /// Restore to this path instead of the original location.
+    #[arg(long, value_name = "PATH")]
+    to: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct QuarantineArgs {
+    /// Quarantine operation to run.
+    #[command(subcommand)]
+    command: QuarantineCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum QuarantineCommand {
+    /// List quarantined artifacts and when they expire.
+    List,
+    /// Move a quarantined artifact back to where it was.
+    Restore(QuarantineRestoreArgs),
+    /// Delete quarantined artifacts now, reclaiming their space.
+    Purge(QuarantinePurgeArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct QuarantineRestoreArgs {
+    /// Quarantine entry id, or the path the artifact was quarantined from.
+    #[arg(value_name = "ID|PATH")]
+    target: String,
+    /// Restore to this path (on the same filesystem) instead of the original.
+    #[arg(long, value_name = "PATH")]
+    to: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+#[command(group(
+    ArgGroup::new("purge_selection")
+        .required(true)
+        .args(["target", "expired", "all"])
+))]
+struct QuarantinePurgeArgs {
+    /// Quarantine entry id, or the path the artifact was quarantined from.
+    #[arg(value_name = "ID|PATH")]
+    target: Option<String>,
+    /// Purge only entries past their expiry.
+    #[arg(long)]
+    expired: bool,
+    /// Purge everything in quarantine.
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct AuditArgs {
+    /// Audit operation to run.
+    #[command(subcommand)]
+    command: AuditCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum AuditCommand {
+    /// List recent deletion batches, newest first.
+    List(AuditListArgs),
+    /// Show everything one batch removed and why.
+    Show(AuditShowArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct AuditListArgs {
+    /// Maximum number of batches to list.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+    /// Only batches that removed this path, an ancestor, or a descendant.
+    #[arg(long, value_name = "PATH")]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct AuditShowArgs {
+    /// Batch id from `sbh audit list` or a `clean` report.
+    #[arg(value_name = "BATCH_ID")]
+    batch_id: String,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct NotifyArgs {
+    /// Notification operation to run.
+    #[command(subcommand)]
+    command: NotifyCommand,
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum NotifyCommand {
+    /// Work with `[[notifications.rules]]`.
+    #[command(subcommand)]
+    Rules(NotifyRulesCommand),
+}
+
+#[derive(Debug, Clone, Subcommand, Serialize)]
+enum NotifyRulesCommand {
+    /// Show which rule each channel applies to a sample event.
+    Test(NotifyRulesTestArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct NotifyRulesTestArgs {
+    /// Event type, e.g. `pressure_changed` or `disk_health`.
+    #[arg(value_name = "EVENT")]
+    event: String,
+    /// Level the event would carry before rules apply.
+    #[arg(long, default_value = "warning", value_name = "LEVEL")]
+    level: NotificationLevel,
+    /// Mount point the event concerns.
+    #[arg(long, value_name = "PATH")]
+    mount: Option<String>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct AnnotateArgs {
+    /// Note to record, e.g. "started nightly build farm run".
+    #[arg(value_name = "MESSAGE", num_args = 1.., required = true)]
+    message: Vec<String>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct PauseArgs {
+    /// How long to pause, e.g. 30m, 2h, 1d (bare numbers are minutes).
+    #[arg(long = "for", value_name = "DURATION")]
+    duration: String,
+    /// Also suppress notifications while paused.
+    #[arg(long)]
+    mute_alerts: bool,
+    /// Note shown in status and the dashboard.
+    #[arg(long, value_name = "TEXT")]
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct SelftestArgs {
+    /// Keep the sandbox for inspection instead of removing it.
+    #[arg(long)]
+    keep: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct ExplainArgs {
+    /// File or directory to explain.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+    /// Detail level: 0 verdict, 1 factors, 2 posterior and losses, 3 full trace.
+    #[arg(long, default_value_t = 2, value_name = "N")]
+    level: u8,
+    /// Pressure urgency to score at, from 0.0 (none, as manual scans) to 1.0.
+    #[arg(long, default_value_t = 0.0, value_name = "URGENCY")]
+    urgency: f64,
+    /// Re-score the inputs recorded at the path's last deletion with the
+    /// current engine and config, to tell whether a change altered the result.
+    #[arg(long, conflicts_with = "urgency")]
+    rescore: bool,
+}
+
 #[derive(Debug, Clone, Args, Serialize, Default)]
 struct BallastArgs {
     /// Ballast operation to run.
@@ -279,6 +885,8 @@ enum BallastCommand {
     Replenish,
     /// Verify ballast integrity.
     Verify,
+    /// Grow or shrink the pool in place and save the new size to the config.
+    Resize(ResizeBallastArgs),
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
@@ -288,6 +896,22 @@ struct ReleaseBallastArgs {
     count: usize,
 }
 
+#[derive(Debug, Clone, Args, Serialize)]
+#[command(group(
+    ArgGroup::new("resize_target")
+        .required(true)
+        .multiple(true)
+        .args(["count", "size"])
+))]
+struct ResizeBallastArgs {
+    /// Files in each pool afterwards (`ballast.file_count`).
+    #[arg(long, value_name = "N")]
+    count: Option<usize>,
+    /// Size of each file afterwards, in bytes (`ballast.file_size_bytes`).
+    #[arg(long, value_name = "BYTES")]
+    size: Option<u64>,
+}
+
 #[derive(Debug, Clone, Args, Serialize, Default)]
 struct ConfigArgs {
     /// Config operation to run.
@@ -309,14 +933,53 @@ enum ConfigCommand {
     Reset,
     /// Set a specific config key.
     Set(ConfigSetArgs),
+    /// Print the config file for use on another host.
+    Export(ConfigExportArgs),
+    /// Preview or apply a config exported from another host.
+    Import(ConfigImportArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct ConfigExportArgs {
+    /// Leave out host-specific keys: `[paths]`, pools, per-mount entries, sockets.
+    #[arg(long)]
+    portable: bool,
+    /// Write to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct ConfigImportArgs {
+    /// Exported config to compare against (`-` reads stdin).
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+    /// Write the imported config, keeping this host's host-specific keys.
+    #[arg(long)]
+    apply: bool,
+    /// Confirm `--apply`.
+    #[arg(long, requires = "apply")]
+    yes: bool,
+    /// Minutes the daemon watches imported enforcement changes before keeping them (0 = no revert).
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        requires = "apply",
+        default_value_t = DEFAULT_REVERT_WINDOW_MINUTES
+    )]
+    revert_window: u64,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
 struct ConfigSetArgs {
     /// Dot-path config key to set.
+    #[arg(add = ArgValueCandidates::new(config_key_candidates))]
     key: String,
     /// New value to apply.
     value: String,
+    /// Minutes the daemon watches an enforcement change before keeping it (0 = no revert).
+    #[arg(long, value_name = "MINUTES", default_value_t = DEFAULT_REVERT_WINDOW_MINUTES)]
+    revert_window: u64,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
@@ -353,15 +1016,19 @@ impl Default for EmergencyArgs {
 #[command(group(
     ArgGroup::new("protect_target")
         .required(true)
-        .args(["path", "list"])
+        .args(["path", "list", "coverage"])
 ))]
 struct ProtectArgs {
     /// Path to protect (creates `.sbh-protect` marker).
-    #[arg(value_name = "PATH", conflicts_with = "list")]
+    #[arg(value_name = "PATH", conflicts_with_all = ["list", "coverage"])]
     path: Option<PathBuf>,
     /// List all protections from marker files + config.
-    #[arg(long, conflicts_with = "path")]
+    #[arg(long, conflicts_with_all = ["path", "coverage"])]
     list: bool,
+    /// Report how much of the space under watched roots is protected, per
+    /// mount, and whether protections put the free-space goal out of reach.
+    #[arg(long, conflicts_with_all = ["path", "list"])]
+    coverage: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
@@ -379,12 +1046,20 @@ struct TuneArgs {
     /// Skip interactive confirmation when applying.
     #[arg(long, requires = "apply")]
     yes: bool,
+    /// Minutes the daemon watches applied changes before keeping them (0 = no revert).
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        requires = "apply",
+        default_value_t = DEFAULT_REVERT_WINDOW_MINUTES
+    )]
+    revert_window: u64,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
 struct CheckArgs {
     /// Path to evaluate (defaults to cwd).
-    #[arg(value_name = "PATH")]
+    #[arg(value_name = "PATH", add = ArgValueCandidates::new(mount_candidates))]
     path: Option<PathBuf>,
     /// Desired minimum free percentage.
     #[arg(long, value_name = "PERCENT")]
@@ -395,6 +1070,36 @@ struct CheckArgs {
     /// Predict if space will last for this many minutes (requires running daemon).
     #[arg(long, value_name = "MINUTES")]
     predict: Option<u64>,
+    /// Print one Nagios/Icinga plugin line and exit 0/1/2/3 (OK/WARNING/CRITICAL/UNKNOWN).
+    #[arg(long)]
+    nagios: bool,
+    /// Warning when free space is below this percentage (default: --target-free).
+    #[arg(long, value_name = "PERCENT", requires = "nagios")]
+    warning: Option<f64>,
+    /// Critical when free space is below this percentage (default: pressure.red_min_free_pct).
+    #[arg(long, value_name = "PERCENT", requires = "nagios")]
+    critical: Option<f64>,
+    /// Ignore the nearest `.sbh-check.toml` above the checked path.
+    #[arg(long, conflicts_with = "nagios")]
+    no_policy_file: bool,
+    /// Output format: `text` (human or JSON), or `gha` for GitHub Actions
+    /// annotations and step outputs.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = CheckFormat::Text,
+        value_name = "FORMAT",
+        conflicts_with = "nagios"
+    )]
+    format: CheckFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CheckFormat {
+    #[default]
+    Text,
+    Gha,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
@@ -402,11 +1107,49 @@ struct BlameArgs {
     /// Maximum rows to return.
     #[arg(long, default_value_t = 25, value_name = "N")]
     top: usize,
+    /// Continuously attribute live write throughput per process.
+    #[arg(long)]
+    watch: bool,
+    /// Refresh interval for watch mode.
+    #[arg(
+        long,
+        default_value_t = 2_000,
+        value_name = "MILLISECONDS",
+        requires = "watch"
+    )]
+    refresh_ms: u64,
+    /// Only show writers at or above this rate in MiB/s (watch mode).
+    #[arg(long, value_name = "MIB_PER_SEC", requires = "watch")]
+    threshold: Option<f64>,
+    /// Show who was writing before a past pressure event: last, last-orange,
+    /// last-red, or last-critical.
+    #[arg(long, value_name = "INCIDENT", conflicts_with = "watch")]
+    incident: Option<String>,
+    /// Seconds between the two write-counter samples behind the rates.
+    #[arg(
+        long,
+        default_value_t = 5,
+        value_name = "SECONDS",
+        conflicts_with_all = ["watch", "incident", "artifacts"]
+    )]
+    window_secs: u64,
+    /// Group build-artifact directories under the scan roots by the process
+    /// working in them, instead of sampling writes.
+    #[arg(long, conflicts_with_all = ["watch", "incident"])]
+    artifacts: bool,
 }
 
 impl Default for BlameArgs {
     fn default() -> Self {
-        Self { top: 25 }
+        Self {
+            top: 25,
+            watch: false,
+            refresh_ms: 2_000,
+            threshold: None,
+            incident: None,
+            window_secs: 5,
+            artifacts: false,
+        }
     }
 }
 
@@ -440,6 +1183,9 @@ struct CompletionsArgs {
     /// Shell to generate completion script for.
     #[arg(value_enum)]
     shell: CompletionShell,
+    /// Emit a static script instead of the dynamic registration stub.
+    #[arg(long = "static")]
+    static_script: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
@@ -546,6 +1292,26 @@ struct LogArgs {
     r#type: Option<String>,
 }
 
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct HelpArgs {
+    /// Command path (e.g. `ballast release`) or guide topic (e.g. `scoring`).
+    #[arg(value_name = "TOPIC")]
+    topic: Vec<String>,
+    /// List long-form guide topics.
+    #[arg(long, conflicts_with = "topic")]
+    topics: bool,
+    /// Print directly instead of through `$PAGER`.
+    #[arg(long)]
+    no_pager: bool,
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct ManArgs {
+    /// Write one page per command into this directory instead of printing `sbh.1`.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OutputMode {
     Human,
@@ -568,6 +1334,9 @@ pub enum CliError {
     /// Operation partially succeeded.
     #[error("{0}")]
     Partial(String),
+    /// Check-plugin status already printed on stdout (`check --nagios`).
+    #[error("plugin status {}", .0.label())]
+    Plugin(PluginStatus),
     /// JSON serialization failed.
     #[error("failed to serialize output: {0}")]
     Json(#[from] serde_json::Error),
@@ -584,63 +1353,481 @@ impl CliError {
             Self::Runtime(_) | Self::Io(_) => 2,
             Self::Internal(_) | Self::Json(_) => 3,
             Self::Partial(_) => 4,
+            Self::Plugin(status) => status.exit_code(),
         }
     }
-}
 
-/// Dispatch CLI commands.
-pub fn run(cli: &Cli) -> Result<(), CliError> {
-    if cli.no_color {
-        control::set_override(false);
+    /// Stable machine-readable error code for `--json` error objects.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::User(_) => "user_error",
+            Self::Runtime(_) => "runtime_error",
+            Self::Internal(_) => "internal_error",
+            Self::Partial(_) => "partial_failure",
+            Self::Plugin(_) => "plugin_status",
+            Self::Json(_) => "serialization_error",
+            Self::Io(_) => "output_error",
+        }
     }
 
-    match &cli.command {
-        Command::Daemon(args) => run_daemon(cli, args),
-        Command::Install(args) => run_install(cli, args),
-        Command::Uninstall(args) => run_uninstall(cli, args),
-        Command::Status(args) => run_status(cli, args),
-        Command::Stats(args) => run_stats(cli, args),
-        Command::Scan(args) => run_scan(cli, args),
-        Command::Clean(args) => run_clean(cli, args),
-        Command::Ballast(args) => run_ballast(cli, args),
-        Command::Config(args) => run_config(cli, args),
-        Command::Version(args) => emit_version(cli, args),
-        Command::Emergency(args) => run_emergency(cli, args),
-        Command::Protect(args) => run_protect(cli, args),
-        Command::Unprotect(args) => run_unprotect(cli, args),
-        Command::Tune(args) => run_tune(cli, args),
-        Command::Check(args) => run_check(cli, args),
-        Command::Blame(args) => run_blame(cli, args),
-        Command::Dashboard(args) => run_dashboard(cli, args),
-        Command::Completions(args) => {
-            let mut command = Cli::command();
-            let binary_name = command.get_name().to_string();
-            generate(args.shell, &mut command, binary_name, &mut io::stdout());
-            Ok(())
+    /// Generic next step for the operator or calling agent.
+    pub const fn remediation(&self) -> &'static str {
+        match self {
+            Self::User(_) => "check the arguments; run `sbh <command> --help` for usage",
+            Self::Runtime(_) => {
+                "check permissions, config, and disk state; re-run with --log-level debug for detail"
+            }
+            Self::Internal(_) | Self::Json(_) => {
+                "this is a bug; re-run with --log-level trace and report the output"
+            }
+            Self::Partial(_) => "inspect the per-item results and re-run to retry remaining items",
+            Self::Plugin(_) => "see the plugin status line on stdout",
+            Self::Io(_) => "stdout could not be written; check for a closed pipe or full disk",
         }
-        Command::Update(args) => run_update(cli, args),
-        Command::Setup(args) => run_setup(cli, args),
-        Command::Log(args) => run_log(cli, args),
     }
-}
 
-fn to_runtime_daemon_args(args: &DaemonArgs) -> RuntimeDaemonArgs {
-    RuntimeDaemonArgs {
-        foreground: !args.background,
-        pidfile: args.pidfile.clone(),
-        watchdog_sec: args.watchdog_sec,
+    /// Structured error payload emitted on stdout in JSON mode.
+    fn to_json(&self, command: &str) -> Value {
+        json!({
+            "command": command,
+            "error": {
+                "code": self.code(),
+                "message": self.to_string(),
+                "remediation": self.remediation(),
+                "exit_code": self.exit_code(),
+            },
+        })
+    }
+}
+
+/// One row of the documented exit-code contract.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct ExitCodeDoc {
+    code: i32,
+    name: &'static str,
+    meaning: &'static str,
+    typical_causes: &'static [&'static str],
+}
+
+/// The full exit-code contract. `CliError::exit_code` must only return codes
+/// listed here; tests enforce this in both directions.
+const EXIT_CODES: &[ExitCodeDoc] = &[
+    ExitCodeDoc {
+        code: 0,
+        name: "success",
+        meaning: "Command completed",
+        typical_causes: &[],
+    },
+    ExitCodeDoc {
+        code: 1,
+        name: "user_error",
+        meaning: "Invalid input or a refused operation",
+        typical_causes: &[
+            "invalid flag value or unknown help topic",
+            "invalid config value rejected by `config set`/`config validate`",
+            "deletion refused without --yes in non-interactive mode",
+            "unsupported flag combination for this platform",
+        ],
+    },
+    ExitCodeDoc {
+        code: 2,
+        name: "runtime_error",
+        meaning: "Environment or runtime failure; also clap usage errors",
+        typical_causes: &[
+            "unknown flag or missing required argument (usage error)",
+            "explicit --config path missing or unparsable",
+            "filesystem, permission, or database failure",
+            "stdout could not be written",
+        ],
+    },
+    ExitCodeDoc {
+        code: 3,
+        name: "internal_error",
+        meaning: "Internal bug or invariant violation",
+        typical_causes: &["JSON serialization failure", "violated internal invariant"],
+    },
+    ExitCodeDoc {
+        code: 4,
+        name: "partial_failure",
+        meaning: "Operation partially succeeded",
+        typical_causes: &[
+            "some items succeeded while others failed",
+            "a scan root timed out (--root-timeout) or failed while others completed",
+        ],
+    },
+];
+
+fn run_exit_codes(cli: &Cli) -> Result<(), CliError> {
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("  {:>4}  {:<16}  Meaning", "Code", "Name");
+            println!("  {}", "-".repeat(70));
+            for doc in EXIT_CODES {
+                println!("  {:>4}  {:<16}  {}", doc.code, doc.name, doc.meaning);
+                for cause in doc.typical_causes {
+                    println!("  {:>4}  {:<16}    - {cause}", "", "");
+                }
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "exit-codes",
+                "exit_codes": EXIT_CODES,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set once a JSON error object has been written so a failure yields exactly one.
+static JSON_ERROR_EMITTED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Set once a command has written a JSON document to stdout. A command that
+/// reports its own failure (`ballast release` with per-file `errors`, a
+/// failed `update`) already produced the one document a `--json` caller
+/// parses, so no error object follows it.
+static JSON_PAYLOAD_EMITTED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+fn write_json_error_once(payload: &Value) {
+    if !JSON_ERROR_EMITTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        // Best effort: the failure being reported may itself be a broken stdout.
+        let _ = write_json_line(payload);
+    }
+}
+
+/// Report a command failure: prose on stderr, plus one structured error object
+/// on stdout in JSON mode so agent wrappers never need to parse prose.
+pub fn report_error(cli: &Cli, err: &CliError) {
+    // The plugin line is the whole output; anything else would corrupt it.
+    if matches!(err, CliError::Plugin(_)) {
+        return;
+    }
+    eprintln!("sbh: {err}");
+    // A `--json-stream` run ends with the error as its last event instead.
+    let payload_written = JSON_PAYLOAD_EMITTED.load(std::sync::atomic::Ordering::SeqCst)
+        && !cli.command.streams_json();
+    if output_mode(cli) == OutputMode::Json && !payload_written {
+        write_json_error_once(&err.to_json(cli.command.name()));
+    }
+}
+
+/// Report an argument-parsing failure and exit with clap's exit code.
+///
+/// Help and version requests exit normally. Real usage errors also produce a
+/// JSON error object when `--json` (or JSON output mode) was requested.
+pub fn exit_with_parse_error(err: &clap::Error) -> ! {
+    use clap::error::ErrorKind;
+
+    if matches!(
+        err.kind(),
+        ErrorKind::DisplayHelp
+            | ErrorKind::DisplayVersion
+            | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+    ) {
+        err.exit();
+    }
+
+    let json_flag = std::env::args_os().any(|arg| arg == "--json");
+    let env_mode = std::env::var("SBH_OUTPUT_FORMAT").ok();
+    if resolve_output_mode(json_flag, env_mode.as_deref(), io::stdout().is_terminal())
+        == OutputMode::Json
+    {
+        write_json_error_once(&usage_error_json(err));
+    }
+    err.exit();
+}
+
+fn usage_error_json(err: &clap::Error) -> Value {
+    let rendered = err.render().to_string();
+    let message = rendered
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("error: ")
+        .to_string();
+    json!({
+        "command": Value::Null,
+        "error": {
+            "code": "usage_error",
+            "message": message,
+            "remediation": "check the arguments; run `sbh --help` for usage",
+            "exit_code": err.exit_code(),
+        },
+    })
+}
+
+impl Command {
+    /// Subcommand name as typed on the command line.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Daemon(_) => "daemon",
+            Self::Install(_) => "install",
+            Self::Uninstall(_) => "uninstall",
+            Self::Status(_) => "status",
+            Self::Stats(_) => "stats",
+            Self::Scan(_) => "scan",
+            Self::Clean(_) => "clean",
+            Self::Ballast(_) => "ballast",
+            Self::Config(_) => "config",
+            Self::Version(_) => "version",
+            Self::Emergency(_) => "emergency",
+            Self::Protect(_) => "protect",
+            Self::Unprotect(_) => "unprotect",
+            Self::Tune(_) => "tune",
+            Self::Check(_) => "check",
+            Self::Blame(_) => "blame",
+            Self::Dashboard(_) => "dashboard",
+            Self::Completions(_) => "completions",
+            Self::Update(_) => "update",
+            Self::Setup(_) => "setup",
+            Self::Log(_) => "log",
+            Self::Help(_) => "help",
+            Self::Man(_) => "man",
+            Self::ExitCodes => "exit-codes",
+            Self::Bench(_) => "bench",
+            Self::Ci(_) => "ci",
+            Self::RunnerHook(_) => "runner-hook",
+            Self::Workspace(_) => "workspace",
+            Self::Export(_) => "export",
+            Self::Restore(_) => "restore",
+            Self::Quarantine(_) => "quarantine",
+            Self::Audit(_) => "audit",
+            Self::Notify(_) => "notify",
+            Self::Annotate(_) => "annotate",
+            Self::Pause(_) => "pause",
+            Self::Resume => "resume",
+            Self::Selftest(_) => "selftest",
+            Self::Doctor => "doctor",
+            Self::Explain(_) => "explain",
+        }
+    }
+
+    /// `--json-stream` was given, which implies `--json`.
+    const fn streams_json(&self) -> bool {
+        match self {
+            Self::Scan(args) => args.json_stream,
+            Self::Clean(args) => args.json_stream,
+            _ => false,
+        }
+    }
+}
+
+/// Dispatch CLI commands.
+pub fn run(cli: &Cli) -> Result<(), CliError> {
+    if cli.no_color {
+        control::set_override(false);
+    }
+    if cli.container {
+        container::enable();
+    }
+    init_diagnostics(cli)?;
+    if container::is_enabled()
+        && let Some(name) = match cli.command {
+            Command::Install(_) => Some("install"),
+            Command::Uninstall(_) => Some("uninstall"),
+            Command::Update(_) => Some("update"),
+            _ => None,
+        }
+    {
+        return Err(CliError::User(format!(
+            "sbh {name} manages a host installation and is not available in container mode; \
+             update the image instead"
+        )));
+    }
+
+    match &cli.command {
+        Command::Daemon(args) => run_daemon(cli, args),
+        Command::Install(args) => run_install(cli, args),
+        Command::Uninstall(args) => run_uninstall(cli, args),
+        Command::Status(args) => run_status(cli, args),
+        Command::Stats(args) => run_stats(cli, args),
+        Command::Scan(args) => run_scan(cli, args),
+        Command::Clean(args) => run_clean(cli, args),
+        Command::Ballast(args) => run_ballast(cli, args),
+        Command::Config(args) => run_config(cli, args),
+        Command::Version(args) => emit_version(cli, args),
+        Command::Emergency(args) => run_emergency(cli, args),
+        Command::Protect(args) => run_protect(cli, args),
+        Command::Unprotect(args) => run_unprotect(cli, args),
+        Command::Tune(args) => run_tune(cli, args),
+        Command::Check(args) => run_check(cli, args),
+        Command::Blame(args) => run_blame(cli, args),
+        Command::Dashboard(args) => run_dashboard(cli, args),
+        Command::Completions(args) => {
+            write_completion_script(args.shell, args.static_script, &mut io::stdout())?;
+            Ok(())
+        }
+        Command::Update(args) => run_update(cli, args),
+        Command::Setup(args) => run_setup(cli, args),
+        Command::Log(args) => run_log(cli, args),
+        Command::Help(args) => run_help(cli, args),
+        Command::Man(args) => run_man(cli, args),
+        Command::ExitCodes => run_exit_codes(cli),
+        Command::Bench(args) => match &args.command {
+            BenchCommand::Scan(scan_args) => run_bench_scan(cli, scan_args),
+        },
+        Command::Ci(args) => match &args.command {
+            CiCommand::Begin(path_args) => run_ci_begin(cli, path_args),
+            CiCommand::Report(path_args) => run_ci_report(cli, path_args),
+        },
+        Command::RunnerHook(args) => match &args.command {
+            RunnerHookCommand::PreJob(hook_args) => run_runner_pre_job(cli, hook_args),
+            RunnerHookCommand::PostJob(hook_args) => run_runner_post_job(cli, hook_args),
+        },
+        Command::Workspace(args) => match &args.command {
+            WorkspaceCommand::Create(create_args) => run_workspace_create(cli, create_args),
+            WorkspaceCommand::Destroy(destroy_args) => run_workspace_destroy(cli, destroy_args),
+        },
+        Command::Export(args) => match &args.command {
+            ExportCommand::Prometheus(export_args) => run_export_prometheus(cli, export_args),
+            ExportCommand::GrafanaDashboard(export_args) => run_export_grafana(cli, export_args),
+        },
+        Command::Restore(args) => run_restore(cli, args),
+        Command::Quarantine(args) => match &args.command {
+            QuarantineCommand::List => run_quarantine_list(cli),
+            QuarantineCommand::Restore(restore_args) => run_quarantine_restore(cli, restore_args),
+            QuarantineCommand::Purge(purge_args) => run_quarantine_purge(cli, purge_args),
+        },
+        Command::Audit(args) => match &args.command {
+            AuditCommand::List(list_args) => run_audit_list(cli, list_args),
+            AuditCommand::Show(show_args) => run_audit_show(cli, show_args),
+        },
+        Command::Notify(args) => match &args.command {
+            NotifyCommand::Rules(NotifyRulesCommand::Test(test_args)) => {
+                run_notify_rules_test(cli, test_args)
+            }
+        },
+        Command::Annotate(args) => run_annotate(cli, args),
+        Command::Pause(args) => run_pause(cli, args),
+        Command::Resume => run_resume(cli),
+        Command::Selftest(args) => run_selftest(cli, args),
+        Command::Doctor => run_doctor(cli),
+        Command::Explain(args) => run_explain(cli, args),
+    }
+}
+
+/// Resolve the diagnostic level from global flags. `None` leaves diagnostics off.
+///
+/// An explicit `--log-level` wins; otherwise `--verbose` or `--log-file` alone
+/// imply `info`.
+fn resolve_diag_level(
+    log_level: Option<DiagLevel>,
+    has_log_file: bool,
+    verbose: bool,
+) -> Option<DiagLevel> {
+    log_level.or_else(|| (verbose || has_log_file).then_some(DiagLevel::Info))
+}
+
+fn init_diagnostics(cli: &Cli) -> Result<(), CliError> {
+    // The daemon always logs; it starts diagnostics once its config is loaded.
+    if matches!(cli.command, Command::Daemon(_)) {
+        return Ok(());
+    }
+    let Some(level) = resolve_diag_level(cli.log_level, cli.log_file.is_some(), cli.verbose) else {
+        return Ok(());
+    };
+    start_diagnostics(cli, level, cli.log_format.unwrap_or_default())
+}
+
+fn start_diagnostics(cli: &Cli, level: DiagLevel, format: DiagFormat) -> Result<(), CliError> {
+    let target = cli
+        .log_file
+        .clone()
+        .map_or(DiagTarget::Stderr, DiagTarget::File);
+    diag::init(level, format, &target)
+        .map_err(|e| CliError::User(format!("cannot open --log-file: {e}")))?;
+    diag::debug(
+        "cli",
+        format_args!(
+            "sbh {} starting; diagnostics at {level}",
+            env!("CARGO_PKG_VERSION")
+        ),
+    );
+    Ok(())
+}
+
+fn to_runtime_daemon_args(args: &DaemonArgs) -> RuntimeDaemonArgs {
+    RuntimeDaemonArgs {
+        foreground: !args.background,
+        pidfile: args.pidfile.clone(),
+        watchdog_sec: args.watchdog_sec,
+        once: args.once,
     }
 }
 
 fn run_daemon(cli: &Cli, args: &DaemonArgs) -> Result<(), CliError> {
-    let config =
+    let mut config =
         Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    start_diagnostics(
+        cli,
+        cli.log_level.unwrap_or(config.logging.level),
+        cli.log_format.unwrap_or(config.logging.format),
+    )?;
     let runtime_args = to_runtime_daemon_args(args);
-    let mut daemon = MonitoringDaemon::init(config, &runtime_args)
-        .map_err(|e| CliError::Runtime(format!("failed to initialize daemon: {e}")))?;
-    daemon
-        .run()
-        .map_err(|e| CliError::Runtime(format!("daemon runtime failure: {e}")))
+    if !args.once {
+        return MonitoringDaemon::run_supervised(config, &runtime_args)
+            .map_err(|e| CliError::Runtime(format!("daemon runtime failure: {e}")));
+    }
+
+    // Two loops enforcing against the same disks would race each other.
+    if let Some(ControlReply::Pong { pid, .. }) = daemon_request(&config, &ControlRequest::Ping) {
+        return Err(CliError::User(format!(
+            "a daemon is already running (pid {pid}); stop it before `sbh daemon --once`"
+        )));
+    }
+    if args.dry_run {
+        config.scanner.dry_run = true;
+    }
+    let summary = MonitoringDaemon::run_once(config, &runtime_args)
+        .map_err(|e| CliError::Runtime(format!("daemon run failed: {e}")))?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Pressure: {} (urgency {:.2}) on {}",
+                summary.level.to_uppercase(),
+                summary.urgency,
+                summary.causing_mount.display(),
+            );
+            if let Some(secs) = summary.predicted_seconds {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let eta = std::time::Duration::from_secs(secs as u64);
+                println!("Forecast: red in {}", format_duration(eta));
+            }
+            println!("Prediction: {}", summary.predictive_action);
+            let mut policy = summary.policy_mode.clone();
+            if summary.dry_run {
+                policy.push_str(" (dry run)");
+            }
+            if summary.paused {
+                policy.push_str(" (paused by maintenance window)");
+            }
+            println!("Policy: {policy}");
+            println!(
+                "Scans: {} ({} candidates)  Deleted: {} items, {} freed  Errors: {}",
+                summary.scans,
+                summary.candidates,
+                summary.deletions,
+                format_bytes(summary.bytes_freed),
+                summary.errors,
+            );
+            println!(
+                "Ballast: {} of {} files available",
+                summary.ballast_available, summary.ballast_total,
+            );
+            println!("Completed in {} ms", summary.elapsed_ms);
+        }
+        OutputMode::Json => {
+            let mut payload = serde_json::to_value(&summary)?;
+            payload["command"] = json!("daemon");
+            payload["once"] = json!(true);
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
 }
 
 #[allow(clippy::too_many_lines)]
@@ -686,9 +1873,11 @@ fn run_install(cli: &Cli, args: &InstallArgs) -> Result<(), CliError> {
         return Ok(());
     }
 
-    if !args.from_source && !args.systemd && !args.launchd {
+    let service_requested = args.systemd || args.launchd || args.scheduled_task;
+    if !args.from_source && !service_requested {
         return Err(CliError::User(
-            "specify --systemd, --launchd, --from-source, --wizard, or --auto".to_string(),
+            "specify --systemd, --launchd, --scheduled-task, --from-source, --wizard, or --auto"
+                .to_string(),
         ));
     }
 
@@ -705,6 +1894,11 @@ fn run_install(cli: &Cli, args: &InstallArgs) -> Result<(), CliError> {
             "Error: --launchd is only supported on macOS. Use --systemd on Linux.".to_string(),
         ));
     }
+    if args.scheduled_task && !cfg!(windows) {
+        return Err(CliError::User(
+            "Error: --scheduled-task is only supported on Windows.".to_string(),
+        ));
+    }
 
     // System-scope systemd requires root; catch early with actionable guidance.
     if args.systemd && !args.user {
@@ -777,7 +1971,7 @@ fn run_install(cli: &Cli, args: &InstallArgs) -> Result<(), CliError> {
         }
 
         // If no service flags were specified, we're done after the binary install.
-        if !args.systemd && !args.launchd {
+        if !service_requested {
             return Ok(());
         }
         // Otherwise, fall through to service installation below.
@@ -828,11 +2022,18 @@ fn run_install(cli: &Cli, args: &InstallArgs) -> Result<(), CliError> {
     }
 
     // -- service registration -------------------------------------------------
-    if !args.systemd && !args.launchd {
+    if !service_requested {
         // No service registration requested; orchestration-only install is done.
         return Ok(());
     }
 
+    if args.scheduled_task {
+        let mgr = ScheduledTaskServiceManager::from_env(args.user)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        let scope = if args.user { "user" } else { "system" };
+        return run_scheduled_task_action(cli, &mgr, "install", scope);
+    }
+
     if args.launchd {
         let mgr = LaunchdServiceManager::from_env(args.user)
             .map_err(|e| CliError::Runtime(e.to_string()))?;
@@ -968,8 +2169,22 @@ fn run_install(cli: &Cli, args: &InstallArgs) -> Result<(), CliError> {
 
 #[allow(clippy::too_many_lines)]
 fn run_uninstall(cli: &Cli, args: &UninstallArgs) -> Result<(), CliError> {
-    if !args.systemd && !args.launchd {
-        return Err(CliError::User("specify --systemd or --launchd".to_string()));
+    if !args.systemd && !args.launchd && !args.scheduled_task {
+        return Err(CliError::User(
+            "specify --systemd, --launchd, or --scheduled-task".to_string(),
+        ));
+    }
+
+    if args.scheduled_task {
+        // One task name serves both scopes, so deletion does not need to know
+        // which one installed it.
+        let mgr = ScheduledTaskServiceManager::from_env(false)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        run_scheduled_task_action(cli, &mgr, "uninstall", "any")?;
+        if args.purge {
+            run_uninstall_purge(cli)?;
+        }
+        return Ok(());
     }
 
     if args.launchd {
@@ -1113,6 +2328,49 @@ fn run_uninstall(cli: &Cli, args: &UninstallArgs) -> Result<(), CliError> {
     }
 }
 
+/// Install or uninstall the Windows scheduled task and report the result.
+fn run_scheduled_task_action(
+    cli: &Cli,
+    mgr: &ScheduledTaskServiceManager,
+    action: &'static str,
+    scope: &'static str,
+) -> Result<(), CliError> {
+    let outcome = if action == "install" {
+        mgr.install()
+    } else {
+        mgr.uninstall()
+    };
+    let result = ServiceActionResult {
+        action,
+        service_type: "scheduled_task",
+        scope,
+        unit_path: mgr.config().task_path(),
+        success: outcome.is_ok(),
+        error: outcome.as_ref().err().map(ToString::to_string),
+    };
+
+    match output_mode(cli) {
+        OutputMode::Human => match &outcome {
+            Ok(()) if action == "install" => {
+                println!("Installed scheduled task ({scope} scope).");
+                println!("  Task: {}", result.unit_path.display());
+                println!("  Start now with:");
+                println!("    schtasks /Run /TN sbh");
+            }
+            Ok(()) => {
+                println!("Removed scheduled task.");
+                println!("  Task: {}", result.unit_path.display());
+            }
+            Err(e) => eprintln!("Failed to {action} scheduled task: {e}"),
+        },
+        OutputMode::Json => {
+            let payload = serde_json::to_value(&result)?;
+            write_json_line(&payload)?;
+        }
+    }
+    outcome.map_err(|e| CliError::Runtime(format!("{action} failed: {e}")))
+}
+
 fn run_uninstall_purge(cli: &Cli) -> Result<(), CliError> {
     use storage_ballast_helper::cli::install::{
         UninstallOptions, format_uninstall_report, run_uninstall_cleanup,
@@ -1164,6 +2422,162 @@ fn parse_window_duration(s: &str) -> Result<std::time::Duration, CliError> {
     Ok(std::time::Duration::from_secs(n * multiplier))
 }
 
+/// `--older-than` / `--newer-than` filter applied to candidate ages on top
+/// of scoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct AgeWindow {
+    older_than: Option<std::time::Duration>,
+    newer_than: Option<std::time::Duration>,
+}
+
+impl AgeWindow {
+    fn from_args(older_than: Option<&str>, newer_than: Option<&str>) -> Result<Self, CliError> {
+        let window = Self {
+            older_than: older_than.map(parse_window_duration).transpose()?,
+            newer_than: newer_than.map(parse_window_duration).transpose()?,
+        };
+        if let (Some(older), Some(newer)) = (window.older_than, window.newer_than)
+            && older >= newer
+        {
+            return Err(CliError::User(format!(
+                "--older-than {} and --newer-than {} leave no ages to match",
+                format_duration(older),
+                format_duration(newer)
+            )));
+        }
+        Ok(window)
+    }
+
+    /// Whether a candidate of this age passes the window.
+    fn contains(self, age: std::time::Duration) -> bool {
+        self.older_than.is_none_or(|older| age >= older)
+            && self.newer_than.is_none_or(|newer| age < newer)
+    }
+
+    /// Human summary such as "older than 7d 0h", or `None` when unbounded.
+    fn describe(self) -> Option<String> {
+        let older = self
+            .older_than
+            .map(|d| format!("older than {}", format_duration(d)));
+        let newer = self
+            .newer_than
+            .map(|d| format!("newer than {}", format_duration(d)));
+        match (older, newer) {
+            (Some(older), Some(newer)) => Some(format!("{older} and {newer}")),
+            (one, other) => one.or(other),
+        }
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "older_than_seconds": self.older_than.map(|d| d.as_secs()),
+            "newer_than_seconds": self.newer_than.map(|d| d.as_secs()),
+        })
+    }
+}
+
+/// Levels below a candidate directory searched for `--exclude` matches.
+const EXCLUDE_SEARCH_DEPTH: usize = 3;
+
+/// `--include` / `--exclude` globs applied to candidate paths on top of
+/// scoring. A candidate is kept when it (or a directory above it) matches
+/// some `--include`, if any are given, and neither it, a directory above
+/// it, nor anything shortly below it matches an `--exclude`.
+#[derive(Debug, Clone, Default)]
+struct PathGlobs {
+    include: Vec<GlobPattern>,
+    exclude: Vec<GlobPattern>,
+}
+
+impl PathGlobs {
+    fn from_args(include: &[String], exclude: &[String]) -> Result<Self, CliError> {
+        let compile = |flag: &str, patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    let anchored = if Path::new(pattern).is_absolute() || pattern.starts_with("**")
+                    {
+                        pattern.clone()
+                    } else {
+                        format!("**/{pattern}")
+                    };
+                    GlobPattern::new(&anchored)
+                        .map_err(|e| CliError::User(format!("{flag} {pattern}: {e}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: compile("--include", include)?,
+            exclude: compile("--exclude", exclude)?,
+        })
+    }
+
+    fn admits(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|glob| glob.matches_within(path)))
+            && !self.exclude.iter().any(|glob| glob.matches_within(path))
+            && !self.excluded_below(path, EXCLUDE_SEARCH_DEPTH)
+    }
+
+    /// Whether something inside `dir` is excluded; deleting `dir` would
+    /// take it along.
+    fn excluded_below(&self, dir: &Path, depth: usize) -> bool {
+        if self.exclude.is_empty() || depth == 0 {
+            return false;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return false;
+        };
+        entries.flatten().any(|entry| {
+            let path = entry.path();
+            self.exclude.iter().any(|glob| glob.matches(&path))
+                || (entry.file_type().is_ok_and(|kind| kind.is_dir())
+                    && self.excluded_below(&path, depth - 1))
+        })
+    }
+
+    /// Human summary such as "not matching **/release/**", or `None` when
+    /// unfiltered.
+    fn describe(&self) -> Option<String> {
+        let list = |globs: &[GlobPattern]| {
+            globs
+                .iter()
+                .map(GlobPattern::as_str)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        let include =
+            (!self.include.is_empty()).then(|| format!("matching {}", list(&self.include)));
+        let exclude =
+            (!self.exclude.is_empty()).then(|| format!("not matching {}", list(&self.exclude)));
+        match (include, exclude) {
+            (Some(include), Some(exclude)) => Some(format!("{include} and {exclude}")),
+            (one, other) => one.or(other),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let list =
+            |globs: &[GlobPattern]| -> Value { globs.iter().map(GlobPattern::as_str).collect() };
+        json!({
+            "include": list(&self.include),
+            "exclude": list(&self.exclude),
+        })
+    }
+}
+
+/// Filters applied to scored candidates beyond `--min-score`.
+#[derive(Debug, Clone, Default)]
+struct CandidateFilter {
+    ages: AgeWindow,
+    paths: PathGlobs,
+}
+
+impl CandidateFilter {
+    fn admits(&self, score: &CandidacyScore) -> bool {
+        self.ages.contains(score.age) && self.paths.admits(&score.path)
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn run_stats(cli: &Cli, args: &StatsArgs) -> Result<(), CliError> {
     let config =
@@ -1203,7 +2617,7 @@ fn run_stats(cli: &Cli, args: &StatsArgs) -> Result<(), CliError> {
 
     // JSON mode: delegate to export_json or build custom payload.
     if output_mode(cli) == OutputMode::Json {
-        return run_stats_json(&engine, args, specific_window);
+        return run_stats_json(&engine, &config, args, specific_window);
     }
 
     // Human output.
@@ -1313,63 +2727,203 @@ fn run_stats(cli: &Cli, args: &StatsArgs) -> Result<(), CliError> {
         println!();
     }
 
+    // Per-user accounting.
+    if args.by_user {
+        let window = specific_window.unwrap_or(std::time::Duration::from_secs(24 * 3600));
+        let users = user_accounting(&engine, &config, window)?;
+
+        println!("Per-User Accounting (last {}):", window_label(window));
+        if users.is_empty() {
+            println!("  (none)");
+        } else {
+            println!(
+                "  {:<16}  {:>9}  {:>10}  {:>10}  {:>11}",
+                "User", "Deletions", "Reclaimed", "Written", "Protections"
+            );
+            println!("  {}", "-".repeat(64));
+            for (user, protections) in &users {
+                println!(
+                    "  {:<16}  {:>9}  {:>10}  {:>10}  {:>11}",
+                    user_label(user.uid),
+                    user.deletions,
+                    format_bytes(user.bytes_reclaimed),
+                    format_bytes(user.bytes_written),
+                    protections,
+                );
+            }
+        }
+        println!();
+    }
+
+    // Grouped breakdown.
+    if let Some(group_by) = args.group_by {
+        let window = specific_window.unwrap_or(std::time::Duration::from_secs(24 * 3600));
+        let groups = engine
+            .grouped(window, group_by)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+        println!("By {group_by} (last {}):", window_label(window));
+        if groups.is_empty() {
+            println!("  (none)");
+        } else {
+            println!(
+                "  {:<24}  {:>9}  {:>10}  {:>8}  {:>7}",
+                "Group", "Deletions", "Freed", "Failures", "Ballast"
+            );
+            println!("  {}", "-".repeat(66));
+            for group in &groups {
+                println!(
+                    "  {:<24}  {:>9}  {:>10}  {:>8}  {:>7}",
+                    truncate_path(Path::new(&group.key), 24),
+                    group.deletions,
+                    format_bytes(group.bytes_freed),
+                    group.failures,
+                    group.ballast_released,
+                );
+            }
+        }
+        println!();
+    }
+
     Ok(())
 }
 
-fn run_stats_json(
+/// `stats --by-user`: database accounting joined with `.sbh-protect` markers
+/// under the configured roots, counted by marker owner.
+fn user_accounting(
     engine: &StatsEngine<'_>,
-    args: &StatsArgs,
-    specific_window: Option<std::time::Duration>,
-) -> Result<(), CliError> {
-    let mut payload = if let Some(window) = specific_window {
-        let ws = engine
-            .window_stats(window)
-            .map_err(|e| CliError::Runtime(e.to_string()))?;
-        let full = engine
-            .export_json()
-            .map_err(|e| CliError::Runtime(e.to_string()))?;
-        // Filter to just the requested window.
-        let windows = full
-            .get("windows")
-            .and_then(|w| w.as_array())
-            .cloned()
-            .unwrap_or_default();
-        let matched: Vec<_> = windows
-            .into_iter()
-            .filter(|w| w.get("window_secs").and_then(Value::as_u64) == Some(window.as_secs()))
-            .collect();
-        if matched.is_empty() {
-            // Build from the queried stats directly.
-            json!({
-                "command": "stats",
-                "window_secs": window.as_secs(),
-                "window_label": window_label(window),
-                "deletions": {
-                    "count": ws.deletions.count,
-                    "total_bytes_freed": ws.deletions.total_bytes_freed,
-                    "avg_size": ws.deletions.avg_size,
-                    "median_size": ws.deletions.median_size,
-                    "failures": ws.deletions.failures,
-                },
-                "ballast": {
-                    "files_released": ws.ballast.files_released,
-                    "files_replenished": ws.ballast.files_replenished,
-                    "current_inventory": ws.ballast.current_inventory,
-                    "bytes_available": ws.ballast.bytes_available,
-                },
-                "pressure": {
-                    "current_level": ws.pressure.current_level.as_str(),
-                    "worst_level": ws.pressure.worst_level_reached.as_str(),
-                    "current_free_pct": ws.pressure.current_free_pct,
-                    "transitions": ws.pressure.transitions,
-                },
-            })
-        } else {
-            json!({
-                "command": "stats",
-                "windows": matched,
-            })
+    config: &Config,
+    window: std::time::Duration,
+) -> Result<Vec<(UserStat, usize)>, CliError> {
+    let mut users = engine
+        .user_stats(window)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    let mut registry =
+        ProtectionRegistry::new(None).map_err(|e| CliError::Runtime(e.to_string()))?;
+    for root in &config.scanner.root_paths {
+        let _ = registry.discover_markers(root, 3);
+    }
+    let mut protections: HashMap<Option<u32>, usize> = HashMap::new();
+    for entry in registry.list_protections() {
+        let uid = path_owner(&entry.path.join(protection::MARKER_FILENAME));
+        *protections.entry(uid).or_default() += 1;
+    }
+
+    for uid in protections.keys() {
+        if !users.iter().any(|user| user.uid == *uid) {
+            users.push(UserStat {
+                uid: *uid,
+                ..UserStat::default()
+            });
+        }
+    }
+    Ok(users
+        .into_iter()
+        .map(|user| {
+            let count = protections.get(&user.uid).copied().unwrap_or(0);
+            (user, count)
+        })
+        .collect())
+}
+
+fn path_owner(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        std::fs::symlink_metadata(path).ok().map(|meta| meta.uid())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Login name for `uid`, falling back to the number; `None` is unattributed.
+fn user_label(uid: Option<u32>) -> String {
+    let Some(uid) = uid else {
+        return "(unknown)".to_string();
+    };
+    #[cfg(unix)]
+    {
+        if let Ok(Some(user)) = nix::unistd::User::from_uid(nix::unistd::Uid::from_raw(uid)) {
+            return user.name;
         }
+    }
+    uid.to_string()
+}
+
+/// `sbh stats --window` JSON for one window.
+fn stats_window_json(
+    engine: &StatsEngine<'_>,
+    window: std::time::Duration,
+) -> Result<Value, CliError> {
+    let ws = engine
+        .window_stats(window)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let full = engine
+        .export_json()
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    // Filter to just the requested window.
+    let windows = full
+        .get("windows")
+        .and_then(|w| w.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let matched: Vec<_> = windows
+        .into_iter()
+        .filter(|w| w.get("window_secs").and_then(Value::as_u64) == Some(window.as_secs()))
+        .collect();
+    let payload = if matched.is_empty() {
+        // Build from the queried stats directly.
+        json!({
+            "command": "stats",
+            "window_secs": window.as_secs(),
+            "window_label": window_label(window),
+            "deletions": {
+                "count": ws.deletions.count,
+                "total_bytes_freed": ws.deletions.total_bytes_freed,
+                "avg_size": ws.deletions.avg_size,
+                "median_size": ws.deletions.median_size,
+                "failures": ws.deletions.failures,
+                "top_categories": categories_json(&ws.deletions.top_categories),
+            },
+            "ballast": {
+                "files_released": ws.ballast.files_released,
+                "files_replenished": ws.ballast.files_replenished,
+                "current_inventory": ws.ballast.current_inventory,
+                "bytes_available": ws.ballast.bytes_available,
+            },
+            "pressure": {
+                "green_pct": ws.pressure.time_in_green_pct,
+                "yellow_pct": ws.pressure.time_in_yellow_pct,
+                "orange_pct": ws.pressure.time_in_orange_pct,
+                "red_pct": ws.pressure.time_in_red_pct,
+                "critical_pct": ws.pressure.time_in_critical_pct,
+                "current_level": ws.pressure.current_level.as_str(),
+                "worst_level": ws.pressure.worst_level_reached.as_str(),
+                "current_free_pct": ws.pressure.current_free_pct,
+                "transitions": ws.pressure.transitions,
+            },
+        })
+    } else {
+        json!({
+            "command": "stats",
+            "windows": matched,
+        })
+    };
+    Ok(payload)
+}
+
+fn run_stats_json(
+    engine: &StatsEngine<'_>,
+    config: &Config,
+    args: &StatsArgs,
+    specific_window: Option<std::time::Duration>,
+) -> Result<(), CliError> {
+    let mut payload = if let Some(window) = specific_window {
+        stats_window_json(engine, window)?
     } else {
         let mut full = engine
             .export_json()
@@ -1417,6 +2971,56 @@ fn run_stats_json(
         }
     }
 
+    // Attach by_user if requested.
+    if args.by_user {
+        let window = specific_window.unwrap_or(std::time::Duration::from_secs(24 * 3600));
+        let users_json: Vec<Value> = user_accounting(engine, config, window)?
+            .iter()
+            .map(|(user, protections)| {
+                json!({
+                    "uid": user.uid,
+                    "user": user.uid.map(|uid| user_label(Some(uid))),
+                    "deletions": user.deletions,
+                    "bytes_reclaimed": user.bytes_reclaimed,
+                    "bytes_written": user.bytes_written,
+                    "protections": protections,
+                })
+            })
+            .collect();
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("by_user".to_string(), json!(users_json));
+        }
+    }
+
+    // Attach group_by if requested.
+    if let Some(group_by) = args.group_by {
+        let window = specific_window.unwrap_or(std::time::Duration::from_secs(24 * 3600));
+        let groups_json: Vec<Value> = engine
+            .grouped(window, group_by)
+            .map_err(|e| CliError::Runtime(e.to_string()))?
+            .iter()
+            .map(|g| {
+                json!({
+                    "key": g.key,
+                    "deletions": g.deletions,
+                    "bytes_freed": g.bytes_freed,
+                    "failures": g.failures,
+                    "ballast_released": g.ballast_released,
+                })
+            })
+            .collect();
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert(
+                "group_by".to_string(),
+                json!({
+                    "key": group_by.as_str(),
+                    "window_secs": window.as_secs(),
+                    "groups": groups_json,
+                }),
+            );
+        }
+    }
+
     write_json_line(&payload)?;
     Ok(())
 }
@@ -1445,6 +3049,16 @@ fn print_window_stats_human(ws: &storage_ballast_helper::logger::stats::WindowSt
         if let Some(cat) = &ws.deletions.most_common_category {
             println!("    Top pattern: {cat}");
         }
+        if !ws.deletions.top_categories.is_empty() {
+            let categories: Vec<String> = ws
+                .deletions
+                .top_categories
+                .iter()
+                .take(3)
+                .map(|c| format!("{} {}", c.category, format_bytes(c.total_bytes)))
+                .collect();
+            println!("    Categories:  {}", categories.join(", "));
+        }
     }
     if ws.deletions.failures > 0 {
         println!("    Failures:    {}", ws.deletions.failures);
@@ -1466,6 +3080,14 @@ fn print_window_stats_human(ws: &storage_ballast_helper::logger::stats::WindowSt
     );
     println!("    Worst:       {}", ws.pressure.worst_level_reached);
     println!("    Transitions: {}", ws.pressure.transitions);
+    println!(
+        "    Time:        green {:.0}%, yellow {:.0}%, orange {:.0}%, red {:.0}%, critical {:.0}%",
+        ws.pressure.time_in_green_pct,
+        ws.pressure.time_in_yellow_pct,
+        ws.pressure.time_in_orange_pct,
+        ws.pressure.time_in_red_pct,
+        ws.pressure.time_in_critical_pct,
+    );
 }
 
 #[allow(
@@ -1480,14 +3102,6 @@ fn print_pressure_bar(label: &str, pct: f64) {
     println!("    {label:<9} {pct:>5.1}% |{bar:<bar_width$}|");
 }
 
-/// Information about a running process for blame attribution.
-#[derive(Debug, Clone)]
-struct ProcessBlameInfo {
-    pid: u32,
-    comm: String,
-    cwd: PathBuf,
-}
-
 /// A group of artifacts attributed to a single process or "orphaned".
 #[derive(Debug, Clone)]
 struct BlameGroup {
@@ -1499,65 +3113,134 @@ struct BlameGroup {
     newest: Option<SystemTime>,
 }
 
-#[allow(unused_mut)]
-fn collect_process_info() -> Vec<ProcessBlameInfo> {
-    let mut procs = Vec::new();
-
-    #[cfg(target_os = "linux")]
-    {
-        let Ok(proc_dir) = std::fs::read_dir("/proc") else {
-            return procs;
-        };
-
-        for entry in proc_dir {
-            let Ok(entry) = entry else {
-                continue;
-            };
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-
-            if !name_str.chars().all(|c| c.is_ascii_digit()) {
-                continue;
-            }
-            let Ok(pid) = name_str.parse::<u32>() else {
-                continue;
-            };
+fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
+    if args.watch {
+        return run_blame_watch(cli, args);
+    }
+    if let Some(spec) = &args.incident {
+        return run_blame_incident(cli, args, spec);
+    }
+    if args.artifacts {
+        return run_blame_artifacts(cli, args);
+    }
+    run_blame_processes(cli, args)
+}
 
-            let proc_path = entry.path();
+/// Sample every process's write counter twice, `--window-secs` apart, and
+/// rank the writers, totalled per agent as well.
+#[allow(clippy::too_many_lines)]
+fn run_blame_processes(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
+    let mode = output_mode(cli);
+    let window = std::time::Duration::from_secs(args.window_secs.max(1));
+    if mode == OutputMode::Human {
+        println!(
+            "Sampling process write counters for {}s...",
+            window.as_secs()
+        );
+    }
+    let before = collect_processes();
+    let started = std::time::Instant::now();
+    std::thread::sleep(window);
+    let after = collect_processes();
+    let elapsed = started.elapsed();
+    let (mut processes, agents) = blame_processes(&before, &after, elapsed);
+    processes.truncate(args.top);
+    let readable = after.iter().filter(|p| p.write_bytes.is_some()).count();
 
-            // Read cwd symlink.
-            let Ok(cwd) = std::fs::read_link(proc_path.join("cwd")) else {
-                continue;
-            };
-            if !cwd.is_absolute() {
-                continue;
+    match mode {
+        OutputMode::Human => {
+            println!();
+            if processes.is_empty() {
+                println!("  No readable process has written to disk.");
+            } else {
+                println!(
+                    "  {:<8}  {:<16}  {:<12}  {:>12}  {:>13}  Working Dir",
+                    "PID", "Process", "Agent", "Write Rate", "Total Written"
+                );
+                println!("  {}", "-".repeat(84));
+                for proc in &processes {
+                    println!(
+                        "  {:<8}  {:<16}  {:<12}  {:>12}  {:>13}  {}",
+                        proc.pid,
+                        proc.comm,
+                        proc.agent.unwrap_or("-"),
+                        format_rate(proc.bytes_per_sec),
+                        format_bytes(proc.total_bytes),
+                        proc.cwd.display(),
+                    );
+                }
             }
-
-            // Read comm (process name).
-            let comm = std::fs::read_to_string(proc_path.join("comm"))
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-
-            if comm.is_empty() {
-                continue;
+            if !agents.is_empty() {
+                println!();
+                println!("  By agent:");
+                for agent in &agents {
+                    println!(
+                        "    {:<12}  {:>3} processes  {:>12}  {:>13} total",
+                        agent.agent,
+                        agent.processes,
+                        format_rate(agent.bytes_per_sec),
+                        format_bytes(agent.total_bytes),
+                    );
+                }
             }
-
-            procs.push(ProcessBlameInfo { pid, comm, cwd });
+            println!();
+            println!(
+                "  I/O counters readable for {readable} of {} processes{}. Totals count writes since each process started.",
+                after.len(),
+                if readable < after.len() {
+                    " (run as root to see all)"
+                } else {
+                    ""
+                },
+            );
+        }
+        OutputMode::Json => {
+            let processes_json: Vec<Value> = processes
+                .iter()
+                .map(|proc| {
+                    json!({
+                        "pid": proc.pid,
+                        "comm": proc.comm,
+                        "agent": proc.agent,
+                        "cwd": proc.cwd.to_string_lossy(),
+                        "bytes_per_sec": proc.bytes_per_sec,
+                        "total_bytes": proc.total_bytes,
+                    })
+                })
+                .collect();
+            let agents_json: Vec<Value> = agents
+                .iter()
+                .map(|agent| {
+                    json!({
+                        "agent": agent.agent,
+                        "processes": agent.processes,
+                        "bytes_per_sec": agent.bytes_per_sec,
+                        "total_bytes": agent.total_bytes,
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "blame",
+                "mode": "processes",
+                "window_ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                "processes": processes_json,
+                "agents": agents_json,
+                "processes_scanned": after.len(),
+                "processes_readable": readable,
+            }))?;
         }
     }
-
-    procs
+    Ok(())
 }
 
 #[allow(clippy::too_many_lines)]
-fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
+fn run_blame_artifacts(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
     let config =
         Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
     let start = std::time::Instant::now();
 
     // Collect process information.
-    let processes = collect_process_info();
+    let processes = collect_processes();
 
     // Walk the configured roots for build artifacts.
     // Canonicalize to ensure absolute paths for system protection checks.
@@ -1600,7 +3283,9 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
             .cloned()
             .collect::<HashSet<_>>(),
     };
-    let walker = DirectoryWalker::new(walker_config, protection);
+    let walker = DirectoryWalker::new(walker_config, protection)
+        .with_artifact_roots(config.scanner.artifact_root_sampling())
+        .with_device_limits(config.scanner.device_limits());
 
     let entries = walker
         .walk()
@@ -1751,70 +3436,441 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
     Ok(())
 }
 
-// ──────────────────── tuning engine ────────────────────
+const BYTES_PER_MIB: f64 = 1_048_576.0;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TuningCategory {
-    Ballast,
-    Threshold,
-    Scoring,
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_rate(bytes_per_sec: f64) -> String {
+    let sign = if bytes_per_sec < 0.0 { "-" } else { "" };
+    format!("{sign}{}/s", format_bytes(bytes_per_sec.abs() as u64))
 }
 
-impl std::fmt::Display for TuningCategory {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Ballast => f.write_str("Ballast"),
-            Self::Threshold => f.write_str("Threshold"),
-            Self::Scoring => f.write_str("Scoring"),
-        }
+fn run_blame_watch(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
+    let mode = output_mode(cli);
+    validate_live_mode_output(mode, "blame --watch", true)?;
+    let refresh = std::time::Duration::from_millis(normalize_refresh_ms(args.refresh_ms));
+    let threshold_bytes_per_sec = args.threshold.unwrap_or(0.0).max(0.0) * BYTES_PER_MIB;
+
+    let mut sampled_at = std::time::Instant::now();
+    let (_, mut samples) = compute_writer_rates(
+        &HashMap::new(),
+        &collect_processes(),
+        refresh,
+        threshold_bytes_per_sec,
+    );
+    if mode == OutputMode::Human {
+        println!("Sampling process write counters...");
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TuningRisk {
-    Low,
-    Medium,
-    #[allow(dead_code)] // scaffolding for PID-tuning recommendations
-    High,
+    loop {
+        std::thread::sleep(refresh);
+        let processes = collect_processes();
+        let now = std::time::Instant::now();
+        let (mut writers, next) = compute_writer_rates(
+            &samples,
+            &processes,
+            now.duration_since(sampled_at),
+            threshold_bytes_per_sec,
+        );
+        samples = next;
+        sampled_at = now;
+        writers.truncate(args.top);
+        render_blame_watch_frame(mode, refresh, threshold_bytes_per_sec, &writers, &processes)?;
+    }
 }
 
-impl std::fmt::Display for TuningRisk {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Low => f.write_str("low"),
-            Self::Medium => f.write_str("medium"),
-            Self::High => f.write_str("high"),
+/// Draw one `blame --watch` refresh: a cleared screen with the writer table,
+/// or one JSON line.
+fn render_blame_watch_frame(
+    mode: OutputMode,
+    refresh: std::time::Duration,
+    threshold_bytes_per_sec: f64,
+    writers: &[WriterRate],
+    processes: &[ProcessIo],
+) -> Result<(), CliError> {
+    let readable = processes.iter().filter(|p| p.write_bytes.is_some()).count();
+    match mode {
+        OutputMode::Human => {
+            print!("\x1B[2J\x1B[H");
+            println!(
+                "Live Write Attribution (every {:.1}s, >= {}):",
+                refresh.as_secs_f64(),
+                format_rate(threshold_bytes_per_sec),
+            );
+            println!();
+            if writers.is_empty() {
+                println!("  No process is writing above the threshold.");
+            } else {
+                println!(
+                    "    {:<8}  {:<20}  {:>14}  {:>15}  Working Dir",
+                    "PID", "Process", "Write Rate", "Change"
+                );
+                println!("  {}", "-".repeat(76));
+                for writer in writers {
+                    let change = format_rate(writer.change_bytes_per_sec);
+                    println!(
+                        "  {} {:<8}  {:<20}  {:>14}  {:>15}  {}",
+                        if writer.highlighted { '*' } else { ' ' },
+                        writer.pid,
+                        writer.comm,
+                        format_rate(writer.bytes_per_sec),
+                        if writer.change_bytes_per_sec > 0.0 {
+                            format!("+{change}")
+                        } else {
+                            change
+                        },
+                        writer.cwd.display(),
+                    );
+                }
+            }
+            println!();
+            println!(
+                "  * largest rate changes. I/O counters readable for {readable} of {} processes{}.",
+                processes.len(),
+                if readable < processes.len() {
+                    " (run as root to see all)"
+                } else {
+                    ""
+                },
+            );
+            println!(
+                "  Refreshing every {}ms (Ctrl-C to exit)",
+                refresh.as_millis()
+            );
+        }
+        OutputMode::Json => {
+            let writers_json: Vec<Value> = writers
+                .iter()
+                .map(|w| {
+                    json!({
+                        "pid": w.pid,
+                        "comm": w.comm,
+                        "cwd": w.cwd.to_string_lossy(),
+                        "bytes_per_sec": w.bytes_per_sec,
+                        "change_bytes_per_sec": w.change_bytes_per_sec,
+                        "highlighted": w.highlighted,
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "blame",
+                "mode": "watch",
+                "refresh_ms": u64::try_from(refresh.as_millis()).unwrap_or(u64::MAX),
+                "threshold_bytes_per_sec": threshold_bytes_per_sec,
+                "writers": writers_json,
+                "processes_scanned": processes.len(),
+                "processes_readable": readable,
+            }))?;
         }
     }
+    io::stdout().flush()?;
+    Ok(())
 }
 
-#[derive(Debug, Clone)]
-struct Recommendation {
-    category: TuningCategory,
-    config_key: String,
-    current_value: String,
-    suggested_value: String,
-    rationale: String,
-    confidence: f64,
-    risk: TuningRisk,
+/// How long after an incident cleanup activity is attributed to it.
+const INCIDENT_CLEANUP_WINDOW_SECS: i64 = 1_800;
+
+/// Pressure levels (as logged) matched by a `--incident` spec: the named
+/// level and anything worse.
+fn incident_levels(spec: &str) -> Result<&'static [&'static str], CliError> {
+    match spec.trim().to_ascii_lowercase().as_str() {
+        "last" | "last-orange" => Ok(&["Orange", "Red", "Critical"]),
+        "last-red" => Ok(&["Red", "Critical"]),
+        "last-critical" => Ok(&["Critical"]),
+        _ => Err(CliError::User(format!(
+            "unknown incident '{spec}'; expected last, last-orange, last-red, or last-critical"
+        ))),
+    }
 }
 
-#[allow(
-    clippy::too_many_lines,
-    clippy::cast_precision_loss,
-    clippy::cast_possible_truncation,
-    clippy::cast_sign_loss
-)]
-fn generate_recommendations(
-    config: &Config,
-    stats: &[storage_ballast_helper::logger::stats::WindowStats],
-) -> Vec<Recommendation> {
-    let mut recs = Vec::new();
+#[allow(clippy::too_many_lines)]
+fn run_blame_incident(cli: &Cli, args: &BlameArgs, spec: &str) -> Result<(), CliError> {
+    let levels = incident_levels(spec)?;
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
 
-    // Use the 24-hour window for most analysis (index 4 in STANDARD_WINDOWS).
-    let day_stats = stats.iter().find(|ws| ws.window.as_secs() == 86_400);
-    // Use the 7-day window for trend analysis.
-    let week_stats = stats.iter().find(|ws| ws.window.as_secs() == 604_800);
+    if !config.paths.sqlite_db.exists() {
+        match output_mode(cli) {
+            OutputMode::Human => {
+                println!(
+                    "No activity database found at {}.",
+                    config.paths.sqlite_db.display()
+                );
+                println!("  Run the daemon to start recording incidents.");
+            }
+            OutputMode::Json => {
+                write_json_line(&json!({
+                    "command": "blame",
+                    "mode": "incident",
+                    "error": "no_database",
+                    "db_path": config.paths.sqlite_db.to_string_lossy(),
+                }))?;
+            }
+        }
+        return Ok(());
+    }
+
+    let db = SqliteLogger::open(&config.paths.sqlite_db)
+        .map_err(|e| CliError::Runtime(format!("open activity database: {e}")))?;
+    let event = db
+        .recent_activity_of_type("attribution_snapshot", 1_000)
+        .map_err(|e| CliError::Runtime(e.to_string()))?
+        .into_iter()
+        .find(|row| {
+            row.pressure_level
+                .as_deref()
+                .is_some_and(|level| levels.iter().any(|l| l.eq_ignore_ascii_case(level)))
+        });
+    let Some(event) = event else {
+        match output_mode(cli) {
+            OutputMode::Human => {
+                println!("No {spec} incident recorded.");
+                println!(
+                    "  The daemon snapshots recent writers when pressure escalates to orange or worse."
+                );
+            }
+            OutputMode::Json => {
+                write_json_line(&json!({
+                    "command": "blame",
+                    "mode": "incident",
+                    "incident": spec,
+                    "event": null,
+                }))?;
+            }
+        }
+        return Ok(());
+    };
+
+    let mut snapshot: AttributionSnapshot =
+        serde_json::from_str(event.details.as_deref().unwrap_or_default()).map_err(|e| {
+            CliError::Runtime(format!(
+                "malformed attribution snapshot at {}: {e}",
+                event.timestamp
+            ))
+        })?;
+    let workspaces = snapshot.workspaces();
+    snapshot.writers.truncate(args.top);
+
+    let until = chrono::DateTime::parse_from_rfc3339(&event.timestamp)
+        .map(|at| {
+            (at + chrono::Duration::seconds(INCIDENT_CLEANUP_WINDOW_SECS))
+                .with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        })
+        .map_err(|e| CliError::Runtime(format!("bad incident timestamp: {e}")))?;
+    let mount = event.path.as_deref().map(Path::new);
+    let cleanup: Vec<ActivityRow> = db
+        .activity_between(&event.timestamp, &until)
+        .map_err(|e| CliError::Runtime(e.to_string()))?
+        .into_iter()
+        .filter(|row| {
+            matches!(
+                row.event_type.as_str(),
+                "artifact_delete" | "ballast_release"
+            )
+        })
+        .filter(|row| {
+            mount.is_none_or(|mount| {
+                row.path
+                    .as_deref()
+                    .is_none_or(|path| Path::new(path).starts_with(mount))
+            })
+        })
+        .collect();
+    let row_bytes = |row: &ActivityRow| u64::try_from(row.size_bytes.unwrap_or(0)).unwrap_or(0);
+    let mut deletions: Vec<&ActivityRow> = cleanup
+        .iter()
+        .filter(|row| row.event_type == "artifact_delete" && row.success == 1)
+        .collect();
+    let deletions_failed = cleanup
+        .iter()
+        .filter(|row| row.event_type == "artifact_delete" && row.success == 0)
+        .count();
+    let bytes_freed: u64 = deletions.iter().map(|row| row_bytes(row)).sum();
+    let ballast_released: u64 = cleanup
+        .iter()
+        .filter(|row| row.event_type == "ballast_release")
+        .map(row_bytes)
+        .sum();
+    deletions.sort_by_key(|row| std::cmp::Reverse(row_bytes(row)));
+
+    let mount_label = event.path.as_deref().unwrap_or("?");
+    let level = event.pressure_level.as_deref().unwrap_or("?");
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "{level} incident on {mount_label} at {} ({:.1}% free)",
+                event.timestamp,
+                event.free_pct.unwrap_or(0.0),
+            );
+            println!();
+            println!(
+                "Writers in the {} before the event:",
+                format_duration(std::time::Duration::from_secs(snapshot.window_secs))
+            );
+            if snapshot.writers.is_empty() {
+                println!("  No process writes were sampled.");
+            } else {
+                println!(
+                    "  {:<8}  {:<20}  {:>10}  {:>14}  Working Dir",
+                    "PID", "Process", "Written", "Peak Rate"
+                );
+                println!("  {}", "-".repeat(76));
+                for writer in &snapshot.writers {
+                    println!(
+                        "  {:<8}  {:<20}  {:>10}  {:>14}  {}",
+                        writer.pid,
+                        writer.comm,
+                        format_bytes(writer.bytes_written),
+                        format_rate(writer.peak_bytes_per_sec),
+                        writer.cwd,
+                    );
+                }
+                println!();
+                println!("  By workspace:");
+                for workspace in workspaces.iter().take(args.top) {
+                    println!(
+                        "    {:>10}  {} process(es)  {}",
+                        format_bytes(workspace.bytes_written),
+                        workspace.processes,
+                        workspace.cwd,
+                    );
+                }
+            }
+            println!();
+            println!(
+                "Cleanup in the {} after:",
+                format_duration(std::time::Duration::from_secs(
+                    INCIDENT_CLEANUP_WINDOW_SECS.unsigned_abs()
+                ))
+            );
+            println!(
+                "  {} artifacts deleted ({} freed), {} failed, {} ballast released",
+                deletions.len(),
+                format_bytes(bytes_freed),
+                deletions_failed,
+                format_bytes(ballast_released),
+            );
+            for row in deletions.iter().take(10) {
+                println!(
+                    "    {:>10}  {}",
+                    format_bytes(row_bytes(row)),
+                    row.path.as_deref().unwrap_or("-"),
+                );
+            }
+        }
+        OutputMode::Json => {
+            let workspaces_json: Vec<Value> = workspaces
+                .iter()
+                .take(args.top)
+                .map(|w| {
+                    json!({
+                        "cwd": w.cwd,
+                        "bytes_written": w.bytes_written,
+                        "processes": w.processes,
+                    })
+                })
+                .collect();
+            let deletions_json: Vec<Value> = deletions
+                .iter()
+                .map(|row| {
+                    json!({
+                        "timestamp": row.timestamp,
+                        "path": row.path,
+                        "size_bytes": row_bytes(row),
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "blame",
+                "mode": "incident",
+                "incident": spec,
+                "event": {
+                    "timestamp": event.timestamp,
+                    "mount_point": event.path,
+                    "pressure": event.pressure_level,
+                    "free_pct": event.free_pct,
+                },
+                "window_secs": snapshot.window_secs,
+                "writers": snapshot.writers,
+                "workspaces": workspaces_json,
+                "cleanup": {
+                    "window_secs": INCIDENT_CLEANUP_WINDOW_SECS,
+                    "artifacts_deleted": deletions.len(),
+                    "deletions_failed": deletions_failed,
+                    "bytes_freed": bytes_freed,
+                    "ballast_released_bytes": ballast_released,
+                    "deletions": deletions_json,
+                },
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+// ──────────────────── tuning engine ────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TuningCategory {
+    Ballast,
+    Threshold,
+    Scoring,
+}
+
+impl std::fmt::Display for TuningCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ballast => f.write_str("Ballast"),
+            Self::Threshold => f.write_str("Threshold"),
+            Self::Scoring => f.write_str("Scoring"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TuningRisk {
+    Low,
+    Medium,
+    #[allow(dead_code)] // scaffolding for PID-tuning recommendations
+    High,
+}
+
+impl std::fmt::Display for TuningRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => f.write_str("low"),
+            Self::Medium => f.write_str("medium"),
+            Self::High => f.write_str("high"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Recommendation {
+    category: TuningCategory,
+    config_key: String,
+    current_value: String,
+    suggested_value: String,
+    rationale: String,
+    confidence: f64,
+    risk: TuningRisk,
+}
+
+#[allow(
+    clippy::too_many_lines,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn generate_recommendations(
+    config: &Config,
+    stats: &[storage_ballast_helper::logger::stats::WindowStats],
+) -> Vec<Recommendation> {
+    let mut recs = Vec::new();
+
+    // Use the 24-hour window for most analysis (index 4 in STANDARD_WINDOWS).
+    let day_stats = stats.iter().find(|ws| ws.window.as_secs() == 86_400);
+    // Use the 7-day window for trend analysis.
+    let week_stats = stats.iter().find(|ws| ws.window.as_secs() == 604_800);
     // Use the 1-hour window for recent activity.
     let hour_stats = stats.iter().find(|ws| ws.window.as_secs() == 3_600);
 
@@ -1977,6 +4033,31 @@ fn generate_recommendations(
     recs
 }
 
+#[allow(clippy::too_many_lines)]
+/// The pending burn-in promotions, ahead of the tuning recommendations.
+fn print_burn_in_human(burn_in: &BurnIn, config: &Config) {
+    let since = |at: chrono::DateTime<chrono::Utc>| at.format("%Y-%m-%d %H:%M UTC").to_string();
+    println!(
+        "Burn-in: fresh install since {}, deletions held to {} mode",
+        since(burn_in.started_at),
+        burn_in.stage,
+    );
+    match burn_in.clean_since {
+        Some(clean_since) => println!("  Clean guard windows since {}", since(clean_since)),
+        None => println!("  Clock stopped: guardrail breach in progress"),
+    }
+    if burn_in.restarts > 0 {
+        println!("  Restarted by {} breach(es)", burn_in.restarts);
+    }
+    for step in burn_in.schedule(&config.policy) {
+        let at = step
+            .at
+            .map_or_else(|| "after the breach clears".to_string(), since);
+        println!("  -> {:<8} {at}", step.mode.to_string());
+    }
+    println!();
+}
+
 #[allow(clippy::too_many_lines)]
 fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
     let config =
@@ -2001,11 +4082,18 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
     } else {
         Vec::new()
     };
+    let burn_in = read_burn_in(&burn_in_path(&config.paths.state_file))
+        .ok()
+        .flatten()
+        .filter(BurnIn::is_active);
 
     if !args.apply {
         // Display recommendations.
         match output_mode(cli) {
             OutputMode::Human => {
+                if let Some(burn_in) = &burn_in {
+                    print_burn_in_human(burn_in, &config);
+                }
                 if recs.is_empty() {
                     if db.is_none() {
                         println!("No activity database found. Run the daemon to collect data.");
@@ -2052,6 +4140,13 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
                     "command": "tune",
                     "recommendations": recs_json,
                     "has_database": db.is_some(),
+                    "burn_in": burn_in.as_ref().map(|burn_in| json!({
+                        "started_at": burn_in.started_at,
+                        "stage": burn_in.stage,
+                        "clean_since": burn_in.clean_since,
+                        "restarts": burn_in.restarts,
+                        "schedule": burn_in.schedule(&config.policy),
+                    })),
                 });
                 write_json_line(&payload)?;
             }
@@ -2101,13 +4196,14 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
     // Read existing config TOML.
     let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
 
-    let mut toml_value: toml::Value = if config_path.exists() {
-        let raw = std::fs::read_to_string(&config_path)
-            .map_err(|e| CliError::Runtime(format!("read config: {e}")))?;
-        toml::from_str(&raw).map_err(|e| CliError::Runtime(format!("parse config: {e}")))?
+    let previous_config = if config_path.exists() {
+        std::fs::read_to_string(&config_path)
+            .map_err(|e| CliError::Runtime(format!("read config: {e}")))?
     } else {
-        toml::Value::Table(toml::map::Map::new())
+        String::new()
     };
+    let mut toml_value: toml::Value = toml::from_str(&previous_config)
+        .map_err(|e| CliError::Runtime(format!("parse config: {e}")))?;
 
     // Apply each recommendation.
     let mut applied = Vec::new();
@@ -2125,6 +4221,14 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
         .map_err(|e| CliError::Runtime(format!("serialize config: {e}")))?;
     std::fs::write(&config_path, &toml_str)
         .map_err(|e| CliError::Runtime(format!("write config: {e}")))?;
+    let keys: Vec<&str> = applied.iter().map(|r| r.config_key.as_str()).collect();
+    let probation =
+        start_config_probation(&config, "tune", &keys, previous_config, args.revert_window)?;
+    // Have a running daemon pick the changes up now rather than at restart.
+    let daemon_reloaded = matches!(
+        daemon_request(&config, &ControlRequest::Reload),
+        Some(ControlReply::Accepted)
+    );
 
     match output_mode(cli) {
         OutputMode::Human => {
@@ -2136,6 +4240,12 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
                 );
             }
             println!("\nConfig updated: {}", config_path.display());
+            if daemon_reloaded {
+                println!("Running daemon asked to reload the config.");
+            }
+            if let Some(probation) = &probation {
+                print_probation_notice(probation);
+            }
         }
         OutputMode::Json => {
             let changes: Vec<Value> = applied
@@ -2154,6 +4264,8 @@ fn run_tune(cli: &Cli, args: &TuneArgs) -> Result<(), CliError> {
                 "applied": changes.len(),
                 "changes": changes,
                 "config_path": config_path.to_string_lossy(),
+                "daemon_reloaded": daemon_reloaded,
+                "probation_until": probation.as_ref().map(|p| p.until.as_str()),
             });
             write_json_line(&payload)?;
         }
@@ -2310,17 +4422,20 @@ fn run_config(cli: &Cli, args: &ConfigArgs) -> Result<(), CliError> {
             }
             Ok(())
         }
+        Some(ConfigCommand::Export(export_args)) => run_config_export(cli, export_args),
+        Some(ConfigCommand::Import(import_args)) => run_config_import(cli, import_args),
         Some(ConfigCommand::Set(set_args)) => {
             let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
 
             // Read existing TOML or start from empty table.
-            let mut toml_value: toml::Value = if config_path.exists() {
-                let raw = std::fs::read_to_string(&config_path)
-                    .map_err(|e| CliError::Runtime(format!("read config: {e}")))?;
-                toml::from_str(&raw).map_err(|e| CliError::Runtime(format!("parse config: {e}")))?
+            let previous_config = if config_path.exists() {
+                std::fs::read_to_string(&config_path)
+                    .map_err(|e| CliError::Runtime(format!("read config: {e}")))?
             } else {
-                toml::Value::Table(toml::map::Map::new())
+                String::new()
             };
+            let mut toml_value: toml::Value = toml::from_str(&previous_config)
+                .map_err(|e| CliError::Runtime(format!("parse config: {e}")))?;
 
             // Navigate dot-path and set value.
             set_toml_value(&mut toml_value, &set_args.key, &set_args.value)?;
@@ -2340,16 +4455,26 @@ fn run_config(cli: &Cli, args: &ConfigArgs) -> Result<(), CliError> {
             std::fs::write(&tmp_path, &toml_str)
                 .map_err(|e| CliError::Runtime(format!("write temp config: {e}")))?;
 
-            if let Err(e) = Config::load(Some(&tmp_path)) {
-                let _ = std::fs::remove_file(&tmp_path);
-                return Err(CliError::User(format!(
-                    "refusing to write invalid config: {e}"
-                )));
-            }
+            let new_config = match Config::load(Some(&tmp_path)) {
+                Ok(new_config) => new_config,
+                Err(e) => {
+                    let _ = std::fs::remove_file(&tmp_path);
+                    return Err(CliError::User(format!(
+                        "refusing to write invalid config: {e}"
+                    )));
+                }
+            };
 
             // Validation passed — atomically replace the real config.
             std::fs::rename(&tmp_path, &config_path)
                 .map_err(|e| CliError::Runtime(format!("rename config: {e}")))?;
+            let probation = start_config_probation(
+                &new_config,
+                "config set",
+                &[set_args.key.as_str()],
+                previous_config,
+                set_args.revert_window,
+            )?;
 
             match output_mode(cli) {
                 OutputMode::Human => {
@@ -2359,6 +4484,9 @@ fn run_config(cli: &Cli, args: &ConfigArgs) -> Result<(), CliError> {
                         set_args.value,
                         config_path.display()
                     );
+                    if let Some(probation) = &probation {
+                        print_probation_notice(probation);
+                    }
                 }
                 OutputMode::Json => {
                     let payload = json!({
@@ -2367,6 +4495,7 @@ fn run_config(cli: &Cli, args: &ConfigArgs) -> Result<(), CliError> {
                         "value": set_args.value,
                         "path": config_path.to_string_lossy(),
                         "valid": true,
+                        "probation_until": probation.as_ref().map(|p| p.until.as_str()),
                     });
                     write_json_line(&payload)?;
                 }
@@ -2376,57 +4505,306 @@ fn run_config(cli: &Cli, args: &ConfigArgs) -> Result<(), CliError> {
     }
 }
 
-/// Set a value in a TOML table using a dot-separated path.
-fn set_toml_value(root: &mut toml::Value, dot_path: &str, raw_value: &str) -> Result<(), CliError> {
-    let parts: Vec<&str> = dot_path.split('.').collect();
-    if parts.is_empty() {
-        return Err(CliError::User("empty config key".to_string()));
-    }
-
-    let mut current = root;
-    for &part in &parts[..parts.len() - 1] {
-        current = current
-            .as_table_mut()
-            .ok_or_else(|| CliError::User(format!("key path component is not a table: {part}")))?
-            .entry(part)
-            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+/// The config file at `path` as written, or an empty file (all defaults)
+/// when there is none.
+fn read_config_file(path: &Path) -> Result<String, CliError> {
+    if path.exists() {
+        std::fs::read_to_string(path)
+            .map_err(|e| CliError::Runtime(format!("read config {}: {e}", path.display())))
+    } else {
+        Ok(String::new())
     }
+}
 
-    let table = current
-        .as_table_mut()
-        .ok_or_else(|| CliError::User("parent is not a table".to_string()))?;
-    let key = &parts[parts.len() - 1];
-    table.insert((*key).to_string(), parse_toml_value(raw_value));
+fn run_config_export(cli: &Cli, args: &ConfigExportArgs) -> Result<(), CliError> {
+    let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
+    let raw = read_config_file(&config_path)?;
+    let mut table: toml::Table = toml::from_str(&raw)
+        .map_err(|e| CliError::User(format!("parse config {}: {e}", config_path.display())))?;
+    let stripped = if args.portable {
+        strip_host_specific(&mut table)
+    } else {
+        Vec::new()
+    };
+    let body = toml::to_string_pretty(&table)
+        .map_err(|e| CliError::Runtime(format!("serialize config: {e}")))?;
+    let portable = if args.portable {
+        " (portable: host-specific keys removed)"
+    } else {
+        ""
+    };
+    let text = format!(
+        "# Exported by sbh {} from {}{portable}\n{body}",
+        env!("CARGO_PKG_VERSION"),
+        config_path.display(),
+    );
 
+    // The TOML itself is the output; only a file export reports in JSON.
+    let Some(output) = &args.output else {
+        print!("{text}");
+        return Ok(());
+    };
+    std::fs::write(output, &text)
+        .map_err(|e| CliError::Runtime(format!("write {}: {e}", output.display())))?;
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Exported {} to {}", config_path.display(), output.display());
+            if !stripped.is_empty() {
+                println!("  Left out host-specific keys: {}", stripped.join(", "));
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "config export",
+                "path": config_path.to_string_lossy(),
+                "output": output.to_string_lossy(),
+                "portable": args.portable,
+                "stripped": stripped,
+            });
+            write_json_line(&payload)?;
+        }
+    }
     Ok(())
 }
 
-/// Parse a raw string into a TOML value, guessing the type.
-fn parse_toml_value(raw: &str) -> toml::Value {
-    if let Ok(b) = raw.parse::<bool>() {
-        return toml::Value::Boolean(b);
-    }
-    if let Ok(i) = raw.parse::<i64>() {
-        return toml::Value::Integer(i);
-    }
-    if let Ok(f) = raw.parse::<f64>() {
-        return toml::Value::Float(f);
+fn run_config_import(cli: &Cli, args: &ConfigImportArgs) -> Result<(), CliError> {
+    let source = args.file.to_string_lossy().into_owned();
+    let raw_incoming = if source == "-" {
+        let mut raw = String::new();
+        io::Read::read_to_string(&mut io::stdin(), &mut raw)
+            .map_err(|e| CliError::Runtime(format!("read stdin: {e}")))?;
+        raw
+    } else {
+        std::fs::read_to_string(&args.file)
+            .map_err(|e| CliError::User(format!("read {source}: {e}")))?
+    };
+    let incoming: toml::Table = toml::from_str(&raw_incoming)
+        .map_err(|e| CliError::User(format!("parse {source}: {e}")))?;
+
+    let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
+    let previous_config = read_config_file(&config_path)?;
+    let local: toml::Table = toml::from_str(&previous_config)
+        .map_err(|e| CliError::User(format!("parse config {}: {e}", config_path.display())))?;
+    let local_config = Config::from_toml_str(&previous_config)
+        .map_err(|e| CliError::User(format!("local config {}: {e}", config_path.display())))?;
+    let merged = toml::to_string_pretty(&merge_import(incoming, &local))
+        .map_err(|e| CliError::Runtime(format!("serialize config: {e}")))?;
+    let merged_config = Config::from_toml_str(&merged)
+        .map_err(|e| CliError::User(format!("{source} is not valid here: {e}")))?;
+    let changes = three_way_diff(&Config::default(), &local_config, &merged_config)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    if !args.apply || !args.yes || changes.is_empty() {
+        match output_mode(cli) {
+            OutputMode::Human => print_config_import_human(&source, &config_path, &changes),
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "config import",
+                    "source": source,
+                    "path": config_path.to_string_lossy(),
+                    "applied": false,
+                    "changes": changes,
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        if args.apply && !args.yes {
+            return Err(CliError::User(
+                "use --yes to confirm, or review the diff with `sbh config import` first"
+                    .to_string(),
+            ));
+        }
+        return Ok(());
     }
-    toml::Value::String(raw.to_string())
-}
 
-/// Print a recursive diff of two JSON values.
-fn print_json_diff(prefix: &str, default: &Value, effective: &Value) {
-    match (default, effective) {
-        (Value::Object(def_map), Value::Object(eff_map)) => {
-            let mut all_keys: Vec<&String> = def_map.keys().chain(eff_map.keys()).collect();
-            all_keys.sort();
-            all_keys.dedup();
+    let new_config = write_validated_config(&config_path, &merged)?;
+    let keys: Vec<&str> = changes.iter().map(|change| change.key.as_str()).collect();
+    let probation = start_config_probation(
+        &new_config,
+        "config import",
+        &keys,
+        previous_config,
+        args.revert_window,
+    )?;
+    let daemon_reloaded = matches!(
+        daemon_request(&new_config, &ControlRequest::Reload),
+        Some(ControlReply::Accepted)
+    );
 
-            for key in all_keys {
-                let path = if prefix.is_empty() {
-                    key.clone()
-                } else {
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Imported {source} into {}: {} key(s) changed",
+                config_path.display(),
+                changes.len()
+            );
+            if daemon_reloaded {
+                println!("Running daemon asked to reload the config.");
+            }
+            if let Some(probation) = &probation {
+                print_probation_notice(probation);
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "config import",
+                "source": source,
+                "path": config_path.to_string_lossy(),
+                "applied": true,
+                "changes": changes,
+                "daemon_reloaded": daemon_reloaded,
+                "probation_until": probation.as_ref().map(|p| p.until.as_str()),
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `contents` to `config_path` only if it loads as a valid config,
+/// validating a temp file first so a reloading daemon never sees a bad one.
+fn write_validated_config(config_path: &Path, contents: &str) -> Result<Config, CliError> {
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| CliError::Runtime(format!("create config dir: {e}")))?;
+    }
+    let tmp_path = config_path.with_extension("toml.tmp");
+    std::fs::write(&tmp_path, contents)
+        .map_err(|e| CliError::Runtime(format!("write temp config: {e}")))?;
+    let new_config = match Config::load(Some(&tmp_path)) {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(CliError::User(format!(
+                "refusing to write invalid config: {e}"
+            )));
+        }
+    };
+    std::fs::rename(&tmp_path, config_path)
+        .map_err(|e| CliError::Runtime(format!("rename config: {e}")))?;
+    Ok(new_config)
+}
+
+fn print_config_import_human(source: &str, config_path: &Path, changes: &[ConfigChange]) {
+    println!("--- local ({})", config_path.display());
+    println!("+++ incoming ({source})");
+    if changes.is_empty() {
+        println!("\nNo differences outside host-specific keys.");
+        return;
+    }
+    let count = |kind| changes.iter().filter(|change| change.kind == kind).count();
+    println!(
+        "\n{} key(s) differ: {} incoming, {} local only, {} conflicting. \
+         Host-specific keys keep their local values.\n",
+        changes.len(),
+        count(ConfigChangeKind::Incoming),
+        count(ConfigChangeKind::Local),
+        count(ConfigChangeKind::Conflict),
+    );
+    for change in changes {
+        println!("  [{}] {}", change.kind.as_str(), change.key);
+        println!("      default:  {}", change.default);
+        println!("      local:    {}", change.local);
+        println!("      incoming: {}", change.incoming);
+    }
+    println!("\n  Run `sbh config import {source} --apply --yes` to take the incoming values.");
+}
+
+/// Put enforcement changes just written by `sbh {source}` on probation, so
+/// the daemon restores `previous_config` if its policy engine falls back to
+/// safe mode within `window_minutes`. A change made while an earlier one is
+/// still on probation keeps the earlier saved config.
+fn start_config_probation(
+    config: &Config,
+    source: &str,
+    keys: &[&str],
+    previous_config: String,
+    window_minutes: u64,
+) -> Result<Option<ConfigProbation>, CliError> {
+    let keys: Vec<String> = keys
+        .iter()
+        .filter(|key| is_enforcement_key(key))
+        .map(|key| (*key).to_string())
+        .collect();
+    if keys.is_empty() || window_minutes == 0 {
+        return Ok(None);
+    }
+
+    let path = probation_path(&config.paths.state_file);
+    let now = chrono::Utc::now();
+    let window = std::time::Duration::from_secs(window_minutes.saturating_mul(60));
+    let mut probation = ConfigProbation::new(now, window, source, keys, previous_config);
+    if let Some(pending) = read_probation(&path)
+        .ok()
+        .flatten()
+        .filter(|pending| pending.is_active(now))
+    {
+        probation = pending.extend(probation);
+    }
+    write_probation(&path, &probation)
+        .map_err(|e| CliError::Runtime(format!("write config probation: {e}")))?;
+    Ok(Some(probation))
+}
+
+fn print_probation_notice(probation: &ConfigProbation) {
+    println!(
+        "On probation until {}: the daemon restores the previous config if its policy engine \
+         falls back to safe mode.",
+        probation.until
+    );
+}
+
+/// Set a value in a TOML table using a dot-separated path.
+fn set_toml_value(root: &mut toml::Value, dot_path: &str, raw_value: &str) -> Result<(), CliError> {
+    let parts: Vec<&str> = dot_path.split('.').collect();
+    if parts.is_empty() {
+        return Err(CliError::User("empty config key".to_string()));
+    }
+
+    let mut current = root;
+    for &part in &parts[..parts.len() - 1] {
+        current = current
+            .as_table_mut()
+            .ok_or_else(|| CliError::User(format!("key path component is not a table: {part}")))?
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    }
+
+    let table = current
+        .as_table_mut()
+        .ok_or_else(|| CliError::User("parent is not a table".to_string()))?;
+    let key = &parts[parts.len() - 1];
+    table.insert((*key).to_string(), parse_toml_value(raw_value));
+
+    Ok(())
+}
+
+/// Parse a raw string into a TOML value, guessing the type.
+fn parse_toml_value(raw: &str) -> toml::Value {
+    if let Ok(b) = raw.parse::<bool>() {
+        return toml::Value::Boolean(b);
+    }
+    if let Ok(i) = raw.parse::<i64>() {
+        return toml::Value::Integer(i);
+    }
+    if let Ok(f) = raw.parse::<f64>() {
+        return toml::Value::Float(f);
+    }
+    toml::Value::String(raw.to_string())
+}
+
+/// Print a recursive diff of two JSON values.
+fn print_json_diff(prefix: &str, default: &Value, effective: &Value) {
+    match (default, effective) {
+        (Value::Object(def_map), Value::Object(eff_map)) => {
+            let mut all_keys: Vec<&String> = def_map.keys().chain(eff_map.keys()).collect();
+            all_keys.sort();
+            all_keys.dedup();
+
+            for key in all_keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
                     format!("{prefix}.{key}")
                 };
 
@@ -2456,190 +4834,222 @@ fn print_json_diff(prefix: &str, default: &Value, effective: &Value) {
     }
 }
 
+/// Hand `request` to a running daemon over its control socket; `None` when no
+/// daemon is listening, so the caller runs the command in-process.
+fn daemon_request(config: &Config, request: &ControlRequest) -> Option<ControlReply> {
+    if !config.control.enabled {
+        return None;
+    }
+    let path = config.control.socket_path();
+    match control_socket::send(&path, request) {
+        Ok(reply) => reply,
+        Err(e) => {
+            eprintln!("Warning: daemon control socket unusable, running locally: {e}");
+            None
+        }
+    }
+}
+
+/// Error for a daemon reply other than the one a command expects.
+fn daemon_reply_error(reply: ControlReply) -> CliError {
+    match reply {
+        ControlReply::Error { message } => CliError::Runtime(format!("daemon: {message}")),
+        other => CliError::Internal(format!("unexpected daemon reply: {other:?}")),
+    }
+}
+
+fn print_daemon_release(cli: &Cli, count: usize, reply: ControlReply) -> Result<(), CliError> {
+    let (mount, files_released, bytes_freed, remaining, errors) = match reply {
+        ControlReply::BallastReleased {
+            mount,
+            files_released,
+            bytes_freed,
+            remaining,
+            errors,
+        } => (mount, files_released, bytes_freed, remaining, errors),
+        other => return Err(daemon_reply_error(other)),
+    };
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Ballast release complete (via daemon):");
+            println!("  Mount: {mount}");
+            println!("  Files released: {files_released} of {count} requested");
+            println!("  Bytes freed: {}", format_bytes(bytes_freed));
+            println!("  Remaining: {remaining} files");
+            if !errors.is_empty() {
+                println!("  Errors:");
+                for err in &errors {
+                    eprintln!("    {err}");
+                }
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "ballast release",
+                "via": "daemon",
+                "mount": mount,
+                "requested": count,
+                "files_released": files_released,
+                "bytes_freed": bytes_freed,
+                "remaining": remaining,
+                "errors": errors,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Partial(format!(
+            "{} errors during release",
+            errors.len()
+        )))
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn run_ballast(cli: &Cli, args: &BallastArgs) -> Result<(), CliError> {
     let config =
         Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
 
-    let mut manager = BallastManager::new(config.paths.ballast_dir.clone(), config.ballast.clone())
-        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mut pools = open_ballast_pools(&config)?;
+    let multi = pools.len() > 1;
 
     match &args.command {
         None | Some(BallastCommand::Status) => {
-            let inventory = manager.inventory().to_vec();
-            let available = manager.available_count();
-            let releasable = manager.releasable_bytes();
-
             match output_mode(cli) {
                 OutputMode::Human => {
                     println!("Ballast Pool Status");
-                    println!("  Directory: {}", config.paths.ballast_dir.display());
-                    println!(
-                        "  Configured: {} files x {}",
-                        config.ballast.file_count,
-                        format_bytes(config.ballast.file_size_bytes)
-                    );
-                    println!(
-                        "  Total pool: {}",
-                        format_bytes(ballast_total_pool_bytes(
-                            config.ballast.file_count,
-                            config.ballast.file_size_bytes,
-                        ))
-                    );
-                    println!(
-                        "  Available: {available} files ({} releasable)",
-                        format_bytes(releasable)
-                    );
-                    println!(
-                        "  Missing: {} files",
-                        config.ballast.file_count.saturating_sub(inventory.len())
-                    );
-
-                    if !inventory.is_empty() {
-                        println!(
-                            "\n  {:>5}  {:>10}  {:>10}  {:<10}",
-                            "Index", "Size", "Integrity", "Created"
-                        );
-                        println!("  {}", "-".repeat(45));
-                        for file in &inventory {
-                            let integrity = if file.integrity_ok { "OK" } else { "CORRUPT" };
-                            let created = if file.created_at.is_empty() {
-                                "unknown".to_string()
-                            } else {
-                                file.created_at.chars().take(10).collect()
-                            };
-                            println!(
-                                "  {:>5}  {:>10}  {:>10}  {:<10}",
-                                file.index,
-                                format_bytes(file.size),
-                                integrity,
-                                created,
-                            );
+                    for (i, manager) in pools.iter().enumerate() {
+                        if i > 0 {
+                            println!();
                         }
+                        print_ballast_pool_status(manager);
                     }
                 }
                 OutputMode::Json => {
-                    let files: Vec<Value> = inventory
-                        .iter()
-                        .map(|f| {
-                            json!({
-                                "index": f.index,
-                                "size": f.size,
-                                "integrity_ok": f.integrity_ok,
-                                "created_at": f.created_at,
-                                "path": f.path.to_string_lossy(),
-                            })
-                        })
-                        .collect();
-
-                    let payload = json!({
-                        "command": "ballast status",
-                        "directory": config.paths.ballast_dir.to_string_lossy(),
-                        "configured_count": config.ballast.file_count,
-                        "configured_size_bytes": config.ballast.file_size_bytes,
-                        "total_pool_bytes": ballast_total_pool_bytes(
-                            config.ballast.file_count,
-                            config.ballast.file_size_bytes,
-                        ),
-                        "available_count": available,
-                        "releasable_bytes": releasable,
-                        "missing_count":
-                            config.ballast.file_count.saturating_sub(inventory.len()),
-                        "files": files,
-                    });
-                    write_json_line(&payload)?;
+                    let per_pool = pools.iter().map(ballast_pool_status_json).collect();
+                    write_ballast_json("ballast status", per_pool)?;
                 }
             }
             Ok(())
         }
-        Some(BallastCommand::Provision) => {
+        Some(command @ (BallastCommand::Provision | BallastCommand::Replenish)) => {
+            let replenish = matches!(command, BallastCommand::Replenish);
+            let (title, verb, created_label) = if replenish {
+                ("replenish", "replenish", "Files recreated")
+            } else {
+                ("provision", "provisioning", "Files created")
+            };
             let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
             let collector = FsStatsCollector::new(platform, std::time::Duration::from_millis(500));
-            #[allow(clippy::redundant_clone)]
-            let ballast_dir = config.paths.ballast_dir.clone();
-            #[allow(clippy::cast_precision_loss)]
-            let free_check = move || -> f64 {
-                collector
-                    .collect(&ballast_dir)
-                    .map(|s| {
-                        if s.total_bytes == 0 {
-                            0.0
-                        } else {
-                            s.available_bytes as f64 / s.total_bytes as f64 * 100.0
-                        }
-                    })
-                    .unwrap_or(0.0)
-            };
-            let report = manager
-                .provision(Some(&free_check))
+            let mut per_pool = Vec::new();
+            let mut error_count = 0;
+
+            for manager in &mut pools {
+                let ballast_dir = manager.ballast_dir().to_path_buf();
+                let free_check = || ballast_free_pct(&collector, &ballast_dir);
+                let report = if replenish {
+                    manager.replenish(Some(&free_check))
+                } else {
+                    manager.provision(Some(&free_check))
+                }
                 .map_err(|e| CliError::Runtime(e.to_string()))?;
+                error_count += report.errors.len();
 
-            match output_mode(cli) {
-                OutputMode::Human => {
-                    println!("Ballast provision complete:");
-                    println!("  Files created: {}", report.files_created);
-                    println!("  Files skipped (existing): {}", report.files_skipped);
-                    println!(
-                        "  Total bytes allocated: {}",
-                        format_bytes(report.total_bytes)
-                    );
-                    if !report.errors.is_empty() {
-                        println!("  Errors:");
-                        for err in &report.errors {
-                            eprintln!("    {err}");
+                match output_mode(cli) {
+                    OutputMode::Human => {
+                        if multi {
+                            println!("Ballast {title} complete ({}):", ballast_dir.display());
+                        } else {
+                            println!("Ballast {title} complete:");
+                        }
+                        println!("  {created_label}: {}", report.files_created);
+                        println!("  Files skipped (existing): {}", report.files_skipped);
+                        println!(
+                            "  Total bytes allocated: {}",
+                            format_bytes(report.total_bytes)
+                        );
+                        if !report.errors.is_empty() {
+                            println!("  Errors:");
+                            for err in &report.errors {
+                                eprintln!("    {err}");
+                            }
                         }
                     }
-                }
-                OutputMode::Json => {
-                    let payload = json!({
-                        "command": "ballast provision",
+                    OutputMode::Json => per_pool.push(json!({
+                        "directory": ballast_dir.to_string_lossy(),
                         "files_created": report.files_created,
                         "files_skipped": report.files_skipped,
                         "total_bytes": report.total_bytes,
                         "errors": report.errors,
-                    });
-                    write_json_line(&payload)?;
+                    })),
                 }
             }
+            if output_mode(cli) == OutputMode::Json {
+                write_ballast_json(&format!("ballast {title}"), per_pool)?;
+            }
 
-            if report.errors.is_empty() {
+            if error_count == 0 {
                 Ok(())
             } else {
                 Err(CliError::Partial(format!(
-                    "{} errors during provisioning",
-                    report.errors.len()
+                    "{error_count} errors during {verb}"
                 )))
             }
         }
         Some(BallastCommand::Release(release_args)) => {
             let count = release_args.count;
-            let available = manager.available_count();
+            let available: usize = pools.iter().map(BallastManager::available_count).sum();
 
             if count == 0 {
                 return Err(CliError::User("release count must be > 0".to_string()));
             }
+            // A running daemon releases from its own pools so its release
+            // controller knows the files are gone.
+            let request = ControlRequest::ReleaseBallast { count };
+            if let Some(reply) = daemon_request(&config, &request) {
+                return print_daemon_release(cli, count, reply);
+            }
             if available == 0 {
                 return Err(CliError::User(
                     "no ballast files available to release".to_string(),
                 ));
             }
 
-            let report = manager
-                .release(count)
-                .map_err(|e| CliError::Runtime(e.to_string()))?;
+            // Drain pools in configured order until the request is met.
+            let mut files_released = 0;
+            let mut bytes_freed = 0u64;
+            let mut errors = Vec::new();
+            for manager in &mut pools {
+                let wanted = count.saturating_sub(files_released);
+                if wanted == 0 {
+                    break;
+                }
+                let take = wanted.min(manager.available_count());
+                if take == 0 {
+                    continue;
+                }
+                let report = manager
+                    .release(take)
+                    .map_err(|e| CliError::Runtime(e.to_string()))?;
+                files_released += report.files_released;
+                bytes_freed = bytes_freed.saturating_add(report.bytes_freed);
+                errors.extend(report.errors);
+            }
+            let remaining: usize = pools.iter().map(BallastManager::available_count).sum();
 
             match output_mode(cli) {
                 OutputMode::Human => {
                     println!("Ballast release complete:");
-                    println!(
-                        "  Files released: {} of {} requested",
-                        report.files_released, count
-                    );
-                    println!("  Bytes freed: {}", format_bytes(report.bytes_freed));
-                    println!("  Remaining: {} files", manager.available_count());
-                    if !report.errors.is_empty() {
+                    println!("  Files released: {files_released} of {count} requested");
+                    println!("  Bytes freed: {}", format_bytes(bytes_freed));
+                    println!("  Remaining: {remaining} files");
+                    if !errors.is_empty() {
                         println!("  Errors:");
-                        for err in &report.errors {
+                        for err in &errors {
                             eprintln!("    {err}");
                         }
                     }
@@ -2648,126 +5058,86 @@ fn run_ballast(cli: &Cli, args: &BallastArgs) -> Result<(), CliError> {
                     let payload = json!({
                         "command": "ballast release",
                         "requested": count,
-                        "files_released": report.files_released,
-                        "bytes_freed": report.bytes_freed,
-                        "remaining": manager.available_count(),
-                        "errors": report.errors,
+                        "files_released": files_released,
+                        "bytes_freed": bytes_freed,
+                        "remaining": remaining,
+                        "errors": errors,
                     });
                     write_json_line(&payload)?;
                 }
             }
 
-            if report.errors.is_empty() {
+            if errors.is_empty() {
                 Ok(())
             } else {
                 Err(CliError::Partial(format!(
                     "{} errors during release",
-                    report.errors.len()
+                    errors.len()
                 )))
             }
         }
-        Some(BallastCommand::Replenish) => {
-            let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
-            let collector = FsStatsCollector::new(platform, std::time::Duration::from_millis(500));
-            #[allow(clippy::redundant_clone)]
-            let ballast_dir = config.paths.ballast_dir.clone();
-            #[allow(clippy::cast_precision_loss)]
-            let free_check = move || -> f64 {
-                collector
-                    .collect(&ballast_dir)
-                    .map(|s| {
-                        if s.total_bytes == 0 {
-                            0.0
+        Some(BallastCommand::Resize(resize_args)) => {
+            run_ballast_resize(cli, &config, &mut pools, resize_args)
+        }
+        Some(BallastCommand::Verify) => {
+            let mut per_pool = Vec::new();
+            let mut files_corrupted = 0;
+            let mut needs_provision = false;
+
+            for manager in &mut pools {
+                let report = manager
+                    .verify()
+                    .map_err(|e| CliError::Runtime(e.to_string()))?;
+                files_corrupted += report.files_corrupted;
+                needs_provision |= report.files_corrupted > 0 || report.files_missing > 0;
+
+                match output_mode(cli) {
+                    OutputMode::Human => {
+                        if multi {
+                            println!(
+                                "Ballast verification ({}):",
+                                manager.ballast_dir().display()
+                            );
                         } else {
-                            s.available_bytes as f64 / s.total_bytes as f64 * 100.0
+                            println!("Ballast verification:");
                         }
-                    })
-                    .unwrap_or(0.0)
-            };
-            let report = manager
-                .replenish(Some(&free_check))
-                .map_err(|e| CliError::Runtime(e.to_string()))?;
-
-            match output_mode(cli) {
-                OutputMode::Human => {
-                    println!("Ballast replenish complete:");
-                    println!("  Files recreated: {}", report.files_created);
-                    println!("  Files skipped (existing): {}", report.files_skipped);
-                    println!(
-                        "  Total bytes allocated: {}",
-                        format_bytes(report.total_bytes)
-                    );
-                    if !report.errors.is_empty() {
-                        println!("  Errors:");
-                        for err in &report.errors {
-                            eprintln!("    {err}");
+                        println!("  Files checked: {}", report.files_checked);
+                        println!("  OK: {}", report.files_ok);
+                        println!("  Corrupted: {}", report.files_corrupted);
+                        println!("  Missing: {}", report.files_missing);
+
+                        if !report.details.is_empty() {
+                            println!("\n  Details:");
+                            for detail in &report.details {
+                                println!("    {detail}");
+                            }
                         }
                     }
+                    OutputMode::Json => per_pool.push(json!({
+                        "directory": manager.ballast_dir().to_string_lossy(),
+                        "files_checked": report.files_checked,
+                        "files_ok": report.files_ok,
+                        "files_corrupted": report.files_corrupted,
+                        "files_missing": report.files_missing,
+                        "details": report.details,
+                    })),
                 }
-                OutputMode::Json => {
-                    let payload = json!({
-                        "command": "ballast replenish",
-                        "files_created": report.files_created,
-                        "files_skipped": report.files_skipped,
-                        "total_bytes": report.total_bytes,
-                        "errors": report.errors,
-                    });
-                    write_json_line(&payload)?;
-                }
-            }
-
-            if report.errors.is_empty() {
-                Ok(())
-            } else {
-                Err(CliError::Partial(format!(
-                    "{} errors during replenish",
-                    report.errors.len()
-                )))
             }
-        }
-        Some(BallastCommand::Verify) => {
-            let report = manager
-                .verify()
-                .map_err(|e| CliError::Runtime(e.to_string()))?;
 
             match output_mode(cli) {
                 OutputMode::Human => {
-                    println!("Ballast verification:");
-                    println!("  Files checked: {}", report.files_checked);
-                    println!("  OK: {}", report.files_ok);
-                    println!("  Corrupted: {}", report.files_corrupted);
-                    println!("  Missing: {}", report.files_missing);
-
-                    if !report.details.is_empty() {
-                        println!("\n  Details:");
-                        for detail in &report.details {
-                            println!("    {detail}");
-                        }
-                    }
-
-                    if report.files_corrupted > 0 || report.files_missing > 0 {
+                    if needs_provision {
                         println!(
                             "\n  Run 'sbh ballast provision' to recreate missing/corrupted files."
                         );
                     }
                 }
-                OutputMode::Json => {
-                    let payload = json!({
-                        "command": "ballast verify",
-                        "files_checked": report.files_checked,
-                        "files_ok": report.files_ok,
-                        "files_corrupted": report.files_corrupted,
-                        "files_missing": report.files_missing,
-                        "details": report.details,
-                    });
-                    write_json_line(&payload)?;
-                }
+                OutputMode::Json => write_ballast_json("ballast verify", per_pool)?,
             }
 
-            if report.files_corrupted > 0 {
+            if files_corrupted > 0 {
                 Err(CliError::Partial(format!(
-                    "{} corrupted ballast files",
-                    report.files_corrupted
+                    "{files_corrupted} corrupted ballast files"
                 )))
             } else {
                 Ok(())
@@ -2776,15 +5146,268 @@ fn run_ballast(cli: &Cli, args: &BallastArgs) -> Result<(), CliError> {
     }
 }
 
-const fn normalize_refresh_ms(refresh_ms: u64) -> u64 {
-    if refresh_ms < LIVE_REFRESH_MIN_MS {
-        LIVE_REFRESH_MIN_MS
-    } else {
-        refresh_ms
-    }
-}
-
-fn validate_live_mode_output(
+/// Save the new size to the config, then bring each pool to it by creating,
+/// removing, or resizing only the files that differ.
+#[allow(clippy::too_many_lines)]
+fn run_ballast_resize(
+    cli: &Cli,
+    config: &Config,
+    pools: &mut [BallastManager],
+    args: &ResizeBallastArgs,
+) -> Result<(), CliError> {
+    let file_count = args.count.unwrap_or(config.ballast.file_count);
+    let file_size_bytes = args.size.unwrap_or(config.ballast.file_size_bytes);
+
+    // Write the config first: it is validated before it replaces the old
+    // one, so a rejected size leaves every ballast file untouched.
+    let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
+    let mut toml_value: toml::Value = toml::from_str(&read_config_file(&config_path)?)
+        .map_err(|e| CliError::Runtime(format!("parse config: {e}")))?;
+    set_toml_value(
+        &mut toml_value,
+        "ballast.file_count",
+        &file_count.to_string(),
+    )?;
+    set_toml_value(
+        &mut toml_value,
+        "ballast.file_size_bytes",
+        &file_size_bytes.to_string(),
+    )?;
+    let toml_str = toml::to_string_pretty(&toml_value)
+        .map_err(|e| CliError::Runtime(format!("serialize config: {e}")))?;
+    let new_config = write_validated_config(&config_path, &toml_str)?;
+
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let collector = FsStatsCollector::new(platform, std::time::Duration::from_millis(500));
+    let multi = pools.len() > 1;
+    let mut per_pool = Vec::new();
+    let mut error_count = 0;
+    for manager in pools.iter_mut() {
+        let ballast_dir = manager.ballast_dir().to_path_buf();
+        // Explicit pools keep their own sizes where they set them.
+        let target = if new_config.ballast.pools.is_empty() {
+            new_config.ballast.clone()
+        } else {
+            new_config.ballast.pool_settings("", &ballast_dir)
+        };
+        let old_count = manager.config().file_count;
+        let old_size = manager.config().file_size_bytes;
+        let free_check = || ballast_free_pct(&collector, &ballast_dir);
+        let report = manager
+            .resize(target.file_count, target.file_size_bytes, Some(&free_check))
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        error_count += report.errors.len();
+
+        match output_mode(cli) {
+            OutputMode::Human => {
+                if multi {
+                    println!("Ballast resize complete ({}):", ballast_dir.display());
+                } else {
+                    println!("Ballast resize complete:");
+                }
+                println!(
+                    "  Pool: {} x {} -> {} x {}",
+                    old_count,
+                    format_bytes(old_size),
+                    target.file_count,
+                    format_bytes(target.file_size_bytes)
+                );
+                println!(
+                    "  Files created: {}, removed: {}, resized in place: {}",
+                    report.files_created, report.files_removed, report.files_resized
+                );
+                println!("  Bytes allocated: {}", format_bytes(report.bytes_added));
+                println!("  Bytes freed: {}", format_bytes(report.bytes_freed));
+                if !report.errors.is_empty() {
+                    println!("  Errors:");
+                    for err in &report.errors {
+                        eprintln!("    {err}");
+                    }
+                }
+            }
+            OutputMode::Json => per_pool.push(json!({
+                "directory": ballast_dir.to_string_lossy(),
+                "file_count": target.file_count,
+                "file_size_bytes": target.file_size_bytes,
+                "files_created": report.files_created,
+                "files_removed": report.files_removed,
+                "files_resized": report.files_resized,
+                "bytes_added": report.bytes_added,
+                "bytes_freed": report.bytes_freed,
+                "errors": report.errors,
+            })),
+        }
+    }
+
+    // Have a running daemon pick up the new pool size now.
+    let daemon_reloaded = matches!(
+        daemon_request(&new_config, &ControlRequest::Reload),
+        Some(ControlReply::Accepted)
+    );
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("\nConfig updated: {}", config_path.display());
+            if daemon_reloaded {
+                println!("Running daemon asked to reload the config.");
+            }
+        }
+        OutputMode::Json => {
+            let mut payload = ballast_json_payload("ballast resize", per_pool);
+            payload["config_path"] = json!(config_path.to_string_lossy());
+            payload["daemon_reloaded"] = json!(daemon_reloaded);
+            write_json_line(&payload)?;
+        }
+    }
+
+    if error_count == 0 {
+        Ok(())
+    } else {
+        Err(CliError::Partial(format!(
+            "{error_count} errors during resize"
+        )))
+    }
+}
+
+/// Ballast pools the CLI manages: each `[[ballast.pools]]` entry, or the
+/// single `paths.ballast_dir` pool when none are configured.
+fn open_ballast_pools(config: &Config) -> Result<Vec<BallastManager>, CliError> {
+    if config.ballast.pools.is_empty() {
+        let manager = BallastManager::new(config.paths.ballast_dir.clone(), config.ballast.clone())
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        return Ok(vec![manager]);
+    }
+    config
+        .ballast
+        .pools
+        .iter()
+        .map(|pool| {
+            let settings = config.ballast.pool_settings("", &pool.path);
+            BallastManager::new(pool.path.clone(), settings)
+                .map_err(|e| CliError::Runtime(e.to_string()))
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ballast_free_pct(collector: &FsStatsCollector, ballast_dir: &Path) -> f64 {
+    collector.collect(ballast_dir).map_or(0.0, |s| {
+        if s.total_bytes == 0 {
+            0.0
+        } else {
+            s.available_bytes as f64 / s.total_bytes as f64 * 100.0
+        }
+    })
+}
+
+/// One pool's JSON keeps the historical flat shape; several pools nest
+/// under `pools`.
+fn write_ballast_json(command: &str, per_pool: Vec<Value>) -> Result<(), CliError> {
+    write_json_line(&ballast_json_payload(command, per_pool))
+}
+
+fn ballast_json_payload(command: &str, mut per_pool: Vec<Value>) -> Value {
+    if per_pool.len() == 1 {
+        let mut payload = per_pool.remove(0);
+        payload["command"] = json!(command);
+        payload
+    } else {
+        json!({
+            "command": command,
+            "pools": per_pool,
+        })
+    }
+}
+
+fn print_ballast_pool_status(manager: &BallastManager) {
+    let inventory = manager.inventory();
+    let settings = manager.config();
+    println!("  Directory: {}", manager.ballast_dir().display());
+    println!(
+        "  Configured: {} files x {}",
+        settings.file_count,
+        format_bytes(settings.file_size_bytes)
+    );
+    println!(
+        "  Total pool: {}",
+        format_bytes(ballast_total_pool_bytes(
+            settings.file_count,
+            settings.file_size_bytes,
+        ))
+    );
+    println!(
+        "  Available: {} files ({} releasable)",
+        manager.available_count(),
+        format_bytes(manager.releasable_bytes())
+    );
+    println!(
+        "  Missing: {} files",
+        settings.file_count.saturating_sub(inventory.len())
+    );
+
+    if !inventory.is_empty() {
+        println!(
+            "\n  {:>5}  {:>10}  {:>10}  {:<10}",
+            "Index", "Size", "Integrity", "Created"
+        );
+        println!("  {}", "-".repeat(45));
+        for file in inventory {
+            let integrity = if file.integrity_ok { "OK" } else { "CORRUPT" };
+            let created = if file.created_at.is_empty() {
+                "unknown".to_string()
+            } else {
+                file.created_at.chars().take(10).collect()
+            };
+            println!(
+                "  {:>5}  {:>10}  {:>10}  {:<10}",
+                file.index,
+                format_bytes(file.size),
+                integrity,
+                created,
+            );
+        }
+    }
+}
+
+fn ballast_pool_status_json(manager: &BallastManager) -> Value {
+    let inventory = manager.inventory();
+    let settings = manager.config();
+    let files: Vec<Value> = inventory
+        .iter()
+        .map(|f| {
+            json!({
+                "index": f.index,
+                "size": f.size,
+                "integrity_ok": f.integrity_ok,
+                "created_at": f.created_at,
+                "path": f.path.to_string_lossy(),
+            })
+        })
+        .collect();
+
+    json!({
+        "directory": manager.ballast_dir().to_string_lossy(),
+        "configured_count": settings.file_count,
+        "configured_size_bytes": settings.file_size_bytes,
+        "total_pool_bytes": ballast_total_pool_bytes(
+            settings.file_count,
+            settings.file_size_bytes,
+        ),
+        "available_count": manager.available_count(),
+        "releasable_bytes": manager.releasable_bytes(),
+        "missing_count": settings.file_count.saturating_sub(inventory.len()),
+        "files": files,
+    })
+}
+
+const fn normalize_refresh_ms(refresh_ms: u64) -> u64 {
+    if refresh_ms < LIVE_REFRESH_MIN_MS {
+        LIVE_REFRESH_MIN_MS
+    } else {
+        refresh_ms
+    }
+}
+
+fn validate_live_mode_output(
     mode: OutputMode,
     command: &str,
     allow_json_live: bool,
@@ -3004,6 +5627,12 @@ fn run_dashboard(cli: &Cli, args: &DashboardArgs) -> Result<(), CliError> {
 }
 
 fn run_status(cli: &Cli, args: &StatusArgs) -> Result<(), CliError> {
+    if let Some(system) = &args.discovery {
+        return run_status_discovery(cli, system);
+    }
+    if args.telegraf {
+        return run_status_telegraf(cli);
+    }
     if args.watch {
         run_live_status_loop(cli, STATUS_WATCH_REFRESH_MS, "status --watch", true)
     } else {
@@ -3011,6 +5640,89 @@ fn run_status(cli: &Cli, args: &StatusArgs) -> Result<(), CliError> {
     }
 }
 
+/// Measure every writable mount for the monitoring feeds, attaching the
+/// daemon's fill rate where `daemon_state` has one.
+fn discover_mounts(
+    config: &Config,
+    daemon_state: Option<&DaemonState>,
+) -> Result<Vec<storage_ballast_helper::cli::discovery::DiscoveredMount>, CliError> {
+    use storage_ballast_helper::cli::discovery::DiscoveredMount;
+
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mounts = platform
+        .mount_points()
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mut collector =
+        FsStatsCollector::new(std::sync::Arc::clone(&platform), std::time::Duration::ZERO);
+    collector.set_stats_timeout(config.telemetry.fs_stats_timeout());
+    let mount_paths: Vec<PathBuf> = mounts.iter().map(|mount| mount.path.clone()).collect();
+    let mut mount_stats: HashMap<PathBuf, FsStats> = collector
+        .probe_mounts(&mount_paths)
+        .into_iter()
+        .filter_map(|(path, stats)| Some((path, stats.ok()?)))
+        .collect();
+
+    Ok(mounts
+        .iter()
+        .filter_map(|mount| {
+            // Stale, pseudo, and read-only filesystems are not monitored.
+            let stats = mount_stats.remove(&mount.path)?;
+            if stats.total_bytes == 0 || stats.is_readonly {
+                return None;
+            }
+            let path = mount.path.to_string_lossy().into_owned();
+            let free_pct = stats.free_pct();
+            Some(DiscoveredMount {
+                rate_bps: daemon_state
+                    .and_then(|state| state.rates.get(&path))
+                    .map(|rate| rate.bytes_per_sec),
+                path,
+                fs_type: stats.fs_type,
+                class: classify_mount(mount, &config.mounts).as_str().to_string(),
+                total_bytes: stats.total_bytes,
+                free_bytes: stats.available_bytes,
+                free_pct,
+                level: pressure_level_str(free_pct, &config.pressure.for_mount_now(&mount.path))
+                    .to_string(),
+            })
+        })
+        .collect())
+}
+
+fn run_status_discovery(cli: &Cli, system: &str) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::discovery::zabbix_lld;
+
+    if system != "zabbix" {
+        return Err(CliError::User(format!(
+            "unknown discovery format '{system}'; expected zabbix"
+        )));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mounts = discover_mounts(&config, None)?;
+    write_json_line(&zabbix_lld(
+        &mounts,
+        config.pressure.yellow_min_free_pct,
+        config.pressure.red_min_free_pct,
+    ))?;
+    Ok(())
+}
+
+fn run_status_telegraf(cli: &Cli) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::discovery::telegraf_records;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    // Stale daemon counters would read as a flat line; omit the record instead.
+    let state = read_state(&config.paths.state_file)
+        .ok()
+        .filter(StateSnapshot::is_fresh)
+        .map(|snapshot| snapshot.state);
+    let mounts = discover_mounts(&config, state.as_ref())?;
+    write_json_line(&telegraf_records(&mounts, state.as_ref()))?;
+    Ok(())
+}
+
 #[allow(clippy::too_many_lines)]
 fn render_status(cli: &Cli) -> Result<(), CliError> {
     let config =
@@ -3018,30 +5730,58 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
     let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
     let version = env!("CARGO_PKG_VERSION");
 
-    // Gather filesystem stats for all root paths + standard mounts.
+    // Gather filesystem stats for all root paths + standard mounts. Probed
+    // concurrently with a timeout so a hung network mount shows as stale.
     let mounts = platform
         .mount_points()
         .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mut collector =
+        FsStatsCollector::new(std::sync::Arc::clone(&platform), std::time::Duration::ZERO);
+    collector.set_stats_timeout(config.telemetry.fs_stats_timeout());
+    let mount_paths: Vec<PathBuf> = mounts.iter().map(|mount| mount.path.clone()).collect();
+    let mut mount_stats: HashMap<PathBuf, FsStats> = HashMap::new();
+    for (path, stats) in collector.probe_mounts(&mount_paths) {
+        if let Ok(stats) = stats {
+            mount_stats.insert(path, stats);
+        }
+    }
+    let stale_mounts: HashSet<PathBuf> = collector
+        .stale_mounts()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    // Read-only mounts are listed only when they hold a scan root; snap and
+    // image mounts are read-only by design and not worth a row.
+    let default_roots;
+    let roots = if config.scanner.root_paths.is_empty() {
+        default_roots = [PathBuf::from("/")];
+        &default_roots[..]
+    } else {
+        &config.scanner.root_paths
+    };
+    let watched = MountWatcher::new(roots, &mounts);
 
     // Read daemon state.json for EWMA predictions (optional).
-    let daemon_state = std::fs::read_to_string(&config.paths.state_file)
-        .ok()
-        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok());
+    let state_read = read_state(&config.paths.state_file);
+    let daemon_state = state_read.as_ref().ok();
+    let state_warning = match &state_read {
+        Err(err @ StateReadError::Parse(StateParseError::NewerSchema(_))) => Some(err.to_string()),
+        _ => None,
+    };
 
     // I26: Check file modification time to detect stale state from a crashed daemon.
     let daemon_running = {
-        let state_file_fresh = daemon_state.is_some() && {
-            let stale_threshold =
-                std::time::Duration::from_secs(DAEMON_STATE_STALE_THRESHOLD_SECS);
-            std::fs::metadata(&config.paths.state_file)
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .is_some_and(|modified| {
-                    SystemTime::now()
-                        .duration_since(modified)
-                        .unwrap_or_default()
-                        <= stale_threshold
-                })
+        let state_file_fresh = match &state_read {
+            Ok(snapshot) => snapshot.is_fresh(),
+            // A newer daemon's state is unread but still proves it is alive.
+            Err(StateReadError::Parse(StateParseError::NewerSchema(_))) => {
+                std::fs::metadata(&config.paths.state_file)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                    .is_some_and(|age| age.as_secs() <= DAEMON_STATE_STALE_THRESHOLD_SECS)
+            }
+            Err(_) => false,
         };
 
         // Cross-user fallback: when the daemon runs as root (systemd) but CLI
@@ -3068,6 +5808,10 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
         None
     };
     let memory_info = platform.memory_info().ok();
+    let disks = status_disk_health(&mounts, &mount_stats, &config);
+    let now = chrono::Utc::now();
+    let maintenance = active_maintenance(&maintenance_path(&config.paths.state_file), now);
+    let profile = config.pressure.active_profile(chrono::Local::now().time());
 
     match output_mode(cli) {
         OutputMode::Human => {
@@ -3078,6 +5822,65 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
             } else {
                 println!("  Daemon: not running (degraded mode)");
             }
+            if let Some(warning) = &state_warning {
+                println!("  Warning: {warning}");
+            }
+            // A refused reload leaves the daemon on its previous config.
+            if let Some(reload) = daemon_state.and_then(|s| s.state.config_reload.as_ref())
+                && reload.outcome == "rejected"
+            {
+                println!(
+                    "  Config reload: rejected at {} ({}); previous config still in force",
+                    reload.at,
+                    reload.reason.as_deref().unwrap_or("unknown reason"),
+                );
+            }
+            if let Some(window) = &maintenance {
+                let left = window.remaining(now).unwrap_or_default();
+                let muted = if window.mute_alerts {
+                    ", alerts muted"
+                } else {
+                    ""
+                };
+                let reason = window
+                    .reason
+                    .as_deref()
+                    .map(|reason| format!(" ({reason})"))
+                    .unwrap_or_default();
+                println!(
+                    "  Maintenance: enforcement paused until {} ({} left{muted}){reason}",
+                    window.until,
+                    format_duration(left),
+                );
+            }
+            // Automated cleanup holding off after a recent pass.
+            for (mount, cooldown) in daemon_state.iter().flat_map(|s| &s.state.cleanup_cooldowns) {
+                if let Some(left) = cooldown.remaining(now) {
+                    println!(
+                        "  Cleanup cooldown: {mount}, next automated cleanup eligible in {}",
+                        format_duration(left),
+                    );
+                }
+            }
+            if let Some(deep) = daemon_state.and_then(|s| s.state.deep_scan.as_ref()) {
+                println!(
+                    "  Deep scan: last {}, next {} ({})",
+                    deep.last_at.as_deref().unwrap_or("never"),
+                    deep.next_at.as_deref().unwrap_or("never"),
+                    deep.schedule,
+                );
+            }
+            if let Some((profile, weight)) = profile {
+                let blend = if weight < 1.0 {
+                    format!(", {:.0}% blended in", weight * 100.0)
+                } else {
+                    String::new()
+                };
+                println!(
+                    "  Pressure profile: {} ({}-{}{blend})",
+                    profile.name, profile.start, profile.end,
+                );
+            }
 
             // Pressure status table.
             println!("\nPressure Status:");
@@ -3089,20 +5892,49 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
 
             let mut overall_level = "green";
             for mount in &mounts {
-                let Ok(stats) = platform.fs_stats(&mount.path) else {
+                if stale_mounts.contains(&mount.path) {
+                    println!(
+                        "  {:<20}  {:>10}  {:>10}  {:>7}  {:<10}",
+                        format!("{} ({})", mount.path.display(), mount.fs_type),
+                        "?",
+                        "?",
+                        "?",
+                        "STALE",
+                    );
+                    continue;
+                }
+                let Some(stats) = mount_stats.get(&mount.path) else {
                     continue;
                 };
 
                 // Skip pseudo/virtual/read-only filesystems (squashfs snap
                 // mounts, proc, sysfs, etc.) — they can't fill up and don't
                 // represent actionable storage pressure.
-                if stats.total_bytes == 0 || stats.is_readonly {
+                if stats.total_bytes == 0 {
+                    continue;
+                }
+                // A watched mount gone read-only cannot be cleaned; flag it.
+                if stats.is_readonly {
+                    if watched.is_watched(&mount.path) {
+                        println!(
+                            "  {:<20}  {:>10}  {:>10}  {:>6.1}%  {:<10}",
+                            mount.path.display(),
+                            format_bytes(stats.total_bytes),
+                            format_bytes(stats.available_bytes),
+                            stats.free_pct(),
+                            "READONLY",
+                        );
+                    }
                     continue;
                 }
 
                 let free_pct = stats.free_pct();
-                let level = pressure_level_str(free_pct, &config);
-                if pressure_severity(level) > pressure_severity(overall_level) {
+                let level =
+                    pressure_level_str(free_pct, &config.pressure.for_mount_now(&mount.path));
+                let class = classify_mount(mount, &config.mounts);
+                if pressure_severity(level) > pressure_severity(overall_level)
+                    && (class.is_permanent() || !config.mounts.exclude_removable_from_pressure)
+                {
                     overall_level = level;
                 }
 
@@ -3111,10 +5943,15 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 } else {
                     ""
                 };
+                let class_note = if class.is_permanent() {
+                    String::new()
+                } else {
+                    format!(" ({})", class.as_str())
+                };
 
                 println!(
                     "  {:<20}  {:>10}  {:>10}  {:>6.1}%  {:<10}",
-                    format!("{}{ram_note}", mount.path.display()),
+                    format!("{}{ram_note}{class_note}", mount.path.display()),
                     format_bytes(stats.total_bytes),
                     format_bytes(stats.available_bytes),
                     free_pct,
@@ -3155,17 +5992,30 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 }
             }
 
+            if !disks.is_empty() {
+                println!("\nDisk Health:");
+                for status in &disks {
+                    let filling_note = if status.filling && status.health.verdict.is_alarming() {
+                        "  [FILLING]"
+                    } else {
+                        ""
+                    };
+                    println!(
+                        "  {:<10}  {:<9}  {}{filling_note}",
+                        status.health.disk,
+                        status.health.verdict.as_str().to_uppercase(),
+                        status.health.detail,
+                    );
+                }
+            }
+
             // Rate estimates from daemon state.
-            if let Some(state) = &daemon_state
-                && let Some(rates) = state.get("rates").and_then(Value::as_object)
-                && !rates.is_empty()
+            if let Some(snapshot) = daemon_state
+                && !snapshot.state.rates.is_empty()
             {
                 println!("\nRate Estimates:");
-                for (mount, rate_obj) in rates {
-                    let bps = rate_obj
-                        .get("bytes_per_sec")
-                        .and_then(Value::as_f64)
-                        .unwrap_or(0.0);
+                for (mount, rate) in &snapshot.state.rates {
+                    let bps = rate.bytes_per_sec;
                     let trend = if bps > 0.0 {
                         "filling"
                     } else if bps < 0.0 {
@@ -3198,6 +6048,15 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                     config.ballast.file_size_bytes,
                 )),
             );
+            if let Some(snapshot) = daemon_state
+                && let Some(pct) = snapshot.state.ballast.corruption_pct()
+            {
+                let ballast = &snapshot.state.ballast;
+                println!(
+                    "  Integrity: {} of {} checks corrupted ({pct:.2}%), {} re-created",
+                    ballast.files_corrupted, ballast.files_verified, ballast.files_repaired,
+                );
+            }
 
             // Recent activity from database.
             if let Some(stats) = &db_stats {
@@ -3222,16 +6081,45 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
             let mut overall_level = "green";
 
             for mount in &mounts {
-                let Ok(stats) = platform.fs_stats(&mount.path) else {
+                if stale_mounts.contains(&mount.path) {
+                    mounts_json.push(json!({
+                        "path": mount.path.to_string_lossy(),
+                        "level": "stale",
+                        "fs_type": mount.fs_type,
+                        "class": classify_mount(mount, &config.mounts).as_str(),
+                        "stale": true,
+                    }));
+                    continue;
+                }
+                let Some(stats) = mount_stats.get(&mount.path) else {
                     continue;
                 };
                 // Skip pseudo/virtual/read-only filesystems.
-                if stats.total_bytes == 0 || stats.is_readonly {
+                if stats.total_bytes == 0 {
+                    continue;
+                }
+                if stats.is_readonly {
+                    if watched.is_watched(&mount.path) {
+                        mounts_json.push(json!({
+                            "path": mount.path.to_string_lossy(),
+                            "total": stats.total_bytes,
+                            "free": stats.available_bytes,
+                            "free_pct": stats.free_pct(),
+                            "level": "readonly",
+                            "fs_type": stats.fs_type,
+                            "class": classify_mount(mount, &config.mounts).as_str(),
+                            "stale": false,
+                        }));
+                    }
                     continue;
                 }
                 let free_pct = stats.free_pct();
-                let level = pressure_level_str(free_pct, &config);
-                if pressure_severity(level) > pressure_severity(overall_level) {
+                let level =
+                    pressure_level_str(free_pct, &config.pressure.for_mount_now(&mount.path));
+                let class = classify_mount(mount, &config.mounts);
+                if pressure_severity(level) > pressure_severity(overall_level)
+                    && (class.is_permanent() || !config.mounts.exclude_removable_from_pressure)
+                {
                     overall_level = level;
                 }
 
@@ -3242,6 +6130,8 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                     "free_pct": free_pct,
                     "level": level,
                     "fs_type": stats.fs_type,
+                    "class": class.as_str(),
+                    "stale": false,
                 }));
             }
 
@@ -3262,6 +6152,12 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
             "pressure": {
                 "mounts": mounts_json,
                 "overall": overall_level,
+                "profile": profile.map(|(profile, weight)| json!({
+                    "name": profile.name,
+                    "start": profile.start,
+                    "end": profile.end,
+                    "weight": weight,
+                })),
             },
                 "ballast": {
                     "file_count": config.ballast.file_count,
@@ -3270,6 +6166,15 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                         config.ballast.file_count,
                         config.ballast.file_size_bytes,
                     ),
+                    "integrity": daemon_state.map(|s| {
+                        let ballast = &s.state.ballast;
+                        json!({
+                            "files_verified": ballast.files_verified,
+                            "files_corrupted": ballast.files_corrupted,
+                            "files_repaired": ballast.files_repaired,
+                            "corruption_pct": ballast.corruption_pct(),
+                        })
+                    }),
                 },
                 "memory": memory_info.as_ref().map(|memory| {
                     let swap_used_bytes = memory.swap_total_bytes.saturating_sub(memory.swap_free_bytes);
@@ -3284,8 +6189,27 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                         "swap_thrash_risk": is_swap_thrash_risk(memory),
                     })
                 }),
+                "disk_health": disks.iter().map(|status| json!({
+                    "disk": status.health.disk,
+                    "verdict": status.health.verdict,
+                    "source": status.health.source,
+                    "detail": status.health.detail,
+                    "mounts": status.mounts,
+                    "filling": status.filling,
+                })).collect::<Vec<_>>(),
                 "recent_hour": recent,
-                "policy_mode": daemon_state.as_ref().and_then(|s| s.get("policy_mode")).and_then(|v| v.as_str()),
+                "policy_mode": daemon_state.map(|s| s.state.policy_mode.as_str()).filter(|mode| !mode.is_empty()),
+                "maintenance": maintenance.as_ref().map(|window| json!({
+                    "until": window.until,
+                    "started_at": window.started_at,
+                    "remaining_secs": window.remaining(now).unwrap_or_default().as_secs(),
+                    "mute_alerts": window.mute_alerts,
+                    "reason": window.reason,
+                })),
+                "config_reload": daemon_state.and_then(|s| s.state.config_reload.as_ref()),
+                "cleanup_cooldowns": daemon_state.map(|s| &s.state.cleanup_cooldowns),
+                "deep_scan": daemon_state.and_then(|s| s.state.deep_scan.as_ref()),
+                "state_warning": state_warning,
             });
             write_json_line(&payload)?;
         }
@@ -3416,11 +6340,12 @@ fn format_log_line(line: &str) -> String {
 /// Cross-user daemon detection fallback: check systemd service and /proc.
 /// Used when the state file isn't found (e.g. daemon runs as root, CLI as ubuntu).
 fn detect_daemon_running_fallback() -> bool {
-    // Method 1: Check systemd service status.
-    if let Ok(output) = std::process::Command::new("systemctl")
-        .args(["is-active", "sbh.service"])
-        .stderr(std::process::Stdio::null())
-        .output()
+    // Method 1: Check systemd service status (there is none in a container).
+    if !container::is_enabled()
+        && let Ok(output) = std::process::Command::new("systemctl")
+            .args(["is-active", "sbh.service"])
+            .stderr(std::process::Stdio::null())
+            .output()
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
         if stdout.trim() == "active" {
@@ -3454,14 +6379,16 @@ fn detect_daemon_running_fallback() -> bool {
 }
 
 /// Map free percentage to pressure level string.
-fn pressure_level_str(free_pct: f64, config: &Config) -> &'static str {
-    if free_pct >= config.pressure.green_min_free_pct {
+/// Level name for `free_pct` under thresholds already resolved for the mount
+/// (`PressureConfig::for_mount`).
+fn pressure_level_str(free_pct: f64, pressure: &PressureConfig) -> &'static str {
+    if free_pct >= pressure.green_min_free_pct {
         "green"
-    } else if free_pct >= config.pressure.yellow_min_free_pct {
+    } else if free_pct >= pressure.yellow_min_free_pct {
         "yellow"
-    } else if free_pct >= config.pressure.orange_min_free_pct {
+    } else if free_pct >= pressure.orange_min_free_pct {
         "orange"
-    } else if free_pct >= config.pressure.red_min_free_pct {
+    } else if free_pct >= pressure.red_min_free_pct {
         "red"
     } else {
         "critical"
@@ -3523,6 +6450,50 @@ fn is_swap_thrash_risk(memory: &MemoryInfo) -> bool {
     memory.available_bytes < MIN_AVAILABLE_RAM_BYTES
 }
 
+/// Health of one disk behind the mounts shown by `sbh status`.
+struct DiskStatus {
+    health: DiskHealth,
+    mounts: Vec<String>,
+    /// One of its mounts is below the green threshold.
+    filling: bool,
+}
+
+/// Probe each distinct disk behind the writable mounts in `mount_stats`.
+/// Empty when `telemetry.disk_health_interval_secs` is 0.
+fn status_disk_health(
+    mounts: &[MountPoint],
+    mount_stats: &HashMap<PathBuf, FsStats>,
+    config: &Config,
+) -> Vec<DiskStatus> {
+    if config.telemetry.disk_health_interval_secs == 0 {
+        return Vec::new();
+    }
+    let mut disks: BTreeMap<String, (Vec<String>, bool)> = BTreeMap::new();
+    for mount in mounts {
+        let Some(stats) = mount_stats.get(&mount.path) else {
+            continue;
+        };
+        if stats.total_bytes == 0 || stats.is_readonly {
+            continue;
+        }
+        let Some(disk) = disk_health::backing_disk(&mount.device) else {
+            continue;
+        };
+        let entry = disks.entry(disk).or_default();
+        entry.0.push(mount.path.to_string_lossy().into_owned());
+        let levels = config.pressure.for_mount_now(&mount.path);
+        entry.1 |= stats.free_pct() < levels.green_min_free_pct;
+    }
+    disks
+        .into_iter()
+        .map(|(disk, (mounts, filling))| DiskStatus {
+            health: disk_health::probe(&disk),
+            mounts,
+            filling,
+        })
+        .collect()
+}
+
 fn ballast_total_pool_bytes(file_count: usize, file_size_bytes: u64) -> u64 {
     u64::try_from(file_count)
         .ok()
@@ -3531,6 +6502,9 @@ fn ballast_total_pool_bytes(file_count: usize, file_size_bytes: u64) -> u64 {
 }
 
 fn run_protect(cli: &Cli, args: &ProtectArgs) -> Result<(), CliError> {
+    if args.coverage {
+        return run_protect_coverage(cli);
+    }
     if args.list {
         // List all protections (markers + config patterns).
         let config =
@@ -3630,55 +6604,450 @@ fn run_protect(cli: &Cli, args: &ProtectArgs) -> Result<(), CliError> {
     Ok(())
 }
 
-fn run_unprotect(cli: &Cli, args: &UnprotectArgs) -> Result<(), CliError> {
-    // Canonicalize to resolve symlinks and relative components.
-    let canonical = args
-        .path
-        .canonicalize()
-        .map_err(|e| CliError::User(format!("cannot resolve path {}: {e}", args.path.display())))?;
-
-    let removed =
-        protection::remove_marker(&canonical).map_err(|e| CliError::Runtime(e.to_string()))?;
+/// A single protection shielding more than this share of its mount's used
+/// space is flagged as suspiciously broad.
+const BROAD_PROTECTION_PCT: f64 = 80.0;
+
+/// Protection coverage of the watched roots on one mount.
+struct MountCoverage {
+    stats: FsStats,
+    roots: Vec<PathBuf>,
+    watched_bytes: u64,
+    watched_dirs: u64,
+    protections: Vec<CoveredProtection>,
+}
 
-    match output_mode(cli) {
-        OutputMode::Human => {
-            if removed {
-                println!("Unprotected: {} (marker removed)", canonical.display());
-            } else {
-                println!(
-                    "No protection marker found at {}",
-                    canonical.join(protection::MARKER_FILENAME).display(),
-                );
+impl MountCoverage {
+    fn add(&mut self, root: &Path, coverage: ProtectionCoverage) {
+        self.roots.push(root.to_path_buf());
+        self.watched_bytes += coverage.total_bytes;
+        self.watched_dirs += coverage.total_dirs;
+        for covered in coverage.protected {
+            match self.protections.iter_mut().find(|p| p.path == covered.path) {
+                Some(existing) => {
+                    existing.bytes += covered.bytes;
+                    existing.dirs += covered.dirs;
+                }
+                None => self.protections.push(covered),
             }
         }
-        OutputMode::Json => {
-            let payload = json!({
-                "command": "unprotect",
-                "path": canonical.to_string_lossy(),
-                "removed": removed,
-            });
-            write_json_line(&payload)?;
-        }
     }
 
-    Ok(())
-}
+    fn protected_bytes(&self) -> u64 {
+        self.protections.iter().map(|p| p.bytes).sum()
+    }
 
-#[allow(clippy::too_many_lines)]
-fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
-    let config =
-        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
-    let start = std::time::Instant::now();
+    fn protected_dirs(&self) -> u64 {
+        self.protections.iter().map(|p| p.dirs).sum()
+    }
 
-    // Determine scan roots: CLI paths or configured watched paths.
-    // Canonicalize to ensure absolute paths for system protection checks.
-    let raw_roots = if args.paths.is_empty() {
-        config.scanner.root_paths.clone()
-    } else {
-        args.paths.clone()
-    };
+    fn used_bytes(&self) -> u64 {
+        self.stats.total_bytes.saturating_sub(self.stats.free_bytes)
+    }
+}
 
-    let root_paths: Vec<PathBuf> = raw_roots
+/// Where a mount stands against its green free-space threshold once
+/// protections are taken into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CoverageGoal {
+    /// Already at or above the threshold.
+    Met,
+    /// Unprotected files under the watched roots plus ballast could get there.
+    Reachable,
+    /// Only deleting protected files could get there.
+    BlockedByProtection,
+    /// Out of reach even with every protection lifted.
+    Unreachable,
+}
+
+impl CoverageGoal {
+    fn assess(bytes_needed: u64, reclaimable: u64, protected: u64) -> Self {
+        if bytes_needed == 0 {
+            Self::Met
+        } else if bytes_needed <= reclaimable {
+            Self::Reachable
+        } else if bytes_needed <= reclaimable.saturating_add(protected) {
+            Self::BlockedByProtection
+        } else {
+            Self::Unreachable
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Met => "met",
+            Self::Reachable => "reachable",
+            Self::BlockedByProtection => "blocked_by_protection",
+            Self::Unreachable => "unreachable",
+        }
+    }
+}
+
+/// Releasable ballast on `mount` that the watched roots do not already
+/// count: the configured pool directories that live on it.
+fn mount_ballast_bytes(
+    config: &Config,
+    collector: &FsStatsCollector,
+    mount: &Path,
+    roots: &[&PathBuf],
+) -> u64 {
+    let mount_str = mount.to_string_lossy();
+    let mut dirs: Vec<PathBuf> = config
+        .ballast
+        .pools
+        .iter()
+        .map(|pool| pool.path.clone())
+        .chain([config.paths.ballast_dir.clone()])
+        .chain(
+            config
+                .ballast
+                .effective_ballast_dir(&mount_str)
+                .map(Path::to_path_buf),
+        )
+        .chain([mount.join(BALLAST_SUBDIR)])
+        .filter(|dir| {
+            dir.is_dir()
+                && !roots.iter().any(|root| dir.starts_with(root))
+                && collector
+                    .collect(dir)
+                    .is_ok_and(|stats| stats.mount_point == mount)
+        })
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs.into_iter()
+        .map(|dir| {
+            let settings = config.ballast.pool_settings(&mount_str, &dir);
+            BallastManager::inspect(dir, settings).releasable_bytes()
+        })
+        .sum()
+}
+
+/// `sbh protect --coverage`: how much of the watched space markers and
+/// `scanner.protected_paths` shield on each mount, which protections look
+/// too broad, and whether they leave the green threshold out of reach.
+#[allow(clippy::too_many_lines)]
+fn run_protect_coverage(cli: &Cli) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let mut registry = ProtectionRegistry::new(Some(&config.scanner.protected_paths))
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let collector = FsStatsCollector::new(platform, std::time::Duration::ZERO);
+
+    // A root nested in another would count its subtree twice.
+    let all_roots = &config.scanner.root_paths;
+    let roots: Vec<&PathBuf> = all_roots
+        .iter()
+        .filter(|root| {
+            root.exists()
+                && !all_roots
+                    .iter()
+                    .any(|other| other != *root && root.starts_with(other))
+        })
+        .collect();
+
+    let mut mounts = BTreeMap::<PathBuf, MountCoverage>::new();
+    for root in &roots {
+        let stats = collector
+            .collect(root)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        let coverage = registry
+            .measure_coverage(root)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        mounts
+            .entry(stats.mount_point.clone())
+            .or_insert_with(|| MountCoverage {
+                stats,
+                roots: Vec::new(),
+                watched_bytes: 0,
+                watched_dirs: 0,
+                protections: Vec::new(),
+            })
+            .add(root, coverage);
+    }
+
+    for coverage in mounts.values_mut() {
+        coverage
+            .protections
+            .sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+    }
+    let assessed: Vec<(&PathBuf, &MountCoverage, CoverageAssessment)> = mounts
+        .iter()
+        .map(|(mount, coverage)| {
+            let assessment = assess_coverage(&config, &collector, mount, coverage, &roots);
+            (mount, coverage, assessment)
+        })
+        .collect();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if assessed.is_empty() {
+                println!("No watched roots exist (scanner.root_paths).");
+                return Ok(());
+            }
+            println!("Protection coverage of watched roots\n");
+            for (mount, coverage, assessment) in &assessed {
+                let protected = coverage.protected_bytes();
+                let roots: Vec<String> = coverage
+                    .roots
+                    .iter()
+                    .map(|root| root.display().to_string())
+                    .collect();
+                println!("{} ({})", mount.display(), coverage.stats.fs_type);
+                println!("  Watched:    {}", roots.join(", "));
+                println!(
+                    "  Protected:  {} of {} ({:.1}%), {} of {} directories",
+                    format_bytes(protected),
+                    format_bytes(coverage.watched_bytes),
+                    bytes_to_pct(protected, coverage.watched_bytes),
+                    coverage.protected_dirs(),
+                    coverage.watched_dirs,
+                );
+                for covered in &coverage.protections {
+                    let source = match &covered.source {
+                        protection::ProtectionSource::MarkerFile => "marker",
+                        protection::ProtectionSource::ConfigPattern(_) => "config pattern",
+                    };
+                    println!(
+                        "    {:>10}  {} ({source})",
+                        format_bytes(covered.bytes),
+                        covered.path.display()
+                    );
+                }
+                if assessment.goal == CoverageGoal::Met {
+                    println!(
+                        "  Goal:       already at {:.0}% free or more",
+                        assessment.target_pct
+                    );
+                } else {
+                    println!(
+                        "  Goal:       {:.0}% free needs {} more; {} unprotected and {} \
+                         ballast reclaimable",
+                        assessment.target_pct,
+                        format_bytes(assessment.bytes_needed),
+                        format_bytes(
+                            assessment
+                                .reclaimable_bytes
+                                .saturating_sub(assessment.ballast_bytes)
+                        ),
+                        format_bytes(assessment.ballast_bytes),
+                    );
+                }
+                for warning in &assessment.warnings {
+                    println!("  Warning:    {warning}");
+                }
+                println!();
+            }
+        }
+        OutputMode::Json => {
+            let reports: Vec<Value> = assessed
+                .iter()
+                .map(|(mount, coverage, assessment)| coverage_json(mount, coverage, assessment))
+                .collect();
+            let payload = json!({
+                "command": "protect",
+                "action": "coverage",
+                "broad_threshold_pct": BROAD_PROTECTION_PCT,
+                "mounts": reports,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// A mount's standing against its green threshold given its protections.
+struct CoverageAssessment {
+    ballast_bytes: u64,
+    /// Unprotected bytes under the watched roots plus `ballast_bytes`.
+    reclaimable_bytes: u64,
+    target_pct: f64,
+    bytes_needed: u64,
+    goal: CoverageGoal,
+    warnings: Vec<String>,
+}
+
+fn assess_coverage(
+    config: &Config,
+    collector: &FsStatsCollector,
+    mount: &Path,
+    coverage: &MountCoverage,
+    roots: &[&PathBuf],
+) -> CoverageAssessment {
+    let used = coverage.used_bytes();
+    let protected = coverage.protected_bytes();
+    let ballast_bytes = mount_ballast_bytes(config, collector, mount, roots);
+    let reclaimable_bytes = coverage.watched_bytes.saturating_sub(protected) + ballast_bytes;
+    let target_pct = config.pressure.for_mount(mount).green_min_free_pct;
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let wanted = (coverage.stats.total_bytes as f64 * target_pct / 100.0).ceil() as u64;
+    let bytes_needed = wanted.saturating_sub(coverage.stats.available_bytes);
+    let goal = CoverageGoal::assess(bytes_needed, reclaimable_bytes, protected);
+
+    let mut warnings: Vec<String> = coverage
+        .protections
+        .iter()
+        .filter(|covered| bytes_to_pct(covered.bytes, used) > BROAD_PROTECTION_PCT)
+        .map(|covered| {
+            format!(
+                "{} shields {:.0}% of the mount's used space; check it is not broader than \
+                 intended",
+                covered.path.display(),
+                bytes_to_pct(covered.bytes, used)
+            )
+        })
+        .collect();
+    match goal {
+        CoverageGoal::BlockedByProtection => warnings.push(format!(
+            "{target_pct:.0}% free needs {} more but only {} is unprotected under watched \
+             roots or in ballast; protections hold the other {}",
+            format_bytes(bytes_needed),
+            format_bytes(reclaimable_bytes),
+            format_bytes(protected),
+        )),
+        CoverageGoal::Unreachable => warnings.push(format!(
+            "{target_pct:.0}% free needs {} more, beyond everything under watched roots and \
+             in ballast even with protections lifted",
+            format_bytes(bytes_needed),
+        )),
+        CoverageGoal::Met | CoverageGoal::Reachable => {}
+    }
+
+    CoverageAssessment {
+        ballast_bytes,
+        reclaimable_bytes,
+        target_pct,
+        bytes_needed,
+        goal,
+        warnings,
+    }
+}
+
+fn coverage_json(mount: &Path, coverage: &MountCoverage, assessment: &CoverageAssessment) -> Value {
+    let used = coverage.used_bytes();
+    let protected = coverage.protected_bytes();
+    let protections: Vec<Value> = coverage
+        .protections
+        .iter()
+        .map(|covered| {
+            let source = match &covered.source {
+                protection::ProtectionSource::MarkerFile => "marker".to_string(),
+                protection::ProtectionSource::ConfigPattern(p) => format!("config:{p}"),
+            };
+            let pct = bytes_to_pct(covered.bytes, used);
+            json!({
+                "path": covered.path.to_string_lossy(),
+                "source": source,
+                "bytes": covered.bytes,
+                "dirs": covered.dirs,
+                "pct_of_used": pct,
+                "broad": pct > BROAD_PROTECTION_PCT,
+            })
+        })
+        .collect();
+    json!({
+        "mount": mount.to_string_lossy(),
+        "fs_type": coverage.stats.fs_type,
+        "roots": coverage.roots.iter().map(|r| r.to_string_lossy()).collect::<Vec<_>>(),
+        "used_bytes": used,
+        "watched_bytes": coverage.watched_bytes,
+        "watched_dirs": coverage.watched_dirs,
+        "protected_bytes": protected,
+        "protected_dirs": coverage.protected_dirs(),
+        "protected_pct": bytes_to_pct(protected, coverage.watched_bytes),
+        "ballast_bytes": assessment.ballast_bytes,
+        "green_min_free_pct": assessment.target_pct,
+        "bytes_needed": assessment.bytes_needed,
+        "reclaimable_bytes": assessment.reclaimable_bytes,
+        "goal": assessment.goal.as_str(),
+        "protections": protections,
+        "warnings": assessment.warnings,
+    })
+}
+
+fn run_unprotect(cli: &Cli, args: &UnprotectArgs) -> Result<(), CliError> {
+    // Canonicalize to resolve symlinks and relative components.
+    let canonical = args
+        .path
+        .canonicalize()
+        .map_err(|e| CliError::User(format!("cannot resolve path {}: {e}", args.path.display())))?;
+
+    let removed =
+        protection::remove_marker(&canonical).map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if removed {
+                println!("Unprotected: {} (marker removed)", canonical.display());
+            } else {
+                println!(
+                    "No protection marker found at {}",
+                    canonical.join(protection::MARKER_FILENAME).display(),
+                );
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "unprotect",
+                "path": canonical.to_string_lossy(),
+                "removed": removed,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
+    let filter = CandidateFilter {
+        ages: AgeWindow::from_args(args.older_than.as_deref(), args.newer_than.as_deref())?,
+        paths: PathGlobs::from_args(&args.include, &args.exclude)?,
+    };
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    if args.daemon {
+        match daemon_request(&config, &ControlRequest::Scan) {
+            Some(ControlReply::Accepted) => {
+                match output_mode(cli) {
+                    OutputMode::Human => {
+                        println!("Daemon scan queued; results appear in `sbh log`.");
+                    }
+                    OutputMode::Json => {
+                        write_json_line(&json!({
+                            "command": "scan",
+                            "via": "daemon",
+                            "queued": true,
+                        }))?;
+                    }
+                }
+                return Ok(());
+            }
+            Some(reply) => return Err(daemon_reply_error(reply)),
+            None => {
+                if output_mode(cli) == OutputMode::Human {
+                    eprintln!("No daemon is listening; running a local scan report.");
+                }
+            }
+        }
+    }
+    let start = std::time::Instant::now();
+
+    // Determine scan roots: CLI paths or configured watched paths.
+    // Canonicalize to ensure absolute paths for system protection checks.
+    let raw_roots = if args.paths.is_empty() {
+        config.scanner.root_paths.clone()
+    } else {
+        args.paths.clone()
+    };
+
+    let root_paths: Vec<PathBuf> = raw_roots
         .into_iter()
         .filter_map(|p| match p.canonicalize() {
             Ok(abs) => Some(abs),
@@ -3694,7 +7063,17 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
     if root_paths.is_empty() {
         return Err(CliError::User("no valid scan paths found".to_string()));
     }
+    if args.orphaned_fds {
+        return run_scan_orphaned_fds(cli, args, &root_paths);
+    }
+    diag::debug("scan", format_args!("scan roots: {root_paths:?}"));
     let scan_roots = root_paths.clone();
+    // Started before the walk so nothing created during it is missed.
+    let watcher = if args.watch {
+        Some(TreeWatcher::new(&root_paths).map_err(|e| CliError::Runtime(e.to_string()))?)
+    } else {
+        None
+    };
 
     // Build protection registry from config patterns.
     let protection_patterns = if config.scanner.protected_paths.is_empty() {
@@ -3719,54 +7098,87 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
             .cloned()
             .collect::<HashSet<_>>(),
     };
-    let walker = DirectoryWalker::new(walker_config, protection);
-
-    // Walk the filesystem.
-    let entries = walker
-        .walk()
-        .map_err(|e| CliError::Runtime(e.to_string()))?;
-    let dir_count = entries.len();
-
-    // Classify and score each entry first. Open-file checks are expensive and only
-    // needed for candidates that survive score/veto filters.
-    let registry = ArtifactPatternRegistry::default();
-    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes);
-    let now = SystemTime::now();
-
-    let mut preliminary: Vec<_> = entries
-        .iter()
-        .map(|entry| {
-            let classification = registry.classify(&entry.path, entry.structural_signals);
-            let age = now
-                .duration_since(entry.metadata.effective_age_timestamp())
-                .unwrap_or_default();
-            let candidate = CandidateInput {
-                path: entry.path.clone(),
-                size_bytes: entry.metadata.content_size_bytes,
-                age,
-                classification,
-                signals: entry.structural_signals,
-                is_open: false,
-                excluded: false,
-            };
-            engine.score_candidate(&candidate, 0.0) // No pressure urgency for manual scan.
-        })
-        .filter(|score| !score.vetoed && score.total_score >= args.min_score)
-        .collect();
+    // Baseline for --profile-syscalls: a portable-mode walk of the same roots.
+    let portable_syscalls = if args.profile_syscalls {
+        let profile = std::sync::Arc::new(SyscallProfile::default());
+        let baseline_protection = ProtectionRegistry::new(protection_patterns)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        DirectoryWalker::new(walker_config.clone(), baseline_protection)
+            .with_large_files(config.scanner.large_file_min_bytes)
+            .with_artifact_roots(config.scanner.artifact_root_sampling())
+            .with_device_limits(config.scanner.device_limits())
+            .with_stat_mode(StatMode::Portable)
+            .with_syscall_profile(std::sync::Arc::clone(&profile))
+            .walk_isolated(args.root_timeout.map(std::time::Duration::from_secs));
+        Some(profile.snapshot())
+    } else {
+        None
+    };
+    let reduced_profile = std::sync::Arc::new(SyscallProfile::default());
+    let walker = DirectoryWalker::new(walker_config, protection)
+        .with_large_files(config.scanner.large_file_min_bytes)
+        .with_artifact_roots(config.scanner.artifact_root_sampling())
+        .with_device_limits(config.scanner.device_limits())
+        .with_syscall_profile(std::sync::Arc::clone(&reduced_profile));
+
+    // Walk each root in its own error domain, scoring entries as they stream in.
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    // Only the best `top` (plus headroom for open-file vetoes) are retained;
+    // everything else just feeds the above-threshold counters.
+    let stream: &(dyn Fn(&CandidacyScore) + Sync) = &|score| stream_candidate("scan", score);
+    let PipelinedScan {
+        scored: preliminary,
+        above_threshold,
+        above_threshold_bytes,
+        walked: dir_count,
+        roots: root_reports,
+        mut timings,
+    } = walk_and_score(
+        &walker,
+        args.root_timeout.map(std::time::Duration::from_secs),
+        &engine,
+        args.min_score,
+        &filter,
+        Some(
+            args.top
+                .saturating_add(args.top.max(OPEN_FILE_VETO_HEADROOM)),
+        ),
+        args.json_stream.then_some(stream),
+    );
+    let walk_complete = root_reports.iter().all(RootWalkReport::is_complete);
+    let syscall_profile = portable_syscalls.map(|portable| (portable, reduced_profile.snapshot()));
+    diag::info(
+        "scan",
+        format_args!(
+            "walked {dir_count} entries in {:.2}s; scoring busy {:.2}s, tail {:.2}s",
+            timings.walk.as_secs_f64(),
+            timings.scoring_busy.as_secs_f64(),
+            timings.scoring_tail.as_secs_f64()
+        ),
+    );
 
-    preliminary.sort_by(|a, b| {
-        b.total_score
-            .partial_cmp(&a.total_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    diag::debug(
+        "scan",
+        format_args!(
+            "{above_threshold} entries scored at or above {:.2}; kept {}",
+            args.min_score,
+            preliminary.len()
+        ),
+    );
 
     // Open-file checks can only veto candidates, never improve scores.
     // Evaluate in rank order and stop once we have enough results.
     let mut candidates: Vec<_> = Vec::with_capacity(args.top.min(preliminary.len()));
+    let open_files_start = std::time::Instant::now();
     if args.top > 0 && !preliminary.is_empty() {
         let (open_paths, _) = collect_open_path_ancestors(&scan_roots);
         for score in preliminary {
             if is_path_open_by_ancestor(&score.path, &open_paths) {
+                diag::debug(
+                    "scan",
+                    format_args!("open-file veto: {}", score.path.display()),
+                );
                 continue;
             }
             candidates.push(score);
@@ -3776,50 +7188,109 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
         }
     }
 
+    timings.open_files = open_files_start.elapsed();
+
     let elapsed = start.elapsed();
     let total_reclaimable: u64 = candidates.iter().map(|c| c.size_bytes).sum();
+    let total_reclaimable_disk: u64 = candidates.iter().map(|c| c.disk_bytes).sum();
 
     match output_mode(cli) {
         OutputMode::Human => {
             println!(
-                "Build Artifact Scan Results\n  Scanned: {} directories in {:.1}s\n  Candidates found: {} (above threshold {:.2})\n",
+                "Build Artifact Scan Results\n  Scanned: {} directories in {:.1}s\n  Candidates found: {} (above threshold {:.2})",
                 dir_count,
                 elapsed.as_secs_f64(),
                 candidates.len(),
                 args.min_score,
             );
+            if let Some(window) = filter.ages.describe() {
+                println!("  Age filter: {window}");
+            }
+            if let Some(globs) = filter.paths.describe() {
+                println!("  Path filter: {globs}");
+            }
+            if above_threshold > candidates.len() {
+                println!(
+                    "  Showing top {} of {} above threshold ({} total)",
+                    candidates.len(),
+                    above_threshold,
+                    format_bytes(above_threshold_bytes),
+                );
+            }
+            println!();
 
             if candidates.is_empty() {
                 println!("  No candidates found above threshold.");
             } else {
                 println!(
-                    "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6}  {:<12}",
-                    "#", "Path", "Size", "Age", "Score", "Type"
+                    "  {:>3}  {:<50}  {:>10}  {:>10}  {:>10}  {:>6}  {:<12}",
+                    "#", "Path", "Size", "On disk", "Age", "Score", "Type"
                 );
-                println!("  {}", "-".repeat(100));
+                println!("  {}", "-".repeat(112));
 
                 for (i, candidate) in candidates.iter().enumerate() {
                     let age = candidate.age;
                     let age_str = format_duration(age);
                     let size_str = format_bytes(candidate.size_bytes);
+                    let disk_str = format_bytes(candidate.disk_bytes);
                     let type_str = format!("{:?}", candidate.classification.category);
                     let path_str = truncate_path(&candidate.path, 50);
 
                     println!(
-                        "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6.2}  {:<12}",
+                        "  {:>3}  {:<50}  {:>10}  {:>10}  {:>10}  {:>6.2}  {:<12}",
                         i + 1,
                         path_str,
                         size_str,
+                        disk_str,
                         age_str,
                         candidate.total_score,
                         type_str,
                     );
                 }
                 println!();
-                println!("  Total reclaimable: {}", format_bytes(total_reclaimable));
+                println!(
+                    "  Total reclaimable: {}",
+                    format_bytes_on_disk(total_reclaimable, total_reclaimable_disk)
+                );
                 println!("  Use 'sbh clean' to delete these candidates.");
             }
 
+            println!(
+                "\n  Phases: walk {:.2}s, scoring {:.2}s busy ({:.2}s after walk), open-file check {:.2}s",
+                timings.walk.as_secs_f64(),
+                timings.scoring_busy.as_secs_f64(),
+                timings.scoring_tail.as_secs_f64(),
+                timings.open_files.as_secs_f64(),
+            );
+
+            if let Some((portable, reduced)) = &syscall_profile {
+                println!("\n  Metadata syscalls per 1k entries:");
+                for (label, counts) in [("portable", portable), ("reduced", reduced)] {
+                    println!(
+                        "    {label:<9} {:>8.0}  (dir reads {}, dir stats {}, file stats {}, symlink stats {}; {} entries)",
+                        counts.per_1k_entries(),
+                        counts.dir_reads,
+                        counts.dir_stats,
+                        counts.file_stats,
+                        counts.symlink_stats,
+                        counts.entries,
+                    );
+                }
+            }
+
+            if root_reports.len() > 1 || !walk_complete {
+                println!("\n  Roots:");
+                for report in &root_reports {
+                    println!(
+                        "    {:<50}  {:>8} entries  {:>6.1}s  {}",
+                        truncate_path(&report.root, 50),
+                        report.entries,
+                        report.elapsed.as_secs_f64(),
+                        root_status_label(&report.status),
+                    );
+                }
+            }
+
             // Show protected paths if requested.
             if args.show_protected {
                 let protections = {
@@ -3839,40 +7310,37 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
             }
         }
         OutputMode::Json => {
-            let entries_json: Vec<Value> = candidates
-                .iter()
-                .map(|c| {
-                    json!({
-                        "path": c.path.to_string_lossy(),
-                        "size_bytes": c.size_bytes,
-                        "age_seconds": c.age.as_secs(),
-                        "total_score": c.total_score,
-                        "category": format!("{:?}", c.classification.category),
-                        "pattern_name": c.classification.pattern_name,
-                        "confidence": c.classification.combined_confidence,
-                        "decision": format!("{:?}", c.decision.action),
-                        "factors": {
-                            "location": c.factors.location,
-                            "name": c.factors.name,
-                            "age": c.factors.age,
-                            "size": c.factors.size,
-                            "structure": c.factors.structure,
-                            "pressure_multiplier": c.factors.pressure_multiplier,
-                        },
-                    })
-                })
-                .collect();
+            let entries_json: Vec<Value> = candidates.iter().map(candidate_json).collect();
 
             let mut payload = json!({
                 "command": "scan",
                 "scanned_directories": dir_count,
                 "elapsed_seconds": elapsed.as_secs_f64(),
                 "min_score": args.min_score,
+                "age_filter": filter.ages.to_json(),
+                "path_filter": filter.paths.to_json(),
                 "candidates_count": entries_json.len(),
+                "above_threshold_count": above_threshold,
+                "above_threshold_bytes": above_threshold_bytes,
                 "total_reclaimable_bytes": total_reclaimable,
+                "total_reclaimable_disk_bytes": total_reclaimable_disk,
                 "candidates": entries_json,
+                "roots": root_reports_json(&root_reports),
+                "phases": timings.to_json(),
             });
 
+            if let (Some((portable, reduced)), Some(obj)) =
+                (&syscall_profile, payload.as_object_mut())
+            {
+                obj.insert(
+                    "syscall_profile".to_string(),
+                    json!({
+                        "portable": syscall_counts_json(portable),
+                        "reduced": syscall_counts_json(reduced),
+                    }),
+                );
+            }
+
             if args.show_protected {
                 let protections = {
                     let prot = walker.protection().read();
@@ -3900,681 +7368,4317 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
         }
     }
 
-    Ok(())
+    if let Some(watcher) = &watcher {
+        let shown = candidates.iter().map(|c| c.path.clone()).collect();
+        return run_scan_watch(cli, args, &walker, watcher, &engine, &scan_roots, shown);
+    }
+
+    if walk_complete {
+        Ok(())
+    } else {
+        let incomplete = root_reports
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.status,
+                    RootStatus::Partial { .. } | RootStatus::Failed { .. }
+                )
+            })
+            .count();
+        Err(CliError::Partial(format!(
+            "{incomplete} of {} scan roots did not complete; results are partial",
+            root_reports.len()
+        )))
+    }
 }
 
-#[allow(clippy::too_many_lines)]
-fn run_clean(cli: &Cli, args: &CleanArgs) -> Result<(), CliError> {
-    let config =
-        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
-    let start = std::time::Instant::now();
+/// A scored candidate as it appears in `scan` JSON output.
+fn candidate_json(c: &CandidacyScore) -> Value {
+    json!({
+        "path": c.path.to_string_lossy(),
+        "size_bytes": c.size_bytes,
+        "disk_bytes": c.disk_bytes,
+        "age_seconds": c.age.as_secs(),
+        "total_score": c.total_score,
+        "category": format!("{:?}", c.classification.category),
+        "pattern_name": c.classification.pattern_name,
+        "confidence": c.classification.combined_confidence,
+        "decision": format!("{:?}", c.decision.action),
+        "factors": {
+            "location": c.factors.location,
+            "name": c.factors.name,
+            "age": c.factors.age,
+            "size": c.factors.size,
+            "structure": c.factors.structure,
+            "pressure_multiplier": c.factors.pressure_multiplier,
+        },
+    })
+}
 
-    // Determine scan roots: CLI paths or configured watched paths.
-    // Canonicalize to ensure absolute paths for system protection checks.
-    let raw_roots = if args.paths.is_empty() {
-        config.scanner.root_paths.clone()
-    } else {
-        args.paths.clone()
-    };
+/// `--json-stream`: one line per candidate as soon as it scores. Open-file
+/// vetoes and `--top` are applied later, so the closing report is the
+/// authoritative list.
+fn stream_candidate(command: &str, score: &CandidacyScore) {
+    let mut line = candidate_json(score);
+    line["command"] = json!(command);
+    line["event"] = json!("candidate");
+    // A closed pipe surfaces when the final report is written.
+    let _ = write_json_line(&line);
+}
 
-    let root_paths: Vec<PathBuf> = raw_roots
-        .into_iter()
-        .filter_map(|p| match p.canonicalize() {
-            Ok(abs) => Some(abs),
-            Err(e) => {
-                if output_mode(cli) == OutputMode::Human {
-                    eprintln!("Warning: skipping invalid path {}: {}", p.display(), e);
-                }
-                None
+/// `--json-stream`: one line per clean item as the executor reaches it.
+fn clean_item_observer(dry_run: bool) -> ItemObserver {
+    Box::new(move |candidate, outcome| {
+        let mut line = json!({
+            "command": "clean",
+            "event": "item",
+            "outcome": outcome.label(),
+            "dry_run": dry_run,
+            "path": candidate.path.to_string_lossy(),
+            "size_bytes": candidate.size_bytes,
+            "disk_bytes": candidate.disk_bytes,
+            "total_score": candidate.total_score,
+        });
+        match outcome {
+            ItemOutcome::Truncated { freed_bytes } => line["freed_bytes"] = json!(freed_bytes),
+            ItemOutcome::Skipped(reason) => {
+                line["reason"] = json!(reason.map_or("target_met", SkipReason::as_str));
             }
-        })
-        .collect();
+            ItemOutcome::Failed { error_code, error } => {
+                line["error_code"] = json!(error_code);
+                line["error"] = json!(error);
+            }
+            ItemOutcome::Deleted | ItemOutcome::Relocated | ItemOutcome::Quarantined => {}
+        }
+        let _ = write_json_line(&line);
+    })
+}
 
-    if root_paths.is_empty() {
-        return Err(CliError::User("no valid scan paths found".to_string()));
-    }
+/// Longest `scan --watch` waits before rescoring artifacts that are still
+/// too young or too small to report.
+const SCAN_WATCH_RECHECK: std::time::Duration = std::time::Duration::from_secs(5);
 
-    // Build protection registry.
-    let protection_patterns = if config.scanner.protected_paths.is_empty() {
-        None
-    } else {
-        Some(config.scanner.protected_paths.as_slice())
-    };
-    let protection = ProtectionRegistry::new(protection_patterns)
-        .map_err(|e| CliError::Runtime(e.to_string()))?;
+/// Quiet period that ends a `scan --watch` event batch.
+const SCAN_WATCH_SETTLE: std::time::Duration = std::time::Duration::from_millis(500);
 
-    // Walk the filesystem.
-    let walker_config = WalkerConfig {
-        root_paths: root_paths.clone(),
-        max_depth: config.scanner.max_depth,
-        follow_symlinks: config.scanner.follow_symlinks,
-        cross_devices: config.scanner.cross_devices,
-        parallelism: config.scanner.parallelism,
-        excluded_paths: config
-            .scanner
-            .excluded_paths
-            .iter()
-            .cloned()
-            .collect::<HashSet<_>>(),
+/// `scan --watch`: after the initial report, follow filesystem events and
+/// report artifacts as they cross `--min-score` (`+`) or disappear (`-`).
+/// Only changed directories are re-read; artifacts below the threshold are
+/// kept and rescored as they age.
+#[allow(clippy::too_many_lines)]
+fn run_scan_watch(
+    cli: &Cli,
+    args: &ScanArgs,
+    walker: &DirectoryWalker,
+    watcher: &TreeWatcher,
+    engine: &ScoringEngine,
+    roots: &[PathBuf],
+    mut reported: HashSet<PathBuf>,
+) -> Result<(), CliError> {
+    let mode = output_mode(cli);
+    let filter = CandidateFilter {
+        ages: AgeWindow::from_args(args.older_than.as_deref(), args.newer_than.as_deref())?,
+        paths: PathGlobs::from_args(&args.include, &args.exclude)?,
     };
-    let walker = DirectoryWalker::new(walker_config, protection);
-    let entries = walker
-        .walk()
-        .map_err(|e| CliError::Runtime(e.to_string()))?;
-    let dir_count = entries.len();
+    let registry = ArtifactPatternRegistry::default();
+    let mut pending: HashMap<PathBuf, WalkEntry> = HashMap::new();
+    if mode == OutputMode::Human {
+        println!(
+            "\nWatching {} root(s) for new artifacts (Ctrl-C to exit)...",
+            watcher.roots().len()
+        );
+        io::stdout().flush()?;
+    }
 
-    // Count protected directories encountered.
-    let protected_count = walker.protection().read().list_protections().len();
+    loop {
+        let changes = watcher.wait(SCAN_WATCH_RECHECK, SCAN_WATCH_SETTLE);
+        if changes.rescan {
+            match mode {
+                OutputMode::Human => eprintln!(
+                    "Warning: filesystem events were lost; rerun `sbh scan` for a complete report."
+                ),
+                OutputMode::Json => write_json_line(&json!({
+                    "command": "scan",
+                    "mode": "watch",
+                    "event": "events_lost",
+                }))?,
+            }
+        }
 
-    // Classify and score each entry.
-    // Optimize: Score first with is_open=false, then filter, then check open files on survivors.
-    // Also apply CLI min_score override to the engine config.
-    let registry = ArtifactPatternRegistry::default();
-    let mut scoring_config = config.scoring.clone();
-    scoring_config.min_score = args.min_score;
-    let engine = ScoringEngine::from_config(&scoring_config, config.scanner.min_file_age_minutes);
-    let now = SystemTime::now();
+        let is_gone = |path: &Path| path.ancestors().any(|a| changes.removed.contains(a));
+        let mut gone: Vec<PathBuf> = reported.iter().filter(|p| is_gone(p)).cloned().collect();
+        gone.sort();
+        reported.retain(|p| !is_gone(p));
+        pending.retain(|p, _| !is_gone(p));
 
-    let mut scored: Vec<CandidacyScore> = entries
-        .iter()
-        .map(|entry| {
-            let classification = registry.classify(&entry.path, entry.structural_signals);
-            let age = now
-                .duration_since(entry.metadata.effective_age_timestamp())
-                .unwrap_or_default();
-            let candidate = CandidateInput {
-                path: entry.path.clone(),
-                size_bytes: entry.metadata.content_size_bytes,
-                age,
-                classification,
-                signals: entry.structural_signals,
-                is_open: false, // Deferred check
-                excluded: false,
-            };
-            engine.score_candidate(&candidate, 0.0)
-        })
-        .filter(|score| !score.vetoed && score.total_score >= args.min_score)
-        .collect();
+        let fresh = walker
+            .stream_changes(&changes)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        for entry in fresh {
+            if reported.contains(&entry.path) {
+                continue;
+            }
+            let classification = registry.classify_entry(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.structural_signals,
+            );
+            if classification.category == ArtifactCategory::Unknown {
+                pending.remove(&entry.path);
+            } else {
+                pending.insert(entry.path.clone(), entry);
+            }
+        }
+        {
+            let protection = walker.protection().read();
+            pending.retain(|p, _| !protection.is_protected(p));
+        }
 
-    // Filter open files from survivors.
-    if !scored.is_empty() {
-        let (open_paths, _) = collect_open_path_ancestors(&root_paths);
-        scored.retain(|candidate| !is_path_open_by_ancestor(&candidate.path, &open_paths));
+        let now = SystemTime::now();
+        let mut appeared: Vec<CandidacyScore> = pending
+            .values()
+            .map(|entry| score_walk_entry(&registry, engine, entry, now))
+            .filter(|score| {
+                !score.vetoed && score.total_score >= args.min_score && filter.admits(score)
+            })
+            .collect();
+        if !appeared.is_empty() {
+            // Held-open artifacts stay pending until their writer lets go.
+            let (open_paths, _) = collect_open_path_ancestors(roots);
+            appeared.retain(|score| !is_path_open_by_ancestor(&score.path, &open_paths));
+        }
+        appeared.sort_by(|a, b| b.total_score.total_cmp(&a.total_score));
+
+        for score in &appeared {
+            pending.remove(&score.path);
+            reported.insert(score.path.clone());
+            match mode {
+                OutputMode::Human => println!(
+                    "  + {}  {}  score {:.2}  {:?}",
+                    score.path.display(),
+                    format_bytes(score.size_bytes),
+                    score.total_score,
+                    score.classification.category,
+                ),
+                OutputMode::Json => write_json_line(&json!({
+                    "command": "scan",
+                    "mode": "watch",
+                    "event": "appeared",
+                    "path": score.path.to_string_lossy(),
+                    "size_bytes": score.size_bytes,
+                    "age_seconds": score.age.as_secs(),
+                    "total_score": score.total_score,
+                    "category": format!("{:?}", score.classification.category),
+                }))?,
+            }
+        }
+        for path in &gone {
+            match mode {
+                OutputMode::Human => println!("  - {}", path.display()),
+                OutputMode::Json => write_json_line(&json!({
+                    "command": "scan",
+                    "mode": "watch",
+                    "event": "removed",
+                    "path": path.to_string_lossy(),
+                }))?,
+            }
+        }
+        io::stdout().flush()?;
     }
+}
 
-    let scan_elapsed = start.elapsed();
-
-    // Build deletion plan.
-    let deletion_config = DeletionConfig {
-        max_batch_size: args.max_items.unwrap_or(config.scanner.max_delete_batch),
-        dry_run: args.dry_run,
-        min_score: args.min_score,
-        check_open_files: true,
-        ..Default::default()
+/// `scan --orphaned-fds`: unlinked files still held open on the mounts of
+/// `roots`, grouped by holding process, optionally truncated in place.
+#[allow(clippy::too_many_lines)]
+fn run_scan_orphaned_fds(cli: &Cli, args: &ScanArgs, roots: &[PathBuf]) -> Result<(), CliError> {
+    let human = output_mode(cli) == OutputMode::Human;
+    #[cfg(unix)]
+    let devices: HashSet<u64> = {
+        use std::os::unix::fs::MetadataExt;
+        roots
+            .iter()
+            .filter_map(|root| std::fs::metadata(root).ok())
+            .map(|meta| meta.dev())
+            .collect()
     };
-    let executor = DeletionExecutor::new(deletion_config, None);
-    let plan = executor.plan(scored);
+    #[cfg(not(unix))]
+    let devices: HashSet<u64> = {
+        let _ = roots;
+        HashSet::new()
+    };
+    let scan = orphaned_fds::scan(&devices);
 
-    if plan.candidates.is_empty() {
-        match output_mode(cli) {
-            OutputMode::Human => {
+    if human {
+        if scan.holders.is_empty() {
+            println!("No open-but-deleted files on the scanned mounts.");
+        } else {
+            println!(
+                "Open-but-deleted files: {} pinned by {} processes\n",
+                format_bytes(scan.wasted_bytes),
+                scan.holders.len()
+            );
+            for holder in &scan.holders {
                 println!(
-                    "Scanned {dir_count} directories in {:.1}s — no cleanup candidates found above threshold {:.2}.",
-                    scan_elapsed.as_secs_f64(),
-                    args.min_score
+                    "  {} (PID {}): {} in {} files",
+                    holder.comm,
+                    holder.pid,
+                    format_bytes(holder.wasted_bytes),
+                    holder.files.len()
                 );
-                if protected_count > 0 {
+                for file in &holder.files {
                     println!(
-                        "  {protected_count} directories protected (use 'sbh protect --list' to see)."
+                        "    fd {:<4} {:>10}  {}",
+                        file.fd,
+                        format_bytes(file.size_bytes),
+                        truncate_path(&file.path, 60)
                     );
                 }
-            }
-            OutputMode::Json => {
-                let payload = json!({
-                    "command": "clean",
-                    "scanned_directories": dir_count,
-                    "elapsed_seconds": scan_elapsed.as_secs_f64(),
-                    "candidates_count": 0,
-                    "items_deleted": 0,
-                    "bytes_freed": 0,
-                    "dry_run": args.dry_run,
-                    "protected_count": protected_count,
-                });
-                write_json_line(&payload)?;
+                println!("    fix: {}", holder.suggestion());
             }
         }
-        return Ok(());
-    }
-
-    // Display the plan.
-    if output_mode(cli) == OutputMode::Human {
-        println!("The following items will be deleted:\n");
-        print_deletion_plan(&plan);
-        println!(
-            "\nTotal: {} items, {}",
-            plan.estimated_items,
-            format_bytes(plan.total_reclaimable_bytes)
-        );
-        if protected_count > 0 {
-            println!(
-                "  {protected_count} directories protected (use 'sbh protect --list' to see)."
-            );
+        if !scan.complete {
+            println!("\nWarning: /proc walk hit its time budget; results are partial.");
         }
-        println!();
     }
 
-    // Decide execution mode.
-    if args.dry_run {
-        // Dry-run: show plan, execute in dry-run mode for the report.
-        let report = executor.execute(&plan, None);
-        match output_mode(cli) {
-            OutputMode::Human => {
-                println!(
-                    "Dry run complete: {} items ({}) would be freed.",
-                    report.items_deleted,
-                    format_bytes(report.bytes_freed),
-                );
+    let truncatable: Vec<_> = scan
+        .holders
+        .iter()
+        .flat_map(|holder| &holder.files)
+        .filter(|file| file.remedy == OrphanRemedy::Truncate)
+        .collect();
+
+    let mut bytes_freed: u64 = 0;
+    let mut failures: Vec<Value> = Vec::new();
+    if args.truncate && !truncatable.is_empty() {
+        if !args.yes {
+            if !io::stdout().is_terminal() {
+                return Err(CliError::User(
+                    "refusing to truncate held files in non-interactive mode; pass --yes to confirm"
+                        .to_string(),
+                ));
             }
-            OutputMode::Json => {
-                emit_clean_report_json(&plan, &report, dir_count, scan_elapsed, protected_count)?;
+            print!("\nTruncate {} held files? [y/N] ", truncatable.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin()
+                .read_line(&mut input)
+                .map_err(|e| CliError::Runtime(e.to_string()))?;
+            if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+                println!("Aborted.");
+                return Ok(());
             }
         }
-    } else if !io::stdout().is_terminal() && !args.yes {
-        // Non-TTY without --yes: refuse to delete silently.
-        match output_mode(cli) {
-            OutputMode::Human => {
-                eprintln!("sbh: refusing to delete in non-interactive mode without --yes");
-            }
-            OutputMode::Json => {
-                let payload = json!({
-                    "command": "clean",
-                    "error": "non_interactive_without_yes",
-                    "candidates_count": plan.estimated_items,
-                });
-                write_json_line(&payload)?;
+        for file in &truncatable {
+            match orphaned_fds::truncate_orphan(file) {
+                Ok(freed) => bytes_freed += freed,
+                Err(e) => {
+                    if human {
+                        eprintln!("  PID {} fd {}: {e}", file.pid, file.fd);
+                    }
+                    failures.push(json!({
+                        "pid": file.pid,
+                        "fd": file.fd,
+                        "error": e.to_string(),
+                    }));
+                }
             }
         }
-        return Err(CliError::User(
-            "pass --yes to confirm deletion in non-interactive mode".to_string(),
-        ));
-    } else if args.yes || !io::stdout().is_terminal() {
-        // Automatic mode: confirmed via --yes.
-        let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
-        let collector = std::sync::Arc::new(FsStatsCollector::new(
-            platform,
-            std::time::Duration::from_millis(500),
-        ));
-        let pressure_check = build_pressure_check(args.target_free, collector);
-        let report = executor.execute(
-            &plan,
-            pressure_check
-                .as_ref()
-                .map(|f| f as &dyn Fn(&std::path::Path) -> bool),
-        );
-
-        match output_mode(cli) {
-            OutputMode::Human => {
-                print_clean_summary(&report);
-            }
-            OutputMode::Json => {
-                emit_clean_report_json(&plan, &report, dir_count, scan_elapsed, protected_count)?;
-            }
+        if human {
+            println!("Truncated held files: {} freed.", format_bytes(bytes_freed));
         }
-    } else {
-        // Interactive mode.
-        run_interactive_clean(
-            cli,
-            &plan,
-            args,
-            &root_paths,
-            dir_count,
-            scan_elapsed,
-            protected_count,
-        )?;
     }
 
+    if !human {
+        let holders: Vec<Value> = scan
+            .holders
+            .iter()
+            .map(|holder| {
+                let files: Vec<Value> = holder
+                    .files
+                    .iter()
+                    .map(|file| {
+                        json!({
+                            "fd": file.fd,
+                            "path": file.path.to_string_lossy(),
+                            "size_bytes": file.size_bytes,
+                            "remedy": file.remedy.as_str(),
+                        })
+                    })
+                    .collect();
+                json!({
+                    "pid": holder.pid,
+                    "comm": holder.comm,
+                    "unit": holder.unit,
+                    "wasted_bytes": holder.wasted_bytes,
+                    "remedy": holder.remedy().as_str(),
+                    "suggestion": holder.suggestion(),
+                    "files": files,
+                })
+            })
+            .collect();
+        write_json_line(&json!({
+            "command": "scan",
+            "mode": "orphaned_fds",
+            "wasted_bytes": scan.wasted_bytes,
+            "complete": scan.complete,
+            "holders": holders,
+            "truncated": args.truncate,
+            "bytes_freed": bytes_freed,
+            "errors": failures,
+        }))?;
+    }
+
+    if !failures.is_empty() {
+        return Err(CliError::Partial(format!(
+            "{} held files could not be truncated",
+            failures.len()
+        )));
+    }
+    if !scan.complete {
+        return Err(CliError::Partial(
+            "/proc walk hit its time budget; results are partial".to_string(),
+        ));
+    }
     Ok(())
 }
 
-/// Print the deletion plan in a numbered table.
-fn print_deletion_plan(plan: &DeletionPlan) {
-    for (i, candidate) in plan.candidates.iter().enumerate() {
-        let age_str = format_duration(candidate.age);
-        let size_str = format_bytes(candidate.size_bytes);
-        let path_str = truncate_path(&candidate.path, 60);
-
-        println!(
-            "  {:>3}. {} ({}, score {:.2}, {} old)",
-            i + 1,
-            path_str,
-            size_str,
-            candidate.total_score,
-            age_str,
-        );
+fn syscall_counts_json(counts: &SyscallCounts) -> Value {
+    let mut value = json!(counts);
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("total".to_string(), json!(counts.total()));
+        obj.insert("per_1k_entries".to_string(), json!(counts.per_1k_entries()));
     }
+    value
 }
 
-/// Build a pressure check closure if --target-free was specified.
-#[allow(clippy::type_complexity)]
-fn build_pressure_check(
-    target_free: Option<f64>,
-    collector: std::sync::Arc<FsStatsCollector>,
-) -> Option<Box<dyn Fn(&Path) -> bool>> {
-    let target = target_free?;
-    Some(Box::new(move |path: &Path| {
-        collector
-            .collect(path)
-            .map(|stats| stats.free_pct() >= target)
-            .unwrap_or(false)
-    }))
+fn root_status_label(status: &RootStatus) -> String {
+    match status {
+        RootStatus::Complete => "complete".to_string(),
+        RootStatus::Partial { reason } => format!("partial ({reason})"),
+        RootStatus::Skipped { reason } => format!("skipped ({reason})"),
+        RootStatus::Failed { error } => format!("failed ({error})"),
+    }
 }
 
-/// Interactive clean: prompt user for each candidate.
-#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
-fn run_interactive_clean(
-    cli: &Cli,
-    plan: &DeletionPlan,
-    args: &CleanArgs,
-    _root_paths: &[PathBuf],
-    dir_count: usize,
-    scan_elapsed: std::time::Duration,
-    protected_count: usize,
-) -> Result<(), CliError> {
-    let stdin = io::stdin();
-    let mut input = String::new();
-    let mut items_deleted: usize = 0;
-    let mut items_skipped: usize = 0;
-    let mut bytes_freed: u64 = 0;
-    let mut delete_all = false;
+fn root_reports_json(reports: &[RootWalkReport]) -> Vec<Value> {
+    reports
+        .iter()
+        .map(|r| {
+            let mut value = json!({
+                "path": r.root.to_string_lossy(),
+                "entries": r.entries,
+                "elapsed_seconds": r.elapsed.as_secs_f64(),
+            });
+            if let (Some(obj), Ok(Value::Object(status))) =
+                (value.as_object_mut(), serde_json::to_value(&r.status))
+            {
+                obj.extend(status);
+            }
+            value
+        })
+        .collect()
+}
 
-    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
-    // Interactive mode is slow enough that we can use short TTL or no cache,
-    // but FsStatsCollector handles mount resolution which is what we need.
-    let collector = std::sync::Arc::new(FsStatsCollector::new(
-        platform,
-        std::time::Duration::from_millis(500),
-    ));
+/// Extra candidates `scan --top N` retains beyond N so open-file vetoes
+/// (applied after scoring) rarely leave fewer than N to show.
+const OPEN_FILE_VETO_HEADROOM: usize = 64;
+
+/// Bounded hand-off between the walk collector and scoring workers. Small
+/// enough that a fast walker cannot buffer a whole tree in memory.
+const SCORING_CHANNEL_CAPACITY: usize = 1024;
+
+/// Wall-clock time of each scan phase. Scoring overlaps the walk, so
+/// `scoring_busy` (summed worker time) can exceed `scoring_tail`, the part
+/// of scoring left after the walk finished.
+#[derive(Debug, Clone, Copy, Default)]
+struct ScanPhaseTimings {
+    walk: std::time::Duration,
+    scoring_busy: std::time::Duration,
+    scoring_tail: std::time::Duration,
+    open_files: std::time::Duration,
+}
 
-    println!("Proceed with deletion? [y/N/a(ll)/s(kip)/q(uit)]");
-    println!("  y - delete this item    a - delete all remaining");
-    println!("  n - skip this item      s - skip all remaining");
-    println!("  q - quit\n");
+impl ScanPhaseTimings {
+    fn to_json(self) -> Value {
+        json!({
+            "walk_seconds": self.walk.as_secs_f64(),
+            "scoring_busy_seconds": self.scoring_busy.as_secs_f64(),
+            "scoring_tail_seconds": self.scoring_tail.as_secs_f64(),
+            "open_files_seconds": self.open_files.as_secs_f64(),
+        })
+    }
+}
 
-    for (i, candidate) in plan.candidates.iter().enumerate() {
-        // Check target_free skip condition.
-        if let Some(target) = args.target_free
-            && let Ok(stats) = collector.collect(&candidate.path)
-            && stats.free_pct() >= target
-        {
+/// Output of [`walk_and_score`].
+struct PipelinedScan {
+    /// Best unvetoed candidates at or above the score threshold, best first;
+    /// at most `keep` of them when a bound was given.
+    scored: Vec<CandidacyScore>,
+    /// Every candidate at or above the threshold, including ones not kept.
+    above_threshold: usize,
+    above_threshold_bytes: u64,
+    walked: usize,
+    roots: Vec<RootWalkReport>,
+    timings: ScanPhaseTimings,
+}
+
+/// Stream walker entries through a pool of scoring threads so stat I/O and
+/// classification/scoring overlap instead of running back to back.
+///
+/// With `keep = Some(n)` only the best `n` candidates are retained (bounded
+/// heap per worker); the rest only feed the above-threshold counters.
+/// Open-file vetoes are not applied here; they are only worth their cost
+/// for the candidates that survive scoring. Candidates `filter` rejects count
+/// as below the threshold.
+fn walk_and_score(
+    walker: &DirectoryWalker,
+    root_timeout: Option<std::time::Duration>,
+    engine: &ScoringEngine,
+    min_score: f64,
+    filter: &CandidateFilter,
+    keep: Option<usize>,
+    on_candidate: Option<&(dyn Fn(&CandidacyScore) + Sync)>,
+) -> PipelinedScan {
+    let registry = ArtifactPatternRegistry::default();
+    let now = SystemTime::now();
+    let capacity = keep.unwrap_or(usize::MAX);
+    let workers = std::thread::available_parallelism()
+        .map_or(2, std::num::NonZeroUsize::get)
+        .clamp(1, 8);
+    let start = std::time::Instant::now();
+    let (entry_tx, entry_rx) = crossbeam_channel::bounded::<WalkEntry>(SCORING_CHANNEL_CAPACITY);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let entry_rx = entry_rx.clone();
+                let registry = &registry;
+                scope.spawn(move || {
+                    let mut kept = TopCandidates::new(capacity);
+                    let mut busy = std::time::Duration::ZERO;
+                    for entry in entry_rx {
+                        let scored_at = std::time::Instant::now();
+                        let score = score_walk_entry(registry, engine, &entry, now);
+                        if !score.vetoed && score.total_score >= min_score && filter.admits(&score)
+                        {
+                            if let Some(on_candidate) = on_candidate {
+                                on_candidate(&score);
+                            }
+                            kept.push(score);
+                        }
+                        busy += scored_at.elapsed();
+                    }
+                    (kept, busy)
+                })
+            })
+            .collect();
+        drop(entry_rx);
+
+        let mut entries_walked = 0_usize;
+        let roots = walker.walk_isolated_with(root_timeout, |entry| {
+            entries_walked += 1;
+            // Workers only stop once the channel closes, so send cannot fail.
+            let _ = entry_tx.send(entry);
+        });
+        drop(entry_tx);
+        let walk = start.elapsed();
+
+        let mut top = TopCandidates::new(capacity);
+        let mut scoring_busy = std::time::Duration::ZERO;
+        for handle in handles {
+            let (kept, busy) = handle
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            top.merge(kept);
+            scoring_busy += busy;
+        }
+        let above_threshold = top.offered();
+        let above_threshold_bytes = top.offered_bytes();
+
+        PipelinedScan {
+            scored: top.into_sorted_vec(),
+            above_threshold,
+            above_threshold_bytes,
+            walked: entries_walked,
+            roots,
+            timings: ScanPhaseTimings {
+                walk,
+                scoring_busy,
+                scoring_tail: start.elapsed().saturating_sub(walk),
+                open_files: std::time::Duration::ZERO,
+            },
+        }
+    })
+}
+
+#[allow(clippy::too_many_lines)]
+fn run_bench_scan(cli: &Cli, args: &BenchScanArgs) -> Result<(), CliError> {
+    let iterations = args.iterations.max(1);
+
+    // Synthetic runs use built-in defaults (not the local config) and no
+    // minimum age, since generated directories are brand new; that keeps
+    // results comparable across machines.
+    let (config, roots, synthetic) = if args.synthetic {
+        let spec = SyntheticTreeSpec {
+            projects: args.projects,
+            seed: args
+                .seed
+                .unwrap_or_else(|| SyntheticTreeSpec::default().seed),
+            ..SyntheticTreeSpec::default()
+        };
+        let root = synthetic::default_base_dir().join(format!(
+            "sbh-bench-{:x}-{}",
+            spec.seed,
+            std::process::id()
+        ));
+        if root.exists() {
+            return Err(CliError::Runtime(format!(
+                "synthetic bench directory already exists: {}",
+                root.display()
+            )));
+        }
+        let generated_at = std::time::Instant::now();
+        let stats =
+            synthetic::generate(&root, &spec).map_err(|e| CliError::Runtime(e.to_string()))?;
+        let generate_secs = generated_at.elapsed().as_secs_f64();
+        let mut config = Config::default();
+        config.scanner.min_file_age_minutes = 0;
+        (
+            config,
+            vec![root.clone()],
+            Some((spec, stats, root, generate_secs)),
+        )
+    } else {
+        let config =
+            Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+        let roots = if args.paths.is_empty() {
+            config.scanner.root_paths.clone()
+        } else {
+            args.paths.clone()
+        };
+        (config, roots, None)
+    };
+
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let mut runs = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let protection = ProtectionRegistry::new(Some(&config.scanner.protected_paths))
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        let walker = DirectoryWalker::new(
+            WalkerConfig {
+                root_paths: roots.clone(),
+                max_depth: config.scanner.max_depth,
+                follow_symlinks: config.scanner.follow_symlinks,
+                cross_devices: config.scanner.cross_devices,
+                parallelism: config.scanner.parallelism,
+                excluded_paths: config.scanner.excluded_paths.iter().cloned().collect(),
+            },
+            protection,
+        )
+        .with_artifact_roots(config.scanner.artifact_root_sampling())
+        .with_device_limits(config.scanner.device_limits());
+        let started = std::time::Instant::now();
+        let result = walk_and_score(
+            &walker,
+            None,
+            &engine,
+            config.scoring.min_score,
+            &CandidateFilter::default(),
+            Some(20),
+            None,
+        );
+        runs.push((started.elapsed(), result));
+    }
+
+    if let Some((_, _, root, _)) = &synthetic
+        && !args.keep
+        && let Err(e) = std::fs::remove_dir_all(root)
+    {
+        eprintln!("Warning: could not remove {}: {e}", root.display());
+    }
+
+    let mut totals: Vec<f64> = runs.iter().map(|(d, _)| d.as_secs_f64()).collect();
+    totals.sort_by(f64::total_cmp);
+    let median = totals[totals.len() / 2];
+    let walked = runs.last().map_or(0, |(_, r)| r.walked);
+    #[allow(clippy::cast_precision_loss)]
+    let entries_per_sec = if median > 0.0 {
+        walked as f64 / median
+    } else {
+        0.0
+    };
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if let Some((spec, stats, root, generate_secs)) = &synthetic {
+                println!(
+                    "Synthetic tree: seed {:#x}, {} projects, {} dirs, {} files, {} logical ({} artifact roots)",
+                    spec.seed,
+                    spec.projects,
+                    stats.directories,
+                    stats.files,
+                    format_bytes(stats.logical_bytes),
+                    stats.artifact_roots,
+                );
+                println!("  Generated in {generate_secs:.2}s at {}", root.display());
+            }
             println!(
-                "  Target free space ({target:.1}%) achieved on {}. Skipping.",
-                stats.mount_point.display()
+                "\n  {:>3}  {:>9}  {:>9}  {:>12}  {:>9}  {:>10}",
+                "#", "Total", "Walk", "Score busy", "Entries", "Candidates"
             );
-            items_skipped += 1;
-            continue;
+            println!("  {}", "-".repeat(62));
+            for (i, (total, r)) in runs.iter().enumerate() {
+                println!(
+                    "  {:>3}  {:>8.3}s  {:>8.3}s  {:>11.3}s  {:>9}  {:>10}",
+                    i + 1,
+                    total.as_secs_f64(),
+                    r.timings.walk.as_secs_f64(),
+                    r.timings.scoring_busy.as_secs_f64(),
+                    r.walked,
+                    r.above_threshold,
+                );
+            }
+            println!(
+                "\n  Median {median:.3}s over {iterations} runs ({entries_per_sec:.0} entries/s)"
+            );
+        }
+        OutputMode::Json => {
+            let runs_json: Vec<Value> = runs
+                .iter()
+                .map(|(total, r)| {
+                    json!({
+                        "total_seconds": total.as_secs_f64(),
+                        "entries": r.walked,
+                        "candidates_above_threshold": r.above_threshold,
+                        "phases": r.timings.to_json(),
+                    })
+                })
+                .collect();
+            let payload = json!({
+                "command": "bench scan",
+                "roots": roots.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>(),
+                "synthetic": synthetic.as_ref().map(|(spec, stats, _, generate_secs)| json!({
+                    "spec": spec,
+                    "stats": stats,
+                    "generate_seconds": generate_secs,
+                })),
+                "runs": runs_json,
+                "median_seconds": median,
+                "entries_per_second": entries_per_sec,
+            });
+            write_json_line(&payload)?;
         }
+    }
+    Ok(())
+}
 
-        let action = if delete_all {
-            'y'
+/// Classify and score one walked entry with no pressure urgency (manual runs).
+fn score_walk_entry(
+    registry: &ArtifactPatternRegistry,
+    engine: &ScoringEngine,
+    entry: &WalkEntry,
+    now: SystemTime,
+) -> CandidacyScore {
+    let classification =
+        registry.classify_entry(&entry.path, entry.metadata.is_dir, entry.structural_signals);
+    let age = now
+        .duration_since(entry.metadata.effective_age_timestamp())
+        .unwrap_or_default();
+    let candidate = CandidateInput {
+        path: entry.path.clone(),
+        size_bytes: entry.metadata.content_size_bytes,
+        disk_bytes: entry.metadata.content_disk_bytes,
+        age,
+        classification,
+        signals: entry.structural_signals,
+        is_open: false, // Deferred check
+        excluded: false,
+    };
+    engine.score_candidate(&candidate, 0.0)
+}
+
+/// `sbh explain PATH`: run one path through protection checks,
+/// classification, and scoring as a scan would, and say why it is or is not
+/// a candidate.
+#[allow(clippy::too_many_lines)]
+fn run_explain(cli: &Cli, args: &ExplainArgs) -> Result<(), CliError> {
+    if args.rescore {
+        return run_explain_rescore(cli, args);
+    }
+    if !(0.0..=1.0).contains(&args.urgency) {
+        return Err(CliError::User(format!(
+            "--urgency must be between 0.0 and 1.0 (got {})",
+            args.urgency
+        )));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let path = args
+        .path
+        .canonicalize()
+        .map_err(|e| CliError::User(format!("cannot explain {}: {e}", args.path.display())))?;
+    let level = ExplainLevel::from_int(args.level);
+
+    // Where a scan would (or would not) reach the path.
+    let root = config
+        .scanner
+        .root_paths
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .cloned();
+    // Exclusions stop the walk where it meets them, so only those at or
+    // below the root matter.
+    let excluded_by = root.as_ref().and_then(|root| {
+        config
+            .scanner
+            .excluded_paths
+            .iter()
+            .find(|excluded| path.starts_with(excluded) && excluded.starts_with(root))
+            .cloned()
+    });
+    let mut protection = ProtectionRegistry::new(Some(&config.scanner.protected_paths))
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    for dir in path.ancestors() {
+        if dir.join(protection::MARKER_FILENAME).exists() {
+            protection.register_marker(dir);
+        }
+    }
+    let protected_by = protection.protection_reason(&path);
+
+    // Read the entry like the walker does; protections are reported above
+    // rather than allowed to hide the score.
+    let walker = DirectoryWalker::new(
+        WalkerConfig {
+            root_paths: vec![path.clone()],
+            max_depth: config.scanner.max_depth,
+            follow_symlinks: config.scanner.follow_symlinks,
+            cross_devices: true,
+            parallelism: 1,
+            excluded_paths: HashSet::new(),
+        },
+        ProtectionRegistry::marker_only(),
+    )
+    .with_artifact_roots(config.scanner.artifact_root_sampling());
+    let entry = walker.inspect(&path);
+
+    let (open_paths, _) = collect_open_path_ancestors(std::slice::from_ref(&path));
+    let is_open = is_path_open_by_ancestor(&path, &open_paths);
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let classification = entry.as_ref().map(|entry| {
+        ArtifactPatternRegistry::default().classify_entry(
+            &entry.path,
+            entry.metadata.is_dir,
+            entry.structural_signals,
+        )
+    });
+    let record = entry
+        .as_ref()
+        .zip(classification.clone())
+        .map(|(entry, classification)| {
+            let age = SystemTime::now()
+                .duration_since(entry.metadata.effective_age_timestamp())
+                .unwrap_or_default();
+            let candidate = CandidateInput {
+                path: entry.path.clone(),
+                size_bytes: entry.metadata.content_size_bytes,
+                disk_bytes: entry.metadata.content_disk_bytes,
+                age,
+                classification,
+                signals: entry.structural_signals,
+                is_open,
+                excluded: false,
+            };
+            let score = engine.score_candidate(&candidate, args.urgency);
+            DecisionRecordBuilder::new().build(&score, PolicyMode::DryRun, None, None, None)
+        });
+
+    let min_score = config.scoring.min_score;
+    let verdict = if root.is_none() {
+        "not a candidate: outside scanner.root_paths, so scans never reach it".to_string()
+    } else if let Some(excluded) = &excluded_by {
+        format!(
+            "not a candidate: under excluded path {}",
+            excluded.display()
+        )
+    } else if let Some(reason) = &protected_by {
+        format!("not a candidate: {reason}")
+    } else if let Some(record) = &record {
+        if record.vetoed {
+            format!(
+                "not a candidate: vetoed ({})",
+                record.veto_reason.as_deref().unwrap_or("unknown")
+            )
+        } else if record.total_score < min_score {
+            format!(
+                "not a candidate: score {:.3} below scoring.min_score {min_score:.2}",
+                record.total_score
+            )
+        } else if record.action != ActionRecord::Delete {
+            // `scan` lists it, but `clean` and the daemon only act on deletes.
+            format!(
+                "not a candidate: score {:.3} clears {min_score:.2} but the decision is {} \
+                 (expected loss keep {:.3}, delete {:.3})",
+                record.total_score,
+                record.action,
+                record.expected_loss_keep,
+                record.expected_loss_delete
+            )
         } else {
-            let path_str = truncate_path(&candidate.path, 60);
-            let size_str = format_bytes(candidate.size_bytes);
-            print!(
-                "  [{}/{}] {} ({}, score {:.2})? ",
-                i + 1,
-                plan.candidates.len(),
-                path_str,
-                size_str,
-                candidate.total_score,
-            );
-            io::stdout().flush()?;
+            format!(
+                "candidate: score {:.3} >= {min_score:.2}, decision {}",
+                record.total_score, record.action
+            )
+        }
+    } else {
+        "not a candidate: unreadable, or holds a .sbh-protect marker".to_string()
+    };
+    let signals = entry
+        .as_ref()
+        .map(|entry| signal_names(entry.structural_signals))
+        .unwrap_or_default();
+    let settings = explain_settings(&config, classification.as_ref());
 
-            input.clear();
-            stdin
-                .read_line(&mut input)
-                .map_err(|e| CliError::Runtime(e.to_string()))?;
-            match input.trim().to_lowercase().as_str() {
-                "y" | "yes" => 'y',
-                "a" | "all" => {
-                    delete_all = true;
-                    'y'
-                }
-                "s" | "skip" => {
-                    println!("  Skipping all remaining items.");
-                    break;
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Explain {}", path.display());
+            println!();
+            if let Some(entry) = &entry {
+                let age = SystemTime::now()
+                    .duration_since(entry.metadata.effective_age_timestamp())
+                    .unwrap_or_default();
+                println!(
+                    "  Entry:       {}, {}, {} old",
+                    if entry.metadata.is_dir {
+                        "directory"
+                    } else {
+                        "file"
+                    },
+                    format_bytes(entry.metadata.content_size_bytes),
+                    format_duration(age),
+                );
+            }
+            match &root {
+                Some(root) => println!("  Watched:     under {}", root.display()),
+                None => println!("  Watched:     no (not under any scanner.root_paths)"),
+            }
+            if let Some(excluded) = &excluded_by {
+                println!(
+                    "  Excluded:    by scanner.excluded_paths {}",
+                    excluded.display()
+                );
+            }
+            println!(
+                "  Protection:  {}",
+                protected_by.as_deref().unwrap_or("none")
+            );
+            println!(
+                "  Open files:  {}",
+                if is_open {
+                    "held open by a running process"
+                } else {
+                    "none"
                 }
-                "q" | "quit" => {
-                    println!("  Quitting without further deletions.");
-                    break;
+            );
+            if let Some(classification) = &classification {
+                println!(
+                    "  Pattern:     {} ({:?}; name {:.0}%, structure {:.0}%)",
+                    classification.pattern_name,
+                    classification.category,
+                    classification.name_confidence * 100.0,
+                    classification.structural_confidence * 100.0,
+                );
+                println!(
+                    "  Signals:     {}",
+                    if signals.is_empty() {
+                        "none".to_string()
+                    } else {
+                        signals.join(", ")
+                    }
+                );
+            }
+            println!(
+                "  Threshold:   scoring.min_score {min_score:.2}, min age {}m, urgency {:.2}",
+                config.scanner.min_file_age_minutes, args.urgency
+            );
+            for (i, setting) in settings.iter().enumerate() {
+                let label = if i == 0 { "Config:" } else { "" };
+                match &setting.default {
+                    Some(default) => println!(
+                        "  {label:<12} {} = {} (default {default})",
+                        setting.key, setting.value
+                    ),
+                    None => println!("  {label:<12} {} = {}", setting.key, setting.value),
                 }
-                _ => 'n', // Default to skip.
             }
-        };
-
-        if action == 'y' {
-            // Re-check if path is still in use before deleting.
-            let (fresh_open_paths, _) =
-                collect_open_path_ancestors(std::slice::from_ref(&candidate.path));
-            if is_path_open_by_ancestor(&candidate.path, &fresh_open_paths) {
-                eprintln!("    Skipped (now in use): {}", candidate.path.display());
-                items_skipped += 1;
-            } else {
-                match delete_single_candidate(candidate) {
-                    Ok(()) => {
-                        items_deleted += 1;
-                        bytes_freed += candidate.size_bytes;
-                        if !delete_all {
-                            println!("    Deleted.");
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("    Failed to delete {}: {e}", candidate.path.display());
+            if let Some(record) = &record {
+                println!();
+                for line in format_explain(record, level).lines() {
+                    if line.trim().is_empty() {
+                        println!();
+                    } else {
+                        println!("  {line}");
                     }
                 }
             }
-        } else {
-            items_skipped += 1;
+            println!();
+            println!("Verdict: {verdict}");
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "explain",
+                "path": path.to_string_lossy(),
+                "root": root.as_ref().map(|root| root.to_string_lossy()),
+                "excluded_by": excluded_by.as_ref().map(|p| p.to_string_lossy()),
+                "protected_by": protected_by,
+                "is_open": is_open,
+                "signals": signals,
+                "pattern": classification.as_ref().map(|classification| json!({
+                    "name": classification.pattern_name,
+                    "category": format!("{:?}", classification.category),
+                    "name_confidence": classification.name_confidence,
+                    "structural_confidence": classification.structural_confidence,
+                    "combined_confidence": classification.combined_confidence,
+                })),
+                "config": settings,
+                "min_score": min_score,
+                "min_file_age_minutes": config.scanner.min_file_age_minutes,
+                "urgency": args.urgency,
+                "candidate": verdict.starts_with("candidate"),
+                "verdict": verdict,
+                "decision": record.as_ref().map(|record| record.to_json_at_level(level)),
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Most recent deletion batches searched for a path's last deletion.
+const RESCORE_SEARCH_BATCHES: usize = 1_000;
+
+/// `sbh explain PATH --rescore`: score the inputs recorded when PATH was last
+/// deleted with today's engine and config, and say whether the result moved
+/// and which of the two changed.
+fn run_explain_rescore(cli: &Cli, args: &ExplainArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    // The path is usually long gone, so make it absolute without resolving it.
+    let path = std::path::absolute(&args.path)?;
+    let level = ExplainLevel::from_int(args.level);
+    let manifests = AuditLog::for_paths(&config.paths)
+        .list(RESCORE_SEARCH_BATCHES, Some(&path))
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let (manifest, item) = manifests
+        .iter()
+        .find_map(|manifest| {
+            manifest
+                .items
+                .iter()
+                .find(|item| item.path == path)
+                .map(|item| (manifest, item))
+        })
+        .ok_or_else(|| {
+            CliError::User(format!(
+                "no recorded deletion of {} (see 'sbh audit list --path')",
+                path.display()
+            ))
+        })?;
+    let inputs = item.inputs.as_ref().ok_or_else(|| {
+        CliError::User(format!(
+            "batch {} predates recorded scoring inputs; nothing to re-score",
+            manifest.batch_id
+        ))
+    })?;
+
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let score = engine.score_candidate(&inputs.to_candidate(&item.path), inputs.urgency);
+    let record = DecisionRecordBuilder::new().build(&score, PolicyMode::DryRun, None, None, None);
+    render_rescore(
+        output_mode(cli),
+        level,
+        manifest,
+        item,
+        inputs,
+        &record,
+        engine.config_hash(),
+    )
+}
+
+/// Compare a recorded deletion with how the current engine and config
+/// score the same inputs.
+fn render_rescore(
+    mode: OutputMode,
+    level: ExplainLevel,
+    manifest: &DeletionManifest,
+    item: &ManifestItem,
+    inputs: &ScoringInputs,
+    record: &DecisionRecord,
+    config_hash: &str,
+) -> Result<(), CliError> {
+    let path = &item.path;
+    let engine_changed = inputs.engine_version != SCORING_ENGINE_VERSION;
+    let config_changed = inputs.config_hash != config_hash;
+    let reproduced =
+        (record.total_score - item.score).abs() < 1e-9 && record.action == ActionRecord::Delete;
+    let cause = match (engine_changed, config_changed) {
+        (true, true) => "both the scoring engine and the scoring config changed",
+        (true, false) => "the scoring engine changed; the config did not",
+        (false, true) => "the scoring config changed; the engine did not",
+        (false, false) => "neither the engine nor the config changed",
+    };
+    let verdict = if reproduced {
+        format!(
+            "reproduced: still scores {:.3} and would be deleted ({cause})",
+            record.total_score
+        )
+    } else {
+        format!(
+            "differs: score {:.3} -> {:.3}, decision now {} ({cause})",
+            item.score, record.total_score, record.action
+        )
+    };
+
+    match mode {
+        OutputMode::Human => {
+            println!("Re-score {}", path.display());
+            println!();
+            println!(
+                "  Deleted:     {} in batch {} ({})",
+                manifest.created_at,
+                manifest.batch_id,
+                item.action.as_str()
+            );
+            println!(
+                "  Recorded:    score {:.3}, engine v{}, config {}, urgency {:.2}",
+                item.score, inputs.engine_version, inputs.config_hash, inputs.urgency
+            );
+            println!(
+                "  Now:         score {:.3}, engine v{SCORING_ENGINE_VERSION}, config {config_hash}",
+                record.total_score
+            );
+            println!();
+            for line in format_explain(record, level).lines() {
+                if line.trim().is_empty() {
+                    println!();
+                } else {
+                    println!("  {line}");
+                }
+            }
+            println!();
+            println!("Verdict: {verdict}");
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "explain",
+                "path": path.to_string_lossy(),
+                "rescore": {
+                    "batch_id": manifest.batch_id,
+                    "deleted_at": manifest.created_at,
+                    "recorded": {
+                        "score": item.score,
+                        "engine_version": inputs.engine_version,
+                        "config_hash": inputs.config_hash,
+                    },
+                    "current": {
+                        "score": record.total_score,
+                        "action": record.action,
+                        "engine_version": SCORING_ENGINE_VERSION,
+                        "config_hash": config_hash,
+                    },
+                    "reproduced": reproduced,
+                    "engine_changed": engine_changed,
+                    "config_changed": config_changed,
+                },
+                "verdict": verdict,
+                "decision": record.to_json_at_level(level),
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// One config value that fed an explained score.
+#[derive(Debug, Serialize)]
+struct ExplainSetting {
+    key: String,
+    value: String,
+    /// The built-in default, when the config overrides it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+}
+
+/// The config values scoring `classification` reads, each flagged with its
+/// default when the config changes it.
+fn explain_settings(
+    config: &Config,
+    classification: Option<&ArtifactClassification>,
+) -> Vec<ExplainSetting> {
+    fn setting(
+        key: &str,
+        value: &dyn std::fmt::Display,
+        default: &dyn std::fmt::Display,
+    ) -> ExplainSetting {
+        let (value, default) = (value.to_string(), default.to_string());
+        ExplainSetting {
+            key: key.to_string(),
+            default: (value != default).then_some(default),
+            value,
+        }
+    }
+    let defaults = Config::default();
+    let (scoring, base) = (&config.scoring, &defaults.scoring);
+    let mut settings = vec![
+        setting(
+            "scanner.min_file_age_minutes",
+            &config.scanner.min_file_age_minutes,
+            &defaults.scanner.min_file_age_minutes,
+        ),
+        setting("scoring.min_score", &scoring.min_score, &base.min_score),
+        setting(
+            "scoring.location_weight",
+            &scoring.location_weight,
+            &base.location_weight,
+        ),
+        setting(
+            "scoring.name_weight",
+            &scoring.name_weight,
+            &base.name_weight,
+        ),
+        setting("scoring.age_weight", &scoring.age_weight, &base.age_weight),
+        setting(
+            "scoring.size_weight",
+            &scoring.size_weight,
+            &base.size_weight,
+        ),
+        setting(
+            "scoring.structure_weight",
+            &scoring.structure_weight,
+            &base.structure_weight,
+        ),
+        setting(
+            "scoring.false_positive_loss",
+            &scoring.false_positive_loss,
+            &base.false_positive_loss,
+        ),
+        setting(
+            "scoring.false_negative_loss",
+            &scoring.false_negative_loss,
+            &base.false_negative_loss,
+        ),
+        setting(
+            "scoring.calibration_floor",
+            &scoring.calibration_floor,
+            &base.calibration_floor,
+        ),
+    ];
+    // A `[costs]` entry for the pattern wins over one for its category.
+    if let Some(classification) = classification {
+        let pattern = classification.pattern_name.as_ref();
+        let category = classification.category.config_name();
+        if let Some((key, cost)) = [pattern, category]
+            .into_iter()
+            .find_map(|key| config.costs.0.get(key).map(|cost| (key, cost)))
+        {
+            settings.push(setting(&format!("costs.{key}"), cost, &"unset"));
+        }
+    }
+    settings
+}
+
+/// Names of the structural signals that are set.
+fn signal_names(signals: StructuralSignals) -> Vec<&'static str> {
+    [
+        ("incremental", signals.has_incremental),
+        ("deps", signals.has_deps),
+        ("build", signals.has_build),
+        ("fingerprint", signals.has_fingerprint),
+        (".git", signals.has_git),
+        ("Cargo.toml", signals.has_cargo_toml),
+        ("mostly object files", signals.mostly_object_files),
+    ]
+    .into_iter()
+    .filter_map(|(name, set)| set.then_some(name))
+    .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+fn run_clean(cli: &Cli, args: &CleanArgs) -> Result<(), CliError> {
+    if args.caches {
+        return run_clean_caches(cli, args);
+    }
+    if let Some(plan_file) = &args.execute_plan {
+        return run_clean_execute_plan(cli, args, plan_file);
+    }
+    let filter = CandidateFilter {
+        ages: AgeWindow::from_args(args.older_than.as_deref(), args.newer_than.as_deref())?,
+        paths: PathGlobs::from_args(&args.include, &args.exclude)?,
+    };
+    if args.json_stream && !args.yes && !args.dry_run {
+        return Err(CliError::User(
+            "--json-stream cannot prompt for each item; pass --yes or --dry-run".to_string(),
+        ));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let start = std::time::Instant::now();
+
+    // Determine scan roots: CLI paths or configured watched paths.
+    // Canonicalize to ensure absolute paths for system protection checks.
+    let raw_roots = if args.paths.is_empty() {
+        config.scanner.root_paths.clone()
+    } else {
+        args.paths.clone()
+    };
+
+    let mut root_paths: Vec<PathBuf> = raw_roots
+        .into_iter()
+        .filter_map(|p| match p.canonicalize() {
+            Ok(abs) => Some(abs),
+            Err(e) => {
+                if output_mode(cli) == OutputMode::Human {
+                    eprintln!("Warning: skipping invalid path {}: {}", p.display(), e);
+                }
+                None
+            }
+        })
+        .collect();
+
+    // --mount: keep only the roots on that mount and never leave its device.
+    let mount_scope = match &args.mount {
+        Some(mount) => {
+            let (mount_point, on_mount) = resolve_mount_roots(mount, &root_paths)?;
+            if output_mode(cli) == OutputMode::Human {
+                println!(
+                    "Cleaning mount {} ({} of {} configured roots).",
+                    mount_point.display(),
+                    on_mount.len(),
+                    root_paths.len()
+                );
+            }
+            root_paths = on_mount;
+            Some(mount_point)
+        }
+        None => None,
+    };
+
+    if root_paths.is_empty() {
+        return Err(CliError::User("no valid scan paths found".to_string()));
+    }
+
+    // Build protection registry.
+    let protection_patterns = if config.scanner.protected_paths.is_empty() {
+        None
+    } else {
+        Some(config.scanner.protected_paths.as_slice())
+    };
+    let protection = ProtectionRegistry::new(protection_patterns)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    // Walk the filesystem.
+    let walker_config = WalkerConfig {
+        root_paths: root_paths.clone(),
+        max_depth: config.scanner.max_depth,
+        follow_symlinks: config.scanner.follow_symlinks,
+        cross_devices: config.scanner.cross_devices && mount_scope.is_none(),
+        parallelism: config.scanner.parallelism,
+        excluded_paths: config
+            .scanner
+            .excluded_paths
+            .iter()
+            .cloned()
+            .collect::<HashSet<_>>(),
+    };
+    let walker = DirectoryWalker::new(walker_config, protection)
+        .with_large_files(config.scanner.large_file_min_bytes)
+        .with_artifact_roots(config.scanner.artifact_root_sampling())
+        .with_device_limits(config.scanner.device_limits());
+
+    // Classify and score entries as the walk streams them in.
+    // Score first with is_open=false, then filter, then check open files on survivors.
+    // Also apply CLI min_score override to the engine config.
+    let mut scoring_config = config.scoring.clone();
+    scoring_config.min_score = args.min_score;
+    let engine = ScoringEngine::from_config(&scoring_config, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let stream: &(dyn Fn(&CandidacyScore) + Sync) = &|score| stream_candidate("clean", score);
+    let PipelinedScan {
+        mut scored,
+        walked: dir_count,
+        roots: root_reports,
+        timings,
+        ..
+    } = walk_and_score(
+        &walker,
+        None,
+        &engine,
+        args.min_score,
+        &filter,
+        None,
+        args.json_stream.then_some(stream),
+    );
+    diag::info(
+        "clean",
+        format_args!(
+            "walked {dir_count} entries under {} roots in {:.2}s; scoring busy {:.2}s",
+            root_paths.len(),
+            timings.walk.as_secs_f64(),
+            timings.scoring_busy.as_secs_f64()
+        ),
+    );
+    let root_count = root_reports.len();
+    let incomplete_roots: Vec<RootWalkReport> = root_reports
+        .into_iter()
+        .filter(|r| !r.is_complete())
+        .collect();
+    for report in &incomplete_roots {
+        if output_mode(cli) == OutputMode::Human {
+            eprintln!(
+                "Warning: root {} did not complete ({}); its candidates may be incomplete",
+                report.root.display(),
+                root_status_label(&report.status)
+            );
+        }
+    }
+    let incomplete_json = root_reports_json(&incomplete_roots);
+
+    // Count protected directories encountered.
+    let protected_count = walker.protection().read().list_protections().len();
+
+    // Filter open files from survivors. Allowlisted file categories stay:
+    // the executor truncates them instead of deleting.
+    let truncatable = config.scanner.truncate_allowlist();
+    if !scored.is_empty() {
+        let (open_paths, _) = collect_open_path_ancestors(&root_paths);
+        scored.retain(|candidate| {
+            truncatable.contains(&candidate.classification.category)
+                || !is_path_open_by_ancestor(&candidate.path, &open_paths)
+        });
+    }
+
+    let scan_elapsed = start.elapsed();
+
+    // Build deletion plan.
+    let deletion_config = DeletionConfig {
+        max_batch_size: args.max_items.unwrap_or(config.scanner.max_delete_batch),
+        dry_run: args.dry_run,
+        min_score: args.min_score,
+        check_open_files: true,
+        truncate_categories: truncatable,
+        relocation: config.archive.relocation(),
+        quarantine_ttl: args.quarantine.then(|| config.quarantine.ttl()),
+        quarantine_offload: config.quarantine.offload(),
+        io_throttle: config.scanner.io_throttle(),
+        workers: config.scanner.delete_workers,
+        ..Default::default()
+    };
+    let audit_log = AuditLog::for_paths(&config.paths);
+    let mut executor = DeletionExecutor::new(deletion_config, None).with_audit(
+        audit_log.clone(),
+        AuditContext {
+            source: "clean",
+            ..Default::default()
+        },
+    );
+    if args.json_stream {
+        executor = executor.with_observer(clean_item_observer(args.dry_run));
+    }
+    // With --target-free on a single mount, plan only the cheapest set that
+    // reaches the target instead of everything above threshold.
+    let shortfall = args
+        .target_free
+        .and_then(|target| reclaim_shortfall(target, mount_scope.as_deref(), &root_paths));
+    let plan = match &shortfall {
+        Some((_, bytes_needed)) => executor.plan_for_target(scored, *bytes_needed),
+        None => executor.plan(scored),
+    };
+    diag::debug(
+        "clean",
+        format_args!(
+            "plan: {} items, {} bytes reclaimable",
+            plan.estimated_items, plan.total_reclaimable_bytes
+        ),
+    );
+    let plan_hash = plan.plan_hash();
+    if let Some(expected) = &args.expect_plan_hash
+        && !expected.trim().eq_ignore_ascii_case(&plan_hash)
+    {
+        return Err(CliError::User(format!(
+            "plan changed since it was approved (expected hash {}, now {plan_hash}); \
+             review it again with `sbh clean --dry-run --plan-hash`",
+            expected.trim()
+        )));
+    }
+
+    if let Some(plan_file) = &args.emit_plan {
+        let mut plan = plan;
+        plan.candidates
+            .truncate(args.max_items.unwrap_or(config.scanner.max_delete_batch));
+        emit_clean_plan(cli, &plan, plan_file)?;
+        return incomplete_walk_result(&incomplete_roots, root_count);
+    }
+
+    if plan.candidates.is_empty() {
+        let target_already_met = matches!(shortfall, Some((_, 0)));
+        match output_mode(cli) {
+            OutputMode::Human => {
+                if let (true, Some((mount, _)), Some(target)) =
+                    (target_already_met, &shortfall, args.target_free)
+                {
+                    println!(
+                        "{} is already at or above {target:.1}% free — nothing to clean.",
+                        mount.display()
+                    );
+                } else {
+                    use std::fmt::Write as _;
+                    let mut filters = String::new();
+                    for part in [filter.ages.describe(), filter.paths.describe()]
+                        .into_iter()
+                        .flatten()
+                    {
+                        let _ = write!(filters, " {part}");
+                    }
+                    println!(
+                        "Scanned {dir_count} directories in {:.1}s — no cleanup candidates found above threshold {:.2}{filters}.",
+                        scan_elapsed.as_secs_f64(),
+                        args.min_score,
+                    );
+                }
+                if protected_count > 0 {
+                    println!(
+                        "  {protected_count} directories protected (use 'sbh protect --list' to see)."
+                    );
+                }
+            }
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "clean",
+                    "scanned_directories": dir_count,
+                    "elapsed_seconds": scan_elapsed.as_secs_f64(),
+                    "candidates_count": 0,
+                    "items_deleted": 0,
+                    "bytes_freed": 0,
+                    "dry_run": args.dry_run,
+                    "protected_count": protected_count,
+                    "target_already_met": target_already_met,
+                    "age_filter": filter.ages.to_json(),
+                    "path_filter": filter.paths.to_json(),
+                    "incomplete_roots": incomplete_json,
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return incomplete_walk_result(&incomplete_roots, root_count);
+    }
+
+    // Display the plan.
+    if output_mode(cli) == OutputMode::Human {
+        let action = if args.quarantine {
+            "quarantined"
+        } else {
+            "deleted"
+        };
+        println!("The following items will be {action}:\n");
+        print_deletion_plan(&plan);
+        println!(
+            "\nTotal: {} items, {}",
+            plan.estimated_items,
+            format_bytes_on_disk(
+                plan.total_reclaimable_bytes,
+                plan.total_reclaimable_disk_bytes
+            )
+        );
+        if let Some(window) = filter.ages.describe() {
+            println!("  Only candidates {window}.");
+        }
+        if let Some(globs) = filter.paths.describe() {
+            println!("  Only candidates {globs}.");
+        }
+        if let (Some((mount, bytes_needed)), Some(target)) = (&shortfall, args.target_free) {
+            println!(
+                "  Planned to free {} on {} to reach {target:.1}% free; {} more candidates not needed.",
+                format_bytes(*bytes_needed),
+                mount.display(),
+                plan.deferred
+            );
+        }
+        if protected_count > 0 {
+            println!(
+                "  {protected_count} directories protected (use 'sbh protect --list' to see)."
+            );
+        }
+        if args.plan_hash {
+            println!("  Plan hash: {plan_hash}");
+            println!("  Execute exactly this plan with --expect-plan-hash {plan_hash} --yes.");
+        }
+        println!();
+    }
+
+    // Decide execution mode.
+    if args.dry_run {
+        // Dry-run: show plan, execute in dry-run mode for the report.
+        let report = executor.execute(&plan, None);
+        match output_mode(cli) {
+            OutputMode::Human => {
+                println!(
+                    "Dry run complete: {} items ({}) would be freed.",
+                    report.items_deleted + report.items_truncated + report.items_relocated,
+                    format_bytes_on_disk(report.bytes_freed, report.disk_bytes_freed),
+                );
+                if report.items_quarantined > 0 {
+                    println!(
+                        "  {} items ({}) would be quarantined.",
+                        report.items_quarantined,
+                        format_bytes(report.bytes_quarantined),
+                    );
+                }
+            }
+            OutputMode::Json => {
+                emit_clean_report_json(
+                    &plan,
+                    &report,
+                    &[],
+                    dir_count,
+                    scan_elapsed,
+                    protected_count,
+                    &incomplete_json,
+                )?;
+            }
+        }
+    } else if !io::stdout().is_terminal() && !args.yes {
+        // Non-TTY without --yes: refuse to delete silently.
+        return Err(CliError::User(format!(
+            "refusing to delete {} candidates in non-interactive mode; pass --yes to confirm",
+            plan.estimated_items
+        )));
+    } else if args.yes || !io::stdout().is_terminal() {
+        // Automatic mode: confirmed via --yes.
+        let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+        let before = snapshot_plan_mounts(&platform, &plan);
+        let collector = std::sync::Arc::new(FsStatsCollector::new(
+            std::sync::Arc::clone(&platform),
+            std::time::Duration::from_millis(500),
+        ));
+        let pressure_check = build_pressure_check(args.target_free, collector, mount_scope);
+        let report = executor.execute(
+            &plan,
+            pressure_check
+                .as_ref()
+                .map(|f| f as &(dyn Fn(&std::path::Path) -> bool + Sync)),
+        );
+        let verified = verify_clean_reclaim(platform, &plan, &report, &before);
+
+        match output_mode(cli) {
+            OutputMode::Human => {
+                print_clean_summary(&report, &verified);
+            }
+            OutputMode::Json => {
+                emit_clean_report_json(
+                    &plan,
+                    &report,
+                    &verified,
+                    dir_count,
+                    scan_elapsed,
+                    protected_count,
+                    &incomplete_json,
+                )?;
+            }
+        }
+    } else {
+        // Interactive mode.
+        run_interactive_clean(
+            cli,
+            &plan,
+            args,
+            &audit_log,
+            args.quarantine.then(|| config.quarantine.ttl()),
+            config.quarantine.offload().as_ref(),
+            mount_scope.as_deref(),
+            dir_count,
+            scan_elapsed,
+            protected_count,
+        )?;
+    }
+
+    incomplete_walk_result(&incomplete_roots, root_count)
+}
+
+/// `Partial` when some roots did not finish walking, so a caller can tell
+/// the cleanup covered less than it asked for.
+fn incomplete_walk_result(
+    incomplete: &[RootWalkReport],
+    root_count: usize,
+) -> Result<(), CliError> {
+    if incomplete.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::Partial(format!(
+        "{} of {root_count} clean roots did not complete; candidates are partial",
+        incomplete.len()
+    )))
+}
+
+/// Print the deletion plan in a numbered table.
+fn print_deletion_plan(plan: &DeletionPlan) {
+    for (i, candidate) in plan.candidates.iter().enumerate() {
+        let age_str = format_duration(candidate.age);
+        let size_str = format_bytes_on_disk(candidate.size_bytes, candidate.disk_bytes);
+        let path_str = truncate_path(&candidate.path, 60);
+
+        println!(
+            "  {:>3}. {} ({}, score {:.2}, {} old)",
+            i + 1,
+            path_str,
+            size_str,
+            candidate.total_score,
+            age_str,
+        );
+    }
+    if !plan.subsumed.is_empty() {
+        println!(
+            "  ({} nested candidates folded into their parent directories)",
+            plan.subsumed.len()
+        );
+    }
+}
+
+/// `clean --emit-plan`: write the plan for review instead of running it.
+fn emit_clean_plan(cli: &Cli, plan: &DeletionPlan, plan_file: &Path) -> Result<(), CliError> {
+    let file = PlanFile::from_plan(plan);
+    file.write(plan_file)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if !plan.candidates.is_empty() {
+                print_deletion_plan(plan);
+                println!();
+            }
+            println!(
+                "Wrote a plan of {} items ({}) to {}.",
+                file.entries.len(),
+                format_bytes(file.total_bytes),
+                plan_file.display()
+            );
+            println!("  Plan hash: {}", file.plan_hash);
+            println!(
+                "  Review it, then run `sbh clean --execute-plan {} --yes`.",
+                plan_file.display()
+            );
+        }
+        OutputMode::Json => write_json_line(&json!({
+            "command": "clean",
+            "mode": "emit_plan",
+            "plan_file": plan_file,
+            "candidates_count": file.entries.len(),
+            "total_bytes": file.total_bytes,
+            "plan_hash": file.plan_hash,
+        }))?,
+    }
+    Ok(())
+}
+
+/// `clean --execute-plan`: run a reviewed plan file. Entries that changed
+/// since it was written are skipped; the rest are rescored from their
+/// recorded inputs for the audit log and deleted as planned.
+fn run_clean_execute_plan(cli: &Cli, args: &CleanArgs, plan_file: &Path) -> Result<(), CliError> {
+    if !args.yes && !args.dry_run {
+        return Err(CliError::User(
+            "--execute-plan runs unattended; pass --yes, or --dry-run to see what would still run"
+                .to_string(),
+        ));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let file = read_approved_plan(
+        plan_file,
+        args.expect_plan_hash.as_deref(),
+        &config.scanner.root_paths,
+    )?;
+    let protection_patterns = if config.scanner.protected_paths.is_empty() {
+        None
+    } else {
+        Some(config.scanner.protected_paths.as_slice())
+    };
+    let mut protection = ProtectionRegistry::new(protection_patterns)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+    let (current, stale) = file.check(&mut protection);
+
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
+    let plan = replan_entries(&engine, &current);
+    let deletion_config = DeletionConfig {
+        max_batch_size: usize::MAX,
+        dry_run: args.dry_run,
+        min_score: args.min_score,
+        check_open_files: true,
+        truncate_categories: config.scanner.truncate_allowlist(),
+        relocation: config.archive.relocation(),
+        quarantine_ttl: args.quarantine.then(|| config.quarantine.ttl()),
+        quarantine_offload: config.quarantine.offload(),
+        io_throttle: config.scanner.io_throttle(),
+        workers: config.scanner.delete_workers,
+        ..Default::default()
+    };
+    let executor = DeletionExecutor::new(deletion_config, None).with_audit(
+        AuditLog::for_paths(&config.paths),
+        AuditContext {
+            source: "clean",
+            ..Default::default()
+        },
+    );
+
+    if output_mode(cli) == OutputMode::Human {
+        println!(
+            "Plan {} from {}: {} items, {}.",
+            plan_file.display(),
+            file.created_at,
+            file.entries.len(),
+            format_bytes(file.total_bytes)
+        );
+        for (entry, reason) in &stale {
+            println!(
+                "  Skipped, {} since planning: {}",
+                reason.as_str(),
+                entry.path.display()
+            );
+        }
+        println!();
+    }
+
+    let (report, verified) = if args.dry_run {
+        (executor.execute(&plan, None), Vec::new())
+    } else {
+        let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+        let before = snapshot_plan_mounts(&platform, &plan);
+        let report = executor.execute(&plan, None);
+        let verified = verify_clean_reclaim(platform, &plan, &report, &before);
+        (report, verified)
+    };
+    match output_mode(cli) {
+        OutputMode::Human => print_clean_summary(&report, &verified),
+        OutputMode::Json => {
+            let mut payload = clean_report_json(
+                &plan,
+                &report,
+                &verified,
+                0,
+                std::time::Duration::ZERO,
+                0,
+                &[],
+            );
+            payload["mode"] = json!("execute_plan");
+            payload["plan_file"] = json!(plan_file);
+            payload["plan_created_at"] = json!(file.created_at);
+            payload["stale"] = stale
+                .iter()
+                .map(|(entry, reason)| json!({ "path": entry.path, "reason": reason }))
+                .collect();
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read a plan file, refusing it unless it matches `expected_hash` (when
+/// given) and every entry lies under one of `root_paths`.
+fn read_approved_plan(
+    plan_file: &Path,
+    expected_hash: Option<&str>,
+    root_paths: &[PathBuf],
+) -> Result<PlanFile, CliError> {
+    let file = PlanFile::read(plan_file).map_err(|e| CliError::User(e.to_string()))?;
+    if let Some(expected) = expected_hash
+        && !expected.trim().eq_ignore_ascii_case(&file.plan_hash)
+    {
+        return Err(CliError::User(format!(
+            "{} is not the approved plan (expected hash {}, file has {})",
+            plan_file.display(),
+            expected.trim(),
+            file.plan_hash
+        )));
+    }
+    // Entries hold canonical paths, so compare against canonical roots.
+    let roots: Vec<PathBuf> = root_paths
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect();
+    if let Some(entry) = file.entry_outside(&roots) {
+        return Err(CliError::User(format!(
+            "{} plans {}, which is not plainly under a configured scan root \
+             (outside them, or reached through `..` or a symlink); refusing the plan",
+            plan_file.display(),
+            entry.path.display()
+        )));
+    }
+    Ok(file)
+}
+
+/// Re-score still-current plan entries from their recorded inputs, keeping
+/// the planned sizes and order.
+fn replan_entries(engine: &ScoringEngine, entries: &[&PlanEntry]) -> DeletionPlan {
+    let candidates: Vec<CandidacyScore> = entries
+        .iter()
+        .map(|entry| {
+            let input = entry.inputs.to_candidate(&entry.path);
+            let mut candidate = engine.score_candidate(&input, entry.inputs.urgency);
+            candidate.size_bytes = entry.size_bytes;
+            candidate.disk_bytes = entry.disk_bytes;
+            candidate
+        })
+        .collect();
+    DeletionPlan {
+        total_reclaimable_bytes: candidates.iter().map(|c| c.size_bytes).sum(),
+        total_reclaimable_disk_bytes: candidates.iter().map(|c| c.disk_bytes).sum(),
+        estimated_items: candidates.len(),
+        candidates,
+        subsumed: Vec::new(),
+        deferred: 0,
+    }
+}
+
+/// Build a pressure check closure if --target-free was specified.
+///
+/// With `mount_scope`, progress is read from that mount only instead of the
+/// mount holding each candidate.
+#[allow(clippy::type_complexity)]
+fn build_pressure_check(
+    target_free: Option<f64>,
+    collector: std::sync::Arc<FsStatsCollector>,
+    mount_scope: Option<PathBuf>,
+) -> Option<Box<dyn Fn(&Path) -> bool + Sync>> {
+    let target = target_free?;
+    Some(Box::new(move |path: &Path| {
+        collector
+            .collect(mount_scope.as_deref().unwrap_or(path))
+            .map(|stats| stats.free_pct() >= target)
+            .unwrap_or(false)
+    }))
+}
+
+/// Bytes `clean` must free to bring its mount to `target_free` percent.
+///
+/// Returns the mount and the shortfall (0 when already at target), or `None`
+/// when the roots span several mounts. In that case the plan keeps score
+/// order and the per-candidate pressure check decides when to stop.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn reclaim_shortfall(
+    target_free: f64,
+    mount_scope: Option<&Path>,
+    roots: &[PathBuf],
+) -> Option<(PathBuf, u64)> {
+    let platform = detect_platform().ok()?;
+    let collector = FsStatsCollector::new(platform, std::time::Duration::from_millis(500));
+    let stats = if let Some(mount) = mount_scope {
+        collector.collect(mount).ok()?
+    } else {
+        let mut all = roots.iter().map(|root| collector.collect(root));
+        let first = all.next()?.ok()?;
+        for other in all {
+            if other.ok()?.mount_point != first.mount_point {
+                diag::info(
+                    "clean",
+                    format_args!("roots span several mounts; planning in score order"),
+                );
+                return None;
+            }
+        }
+        first
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let wanted = (stats.total_bytes as f64 * target_free / 100.0).ceil() as u64;
+    Some((
+        stats.mount_point,
+        wanted.saturating_sub(stats.available_bytes),
+    ))
+}
+
+/// `clean --caches`: clean each known tool cache with its own semantics.
+#[allow(clippy::too_many_lines)]
+fn run_clean_caches(cli: &Cli, args: &CleanArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let roots = CacheRoots::from_env()
+        .ok_or_else(|| CliError::User("HOME is not set; cannot locate tool caches".to_string()))?;
+    let items = tool_caches::plan(&roots, SystemTime::now(), &config.caches);
+    let human = output_mode(cli) == OutputMode::Human;
+
+    if human {
+        if items.is_empty() {
+            println!("No known tool caches found.");
+            return Ok(());
+        }
+        println!("Tool caches:");
+        for item in &items {
+            let reclaim = match (item.native, item.cache.action) {
+                (Some(native), _) => format!("via `{}`", native.argv.join(" ")),
+                (None, CacheAction::LeaveAlone) => "left alone".to_string(),
+                (None, _) => format_bytes(item.reclaimable_bytes),
+            };
+            println!(
+                "  {:<12} {:>10} total, {:>10} reclaimable  {}",
+                item.cache.tool,
+                format_bytes(item.total_bytes),
+                reclaim,
+                item.path.display(),
+            );
+            println!("  {:<12} {}", "", item.cache.description);
+            if let Some(reason) = &item.fallback_reason {
+                println!("  {:<12} native prune not used: {reason}", "");
+            }
+        }
+    }
+
+    if !args.dry_run && !args.yes {
+        if !io::stdout().is_terminal() {
+            return Err(CliError::User(
+                "refusing to clean tool caches in non-interactive mode; pass --yes to confirm"
+                    .to_string(),
+            ));
+        }
+        print!("Proceed? [y/N] ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut bytes_freed: u64 = 0;
+    let mut failures = 0usize;
+    let mut results = Vec::with_capacity(items.len());
+    for item in &items {
+        let outcome = if args.dry_run {
+            Ok(CacheCleanOutcome {
+                bytes_freed: item.reclaimable_bytes,
+                items_removed: item.targets.len(),
+            })
+        } else {
+            tool_caches::execute(item)
+        };
+        let (outcome, error) = match outcome {
+            Ok(outcome) => (outcome, None),
+            Err(e) => {
+                failures += 1;
+                (CacheCleanOutcome::default(), Some(e.to_string()))
+            }
+        };
+        bytes_freed += outcome.bytes_freed;
+        if human && let Some(error) = &error {
+            eprintln!("  {}: failed: {error}", item.path.display());
+        }
+        results.push(json!({
+            "tool": item.cache.tool,
+            "path": item.path.to_string_lossy(),
+            "total_bytes": item.total_bytes,
+            "reclaimable_bytes": item.reclaimable_bytes,
+            "bytes_freed": outcome.bytes_freed,
+            "items_removed": outcome.items_removed,
+            "method": if item.native.is_some() { "native" } else { "direct" },
+            "native_command": item.native.map(|n| n.argv.join(" ")),
+            "fallback_reason": item.fallback_reason,
+            "error": error,
+        }));
+    }
+
+    if human {
+        if args.dry_run {
+            println!("Dry run: {} would be freed.", format_bytes(bytes_freed));
+        } else {
+            println!("Tool caches cleaned: {} freed.", format_bytes(bytes_freed));
+        }
+    } else {
+        write_json_line(&json!({
+            "command": "clean",
+            "mode": "caches",
+            "dry_run": args.dry_run,
+            "bytes_freed": bytes_freed,
+            "caches": results,
+        }))?;
+    }
+
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(CliError::Partial(format!(
+            "{failures} tool caches could not be cleaned"
+        )))
+    }
+}
+
+/// Resolve `clean --mount`: the mount point holding `mount`, and the subset
+/// of `roots` that live on that mount point.
+fn resolve_mount_roots(
+    mount: &Path,
+    roots: &[PathBuf],
+) -> Result<(PathBuf, Vec<PathBuf>), CliError> {
+    let abs = mount
+        .canonicalize()
+        .map_err(|e| CliError::User(format!("invalid mount {}: {e}", mount.display())))?;
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let collector = FsStatsCollector::new(platform, std::time::Duration::from_millis(500));
+    let mount_point = collector
+        .collect(&abs)
+        .map_err(|e| CliError::Runtime(e.to_string()))?
+        .mount_point;
+    let on_mount: Vec<PathBuf> = roots
+        .iter()
+        .filter(|root| {
+            collector
+                .collect(root)
+                .is_ok_and(|stats| stats.mount_point == mount_point)
+        })
+        .cloned()
+        .collect();
+    if on_mount.is_empty() {
+        return Err(CliError::User(format!(
+            "no configured scan roots are on mount {}; add one to scanner.root_paths or pass paths instead of --mount",
+            mount_point.display()
+        )));
+    }
+    Ok((mount_point, on_mount))
+}
+
+/// Interactive clean: prompt user for each candidate.
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+fn run_interactive_clean(
+    cli: &Cli,
+    plan: &DeletionPlan,
+    args: &CleanArgs,
+    audit_log: &AuditLog,
+    quarantine_ttl: Option<std::time::Duration>,
+    quarantine_offload: Option<&ObjectStore>,
+    mount_scope: Option<&Path>,
+    dir_count: usize,
+    scan_elapsed: std::time::Duration,
+    protected_count: usize,
+) -> Result<(), CliError> {
+    let stdin = io::stdin();
+    let mut input = String::new();
+    let mut items_deleted: usize = 0;
+    let mut items_quarantined: usize = 0;
+    let mut items_skipped: usize = 0;
+    let mut bytes_freed: u64 = 0;
+    let mut disk_bytes_freed: u64 = 0;
+    let mut delete_all = false;
+
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    // Interactive mode is slow enough that we can use short TTL or no cache,
+    // but FsStatsCollector handles mount resolution which is what we need.
+    let collector = std::sync::Arc::new(FsStatsCollector::new(
+        platform,
+        std::time::Duration::from_millis(500),
+    ));
+
+    println!("Proceed with deletion? [y/N/a(ll)/s(kip)/q(uit)]");
+    println!("  y - delete this item    a - delete all remaining");
+    println!("  n - skip this item      s - skip all remaining");
+    println!("  q - quit\n");
+
+    for (i, candidate) in plan.candidates.iter().enumerate() {
+        // Check target_free skip condition.
+        if let Some(target) = args.target_free
+            && let Ok(stats) = collector.collect(mount_scope.unwrap_or(&candidate.path))
+            && stats.free_pct() >= target
+        {
+            println!(
+                "  Target free space ({target:.1}%) achieved on {}. Skipping.",
+                stats.mount_point.display()
+            );
+            items_skipped += 1;
+            continue;
+        }
+
+        let action = if delete_all {
+            'y'
+        } else {
+            let path_str = truncate_path(&candidate.path, 60);
+            let size_str = format_bytes_on_disk(candidate.size_bytes, candidate.disk_bytes);
+            println!(
+                "  [{}/{}] {} ({}, score {:.2})",
+                i + 1,
+                plan.candidates.len(),
+                path_str,
+                size_str,
+                candidate.total_score,
+            );
+            print_content_preview(&candidate.path);
+            print!(
+                "  {}? ",
+                if quarantine_ttl.is_some() {
+                    "Quarantine"
+                } else {
+                    "Delete"
+                }
+            );
+            io::stdout().flush()?;
+
+            input.clear();
+            stdin
+                .read_line(&mut input)
+                .map_err(|e| CliError::Runtime(e.to_string()))?;
+            match input.trim().to_lowercase().as_str() {
+                "y" | "yes" => 'y',
+                "a" | "all" => {
+                    delete_all = true;
+                    'y'
+                }
+                "s" | "skip" => {
+                    println!("  Skipping all remaining items.");
+                    break;
+                }
+                "q" | "quit" => {
+                    println!("  Quitting without further deletions.");
+                    break;
+                }
+                _ => 'n', // Default to skip.
+            }
+        };
+
+        if action == 'y' {
+            // Re-check if path is still in use before deleting.
+            let (fresh_open_paths, _) =
+                collect_open_path_ancestors(std::slice::from_ref(&candidate.path));
+            if is_path_open_by_ancestor(&candidate.path, &fresh_open_paths) {
+                eprintln!("    Skipped (now in use): {}", candidate.path.display());
+                items_skipped += 1;
+                continue;
+            }
+            // Each confirmed item is its own audited batch.
+            let disposition = if quarantine_ttl.is_some() {
+                ManifestAction::Quarantine
+            } else {
+                ManifestAction::Delete
+            };
+            let manifest = DeletionManifest::new(
+                "clean",
+                None,
+                vec![ManifestItem::from_candidate(candidate, disposition, None)],
+            );
+            if let Err(e) = audit_log.record(&manifest) {
+                diag::warn(
+                    "clean",
+                    format_args!(
+                        "deletion manifest {} not fully written: {e}",
+                        manifest.batch_id
+                    ),
+                );
+            }
+            if let Some(ttl) = quarantine_ttl {
+                match quarantine::quarantine(
+                    &candidate.path,
+                    candidate.classification.category,
+                    candidate.size_bytes,
+                    ttl,
+                    quarantine_offload,
+                ) {
+                    Ok(entry) => {
+                        items_quarantined += 1;
+                        if entry.object.is_some() {
+                            bytes_freed += candidate.size_bytes;
+                            disk_bytes_freed += candidate.disk_bytes;
+                        }
+                        if !delete_all {
+                            println!("    Quarantined.");
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("    Failed to quarantine {}: {e}", candidate.path.display());
+                    }
+                }
+            } else {
+                match delete_single_candidate(candidate) {
+                    Ok(()) => {
+                        items_deleted += 1;
+                        bytes_freed += candidate.size_bytes;
+                        disk_bytes_freed += candidate.disk_bytes;
+                        if !delete_all {
+                            println!("    Deleted.");
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("    Failed to delete {}: {e}", candidate.path.display());
+                    }
+                }
+            }
+        } else {
+            items_skipped += 1;
+        }
+    }
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("\nCleanup complete:");
+            println!(
+                "  Deleted: {items_deleted} items, freed {}",
+                format_bytes_on_disk(bytes_freed, disk_bytes_freed)
+            );
+            if items_quarantined > 0 {
+                println!("  Quarantined: {items_quarantined} items (see `sbh quarantine list`)");
+            }
+            if items_skipped > 0 {
+                println!("  Skipped: {items_skipped} items");
+            }
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "clean",
+                "scanned_directories": dir_count,
+                "elapsed_seconds": scan_elapsed.as_secs_f64(),
+                "candidates_count": plan.estimated_items,
+                "items_deleted": items_deleted,
+                "items_quarantined": items_quarantined,
+                "items_skipped": items_skipped,
+                "bytes_freed": bytes_freed,
+                "disk_bytes_freed": disk_bytes_freed,
+                "dry_run": false,
+                "protected_count": protected_count,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Largest files and file types in a sample of `path`, for a reviewer to
+/// confirm it holds what its classification claims.
+fn print_content_preview(path: &Path) {
+    let preview = match ContentPreview::gather(path) {
+        Ok(preview) => preview,
+        Err(e) => {
+            println!("      (no preview: {e})");
+            return;
+        }
+    };
+    if preview.files == 0 {
+        println!("      (no files in {} directories)", preview.dirs);
+        return;
+    }
+    let largest: Vec<String> = preview
+        .largest
+        .iter()
+        .map(|file| {
+            format!(
+                "{} ({})",
+                truncate_path(&file.path, 40),
+                format_bytes(file.size_bytes)
+            )
+        })
+        .collect();
+    println!("      largest: {}", largest.join(", "));
+    let types: Vec<String> = preview
+        .types
+        .iter()
+        .map(|kind| {
+            format!(
+                "{} x{} ({})",
+                kind.extension,
+                kind.files,
+                format_bytes(kind.bytes)
+            )
+        })
+        .collect();
+    println!(
+        "      types:   {}{}",
+        types.join(", "),
+        if preview.truncated {
+            format!("  [first {} files only]", preview.files)
+        } else {
+            String::new()
+        }
+    );
+}
+
+/// Delete a single candidate path (file or directory).
+fn delete_single_candidate(candidate: &CandidacyScore) -> std::result::Result<(), String> {
+    if candidate.path.is_dir() {
+        std::fs::remove_dir_all(&candidate.path).map_err(|e| e.to_string())
+    } else {
+        std::fs::remove_file(&candidate.path).map_err(|e| e.to_string())
+    }
+}
+
+/// Mount stats for every planned candidate, taken before `execute` as the
+/// baseline for [`verify_clean_reclaim`]. Empty when stats are unavailable.
+fn snapshot_plan_mounts(
+    platform: &std::sync::Arc<dyn Platform>,
+    plan: &DeletionPlan,
+) -> HashMap<PathBuf, FsStats> {
+    let paths: Vec<PathBuf> = plan.candidates.iter().map(|c| c.path.clone()).collect();
+    FsStatsCollector::new(std::sync::Arc::clone(platform), std::time::Duration::ZERO)
+        .collect_many(&paths)
+        .unwrap_or_default()
+}
+
+/// Re-stat the mounts a clean touched and warn about any that gained far
+/// less free space than the report claims.
+fn verify_clean_reclaim(
+    platform: std::sync::Arc<dyn Platform>,
+    plan: &DeletionPlan,
+    report: &storage_ballast_helper::scanner::deletion::DeletionReport,
+    before: &HashMap<PathBuf, FsStats>,
+) -> Vec<ReclaimVerification> {
+    let fresh = FsStatsCollector::new(platform, std::time::Duration::ZERO);
+    let verified = verify_reclaim(plan, report, before, |mount| {
+        fresh.collect(mount).ok().map(|stats| stats.available_bytes)
+    });
+    for v in verified.iter().filter(|v| v.is_shortfall()) {
+        diag::warn(
+            "clean",
+            format_args!(
+                "reclaim shortfall on {}: claimed {} bytes, observed {} bytes",
+                v.mount.display(),
+                v.expected_bytes,
+                v.observed_bytes
+            ),
+        );
+    }
+    verified
+}
+
+/// Print a human-readable cleanup summary from a DeletionReport.
+fn print_clean_summary(
+    report: &storage_ballast_helper::scanner::deletion::DeletionReport,
+    verified: &[ReclaimVerification],
+) {
+    if report.dry_run {
+        println!(
+            "Dry run: {} items ({}) would be freed.",
+            report.items_deleted + report.items_truncated + report.items_relocated,
+            format_bytes_on_disk(report.bytes_freed, report.disk_bytes_freed),
+        );
+        if report.items_truncated > 0 {
+            println!(
+                "  {} of them are open files that would be truncated.",
+                report.items_truncated
+            );
+        }
+        if report.items_relocated > 0 {
+            println!(
+                "  {} of them would be moved to the archive instead of deleted.",
+                report.items_relocated
+            );
+        }
+        if report.items_quarantined > 0 {
+            println!(
+                "  {} items ({}) would be quarantined.",
+                report.items_quarantined,
+                format_bytes(report.bytes_quarantined),
+            );
+        }
+    } else {
+        println!("Cleanup complete:");
+        println!(
+            "  Deleted: {} items in {:.1}s, freed {}",
+            report.items_deleted,
+            report.duration.as_secs_f64(),
+            format_bytes_on_disk(report.bytes_freed, report.disk_bytes_freed),
+        );
+        if !report.throttled.is_zero() {
+            println!(
+                "  Paced: {:.1}s waiting out IO pressure (scanner.io_throttle_stall_pct)",
+                report.throttled.as_secs_f64(),
+            );
+        }
+        if report.items_truncated > 0 {
+            println!("  Truncated: {} open files", report.items_truncated);
+        }
+        if report.items_relocated > 0 {
+            println!(
+                "  Archived: {} items (see `sbh restore --list`)",
+                report.items_relocated
+            );
+        }
+        if report.items_quarantined > 0 {
+            println!(
+                "  Quarantined: {} items, {} reclaimed once purged (see `sbh quarantine list`)",
+                report.items_quarantined,
+                format_bytes(report.bytes_quarantined),
+            );
+        }
+        if report.items_skipped > 0 {
+            println!("  Skipped: {} items", report.items_skipped);
+        }
+        if report.items_failed > 0 {
+            println!("  Failed: {} items", report.items_failed);
+            for err in &report.errors {
+                eprintln!("    {}: {}", err.path.display(), err.error);
+            }
+        }
+        if report.circuit_breaker_tripped {
+            println!("  Warning: circuit breaker was tripped due to consecutive failures.");
+        }
+        if let Some(batch_id) = &report.audit_batch_id {
+            println!("  Audit: sbh audit show {batch_id}");
+        }
+        for v in verified.iter().filter(|v| v.is_shortfall()) {
+            println!(
+                "  Warning: {} gained only {} of the {} claimed; snapshots, hardlinks, or deleted-but-open files may still hold the space.",
+                v.mount.display(),
+                format_bytes(v.observed_bytes),
+                format_bytes(v.expected_bytes),
+            );
+        }
+    }
+}
+
+/// Emit the clean report in JSON format.
+fn emit_clean_report_json(
+    plan: &DeletionPlan,
+    report: &storage_ballast_helper::scanner::deletion::DeletionReport,
+    verified: &[ReclaimVerification],
+    dir_count: usize,
+    scan_elapsed: std::time::Duration,
+    protected_count: usize,
+    incomplete_roots: &[Value],
+) -> Result<(), CliError> {
+    write_json_line(&clean_report_json(
+        plan,
+        report,
+        verified,
+        dir_count,
+        scan_elapsed,
+        protected_count,
+        incomplete_roots,
+    ))
+}
+
+/// The clean report as JSON.
+fn clean_report_json(
+    plan: &DeletionPlan,
+    report: &storage_ballast_helper::scanner::deletion::DeletionReport,
+    verified: &[ReclaimVerification],
+    dir_count: usize,
+    scan_elapsed: std::time::Duration,
+    protected_count: usize,
+    incomplete_roots: &[Value],
+) -> Value {
+    let errors: Vec<Value> = report
+        .errors
+        .iter()
+        .map(|e| {
+            json!({
+                "path": e.path.to_string_lossy(),
+                "error": e.error,
+                "error_code": e.error_code,
+                "recoverable": e.recoverable,
+            })
+        })
+        .collect();
+    let verification: Vec<Value> = verified
+        .iter()
+        .map(|v| {
+            json!({
+                "mount": v.mount.to_string_lossy(),
+                "expected_bytes": v.expected_bytes,
+                "observed_bytes": v.observed_bytes,
+                "shortfall": v.is_shortfall(),
+            })
+        })
+        .collect();
+
+    json!({
+        "command": "clean",
+        "scanned_directories": dir_count,
+        "elapsed_seconds": scan_elapsed.as_secs_f64(),
+        "candidates_count": plan.estimated_items,
+        "plan_hash": plan.plan_hash(),
+        "subsumed_count": plan.subsumed.len(),
+        "deferred_count": plan.deferred,
+        "items_deleted": report.items_deleted,
+        "items_truncated": report.items_truncated,
+        "items_relocated": report.items_relocated,
+        "items_quarantined": report.items_quarantined,
+        "bytes_quarantined": report.bytes_quarantined,
+        "items_skipped": report.items_skipped,
+        "items_failed": report.items_failed,
+        "bytes_freed": report.bytes_freed,
+        "disk_bytes_freed": report.disk_bytes_freed,
+        "duration_seconds": report.duration.as_secs_f64(),
+        "throttled_seconds": report.throttled.as_secs_f64(),
+        "dry_run": report.dry_run,
+        "circuit_breaker_tripped": report.circuit_breaker_tripped,
+        "audit_batch_id": report.audit_batch_id,
+        "protected_count": protected_count,
+        "incomplete_roots": incomplete_roots,
+        "errors": errors,
+        "reclaim_verification": verification,
+    })
+}
+
+#[allow(
+    clippy::too_many_lines,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn run_check(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
+    if args.nagios {
+        return run_check_nagios(cli, args);
+    }
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    // Determine check path: CLI arg, or cwd.
+    let check_path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
+
+    // Project requirements from `.sbh-check.toml`; flags take precedence.
+    let policy_file = if args.no_policy_file {
+        None
+    } else {
+        find_check_policy(&check_path).map_err(|e| CliError::User(e.to_string()))?
+    };
+    let mut args = args.clone();
+    if let Some((_, policy)) = &policy_file {
+        args.need = args.need.or(policy.need);
+        args.predict = args.predict.or(policy.predict_minutes);
+        args.target_free = args.target_free.or(policy.target_free_pct);
+    }
+    let policy_path = policy_file
+        .as_ref()
+        .map(|(path, _)| path.to_string_lossy().into_owned());
+    let per_policy = policy_file
+        .as_ref()
+        .map_or_else(String::new, |(path, _)| format!(" by {}", path.display()));
+
+    let stats = platform
+        .fs_stats(&check_path)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    let free_pct = stats.free_pct();
+    let config = Config::load(cli.config.as_deref()).unwrap_or_default();
+    let threshold_pct = args
+        .target_free
+        .unwrap_or(config.pressure.yellow_min_free_pct);
+    let gha = args.format == CheckFormat::Gha;
+
+    // Fill rate from the daemon: needed for --predict, and reported as a
+    // step output under --format gha.
+    let rate_bps = if args.predict.is_some() || gha {
+        read_daemon_prediction(&config.paths.state_file, &check_path)
+    } else {
+        None
+    };
+    let minutes_until_full = rate_bps.filter(|rate| *rate > 0.0).map(|rate| {
+        // Positive rate means filling; estimate time to threshold.
+        let bytes_until_threshold = stats
+            .available_bytes
+            .saturating_sub((threshold_pct / 100.0 * stats.total_bytes as f64) as u64);
+        bytes_until_threshold as f64 / rate / 60.0
+    });
+
+    // Check 1: absolute free space requirement.
+    if let Some(need_bytes) = args.need
+        && stats.available_bytes < need_bytes
+    {
+        let message = format!(
+            "{} has {} free but {} required{per_policy}. Run: sbh emergency {}",
+            stats.mount_point.display(),
+            format_bytes(stats.available_bytes),
+            format_bytes(need_bytes),
+            check_path.display(),
+        );
+        if gha {
+            emit_check_gha(
+                CheckGhaResult::fail(CHECK_BELOW_NEED, "Not enough free disk space", message),
+                free_pct,
+                minutes_until_full,
+            )?;
+            return Err(CliError::Runtime("insufficient disk space".to_string()));
+        }
+        match output_mode(cli) {
+            OutputMode::Human => eprintln!("sbh: {message}"),
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "check",
+                    "status": "critical",
+                    "reason": CHECK_BELOW_NEED,
+                    "path": check_path.to_string_lossy(),
+                    "mount_point": stats.mount_point.to_string_lossy(),
+                    "free_bytes": stats.available_bytes,
+                    "need_bytes": need_bytes,
+                    "free_pct": free_pct,
+                    "policy_file": policy_path,
+                    "exit_code": 2,
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return Err(CliError::Runtime("insufficient disk space".to_string()));
+    }
+
+    // Check 2: percentage threshold.
+    if free_pct < threshold_pct {
+        let message = format!(
+            "{} has {} free ({:.1}%). Run: sbh emergency {}",
+            stats.mount_point.display(),
+            format_bytes(stats.available_bytes),
+            free_pct,
+            check_path.display(),
+        );
+        if gha {
+            emit_check_gha(
+                CheckGhaResult::fail(CHECK_BELOW_TARGET_FREE, "Disk space low", message),
+                free_pct,
+                minutes_until_full,
+            )?;
+            return Err(CliError::Runtime("disk space below threshold".to_string()));
+        }
+        match output_mode(cli) {
+            OutputMode::Human => eprintln!("sbh: {message}"),
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "check",
+                    "status": "critical",
+                    "reason": CHECK_BELOW_TARGET_FREE,
+                    "path": check_path.to_string_lossy(),
+                    "mount_point": stats.mount_point.to_string_lossy(),
+                    "free_bytes": stats.available_bytes,
+                    "total_bytes": stats.total_bytes,
+                    "free_pct": free_pct,
+                    "threshold_pct": threshold_pct,
+                    "policy_file": policy_path,
+                    "exit_code": 2,
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return Err(CliError::Runtime("disk space below threshold".to_string()));
+    }
+
+    // Check 2.5: warn if state.json is stale (daemon may not be running).
+    if output_mode(cli) == OutputMode::Human || gha {
+        match read_state(&config.paths.state_file) {
+            Ok(snapshot) if !snapshot.is_fresh() => {
+                eprintln!(
+                    "sbh: warning: state.json is {:.0}s old (daemon may not be running)",
+                    snapshot.age.as_secs_f64(),
+                );
+            }
+            Err(err @ StateReadError::Parse(StateParseError::NewerSchema(_))) => {
+                eprintln!("sbh: warning: {err}");
+            }
+            _ => {}
+        }
+    }
+
+    // Check 3: prediction from daemon state.json (if available and --predict requested).
+    // No prediction (daemon not running or not filling) is degraded mode,
+    // not an error.
+    if let (Some(predict_minutes), Some(minutes_left)) = (args.predict, minutes_until_full)
+        && minutes_left < predict_minutes as f64
+    {
+        let message = format!(
+            "{} has {} free but predicted full in {:.0} min (need {} min{per_policy})",
+            stats.mount_point.display(),
+            format_bytes(stats.available_bytes),
+            minutes_left,
+            predict_minutes,
+        );
+        if gha {
+            let result = CheckGhaResult {
+                status: "warning",
+                exit_code: 1,
+                ..CheckGhaResult::fail(CHECK_PREDICTED_FULL, "Disk predicted to fill", message)
+            };
+            emit_check_gha(result, free_pct, minutes_until_full)?;
+            return Err(CliError::User(
+                "predicted disk full within window".to_string(),
+            ));
+        }
+        match output_mode(cli) {
+            OutputMode::Human => eprintln!("sbh: {message}"),
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "check",
+                    "status": "warning",
+                    "reason": CHECK_PREDICTED_FULL,
+                    "path": check_path.to_string_lossy(),
+                    "mount_point": stats.mount_point.to_string_lossy(),
+                    "free_bytes": stats.available_bytes,
+                    "free_pct": free_pct,
+                    "rate_bytes_per_sec": rate_bps,
+                    "minutes_until_full": minutes_left,
+                    "predict_minutes": predict_minutes,
+                    "policy_file": policy_path,
+                    "exit_code": 1,
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return Err(CliError::User(
+            "predicted disk full within window".to_string(),
+        ));
+    }
+
+    // All checks passed — silent success on human mode.
+    if gha {
+        emit_check_gha(CheckGhaResult::ok(), free_pct, minutes_until_full)?;
+    } else if output_mode(cli) == OutputMode::Json {
+        let payload = json!({
+            "command": "check",
+            "status": "ok",
+            "reason": Value::Null,
+            "path": check_path.to_string_lossy(),
+            "mount_point": stats.mount_point.to_string_lossy(),
+            "free_bytes": stats.available_bytes,
+            "total_bytes": stats.total_bytes,
+            "free_pct": free_pct,
+            "policy_file": policy_path,
+            "exit_code": 0,
+        });
+        write_json_line(&payload)?;
+    }
+
+    Ok(())
+}
+
+/// Machine-readable `check` failure reasons, in JSON `reason` and the `gha`
+/// `reason` step output.
+const CHECK_BELOW_NEED: &str = "below_need";
+const CHECK_BELOW_TARGET_FREE: &str = "below_target_free";
+const CHECK_PREDICTED_FULL: &str = "predicted_full";
+
+/// Outcome of one `check --format gha` run.
+struct CheckGhaResult {
+    status: &'static str,
+    exit_code: i32,
+    reason: Option<&'static str>,
+    title: &'static str,
+    message: String,
+}
+
+impl CheckGhaResult {
+    const fn ok() -> Self {
+        Self {
+            status: "ok",
+            exit_code: 0,
+            reason: None,
+            title: "",
+            message: String::new(),
+        }
+    }
+
+    const fn fail(reason: &'static str, title: &'static str, message: String) -> Self {
+        Self {
+            status: "critical",
+            exit_code: 2,
+            reason: Some(reason),
+            title,
+            message,
+        }
+    }
+}
+
+/// `check --format gha`: an `::error::` or `::warning::` annotation for a
+/// failed check, and step outputs for later steps whatever the result.
+/// Outside GitHub Actions (no `$GITHUB_OUTPUT`) the outputs are skipped.
+fn emit_check_gha(
+    result: CheckGhaResult,
+    free_pct: f64,
+    minutes_until_full: Option<f64>,
+) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::ci::{Annotation, AnnotationLevel, step_output_lines};
+
+    if result.reason.is_some() {
+        let annotation = Annotation {
+            level: if result.exit_code == 1 {
+                AnnotationLevel::Warning
+            } else {
+                AnnotationLevel::Error
+            },
+            title: result.title.to_string(),
+            message: result.message,
+        };
+        println!("{}", annotation.to_workflow_command());
+    }
+    let Some(output_path) = std::env::var_os("GITHUB_OUTPUT") else {
+        return Ok(());
+    };
+    let lines = step_output_lines(&[
+        ("status", result.status.to_string()),
+        ("reason", result.reason.unwrap_or_default().to_string()),
+        ("free_pct", format!("{free_pct:.2}")),
+        (
+            "minutes_until_full",
+            minutes_until_full.map_or_else(String::new, |minutes| format!("{minutes:.0}")),
+        ),
+        ("exit_code", result.exit_code.to_string()),
+    ]);
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&output_path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|e| {
+            CliError::Runtime(format!(
+                "write step outputs to {}: {e}",
+                Path::new(&output_path).display()
+            ))
+        })
+}
+
+/// `check --nagios`: one plugin line on stdout, status carried by the exit code.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn run_check_nagios(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::nagios::{CheckInput, evaluate, perfdata, render_line};
+
+    let check_path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
+    let stats = match detect_platform().and_then(|platform| platform.fs_stats(&check_path)) {
+        Ok(stats) => stats,
+        Err(err) => {
+            let message = format!("cannot stat {}: {err}", check_path.display());
+            println!("{}", render_line(PluginStatus::Unknown, &message, ""));
+            return Err(CliError::Plugin(PluginStatus::Unknown));
+        }
+    };
+    let config = Config::load(cli.config.as_deref()).unwrap_or_default();
+    let warning_pct = args
+        .warning
+        .or(args.target_free)
+        .unwrap_or(config.pressure.yellow_min_free_pct);
+    let critical_pct = args.critical.unwrap_or(config.pressure.red_min_free_pct);
+
+    let rate_bps = read_daemon_prediction(&config.paths.state_file, &check_path);
+    let minutes_left = rate_bps.filter(|rate| *rate > 0.0).map(|rate| {
+        let floor = (warning_pct / 100.0 * stats.total_bytes as f64) as u64;
+        stats.available_bytes.saturating_sub(floor) as f64 / rate / 60.0
+    });
+    let input = CheckInput {
+        free_pct: stats.free_pct(),
+        free_bytes: stats.available_bytes,
+        total_bytes: stats.total_bytes,
+        warning_pct,
+        critical_pct,
+        need_bytes: args.need,
+        rate_bps,
+        minutes_left,
+        predict_minutes: args.predict,
+    };
+    let (plugin_status, reasons) = evaluate(&input);
+
+    let mut message = format!(
+        "{} {:.1}% free ({})",
+        stats.mount_point.display(),
+        input.free_pct,
+        format_bytes(stats.available_bytes),
+    );
+    for reason in &reasons {
+        message.push_str(", ");
+        message.push_str(reason);
+    }
+    let daemon_level = read_state(&config.paths.state_file)
+        .ok()
+        .filter(StateSnapshot::is_fresh)
+        .and_then(|snapshot| {
+            let mount_point = stats.mount_point.to_string_lossy();
+            snapshot
+                .state
+                .pressure
+                .mounts
+                .into_iter()
+                .find(|mount| mount.path == mount_point)
+                .map(|mount| mount.level)
+        });
+    if let Some(level) = daemon_level {
+        use std::fmt::Write as _;
+        let _ = write!(message, "; daemon pressure {level}");
+    }
+    println!(
+        "{}",
+        render_line(plugin_status, &message, &perfdata(&input))
+    );
+
+    if plugin_status == PluginStatus::Ok {
+        Ok(())
+    } else {
+        Err(CliError::Plugin(plugin_status))
+    }
+}
+
+fn ci_stats(args: &CiPathArgs) -> Result<FsStats, CliError> {
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
+    platform
+        .fs_stats(&path)
+        .map_err(|e| CliError::Runtime(e.to_string()))
+}
+
+fn run_ci_begin(cli: &Cli, args: &CiPathArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::ci::{CiBaseline, baseline_path};
+
+    let stats = ci_stats(args)?;
+    let baseline = CiBaseline {
+        recorded_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        mount_point: stats.mount_point.clone(),
+        total_bytes: stats.total_bytes,
+        free_bytes: stats.available_bytes,
+    };
+    let path = baseline_path(|key| std::env::var(key).ok());
+    let raw = serde_json::to_string(&baseline)
+        .map_err(|e| CliError::Internal(format!("serialize CI baseline: {e}")))?;
+    std::fs::write(&path, raw)
+        .map_err(|e| CliError::Runtime(format!("write {}: {e}", path.display())))?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Recorded {} free on {} (baseline: {})",
+                format_bytes(baseline.free_bytes),
+                baseline.mount_point.display(),
+                path.display(),
+            );
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "ci begin",
+                "baseline_path": path.to_string_lossy(),
+                "baseline": baseline,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Artifacts sbh deleted on `mount_point` since `since`, from the activity log.
+fn ci_cleaned_items(
+    config: &Config,
+    mount_point: &Path,
+    since: &str,
+    until: &str,
+) -> Vec<storage_ballast_helper::cli::ci::CleanedItem> {
+    use storage_ballast_helper::cli::ci::CleanedItem;
+
+    if !config.paths.sqlite_db.exists() {
+        return Vec::new();
+    }
+    let Ok(db) = SqliteLogger::open(&config.paths.sqlite_db) else {
+        return Vec::new();
+    };
+    db.activity_between(since, until)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|row| row.event_type == "artifact_delete" && row.success == 1)
+        .filter_map(|row| {
+            let path = row.path?;
+            let size_bytes = row.size_bytes.and_then(|size| u64::try_from(size).ok());
+            Path::new(&path)
+                .starts_with(mount_point)
+                .then(|| CleanedItem {
+                    path,
+                    size_bytes: size_bytes.unwrap_or(0),
+                })
+        })
+        .collect()
+}
+
+fn run_ci_report(cli: &Cli, args: &CiPathArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::ci::{CiProvider, CiReport, baseline_path, read_baseline};
+
+    let lookup = |key: &str| std::env::var(key).ok();
+    let provider = CiProvider::detect(lookup);
+    let config = Config::load(cli.config.as_deref()).unwrap_or_default();
+    let stats = ci_stats(args)?;
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+
+    // A baseline from a different mount (or none at all) can't be compared.
+    let baseline =
+        read_baseline(&baseline_path(lookup)).filter(|b| b.mount_point == stats.mount_point);
+    let cleaned = baseline
+        .as_ref()
+        .map(|b| ci_cleaned_items(&config, &stats.mount_point, &b.recorded_at, &now))
+        .unwrap_or_default();
+
+    let report = CiReport {
+        mount_point: stats.mount_point.clone(),
+        total_bytes: stats.total_bytes,
+        before_free_bytes: baseline.as_ref().map(|b| b.free_bytes),
+        after_free_bytes: stats.available_bytes,
+        cleaned,
+        warn_free_pct: config.pressure.yellow_min_free_pct,
+        critical_free_pct: config.pressure.red_min_free_pct,
+    };
+    let annotations = report.annotations();
+    let markdown = report.to_markdown();
+
+    // On GitHub Actions the summary goes to the step summary file; a failed
+    // append falls back to stdout so the report is never lost.
+    let summary_path = match provider {
+        CiProvider::GitHubActions => lookup("GITHUB_STEP_SUMMARY").map(PathBuf::from),
+        CiProvider::GitLab | CiProvider::Buildkite | CiProvider::Jenkins | CiProvider::Unknown => {
+            None
+        }
+    };
+    let summary_written = summary_path.as_ref().is_some_and(|path| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(markdown.as_bytes()))
+            .is_ok()
+    });
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if !summary_written {
+                print!("{markdown}");
+            }
+            // Elsewhere the markdown already carries the findings inline.
+            if provider == CiProvider::GitHubActions {
+                for annotation in &annotations {
+                    println!("{}", annotation.to_workflow_command());
+                }
+            }
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "ci report",
+                "provider": provider.as_str(),
+                "mount_point": report.mount_point.to_string_lossy(),
+                "total_bytes": report.total_bytes,
+                "before_free_bytes": report.before_free_bytes,
+                "after_free_bytes": report.after_free_bytes,
+                "cleaned_bytes": report.cleaned_bytes(),
+                "cleaned": report.cleaned,
+                "predicted_next_free_bytes": report.predicted_next_free_bytes(),
+                "annotations": annotations,
+                "summary_path": summary_path
+                    .filter(|_| summary_written)
+                    .map(|path| path.to_string_lossy().into_owned()),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Workspace a runner hook acts on: the argument, the runner's workspace
+/// variable, or cwd.
+fn runner_workspace(arg: Option<&PathBuf>) -> PathBuf {
+    use storage_ballast_helper::cli::ci::workspace_dir;
+
+    arg.cloned()
+        .or_else(|| workspace_dir(|key| std::env::var(key).ok()))
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")))
+}
+
+/// Pre-job hooks may run before the checkout exists; measure the nearest
+/// existing ancestor instead.
+fn existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map_or_else(|| PathBuf::from("/"), Path::to_path_buf)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn run_runner_pre_job(cli: &Cli, args: &PreJobArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+    let workspace = runner_workspace(args.workspace.as_ref());
+    let probe = existing_ancestor(&workspace);
+    let stats = platform
+        .fs_stats(&probe)
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    // --need raises the target to whatever percentage covers it.
+    let mut target_free = args
+        .target_free
+        .unwrap_or(config.pressure.yellow_min_free_pct);
+    if let Some(need) = args.need
+        && stats.total_bytes > 0
+    {
+        target_free = target_free.max(need as f64 * 100.0 / stats.total_bytes as f64);
+    }
+    let reclaim = stats.free_pct() < target_free;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Workspace mount {}: {} free ({:.1}%), target {target_free:.1}% — {}",
+                stats.mount_point.display(),
+                format_bytes(stats.available_bytes),
+                stats.free_pct(),
+                if reclaim { "reclaiming" } else { "ok" },
+            );
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "runner-hook",
+                "phase": "pre-job",
+                "workspace": workspace.to_string_lossy(),
+                "mount_point": stats.mount_point.to_string_lossy(),
+                "free_bytes": stats.available_bytes,
+                "free_pct": stats.free_pct(),
+                "target_free_pct": target_free,
+                "reclaim": reclaim,
+            }))?;
+        }
+    }
+
+    if reclaim {
+        // Prefer the configured roots on the workspace mount; a runner host
+        // without any falls back to the workspace itself.
+        let has_roots = resolve_mount_roots(&stats.mount_point, &config.scanner.root_paths).is_ok();
+        let clean_args = if has_roots {
+            CleanArgs {
+                mount: Some(stats.mount_point),
+                ..CleanArgs::default()
+            }
+        } else {
+            CleanArgs {
+                paths: vec![probe.clone()],
+                ..CleanArgs::default()
+            }
+        };
+        run_clean(
+            cli,
+            &CleanArgs {
+                target_free: Some(target_free),
+                dry_run: args.dry_run,
+                yes: true,
+                ..clean_args
+            },
+        )?;
+    }
+
+    if let Some(need) = args.need
+        && !args.dry_run
+    {
+        let after = platform
+            .fs_stats(&probe)
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        if after.available_bytes < need {
+            return Err(CliError::Runtime(format!(
+                "{} has {} free but the job needs {}",
+                after.mount_point.display(),
+                format_bytes(after.available_bytes),
+                format_bytes(need),
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn run_runner_post_job(cli: &Cli, args: &PostJobArgs) -> Result<(), CliError> {
+    let workspace = runner_workspace(args.workspace.as_ref());
+    let exists = workspace.is_dir();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if exists {
+                println!(
+                    "{} workspace {}",
+                    if args.clean { "Cleaning" } else { "Scoring" },
+                    workspace.display()
+                );
+            } else {
+                println!("Workspace {} is gone — nothing to do.", workspace.display());
+            }
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "runner-hook",
+                "phase": "post-job",
+                "workspace": workspace.to_string_lossy(),
+                "exists": exists,
+                "clean": args.clean,
+            }))?;
+        }
+    }
+    if !exists {
+        return Ok(());
+    }
+
+    // Without --clean this is a dry run: the plan shows the scored
+    // candidates and what they would free.
+    run_clean(
+        cli,
+        &CleanArgs {
+            paths: vec![workspace],
+            min_score: args.min_score,
+            dry_run: !args.clean,
+            yes: true,
+            ..CleanArgs::default()
+        },
+    )
+}
+
+/// Record a one-off CLI event in the activity log (SQLite + JSONL).
+fn log_cli_event(config: &Config, event: ActivityEvent) {
+    use storage_ballast_helper::logger::dual::{DualLoggerConfig, spawn_logger};
+    use storage_ballast_helper::logger::jsonl::JsonlConfig;
+
+    let logger_config = DualLoggerConfig {
+        sqlite_path: Some(config.paths.sqlite_db.clone()),
+        jsonl_config: JsonlConfig {
+            path: config.paths.jsonl_log.clone(),
+            ..JsonlConfig::default()
+        },
+        channel_capacity: 16,
+    };
+    match spawn_logger(logger_config) {
+        Ok((handle, join)) => {
+            handle.send(event);
+            handle.shutdown();
+            let _ = join.join();
+        }
+        Err(e) => eprintln!("sbh: warning: activity event not logged: {e}"),
+    }
+}
+
+fn run_workspace_create(cli: &Cli, args: &WorkspaceCreateArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::workspace::{WorkspaceLease, create_workspace};
+    use storage_ballast_helper::core::errors::SbhError;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let now = chrono::Utc::now();
+    let expires_at = match args.lease.as_deref() {
+        Some(raw) => {
+            let ttl = chrono::Duration::from_std(parse_window_duration(raw)?)
+                .map_err(|e| CliError::User(format!("invalid lease duration {raw}: {e}")))?;
+            Some((now + ttl).to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        }
+        None => None,
+    };
+
+    // Budget accounting: a budget the mount can't hold right now is refused
+    // up front rather than discovered mid-run.
+    if let Some(budget) = args.budget {
+        let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+        let stats = platform
+            .fs_stats(&existing_ancestor(&args.path))
+            .map_err(|e| CliError::Runtime(e.to_string()))?;
+        if budget > stats.available_bytes {
+            return Err(CliError::Runtime(format!(
+                "budget {} exceeds the {} free on {}",
+                format_bytes(budget),
+                format_bytes(stats.available_bytes),
+                stats.mount_point.display(),
+            )));
+        }
+    }
+
+    let lease = WorkspaceLease {
+        owner: args.owner.clone(),
+        created_at: now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        expires_at,
+        budget_bytes: args.budget,
+    };
+    create_workspace(&args.path, &lease).map_err(|e| {
+        if matches!(e, SbhError::SafetyVeto { .. }) {
+            CliError::User(e.to_string())
+        } else {
+            CliError::Runtime(e.to_string())
+        }
+    })?;
+    let path = args
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| args.path.clone());
+    log_cli_event(
+        &config,
+        ActivityEvent::WorkspaceCreated {
+            path: path.to_string_lossy().into_owned(),
+            lease: serde_json::to_string(&lease)
+                .map_err(|e| CliError::Internal(format!("serialize lease: {e}")))?,
+        },
+    );
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Created workspace {} for {}", path.display(), lease.owner);
+            if let Some(budget) = lease.budget_bytes {
+                println!("  Budget: {}", format_bytes(budget));
+            }
+            if let Some(expiry) = &lease.expires_at {
+                println!("  Lease expires: {expiry}");
+            }
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "workspace create",
+                "path": path.to_string_lossy(),
+                "lease": lease,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_workspace_destroy(cli: &Cli, args: &WorkspaceDestroyArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::workspace::{
+        find_protected_dir, live_processes, read_lease, remove_workspace,
+    };
+    use storage_ballast_helper::scanner::tool_caches::tree_size;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let path = args
+        .path
+        .canonicalize()
+        .map_err(|e| CliError::User(format!("invalid workspace {}: {e}", args.path.display())))?;
+
+    // Ownership is explicit only when the lease says so; anything else goes
+    // through scoring with `sbh clean`.
+    let lease = read_lease(&path)
+        .map_err(|e| CliError::Runtime(e.to_string()))?
+        .ok_or_else(|| {
+            CliError::User(format!(
+                "{} has no workspace lease; it was not created by 'sbh workspace create' (use 'sbh clean')",
+                path.display()
+            ))
+        })?;
+    let expired = lease.is_expired(chrono::Utc::now());
+    if !expired && args.owner.as_deref() != Some(lease.owner.as_str()) {
+        return Err(CliError::User(format!(
+            "workspace lease is held by '{}' until {}; pass --owner {} to destroy it now",
+            lease.owner,
+            lease.expires_at.as_deref().unwrap_or("released"),
+            lease.owner,
+        )));
+    }
+    if let Some(protected) = find_protected_dir(&path) {
+        return Err(CliError::User(format!(
+            "{} is protected by a .sbh-protect marker; remove it with 'sbh unprotect' first",
+            protected.display()
+        )));
+    }
+    let live = live_processes(&path);
+    if !live.is_empty() {
+        let pids: Vec<String> = live
+            .iter()
+            .map(|process| format!("{} ({})", process.pid, process.name))
+            .collect();
+        return Err(CliError::Runtime(format!(
+            "workspace {} is still in use by {}",
+            path.display(),
+            pids.join(", ")
+        )));
+    }
+
+    let size_bytes = tree_size(&path);
+    let overrun_bytes = lease.overrun_bytes(size_bytes);
+    if !args.dry_run {
+        remove_workspace(&path).map_err(|e| CliError::Runtime(e.to_string()))?;
+        log_cli_event(
+            &config,
+            ActivityEvent::WorkspaceDestroyed {
+                path: path.to_string_lossy().into_owned(),
+                size_bytes,
+                overrun_bytes,
+                lease: serde_json::to_string(&lease)
+                    .map_err(|e| CliError::Internal(format!("serialize lease: {e}")))?,
+            },
+        );
+    }
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            let verb = if args.dry_run {
+                "Would destroy"
+            } else {
+                "Destroyed"
+            };
+            println!(
+                "{verb} workspace {} ({}, owner {})",
+                path.display(),
+                format_bytes(size_bytes),
+                lease.owner
+            );
+            if overrun_bytes > 0 {
+                println!(
+                    "  Over budget by {} (budget {})",
+                    format_bytes(overrun_bytes),
+                    format_bytes(lease.budget_bytes.unwrap_or(0))
+                );
+            }
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "workspace destroy",
+                "path": path.to_string_lossy(),
+                "dry_run": args.dry_run,
+                "size_bytes": size_bytes,
+                "overrun_bytes": overrun_bytes,
+                "lease_expired": expired,
+                "lease": lease,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `contents` to stdout, or atomically to `output` and report it.
+fn write_export(
+    cli: &Cli,
+    command: &str,
+    output: Option<&Path>,
+    contents: &str,
+) -> Result<(), CliError> {
+    let Some(output) = output else {
+        print!("{contents}");
+        return Ok(());
+    };
+    // Write-then-rename so collectors never read a half-written file.
+    let mut tmp_name = output.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+    std::fs::write(&tmp_path, contents)
+        .map_err(|e| CliError::Runtime(format!("write {}: {e}", tmp_path.display())))?;
+    std::fs::rename(&tmp_path, output)
+        .map_err(|e| CliError::Runtime(format!("rename to {}: {e}", output.display())))?;
+
+    match output_mode(cli) {
+        OutputMode::Human => println!("Wrote {}", output.display()),
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": command,
+                "output": output.to_string_lossy(),
+                "bytes": contents.len(),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_export_prometheus(cli: &Cli, args: &ExportPrometheusArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::core::metrics::render_prometheus;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let snapshot = read_state(&config.paths.state_file).map_err(|e| {
+        CliError::Runtime(format!(
+            "cannot read {}: {e}",
+            config.paths.state_file.display()
+        ))
+    })?;
+    if !snapshot.is_fresh() {
+        eprintln!(
+            "sbh: warning: state.json is {:.0}s old (daemon may not be running)",
+            snapshot.age.as_secs_f64(),
+        );
+    }
+    write_export(
+        cli,
+        "export prometheus",
+        args.output.as_deref(),
+        &render_prometheus(&snapshot.state),
+    )
+}
+
+fn run_export_grafana(cli: &Cli, args: &ExportGrafanaArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::core::metrics::grafana_dashboard;
+
+    let dashboard = grafana_dashboard(args.datasource.as_deref());
+    let mut raw = serde_json::to_string_pretty(&dashboard)
+        .map_err(|e| CliError::Internal(format!("serialize dashboard: {e}")))?;
+    raw.push('\n');
+    write_export(
+        cli,
+        "export grafana-dashboard",
+        args.output.as_deref(),
+        &raw,
+    )
+}
+
+fn run_restore(cli: &Cli, args: &RestoreArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::core::errors::SbhError;
+    use storage_ballast_helper::scanner::archive::{find_entry, read_index, restore};
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let root = &config.archive.path;
+    if root.as_os_str().is_empty() {
+        return Err(CliError::User(
+            "no archive configured; set archive.path in the config".to_string(),
+        ));
+    }
+    let entries = read_index(root).map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    let Some(target) = args.target.as_deref().filter(|_| !args.list) else {
+        match output_mode(cli) {
+            OutputMode::Human => {
+                if entries.is_empty() {
+                    println!("Archive {} is empty.", root.display());
+                }
+                let now = chrono::Utc::now();
+                for entry in &entries {
+                    let expired = entry
+                        .object
+                        .as_ref()
+                        .is_some_and(|object| object.is_expired(now));
+                    println!(
+                        "{}  {:>10}  {:<14} {}{}",
+                        entry.id,
+                        format_bytes(entry.size_bytes),
+                        entry.category,
+                        entry.original_path.display(),
+                        if expired { "  (expired)" } else { "" }
+                    );
+                }
+            }
+            OutputMode::Json => {
+                write_json_line(&json!({
+                    "command": "restore",
+                    "archive": root.to_string_lossy(),
+                    "entries": entries,
+                }))?;
+            }
+        }
+        return Ok(());
+    };
+
+    let entry = find_entry(&entries, target).ok_or_else(|| {
+        CliError::User(format!(
+            "no archived artifact matches '{target}' (see 'sbh restore --list')"
+        ))
+    })?;
+    let dest = args.to.as_deref().unwrap_or(&entry.original_path);
+    restore(root, entry, dest).map_err(|e| match e {
+        SbhError::SafetyVeto { .. } => CliError::User(e.to_string()),
+        other => CliError::Runtime(other.to_string()),
+    })?;
+    log_cli_event(
+        &config,
+        ActivityEvent::ArtifactRestored {
+            path: dest.to_string_lossy().into_owned(),
+            archived_path: entry.archived_path.to_string_lossy().into_owned(),
+            size_bytes: entry.size_bytes,
+        },
+    );
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Restored {} ({}) to {}",
+                entry.id,
+                format_bytes(entry.size_bytes),
+                dest.display()
+            );
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "restore",
+                "restored": entry,
+                "path": dest.to_string_lossy(),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Quarantines on this host: at or above each configured root, local mount
+/// point, `$HOME`, and the working directory.
+fn quarantine_roots(config: &Config) -> Vec<PathBuf> {
+    let mut dirs = config.scanner.root_paths.clone();
+    if let Ok(platform) = detect_platform()
+        && let Ok(mounts) = platform.mount_points()
+    {
+        // Block-device mounts only: probing a hung network mount would block.
+        dirs.extend(
+            mounts
+                .into_iter()
+                .filter(|mount| mount.device.starts_with("/dev/"))
+                .map(|mount| mount.path),
+        );
+    }
+    dirs.extend(std::env::var_os("HOME").map(PathBuf::from));
+    dirs.extend(std::env::current_dir().ok());
+    quarantine::discover_roots(dirs.iter().map(PathBuf::as_path))
+}
+
+/// Every quarantined entry on the host with the quarantine holding it.
+fn quarantine_entries(config: &Config) -> Result<Vec<(PathBuf, QuarantineEntry)>, CliError> {
+    let mut all = Vec::new();
+    for root in quarantine_roots(config) {
+        let entries =
+            quarantine::read_index(&root).map_err(|e| CliError::Runtime(e.to_string()))?;
+        all.extend(entries.into_iter().map(|entry| (root.clone(), entry)));
+    }
+    Ok(all)
+}
+
+fn run_quarantine_list(cli: &Cli) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let entries = quarantine_entries(&config)?;
+    let now = chrono::Utc::now();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if entries.is_empty() {
+                println!("Nothing is quarantined.");
+            }
+            for (_, entry) in &entries {
+                println!(
+                    "{}  {:>10}  {:<14} {}  (expires {}{}{})",
+                    entry.id,
+                    format_bytes(entry.size_bytes),
+                    entry.category,
+                    entry.original_path.display(),
+                    entry.expires_at,
+                    if entry.is_expired(now) { ", due" } else { "" },
+                    if entry.object.is_some() {
+                        ", offloaded"
+                    } else {
+                        ""
+                    }
+                );
+            }
+            let total: u64 = entries.iter().map(|(_, entry)| entry.local_bytes()).sum();
+            if total > 0 {
+                println!(
+                    "\n{} items, {} reclaimable with 'sbh quarantine purge --all'",
+                    entries.len(),
+                    format_bytes(total)
+                );
+            }
+        }
+        OutputMode::Json => {
+            let listed: Vec<Value> = entries
+                .iter()
+                .map(|(root, entry)| {
+                    json!({
+                        "quarantine": root.to_string_lossy(),
+                        "entry": entry,
+                        "expired": entry.is_expired(now),
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "quarantine list",
+                "entries": listed,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_quarantine_restore(cli: &Cli, args: &QuarantineRestoreArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::core::errors::SbhError;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let entries = quarantine_entries(&config)?;
+    let (root, entry) = entries
+        .iter()
+        .rev()
+        .find(|(_, entry)| entry.matches(&args.target))
+        .ok_or_else(|| {
+            CliError::User(format!(
+                "no quarantined artifact matches '{}' (see 'sbh quarantine list')",
+                args.target
+            ))
+        })?;
+    let dest = args.to.as_deref().unwrap_or(&entry.original_path);
+    quarantine::restore(root, entry, dest).map_err(|e| match e {
+        SbhError::SafetyVeto { .. } => CliError::User(e.to_string()),
+        other => CliError::Runtime(other.to_string()),
+    })?;
+    log_cli_event(
+        &config,
+        ActivityEvent::ArtifactRestored {
+            path: dest.to_string_lossy().into_owned(),
+            archived_path: entry.stored_at(),
+            size_bytes: entry.size_bytes,
+        },
+    );
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Restored {} ({}) to {}",
+                entry.id,
+                format_bytes(entry.size_bytes),
+                dest.display()
+            );
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "quarantine restore",
+                "restored": entry,
+                "path": dest.to_string_lossy(),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_quarantine_purge(cli: &Cli, args: &QuarantinePurgeArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::core::errors::SbhError;
+
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let now = chrono::Utc::now();
+    let reason = if args.expired { "expired" } else { "manual" };
+    let select = |entry: &QuarantineEntry| {
+        args.target.as_ref().map_or_else(
+            || args.all || entry.is_expired(now),
+            |target| entry.matches(target),
+        )
+    };
+
+    let mut purged = Vec::new();
+    let mut failed = Vec::new();
+    // Quarantines this user does not own are left alone, not fatal.
+    let mut refused = Vec::new();
+    for root in quarantine_roots(&config) {
+        let report = match quarantine::purge(&root, select) {
+            Ok(report) => report,
+            Err(e @ SbhError::SafetyVeto { .. }) => {
+                refused.push((root, e.to_string()));
+                continue;
+            }
+            Err(e) => return Err(CliError::Runtime(e.to_string())),
+        };
+        for entry in &report.purged {
+            log_cli_event(
+                &config,
+                ActivityEvent::QuarantinePurged {
+                    path: entry.original_path.to_string_lossy().into_owned(),
+                    quarantined_path: entry.stored_at(),
+                    size_bytes: entry.local_bytes(),
+                    reason: reason.to_string(),
+                },
+            );
+        }
+        purged.extend(report.purged);
+        failed.extend(report.failed);
+    }
+    if let Some(target) = &args.target
+        && purged.is_empty()
+        && failed.is_empty()
+        && refused.is_empty()
+    {
+        return Err(CliError::User(format!(
+            "no quarantined artifact matches '{target}' (see 'sbh quarantine list')"
+        )));
+    }
+    let bytes_freed: u64 = purged.iter().map(QuarantineEntry::local_bytes).sum();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Purged {} quarantined items, {} freed.",
+                purged.len(),
+                format_bytes(bytes_freed)
+            );
+            for (entry, error) in &failed {
+                eprintln!(
+                    "  Failed to purge {}: {error}",
+                    entry.quarantined_path.display()
+                );
+            }
+            for (root, error) in &refused {
+                eprintln!("  Skipped {}: {error}", root.display());
+            }
+        }
+        OutputMode::Json => {
+            let failures: Vec<Value> = failed
+                .iter()
+                .map(|(entry, error)| json!({"entry": entry, "error": error}))
+                .collect();
+            let refusals: Vec<Value> = refused
+                .iter()
+                .map(|(root, error)| json!({"root": root, "error": error}))
+                .collect();
+            write_json_line(&json!({
+                "command": "quarantine purge",
+                "purged": purged,
+                "bytes_freed": bytes_freed,
+                "failed": failures,
+                "refused_roots": refusals,
+            }))?;
+        }
+    }
+    let mut problems = Vec::new();
+    if !failed.is_empty() {
+        problems.push(format!(
+            "{} quarantined items could not be purged",
+            failed.len()
+        ));
+    }
+    if !refused.is_empty() {
+        problems.push(format!(
+            "{} quarantines were refused as not safely owned",
+            refused.len()
+        ));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Partial(problems.join("; ")))
+    }
+}
+
+fn run_audit_list(cli: &Cli, args: &AuditListArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    // The path may be long gone, so make it absolute without resolving it.
+    let path = args.path.as_deref().map(std::path::absolute).transpose()?;
+    let manifests = AuditLog::for_paths(&config.paths)
+        .list(args.limit, path.as_deref())
+        .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if manifests.is_empty() {
+                println!("No deletion batches recorded.");
+            }
+            for manifest in &manifests {
+                println!(
+                    "{}  {}  {:<11} {:<8} {:>4} items  {:>10}",
+                    manifest.batch_id,
+                    manifest.created_at,
+                    manifest.source,
+                    manifest.policy_mode.as_deref().unwrap_or("-"),
+                    manifest.items.len(),
+                    format_bytes(manifest.total_bytes()),
+                );
+            }
+        }
+        OutputMode::Json => {
+            let listed: Vec<Value> = manifests
+                .iter()
+                .map(|manifest| {
+                    json!({
+                        "batch_id": manifest.batch_id,
+                        "created_at": manifest.created_at,
+                        "source": manifest.source,
+                        "policy_mode": manifest.policy_mode,
+                        "item_count": manifest.items.len(),
+                        "total_bytes": manifest.total_bytes(),
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "audit list",
+                "batches": listed,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_audit_show(cli: &Cli, args: &AuditShowArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let manifest = AuditLog::for_paths(&config.paths)
+        .find(&args.batch_id)
+        .map_err(|e| CliError::Runtime(e.to_string()))?
+        .ok_or_else(|| {
+            CliError::User(format!(
+                "no deletion batch '{}' (see 'sbh audit list')",
+                args.batch_id
+            ))
+        })?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Batch {}", manifest.batch_id);
+            println!("  Created: {}", manifest.created_at);
+            match &manifest.policy_mode {
+                Some(mode) => println!("  Source:  {} (policy mode {mode})", manifest.source),
+                None => println!("  Source:  {}", manifest.source),
+            }
+            println!(
+                "  Items:   {}, {}",
+                manifest.items.len(),
+                format_bytes(manifest.total_bytes())
+            );
+            for item in &manifest.items {
+                println!("\n  {:<10} {}", item.action.as_str(), item.path.display());
+                print!(
+                    "    {} {} via {}, score {:.2}",
+                    format_bytes(item.size_bytes),
+                    item.category,
+                    item.pattern,
+                    item.score,
+                );
+                match item.decision_id {
+                    Some(id) => println!(", decision #{id}"),
+                    None => println!(),
+                }
+                let f = &item.factors;
+                println!(
+                    "    factors: location {:.2}, name {:.2}, age {:.2}, size {:.2}, structure {:.2}",
+                    f.location, f.name, f.age, f.size, f.structure
+                );
+                let listing = &item.top_level;
+                match &listing.error {
+                    Some(error) => println!("    top level: unreadable ({error})"),
+                    None => println!(
+                        "    top level: {} entries, sha256 {}",
+                        listing.total, listing.sha256
+                    ),
+                }
+                for entry in &listing.entries {
+                    println!(
+                        "      {:<7} {:>10}  {}{}",
+                        entry.kind,
+                        format_bytes(entry.size_bytes),
+                        entry.name,
+                        entry
+                            .sha256
+                            .as_deref()
+                            .map(|hash| format!("  sha256 {hash}"))
+                            .unwrap_or_default(),
+                    );
+                }
+                if listing.total > listing.entries.len() {
+                    println!("      ... {} more", listing.total - listing.entries.len());
+                }
+            }
+        }
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "audit show",
+                "manifest": manifest,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_annotate(cli: &Cli, args: &AnnotateArgs) -> Result<(), CliError> {
+    let message = args.message.join(" ").trim().to_string();
+    if message.is_empty() {
+        return Err(CliError::User("annotation message is empty".to_string()));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    // A running daemon owns the activity log; otherwise write it directly.
+    let request = ControlRequest::Annotate {
+        message: message.clone(),
+    };
+    let via = match daemon_request(&config, &request) {
+        Some(ControlReply::Accepted) => "daemon",
+        Some(reply) => return Err(daemon_reply_error(reply)),
+        None => {
+            log_cli_event(
+                &config,
+                ActivityEvent::Annotation {
+                    message: message.clone(),
+                },
+            );
+            "local"
         }
+    };
+
+    match output_mode(cli) {
+        OutputMode::Human => println!("Annotated timeline: {message}"),
+        OutputMode::Json => write_json_line(&json!({
+            "command": "annotate",
+            "message": message,
+            "via": via,
+        }))?,
     }
+    Ok(())
+}
+
+fn run_pause(cli: &Cli, args: &PauseArgs) -> Result<(), CliError> {
+    let duration = parse_window_duration(&args.duration)?;
+    if duration.is_zero() {
+        return Err(CliError::User(
+            "pause duration must be positive".to_string(),
+        ));
+    }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let reason = args
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .map(str::to_string);
+    let window = MaintenanceWindow::new(chrono::Utc::now(), duration, args.mute_alerts, reason);
+    let path = maintenance_path(&config.paths.state_file);
+    write_maintenance(&path, &window).map_err(|e| {
+        CliError::Runtime(format!(
+            "failed to write maintenance marker {}: {e}",
+            path.display()
+        ))
+    })?;
 
     match output_mode(cli) {
         OutputMode::Human => {
-            println!("\nCleanup complete:");
             println!(
-                "  Deleted: {items_deleted} items, {} freed",
-                format_bytes(bytes_freed)
+                "Enforcement paused until {} ({}).",
+                window.until,
+                format_duration(duration)
             );
-            if items_skipped > 0 {
-                println!("  Skipped: {items_skipped} items");
+            if window.mute_alerts {
+                println!("Notifications are muted for the window.");
             }
+            println!("Run `sbh resume` to end the window early.");
         }
-        OutputMode::Json => {
-            let payload = json!({
-                "command": "clean",
-                "scanned_directories": dir_count,
-                "elapsed_seconds": scan_elapsed.as_secs_f64(),
-                "candidates_count": plan.estimated_items,
-                "items_deleted": items_deleted,
-                "items_skipped": items_skipped,
-                "bytes_freed": bytes_freed,
-                "dry_run": false,
-                "protected_count": protected_count,
-            });
-            write_json_line(&payload)?;
-        }
+        OutputMode::Json => write_json_line(&json!({
+            "command": "pause",
+            "maintenance": window,
+            "marker": path.to_string_lossy(),
+        }))?,
     }
-
     Ok(())
 }
 
-/// Delete a single candidate path (file or directory).
-fn delete_single_candidate(candidate: &CandidacyScore) -> std::result::Result<(), String> {
-    if candidate.path.is_dir() {
-        std::fs::remove_dir_all(&candidate.path).map_err(|e| e.to_string())
-    } else {
-        std::fs::remove_file(&candidate.path).map_err(|e| e.to_string())
+fn run_resume(cli: &Cli) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let path = maintenance_path(&config.paths.state_file);
+    let was_active = active_maintenance(&path, chrono::Utc::now()).is_some();
+    clear_maintenance(&path).map_err(|e| {
+        CliError::Runtime(format!(
+            "failed to remove maintenance marker {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if was_active {
+                println!("Maintenance window ended; enforcement resumes on the next daemon tick.");
+            } else {
+                println!("No maintenance window in effect.");
+            }
+        }
+        OutputMode::Json => write_json_line(&json!({
+            "command": "resume",
+            "was_paused": was_active,
+        }))?,
     }
+    Ok(())
 }
 
-/// Print a human-readable cleanup summary from a DeletionReport.
-fn print_clean_summary(report: &storage_ballast_helper::scanner::deletion::DeletionReport) {
-    if report.dry_run {
-        println!(
-            "Dry run: {} items ({}) would be freed.",
-            report.items_deleted,
-            format_bytes(report.bytes_freed),
-        );
-    } else {
-        println!("Cleanup complete:");
-        println!(
-            "  Deleted: {} items, {} freed in {:.1}s",
-            report.items_deleted,
-            format_bytes(report.bytes_freed),
-            report.duration.as_secs_f64(),
-        );
-        if report.items_skipped > 0 {
-            println!("  Skipped: {} items", report.items_skipped);
-        }
-        if report.items_failed > 0 {
-            println!("  Failed: {} items", report.items_failed);
-            for err in &report.errors {
-                eprintln!("    {}: {}", err.path.display(), err.error);
+fn run_selftest(cli: &Cli, args: &SelftestArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::selftest::{self, StageStatus};
+
+    let sandbox = selftest::default_sandbox();
+    let report = selftest::run(&sandbox);
+    // A sandbox that already existed belongs to someone else.
+    let ours = report
+        .stages
+        .first()
+        .is_some_and(|stage| stage.status != StageStatus::Fail);
+    if ours
+        && !args.keep
+        && let Err(e) = std::fs::remove_dir_all(&sandbox)
+    {
+        eprintln!("Warning: could not remove {}: {e}", sandbox.display());
+    }
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            for stage in &report.stages {
+                println!(
+                    "  {:<4}  {:<15} {:>7.1}ms  {}",
+                    stage.status.as_str().to_uppercase(),
+                    stage.name,
+                    stage.elapsed.as_secs_f64() * 1000.0,
+                    stage.detail
+                );
+            }
+            if args.keep && ours {
+                println!("\nSandbox kept at {}", sandbox.display());
             }
         }
-        if report.circuit_breaker_tripped {
-            println!("  Warning: circuit breaker was tripped due to consecutive failures.");
+        OutputMode::Json => {
+            let stages: Vec<Value> = report
+                .stages
+                .iter()
+                .map(|stage| {
+                    json!({
+                        "stage": stage.name,
+                        "status": stage.status.as_str(),
+                        "detail": stage.detail,
+                        "elapsed_ms": stage.elapsed.as_secs_f64() * 1000.0,
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "selftest",
+                "passed": report.passed(),
+                "sandbox": sandbox.to_string_lossy(),
+                "kept": args.keep && ours,
+                "stages": stages,
+            }))?;
         }
     }
-}
 
-/// Emit the clean report in JSON format.
-fn emit_clean_report_json(
-    plan: &DeletionPlan,
-    report: &storage_ballast_helper::scanner::deletion::DeletionReport,
-    dir_count: usize,
-    scan_elapsed: std::time::Duration,
-    protected_count: usize,
-) -> Result<(), CliError> {
-    let errors: Vec<Value> = report
-        .errors
-        .iter()
-        .map(|e| {
-            json!({
-                "path": e.path.to_string_lossy(),
-                "error": e.error,
-                "error_code": e.error_code,
-                "recoverable": e.recoverable,
-            })
-        })
-        .collect();
-
-    let payload = json!({
-        "command": "clean",
-        "scanned_directories": dir_count,
-        "elapsed_seconds": scan_elapsed.as_secs_f64(),
-        "candidates_count": plan.estimated_items,
-        "items_deleted": report.items_deleted,
-        "items_skipped": report.items_skipped,
-        "items_failed": report.items_failed,
-        "bytes_freed": report.bytes_freed,
-        "duration_seconds": report.duration.as_secs_f64(),
-        "dry_run": report.dry_run,
-        "circuit_breaker_tripped": report.circuit_breaker_tripped,
-        "protected_count": protected_count,
-        "errors": errors,
-    });
-    write_json_line(&payload)
+    if report.passed() {
+        Ok(())
+    } else {
+        let failed = report
+            .stages
+            .iter()
+            .filter(|stage| stage.status == StageStatus::Fail)
+            .map(|stage| stage.name)
+            .collect::<Vec<_>>();
+        Err(CliError::Runtime(format!(
+            "selftest failed: {}",
+            failed.join(", ")
+        )))
+    }
 }
 
-#[allow(
-    clippy::too_many_lines,
-    clippy::cast_precision_loss,
-    clippy::cast_possible_truncation,
-    clippy::cast_sign_loss
-)]
-fn run_check(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
-    let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
-
-    // Determine check path: CLI arg, or cwd.
-    let check_path = args
-        .path
-        .clone()
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
-
-    let stats = platform
-        .fs_stats(&check_path)
-        .map_err(|e| CliError::Runtime(e.to_string()))?;
+fn run_doctor(cli: &Cli) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::doctor::{self, CheckStatus};
 
-    let free_pct = stats.free_pct();
-    let config = Config::load(cli.config.as_deref()).unwrap_or_default();
-    let threshold_pct = args
-        .target_free
-        .unwrap_or(config.pressure.yellow_min_free_pct);
+    let report = doctor::run(cli.config.as_deref());
+    let checklist = report.checklist();
 
-    // Check 1: absolute free space requirement.
-    if let Some(need_bytes) = args.need
-        && stats.available_bytes < need_bytes
-    {
-        match output_mode(cli) {
-            OutputMode::Human => {
-                eprintln!(
-                    "sbh: {} has {} free but {} required. Run: sbh emergency {}",
-                    stats.mount_point.display(),
-                    format_bytes(stats.available_bytes),
-                    format_bytes(need_bytes),
-                    check_path.display(),
+    match output_mode(cli) {
+        OutputMode::Human => {
+            for check in &report.checks {
+                println!(
+                    "  {:<4}  {:<9} {}",
+                    check.status.as_str().to_uppercase(),
+                    check.name,
+                    check.detail
                 );
             }
-            OutputMode::Json => {
-                let payload = json!({
-                    "command": "check",
-                    "status": "critical",
-                    "path": check_path.to_string_lossy(),
-                    "mount_point": stats.mount_point.to_string_lossy(),
-                    "free_bytes": stats.available_bytes,
-                    "need_bytes": need_bytes,
-                    "free_pct": free_pct,
-                    "exit_code": 2,
-                });
-                write_json_line(&payload)?;
+            if checklist.is_empty() {
+                println!("\nNo problems found.");
+            } else {
+                println!("\nChecklist:");
+                for step in &checklist {
+                    println!("  [ ] {step}");
+                }
             }
         }
-        return Err(CliError::Runtime("insufficient disk space".to_string()));
+        OutputMode::Json => {
+            write_json_line(&json!({
+                "command": "doctor",
+                "passed": report.passed(),
+                "checks": report.checks,
+                "checklist": checklist,
+            }))?;
+        }
     }
 
-    // Check 2: percentage threshold.
-    if free_pct < threshold_pct {
-        match output_mode(cli) {
-            OutputMode::Human => {
-                eprintln!(
-                    "sbh: {} has {} free ({:.1}%). Run: sbh emergency {}",
-                    stats.mount_point.display(),
-                    format_bytes(stats.available_bytes),
-                    free_pct,
-                    check_path.display(),
-                );
-            }
-            OutputMode::Json => {
-                let payload = json!({
-                    "command": "check",
-                    "status": "critical",
-                    "path": check_path.to_string_lossy(),
-                    "mount_point": stats.mount_point.to_string_lossy(),
-                    "free_bytes": stats.available_bytes,
-                    "total_bytes": stats.total_bytes,
-                    "free_pct": free_pct,
-                    "threshold_pct": threshold_pct,
-                    "exit_code": 2,
-                });
-                write_json_line(&payload)?;
-            }
-        }
-        return Err(CliError::Runtime("disk space below threshold".to_string()));
+    if report.passed() {
+        Ok(())
+    } else {
+        let failed = report
+            .checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+            .map(|check| check.name)
+            .collect::<Vec<_>>();
+        Err(CliError::Runtime(format!(
+            "doctor found problems: {}",
+            failed.join(", ")
+        )))
     }
+}
 
-    // Check 2.5: warn if state.json is stale (daemon may not be running).
-    if let Ok(meta) = std::fs::metadata(&config.paths.state_file)
-        && let Ok(modified) = meta.modified()
-    {
-        let age = SystemTime::now()
-            .duration_since(modified)
-            .unwrap_or_default();
-        let stale_threshold = std::time::Duration::from_secs(DAEMON_STATE_STALE_THRESHOLD_SECS);
-        if age > stale_threshold && output_mode(cli) == OutputMode::Human {
-            eprintln!(
-                "sbh: warning: state.json is {:.0}s old (daemon may not be running)",
-                age.as_secs_f64(),
-            );
-        }
+fn run_notify_rules_test(cli: &Cli, args: &NotifyRulesTestArgs) -> Result<(), CliError> {
+    if !EVENT_TYPES.contains(&args.event.as_str()) {
+        return Err(CliError::User(format!(
+            "unknown event type '{}'; expected one of {}",
+            args.event,
+            EVENT_TYPES.join(", ")
+        )));
     }
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let manager = NotificationManager::from_config(&config.notifications);
+    let key = EventKey {
+        event: &args.event,
+        level: args.level,
+        mount: args.mount.as_deref(),
+    };
+    let routes = manager.routes(&key);
 
-    // Check 3: prediction from daemon state.json (if available and --predict requested).
-    if let Some(predict_minutes) = args.predict {
-        match read_daemon_prediction(&config.paths.state_file, &stats.mount_point) {
-            Some(rate_bps) if rate_bps > 0.0 => {
-                // Positive rate means filling; estimate time to threshold.
-                let bytes_until_threshold = stats
-                    .available_bytes
-                    .saturating_sub((threshold_pct / 100.0 * stats.total_bytes as f64) as u64);
-                let seconds_left = bytes_until_threshold as f64 / rate_bps;
-                let minutes_left = seconds_left / 60.0;
-
-                if minutes_left < predict_minutes as f64 {
-                    match output_mode(cli) {
-                        OutputMode::Human => {
-                            eprintln!(
-                                "sbh: {} has {} free but predicted full in {:.0} min (need {} min)",
-                                stats.mount_point.display(),
-                                format_bytes(stats.available_bytes),
-                                minutes_left,
-                                predict_minutes,
-                            );
-                        }
-                        OutputMode::Json => {
-                            let payload = json!({
-                                "command": "check",
-                                "status": "warning",
-                                "path": check_path.to_string_lossy(),
-                                "mount_point": stats.mount_point.to_string_lossy(),
-                                "free_bytes": stats.available_bytes,
-                                "free_pct": free_pct,
-                                "rate_bytes_per_sec": rate_bps,
-                                "minutes_until_full": minutes_left,
-                                "predict_minutes": predict_minutes,
-                                "exit_code": 1,
-                            });
-                            write_json_line(&payload)?;
-                        }
-                    }
-                    return Err(CliError::User(
-                        "predicted disk full within window".to_string(),
-                    ));
-                }
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "Event: {} at {}{}",
+                args.event,
+                args.level,
+                args.mount
+                    .as_deref()
+                    .map_or_else(String::new, |mount| format!(" on {mount}"))
+            );
+            if routes.is_empty() {
+                println!("  No active notification channels; nothing would be sent.");
             }
-            _ => {
-                // No prediction available — daemon not running or not filling.
-                // This is not an error, just degraded mode.
+            for route in &routes {
+                let outcome = match route.outcome {
+                    RouteOutcome::Muted => "muted".to_string(),
+                    RouteOutcome::BelowMinLevel(level) => {
+                        format!("dropped: {level} is below the channel's min_level")
+                    }
+                    RouteOutcome::Delivered(level) => format!("sent as {level}"),
+                };
+                let rule = route
+                    .rule
+                    .map_or_else(|| "no rule".to_string(), |index| format!("rules[{index}]"));
+                println!("  {:<8} {outcome} ({rule})", route.channel);
             }
         }
+        OutputMode::Json => {
+            let routes: Vec<Value> = routes
+                .iter()
+                .map(|route| {
+                    let (outcome, level) = match route.outcome {
+                        RouteOutcome::Muted => ("muted", None),
+                        RouteOutcome::BelowMinLevel(level) => ("below_min_level", Some(level)),
+                        RouteOutcome::Delivered(level) => ("delivered", Some(level)),
+                    };
+                    json!({
+                        "channel": route.channel,
+                        "rule": route.rule,
+                        "outcome": outcome,
+                        "level": level,
+                    })
+                })
+                .collect();
+            write_json_line(&json!({
+                "command": "notify rules test",
+                "event": args.event,
+                "level": args.level,
+                "mount": args.mount,
+                "routes": routes,
+            }))?;
+        }
     }
-
-    // All checks passed — silent success on human mode.
-    if output_mode(cli) == OutputMode::Json {
-        let payload = json!({
-            "command": "check",
-            "status": "ok",
-            "path": check_path.to_string_lossy(),
-            "mount_point": stats.mount_point.to_string_lossy(),
-            "free_bytes": stats.available_bytes,
-            "total_bytes": stats.total_bytes,
-            "free_pct": free_pct,
-            "exit_code": 0,
-        });
-        write_json_line(&payload)?;
-    }
-
     Ok(())
 }
 
 /// Read EWMA rate prediction from daemon state.json if available and fresh.
-fn read_daemon_prediction(state_path: &Path, mount_point: &Path) -> Option<f64> {
-    let content = std::fs::read_to_string(state_path).ok()?;
-
-    // Check freshness: file modified within the staleness threshold.
-    let meta = std::fs::metadata(state_path).ok()?;
-    let modified = meta.modified().ok()?;
-    let age = SystemTime::now().duration_since(modified).ok()?;
-    if age.as_secs() > DAEMON_STATE_STALE_THRESHOLD_SECS {
-        return None; // Stale state, daemon likely not running.
-    }
-
-    let state: serde_json::Value = serde_json::from_str(&content).ok()?;
-
-    // Look for rate prediction matching the mount point.
-    let rates = state.get("rates")?.as_object()?;
-    let mount_key = mount_point.to_string_lossy();
-    let rate_obj = rates.get(mount_key.as_ref())?;
-    rate_obj.get("bytes_per_sec")?.as_f64()
+///
+/// `path` may be any path on the filesystem; it is resolved to the same
+/// device/mount key the daemon writes, so symlinks and bind mounts match.
+fn read_daemon_prediction(state_path: &Path, path: &Path) -> Option<f64> {
+    let snapshot = read_state(state_path)
+        .ok()
+        .filter(StateSnapshot::is_fresh)?;
+    snapshot
+        .rate_for(&MountKey::resolve(path))
+        .map(|rate| rate.bytes_per_sec)
 }
 
 #[allow(clippy::too_many_lines)]
@@ -4625,7 +11729,10 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
             .cloned()
             .collect::<HashSet<_>>(),
     };
-    let walker = DirectoryWalker::new(walker_config, protection);
+    let walker = DirectoryWalker::new(walker_config, protection)
+        .with_large_files(config.scanner.large_file_min_bytes)
+        .with_artifact_roots(config.scanner.artifact_root_sampling())
+        .with_device_limits(config.scanner.device_limits());
     let entries = walker
         .walk()
         .map_err(|e| CliError::Runtime(e.to_string()))?;
@@ -4636,23 +11743,32 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
 
     // Classify and score using default weights.
     let registry = ArtifactPatternRegistry::default();
-    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes);
+    let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+        .with_costs(&config.costs);
     let now = SystemTime::now();
+    let truncatable = config.scanner.truncate_allowlist();
 
     let scored: Vec<CandidacyScore> = entries
         .iter()
         .map(|entry| {
-            let classification = registry.classify(&entry.path, entry.structural_signals);
+            let classification = registry.classify_entry(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.structural_signals,
+            );
             let age = now
                 .duration_since(entry.metadata.effective_age_timestamp())
                 .unwrap_or_default();
+            let is_open = is_path_open_by_ancestor(&entry.path, &open_paths)
+                && (entry.metadata.is_dir || !truncatable.contains(&classification.category));
             let candidate = CandidateInput {
                 path: entry.path.clone(),
                 size_bytes: entry.metadata.content_size_bytes,
+                disk_bytes: entry.metadata.content_disk_bytes,
                 age,
                 classification,
                 signals: entry.structural_signals,
-                is_open: is_path_open_by_ancestor(&entry.path, &open_paths),
+                is_open,
                 excluded: false,
             };
             // High urgency (0.8) for emergency mode — aggressive scoring.
@@ -4670,6 +11786,8 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
         min_score: config.scoring.min_score,
         check_open_files: true,
         circuit_breaker_threshold: u32::MAX, // Effectively disabled.
+        truncate_categories: truncatable,
+        workers: config.scanner.delete_workers,
         ..Default::default()
     };
     let executor = DeletionExecutor::new(deletion_config, None);
@@ -4695,6 +11813,7 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
                     "candidates_count": 0,
                     "items_deleted": 0,
                     "bytes_freed": 0,
+                    "disk_bytes_freed": 0,
                 });
                 write_json_line(&payload)?;
             }
@@ -4718,7 +11837,10 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
         eprintln!(
             "\nTotal: {} items, {}",
             plan.estimated_items,
-            format_bytes(plan.total_reclaimable_bytes),
+            format_bytes_on_disk(
+                plan.total_reclaimable_bytes,
+                plan.total_reclaimable_disk_bytes
+            ),
         );
         eprintln!();
     }
@@ -4732,27 +11854,29 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
     }
     if args.yes {
         let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
+        let before = snapshot_plan_mounts(&platform, &plan);
         let collector = std::sync::Arc::new(FsStatsCollector::new(
-            platform,
+            std::sync::Arc::clone(&platform),
             std::time::Duration::from_millis(500),
         ));
-        let pressure_check = build_pressure_check(Some(args.target_free), collector);
+        let pressure_check = build_pressure_check(Some(args.target_free), collector, None);
         let report = executor.execute(
             &plan,
             pressure_check
                 .as_ref()
-                .map(|f| f as &dyn Fn(&std::path::Path) -> bool),
+                .map(|f| f as &(dyn Fn(&std::path::Path) -> bool + Sync)),
         );
+        let verified = verify_clean_reclaim(platform, &plan, &report, &before);
 
         match output_mode(cli) {
             OutputMode::Human => {
-                print_clean_summary(&report);
+                print_clean_summary(&report, &verified);
                 eprintln!(
                     "\nConsider installing sbh for ongoing protection: sbh install --systemd --user"
                 );
             }
             OutputMode::Json => {
-                emit_clean_report_json(&plan, &report, dir_count, scan_elapsed, 0)?;
+                emit_clean_report_json(&plan, &report, &verified, dir_count, scan_elapsed, 0, &[])?;
             }
         }
     } else {
@@ -4778,6 +11902,7 @@ fn run_interactive_emergency(
     let mut items_deleted: usize = 0;
     let mut items_skipped: usize = 0;
     let mut bytes_freed: u64 = 0;
+    let mut disk_bytes_freed: u64 = 0;
     let mut delete_all = false;
 
     let platform = detect_platform().map_err(|e| CliError::Runtime(e.to_string()))?;
@@ -4801,7 +11926,7 @@ fn run_interactive_emergency(
             'y'
         } else {
             let path_str = truncate_path(&candidate.path, 60);
-            let size_str = format_bytes(candidate.size_bytes);
+            let size_str = format_bytes_on_disk(candidate.size_bytes, candidate.disk_bytes);
             eprint!(
                 "  [{}/{}] {} ({}, score {:.2})? ",
                 i + 1,
@@ -4839,6 +11964,7 @@ fn run_interactive_emergency(
                 Ok(()) => {
                     items_deleted += 1;
                     bytes_freed += candidate.size_bytes;
+                    disk_bytes_freed += candidate.disk_bytes;
                     if !delete_all {
                         eprintln!("    Deleted.");
                     }
@@ -4856,8 +11982,8 @@ fn run_interactive_emergency(
         OutputMode::Human => {
             eprintln!("\nEmergency cleanup complete:");
             eprintln!(
-                "  Deleted: {items_deleted} items, {} freed",
-                format_bytes(bytes_freed),
+                "  Deleted: {items_deleted} items, freed {}",
+                format_bytes_on_disk(bytes_freed, disk_bytes_freed),
             );
             if items_skipped > 0 {
                 eprintln!("  Skipped: {items_skipped} items");
@@ -4875,6 +12001,7 @@ fn run_interactive_emergency(
                 "items_deleted": items_deleted,
                 "items_skipped": items_skipped,
                 "bytes_freed": bytes_freed,
+                "disk_bytes_freed": disk_bytes_freed,
             });
             write_json_line(&payload)?;
         }
@@ -4909,6 +12036,16 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Apparent size followed by the space it occupies on disk, e.g.
+/// `1.0 GiB / 640.0 MiB on disk`.
+fn format_bytes_on_disk(apparent: u64, disk: u64) -> String {
+    format!(
+        "{} / {} on disk",
+        format_bytes(apparent),
+        format_bytes(disk)
+    )
+}
+
 fn format_duration(duration: std::time::Duration) -> String {
     let secs = duration.as_secs();
     if secs < 60 {
@@ -4982,12 +12119,17 @@ fn write_json_line(payload: &Value) -> Result<(), CliError> {
     let mut stdout = io::stdout().lock();
     serde_json::to_writer(&mut stdout, payload)?;
     writeln!(stdout)?;
+    JSON_PAYLOAD_EMITTED.store(true, std::sync::atomic::Ordering::SeqCst);
     Ok(())
 }
 
 fn output_mode(cli: &Cli) -> OutputMode {
     let env_mode = std::env::var("SBH_OUTPUT_FORMAT").ok();
-    resolve_output_mode(cli.json, env_mode.as_deref(), io::stdout().is_terminal())
+    resolve_output_mode(
+        cli.json || cli.command.streams_json(),
+        env_mode.as_deref(),
+        io::stdout().is_terminal(),
+    )
 }
 
 fn resolve_output_mode(json_flag: bool, env_mode: Option<&str>, stdout_is_tty: bool) -> OutputMode {
@@ -5012,6 +12154,229 @@ fn resolve_output_mode(json_flag: bool, env_mode: Option<&str>, stdout_is_tty: b
     }
 }
 
+// ---------------------------------------------------------------------------
+// Help topics and man pages
+// ---------------------------------------------------------------------------
+
+/// `sbh help [TOPIC...]`.
+///
+/// A single word matching a guide topic renders the guide (so `sbh help
+/// emergency` explains the model; `sbh emergency --help` still lists flags).
+/// Anything else is treated as a command path and shows clap's long help.
+fn run_help(cli: &Cli, args: &HelpArgs) -> Result<(), CliError> {
+    use storage_ballast_helper::cli::guide::{HelpTopic, format_topic_list, page};
+
+    let mode = output_mode(cli);
+
+    if args.topics {
+        match mode {
+            OutputMode::Human => print!("{}", format_topic_list()),
+            OutputMode::Json => {
+                let topics: Vec<Value> = HelpTopic::ALL
+                    .iter()
+                    .map(|t| json!({ "topic": t.name(), "summary": t.summary() }))
+                    .collect();
+                write_json_line(&json!({ "command": "help", "topics": topics }))?;
+            }
+        }
+        return Ok(());
+    }
+
+    if let [name] = args.topic.as_slice()
+        && let Some(topic) = HelpTopic::from_name(name)
+    {
+        match mode {
+            OutputMode::Human => {
+                if args.no_pager || !io::stdout().is_terminal() {
+                    print!("{}", topic.body());
+                } else {
+                    page(topic.body())?;
+                }
+            }
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "help",
+                    "topic": topic.name(),
+                    "summary": topic.summary(),
+                    "body": topic.body(),
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return Ok(());
+    }
+
+    // Command help: let clap render it exactly as `sbh <path> --help` would.
+    let help_argv = std::iter::once("sbh")
+        .chain(args.topic.iter().map(String::as_str))
+        .chain(std::iter::once("--help"));
+    match Cli::try_parse_from(help_argv) {
+        Err(e) if e.kind() == clap::error::ErrorKind::DisplayHelp => {
+            e.print()?;
+            if args.topic.is_empty() {
+                println!();
+                print!("{}", format_topic_list());
+            }
+            Ok(())
+        }
+        _ => Err(CliError::User(format!(
+            "unknown command or help topic: {} (see `sbh help --topics`)",
+            args.topic.join(" ")
+        ))),
+    }
+}
+
+/// `sbh man`: render `sbh.1` to stdout, or one page per command into `--out-dir`.
+///
+/// Pages are rendered at runtime rather than from `build.rs`: the clap
+/// definition lives in this binary next to every handler it dispatches to,
+/// so a build script could only reach it by compiling the CLI twice. The
+/// release workflow runs `sbh man --out-dir` on the built binary and ships
+/// the result as `sbh-man.tar.xz`.
+fn run_man(cli: &Cli, args: &ManArgs) -> Result<(), CliError> {
+    let command = Cli::command();
+
+    let Some(out_dir) = &args.out_dir else {
+        clap_mangen::Man::new(command).render(&mut io::stdout())?;
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| CliError::Runtime(format!("create {}: {e}", out_dir.display())))?;
+    clap_mangen::generate_to(command, out_dir)
+        .map_err(|e| CliError::Runtime(format!("write man pages: {e}")))?;
+
+    let mut pages: Vec<PathBuf> = std::fs::read_dir(out_dir)
+        .map_err(|e| CliError::Runtime(format!("read {}: {e}", out_dir.display())))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "1"))
+        .collect();
+    pages.sort();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Wrote {} man pages to {}", pages.len(), out_dir.display());
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "man",
+                "out_dir": out_dir.to_string_lossy(),
+                "pages": pages.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>(),
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Shell completions
+// ---------------------------------------------------------------------------
+
+/// Environment variable that switches the binary into dynamic completion mode.
+pub const COMPLETE_ENV_VAR: &str = "COMPLETE";
+
+/// Write a completion script for `shell`.
+///
+/// By default this emits the dynamic registration stub, which calls back into
+/// `sbh` on every TAB so candidates (config keys, mounts) reflect the live
+/// system. `static_script` falls back to clap's fully static generator.
+fn write_completion_script(
+    shell: CompletionShell,
+    static_script: bool,
+    buf: &mut dyn Write,
+) -> io::Result<()> {
+    let mut command = Cli::command();
+    let binary_name = command.get_name().to_string();
+
+    let shell_name = shell.to_string();
+    let shells = DynamicShells::builtins();
+    match shells.completer(&shell_name) {
+        Some(completer) if !static_script => completer.write_registration(
+            COMPLETE_ENV_VAR,
+            &binary_name,
+            &binary_name,
+            &binary_name,
+            buf,
+        ),
+        _ => {
+            generate(shell, &mut command, binary_name, buf);
+            Ok(())
+        }
+    }
+}
+
+/// Completion candidates for `config set <KEY>`.
+fn config_key_candidates() -> Vec<CompletionCandidate> {
+    let Ok(value) = serde_json::to_value(Config::default()) else {
+        return Vec::new();
+    };
+    config_dot_keys(&value)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Flatten a serialized config into sorted leaf dot-paths (e.g. `policy.mode`).
+///
+/// Map-valued keys with no default entries (e.g. `ballast.overrides`) are
+/// omitted because they cannot be set with a single scalar value.
+fn config_dot_keys(value: &Value) -> Vec<String> {
+    fn walk(prefix: &str, value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    walk(&path, child, out);
+                }
+            }
+            _ if !prefix.is_empty() => out.push(prefix.to_string()),
+            _ => {}
+        }
+    }
+
+    let mut keys = Vec::new();
+    walk("", value, &mut keys);
+    keys.sort();
+    keys
+}
+
+/// Completion candidates for mount-point arguments, annotated with fs type.
+fn mount_candidates() -> Vec<CompletionCandidate> {
+    let Ok(platform) = detect_platform() else {
+        return Vec::new();
+    };
+    let Ok(mounts) = platform.mount_points() else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<(PathBuf, String)> = mounts
+        .into_iter()
+        .filter(|mount| {
+            // Same filter as `sbh status`: skip pseudo and read-only filesystems.
+            platform
+                .fs_stats(&mount.path)
+                .is_ok_and(|stats| stats.total_bytes > 0 && !stats.is_readonly)
+        })
+        .filter(|mount| seen.insert(mount.path.clone()))
+        .map(|mount| (mount.path, mount.fs_type))
+        .collect();
+    candidates.sort();
+
+    candidates
+        .into_iter()
+        .map(|(path, fs_type)| {
+            CompletionCandidate::new(path.into_os_string()).help(Some(fs_type.into()))
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Update command
 // ---------------------------------------------------------------------------
@@ -5431,10 +12796,18 @@ fn setup_completions(
     }
 
     // Generate completion script.
-    let mut command = Cli::command();
-    let binary_name = command.get_name().to_string();
     let mut buf = Vec::new();
-    generate(shell, &mut command, binary_name, &mut buf);
+    if let Err(e) = write_completion_script(shell, false, &mut buf) {
+        return SetupStepResult {
+            step: step_name,
+            success: false,
+            message: format!("cannot generate completion script: {e}"),
+            remediation: Some(format!(
+                "Generate completions manually:\n  sbh completions {shell:?} > {}",
+                completion_file.display()
+            )),
+        };
+    }
 
     // Create directory if needed.
     if let Some(parent) = completion_file.parent()
@@ -5657,6 +13030,18 @@ mod tests {
             vec!["sbh", "tune", "--apply"],
             vec!["sbh", "check", "/data", "--target-free", "20"],
             vec!["sbh", "blame", "--top", "10"],
+            vec!["sbh", "blame", "--window-secs", "2"],
+            vec!["sbh", "blame", "--artifacts"],
+            vec!["sbh", "blame", "--incident", "last-red"],
+            vec![
+                "sbh",
+                "blame",
+                "--watch",
+                "--threshold",
+                "5",
+                "--refresh-ms",
+                "500",
+            ],
             vec!["sbh", "dashboard", "--refresh-ms", "250"],
             vec!["sbh", "dashboard", "--new-dashboard"],
             vec!["sbh", "dashboard", "--legacy-dashboard"],
@@ -5664,8 +13049,161 @@ mod tests {
             vec!["sbh", "ballast", "release", "2"],
             vec!["sbh", "config", "path"],
             vec!["sbh", "config", "set", "policy.mode", "observe"],
+            vec!["sbh", "config", "export", "--portable", "-o", "fleet.toml"],
+            vec!["sbh", "config", "import", "fleet.toml"],
+            vec!["sbh", "config", "import", "-", "--apply", "--yes"],
             vec!["sbh", "version", "--verbose"],
+            vec!["sbh", "scan", "/data", "/nfs/build", "--root-timeout", "30"],
+            vec!["sbh", "scan", "/data", "--profile-syscalls"],
+            vec!["sbh", "scan", "--orphaned-fds"],
+            vec!["sbh", "scan", "--daemon"],
+            vec!["sbh", "scan", "/data", "--watch", "--min-score", "0.5"],
+            vec![
+                "sbh",
+                "scan",
+                "/data",
+                "--orphaned-fds",
+                "--truncate",
+                "--yes",
+            ],
+            vec!["sbh", "exit-codes"],
+            vec![
+                "sbh",
+                "bench",
+                "scan",
+                "--synthetic",
+                "--projects",
+                "20",
+                "--seed",
+                "7",
+            ],
+            vec!["sbh", "bench", "scan", "/data", "--iterations", "5"],
+            vec![
+                "sbh",
+                "clean",
+                "--mount",
+                "/data",
+                "--target-free",
+                "20",
+                "--dry-run",
+            ],
+            vec!["sbh", "clean", "--caches", "--dry-run"],
+            vec!["sbh", "ci", "begin"],
+            vec!["sbh", "ci", "report", "/data"],
+            vec!["sbh", "runner-hook", "pre-job", "--need", "5000000000"],
+            vec!["sbh", "runner-hook", "pre-job", "/builds/app", "--dry-run"],
+            vec!["sbh", "runner-hook", "post-job", "--clean"],
+            vec!["sbh", "workspace", "create", "/w/a1", "--owner", "a1"],
+            vec!["sbh", "workspace", "destroy", "/w/a1", "--dry-run"],
+            vec!["sbh", "export", "prometheus"],
+            vec!["sbh", "export", "grafana-dashboard"],
+            vec!["sbh", "check", "--nagios", "--warning", "15"],
+            vec!["sbh", "check", "/data", "--no-policy-file"],
+            vec!["sbh", "check", "--format", "gha", "--predict", "30"],
+            vec!["sbh", "status", "--discovery", "zabbix"],
+            vec!["sbh", "status", "--telegraf"],
+            vec!["sbh", "restore", "--list"],
+            vec!["sbh", "restore", "20261016T120000.000Z-1a2b3c4d"],
+            vec!["sbh", "restore", "/data/proj/target", "--to", "/tmp/target"],
+            vec!["sbh", "clean", "/data", "--quarantine", "--yes"],
+            vec!["sbh", "clean", "/data", "--older-than", "7d", "--dry-run"],
+            vec!["sbh", "scan", "--older-than", "1d", "--newer-than", "30d"],
+            vec!["sbh", "scan", "/data", "--json-stream"],
+            vec!["sbh", "clean", "/data", "--json-stream", "--yes"],
+            vec!["sbh", "clean", "/data", "--dry-run", "--plan-hash"],
+            vec!["sbh", "clean", "/data", "--expect-plan-hash", "ab12"],
+            vec!["sbh", "clean", "/data", "--emit-plan", "plan.json"],
+            vec!["sbh", "clean", "--execute-plan", "plan.json", "--yes"],
+            vec![
+                "sbh",
+                "clean",
+                "--execute-plan",
+                "plan.json",
+                "--expect-plan-hash",
+                "ab12",
+                "--yes",
+            ],
+            vec!["sbh", "quarantine", "list"],
+            vec!["sbh", "quarantine", "restore", "/data/proj/target"],
+            vec!["sbh", "quarantine", "purge", "--expired"],
+            vec!["sbh", "quarantine", "purge", "--all"],
+            vec!["sbh", "audit", "list"],
+            vec!["sbh", "audit", "list", "--limit", "5", "--path", "/data"],
+            vec!["sbh", "audit", "show", "20261016T120000Z-4242-1", "--json"],
+            vec!["sbh", "notify", "rules", "test", "pressure_changed"],
+            vec!["sbh", "annotate", "started nightly build farm run"],
+            vec!["sbh", "install", "--scheduled-task", "--user"],
+            vec!["sbh", "uninstall", "--scheduled-task", "--purge"],
+            vec!["sbh", "annotate", "deploy", "v2.3", "started"],
+            vec!["sbh", "pause", "--for", "2h"],
+            vec![
+                "sbh",
+                "pause",
+                "--for",
+                "90m",
+                "--mute-alerts",
+                "--reason",
+                "migration",
+            ],
+            vec!["sbh", "resume"],
+            vec!["sbh", "daemon", "--once"],
+            vec!["sbh", "daemon", "--once", "--dry-run"],
+            vec!["sbh", "selftest"],
+            vec!["sbh", "selftest", "--keep", "--json"],
+            vec!["sbh", "doctor"],
+            vec!["sbh", "doctor", "--json"],
+            vec!["sbh", "explain", "/data/proj/target"],
+            vec!["sbh", "explain", "/data/proj/target", "--level", "3"],
+            vec!["sbh", "explain", "/data/proj/target", "--urgency", "0.8"],
+            vec!["sbh", "explain", "/data/proj/target", "--rescore"],
+            vec![
+                "sbh",
+                "notify",
+                "rules",
+                "test",
+                "pressure_changed",
+                "--level",
+                "yellow",
+                "--mount",
+                "/tmp",
+            ],
         ];
+        assert!(Cli::try_parse_from(["sbh", "quarantine", "purge"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "annotate"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "pause"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "selftest", "/tmp"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "explain"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "audit", "show"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "daemon", "--dry-run"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "daemon", "--once", "--background"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "install", "--scheduled-task", "--systemd"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sbh", "notify", "rules", "test", "x", "--level", "puce"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["sbh", "quarantine", "purge", "--all", "--expired"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "--caches", "--quarantine"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "bench", "scan", "--projects", "5"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "check", "--nagios", "--no-policy-file"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "check", "--nagios", "--format", "gha"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "ci"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "workspace", "create", "/w/a1"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "/data/a", "--mount", "/data"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "--caches", "/data/a"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "--truncate"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "--watch", "--daemon"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "--watch", "--orphaned-fds"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "/data", "--daemon"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "--daemon", "--older-than", "7d"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "--caches", "--newer-than", "1h"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "--caches", "--json-stream"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "clean", "/data", "--plan-hash"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sbh", "clean", "/data", "--execute-plan", "plan.json"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["sbh", "clean", "--emit-plan", "p.json", "--yes"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "scan", "--daemon", "--json-stream"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "bench", "scan", "/data", "--synthetic"]).is_err());
 
         for case in &cases {
             let parsed = Cli::try_parse_from(case.iter().copied());
@@ -5673,22 +13211,189 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_error_json_object_carries_contract_fields() {
+        let err = CliError::Runtime("disk on fire".to_string());
+        let payload = err.to_json("scan");
+        assert_eq!(payload["command"], "scan");
+        assert_eq!(payload["error"]["code"], "runtime_error");
+        assert_eq!(payload["error"]["message"], "disk on fire");
+        assert_eq!(payload["error"]["exit_code"], 2);
+        assert!(
+            payload["error"]["remediation"]
+                .as_str()
+                .is_some_and(|r| !r.is_empty())
+        );
+    }
+
+    #[test]
+    fn exit_code_table_covers_every_cli_error() {
+        let documented: HashSet<i32> = EXIT_CODES.iter().map(|d| d.code).collect();
+        assert_eq!(documented.len(), EXIT_CODES.len(), "duplicate exit codes");
+        let errors = [
+            CliError::User(String::new()),
+            CliError::Runtime(String::new()),
+            CliError::Internal(String::new()),
+            CliError::Partial(String::new()),
+            CliError::Plugin(PluginStatus::Unknown),
+            CliError::Json(serde_json::from_str::<Value>("{").unwrap_err()),
+            CliError::Io(io::Error::other("test")),
+        ];
+        for err in &errors {
+            assert!(
+                documented.contains(&err.exit_code()),
+                "{} exit code {} undocumented",
+                err.code(),
+                err.exit_code()
+            );
+        }
+        // Every non-zero documented code is reachable from some CliError.
+        let reachable: HashSet<i32> = errors.iter().map(CliError::exit_code).collect();
+        for doc in EXIT_CODES.iter().filter(|d| d.code != 0) {
+            assert!(
+                reachable.contains(&doc.code),
+                "code {} unreachable",
+                doc.code
+            );
+        }
+        // clap usage errors share the runtime code.
+        let usage = Cli::try_parse_from(["sbh", "scan", "--no-such-flag"]).unwrap_err();
+        assert!(documented.contains(&usage.exit_code()));
+    }
+
+    #[test]
+    fn cli_error_codes_are_unique() {
+        let errors = [
+            CliError::User(String::new()),
+            CliError::Runtime(String::new()),
+            CliError::Internal(String::new()),
+            CliError::Partial(String::new()),
+            CliError::Plugin(PluginStatus::Unknown),
+            CliError::Json(serde_json::from_str::<Value>("{").unwrap_err()),
+            CliError::Io(io::Error::other("test")),
+        ];
+        let codes: HashSet<&str> = errors.iter().map(CliError::code).collect();
+        assert_eq!(codes.len(), errors.len());
+    }
+
+    #[test]
+    fn usage_error_json_uses_clap_exit_code() {
+        let err = Cli::try_parse_from(["sbh", "scan", "--no-such-flag"]).unwrap_err();
+        let payload = usage_error_json(&err);
+        assert_eq!(payload["error"]["code"], "usage_error");
+        assert_eq!(payload["error"]["exit_code"], err.exit_code());
+        assert!(
+            payload["error"]["message"]
+                .as_str()
+                .is_some_and(|m| m.contains("--no-such-flag"))
+        );
+    }
+
+    #[test]
+    fn global_log_flags_parse_anywhere() {
+        let cli = Cli::try_parse_from([
+            "sbh",
+            "scan",
+            "--log-level",
+            "debug",
+            "--log-file",
+            "/tmp/sbh-diag.log",
+        ])
+        .unwrap();
+        assert_eq!(cli.log_level, Some(DiagLevel::Debug));
+        assert_eq!(cli.log_file, Some(PathBuf::from("/tmp/sbh-diag.log")));
+        assert!(Cli::try_parse_from(["sbh", "--log-level", "loud", "status"]).is_err());
+
+        let cli = Cli::try_parse_from(["sbh", "daemon", "--log-format", "json"]).unwrap();
+        assert_eq!(cli.log_format, Some(DiagFormat::Json));
+        assert!(Cli::try_parse_from(["sbh", "daemon", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn container_flag_is_global() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).unwrap().container;
+        assert!(!parse(&["sbh", "daemon"]));
+        assert!(parse(&["sbh", "--container", "daemon"]));
+        assert!(parse(&["sbh", "doctor", "--container"]));
+    }
+
+    #[test]
+    fn walk_and_score_overlaps_phases_and_ranks_results() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        for i in 0..5 {
+            std::fs::create_dir_all(tmp.path().join(format!("proj{i}/target/debug"))).unwrap();
+        }
+        let walker = DirectoryWalker::new(
+            WalkerConfig {
+                root_paths: vec![tmp.path().to_path_buf()],
+                max_depth: 6,
+                follow_symlinks: false,
+                cross_devices: false,
+                parallelism: 2,
+                excluded_paths: HashSet::new(),
+            },
+            ProtectionRegistry::marker_only(),
+        );
+        let engine = ScoringEngine::from_config(&Config::default().scoring, 0);
+
+        let result = walk_and_score(
+            &walker,
+            None,
+            &engine,
+            0.0,
+            &CandidateFilter::default(),
+            Some(3),
+            None,
+        );
+
+        assert!(result.walked >= 15, "walked {}", result.walked);
+        assert_eq!(result.scored.len(), 3.min(result.above_threshold));
+        assert_eq!(result.roots.len(), 1);
+        assert!(result.roots[0].is_complete());
+        assert!(
+            result
+                .scored
+                .windows(2)
+                .all(|w| w[0].total_score >= w[1].total_score)
+        );
+        assert!(result.timings.scoring_busy > std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn diag_level_resolution_prefers_explicit_level() {
+        assert_eq!(resolve_diag_level(None, false, false), None);
+        assert_eq!(resolve_diag_level(None, false, true), Some(DiagLevel::Info));
+        assert_eq!(resolve_diag_level(None, true, false), Some(DiagLevel::Info));
+        assert_eq!(
+            resolve_diag_level(Some(DiagLevel::Trace), false, false),
+            Some(DiagLevel::Trace)
+        );
+        assert_eq!(
+            resolve_diag_level(Some(DiagLevel::Error), true, true),
+            Some(DiagLevel::Error)
+        );
+    }
+
     #[test]
     fn daemon_args_convert_to_runtime_daemon_args() {
         let args = DaemonArgs {
             background: true,
             pidfile: Some(PathBuf::from("/tmp/sbh.pid")),
             watchdog_sec: 42,
+            once: true,
+            dry_run: false,
         };
         let runtime = to_runtime_daemon_args(&args);
         assert!(!runtime.foreground);
         assert_eq!(runtime.pidfile, Some(PathBuf::from("/tmp/sbh.pid")));
         assert_eq!(runtime.watchdog_sec, 42);
+        assert!(runtime.once);
 
         let runtime_default = to_runtime_daemon_args(&DaemonArgs::default());
         assert!(runtime_default.foreground);
         assert_eq!(runtime_default.pidfile, None);
         assert_eq!(runtime_default.watchdog_sec, 0);
+        assert!(!runtime_default.once);
     }
 
     #[test]
@@ -5864,6 +13569,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn help_subcommand_accepts_topics_and_command_paths() {
+        for case in [
+            vec!["sbh", "help"],
+            vec!["sbh", "help", "scoring"],
+            vec!["sbh", "help", "ballast", "release"],
+            vec!["sbh", "help", "--topics"],
+            vec!["sbh", "help", "pressure-model", "--no-pager"],
+        ] {
+            assert!(Cli::try_parse_from(case.clone()).is_ok(), "{case:?}");
+        }
+        assert!(Cli::try_parse_from(["sbh", "help", "scoring", "--topics"]).is_err());
+    }
+
+    #[test]
+    fn man_page_renders_top_level_command() {
+        let mut buf = Vec::new();
+        clap_mangen::Man::new(Cli::command())
+            .render(&mut buf)
+            .unwrap();
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains(".TH sbh"));
+        assert!(page.contains("emergency"));
+    }
+
+    #[test]
+    fn completions_static_flag_parses() {
+        let parsed = Cli::try_parse_from(["sbh", "completions", "bash", "--static"]).unwrap();
+        let Command::Completions(args) = parsed.command else {
+            panic!("expected completions command");
+        };
+        assert!(args.static_script);
+    }
+
+    #[test]
+    fn completion_scripts_render_for_dynamic_and_static_modes() {
+        for static_script in [false, true] {
+            let mut buf = Vec::new();
+            write_completion_script(CompletionShell::Bash, static_script, &mut buf).unwrap();
+            let script = String::from_utf8(buf).unwrap();
+            assert!(script.contains("sbh"), "static={static_script}: {script}");
+        }
+    }
+
+    #[test]
+    fn config_dot_keys_lists_settable_leaves() {
+        let value = serde_json::to_value(Config::default()).unwrap();
+        let keys = config_dot_keys(&value);
+        assert!(keys.contains(&"policy.initial_mode".to_string()));
+        assert!(keys.contains(&"pressure.prediction.enabled".to_string()));
+        assert!(keys.contains(&"scanner.root_paths".to_string()));
+        assert!(!keys.contains(&"ballast.overrides".to_string()));
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]), "keys must be sorted");
+    }
+
     #[test]
     fn output_mode_resolution_honors_precedence() {
         assert_eq!(
@@ -5885,6 +13645,14 @@ mod tests {
         assert_eq!(resolve_output_mode(false, None, false), OutputMode::Json);
     }
 
+    #[test]
+    fn json_stream_implies_json_output() {
+        let cli = Cli::try_parse_from(["sbh", "scan", "--json-stream"]).unwrap();
+        assert!(cli.command.streams_json());
+        let cli = Cli::try_parse_from(["sbh", "clean", "--dry-run"]).unwrap();
+        assert!(!cli.command.streams_json());
+    }
+
     #[test]
     fn parse_window_duration_valid_inputs() {
         let cases = [
@@ -5912,6 +13680,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn incident_levels_match_named_level_and_worse() {
+        assert_eq!(incident_levels("last-red").unwrap(), ["Red", "Critical"]);
+        assert_eq!(incident_levels("LAST").unwrap().len(), 3);
+        assert_eq!(incident_levels("last-critical").unwrap(), ["Critical"]);
+        assert!(matches!(
+            incident_levels("yesterday"),
+            Err(CliError::User(_))
+        ));
+    }
+
     #[test]
     fn parse_window_duration_rejects_invalid() {
         assert!(parse_window_duration("").is_err());
@@ -5919,6 +13698,67 @@ mod tests {
         assert!(parse_window_duration("10x").is_err());
     }
 
+    #[test]
+    fn age_window_bounds_candidate_ages() {
+        let hour = std::time::Duration::from_secs(3600);
+        let window = AgeWindow::from_args(Some("1d"), Some("7d")).unwrap();
+        assert!(!window.contains(hour));
+        assert!(window.contains(24 * hour));
+        assert!(window.contains(6 * 24 * hour));
+        assert!(!window.contains(7 * 24 * hour));
+        assert_eq!(
+            window.describe().as_deref(),
+            Some("older than 1d 0h and newer than 7d 0h")
+        );
+
+        let open = AgeWindow::from_args(None, None).unwrap();
+        assert!(open.contains(std::time::Duration::ZERO));
+        assert_eq!(open.describe(), None);
+
+        assert!(AgeWindow::from_args(Some("7d"), Some("1h")).is_err());
+        assert!(AgeWindow::from_args(Some("1h"), Some("1h")).is_err());
+        assert!(AgeWindow::from_args(Some("soon"), None).is_err());
+    }
+
+    #[test]
+    fn path_globs_filter_candidates_and_what_they_contain() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        for dir in [
+            "a/target/release",
+            "a/target/debug",
+            "b/target/debug",
+            "c/node_modules",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        let globs = PathGlobs::from_args(&[], &["**/release/**".to_string()]).unwrap();
+        assert!(!globs.admits(&root.join("a/target/release")));
+        assert!(
+            !globs.admits(&root.join("a/target")),
+            "deleting it takes release along"
+        );
+        assert!(globs.admits(&root.join("a/target/debug")));
+        assert!(globs.admits(&root.join("b/target")));
+        assert_eq!(
+            globs.describe().as_deref(),
+            Some("not matching **/release/**")
+        );
+
+        let globs = PathGlobs::from_args(&["target".to_string()], &["b".to_string()]).unwrap();
+        assert!(globs.admits(&root.join("a/target")));
+        assert!(globs.admits(&root.join("a/target/debug")));
+        assert!(!globs.admits(&root.join("b/target")));
+        assert!(!globs.admits(&root.join("c/node_modules")));
+        assert_eq!(
+            globs.to_json(),
+            json!({"include": ["**/target"], "exclude": ["**/b"]})
+        );
+
+        assert!(PathGlobs::default().admits(&root.join("c")));
+    }
+
     #[test]
     fn stats_command_parses_with_all_flags() {
         let cases = [
@@ -5927,6 +13767,9 @@ mod tests {
             vec!["sbh", "stats", "--top-patterns", "10"],
             vec!["sbh", "stats", "--top-deletions", "5"],
             vec!["sbh", "stats", "--pressure-history"],
+            vec!["sbh", "stats", "--by-user", "--window", "7d"],
+            vec!["sbh", "stats", "--group-by", "day", "--window", "7d"],
+            vec!["sbh", "stats", "--group-by", "Category"],
             vec![
                 "sbh",
                 "stats",
@@ -5943,6 +13786,7 @@ mod tests {
             let parsed = Cli::try_parse_from(case.iter().copied());
             assert!(parsed.is_ok(), "failed to parse stats case: {case:?}");
         }
+        assert!(Cli::try_parse_from(["sbh", "stats", "--group-by", "week"]).is_err());
     }
 
     #[test]
@@ -5951,13 +13795,24 @@ mod tests {
             vec!["sbh", "tune"],
             vec!["sbh", "tune", "--apply"],
             vec!["sbh", "tune", "--apply", "--yes"],
+            vec!["sbh", "tune", "--apply", "--yes", "--revert-window", "0"],
+            vec![
+                "sbh",
+                "config",
+                "set",
+                "scoring.min_score",
+                "0.8",
+                "--revert-window",
+                "60",
+            ],
         ];
         for case in &cases {
             let parsed = Cli::try_parse_from(case.iter().copied());
             assert!(parsed.is_ok(), "failed to parse tune case: {case:?}");
         }
-        // --yes without --apply should fail.
+        // --yes and --revert-window without --apply should fail.
         assert!(Cli::try_parse_from(["sbh", "tune", "--yes"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "tune", "--revert-window", "5"]).is_err());
     }
 
     #[test]
@@ -6042,6 +13897,7 @@ mod tests {
                 avg_score: 0.85,
                 avg_age_hours: 1.0,
                 failures: 5,
+                top_categories: Vec::new(),
             },
             ballast: BallastStats::default(),
             pressure: PressureStats::default(),
@@ -6203,6 +14059,17 @@ mod tests {
         assert_eq!(bytes_to_pct(50, 200), 25.0);
     }
 
+    #[test]
+    fn coverage_goal_blames_protection_only_when_it_decides() {
+        assert_eq!(CoverageGoal::assess(0, 0, 0), CoverageGoal::Met);
+        assert_eq!(CoverageGoal::assess(10, 10, 0), CoverageGoal::Reachable);
+        assert_eq!(
+            CoverageGoal::assess(10, 4, 6),
+            CoverageGoal::BlockedByProtection
+        );
+        assert_eq!(CoverageGoal::assess(10, 4, 5), CoverageGoal::Unreachable);
+    }
+
     #[test]
     fn swap_thrash_risk_requires_high_swap_and_low_ram() {
         // High swap + ample RAM → NOT risky (cold pages swapped, normal).
@@ -2,16 +2,27 @@
 
 #![allow(missing_docs)]
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use chrono::{NaiveTime, Timelike};
 use serde::{Deserialize, Serialize};
 
+use crate::core::container;
 use crate::core::errors::{Result, SbhError};
-use crate::daemon::notifications::NotificationConfig;
+use crate::core::schedule::CronSchedule;
+use crate::daemon::coredump::CoredumpConfig;
+use crate::daemon::notifications::{CHANNEL_NAMES, EVENT_TYPES, NotificationConfig};
 use crate::daemon::policy::PolicyConfig;
+use crate::logger::diag;
+use crate::scanner::archive::Relocation;
+use crate::scanner::io_throttle::IoThrottle;
+use crate::scanner::object_store::ObjectStore;
+use crate::scanner::patterns::ArtifactCategory;
+use crate::scanner::walker::DeviceLimits;
 
 /// Full SBH configuration model.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -28,6 +39,16 @@ pub struct Config {
     pub notifications: NotificationConfig,
     pub dashboard: DashboardConfig,
     pub policy: PolicyConfig,
+    pub coredumps: CoredumpConfig,
+    pub caches: CachesConfig,
+    pub mounts: MountsConfig,
+    pub watchdog: WatchdogConfig,
+    pub costs: CostsConfig,
+    pub dbus: DbusConfig,
+    pub archive: ArchiveConfig,
+    pub control: ControlConfig,
+    pub quarantine: QuarantineConfig,
+    pub logging: LoggingConfig,
 }
 
 /// Pressure thresholds and control knobs.
@@ -41,6 +62,156 @@ pub struct PressureConfig {
     pub poll_interval_ms: u64,
     /// Predictive pre-emption settings.
     pub prediction: PredictionConfig,
+    /// Per-mount overrides (`[[pressure.mounts]]`); the entry with the
+    /// longest path covering a mount wins.
+    pub mounts: Vec<PressureMountOverride>,
+    /// Time-of-day threshold profiles (`[[pressure.profiles]]`); the first
+    /// entry whose window covers the local time wins.
+    pub profiles: Vec<PressureProfile>,
+    /// Minutes over which thresholds move into and out of a profile, so a
+    /// window opening or closing does not change the levels in one step.
+    /// Capped at half the window.
+    pub profile_transition_minutes: u64,
+}
+
+/// One `[[pressure.mounts]]` entry. Unset fields inherit `[pressure]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PressureMountOverride {
+    /// Mount point (or a directory above nested mounts) the entry covers.
+    pub path: PathBuf,
+    pub green_min_free_pct: Option<f64>,
+    pub yellow_min_free_pct: Option<f64>,
+    pub orange_min_free_pct: Option<f64>,
+    pub red_min_free_pct: Option<f64>,
+    pub poll_interval_ms: Option<u64>,
+    /// Provision a ballast pool on this mount; `false` keeps it bare.
+    pub ballast: Option<bool>,
+    /// Where this mount's pool lives instead of `<mount>/.sbh/ballast`.
+    /// Must be on the same mount.
+    pub ballast_dir: Option<PathBuf>,
+}
+
+/// One `[[pressure.profiles]]` entry: thresholds for a daily window of local
+/// time. Unset fields keep the mount's usual value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PressureProfile {
+    /// Shown by `sbh status` while the profile is in force.
+    pub name: String,
+    /// Window start, `HH:MM` local time.
+    pub start: String,
+    /// Window end, `HH:MM` local time; earlier than `start` wraps past midnight.
+    pub end: String,
+    pub green_min_free_pct: Option<f64>,
+    pub yellow_min_free_pct: Option<f64>,
+    pub orange_min_free_pct: Option<f64>,
+    pub red_min_free_pct: Option<f64>,
+}
+
+impl PressureProfile {
+    /// Parsed start and end times; `None` if either is not `HH:MM`.
+    #[must_use]
+    pub fn window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let parse = |raw: &str| NaiveTime::parse_from_str(raw.trim(), "%H:%M").ok();
+        Some((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    /// Move `base` towards this profile's thresholds by `weight` in `[0, 1]`.
+    fn blend(&self, base: &mut PressureConfig, weight: f64) {
+        for (target, level) in [
+            (self.green_min_free_pct, &mut base.green_min_free_pct),
+            (self.yellow_min_free_pct, &mut base.yellow_min_free_pct),
+            (self.orange_min_free_pct, &mut base.orange_min_free_pct),
+            (self.red_min_free_pct, &mut base.red_min_free_pct),
+        ] {
+            if let Some(target) = target {
+                *level += (target - *level) * weight;
+            }
+        }
+    }
+}
+
+impl PressureConfig {
+    /// The profile in force at local time `now` and how far thresholds have
+    /// moved to it: 1.0 inside the window, ramping from 0.0 after it opens
+    /// and back to 0.0 as it closes over `profile_transition_minutes`.
+    #[must_use]
+    pub fn active_profile(&self, now: NaiveTime) -> Option<(&PressureProfile, f64)> {
+        const DAY_SECS: u32 = 24 * 60 * 60;
+        let now = now.num_seconds_from_midnight();
+        self.profiles.iter().find_map(|profile| {
+            let (start, end) = profile.window()?;
+            let start = start.num_seconds_from_midnight();
+            let length = (end.num_seconds_from_midnight() + DAY_SECS - start) % DAY_SECS;
+            let elapsed = (now + DAY_SECS - start) % DAY_SECS;
+            if length == 0 || elapsed >= length {
+                return None;
+            }
+            let ramp = self
+                .profile_transition_minutes
+                .saturating_mul(60)
+                .min(u64::from(length / 2));
+            if ramp == 0 {
+                return Some((profile, 1.0));
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let ramp = ramp as f64;
+            let edge = f64::from(elapsed.min(length - elapsed));
+            Some((profile, (edge / ramp).min(1.0)))
+        })
+    }
+
+    /// The `[[pressure.mounts]]` entry covering `mount` with the longest path.
+    #[must_use]
+    pub fn mount_override(&self, mount: &Path) -> Option<&PressureMountOverride> {
+        self.mounts
+            .iter()
+            .filter(|entry| mount.starts_with(&entry.path))
+            .max_by_key(|entry| entry.path.components().count())
+    }
+
+    /// Thresholds and poll interval for `mount`, with any
+    /// `[[pressure.mounts]]` override applied but no time-of-day profile.
+    /// The result carries no per-mount entries or profiles of its own.
+    #[must_use]
+    pub fn for_mount(&self, mount: &Path) -> Self {
+        let mut resolved = Self {
+            mounts: Vec::new(),
+            profiles: Vec::new(),
+            ..self.clone()
+        };
+        if let Some(entry) = self.mount_override(mount) {
+            resolved.green_min_free_pct =
+                entry.green_min_free_pct.unwrap_or(self.green_min_free_pct);
+            resolved.yellow_min_free_pct = entry
+                .yellow_min_free_pct
+                .unwrap_or(self.yellow_min_free_pct);
+            resolved.orange_min_free_pct = entry
+                .orange_min_free_pct
+                .unwrap_or(self.orange_min_free_pct);
+            resolved.red_min_free_pct = entry.red_min_free_pct.unwrap_or(self.red_min_free_pct);
+            resolved.poll_interval_ms = entry.poll_interval_ms.unwrap_or(self.poll_interval_ms);
+        }
+        resolved
+    }
+
+    /// [`Self::for_mount`] with the profile active at local time `now`
+    /// blended in.
+    #[must_use]
+    pub fn for_mount_at(&self, mount: &Path, now: NaiveTime) -> Self {
+        let mut resolved = self.for_mount(mount);
+        if let Some((profile, weight)) = self.active_profile(now) {
+            profile.blend(&mut resolved, weight);
+        }
+        resolved
+    }
+
+    /// Thresholds in force for `mount` right now.
+    #[must_use]
+    pub fn for_mount_now(&self, mount: &Path) -> Self {
+        self.for_mount_at(mount, chrono::Local::now().time())
+    }
 }
 
 /// Knobs for predictive pre-emptive action (EWMA → graduated response).
@@ -64,8 +235,9 @@ pub struct PredictionConfig {
 }
 
 /// Scanner behavior and safety constraints.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ScannerConfig {
     pub root_paths: Vec<PathBuf>,
     pub excluded_paths: Vec<PathBuf>,
@@ -77,10 +249,489 @@ pub struct ScannerConfig {
     pub cross_devices: bool,
     pub dry_run: bool,
     pub max_delete_batch: usize,
+    /// Threads deleting a batch at once. Spare threads split a single large
+    /// directory tree. 1 deletes serially.
+    pub delete_workers: usize,
     pub repeat_deletion_base_cooldown_secs: u64,
     pub repeat_deletion_max_cooldown_secs: u64,
+    /// Seconds after an automated cleanup pass on a mount before the daemon
+    /// starts another there below critical pressure; ballast is released
+    /// one level harder in the meantime. 0 disables the cooldown.
+    pub mount_cooldown_secs: u64,
     /// Maximum wall-clock seconds for a single scan pass. 0 = use built-in default.
     pub scan_time_budget_secs: u64,
+    /// Regular files at least this large are scored individually (core dumps,
+    /// VM images, huge logs). 0 disables large-file discovery.
+    pub large_file_min_bytes: u64,
+    /// Stop descending into directories that classify confidently as
+    /// artifacts (`node_modules/`, a cargo profile dir) and size them whole.
+    pub artifact_root_cutoff: bool,
+    /// Entries under a classified root counted exactly before its size is
+    /// extrapolated from a sample. 0 always counts exactly.
+    pub artifact_root_sample_after: u64,
+    /// Truncate held-open files in `truncate_categories` to zero bytes
+    /// instead of skipping them. Off by default: the contents are lost.
+    pub truncate_open_files: bool,
+    /// Categories eligible for truncation (`log_file`, `core_dump`).
+    pub truncate_categories: Vec<String>,
+    /// Walker threads for a root on a spinning disk (sysfs
+    /// `queue/rotational`). 0 leaves them at full parallelism.
+    pub rotational_parallelism: usize,
+    /// Walker thread caps for roots under these paths, overriding detection.
+    pub device_parallelism: BTreeMap<PathBuf, usize>,
+    /// Keep a recursive inotify/FSEvents watch on the roots and revisit only
+    /// changed directories between full walks.
+    pub watch: bool,
+    /// With `watch`: seconds between full walks that re-verify the
+    /// event-maintained candidate set. 0 = every pass walks in full.
+    pub watch_full_rescan_secs: u64,
+    /// Pause deletion while tasks spend more than this share of wall time,
+    /// in percent, stalled on IO (Linux PSI). 0 disables pacing.
+    pub io_throttle_stall_pct: f64,
+    /// Milliseconds between pressure re-checks while paused.
+    pub io_throttle_pause_ms: u64,
+    /// Longest one candidate's removal may be held up, in milliseconds.
+    pub io_throttle_max_wait_ms: u64,
+    /// Cron expression (local time) for full-depth deep scans of every root,
+    /// e.g. `"0 3 * * *"`. Empty disables them; while set, routine scans
+    /// below red pressure stay shallow and cover only `hot_paths`.
+    pub deep_scan_schedule: String,
+    /// Minimum age for deep-scan candidates; never stricter than
+    /// `min_file_age_minutes`.
+    pub deep_scan_min_file_age_minutes: u64,
+    /// Roots routine scans cover between deep scans. Empty keeps
+    /// `root_paths`.
+    pub hot_paths: Vec<PathBuf>,
+    /// Depth limit for routine scans between deep scans.
+    pub shallow_max_depth: usize,
+}
+
+impl ScannerConfig {
+    /// Categories the deletion executor may truncate when a candidate is
+    /// held open. Empty unless `truncate_open_files` is set.
+    #[must_use]
+    pub fn truncate_allowlist(&self) -> Vec<ArtifactCategory> {
+        if !self.truncate_open_files {
+            return Vec::new();
+        }
+        self.truncate_categories
+            .iter()
+            .filter_map(|name| ArtifactCategory::from_config_name(name))
+            .filter(|category| category.supports_truncation())
+            .collect()
+    }
+
+    /// IO pacing for the deletion executor, or `None` when disabled.
+    #[must_use]
+    pub fn io_throttle(&self) -> Option<IoThrottle> {
+        (self.io_throttle_stall_pct > 0.0).then(|| IoThrottle {
+            stall_pct: self.io_throttle_stall_pct,
+            pause: Duration::from_millis(self.io_throttle_pause_ms),
+            max_wait: Duration::from_millis(self.io_throttle_max_wait_ms),
+        })
+    }
+
+    /// Sampling budget for the walker's classified-root cutoff, or `None`
+    /// when the cutoff is off.
+    #[must_use]
+    pub const fn artifact_root_sampling(&self) -> Option<u64> {
+        if self.artifact_root_cutoff {
+            Some(self.artifact_root_sample_after)
+        } else {
+            None
+        }
+    }
+
+    /// The deep-scan schedule, or `None` when deep scans are off. The
+    /// expression was checked at load, so a parse failure reads as off.
+    #[must_use]
+    pub fn deep_scan_schedule(&self) -> Option<CronSchedule> {
+        if self.deep_scan_schedule.trim().is_empty() {
+            return None;
+        }
+        CronSchedule::parse(&self.deep_scan_schedule).ok()
+    }
+
+    /// Per-device walker thread caps.
+    #[must_use]
+    pub fn device_limits(&self) -> DeviceLimits {
+        DeviceLimits {
+            rotational: (self.rotational_parallelism > 0).then_some(self.rotational_parallelism),
+            overrides: self
+                .device_parallelism
+                .iter()
+                .map(|(path, threads)| (path.clone(), *threads))
+                .collect(),
+        }
+    }
+}
+
+/// `sbh clean --caches` settings: per-tool opt-outs for native pruning.
+///
+/// When enabled and the tool is on `PATH`, its own garbage collector runs
+/// instead of sbh deleting cache contents directly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CachesConfig {
+    /// `cargo cache --autoclean` (needs `cargo-cache`).
+    pub native_cargo: bool,
+    /// `npm cache clean --force`.
+    pub native_npm: bool,
+    /// `pnpm store prune`.
+    pub native_pnpm: bool,
+}
+
+impl Default for CachesConfig {
+    fn default() -> Self {
+        Self {
+            native_cargo: true,
+            native_npm: true,
+            native_pnpm: true,
+        }
+    }
+}
+
+impl CachesConfig {
+    /// Whether native pruning is enabled for `tool`. Tools without a flag
+    /// have no native prune.
+    #[must_use]
+    pub fn native_enabled(&self, tool: &str) -> bool {
+        match tool {
+            "cargo" => self.native_cargo,
+            "npm" => self.native_npm,
+            "pnpm" => self.native_pnpm,
+            _ => false,
+        }
+    }
+}
+
+/// Removable and transient mounts (USB drives, sshfs, rclone).
+///
+/// A mount is removable when sysfs marks its block device removable or
+/// USB-attached, and transient when its filesystem type or path is listed
+/// here. `permanent_paths` overrides both.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MountsConfig {
+    /// Let the daemon delete candidates on removable or transient mounts.
+    pub auto_delete_removable: bool,
+    /// Leave removable and transient mounts out of pressure aggregation.
+    pub exclude_removable_from_pressure: bool,
+    /// Filesystem types treated as transient.
+    pub transient_fs_types: Vec<String>,
+    /// Mount points treated as transient, along with mounts beneath them.
+    pub transient_paths: Vec<PathBuf>,
+    /// Mount points always treated as permanent.
+    pub permanent_paths: Vec<PathBuf>,
+}
+
+impl Default for MountsConfig {
+    fn default() -> Self {
+        Self {
+            auto_delete_removable: false,
+            exclude_removable_from_pressure: false,
+            transient_fs_types: [
+                "fuse.sshfs",
+                "fuse.rclone",
+                "fuse.s3fs",
+                "fuse.gcsfuse",
+                "davfs",
+            ]
+            .map(String::from)
+            .to_vec(),
+            transient_paths: vec![PathBuf::from("/media"), PathBuf::from("/run/media")],
+            permanent_paths: Vec::new(),
+        }
+    }
+}
+
+/// `[costs]`: what it costs to regenerate an artifact, keyed by category
+/// (`rust_target = 20.0`) or pattern name (`bazel-output-base = 0.5`).
+///
+/// Values share the unit of `scoring.false_positive_loss` (CPU minutes work
+/// well), which stays the cost of anything unlisted. Wrongly deleting a
+/// listed artifact costs its entry instead, and entries below the flat loss
+/// mark it as cheap to re-fetch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct CostsConfig(pub BTreeMap<String, f64>);
+
+impl CostsConfig {
+    /// Configured cost for a classified artifact: a pattern-name entry wins
+    /// over its category's.
+    #[must_use]
+    pub fn regeneration_cost(&self, pattern_name: &str, category: ArtifactCategory) -> Option<f64> {
+        self.0
+            .get(pattern_name)
+            .or_else(|| self.0.get(category.config_name()))
+            .copied()
+    }
+}
+
+/// In-process stall watchdog for the daemon main loop. Independent of the
+/// systemd watchdog (`--watchdog-sec`), so a wedged daemon recovers without
+/// a service manager too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    /// Poll intervals without a main-loop tick before the loop counts as
+    /// stalled (never less than 30 seconds).
+    pub stall_intervals: u64,
+    /// Watchdog restarts (re-execs) allowed per hour before the daemon exits.
+    pub max_restarts: usize,
+    /// RSS the daemon tries to stay under by shedding internal caches
+    /// (0 = never shed).
+    pub memory_budget_mb: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stall_intervals: 60,
+            max_restarts: 2,
+            memory_budget_mb: 256,
+        }
+    }
+}
+
+/// DBus service for desktop integration (Linux, `dbus` feature).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DbusConfig {
+    /// Register `org.sbh.Daemon` when the daemon starts.
+    pub enabled: bool,
+    /// Bus to register on: the system bus for a system service, the session
+    /// bus for a per-user one.
+    pub bus: DbusBus,
+}
+
+/// Which message bus the DBus service connects to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DbusBus {
+    #[default]
+    System,
+    Session,
+}
+
+/// Archival tiering: move cold artifacts to a slower filesystem instead of
+/// deleting them, so `sbh restore` can bring them back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// Relocate candidates in `categories` instead of deleting them.
+    pub enabled: bool,
+    /// Cold-storage directory. Must be absolute and, without `s3.bucket`,
+    /// on another filesystem; with a bucket it only holds the index.
+    pub path: PathBuf,
+    /// Categories to relocate (`rust_target`, `vm_image`, ...); everything
+    /// else is deleted as usual.
+    pub categories: Vec<String>,
+    /// Stop relocating once the archive filesystem would drop below this
+    /// free percentage; those candidates then fail instead of being deleted.
+    pub min_free_pct: f64,
+    /// Optional S3-compatible bucket for the archived trees.
+    pub s3: S3Config,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: PathBuf::new(),
+            categories: Vec::new(),
+            min_free_pct: 10.0,
+            s3: S3Config::default(),
+        }
+    }
+}
+
+/// `[archive.s3]` / `[quarantine.s3]`: upload trees to a bucket.
+///
+/// Credentials come from `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`,
+/// never from the config file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct S3Config {
+    /// Bucket name; empty keeps the trees local.
+    pub bucket: String,
+    pub endpoint: String,
+    pub region: String,
+    /// Key prefix for the tarballs; point the lifecycle rule at it. Empty
+    /// uses `sbh-archive/` for the archive and `sbh-quarantine/` for the
+    /// quarantine.
+    pub prefix: String,
+    /// Days the bucket lifecycle rule keeps an object (0 = no rule).
+    pub retention_days: u32,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            bucket: String::new(),
+            endpoint: "https://s3.amazonaws.com".to_string(),
+            region: "us-east-1".to_string(),
+            prefix: String::new(),
+            retention_days: 30,
+        }
+    }
+}
+
+impl S3Config {
+    /// The bucket to upload to, keyed under `default_prefix` unless a
+    /// prefix is set; `None` when no bucket is configured.
+    #[must_use]
+    pub fn object_store(&self, default_prefix: &str) -> Option<ObjectStore> {
+        (!self.bucket.is_empty()).then(|| ObjectStore {
+            endpoint: self.endpoint.clone(),
+            bucket: self.bucket.clone(),
+            prefix: if self.prefix.is_empty() {
+                default_prefix.to_string()
+            } else {
+                self.prefix.clone()
+            },
+            region: self.region.clone(),
+            retention_days: self.retention_days,
+        })
+    }
+
+    fn validate(&self, section: &str) -> Result<()> {
+        if self.bucket.is_empty() {
+            return Ok(());
+        }
+        if !(self.endpoint.starts_with("https://") || self.endpoint.starts_with("http://")) {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "{section}.endpoint must be an http(s) URL, got {:?}",
+                    self.endpoint
+                ),
+            });
+        }
+        if self.bucket.contains('/') || self.region.is_empty() {
+            return Err(SbhError::InvalidConfig {
+                details: format!("{section} needs a bucket name without '/' and a region"),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ArchiveConfig {
+    /// Relocation settings for the deletion executor; `None` when disabled.
+    #[must_use]
+    pub fn relocation(&self) -> Option<Relocation> {
+        let categories: Vec<ArtifactCategory> = self
+            .categories
+            .iter()
+            .filter_map(|name| ArtifactCategory::from_config_name(name))
+            .collect();
+        (self.enabled && !self.path.as_os_str().is_empty() && !categories.is_empty()).then(|| {
+            Relocation {
+                archive_root: self.path.clone(),
+                categories,
+                min_free_pct: self.min_free_pct,
+                object_store: self.s3.object_store("sbh-archive/"),
+            }
+        })
+    }
+}
+
+/// Unix control socket the CLI uses to hand commands to a running daemon.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ControlConfig {
+    /// Serve the socket while the daemon runs.
+    pub enabled: bool,
+    /// Socket path; empty picks `$XDG_RUNTIME_DIR/sbh/control.sock`, or
+    /// `/run/sbh/control.sock` when no runtime directory is set.
+    pub socket_path: PathBuf,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            socket_path: PathBuf::new(),
+        }
+    }
+}
+
+impl ControlConfig {
+    /// Socket the daemon listens on and the CLI connects to.
+    #[must_use]
+    pub fn socket_path(&self) -> PathBuf {
+        if self.socket_path.as_os_str().is_empty() {
+            if container::is_enabled() {
+                return Path::new(container::CONTAINER_DATA_DIR).join("control.sock");
+            }
+            default_control_socket(env::var_os("XDG_RUNTIME_DIR").as_deref())
+        } else {
+            self.socket_path.clone()
+        }
+    }
+}
+
+/// `sbh clean --quarantine`: how long quarantined artifacts are kept.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct QuarantineConfig {
+    /// Hours before the daemon purges a quarantined artifact.
+    pub ttl_hours: u64,
+    /// Optional S3-compatible bucket that quarantined trees are offloaded
+    /// to, so they stop using local disk.
+    pub s3: S3Config,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            ttl_hours: 72,
+            s3: S3Config::default(),
+        }
+    }
+}
+
+impl QuarantineConfig {
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_hours.saturating_mul(3600))
+    }
+
+    /// Bucket quarantined trees are offloaded to; `None` keeps them local.
+    #[must_use]
+    pub fn offload(&self) -> Option<ObjectStore> {
+        self.s3.object_store("sbh-quarantine/")
+    }
+}
+
+/// Daemon diagnostics on stderr (the journal under systemd). The global
+/// `--log-level` and `--log-format` flags override these.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Least severe level written.
+    pub level: diag::DiagLevel,
+    /// `human` lines or one JSON object per line.
+    pub format: diag::DiagFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: diag::DiagLevel::Info,
+            format: diag::DiagFormat::Human,
+        }
+    }
+}
+
+fn default_control_socket(runtime_dir: Option<&std::ffi::OsStr>) -> PathBuf {
+    runtime_dir
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from("/run"), PathBuf::from)
+        .join("sbh")
+        .join("control.sock")
 }
 
 /// Multi-factor score weights and decision-theoretic losses.
@@ -96,6 +747,10 @@ pub struct ScoringConfig {
     pub false_positive_loss: f64,
     pub false_negative_loss: f64,
     pub calibration_floor: f64,
+    /// Consecutive scan passes a path must qualify before the daemon deletes
+    /// it. 1 acts on the first qualifying pass. Red/critical pressure bypasses
+    /// the wait.
+    pub stability_scans: usize,
 }
 
 /// Ballast allocation settings.
@@ -107,10 +762,38 @@ pub struct BallastConfig {
     pub replenish_cooldown_minutes: u64,
     /// Automatically provision ballast pools on each monitored volume.
     pub auto_provision: bool,
+    /// Only replenish when the mount is forecast to stay above the yellow
+    /// threshold for this many minutes at its current fill rate (0 disables
+    /// the forecast).
+    pub replenish_forecast_minutes: u64,
+    /// Recreate one file per replenish interval; when false, recreate as
+    /// many files as the forecast allows at once.
+    pub replenish_incremental: bool,
     /// Per-volume overrides keyed by mount-point path (e.g., "/data").
     /// Uses BTreeMap for stable ordering in hash generation.
     #[serde(default)]
     pub overrides: BTreeMap<String, BallastVolumeOverride>,
+    /// Explicitly placed pools (`[[ballast.pools]]`), provisioned whether or
+    /// not a watched path lives on their filesystem.
+    #[serde(default)]
+    pub pools: Vec<BallastPoolConfig>,
+}
+
+/// An explicitly placed ballast pool.
+///
+/// The pool protects whichever filesystem `path` lives on, one pool per
+/// filesystem. Per-volume overrides do not apply to it; unset sizes fall
+/// back to `ballast.file_count` and `ballast.file_size_bytes`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BallastPoolConfig {
+    /// Directory holding this pool's ballast files.
+    pub path: PathBuf,
+    /// File count for this pool.
+    #[serde(default)]
+    pub file_count: Option<usize>,
+    /// File size in bytes for this pool.
+    #[serde(default)]
+    pub file_size_bytes: Option<u64>,
 }
 
 /// Per-volume override for ballast pool settings.
@@ -123,6 +806,8 @@ pub struct BallastVolumeOverride {
     pub file_count: Option<usize>,
     /// Override file size in bytes for this volume.
     pub file_size_bytes: Option<u64>,
+    /// Pool directory for this volume instead of `<mount>/.sbh/ballast`.
+    pub ballast_dir: Option<PathBuf>,
 }
 
 impl Default for BallastVolumeOverride {
@@ -131,6 +816,7 @@ impl Default for BallastVolumeOverride {
             enabled: true,
             file_count: None,
             file_size_bytes: None,
+            ballast_dir: None,
         }
     }
 }
@@ -162,6 +848,46 @@ impl BallastConfig {
         let key = strip_trailing_separator(mount_path);
         self.overrides.get(key).is_none_or(|o| o.enabled)
     }
+
+    /// Pool directory override for a given mount point, if one is configured.
+    #[must_use]
+    pub fn effective_ballast_dir(&self, mount_path: &str) -> Option<&Path> {
+        let key = strip_trailing_separator(mount_path);
+        self.overrides
+            .get(key)
+            .and_then(|o| o.ballast_dir.as_deref())
+    }
+
+    /// Settings for the single pool in `dir` on `mount_path`. An explicit
+    /// `[[ballast.pools]]` entry for `dir` takes its own sizes; any other
+    /// pool gets the volume's effective sizes.
+    #[must_use]
+    pub fn pool_settings(&self, mount_path: &str, dir: &Path) -> Self {
+        let (file_count, file_size_bytes) = self.pools.iter().find(|p| p.path == dir).map_or_else(
+            || {
+                (
+                    self.effective_file_count(mount_path),
+                    self.effective_file_size_bytes(mount_path),
+                )
+            },
+            |pool| {
+                (
+                    pool.file_count.unwrap_or(self.file_count),
+                    pool.file_size_bytes.unwrap_or(self.file_size_bytes),
+                )
+            },
+        );
+        Self {
+            file_count,
+            file_size_bytes,
+            replenish_cooldown_minutes: self.replenish_cooldown_minutes,
+            auto_provision: self.auto_provision,
+            replenish_forecast_minutes: self.replenish_forecast_minutes,
+            replenish_incremental: self.replenish_incremental,
+            overrides: BTreeMap::new(),
+            pools: Vec::new(),
+        }
+    }
 }
 
 /// Tuning knobs for the VOI scan scheduler.
@@ -215,6 +941,16 @@ impl Default for VoiConfig {
 #[serde(default)]
 pub struct TelemetryConfig {
     pub fs_cache_ttl_ms: u64,
+    /// Per-mount `statvfs` timeout before the mount is reported stale (0 = wait).
+    pub fs_stats_timeout_ms: u64,
+    /// Alert once a watched mount has been stale this long.
+    pub stale_mount_alert_secs: u64,
+    /// How often the daemon samples per-process write counters for incident
+    /// blame (0 = off).
+    pub attribution_sample_secs: u64,
+    /// How often the daemon probes SMART/kernel health of the disks behind
+    /// watched mounts (0 = off).
+    pub disk_health_interval_secs: u64,
     pub ewma_base_alpha: f64,
     pub ewma_min_alpha: f64,
     pub ewma_max_alpha: f64,
@@ -319,6 +1055,9 @@ impl Default for PressureConfig {
             red_min_free_pct: 6.0,
             poll_interval_ms: 1_000,
             prediction: PredictionConfig::default(),
+            mounts: Vec::new(),
+            profiles: Vec::new(),
+            profile_transition_minutes: 30,
         }
     }
 }
@@ -339,15 +1078,20 @@ impl Default for PredictionConfig {
 
 impl Default for ScannerConfig {
     fn default() -> Self {
-        Self {
-            root_paths: vec![
+        let root_paths = if container::is_enabled() {
+            vec![PathBuf::from(container::CONTAINER_WATCH_ROOT)]
+        } else {
+            vec![
                 PathBuf::from("/data/projects"),
                 PathBuf::from("/tmp"),
                 PathBuf::from("/data/tmp"),
                 PathBuf::from("/var/tmp"),
                 PathBuf::from("/home"),
                 PathBuf::from("/root"),
-            ],
+            ]
+        };
+        Self {
+            root_paths,
             excluded_paths: vec![
                 PathBuf::from("/"),
                 PathBuf::from("/boot"),
@@ -368,9 +1112,27 @@ impl Default for ScannerConfig {
             cross_devices: false,
             dry_run: false,
             max_delete_batch: 20,
+            delete_workers: 4,
             repeat_deletion_base_cooldown_secs: 300,
             repeat_deletion_max_cooldown_secs: 3600,
+            mount_cooldown_secs: 600,
             scan_time_budget_secs: 300,
+            large_file_min_bytes: 2 * 1024 * 1024 * 1024,
+            artifact_root_cutoff: true,
+            artifact_root_sample_after: 100_000,
+            truncate_open_files: false,
+            truncate_categories: vec!["log_file".to_string()],
+            rotational_parallelism: 2,
+            device_parallelism: BTreeMap::new(),
+            watch: false,
+            watch_full_rescan_secs: 3600,
+            io_throttle_stall_pct: 40.0,
+            io_throttle_pause_ms: 100,
+            io_throttle_max_wait_ms: 5_000,
+            deep_scan_schedule: String::new(),
+            deep_scan_min_file_age_minutes: 5,
+            hot_paths: Vec::new(),
+            shallow_max_depth: 3,
         }
     }
 }
@@ -387,6 +1149,7 @@ impl Default for ScoringConfig {
             false_positive_loss: 50.0,
             false_negative_loss: 30.0,
             calibration_floor: 0.40,
+            stability_scans: 1,
         }
     }
 }
@@ -398,7 +1161,10 @@ impl Default for BallastConfig {
             file_size_bytes: 1_073_741_824,
             replenish_cooldown_minutes: 30,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides: BTreeMap::new(),
+            pools: Vec::new(),
         }
     }
 }
@@ -407,6 +1173,10 @@ impl Default for TelemetryConfig {
     fn default() -> Self {
         Self {
             fs_cache_ttl_ms: 1_000,
+            fs_stats_timeout_ms: 2_000,
+            stale_mount_alert_secs: 60,
+            attribution_sample_secs: 15,
+            disk_health_interval_secs: 3_600,
             ewma_base_alpha: 0.30,
             ewma_min_alpha: 0.10,
             ewma_max_alpha: 0.75,
@@ -415,6 +1185,18 @@ impl Default for TelemetryConfig {
     }
 }
 
+impl TelemetryConfig {
+    /// `None` when stats timeouts are disabled.
+    #[must_use]
+    pub const fn fs_stats_timeout(&self) -> Option<Duration> {
+        if self.fs_stats_timeout_ms == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(self.fs_stats_timeout_ms))
+        }
+    }
+}
+
 impl Default for UpdateConfig {
     fn default() -> Self {
         let home_dir = env::var_os("HOME").map_or_else(
@@ -443,6 +1225,29 @@ impl Default for UpdateConfig {
 
 impl Default for PathsConfig {
     fn default() -> Self {
+        // Windows: config under %APPDATA%, data under %LOCALAPPDATA%.
+        if container::is_enabled() {
+            let data = PathBuf::from(container::CONTAINER_DATA_DIR);
+            return Self {
+                config_file: PathBuf::from(container::CONTAINER_CONFIG_FILE),
+                ballast_dir: data.join("ballast"),
+                state_file: data.join("state.json"),
+                sqlite_db: data.join("activity.sqlite3"),
+                jsonl_log: data.join("activity.jsonl"),
+            };
+        }
+        #[cfg(windows)]
+        if let (Some(roaming), Some(local)) = (env::var_os("APPDATA"), env::var_os("LOCALAPPDATA"))
+        {
+            let data = PathBuf::from(local).join("sbh");
+            return Self {
+                config_file: PathBuf::from(roaming).join("sbh").join("config.toml"),
+                ballast_dir: data.join("ballast"),
+                state_file: data.join("state.json"),
+                sqlite_db: data.join("activity.sqlite3"),
+                jsonl_log: data.join("activity.jsonl"),
+            };
+        }
         let home_dir = env::var_os("HOME").map_or_else(
             || {
                 eprintln!(
@@ -530,11 +1335,35 @@ impl Config {
                 path: effective_path,
             });
         } else {
+            diag::debug(
+                "config",
+                format_args!(
+                    "{} not found; using built-in defaults",
+                    effective_path.display()
+                ),
+            );
             Self::default()
         };
+        diag::debug(
+            "config",
+            format_args!("resolved config path {}", effective_path.display()),
+        );
 
         cfg.paths.config_file = effective_path;
         cfg.apply_env_overrides()?;
+        if container::is_enabled() {
+            // No system bus inside the image.
+            cfg.dbus.enabled = false;
+        }
+        cfg.normalize_paths();
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Parse and validate config file contents as [`Config::load`] does,
+    /// without environment overrides or a file path.
+    pub fn from_toml_str(raw: &str) -> Result<Self> {
+        let mut cfg: Self = toml::from_str(raw)?;
         cfg.normalize_paths();
         cfg.validate()?;
         Ok(cfg)
@@ -615,16 +1444,53 @@ impl Config {
         )?;
         set_env_usize("SBH_SCANNER_MAX_DEPTH", &mut self.scanner.max_depth)?;
         set_env_usize("SBH_SCANNER_PARALLELISM", &mut self.scanner.parallelism)?;
+        set_env_usize(
+            "SBH_SCANNER_ROTATIONAL_PARALLELISM",
+            &mut self.scanner.rotational_parallelism,
+        )?;
         set_env_bool(
             "SBH_SCANNER_FOLLOW_SYMLINKS",
             &mut self.scanner.follow_symlinks,
         )?;
         set_env_bool("SBH_SCANNER_CROSS_DEVICES", &mut self.scanner.cross_devices)?;
         set_env_bool("SBH_SCANNER_DRY_RUN", &mut self.scanner.dry_run)?;
+        set_env_bool("SBH_SCANNER_WATCH", &mut self.scanner.watch)?;
         set_env_usize(
             "SBH_SCANNER_MAX_DELETE_BATCH",
             &mut self.scanner.max_delete_batch,
         )?;
+        set_env_usize(
+            "SBH_SCANNER_DELETE_WORKERS",
+            &mut self.scanner.delete_workers,
+        )?;
+        set_env_u64(
+            "SBH_SCANNER_LARGE_FILE_MIN_BYTES",
+            &mut self.scanner.large_file_min_bytes,
+        )?;
+        set_env_bool(
+            "SBH_SCANNER_ARTIFACT_ROOT_CUTOFF",
+            &mut self.scanner.artifact_root_cutoff,
+        )?;
+        set_env_u64(
+            "SBH_SCANNER_ARTIFACT_ROOT_SAMPLE_AFTER",
+            &mut self.scanner.artifact_root_sample_after,
+        )?;
+        set_env_f64(
+            "SBH_SCANNER_IO_THROTTLE_STALL_PCT",
+            &mut self.scanner.io_throttle_stall_pct,
+        )?;
+        set_env_u64(
+            "SBH_SCANNER_IO_THROTTLE_PAUSE_MS",
+            &mut self.scanner.io_throttle_pause_ms,
+        )?;
+        set_env_u64(
+            "SBH_SCANNER_IO_THROTTLE_MAX_WAIT_MS",
+            &mut self.scanner.io_throttle_max_wait_ms,
+        )?;
+        set_env_bool(
+            "SBH_SCANNER_TRUNCATE_OPEN_FILES",
+            &mut self.scanner.truncate_open_files,
+        )?;
         set_env_u64(
             "SBH_SCANNER_REPEAT_DELETION_BASE_COOLDOWN_SECS",
             &mut self.scanner.repeat_deletion_base_cooldown_secs,
@@ -633,6 +1499,21 @@ impl Config {
             "SBH_SCANNER_REPEAT_DELETION_MAX_COOLDOWN_SECS",
             &mut self.scanner.repeat_deletion_max_cooldown_secs,
         )?;
+        set_env_u64(
+            "SBH_SCANNER_MOUNT_COOLDOWN_SECS",
+            &mut self.scanner.mount_cooldown_secs,
+        )?;
+        if let Some(raw) = env_var("SBH_SCANNER_DEEP_SCAN_SCHEDULE") {
+            self.scanner.deep_scan_schedule = raw;
+        }
+        set_env_u64(
+            "SBH_SCANNER_DEEP_SCAN_MIN_FILE_AGE_MINUTES",
+            &mut self.scanner.deep_scan_min_file_age_minutes,
+        )?;
+        set_env_usize(
+            "SBH_SCANNER_SHALLOW_MAX_DEPTH",
+            &mut self.scanner.shallow_max_depth,
+        )?;
 
         // scoring
         set_env_f64("SBH_SCORING_MIN_SCORE", &mut self.scoring.min_score)?;
@@ -659,12 +1540,32 @@ impl Config {
             "SBH_SCORING_CALIBRATION_FLOOR",
             &mut self.scoring.calibration_floor,
         )?;
+        set_env_usize(
+            "SBH_SCORING_STABILITY_SCANS",
+            &mut self.scoring.stability_scans,
+        )?;
 
         // telemetry
         set_env_u64(
             "SBH_TELEMETRY_FS_CACHE_TTL_MS",
             &mut self.telemetry.fs_cache_ttl_ms,
         )?;
+        set_env_u64(
+            "SBH_TELEMETRY_FS_STATS_TIMEOUT_MS",
+            &mut self.telemetry.fs_stats_timeout_ms,
+        )?;
+        set_env_u64(
+            "SBH_TELEMETRY_STALE_MOUNT_ALERT_SECS",
+            &mut self.telemetry.stale_mount_alert_secs,
+        )?;
+        set_env_u64(
+            "SBH_TELEMETRY_ATTRIBUTION_SAMPLE_SECS",
+            &mut self.telemetry.attribution_sample_secs,
+        )?;
+        set_env_u64(
+            "SBH_TELEMETRY_DISK_HEALTH_INTERVAL_SECS",
+            &mut self.telemetry.disk_health_interval_secs,
+        )?;
         set_env_f64(
             "SBH_TELEMETRY_EWMA_BASE_ALPHA",
             &mut self.telemetry.ewma_base_alpha,
@@ -699,6 +1600,76 @@ impl Config {
         // policy
         set_env_bool("SBH_POLICY_KILL_SWITCH", &mut self.policy.kill_switch)?;
 
+        // caches
+        set_env_bool("SBH_CACHES_NATIVE_CARGO", &mut self.caches.native_cargo)?;
+        set_env_bool("SBH_CACHES_NATIVE_NPM", &mut self.caches.native_npm)?;
+        set_env_bool("SBH_CACHES_NATIVE_PNPM", &mut self.caches.native_pnpm)?;
+
+        // mounts
+        set_env_bool(
+            "SBH_MOUNTS_AUTO_DELETE_REMOVABLE",
+            &mut self.mounts.auto_delete_removable,
+        )?;
+        set_env_bool(
+            "SBH_MOUNTS_EXCLUDE_REMOVABLE_FROM_PRESSURE",
+            &mut self.mounts.exclude_removable_from_pressure,
+        )?;
+
+        // watchdog
+        set_env_bool("SBH_WATCHDOG_ENABLED", &mut self.watchdog.enabled)?;
+        set_env_u64(
+            "SBH_WATCHDOG_STALL_INTERVALS",
+            &mut self.watchdog.stall_intervals,
+        )?;
+        set_env_usize("SBH_WATCHDOG_MAX_RESTARTS", &mut self.watchdog.max_restarts)?;
+        set_env_u64(
+            "SBH_WATCHDOG_MEMORY_BUDGET_MB",
+            &mut self.watchdog.memory_budget_mb,
+        )?;
+
+        // ballast
+        set_env_u64(
+            "SBH_BALLAST_REPLENISH_FORECAST_MINUTES",
+            &mut self.ballast.replenish_forecast_minutes,
+        )?;
+        set_env_bool(
+            "SBH_BALLAST_REPLENISH_INCREMENTAL",
+            &mut self.ballast.replenish_incremental,
+        )?;
+
+        // dbus
+        set_env_bool("SBH_DBUS_ENABLED", &mut self.dbus.enabled)?;
+
+        // archive
+        set_env_bool("SBH_ARCHIVE_ENABLED", &mut self.archive.enabled)?;
+
+        // control
+        set_env_bool("SBH_CONTROL_ENABLED", &mut self.control.enabled)?;
+
+        // quarantine
+        set_env_u64("SBH_QUARANTINE_TTL_HOURS", &mut self.quarantine.ttl_hours)?;
+
+        // logging
+        if let Some(raw) = env_var("SBH_LOGGING_LEVEL") {
+            self.logging.level = raw.parse().map_err(|details| SbhError::ConfigParse {
+                context: "env",
+                details: format!("SBH_LOGGING_LEVEL={raw:?}: {details}"),
+            })?;
+        }
+        if let Some(raw) = env_var("SBH_LOGGING_FORMAT") {
+            self.logging.format = raw.parse().map_err(|details| SbhError::ConfigParse {
+                context: "env",
+                details: format!("SBH_LOGGING_FORMAT={raw:?}: {details}"),
+            })?;
+        }
+
+        // coredumps
+        set_env_bool("SBH_COREDUMPS_ENABLED", &mut self.coredumps.enabled)?;
+        set_env_u64(
+            "SBH_COREDUMPS_MAX_AGE_HOURS",
+            &mut self.coredumps.max_age_hours,
+        )?;
+
         Ok(())
     }
 
@@ -753,8 +1724,10 @@ impl Config {
             .collect();
         self.ballast.overrides = normalized;
 
-        // Strip trailing slashes from scanner root_paths.
-        for path in &mut self.scanner.root_paths {
+        // Strip trailing slashes from scanner root_paths and per-mount
+        // pressure entries.
+        let mount_paths = self.pressure.mounts.iter_mut().map(|entry| &mut entry.path);
+        for path in self.scanner.root_paths.iter_mut().chain(mount_paths) {
             let s = path.to_string_lossy();
             // Don't strip if it looks like a root ("/" or "C:\").
             let is_unix_root = s.len() == 1;
@@ -767,43 +1740,48 @@ impl Config {
                 }
             }
         }
+
+        // `[[pressure.mounts]]` ballast placement lands in the per-volume
+        // ballast overrides the pool coordinator already reads.
+        for entry in &self.pressure.mounts {
+            if entry.ballast.is_none() && entry.ballast_dir.is_none() {
+                continue;
+            }
+            // Keyed like the lookups, which strip the separator even from "/".
+            let key = strip_trailing_separator(&entry.path.to_string_lossy()).to_string();
+            let volume = self.ballast.overrides.entry(key).or_default();
+            if let Some(enabled) = entry.ballast {
+                volume.enabled = enabled;
+            }
+            if let Some(dir) = &entry.ballast_dir {
+                volume.ballast_dir = Some(dir.clone());
+            }
+        }
     }
 
     #[allow(clippy::too_many_lines)]
     fn validate(&self) -> Result<()> {
-        // I31: Thresholds must be in 0.0..=100.0.
-        for (name, val) in [
-            ("green_min_free_pct", self.pressure.green_min_free_pct),
-            ("yellow_min_free_pct", self.pressure.yellow_min_free_pct),
-            ("orange_min_free_pct", self.pressure.orange_min_free_pct),
-            ("red_min_free_pct", self.pressure.red_min_free_pct),
-        ] {
-            if !(0.0..=100.0).contains(&val) {
+        validate_pressure_levels("pressure", &self.pressure)?;
+        for entry in &self.pressure.mounts {
+            let scope = format!("pressure.mounts[{}]", entry.path.display());
+            if !entry.path.is_absolute() {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope}.path must be absolute"),
+                });
+            }
+            validate_pressure_levels(&scope, &self.pressure.for_mount(&entry.path))?;
+            if let Some(dir) = &entry.ballast_dir
+                && !(dir.is_absolute() && dir.starts_with(&entry.path))
+            {
                 return Err(SbhError::InvalidConfig {
-                    details: format!("pressure.{name} must be in [0, 100], got {val}"),
+                    details: format!(
+                        "{scope}.ballast_dir must be an absolute path on that mount, got {}",
+                        dir.display()
+                    ),
                 });
             }
         }
-
-        if !(self.pressure.green_min_free_pct > self.pressure.yellow_min_free_pct
-            && self.pressure.yellow_min_free_pct > self.pressure.orange_min_free_pct
-            && self.pressure.orange_min_free_pct > self.pressure.red_min_free_pct)
-        {
-            return Err(SbhError::InvalidConfig {
-                details: "pressure thresholds must strictly descend: green > yellow > orange > red"
-                    .to_string(),
-            });
-        }
-
-        // Prevent CPU spin from zero poll interval.
-        if self.pressure.poll_interval_ms < 100 {
-            return Err(SbhError::InvalidConfig {
-                details: format!(
-                    "pressure.poll_interval_ms must be >= 100, got {}",
-                    self.pressure.poll_interval_ms
-                ),
-            });
-        }
+        self.validate_pressure_profiles()?;
 
         if self.pressure.prediction.enabled {
             let pred = &self.pressure.prediction;
@@ -847,11 +1825,29 @@ impl Config {
             validate_prob("prediction.min_confidence", pred.min_confidence)?;
         }
 
+        if self.watchdog.stall_intervals == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "watchdog.stall_intervals must be >= 1".to_string(),
+            });
+        }
         if self.scanner.parallelism == 0 {
             return Err(SbhError::InvalidConfig {
                 details: "scanner.parallelism must be >= 1".to_string(),
             });
         }
+        if let Some(path) = self
+            .scanner
+            .device_parallelism
+            .iter()
+            .find_map(|(path, threads)| (*threads == 0).then_some(path))
+        {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "scanner.device_parallelism for {} must be >= 1",
+                    path.display()
+                ),
+            });
+        }
         if self.scanner.max_depth == 0 {
             return Err(SbhError::InvalidConfig {
                 details: "scanner.max_depth must be >= 1".to_string(),
@@ -862,6 +1858,47 @@ impl Config {
                 details: "scanner.max_delete_batch must be >= 1".to_string(),
             });
         }
+        if !self.scanner.deep_scan_schedule.trim().is_empty()
+            && let Err(SbhError::InvalidConfig { details }) =
+                CronSchedule::parse(&self.scanner.deep_scan_schedule)
+        {
+            return Err(SbhError::InvalidConfig {
+                details: format!("scanner.deep_scan_schedule: {details}"),
+            });
+        }
+        if self.scanner.shallow_max_depth == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.shallow_max_depth must be >= 1".to_string(),
+            });
+        }
+        if self.scanner.delete_workers == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.delete_workers must be >= 1".to_string(),
+            });
+        }
+        if !(0.0..=100.0).contains(&self.scanner.io_throttle_stall_pct) {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.io_throttle_stall_pct must be in [0, 100]".to_string(),
+            });
+        }
+        if self.scanner.io_throttle_stall_pct > 0.0 && self.scanner.io_throttle_pause_ms == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.io_throttle_pause_ms must be >= 1 when pacing is enabled"
+                    .to_string(),
+            });
+        }
+        for name in &self.scanner.truncate_categories {
+            if !ArtifactCategory::from_config_name(name)
+                .is_some_and(ArtifactCategory::supports_truncation)
+            {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "scanner.truncate_categories: {name:?} cannot be truncated \
+                         (allowed: log_file, core_dump)"
+                    ),
+                });
+            }
+        }
         if self.scanner.repeat_deletion_base_cooldown_secs == 0 {
             return Err(SbhError::InvalidConfig {
                 details: "scanner.repeat_deletion_base_cooldown_secs must be >= 1".to_string(),
@@ -882,6 +1919,16 @@ impl Config {
 
         validate_prob("scoring.min_score", self.scoring.min_score)?;
         validate_prob("scoring.calibration_floor", self.scoring.calibration_floor)?;
+        if self.coredumps.sweep_interval_secs == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "coredumps.sweep_interval_secs must be >= 1".to_string(),
+            });
+        }
+        if self.scoring.stability_scans == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scoring.stability_scans must be >= 1".to_string(),
+            });
+        }
 
         // I35: min_score must be <= calibration_floor.
         if self.scoring.min_score > self.scoring.calibration_floor {
@@ -908,6 +1955,14 @@ impl Config {
             }
         }
 
+        for (key, cost) in &self.costs.0 {
+            if !cost.is_finite() || *cost < 0.0 {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("costs.{key} must be a finite value >= 0.0, got {cost}"),
+                });
+            }
+        }
+
         // M13: Loss values must be finite and non-negative.
         if !self.scoring.false_positive_loss.is_finite()
             || !self.scoring.false_negative_loss.is_finite()
@@ -1028,6 +2083,33 @@ impl Config {
             }
         }
 
+        for (i, pool) in self.ballast.pools.iter().enumerate() {
+            let scope = format!("ballast.pools[{i}]");
+            if !pool.path.is_absolute() {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope}.path must be absolute: {}", pool.path.display()),
+                });
+            }
+            if self.ballast.pools[..i].iter().any(|p| p.path == pool.path) {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope}.path repeats {}", pool.path.display()),
+                });
+            }
+            if pool
+                .file_count
+                .is_some_and(|count| count == 0 || count > 100_000)
+            {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope}.file_count must be between 1 and 100000"),
+                });
+            }
+            if pool.file_size_bytes.is_some_and(|size| size < 4096) {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope}.file_size_bytes must be >= 4096 (header size)"),
+                });
+            }
+        }
+
         if self.update.metadata_cache_ttl_seconds == 0 {
             return Err(SbhError::InvalidConfig {
                 details: "update.metadata_cache_ttl_seconds must be > 0".to_string(),
@@ -1046,10 +2128,167 @@ impl Config {
             crate::scanner::protection::validate_glob_pattern(pattern)?;
         }
 
+        if self.archive.enabled && !self.archive.path.is_absolute() {
+            return Err(SbhError::InvalidConfig {
+                details: "archive.path must be an absolute path when archive.enabled=true"
+                    .to_string(),
+            });
+        }
+        for name in &self.archive.categories {
+            if ArtifactCategory::from_config_name(name).is_none() {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("archive.categories: unknown category {name:?}"),
+                });
+            }
+        }
+        self.archive.s3.validate("archive.s3")?;
+        self.quarantine.s3.validate("quarantine.s3")?;
+        if !(0.0..100.0).contains(&self.archive.min_free_pct) {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "archive.min_free_pct must be in [0, 100), got {}",
+                    self.archive.min_free_pct
+                ),
+            });
+        }
+        if !self.control.socket_path.as_os_str().is_empty()
+            && !self.control.socket_path.is_absolute()
+        {
+            return Err(SbhError::InvalidConfig {
+                details: "control.socket_path must be an absolute path".to_string(),
+            });
+        }
+        if self.quarantine.ttl_hours == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "quarantine.ttl_hours must be > 0".to_string(),
+            });
+        }
+        for (index, rule) in self.notifications.rules.iter().enumerate() {
+            if rule.event != "*" && !EVENT_TYPES.contains(&rule.event.as_str()) {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "notifications.rules[{index}].event '{}' is not an event type; expected one of {} or *",
+                        rule.event,
+                        EVENT_TYPES.join(", "),
+                    ),
+                });
+            }
+            if let Some(channel) = rule
+                .channels
+                .iter()
+                .find(|channel| !CHANNEL_NAMES.contains(&channel.as_str()))
+            {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "notifications.rules[{index}].channels has unknown channel '{channel}'"
+                    ),
+                });
+            }
+            if rule.mute && rule.set_level.is_some() {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("notifications.rules[{index}] cannot both mute and set_level"),
+                });
+            }
+        }
+        for (name, value) in &self.notifications.webhook.headers {
+            let bad_name = name.is_empty()
+                || name
+                    .chars()
+                    .any(|c| c == ':' || c.is_whitespace() || c.is_control());
+            if bad_name || value.chars().any(|c| c == '\r' || c == '\n') {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "notifications.webhook.headers has an invalid header '{name}'; \
+                         names need no colons or whitespace and values no line breaks"
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every `[[pressure.profiles]]` entry needs a unique name and a
+    /// non-empty `HH:MM` window, and fully applied it must leave valid levels
+    /// on every configured mount. Part-way through a transition the levels
+    /// are a mix of two valid sets, so they stay valid too.
+    fn validate_pressure_profiles(&self) -> Result<()> {
+        let mut names = BTreeSet::new();
+        for profile in &self.pressure.profiles {
+            let scope = format!("pressure.profiles[{}]", profile.name);
+            if profile.name.trim().is_empty() || !names.insert(profile.name.as_str()) {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "pressure.profiles names must be non-empty and unique, got {:?}",
+                        profile.name
+                    ),
+                });
+            }
+            let Some((start, end)) = profile.window() else {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "{scope}.start and .end must be HH:MM, got {:?} and {:?}",
+                        profile.start, profile.end
+                    ),
+                });
+            };
+            if start == end {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{scope} window is empty: start and end are both {start}"),
+                });
+            }
+            let mut mounts = vec![Path::new("/")];
+            mounts.extend(self.pressure.mounts.iter().map(|entry| &*entry.path));
+            for mount in mounts {
+                let mut levels = self.pressure.for_mount(mount);
+                profile.blend(&mut levels, 1.0);
+                validate_pressure_levels(&scope, &levels)?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Thresholds in `[0, 100]`, strictly descending, and a poll interval that
+/// cannot spin the CPU. `scope` prefixes the key in error messages.
+fn validate_pressure_levels(scope: &str, pressure: &PressureConfig) -> Result<()> {
+    // I31: Thresholds must be in 0.0..=100.0.
+    for (name, val) in [
+        ("green_min_free_pct", pressure.green_min_free_pct),
+        ("yellow_min_free_pct", pressure.yellow_min_free_pct),
+        ("orange_min_free_pct", pressure.orange_min_free_pct),
+        ("red_min_free_pct", pressure.red_min_free_pct),
+    ] {
+        if !(0.0..=100.0).contains(&val) {
+            return Err(SbhError::InvalidConfig {
+                details: format!("{scope}.{name} must be in [0, 100], got {val}"),
+            });
+        }
+    }
+
+    if !(pressure.green_min_free_pct > pressure.yellow_min_free_pct
+        && pressure.yellow_min_free_pct > pressure.orange_min_free_pct
+        && pressure.orange_min_free_pct > pressure.red_min_free_pct)
+    {
+        return Err(SbhError::InvalidConfig {
+            details: format!(
+                "{scope} thresholds must strictly descend: green > yellow > orange > red"
+            ),
+        });
+    }
+
+    // Prevent CPU spin from zero poll interval.
+    if pressure.poll_interval_ms < 100 {
+        return Err(SbhError::InvalidConfig {
+            details: format!(
+                "{scope}.poll_interval_ms must be >= 100, got {}",
+                pressure.poll_interval_ms
+            ),
+        });
+    }
+    Ok(())
+}
+
 fn validate_prob(name: &str, value: f64) -> Result<()> {
     if !(0.0..=1.0).contains(&value) {
         return Err(SbhError::InvalidConfig {
@@ -1124,7 +2363,10 @@ fn strip_trailing_separator(s: &str) -> &str {
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, SbhError};
+    use super::{
+        ArtifactCategory, Config, DbusBus, NaiveTime, PressureProfile, SbhError,
+        default_control_socket,
+    };
     use std::collections::HashMap;
     use std::path::{Path, PathBuf};
 
@@ -1192,6 +2434,40 @@ mod tests {
         assert!(err.to_string().contains("min_observations_for_forecast"));
     }
 
+    #[test]
+    fn notification_rule_with_unknown_event_rejected() {
+        let mut cfg = Config::default();
+        cfg.notifications
+            .rules
+            .push(crate::daemon::notifications::NotificationRule {
+                event: "pressure_change".to_string(),
+                mute: true,
+                ..Default::default()
+            });
+        let err = cfg.validate().expect_err("expected rule validation error");
+        assert!(err.to_string().contains("notifications.rules[0].event"));
+    }
+
+    #[test]
+    fn webhook_headers_cannot_inject_lines() {
+        let mut cfg = Config::default();
+        cfg.notifications.webhook.headers.insert(
+            "Authorization".to_string(),
+            "Bearer x\r\nX-Evil: 1".to_string(),
+        );
+        let err = cfg
+            .validate()
+            .expect_err("expected header validation error");
+        assert!(err.to_string().contains("notifications.webhook.headers"));
+
+        cfg.notifications.webhook.headers.clear();
+        cfg.notifications
+            .webhook
+            .headers
+            .insert("X-Team: ops".to_string(), "1".to_string());
+        assert!(cfg.validate().is_err());
+    }
+
     #[test]
     fn ballast_zero_count_rejected() {
         let mut cfg = Config::default();
@@ -1298,6 +2574,14 @@ mod tests {
         assert!(err.to_string().contains("parallelism"));
     }
 
+    #[test]
+    fn scanner_zero_delete_workers_rejected() {
+        let mut cfg = Config::default();
+        cfg.scanner.delete_workers = 0;
+        let err = cfg.validate().expect_err("expected delete_workers error");
+        assert!(err.to_string().contains("delete_workers"));
+    }
+
     #[test]
     fn scanner_repeat_deletion_base_cooldown_must_be_positive() {
         let mut cfg = Config::default();
@@ -1331,6 +2615,167 @@ mod tests {
         assert!(err.to_string().contains("must be >="));
     }
 
+    #[test]
+    fn costs_table_parses_and_rejects_negative_costs() {
+        let mut cfg: Config =
+            toml::from_str("[costs]\nrust_target = 20.0\nbazel-output-base = 0.5\n")
+                .expect("parse costs");
+        assert!(cfg.validate().is_ok());
+        assert_eq!(
+            cfg.costs
+                .regeneration_cost("bazel-output-base", ArtifactCategory::BuildOutput),
+            Some(0.5)
+        );
+        assert_eq!(
+            cfg.costs
+                .regeneration_cost("cargo-target", ArtifactCategory::RustTarget),
+            Some(20.0)
+        );
+        assert_eq!(
+            cfg.costs
+                .regeneration_cost("node-modules", ArtifactCategory::NodeModules),
+            None
+        );
+
+        cfg.costs.0.insert("rust_target".to_string(), -1.0);
+        let err = cfg.validate().expect_err("expected costs error");
+        assert!(err.to_string().contains("costs.rust_target"));
+    }
+
+    #[test]
+    fn deep_scan_schedule_is_validated() {
+        let mut cfg: Config =
+            toml::from_str("[scanner]\ndeep_scan_schedule = \"0 3 * * *\"\n").expect("parse");
+        assert!(cfg.validate().is_ok());
+        assert_eq!(
+            cfg.scanner.deep_scan_schedule().map(|s| s.to_string()),
+            Some("0 3 * * *".to_string())
+        );
+        assert!(Config::default().scanner.deep_scan_schedule().is_none());
+
+        cfg.scanner.deep_scan_schedule = "0 25 * * *".to_string();
+        let err = cfg.validate().expect_err("expected schedule error");
+        assert!(
+            err.to_string().contains("scanner.deep_scan_schedule"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn control_socket_defaults_under_the_runtime_dir() {
+        let cfg = Config::default();
+        assert!(cfg.control.enabled);
+        assert_eq!(
+            default_control_socket(Some(std::ffi::OsStr::new("/run/user/1000"))),
+            PathBuf::from("/run/user/1000/sbh/control.sock")
+        );
+        assert_eq!(
+            default_control_socket(Some(std::ffi::OsStr::new(""))),
+            PathBuf::from("/run/sbh/control.sock")
+        );
+        assert_eq!(
+            default_control_socket(None),
+            PathBuf::from("/run/sbh/control.sock")
+        );
+
+        let mut cfg: Config =
+            toml::from_str("[control]\nsocket_path = \"sbh.sock\"\n").expect("should parse");
+        let err = cfg.validate().expect_err("relative socket path");
+        assert!(err.to_string().contains("control.socket_path"));
+        cfg.control.socket_path = PathBuf::from("/tmp/sbh.sock");
+        cfg.validate().expect("absolute socket path");
+        assert_eq!(cfg.control.socket_path(), PathBuf::from("/tmp/sbh.sock"));
+    }
+
+    #[test]
+    fn archive_section_validates_path_and_categories() {
+        let mut cfg = Config::default();
+        assert!(cfg.archive.relocation().is_none());
+
+        cfg.archive.enabled = true;
+        cfg.archive.path = PathBuf::from("cold");
+        cfg.archive.categories = vec!["rust_target".to_string()];
+        let err = cfg.validate().expect_err("relative archive path");
+        assert!(err.to_string().contains("archive.path"));
+
+        cfg.archive.path = PathBuf::from("/mnt/cold/sbh");
+        cfg.validate().expect("valid archive config");
+        let relocation = cfg.archive.relocation().expect("relocation enabled");
+        assert_eq!(relocation.categories, vec![ArtifactCategory::RustTarget]);
+        assert!(relocation.object_store.is_none());
+
+        cfg.archive.s3.bucket = "cold-artifacts".to_string();
+        let store = cfg.archive.relocation().unwrap().object_store.unwrap();
+        assert_eq!(store.region, "us-east-1");
+        cfg.archive.s3.endpoint = "s3.amazonaws.com".to_string();
+        let err = cfg.validate().expect_err("endpoint without scheme");
+        assert!(err.to_string().contains("archive.s3.endpoint"));
+        cfg.archive.s3.endpoint = "http://minio:9000".to_string();
+        assert_eq!(
+            cfg.archive
+                .relocation()
+                .unwrap()
+                .object_store
+                .unwrap()
+                .prefix,
+            "sbh-archive/"
+        );
+
+        assert!(cfg.quarantine.offload().is_none());
+        cfg.quarantine.s3.bucket = "cold-artifacts".to_string();
+        cfg.quarantine.s3.region = String::new();
+        let err = cfg
+            .validate()
+            .expect_err("quarantine bucket without a region");
+        assert!(err.to_string().contains("quarantine.s3"));
+        cfg.quarantine.s3.region = "eu-west-1".to_string();
+        assert_eq!(cfg.quarantine.offload().unwrap().prefix, "sbh-quarantine/");
+
+        cfg.archive.categories.push("sourcetree".to_string());
+        let err = cfg.validate().expect_err("unknown category");
+        assert!(err.to_string().contains("sourcetree"));
+    }
+
+    #[test]
+    fn dbus_section_defaults_off_on_the_system_bus() {
+        let cfg = Config::default();
+        assert!(!cfg.dbus.enabled);
+        assert_eq!(cfg.dbus.bus, DbusBus::System);
+
+        let cfg: Config =
+            toml::from_str("[dbus]\nenabled = true\nbus = \"session\"\n").expect("should parse");
+        assert!(cfg.dbus.enabled);
+        assert_eq!(cfg.dbus.bus, DbusBus::Session);
+        assert!(toml::from_str::<Config>("[dbus]\nbus = \"user\"\n").is_err());
+    }
+
+    #[test]
+    fn truncate_categories_reject_directory_categories() {
+        let mut cfg = Config::default();
+        cfg.scanner.truncate_categories = vec!["rust_target".to_string()];
+        let err = cfg
+            .validate()
+            .expect_err("expected truncate_categories error");
+        assert!(err.to_string().contains("truncate_categories"));
+
+        cfg.scanner.truncate_categories = vec!["log_file".to_string(), "core_dump".to_string()];
+        assert!(cfg.validate().is_ok());
+        assert!(cfg.scanner.truncate_allowlist().is_empty());
+        cfg.scanner.truncate_open_files = true;
+        assert_eq!(
+            cfg.scanner.truncate_allowlist(),
+            vec![ArtifactCategory::LogFile, ArtifactCategory::CoreDump]
+        );
+    }
+
+    #[test]
+    fn scoring_stability_scans_zero_rejected() {
+        let mut cfg = Config::default();
+        cfg.scoring.stability_scans = 0;
+        let err = cfg.validate().expect_err("expected stability_scans error");
+        assert!(err.to_string().contains("stability_scans"));
+    }
+
     #[test]
     fn scoring_min_score_out_of_range_rejected() {
         let mut cfg = Config::default();
@@ -1350,6 +2795,7 @@ mod tests {
                 enabled: true,
                 file_count: Some(20),
                 file_size_bytes: None,
+                ballast_dir: None,
             },
         );
         let cfg = BallastConfig {
@@ -1357,7 +2803,10 @@ mod tests {
             file_size_bytes: 1_000_000,
             replenish_cooldown_minutes: 30,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides,
+            pools: Vec::new(),
         };
         assert_eq!(cfg.effective_file_count("/data"), 20);
         assert_eq!(cfg.effective_file_count("/other"), 10);
@@ -1374,6 +2823,7 @@ mod tests {
                 enabled: false,
                 file_count: None,
                 file_size_bytes: None,
+                ballast_dir: None,
             },
         );
         let cfg = BallastConfig {
@@ -1381,12 +2831,41 @@ mod tests {
             file_size_bytes: 1_000_000,
             replenish_cooldown_minutes: 30,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides,
+            pools: Vec::new(),
         };
         assert!(!cfg.is_volume_enabled("/tmp"));
         assert!(cfg.is_volume_enabled("/data"));
     }
 
+    #[test]
+    fn ballast_pools_parse_and_size_each_pool() {
+        let mut cfg: Config = toml::from_str(
+            "[ballast]\nfile_count = 4\n\n[[ballast.pools]]\npath = \"/data/.sbh-ballast\"\nfile_count = 8\n\n[[ballast.pools]]\npath = \"/var/lib/sbh/ballast\"\n",
+        )
+        .expect("should parse");
+        cfg.validate().expect("pools should validate");
+        assert_eq!(cfg.ballast.pools.len(), 2);
+
+        let data = cfg
+            .ballast
+            .pool_settings("/data", Path::new("/data/.sbh-ballast"));
+        assert_eq!(data.file_count, 8);
+        assert_eq!(data.file_size_bytes, cfg.ballast.file_size_bytes);
+        assert!(data.pools.is_empty());
+        let root = cfg
+            .ballast
+            .pool_settings("/", Path::new("/var/lib/sbh/ballast"));
+        assert_eq!(root.file_count, 4);
+
+        cfg.ballast.pools[1].path = PathBuf::from("/data/.sbh-ballast");
+        assert!(cfg.validate().unwrap_err().to_string().contains("repeats"));
+        cfg.ballast.pools[1].path = PathBuf::from("relative/ballast");
+        assert!(cfg.validate().unwrap_err().to_string().contains("absolute"));
+    }
+
     #[test]
     fn normalize_paths_trims_trailing_slashes_and_keeps_root() {
         let mut cfg = Config::default();
@@ -1404,6 +2883,147 @@ mod tests {
         assert!(cfg.scanner.root_paths.contains(&PathBuf::from("/data")));
     }
 
+    #[test]
+    fn pressure_mounts_resolve_most_specific_entry() {
+        let mut cfg: Config = toml::from_str(
+            r#"
+[pressure]
+green_min_free_pct = 20.0
+
+[[pressure.mounts]]
+path = "/data/"
+green_min_free_pct = 5.0
+yellow_min_free_pct = 3.0
+orange_min_free_pct = 2.0
+red_min_free_pct = 1.0
+ballast_dir = "/data/reserve"
+
+[[pressure.mounts]]
+path = "/data/scratch"
+poll_interval_ms = 5000
+ballast = false
+"#,
+        )
+        .expect("should parse");
+        cfg.normalize_paths();
+        cfg.validate().expect("valid per-mount thresholds");
+
+        let root = cfg.pressure.for_mount(Path::new("/"));
+        assert!((root.green_min_free_pct - 20.0).abs() < f64::EPSILON);
+        let data = cfg.pressure.for_mount(Path::new("/data"));
+        assert!((data.green_min_free_pct - 5.0).abs() < f64::EPSILON);
+        assert_eq!(data.poll_interval_ms, 1_000);
+        // The nested entry wins for its mount but inherits what it leaves unset
+        // from [pressure], not from the /data entry.
+        let scratch = cfg.pressure.for_mount(Path::new("/data/scratch"));
+        assert_eq!(scratch.poll_interval_ms, 5_000);
+        assert!((scratch.green_min_free_pct - 20.0).abs() < f64::EPSILON);
+        assert!(scratch.mounts.is_empty());
+        // "/database" is not under "/data".
+        let unrelated = Path::new("/database");
+        assert!(cfg.pressure.mount_override(unrelated).is_none());
+
+        assert_eq!(
+            cfg.ballast.effective_ballast_dir("/data"),
+            Some(Path::new("/data/reserve"))
+        );
+        assert!(!cfg.ballast.is_volume_enabled("/data/scratch"));
+        assert!(cfg.ballast.is_volume_enabled("/data"));
+
+        cfg.pressure.mounts[1].yellow_min_free_pct = Some(30.0);
+        let err = cfg.validate().expect_err("yellow above inherited green");
+        assert!(err.to_string().contains("pressure.mounts[/data/scratch]"));
+        cfg.pressure.mounts[1].yellow_min_free_pct = None;
+        cfg.pressure.mounts[0].ballast_dir = Some(PathBuf::from("/elsewhere"));
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn pressure_profiles_blend_in_over_the_transition_and_wrap_midnight() {
+        let mut cfg: Config = toml::from_str(
+            r#"
+[pressure]
+profile_transition_minutes = 60
+
+[[pressure.profiles]]
+name = "nightly"
+start = "22:00"
+end = "06:00"
+green_min_free_pct = 8.0
+yellow_min_free_pct = 6.0
+orange_min_free_pct = 4.0
+red_min_free_pct = 2.0
+"#,
+        )
+        .expect("should parse");
+        cfg.validate().expect("valid profile");
+        let at = |hh, mm| NaiveTime::from_hms_opt(hh, mm, 0).unwrap();
+        let green_at = |cfg: &Config, time| {
+            cfg.pressure
+                .for_mount_at(Path::new("/"), time)
+                .green_min_free_pct
+        };
+
+        assert!(cfg.pressure.active_profile(at(12, 0)).is_none());
+        assert!((green_at(&cfg, at(12, 0)) - 20.0).abs() < 1e-9);
+        // Half way through the hour-long ramp in, and again on the way out.
+        let (profile, weight) = cfg.pressure.active_profile(at(22, 30)).unwrap();
+        assert_eq!(profile.name, "nightly");
+        assert!((weight - 0.5).abs() < 1e-9);
+        assert!((green_at(&cfg, at(22, 30)) - 14.0).abs() < 1e-9);
+        assert!((green_at(&cfg, at(5, 30)) - 14.0).abs() < 1e-9);
+        // Past midnight the window is still open and fully applied.
+        assert!((green_at(&cfg, at(2, 0)) - 8.0).abs() < 1e-9);
+        assert!(cfg.pressure.active_profile(at(6, 0)).is_none());
+        // A mount without its own thresholds follows the profile too; the
+        // resolved config carries no profiles of its own.
+        let data = cfg.pressure.for_mount_at(Path::new("/data"), at(2, 0));
+        assert!((data.red_min_free_pct - 2.0).abs() < 1e-9);
+        assert!(data.profiles.is_empty());
+
+        cfg.pressure.profile_transition_minutes = 0;
+        assert!((green_at(&cfg, at(22, 0)) - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pressure_profiles_are_validated() {
+        let mut cfg = Config::default();
+        cfg.pressure.profiles.push(PressureProfile {
+            name: "batch".to_string(),
+            start: "01:00".to_string(),
+            end: "05:00".to_string(),
+            yellow_min_free_pct: Some(25.0),
+            ..PressureProfile::default()
+        });
+        let err = cfg.validate().expect_err("yellow above green");
+        assert!(err.to_string().contains("pressure.profiles[batch]"));
+
+        cfg.pressure.profiles[0].yellow_min_free_pct = None;
+        cfg.validate().expect("valid");
+        cfg.pressure.profiles[0].end = "5pm".to_string();
+        assert!(cfg.validate().is_err());
+        cfg.pressure.profiles[0].end = "01:00".to_string();
+        assert!(cfg.validate().is_err());
+        cfg.pressure.profiles[0].end = "05:00".to_string();
+        cfg.pressure.profiles.push(cfg.pressure.profiles[0].clone());
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn pressure_mount_entry_for_root_disables_root_ballast() {
+        let mut cfg: Config = toml::from_str(
+            r#"
+[[pressure.mounts]]
+path = "/"
+ballast = false
+"#,
+        )
+        .expect("should parse");
+        cfg.normalize_paths();
+        assert!(!cfg.ballast.is_volume_enabled("/"));
+        assert!(cfg.ballast.is_volume_enabled("/data"));
+    }
+
     #[test]
     fn windows_path_normalization() {
         let mut cfg = Config::default();
@@ -1545,4 +3165,19 @@ kill_switch = true
         assert_eq!(cfg.dashboard.mode, super::DashboardMode::New);
         assert!(!cfg.dashboard.kill_switch);
     }
+
+    #[test]
+    fn logging_section_parses_and_defaults_to_human_info() {
+        use crate::logger::diag;
+
+        let cfg = Config::default();
+        assert_eq!(cfg.logging.level, diag::DiagLevel::Info);
+        assert_eq!(cfg.logging.format, diag::DiagFormat::Human);
+
+        let cfg: Config = toml::from_str("[logging]\nlevel = \"debug\"\nformat = \"json\"\n")
+            .expect("should parse");
+        assert_eq!(cfg.logging.level, diag::DiagLevel::Debug);
+        assert_eq!(cfg.logging.format, diag::DiagFormat::Json);
+        assert!(toml::from_str::<Config>("[logging]\nformat = \"xml\"\n").is_err());
+    }
 }
@@ -0,0 +1,165 @@
+//! Container mode: `--container` or `SBH_CONTAINER=1`.
+//!
+//! A scratch or distroless image has no service manager, no shell, and no
+//! home directory worth writing to, and usually runs as a non-root user.
+//! Container mode adjusts the defaults to match: config at
+//! [`CONTAINER_CONFIG_FILE`], everything sbh writes (state, database, logs,
+//! control socket) under the [`CONTAINER_DATA_DIR`] volume, and the host
+//! filesystem to protect mounted at [`CONTAINER_WATCH_ROOT`]. Values set in
+//! the config file still win. DBus, the systemd watchdog, and the service
+//! install/uninstall/update commands are off.
+//!
+//! Monitoring, scanning, ballast, and deletion never shell out. Optional
+//! features that wrap an external program (webhooks, object-store uploads,
+//! desktop popups, SMART probes, native cache pruning) are listed by
+//! [`missing_tools`] when the program is absent, so the image can be checked
+//! instead of failing quietly at the first alert.
+
+#![allow(missing_docs)]
+
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::core::config::Config;
+use crate::core::paths::find_on_path;
+use crate::scanner::tool_caches::KNOWN_CACHES;
+
+/// Config file read in container mode.
+pub const CONTAINER_CONFIG_FILE: &str = "/etc/sbh/config.toml";
+
+/// Writable volume for state, database, logs, and the control socket.
+pub const CONTAINER_DATA_DIR: &str = "/var/lib/sbh";
+
+/// Where the host filesystem to watch is expected to be mounted.
+pub const CONTAINER_WATCH_ROOT: &str = "/data";
+
+static CONTAINER: AtomicBool = AtomicBool::new(false);
+
+/// Turn container mode on for this process (the `--container` flag).
+pub fn enable() {
+    CONTAINER.store(true, Ordering::Relaxed);
+}
+
+/// Whether container mode is on, by flag or by `SBH_CONTAINER`.
+#[must_use]
+pub fn is_enabled() -> bool {
+    CONTAINER.load(Ordering::Relaxed)
+        || env::var("SBH_CONTAINER")
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on"))
+}
+
+/// An enabled feature whose external program is not on `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingTool {
+    pub feature: &'static str,
+    pub program: &'static str,
+    /// What the feature does instead; `None` when it just fails.
+    pub fallback: Option<&'static str>,
+}
+
+/// External programs the enabled optional features need but cannot find.
+#[must_use]
+pub fn missing_tools(config: &Config) -> Vec<MissingTool> {
+    let notifications = &config.notifications;
+    let channel_on =
+        |name: &str| notifications.enabled && notifications.channels.iter().any(|c| c == name);
+    let mut needed = Vec::new();
+    if channel_on("webhook")
+        && notifications.webhook.enabled
+        && !notifications.webhook.url.is_empty()
+    {
+        needed.push(("webhook notifications", "curl", None));
+    }
+    if channel_on("desktop") && notifications.desktop.enabled {
+        let program = if cfg!(target_os = "macos") {
+            "osascript"
+        } else {
+            "notify-send"
+        };
+        needed.push(("desktop notifications", program, None));
+    }
+    if config
+        .archive
+        .relocation()
+        .is_some_and(|relocation| relocation.object_store.is_some())
+    {
+        needed.push(("archive uploads", "curl", None));
+        needed.push(("archive uploads", "tar", None));
+    }
+    if config.quarantine.offload().is_some() {
+        needed.push(("quarantine offload", "curl", None));
+        needed.push(("quarantine offload", "tar", None));
+    }
+    if config.telemetry.disk_health_interval_secs > 0 {
+        needed.push((
+            "disk health probes",
+            "smartctl",
+            Some("kernel I/O error counters"),
+        ));
+    }
+    for cache in KNOWN_CACHES {
+        if let Some(native) = cache.native
+            && config.caches.native_enabled(cache.tool)
+            && !needed
+                .iter()
+                .any(|(_, program, _)| *program == native.program)
+        {
+            needed.push((
+                "native cache pruning",
+                native.program,
+                Some("direct cleanup"),
+            ));
+        }
+    }
+    needed
+        .into_iter()
+        .filter(|(_, program, _)| find_on_path(program).is_none())
+        .map(|(feature, program, fallback)| MissingTool {
+            feature,
+            program,
+            fallback,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_enabled_features_need_their_tools() {
+        let mut config = Config::default();
+        assert!(
+            missing_tools(&config)
+                .iter()
+                .all(|tool| tool.fallback.is_some()),
+            "defaults only use programs they can do without"
+        );
+        config.telemetry.disk_health_interval_secs = 0;
+        config.caches.native_cargo = false;
+        config.caches.native_npm = false;
+        config.caches.native_pnpm = false;
+        assert!(missing_tools(&config).is_empty());
+
+        config.notifications.channels.push("webhook".to_string());
+        config.notifications.webhook.enabled = true;
+        assert!(missing_tools(&config).is_empty(), "no URL, nothing to post");
+
+        config.notifications.webhook.url = "https://hooks.example.com/sbh".to_string();
+        let missing = missing_tools(&config);
+        assert!(
+            find_on_path("curl").is_some() || missing.iter().any(|tool| tool.program == "curl"),
+            "{missing:?}"
+        );
+
+        config.caches.native_cargo = true;
+        let missing = missing_tools(&config);
+        assert!(
+            find_on_path("cargo-cache").is_some()
+                || missing
+                    .iter()
+                    .any(|tool| tool.program == "cargo-cache" && tool.fallback.is_some()),
+            "{missing:?}"
+        );
+    }
+}
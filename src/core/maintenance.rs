@@ -0,0 +1,166 @@
+//! Maintenance windows: `sbh pause --for 2h` suspends enforcement until a
+//! deadline, then the daemon resumes on its own.
+//!
+//! The CLI writes `maintenance.json` next to `state.json` and the daemon
+//! reads it every loop iteration, so a pause needs no control socket and
+//! survives a daemon restart. A window past its deadline is simply inactive;
+//! the daemon deletes the file when it notices.
+
+#![allow(missing_docs)]
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::marker;
+
+/// File name of the maintenance marker, a sibling of `state.json`.
+pub const MAINTENANCE_FILE_NAME: &str = "maintenance.json";
+
+/// A bounded pause of enforcement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// RFC 3339 time the pause began.
+    pub started_at: String,
+    /// RFC 3339 time enforcement resumes.
+    pub until: String,
+    /// Also suppress notifications while paused.
+    #[serde(default)]
+    pub mute_alerts: bool,
+    /// Operator note shown in status and the dashboard.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// A window starting at `now` and lasting `duration`.
+    #[must_use]
+    pub fn new(
+        now: DateTime<Utc>,
+        duration: Duration,
+        mute_alerts: bool,
+        reason: Option<String>,
+    ) -> Self {
+        let until = chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|d| now.checked_add_signed(d))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        Self {
+            started_at: now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            until: until.to_rfc3339_opts(SecondsFormat::Secs, true),
+            mute_alerts,
+            reason,
+        }
+    }
+
+    /// Time left at `now`; `None` once the deadline has passed or when
+    /// `until` does not parse (an unreadable deadline never pauses anything).
+    #[must_use]
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let until = DateTime::parse_from_rfc3339(&self.until).ok()?;
+        (until.with_timezone(&Utc) - now)
+            .to_std()
+            .ok()
+            .filter(|left| !left.is_zero())
+    }
+
+    #[must_use]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.remaining(now).is_some()
+    }
+}
+
+/// Maintenance marker path for the given `state.json` path.
+#[must_use]
+pub fn maintenance_path(state_file: &Path) -> PathBuf {
+    state_file.with_file_name(MAINTENANCE_FILE_NAME)
+}
+
+/// Read the marker; `Ok(None)` when there is none.
+pub fn read_maintenance(path: &Path) -> io::Result<Option<MaintenanceWindow>> {
+    marker::read_marker(path)
+}
+
+/// The window in force at `now`, treating unreadable markers as absent.
+#[must_use]
+pub fn active_maintenance(path: &Path, now: DateTime<Utc>) -> Option<MaintenanceWindow> {
+    read_maintenance(path)
+        .ok()
+        .flatten()
+        .filter(|window| window.is_active(now))
+}
+
+/// Write the marker, readable by `sbh status` for any user.
+pub fn write_maintenance(path: &Path, window: &MaintenanceWindow) -> io::Result<()> {
+    marker::write_marker(path, window, 0o644)
+}
+
+/// Remove the marker. Returns whether one existed.
+pub fn clear_maintenance(path: &Path) -> io::Result<bool> {
+    marker::clear_marker(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn window_counts_down_and_expires_at_its_deadline() {
+        let start = at("2026-10-16T12:00:00Z");
+        let window = MaintenanceWindow::new(start, Duration::from_secs(2 * 3600), true, None);
+        assert_eq!(window.until, "2026-10-16T14:00:00Z");
+        assert_eq!(
+            window.remaining(at("2026-10-16T13:30:00Z")),
+            Some(Duration::from_secs(1800))
+        );
+        assert!(window.is_active(start));
+        assert!(!window.is_active(at("2026-10-16T14:00:00Z")));
+        assert!(!window.is_active(at("2026-10-17T00:00:00Z")));
+
+        let garbled = MaintenanceWindow {
+            until: "soon".to_string(),
+            ..window
+        };
+        assert!(!garbled.is_active(start));
+    }
+
+    #[test]
+    fn marker_round_trips_next_to_the_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = maintenance_path(&dir.path().join("state.json"));
+        assert_eq!(path, dir.path().join("maintenance.json"));
+        let now = Utc::now();
+        assert_eq!(read_maintenance(&path).unwrap(), None);
+
+        let window = MaintenanceWindow::new(
+            now,
+            Duration::from_secs(600),
+            false,
+            Some("storage migration".to_string()),
+        );
+        write_maintenance(&path, &window).unwrap();
+        assert_eq!(read_maintenance(&path).unwrap(), Some(window.clone()));
+        assert_eq!(active_maintenance(&path, now), Some(window));
+        assert_eq!(
+            active_maintenance(&path, now + chrono::Duration::seconds(601)),
+            None
+        );
+
+        assert!(clear_maintenance(&path).unwrap());
+        assert!(!clear_maintenance(&path).unwrap());
+
+        fs::write(&path, "{not json").unwrap();
+        assert!(read_maintenance(&path).is_err());
+        assert_eq!(active_maintenance(&path, now), None);
+    }
+}
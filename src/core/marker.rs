@@ -0,0 +1,117 @@
+//! Small JSON files next to `state.json` that the CLI and daemon hand each
+//! other: `maintenance.json`, `probation.json`, and `burn_in.json`.
+//!
+//! Either side may read one at any moment, so every write replaces the file
+//! atomically: a temp file unique to the process and write, fsynced, renamed
+//! into place, and the directory fsynced so the rename survives a crash.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Read a marker; `Ok(None)` when there is none.
+pub fn read_marker<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write a marker with permission bits `mode` (Unix only), creating its
+/// directory if needed.
+pub fn write_marker<T: Serialize>(path: &Path, value: &T, mode: u32) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    replace_file(path, json.as_bytes(), mode)
+}
+
+/// Remove a marker. Returns whether one existed.
+pub fn clear_marker(path: &Path) -> io::Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Atomically replace `path` with `contents`. The new file gets permission
+/// bits `mode` (Unix only) from the moment it exists, so nothing wider is
+/// ever visible.
+pub fn replace_file(path: &Path, contents: &[u8], mode: u32) -> io::Result<()> {
+    static WRITE_SEQ: AtomicU64 = AtomicU64::new(0);
+    let seq = WRITE_SEQ.fetch_add(1, Ordering::Relaxed);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".{name}.{}.{seq}.tmp", std::process::id()));
+
+    let result = (|| {
+        let mut opts = OpenOptions::new();
+        opts.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt as _;
+            opts.mode(mode);
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        let mut file = opts.open(&tmp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, path)?;
+        // Best effort: the new contents are already in place.
+        #[cfg(unix)]
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let _ = fs::File::open(parent).and_then(|dir| dir.sync_all());
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn replaced_files_take_the_requested_mode_and_leave_no_temp_behind() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("probation.json");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_marker(&path, &vec!["secret"], 0o600).unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert_eq!(
+            read_marker::<Vec<String>>(&path).unwrap(),
+            Some(vec!["secret".to_string()])
+        );
+        let names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["probation.json"]);
+
+        assert!(clear_marker(&path).unwrap());
+        assert_eq!(read_marker::<Vec<String>>(&path).unwrap(), None);
+    }
+}
@@ -0,0 +1,648 @@
+//! Metric registry shared by the Prometheus exporter and the Grafana dashboard.
+//!
+//! Every exported series is declared once in [`METRICS`]: its name, help
+//! text, type, labels, and how to read it from `state.json`. The text
+//! exposition ([`render_prometheus`]) and the dashboard
+//! ([`grafana_dashboard`]) are both generated from these definitions, so a
+//! renamed metric changes the panels that query it in the same commit.
+
+#![allow(missing_docs)]
+#![allow(clippy::cast_precision_loss)]
+
+use std::fmt::Write as _;
+
+use serde_json::{Value, json};
+
+use crate::core::state::DaemonState;
+
+/// Prometheus metric type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+impl MetricKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gauge => "gauge",
+            Self::Counter => "counter",
+        }
+    }
+}
+
+/// One labelled value of a metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+impl Sample {
+    fn unlabelled(value: f64) -> Self {
+        Self {
+            labels: Vec::new(),
+            value,
+        }
+    }
+}
+
+/// A metric declaration: what is exported and how it is read from state.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricDef {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: MetricKind,
+    pub labels: &'static [&'static str],
+    pub collect: fn(&DaemonState) -> Vec<Sample>,
+}
+
+// ──────────────────── registry ────────────────────
+
+pub const MOUNT_FREE_PERCENT: MetricDef = MetricDef {
+    name: "sbh_mount_free_percent",
+    help: "Free space on the mount as a percentage of its size.",
+    kind: MetricKind::Gauge,
+    labels: &["mount"],
+    collect: |state| {
+        state
+            .pressure
+            .mounts
+            .iter()
+            .map(|mount| mount_sample(&mount.path, mount.free_pct))
+            .collect()
+    },
+};
+
+pub const MOUNT_PRESSURE_LEVEL: MetricDef = MetricDef {
+    name: "sbh_mount_pressure_level",
+    help: "Pressure level on the mount (0=green, 1=yellow, 2=orange, 3=red, 4=critical).",
+    kind: MetricKind::Gauge,
+    labels: &["mount"],
+    collect: |state| {
+        state
+            .pressure
+            .mounts
+            .iter()
+            .filter_map(|mount| {
+                let code = code_of(PRESSURE_LEVELS, &mount.level)?;
+                Some(mount_sample(&mount.path, code))
+            })
+            .collect()
+    },
+};
+
+pub const MOUNT_FILL_RATE: MetricDef = MetricDef {
+    name: "sbh_mount_fill_rate_bytes_per_second",
+    help: "EWMA rate at which the mount is filling (negative while freeing).",
+    kind: MetricKind::Gauge,
+    labels: &["mount"],
+    collect: |state| {
+        state
+            .pressure
+            .mounts
+            .iter()
+            .filter_map(|mount| Some(mount_sample(&mount.path, mount.rate_bps?)))
+            .collect()
+    },
+};
+
+pub const BYTES_FREED_TOTAL: MetricDef = MetricDef {
+    name: "sbh_bytes_freed_total",
+    help: "Bytes reclaimed by artifact deletion since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.counters.bytes_freed as f64)],
+};
+
+pub const DELETIONS_TOTAL: MetricDef = MetricDef {
+    name: "sbh_deletions_total",
+    help: "Artifacts deleted since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.counters.deletions as f64)],
+};
+
+pub const SCANS_TOTAL: MetricDef = MetricDef {
+    name: "sbh_scans_total",
+    help: "Scans completed since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.counters.scans as f64)],
+};
+
+pub const ERRORS_TOTAL: MetricDef = MetricDef {
+    name: "sbh_errors_total",
+    help: "Errors recorded since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.counters.errors as f64)],
+};
+
+pub const BALLAST_FILES: MetricDef = MetricDef {
+    name: "sbh_ballast_files",
+    help: "Ballast files by state.",
+    kind: MetricKind::Gauge,
+    labels: &["state"],
+    collect: |state| {
+        [
+            ("available", state.ballast.available),
+            ("released", state.ballast.released),
+        ]
+        .into_iter()
+        .map(|(name, count)| Sample {
+            labels: vec![("state", name.to_string())],
+            value: count as f64,
+        })
+        .collect()
+    },
+};
+
+pub const BALLAST_VERIFIED_TOTAL: MetricDef = MetricDef {
+    name: "sbh_ballast_files_verified_total",
+    help: "Ballast files checked by the integrity pass since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.ballast.files_verified as f64)],
+};
+
+pub const BALLAST_CORRUPTED_TOTAL: MetricDef = MetricDef {
+    name: "sbh_ballast_files_corrupted_total",
+    help: "Ballast files that failed the integrity pass since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.ballast.files_corrupted as f64)],
+};
+
+pub const BALLAST_REPAIRED_TOTAL: MetricDef = MetricDef {
+    name: "sbh_ballast_files_repaired_total",
+    help: "Corrupted ballast files re-created since the daemon started.",
+    kind: MetricKind::Counter,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.ballast.files_repaired as f64)],
+};
+
+pub const POLICY_MODE: MetricDef = MetricDef {
+    name: "sbh_policy_mode",
+    help: "Active policy mode (0=observe, 1=canary, 2=enforce, 3=fallback_safe).",
+    kind: MetricKind::Gauge,
+    labels: &[],
+    collect: |state| {
+        code_of(POLICY_MODES, &state.policy_mode)
+            .map(Sample::unlabelled)
+            .into_iter()
+            .collect()
+    },
+};
+
+pub const UPTIME_SECONDS: MetricDef = MetricDef {
+    name: "sbh_daemon_uptime_seconds",
+    help: "Seconds since the daemon started.",
+    kind: MetricKind::Gauge,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.uptime_seconds as f64)],
+};
+
+pub const MEMORY_RSS_BYTES: MetricDef = MetricDef {
+    name: "sbh_memory_rss_bytes",
+    help: "Resident memory of the daemon process.",
+    kind: MetricKind::Gauge,
+    labels: &[],
+    collect: |state| vec![Sample::unlabelled(state.memory_rss_bytes as f64)],
+};
+
+/// Every exported metric, in exposition order.
+pub const METRICS: &[MetricDef] = &[
+    MOUNT_FREE_PERCENT,
+    MOUNT_PRESSURE_LEVEL,
+    MOUNT_FILL_RATE,
+    BYTES_FREED_TOTAL,
+    DELETIONS_TOTAL,
+    SCANS_TOTAL,
+    ERRORS_TOTAL,
+    BALLAST_FILES,
+    BALLAST_VERIFIED_TOTAL,
+    BALLAST_CORRUPTED_TOTAL,
+    BALLAST_REPAIRED_TOTAL,
+    POLICY_MODE,
+    UPTIME_SECONDS,
+    MEMORY_RSS_BYTES,
+];
+
+/// Pressure level names as written to `state.json`, indexed by gauge value.
+const PRESSURE_LEVELS: &[&str] = &["green", "yellow", "orange", "red", "critical"];
+/// Dashboard colors for [`PRESSURE_LEVELS`].
+const PRESSURE_COLORS: &[&str] = &["green", "yellow", "orange", "red", "dark-red"];
+/// Policy mode names as written to `state.json`, indexed by gauge value.
+const POLICY_MODES: &[&str] = &["observe", "canary", "enforce", "fallback_safe"];
+/// Dashboard colors for [`POLICY_MODES`].
+const POLICY_COLORS: &[&str] = &["blue", "yellow", "green", "red"];
+
+fn mount_sample(path: &str, value: f64) -> Sample {
+    Sample {
+        labels: vec![("mount", path.to_string())],
+        value,
+    }
+}
+
+/// Gauge value `sbh_mount_pressure_level` uses for a level name.
+#[must_use]
+pub fn pressure_level_code(level: &str) -> Option<f64> {
+    code_of(PRESSURE_LEVELS, level)
+}
+
+fn code_of(names: &[&str], value: &str) -> Option<f64> {
+    names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(value))
+        .map(|index| index as f64)
+}
+
+// ──────────────────── Prometheus exposition ────────────────────
+
+/// Render `state` in the Prometheus text exposition format.
+#[must_use]
+pub fn render_prometheus(state: &DaemonState) -> String {
+    let mut out = String::new();
+    for metric in METRICS {
+        let _ = writeln!(out, "# HELP {} {}", metric.name, metric.help);
+        let _ = writeln!(out, "# TYPE {} {}", metric.name, metric.kind.as_str());
+        for sample in (metric.collect)(state) {
+            out.push_str(metric.name);
+            if !sample.labels.is_empty() {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                    .collect();
+                let _ = write!(out, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(out, " {}", sample.value);
+        }
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// ──────────────────── Grafana dashboard ────────────────────
+
+/// Datasource reference resolved by Grafana's import dialog.
+const DATASOURCE_INPUT: &str = "DS_PROMETHEUS";
+
+/// How a panel query reads its metric.
+#[derive(Debug, Clone, Copy)]
+enum PanelQuery {
+    /// A per-mount gauge, filtered by the `$mount` variable.
+    PerMount(&'static str),
+    /// A gauge as exported.
+    Gauge(&'static str),
+    /// `rate(counter[5m])`.
+    Rate(&'static str),
+    /// `increase(counter[window])`.
+    Increase(&'static str, &'static str),
+}
+
+impl PanelQuery {
+    fn expr(self) -> String {
+        match self {
+            Self::PerMount(name) => format!("{name}{{mount=~\"$mount\"}}"),
+            Self::Gauge(name) => name.to_string(),
+            Self::Rate(name) => format!("rate({name}[5m])"),
+            Self::Increase(name, window) => format!("increase({name}[{window}])"),
+        }
+    }
+}
+
+/// One dashboard panel. `grid` is `(x, y, w, h)`; `mappings` names the
+/// codes of an enum-valued gauge and their colors.
+struct PanelSpec {
+    title: &'static str,
+    kind: &'static str,
+    grid: (u32, u32, u32, u32),
+    queries: &'static [(PanelQuery, &'static str)],
+    unit: &'static str,
+    mappings: Option<(&'static [&'static str], &'static [&'static str])>,
+}
+
+/// The dashboard layout, top to bottom; panel ids follow this order.
+const PANELS: &[PanelSpec] = &[
+    PanelSpec {
+        title: "Pressure per mount",
+        kind: "state-timeline",
+        grid: (0, 0, 24, 8),
+        queries: &[(PanelQuery::PerMount(MOUNT_PRESSURE_LEVEL.name), "{{mount}}")],
+        unit: "none",
+        mappings: Some((PRESSURE_LEVELS, PRESSURE_COLORS)),
+    },
+    PanelSpec {
+        title: "Free space per mount",
+        kind: "timeseries",
+        grid: (0, 8, 12, 8),
+        queries: &[(PanelQuery::PerMount(MOUNT_FREE_PERCENT.name), "{{mount}}")],
+        unit: "percent",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Fill rate per mount",
+        kind: "timeseries",
+        grid: (12, 8, 12, 8),
+        queries: &[(PanelQuery::PerMount(MOUNT_FILL_RATE.name), "{{mount}}")],
+        unit: "Bps",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Reclaim rate",
+        kind: "timeseries",
+        grid: (0, 16, 12, 8),
+        queries: &[(PanelQuery::Rate(BYTES_FREED_TOTAL.name), "bytes freed")],
+        unit: "Bps",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Deletions, scans, and errors",
+        kind: "timeseries",
+        grid: (12, 16, 12, 8),
+        queries: &[
+            (
+                PanelQuery::Increase(DELETIONS_TOTAL.name, "5m"),
+                "deletions",
+            ),
+            (PanelQuery::Increase(SCANS_TOTAL.name, "5m"), "scans"),
+            (PanelQuery::Increase(ERRORS_TOTAL.name, "5m"), "errors"),
+        ],
+        unit: "short",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Ballast inventory",
+        kind: "bargauge",
+        grid: (0, 24, 8, 8),
+        queries: &[(PanelQuery::Gauge(BALLAST_FILES.name), "{{state}}")],
+        unit: "short",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Policy mode",
+        kind: "state-timeline",
+        grid: (8, 24, 16, 4),
+        queries: &[(PanelQuery::Gauge(POLICY_MODE.name), "mode")],
+        unit: "none",
+        mappings: Some((POLICY_MODES, POLICY_COLORS)),
+    },
+    PanelSpec {
+        title: "Daemon uptime",
+        kind: "stat",
+        grid: (8, 28, 8, 4),
+        queries: &[(PanelQuery::Gauge(UPTIME_SECONDS.name), "uptime")],
+        unit: "s",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Daemon memory",
+        kind: "stat",
+        grid: (16, 28, 8, 4),
+        queries: &[(PanelQuery::Gauge(MEMORY_RSS_BYTES.name), "rss")],
+        unit: "bytes",
+        mappings: None,
+    },
+    PanelSpec {
+        title: "Ballast integrity",
+        kind: "timeseries",
+        grid: (0, 32, 24, 8),
+        queries: &[
+            (
+                PanelQuery::Increase(BALLAST_VERIFIED_TOTAL.name, "1h"),
+                "verified",
+            ),
+            (
+                PanelQuery::Increase(BALLAST_CORRUPTED_TOTAL.name, "1h"),
+                "corrupted",
+            ),
+            (
+                PanelQuery::Increase(BALLAST_REPAIRED_TOTAL.name, "1h"),
+                "repaired",
+            ),
+        ],
+        unit: "short",
+        mappings: None,
+    },
+];
+
+/// Build a ready-to-import Grafana dashboard over the registry's metrics.
+///
+/// With `datasource_uid` the panels point at that datasource directly;
+/// otherwise the dashboard declares a `DS_PROMETHEUS` input that Grafana
+/// asks for on import.
+#[must_use]
+pub fn grafana_dashboard(datasource_uid: Option<&str>) -> Value {
+    let uid = datasource_uid.map_or_else(|| format!("${{{DATASOURCE_INPUT}}}"), str::to_string);
+    let datasource = json!({"type": "prometheus", "uid": uid});
+    let panels: Vec<Value> = PANELS
+        .iter()
+        .zip(1..)
+        .map(|(spec, id)| panel(id, spec, &datasource))
+        .collect();
+
+    let mut dashboard = json!({
+        "uid": "sbh-overview",
+        "title": "Storage Ballast Helper",
+        "tags": ["sbh", "disk"],
+        "editable": true,
+        "schemaVersion": 39,
+        "refresh": "30s",
+        "time": {"from": "now-24h", "to": "now"},
+        "templating": {"list": [{
+            "name": "mount",
+            "label": "Mount",
+            "type": "query",
+            "datasource": datasource,
+            "query": format!("label_values({}, mount)", MOUNT_FREE_PERCENT.name),
+            "refresh": 2,
+            "multi": true,
+            "includeAll": true,
+            "current": {"text": "All", "value": "$__all"},
+        }]},
+        "panels": panels,
+    });
+    if datasource_uid.is_none() {
+        dashboard["__inputs"] = json!([{
+            "name": DATASOURCE_INPUT,
+            "label": "Prometheus",
+            "type": "datasource",
+            "pluginId": "prometheus",
+            "pluginName": "Prometheus",
+        }]);
+    }
+    dashboard
+}
+
+fn panel(id: u32, spec: &PanelSpec, datasource: &Value) -> Value {
+    let targets: Vec<Value> = spec
+        .queries
+        .iter()
+        .zip('A'..)
+        .map(|((query, legend), ref_id)| {
+            json!({
+                "refId": ref_id.to_string(),
+                "expr": query.expr(),
+                "legendFormat": legend,
+                "datasource": datasource,
+            })
+        })
+        .collect();
+    let mappings = spec
+        .mappings
+        .map_or_else(Vec::new, |(names, colors)| value_mappings(names, colors));
+    let (x, y, w, h) = spec.grid;
+    json!({
+        "id": id,
+        "title": spec.title,
+        "type": spec.kind,
+        "gridPos": {"x": x, "y": y, "w": w, "h": h},
+        "datasource": datasource,
+        "targets": targets,
+        "fieldConfig": {"defaults": {"unit": spec.unit, "mappings": mappings}, "overrides": []},
+    })
+}
+
+/// A single Grafana value mapping turning gauge codes back into names.
+fn value_mappings(names: &[&str], colors: &[&str]) -> Vec<Value> {
+    let options: serde_json::Map<String, Value> = names
+        .iter()
+        .zip(colors)
+        .enumerate()
+        .map(|(index, (name, color))| {
+            (
+                index.to_string(),
+                json!({"text": name, "color": color, "index": index}),
+            )
+        })
+        .collect();
+    vec![json!({"type": "value", "options": options})]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::core::state::{BallastState, Counters, MountPressure, PressureState};
+
+    fn sample_state() -> DaemonState {
+        DaemonState {
+            uptime_seconds: 3_600,
+            pressure: PressureState {
+                overall: "orange".to_string(),
+                mounts: vec![
+                    MountPressure {
+                        path: "/data".to_string(),
+                        free_pct: 12.5,
+                        level: "orange".to_string(),
+                        rate_bps: Some(2_048.0),
+                    },
+                    MountPressure {
+                        path: "/odd\"mount".to_string(),
+                        free_pct: 80.0,
+                        level: "green".to_string(),
+                        rate_bps: None,
+                    },
+                ],
+            },
+            ballast: BallastState {
+                available: 7,
+                total: 10,
+                released: 3,
+                files_verified: 40,
+                files_corrupted: 2,
+                files_repaired: 1,
+            },
+            counters: Counters {
+                bytes_freed: 1_000_000,
+                deletions: 4,
+                ..Counters::default()
+            },
+            policy_mode: "enforce".to_string(),
+            ..DaemonState::default()
+        }
+    }
+
+    #[test]
+    fn exposition_declares_every_metric_and_renders_samples() {
+        let text = render_prometheus(&sample_state());
+        for metric in METRICS {
+            assert!(text.contains(&format!("# TYPE {} ", metric.name)));
+            for sample in (metric.collect)(&sample_state()) {
+                let keys: Vec<&str> = sample.labels.iter().map(|(key, _)| *key).collect();
+                assert_eq!(keys, metric.labels, "{} labels", metric.name);
+            }
+        }
+        assert!(text.contains("sbh_mount_free_percent{mount=\"/data\"} 12.5\n"));
+        assert!(text.contains("sbh_mount_pressure_level{mount=\"/data\"} 2\n"));
+        assert!(text.contains("sbh_mount_pressure_level{mount=\"/odd\\\"mount\"} 0\n"));
+        assert!(text.contains("sbh_mount_fill_rate_bytes_per_second{mount=\"/data\"} 2048\n"));
+        assert!(!text.contains("sbh_mount_fill_rate_bytes_per_second{mount=\"/odd"));
+        assert!(text.contains("sbh_bytes_freed_total 1000000\n"));
+        assert!(text.contains("sbh_ballast_files{state=\"released\"} 3\n"));
+        assert!(text.contains("sbh_ballast_files_corrupted_total 2\n"));
+        assert!(text.contains("sbh_policy_mode 2\n"));
+    }
+
+    #[test]
+    fn metric_names_are_unique_and_prefixed() {
+        let names: BTreeSet<&str> = METRICS.iter().map(|metric| metric.name).collect();
+        assert_eq!(names.len(), METRICS.len());
+        assert!(names.iter().all(|name| name.starts_with("sbh_")));
+        assert!(
+            METRICS
+                .iter()
+                .filter(|metric| metric.kind == MetricKind::Counter)
+                .all(|metric| metric.name.ends_with("_total"))
+        );
+    }
+
+    /// Every `sbh_*` series a panel queries must exist in the registry, and
+    /// every registry metric must be charted somewhere.
+    #[test]
+    fn dashboard_queries_match_the_registry() {
+        let dashboard = grafana_dashboard(None);
+        let registry: BTreeSet<&str> = METRICS.iter().map(|metric| metric.name).collect();
+        let mut queried = BTreeSet::new();
+        for panel in dashboard["panels"].as_array().unwrap() {
+            for target in panel["targets"].as_array().unwrap() {
+                let expr = target["expr"].as_str().unwrap();
+                for token in expr.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')) {
+                    if token.starts_with("sbh_") {
+                        assert!(registry.contains(token), "unknown metric {token} in {expr}");
+                        queried.insert(token.to_string());
+                    }
+                }
+            }
+        }
+        let queried: BTreeSet<&str> = queried.iter().map(String::as_str).collect();
+        assert_eq!(queried, registry);
+    }
+
+    #[test]
+    fn datasource_uid_replaces_the_import_input() {
+        let templated = grafana_dashboard(None);
+        assert_eq!(templated["__inputs"][0]["name"], DATASOURCE_INPUT);
+        assert_eq!(
+            templated["panels"][0]["datasource"]["uid"],
+            "${DS_PROMETHEUS}"
+        );
+
+        let pinned = grafana_dashboard(Some("prom-main"));
+        assert!(pinned.get("__inputs").is_none());
+        assert_eq!(
+            pinned["panels"][0]["targets"][0]["datasource"]["uid"],
+            "prom-main"
+        );
+    }
+}
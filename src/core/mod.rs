@@ -1,6 +1,13 @@
 //! Core types: errors, configuration, shared constants.
 
 pub mod config;
+pub mod container;
 pub mod errors;
+pub mod maintenance;
+pub mod marker;
+pub mod metrics;
 pub mod paths;
+pub mod probation;
+pub mod schedule;
+pub mod state;
 pub mod update_cache;
@@ -26,6 +26,15 @@ pub fn resolve_absolute_path(path: &Path) -> PathBuf {
     normalize_syntactic(&absolute)
 }
 
+/// First `program` on `PATH`, for features that run an external tool.
+#[must_use]
+pub fn find_on_path(program: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
 fn normalize_syntactic(path: &Path) -> PathBuf {
     let mut components = Vec::new();
     for component in path.components() {
@@ -0,0 +1,234 @@
+//! Config probation: `sbh tune --apply` and `sbh config set` can be undone
+//! automatically when a change to enforcement settings goes badly.
+//!
+//! Before writing such a change the CLI saves the file it replaces in
+//! `probation.json` next to `state.json`. Once the daemon has loaded the new
+//! config it remembers how many times the policy engine has fallen back to
+//! safe mode. If that count rises before the window closes, the daemon writes
+//! the saved file back and reloads it. A window that closes quietly just
+//! deletes the marker.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::marker;
+
+/// File name of the probation marker, a sibling of `state.json`.
+pub const PROBATION_FILE_NAME: &str = "probation.json";
+
+/// Default probation window for `--revert-window`, in minutes.
+pub const DEFAULT_REVERT_WINDOW_MINUTES: u64 = 30;
+
+/// Config sections that decide what gets deleted and when. A change under
+/// any of them starts probation.
+pub const ENFORCEMENT_SECTIONS: &[&str] = &["pressure", "scoring", "scanner", "ballast", "policy"];
+
+/// Whether a dot-path config key belongs to an enforcement section.
+#[must_use]
+pub fn is_enforcement_key(key: &str) -> bool {
+    let section = key.split('.').next().unwrap_or_default();
+    ENFORCEMENT_SECTIONS.contains(&section)
+}
+
+/// A config change on probation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigProbation {
+    /// Command that made the change (`tune` or `config set`).
+    pub source: String,
+    /// Enforcement keys changed, in the order they were applied.
+    pub keys: Vec<String>,
+    /// Config file contents before the change; empty when there was no file.
+    pub previous_config: String,
+    /// RFC 3339 time the change was written.
+    pub started_at: String,
+    /// RFC 3339 time the change is kept for good.
+    pub until: String,
+}
+
+impl ConfigProbation {
+    /// Probation for a change written at `now`, lasting `window`.
+    #[must_use]
+    pub fn new(
+        now: DateTime<Utc>,
+        window: Duration,
+        source: &str,
+        keys: Vec<String>,
+        previous_config: String,
+    ) -> Self {
+        let until = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|d| now.checked_add_signed(d))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        Self {
+            source: source.to_string(),
+            keys,
+            previous_config,
+            started_at: now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            until: until.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+
+    /// Fold a newer change into this probation. The older saved config is
+    /// kept, since it is the last one known to behave, and the window
+    /// restarts from the newer change.
+    #[must_use]
+    pub fn extend(mut self, newer: Self) -> Self {
+        for key in newer.keys {
+            if !self.keys.contains(&key) {
+                self.keys.push(key);
+            }
+        }
+        self.source = newer.source;
+        self.started_at = newer.started_at;
+        self.until = newer.until;
+        self
+    }
+
+    /// When the change was written; `None` if `started_at` does not parse.
+    #[must_use]
+    pub fn started(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.started_at)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Whether the window is still open at `now`. An unreadable deadline
+    /// counts as closed.
+    #[must_use]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.until).is_ok_and(|until| now < until)
+    }
+}
+
+/// Probation marker path for the given `state.json` path.
+#[must_use]
+pub fn probation_path(state_file: &Path) -> PathBuf {
+    state_file.with_file_name(PROBATION_FILE_NAME)
+}
+
+/// Read the marker; `Ok(None)` when there is none.
+pub fn read_probation(path: &Path) -> io::Result<Option<ConfigProbation>> {
+    marker::read_marker(path)
+}
+
+/// Write the marker owner-only: the saved config may hold webhook tokens
+/// or other secrets.
+pub fn write_probation(path: &Path, probation: &ConfigProbation) -> io::Result<()> {
+    marker::write_marker(path, probation, 0o600)
+}
+
+/// Remove the marker. Returns whether one existed.
+pub fn clear_probation(path: &Path) -> io::Result<bool> {
+    marker::clear_marker(path)
+}
+
+/// Put the saved config back in place of `config_file`, keeping the
+/// current file's permissions (owner-only when there is none).
+pub fn restore_previous_config(config_file: &Path, probation: &ConfigProbation) -> io::Result<()> {
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(config_file).map_or(0o600, |meta| meta.permissions().mode() & 0o7777)
+    };
+    #[cfg(not(unix))]
+    let mode = 0o600;
+    marker::replace_file(config_file, probation.previous_config.as_bytes(), mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn enforcement_keys_are_matched_by_section() {
+        assert!(is_enforcement_key("scoring.min_score"));
+        assert!(is_enforcement_key("pressure.red_min_free_pct"));
+        assert!(is_enforcement_key("policy"));
+        assert!(!is_enforcement_key("notifications.enabled"));
+        assert!(!is_enforcement_key("paths.state_file"));
+        assert!(!is_enforcement_key("scoringx.min_score"));
+    }
+
+    #[test]
+    fn extending_keeps_the_oldest_config_and_restarts_the_window() {
+        let first = ConfigProbation::new(
+            at("2026-10-16T12:00:00Z"),
+            Duration::from_secs(1800),
+            "tune",
+            vec!["scoring.min_score".to_string()],
+            "[scoring]\nmin_score = 0.7\n".to_string(),
+        );
+        assert!(first.is_active(at("2026-10-16T12:29:59Z")));
+        assert!(!first.is_active(at("2026-10-16T12:30:00Z")));
+
+        let second = ConfigProbation::new(
+            at("2026-10-16T12:20:00Z"),
+            Duration::from_secs(1800),
+            "config set",
+            vec![
+                "scoring.min_score".to_string(),
+                "scanner.max_delete_batch".to_string(),
+            ],
+            "[scoring]\nmin_score = 0.6\n".to_string(),
+        );
+        let merged = first.extend(second);
+        assert_eq!(merged.previous_config, "[scoring]\nmin_score = 0.7\n");
+        assert_eq!(
+            merged.keys,
+            ["scoring.min_score", "scanner.max_delete_batch"]
+        );
+        assert_eq!(merged.source, "config set");
+        assert_eq!(merged.started(), Some(at("2026-10-16T12:20:00Z")));
+        assert!(merged.is_active(at("2026-10-16T12:45:00Z")));
+    }
+
+    #[test]
+    fn marker_round_trips_and_restores_the_saved_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = probation_path(&dir.path().join("state.json"));
+        assert_eq!(path, dir.path().join("probation.json"));
+        assert_eq!(read_probation(&path).unwrap(), None);
+
+        let probation = ConfigProbation::new(
+            Utc::now(),
+            Duration::from_secs(600),
+            "tune",
+            vec!["scoring.min_score".to_string()],
+            "[scoring]\nmin_score = 0.7\n".to_string(),
+        );
+        write_probation(&path, &probation).unwrap();
+        assert_eq!(read_probation(&path).unwrap(), Some(probation.clone()));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "the saved config may hold secrets");
+        }
+
+        let config_file = dir.path().join("config.toml");
+        fs::write(&config_file, "[scoring]\nmin_score = 0.4\n").unwrap();
+        restore_previous_config(&config_file, &probation).unwrap();
+        assert_eq!(
+            fs::read_to_string(&config_file).unwrap(),
+            "[scoring]\nmin_score = 0.7\n"
+        );
+
+        assert!(clear_probation(&path).unwrap());
+        assert!(!clear_probation(&path).unwrap());
+        fs::write(&path, "{not json").unwrap();
+        assert!(read_probation(&path).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Cron-style schedules for daemon jobs (`scanner.deep_scan_schedule`).
+//!
+//! The usual five fields, `minute hour day-of-month month day-of-week`, in
+//! local time. Each field takes `*`, a number, a range `a-b`, a step `*/n`
+//! or `a-b/n`, or a comma list of those. Day of week runs 0-7 with both 0 and
+//! 7 meaning Sunday. As in Vixie cron, when both day fields are restricted a
+//! day matching either one is enough. Month and weekday names are not
+//! accepted.
+
+#![allow(missing_docs)]
+
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::core::errors::{Result, SbhError};
+
+/// Years searched for the next run before a schedule counts as never firing
+/// (covers `0 0 29 2 *` across a leap-year gap).
+const SEARCH_YEARS: i64 = 8;
+
+/// A parsed five-field cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day of month was `*`, so only the weekday restricts days (and the
+    /// reverse for `any_weekday`).
+    any_day_of_month: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    /// Parse `expr`, e.g. `"0 3 * * *"` for 03:00 every day.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(invalid(
+                expr,
+                &format!("expected 5 fields, found {}", fields.len()),
+            ));
+        };
+        let mut days_of_week = parse_field(expr, "day of week", weekday, 0, 7)?;
+        // 7 is Sunday too.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            source: fields.join(" "),
+            minutes: parse_field(expr, "minute", minute, 0, 59)?,
+            hours: parse_field(expr, "hour", hour, 0, 23)?,
+            days_of_month: parse_field(expr, "day of month", day, 1, 31)?,
+            months: parse_field(expr, "month", month, 1, 12)?,
+            days_of_week,
+            any_day_of_month: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    /// Whether the schedule fires in the minute holding `at`.
+    #[must_use]
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        self.matches_date(at.date()) && bit(self.hours, at.hour()) && bit(self.minutes, at.minute())
+    }
+
+    /// The first minute strictly after `after` the schedule fires in, or
+    /// `None` when it never fires (`0 0 31 2 *`).
+    #[must_use]
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date();
+        let last = date + Duration::days(366 * SEARCH_YEARS);
+        while date <= last {
+            if self.matches_date(date) {
+                let from = if date == start.date() {
+                    start.time()
+                } else {
+                    NaiveTime::MIN
+                };
+                if let Some(time) = self.first_time_from(from) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !bit(self.months, date.month()) {
+            return false;
+        }
+        let by_day = bit(self.days_of_month, date.day());
+        let by_weekday = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => by_day,
+            (true, false) => by_weekday,
+            (false, false) => by_day || by_weekday,
+        }
+    }
+
+    /// Earliest firing time of day at or after `from`.
+    fn first_time_from(&self, from: NaiveTime) -> Option<NaiveTime> {
+        (from.hour()..24)
+            .filter(|hour| bit(self.hours, *hour))
+            .find_map(|hour| {
+                let first_minute = if hour == from.hour() {
+                    from.minute()
+                } else {
+                    0
+                };
+                (first_minute..60)
+                    .find(|minute| bit(self.minutes, *minute))
+                    .and_then(|minute| NaiveTime::from_hms_opt(hour, minute, 0))
+            })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+const fn bit(mask: u64, value: u32) -> bool {
+    value < 64 && mask & (1 << value) != 0
+}
+
+/// Bitmask of the values `field` selects within `min..=max`.
+fn parse_field(expr: &str, name: &str, field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = parse_value(expr, name, step)?;
+                if step == 0 {
+                    return Err(invalid(expr, &format!("{name} step must be >= 1")));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (
+                parse_value(expr, name, low)?,
+                parse_value(expr, name, high)?,
+            )
+        } else {
+            let value = parse_value(expr, name, range)?;
+            // `5/15` runs from 5 to the end of the range.
+            (value, if part.contains('/') { max } else { value })
+        };
+        if low < min || high > max || low > high {
+            return Err(invalid(
+                expr,
+                &format!("{name} {range:?} is outside {min}-{max}"),
+            ));
+        }
+        for value in (low..=high).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(expr: &str, name: &str, raw: &str) -> Result<u32> {
+    raw.parse::<u32>()
+        .map_err(|_| invalid(expr, &format!("{name} {raw:?} is not a number")))
+}
+
+fn invalid(expr: &str, why: &str) -> SbhError {
+    SbhError::InvalidConfig {
+        details: format!("invalid cron schedule {expr:?}: {why}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn daily_schedule_fires_once_a_day() {
+        let schedule = CronSchedule::parse("0 3 * * *").unwrap();
+        assert!(schedule.matches(at("2026-03-10 03:00")));
+        assert!(!schedule.matches(at("2026-03-10 03:01")));
+        assert_eq!(
+            schedule.next_after(at("2026-03-10 02:59")),
+            Some(at("2026-03-10 03:00"))
+        );
+        assert_eq!(
+            schedule.next_after(at("2026-03-10 03:00")),
+            Some(at("2026-03-11 03:00"))
+        );
+    }
+
+    #[test]
+    fn ranges_steps_and_lists() {
+        let schedule = CronSchedule::parse("*/15 1-5/2 * * 1,3").unwrap();
+        // 2026-03-09 is a Monday.
+        assert!(schedule.matches(at("2026-03-09 03:45")));
+        assert!(!schedule.matches(at("2026-03-09 02:45")));
+        assert!(!schedule.matches(at("2026-03-10 03:45")));
+        assert_eq!(
+            schedule.next_after(at("2026-03-09 05:45")),
+            Some(at("2026-03-11 01:00"))
+        );
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven_and_day_fields_combine_with_or() {
+        // 2026-03-15 is a Sunday.
+        let sunday = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert!(sunday.matches(at("2026-03-15 00:00")));
+        let either = CronSchedule::parse("0 0 1 * 0").unwrap();
+        assert!(either.matches(at("2026-03-15 00:00")));
+        assert!(either.matches(at("2026-04-01 00:00")));
+        assert!(!either.matches(at("2026-04-02 00:00")));
+    }
+
+    #[test]
+    fn impossible_dates_never_fire() {
+        let schedule = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert_eq!(schedule.next_after(at("2026-01-01 00:00")), None);
+        let leap = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(at("2026-01-01 00:00")),
+            Some(at("2028-02-29 00:00"))
+        );
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        for expr in [
+            "",
+            "0 3 * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "*/0 * * * *",
+            "a * * * *",
+        ] {
+            let err = CronSchedule::parse(expr).unwrap_err();
+            assert!(err.to_string().contains("cron"), "{expr}: {err}");
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//! `state.json` schema and the one reader every consumer goes through.
+//!
+//! The daemon writes this file (see `daemon::self_monitor`); `sbh status`,
+//! `sbh check`, and the dashboard read it with [`read_state`], which checks
+//! the schema version, decodes the typed [`DaemonState`], and reports how old
+//! the file is. Keeping a single typed model means a renamed field fails to
+//! compile in every consumer instead of silently reading as absent in one.
+
+#![allow(missing_docs)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::maintenance::MaintenanceWindow;
+
+// ──────────────────── constants ────────────────────
+
+/// How often the daemon writes `state.json` (seconds).
+pub const DAEMON_STATE_WRITE_INTERVAL_SECS: u64 = 30;
+
+/// Floor for treating `state.json` as stale (seconds).
+///
+/// Must be `>= 2 × DAEMON_STATE_WRITE_INTERVAL_SECS` so that CLI commands
+/// (`sbh status`, `sbh check`, `read_daemon_prediction`) never report the
+/// daemon as absent simply because a write cycle hasn't completed yet.
+pub const DAEMON_STATE_STALE_THRESHOLD_SECS: u64 = 90;
+
+/// Layout version written into `state.json` as `state_schema`.
+///
+/// Bump when a field changes meaning or type. Readers accept this version and
+/// the one before it; files written before the field existed read as 0.
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+// ──────────────────── state file schema ────────────────────
+
+/// Top-level state written to `state.json` for CLI consumption.
+///
+/// All fields use `#[serde(default)]` so that minor schema evolution
+/// (new fields added by a newer daemon, or old fields removed) does not
+/// hard-fail deserialization. The dashboard adapter layer detects drift
+/// and surfaces warnings rather than crashing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonState {
+    /// Layout version (see [`STATE_SCHEMA_VERSION`]); 0 when absent.
+    pub state_schema: u32,
+    pub version: String,
+    pub pid: u32,
+    pub started_at: String,
+    pub uptime_seconds: u64,
+    pub last_updated: String,
+    pub pressure: PressureState,
+    pub ballast: BallastState,
+    pub last_scan: LastScanState,
+    pub counters: Counters,
+    pub memory_rss_bytes: u64,
+    /// Active policy engine mode (enforce/observe/canary/fallback_safe).
+    pub policy_mode: String,
+    /// Latest EWMA fill rate per monitored mount, keyed by canonical mount
+    /// point; each entry also carries the mount's device id (see [`MountKey`]).
+    pub rates: BTreeMap<String, MountRate>,
+    /// Maintenance window in force when the state was written (`sbh pause`).
+    pub maintenance: Option<MaintenanceWindow>,
+    /// Outcome of the latest config reload (SIGHUP); `None` until one runs.
+    pub config_reload: Option<ConfigReloadState>,
+    /// Mounts where automated cleanup is holding off after a recent pass,
+    /// keyed by mount point; absent once the mount is eligible again.
+    pub cleanup_cooldowns: BTreeMap<String, CleanupCooldown>,
+    /// Scheduled deep scans; `None` when `scanner.deep_scan_schedule` is off.
+    pub deep_scan: Option<DeepScanState>,
+}
+
+/// Current pressure across monitored mounts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PressureState {
+    pub overall: String,
+    pub mounts: Vec<MountPressure>,
+}
+
+/// Pressure info for a single mount.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MountPressure {
+    pub path: String,
+    #[serde(default)]
+    pub free_pct: f64,
+    pub level: String,
+    pub rate_bps: Option<f64>,
+}
+
+/// EWMA fill rate for one mount.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MountRate {
+    /// Device id of the mounted filesystem; `None` off Unix and from daemons
+    /// that predate it, in which case only the path key is matched.
+    pub device_id: Option<u64>,
+    /// Positive while the mount fills, negative while it recovers.
+    pub bytes_per_sec: f64,
+    /// Seconds until the red threshold at this rate; `None` when not filling.
+    pub seconds_to_threshold: Option<f64>,
+}
+
+/// Identity of a mounted filesystem that survives symlinks and bind mounts.
+///
+/// `/data`, `/mnt/data` (a symlink or bind of it), and any file beneath them
+/// resolve to the same device id even though their path strings differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountKey {
+    pub device_id: Option<u64>,
+    /// Canonical path (symlinks resolved); the input path if that fails.
+    pub path: PathBuf,
+}
+
+impl MountKey {
+    /// Resolve `path` to the key the daemon writes and readers look up.
+    #[must_use]
+    pub fn resolve(path: &Path) -> Self {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        Self {
+            device_id: device_id(&path),
+            path,
+        }
+    }
+
+    /// The `rates` map key for this mount.
+    #[must_use]
+    pub fn rate_key(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+}
+
+/// Filesystem id of `path`, following symlinks. `None` off Unix.
+#[cfg(unix)]
+pub(crate) fn device_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt as _;
+    fs::metadata(path).ok().map(|meta| meta.dev())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn device_id(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Current ballast file state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BallastState {
+    pub available: usize,
+    pub total: usize,
+    pub released: usize,
+    /// Ballast files checked by the daemon's periodic integrity pass.
+    pub files_verified: u64,
+    /// Checked files that failed verification; against `files_verified`
+    /// this is a disk-health signal.
+    pub files_corrupted: u64,
+    /// Corrupted files the daemon re-created.
+    pub files_repaired: u64,
+}
+
+impl BallastState {
+    /// Share of integrity checks that found a corrupted file, or `None`
+    /// before the first pass.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn corruption_pct(&self) -> Option<f64> {
+        (self.files_verified > 0)
+            .then(|| self.files_corrupted as f64 * 100.0 / self.files_verified as f64)
+    }
+}
+
+/// Last scan summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LastScanState {
+    pub at: Option<String>,
+    pub candidates: usize,
+    pub deleted: usize,
+}
+
+/// What happened to the latest config reload request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigReloadState {
+    /// RFC 3339 time the reload was handled.
+    pub at: String,
+    /// `applied`, `unchanged`, or `rejected`.
+    pub outcome: String,
+    /// Why a reload was rejected (parse or validation error).
+    pub reason: Option<String>,
+    /// Hash of the config in force afterwards.
+    pub config_hash: String,
+}
+
+/// When automated cleanup may next run on a mount.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanupCooldown {
+    /// RFC 3339 time the cooldown ends.
+    pub eligible_at: String,
+    /// Seconds left when the state was written.
+    pub remaining_secs: u64,
+}
+
+impl CleanupCooldown {
+    /// Time left at `now`; `None` once `eligible_at` has passed or when it
+    /// does not parse.
+    #[must_use]
+    pub fn remaining(&self, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+        let eligible = chrono::DateTime::parse_from_rfc3339(&self.eligible_at).ok()?;
+        (eligible.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .ok()
+            .filter(|left| !left.is_zero())
+    }
+}
+
+/// Timing of scheduled deep scans (`scanner.deep_scan_schedule`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeepScanState {
+    /// The cron expression in force.
+    pub schedule: String,
+    /// RFC 3339 time the latest deep scan started; `None` before the first
+    /// since the daemon started.
+    pub last_at: Option<String>,
+    /// RFC 3339 time of the next one; `None` if the schedule never fires.
+    pub next_at: Option<String>,
+}
+
+/// Cumulative counters since daemon start.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Counters {
+    pub scans: u64,
+    pub deletions: u64,
+    pub bytes_freed: u64,
+    pub errors: u64,
+    /// Log events silently dropped due to channel back-pressure.
+    pub dropped_log_events: u64,
+}
+
+// ──────────────────── reading ────────────────────
+
+/// Why a `state.json` payload could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateParseError {
+    /// Not a valid `DaemonState` document (truncated, corrupt, wrong shape).
+    Malformed(String),
+    /// Written by a daemon whose `state_schema` is newer than this binary.
+    NewerSchema(u32),
+}
+
+impl std::fmt::Display for StateParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(details) => write!(f, "invalid state file: {details}"),
+            Self::NewerSchema(schema) => write!(
+                f,
+                "state schema {schema} is newer than this binary supports \
+                 ({STATE_SCHEMA_VERSION}); upgrade sbh to read it"
+            ),
+        }
+    }
+}
+
+/// Why [`read_state`] returned no state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateReadError {
+    /// No state file: the daemon is not running or writes elsewhere.
+    Missing,
+    /// The file exists but could not be read (permissions, IO error).
+    Io(String),
+    /// The file was read but its contents are unusable.
+    Parse(StateParseError),
+}
+
+impl std::fmt::Display for StateReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "cannot read state file: not found"),
+            Self::Io(details) => write!(f, "cannot read state file: {details}"),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<StateParseError> for StateReadError {
+    fn from(err: StateParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<io::Error> for StateReadError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::NotFound => Self::Missing,
+            // Binary garbage / invalid UTF-8 is malformed content, not a read error.
+            io::ErrorKind::InvalidData => Self::Parse(StateParseError::Malformed(err.to_string())),
+            _ => Self::Io(err.to_string()),
+        }
+    }
+}
+
+/// A successfully decoded `state.json` and how old it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSnapshot {
+    pub state: DaemonState,
+    /// Time since the file was last written (zero if the clock went back).
+    pub age: Duration,
+    /// Top-level keys present in the file, for schema-drift reporting.
+    pub keys: BTreeSet<String>,
+}
+
+impl StateSnapshot {
+    /// Whether the daemon wrote the file recently enough to be trusted as live.
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        self.age <= Duration::from_secs(DAEMON_STATE_STALE_THRESHOLD_SECS)
+    }
+
+    /// The daemon's fill-rate estimate for the filesystem holding `key`.
+    ///
+    /// Entries match on device id when both sides have one, otherwise on the
+    /// entry's mount point containing `key.path`. Among matches the entry
+    /// whose mount point contains the path wins, then the deepest one.
+    #[must_use]
+    pub fn rate_for(&self, key: &MountKey) -> Option<&MountRate> {
+        self.state
+            .rates
+            .iter()
+            .filter(|(_, rate)| rate.bytes_per_sec.is_finite())
+            .filter_map(|(mount, rate)| {
+                let contains = key.path.starts_with(mount);
+                let same_device = match (key.device_id, rate.device_id) {
+                    (Some(wanted), Some(dev)) => wanted == dev,
+                    _ => contains,
+                };
+                same_device.then_some(((contains, mount.len()), rate))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, rate)| rate)
+    }
+}
+
+/// Read and decode `state.json`.
+///
+/// The schema is checked before the typed decode so a newer daemon's layout
+/// is reported as such rather than as corruption. Freshness is left to the
+/// caller via [`StateSnapshot::age`] / [`StateSnapshot::is_fresh`], since a
+/// stale file is still useful for post-mortem display.
+pub fn read_state(path: &Path) -> Result<StateSnapshot, StateReadError> {
+    let metadata = fs::metadata(path)?;
+    let raw = fs::read_to_string(path)?;
+    let (state, keys) = decode_state(&raw)?;
+
+    let age = SystemTime::now()
+        .duration_since(metadata.modified()?)
+        .unwrap_or_default();
+    Ok(StateSnapshot { state, age, keys })
+}
+
+/// Decode `state.json` contents, checking the schema as [`read_state`] does.
+pub fn parse_state(raw: &str) -> Result<DaemonState, StateParseError> {
+    decode_state(raw).map(|(state, _)| state)
+}
+
+fn decode_state(raw: &str) -> Result<(DaemonState, BTreeSet<String>), StateParseError> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| StateParseError::Malformed(e.to_string()))?;
+    check_state_schema(&value)?;
+    let keys = value
+        .as_object()
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default();
+    let state: DaemonState =
+        serde_json::from_value(value).map_err(|e| StateParseError::Malformed(e.to_string()))?;
+    Ok((state, keys))
+}
+
+/// Reject a document whose `state_schema` is newer than [`STATE_SCHEMA_VERSION`].
+fn check_state_schema(state: &serde_json::Value) -> Result<(), StateParseError> {
+    let schema = state
+        .get("state_schema")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(0, |schema| u32::try_from(schema).unwrap_or(u32::MAX));
+    if schema > STATE_SCHEMA_VERSION {
+        return Err(StateParseError::NewerSchema(schema));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &tempfile::TempDir, contents: &str) -> std::path::PathBuf {
+        let path = dir.path().join("state.json");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn state_schema_current_and_previous_parse_newer_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = serde_json::to_value(DaemonState {
+            state_schema: STATE_SCHEMA_VERSION,
+            pid: 7,
+            ..DaemonState::default()
+        })
+        .unwrap();
+        assert_eq!(written["state_schema"], STATE_SCHEMA_VERSION);
+        let snapshot = read_state(&write(&dir, &written.to_string())).unwrap();
+        assert_eq!(snapshot.state.pid, 7);
+        assert!(snapshot.is_fresh());
+
+        // Pre-versioning files have no `state_schema` key at all.
+        let snapshot = read_state(&write(&dir, r#"{"version":"0.1.0","pid":7}"#)).unwrap();
+        assert_eq!(snapshot.state.state_schema, 0);
+        assert_eq!(
+            snapshot.keys,
+            BTreeSet::from(["pid".to_string(), "version".to_string()])
+        );
+
+        // A newer layout is refused by version, even when it would not decode.
+        let newer = format!(
+            r#"{{"state_schema":{},"pid":"no longer a number"}}"#,
+            STATE_SCHEMA_VERSION + 1
+        );
+        let err = read_state(&write(&dir, &newer)).unwrap_err();
+        assert_eq!(
+            err,
+            StateReadError::Parse(StateParseError::NewerSchema(STATE_SCHEMA_VERSION + 1))
+        );
+        assert!(err.to_string().contains("upgrade sbh"), "got: {err}");
+    }
+
+    #[test]
+    fn ballast_integrity_counters_default_and_yield_a_corruption_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = read_state(&write(&dir, r#"{"ballast":{"available":3}}"#)).unwrap();
+        assert_eq!(snapshot.state.ballast.files_verified, 0);
+        assert_eq!(snapshot.state.ballast.corruption_pct(), None);
+
+        let ballast = BallastState {
+            files_verified: 200,
+            files_corrupted: 3,
+            ..BallastState::default()
+        };
+        assert_eq!(ballast.corruption_pct(), Some(1.5));
+    }
+
+    #[test]
+    fn config_reload_outcome_round_trips_and_is_absent_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = read_state(&write(&dir, r#"{"pid":7}"#)).unwrap();
+        assert_eq!(snapshot.state.config_reload, None);
+
+        let written = serde_json::to_string(&DaemonState {
+            config_reload: Some(ConfigReloadState {
+                at: "2026-10-16T12:00:00Z".to_string(),
+                outcome: "rejected".to_string(),
+                reason: Some("scoring.min_score must be in [0, 1]".to_string()),
+                config_hash: "abc123".to_string(),
+            }),
+            ..DaemonState::default()
+        })
+        .unwrap();
+        let reload = read_state(&write(&dir, &written))
+            .unwrap()
+            .state
+            .config_reload
+            .unwrap();
+        assert_eq!(reload.outcome, "rejected");
+        assert_eq!(reload.config_hash, "abc123");
+    }
+
+    #[test]
+    fn read_errors_are_classified() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            read_state(&dir.path().join("absent.json")).unwrap_err(),
+            StateReadError::Missing
+        );
+        for garbage in ["", r#"{"version":"0.1.0","pid":1"#, "[1, 2]"] {
+            let err = read_state(&write(&dir, garbage)).unwrap_err();
+            assert!(
+                matches!(err, StateReadError::Parse(StateParseError::Malformed(_))),
+                "{garbage:?} gave {err:?}"
+            );
+            assert!(err.to_string().starts_with("invalid state file"));
+        }
+    }
+
+    #[test]
+    fn rate_lookup_matches_mount_path_and_skips_non_finite() {
+        let rate = |bytes_per_sec| MountRate {
+            device_id: None,
+            bytes_per_sec,
+            seconds_to_threshold: None,
+        };
+        let mut state = DaemonState::default();
+        state.rates.insert("/data".to_string(), rate(2048.0));
+        state.rates.insert("/data/cache".to_string(), rate(64.0));
+        state.rates.insert("/scratch".to_string(), rate(f64::NAN));
+        let snapshot = StateSnapshot {
+            state,
+            age: Duration::from_secs(DAEMON_STATE_STALE_THRESHOLD_SECS + 1),
+            keys: BTreeSet::new(),
+        };
+        let key = |path: &str| MountKey {
+            device_id: None,
+            path: PathBuf::from(path),
+        };
+
+        assert!(!snapshot.is_fresh());
+        let bps = |path| snapshot.rate_for(&key(path)).map(|r| r.bytes_per_sec);
+        assert_eq!(bps("/data"), Some(2048.0));
+        assert_eq!(bps("/data/target/debug"), Some(2048.0));
+        assert_eq!(bps("/data/cache/x"), Some(64.0));
+        assert_eq!(bps("/scratch"), None);
+        assert_eq!(bps("/other"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_and_aliased_paths_resolve_to_the_same_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let mount = dir.path().join("data");
+        fs::create_dir_all(mount.join("build")).unwrap();
+        std::os::unix::fs::symlink(&mount, dir.path().join("alias")).unwrap();
+
+        let written = MountKey::resolve(&mount);
+        assert!(written.device_id.is_some());
+        let mut state = DaemonState::default();
+        state.rates.insert(
+            written.rate_key(),
+            MountRate {
+                device_id: written.device_id,
+                bytes_per_sec: 512.0,
+                seconds_to_threshold: Some(30.0),
+            },
+        );
+        let snapshot = StateSnapshot {
+            state,
+            age: Duration::ZERO,
+            keys: BTreeSet::new(),
+        };
+
+        // Via a symlink, from beneath the mount, and from a sibling directory
+        // on the same device (what a bind mount elsewhere looks like).
+        for path in [
+            dir.path().join("alias"),
+            dir.path().join("alias/build"),
+            dir.path().to_path_buf(),
+        ] {
+            let key = MountKey::resolve(&path);
+            assert_eq!(key.device_id, written.device_id, "{}", path.display());
+            assert_eq!(
+                snapshot.rate_for(&key).map(|r| r.bytes_per_sec),
+                Some(512.0),
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
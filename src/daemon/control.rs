@@ -0,0 +1,528 @@
+//! Unix control socket that lets the CLI hand commands to a running daemon.
+//!
+//! A client writes one JSON request line and reads one JSON reply line. As
+//! with the DBus service, nothing is acted on from the socket thread: scans
+//! and reloads set the same flags SIGUSR1 and SIGHUP do, and ballast
+//! releases go through a bounded channel that the main loop drains and
+//! answers, so the daemon's release controller sees every release.
+//! Timeline annotations are queued the same way and written by the main
+//! loop through the daemon's activity logger.
+//!
+//! Unix only; elsewhere [`ControlServer::start`] returns an error and
+//! [`send`] reports no daemon, so the CLI runs commands in-process.
+
+#![allow(missing_docs)]
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender, bounded};
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+use crate::daemon::signals::SignalHandler;
+
+/// Pending ballast releases; more than this are refused as busy.
+const RELEASE_QUEUE_CAP: usize = 8;
+/// Pending timeline annotations; more than this are refused as busy.
+const ANNOTATION_QUEUE_CAP: usize = 64;
+/// How long a client waits for the main loop to answer a release.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+/// Socket read/write timeout for a single request or reply line.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest request line accepted.
+const MAX_REQUEST_BYTES: u64 = 4096;
+
+/// A command sent over the socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlRequest {
+    Ping,
+    /// Full scan on the next loop iteration (same as SIGUSR1).
+    Scan,
+    /// Reload the config file (same as SIGHUP).
+    Reload,
+    /// Release `count` ballast files on the pressured mount.
+    ReleaseBallast {
+        count: usize,
+    },
+    /// Add an operator note to the activity timeline.
+    Annotate {
+        message: String,
+    },
+}
+
+/// The daemon's answer to a [`ControlRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reply", rename_all = "snake_case")]
+pub enum ControlReply {
+    Pong {
+        pid: u32,
+        version: String,
+    },
+    /// The request was queued for the next loop iteration.
+    Accepted,
+    BallastReleased {
+        mount: String,
+        files_released: usize,
+        bytes_freed: u64,
+        remaining: usize,
+        errors: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// A ballast release waiting for the main loop.
+pub struct ReleaseRequest {
+    pub count: usize,
+    reply_tx: Sender<ControlReply>,
+}
+
+impl ReleaseRequest {
+    /// Send the outcome back to the waiting client.
+    pub fn respond(self, reply: ControlReply) {
+        let _ = self.reply_tx.send(reply);
+    }
+}
+
+/// Senders the socket thread hands accepted work to.
+struct Queues {
+    release_tx: Sender<ReleaseRequest>,
+    annotation_tx: Sender<String>,
+}
+
+/// A listening control socket plus the releases and annotations it accepted.
+pub struct ControlServer {
+    path: PathBuf,
+    release_rx: Receiver<ReleaseRequest>,
+    annotation_rx: Receiver<String>,
+}
+
+impl ControlServer {
+    /// Bind `path` (replacing a stale socket left by a crashed daemon) and
+    /// serve requests on a background thread.
+    pub fn start(path: &Path, signal_handler: SignalHandler) -> Result<Self> {
+        let (release_tx, release_rx) = bounded(RELEASE_QUEUE_CAP);
+        let (annotation_tx, annotation_rx) = bounded(ANNOTATION_QUEUE_CAP);
+        let queues = Queues {
+            release_tx,
+            annotation_tx,
+        };
+        #[cfg(unix)]
+        {
+            let listener = imp::bind(path)?;
+            std::thread::Builder::new()
+                .name("sbh-control".to_string())
+                .spawn(move || imp::serve(&listener, &signal_handler, &queues))
+                .map_err(|e| SbhError::Runtime {
+                    details: format!("control socket: cannot spawn thread: {e}"),
+                })?;
+            Ok(Self {
+                path: path.to_path_buf(),
+                release_rx,
+                annotation_rx,
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (signal_handler, queues, release_rx, annotation_rx);
+            Err(SbhError::UnsupportedPlatform {
+                details: format!(
+                    "control socket {} requires Unix domain sockets",
+                    path.display()
+                ),
+            })
+        }
+    }
+
+    /// Socket path being served.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Ballast releases requested since the last call.
+    pub fn take_release_requests(&self) -> Vec<ReleaseRequest> {
+        self.release_rx.try_iter().collect()
+    }
+
+    /// Annotation messages received since the last call.
+    pub fn take_annotations(&self) -> Vec<String> {
+        self.annotation_rx.try_iter().collect()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Send `request` to the daemon listening on `path`.
+///
+/// Returns `Ok(None)` when no daemon is reachable there (no socket, nobody
+/// listening, or a socket owned by another user), so callers can fall back
+/// to running the command in-process.
+pub fn send(path: &Path, request: &ControlRequest) -> Result<Option<ControlReply>> {
+    #[cfg(unix)]
+    {
+        imp::send(path, request)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, request);
+        Ok(None)
+    }
+}
+
+/// Answer one parsed request. Releases are queued for the main loop and
+/// waited on; everything else is answered immediately.
+fn dispatch(
+    request: ControlRequest,
+    signal_handler: &SignalHandler,
+    queues: &Queues,
+) -> ControlReply {
+    match request {
+        ControlRequest::Ping => ControlReply::Pong {
+            pid: std::process::id(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        ControlRequest::Scan => {
+            signal_handler.request_scan();
+            ControlReply::Accepted
+        }
+        ControlRequest::Reload => {
+            signal_handler.request_reload();
+            ControlReply::Accepted
+        }
+        ControlRequest::ReleaseBallast { count: 0 } => ControlReply::Error {
+            message: "release count must be > 0".to_string(),
+        },
+        ControlRequest::ReleaseBallast { count } => {
+            let (reply_tx, reply_rx) = bounded(1);
+            if queues
+                .release_tx
+                .try_send(ReleaseRequest { count, reply_tx })
+                .is_err()
+            {
+                return ControlReply::Error {
+                    message: "ballast release queue is full or the daemon is shutting down"
+                        .to_string(),
+                };
+            }
+            reply_rx
+                .recv_timeout(REPLY_TIMEOUT)
+                .unwrap_or_else(|_| ControlReply::Error {
+                    message: format!("daemon did not answer within {}s", REPLY_TIMEOUT.as_secs()),
+                })
+        }
+        ControlRequest::Annotate { message } => {
+            let message = message.trim();
+            if message.is_empty() {
+                return ControlReply::Error {
+                    message: "annotation message is empty".to_string(),
+                };
+            }
+            match queues.annotation_tx.try_send(message.to_string()) {
+                Ok(()) => ControlReply::Accepted,
+                Err(_) => ControlReply::Error {
+                    message: "annotation queue is full or the daemon is shutting down".to_string(),
+                },
+            }
+        }
+    }
+}
+
+/// Reply to one raw request line.
+fn handle_line(line: &str, signal_handler: &SignalHandler, queues: &Queues) -> ControlReply {
+    match serde_json::from_str::<ControlRequest>(line.trim()) {
+        Ok(request) => dispatch(request, signal_handler, queues),
+        Err(e) => ControlReply::Error {
+            message: format!("invalid request: {e}"),
+        },
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::fs;
+    use std::io::{self, BufRead, BufReader, Read, Write};
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+
+    use super::{
+        ControlReply, ControlRequest, IO_TIMEOUT, MAX_REQUEST_BYTES, Queues, REPLY_TIMEOUT,
+        handle_line,
+    };
+    use crate::core::errors::{Result, SbhError};
+    use crate::daemon::signals::SignalHandler;
+    use crate::logger::diag;
+
+    pub(super) fn bind(path: &Path) -> Result<UnixListener> {
+        let parent = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        if !parent.exists() {
+            fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)
+                .map_err(|e| SbhError::io(parent, e))?;
+        }
+        check_parent(parent)?;
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(SbhError::Runtime {
+                    details: format!(
+                        "control socket {} is served by another daemon",
+                        path.display()
+                    ),
+                });
+            }
+            fs::remove_file(path).map_err(|e| SbhError::io(path, e))?;
+        }
+
+        // Bind inside a private directory and tighten the socket before it
+        // appears at `path`: `bind` creates it with umask permissions, and
+        // nobody else can reach it through a 0700 directory meanwhile.
+        let staging = parent.join(format!(".sbh-control.{}", std::process::id()));
+        let _ = fs::remove_dir_all(&staging);
+        fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&staging)
+            .map_err(|e| SbhError::io(&staging, e))?;
+        let staged = staging.join("control.sock");
+        let bound = UnixListener::bind(&staged)
+            .and_then(|listener| {
+                // Only the daemon's own user may drive it.
+                fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+                fs::rename(&staged, path)?;
+                Ok(listener)
+            })
+            .map_err(|e| SbhError::io(path, e));
+        let _ = fs::remove_dir_all(&staging);
+        bound
+    }
+
+    /// Refuse a socket directory that another user controls: whoever can
+    /// write to it can replace the socket and impersonate the daemon.
+    fn check_parent(parent: &Path) -> Result<()> {
+        let meta = fs::metadata(parent).map_err(|e| SbhError::io(parent, e))?;
+        let euid = nix::unistd::geteuid().as_raw();
+        if meta.uid() != euid && meta.uid() != 0 {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "control socket directory {} is owned by uid {}, not the daemon (uid {euid})",
+                    parent.display(),
+                    meta.uid()
+                ),
+            });
+        }
+        if meta.mode() & 0o022 != 0 {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "control socket directory {} is writable by other users (mode {:o})",
+                    parent.display(),
+                    meta.mode() & 0o7777
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    pub(super) fn serve(listener: &UnixListener, signal_handler: &SignalHandler, queues: &Queues) {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = handle_connection(&stream, signal_handler, queues) {
+                        diag::warn("control", format_args!("request failed: {e}"));
+                    }
+                }
+                Err(e) => diag::warn("control", format_args!("accept failed: {e}")),
+            }
+        }
+    }
+
+    fn handle_connection(
+        stream: &UnixStream,
+        signal_handler: &SignalHandler,
+        queues: &Queues,
+    ) -> io::Result<()> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let mut line = String::new();
+        BufReader::new(stream.take(MAX_REQUEST_BYTES)).read_line(&mut line)?;
+        // A bare connect, e.g. another daemon probing for a stale socket.
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        let reply = handle_line(&line, signal_handler, queues);
+        write_line(stream, &reply)
+    }
+
+    pub(super) fn send(path: &Path, request: &ControlRequest) -> Result<Option<ControlReply>> {
+        let stream = match UnixStream::connect(path) {
+            Ok(stream) => stream,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::NotFound
+                        | io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::PermissionDenied
+                ) =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(SbhError::io(path, e)),
+        };
+        stream
+            .set_read_timeout(Some(REPLY_TIMEOUT + IO_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)))
+            .and_then(|()| write_line(&stream, request))
+            .map_err(|e| SbhError::io(path, e))?;
+        let mut line = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut line)
+            .map_err(|e| SbhError::io(path, e))?;
+        serde_json::from_str(line.trim())
+            .map(Some)
+            .map_err(|e| SbhError::Runtime {
+                details: format!("control socket {}: invalid reply: {e}", path.display()),
+            })
+    }
+
+    fn write_line<T: serde::Serialize>(mut stream: &UnixStream, value: &T) -> io::Result<()> {
+        let mut line = serde_json::to_string(value).map_err(io::Error::other)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_and_replies_use_tagged_json_lines() {
+        assert_eq!(
+            serde_json::to_string(&ControlRequest::ReleaseBallast { count: 2 }).unwrap(),
+            r#"{"command":"release_ballast","count":2}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ControlRequest>(r#"{"command":"scan"}"#).unwrap(),
+            ControlRequest::Scan
+        );
+        assert_eq!(
+            serde_json::to_string(&ControlReply::Accepted).unwrap(),
+            r#"{"reply":"accepted"}"#
+        );
+    }
+
+    #[test]
+    fn flags_are_set_and_releases_are_queued_for_the_main_loop() {
+        let signals = SignalHandler::new();
+        let (release_tx, release_rx) = bounded(RELEASE_QUEUE_CAP);
+        let (annotation_tx, annotation_rx) = bounded(ANNOTATION_QUEUE_CAP);
+        let queues = Queues {
+            release_tx,
+            annotation_tx,
+        };
+
+        let reply = handle_line(r#"{"command":"scan"}"#, &signals, &queues);
+        assert_eq!(reply, ControlReply::Accepted);
+        assert!(signals.should_scan());
+        assert!(matches!(
+            handle_line("not json", &signals, &queues),
+            ControlReply::Error { .. }
+        ));
+        assert!(matches!(
+            handle_line(
+                r#"{"command":"release_ballast","count":0}"#,
+                &signals,
+                &queues
+            ),
+            ControlReply::Error { .. }
+        ));
+
+        let main_loop = std::thread::spawn(move || {
+            let request = release_rx.recv().unwrap();
+            let count = request.count;
+            request.respond(ControlReply::BallastReleased {
+                mount: "/data".to_string(),
+                files_released: count,
+                bytes_freed: 1024,
+                remaining: 1,
+                errors: Vec::new(),
+            });
+        });
+        let reply = handle_line(
+            r#"{"command":"release_ballast","count":2}"#,
+            &signals,
+            &queues,
+        );
+        main_loop.join().unwrap();
+        assert!(matches!(
+            reply,
+            ControlReply::BallastReleased {
+                files_released: 2,
+                ..
+            }
+        ));
+
+        let reply = handle_line(
+            r#"{"command":"annotate","message":"  nightly build farm run "}"#,
+            &signals,
+            &queues,
+        );
+        assert_eq!(reply, ControlReply::Accepted);
+        assert!(matches!(
+            handle_line(r#"{"command":"annotate","message":" "}"#, &signals, &queues),
+            ControlReply::Error { .. }
+        ));
+        assert_eq!(
+            annotation_rx.try_iter().collect::<Vec<_>>(),
+            vec!["nightly build farm run".to_string()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn client_round_trips_through_the_socket_and_reports_no_daemon() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run").join("control.sock");
+        assert_eq!(send(&path, &ControlRequest::Ping).unwrap(), None);
+
+        let server = ControlServer::start(&path, SignalHandler::new()).unwrap();
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let reply = send(&path, &ControlRequest::Ping).unwrap();
+        assert!(matches!(reply, Some(ControlReply::Pong { pid, .. }) if pid == std::process::id()));
+        assert!(ControlServer::start(&path, SignalHandler::new()).is_err());
+
+        drop(server);
+        assert!(!path.exists());
+        assert_eq!(send(&path, &ControlRequest::Ping).unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_a_socket_directory_other_users_can_write() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        let path = shared.join("control.sock");
+
+        assert!(ControlServer::start(&path, SignalHandler::new()).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,407 @@
+//! Core dump policy: keep crash dumps from silently filling the disk.
+//!
+//! Dumps land outside the scanner's usual roots (`/var/lib/systemd/coredump`,
+//! `/var/crash`, the directory named by `kernel.core_pattern`), and a crash
+//! loop can write gigabytes per minute. The daemon sweeps those directories on
+//! its pressure tick:
+//!
+//! - Green: nothing is removed.
+//! - Yellow/Orange: dumps older than `coredumps.max_age_hours` are removed.
+//! - Red/Critical: every dump is removed once it is past a short write grace.
+//!
+//! Each sweep that removes something reports which binaries crashed, so the
+//! notification points at the root cause rather than just the freed bytes.
+//! `core.*` files written into a process's working directory are not swept;
+//! the scanner finds those through large-file discovery.
+
+#![allow(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::monitor::pid::PressureLevel;
+
+/// Dumps modified more recently than this are still being written or compressed.
+pub const WRITE_GRACE: Duration = Duration::from_secs(30);
+/// Minimum spacing between sweeps at red/critical pressure.
+pub const RED_SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+const CORE_PATTERN_PATH: &str = "/proc/sys/kernel/core_pattern";
+const COMPRESSION_SUFFIXES: &[&str] = &[".zst", ".xz", ".lz4", ".gz"];
+
+// ──────────────────── configuration ────────────────────
+
+/// `[coredumps]` section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CoredumpConfig {
+    pub enabled: bool,
+    /// Directories swept (non-recursively) for dumps.
+    pub search_paths: Vec<PathBuf>,
+    /// Also sweep the directory of an absolute `kernel.core_pattern`.
+    pub follow_core_pattern: bool,
+    /// At yellow/orange pressure, dumps older than this are removed.
+    pub max_age_hours: u64,
+    /// Minimum seconds between sweeps below red pressure.
+    pub sweep_interval_secs: u64,
+}
+
+impl Default for CoredumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            search_paths: vec![
+                PathBuf::from("/var/lib/systemd/coredump"),
+                PathBuf::from("/var/crash"),
+            ],
+            follow_core_pattern: true,
+            max_age_hours: 72,
+            sweep_interval_secs: 300,
+        }
+    }
+}
+
+// ──────────────────── dump discovery ────────────────────
+
+/// One crash artifact found in a search directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreDumpFile {
+    pub path: PathBuf,
+    /// Crashed binary, or `"unknown"` when the file name does not say.
+    pub binary: String,
+    pub size_bytes: u64,
+    pub age: Duration,
+}
+
+/// Recognize a crash artifact by file name and extract the crashed binary.
+///
+/// Handles systemd-coredump (`core.<comm>.<uid>.<boot-id>.<pid>.<usec>[.zst]`),
+/// `core.<comm>.<pid>`, `core.<pid>`, `core`, `<comm>.core`, and apport
+/// reports (`_usr_bin_python3.1000.crash`). Returns `None` for anything else.
+#[must_use]
+pub fn crashed_binary(file_name: &str) -> Option<String> {
+    if let Some(report) = file_name.strip_suffix(".crash") {
+        let (exe, uid) = report.rsplit_once('.')?;
+        return is_digits(uid).then(|| exe.replace('_', "/"));
+    }
+    if let Some(comm) = file_name.strip_suffix(".core") {
+        return (!comm.is_empty()).then(|| comm.to_string());
+    }
+    if file_name == "core" {
+        return Some("unknown".to_string());
+    }
+
+    let rest = file_name.strip_prefix("core.")?;
+    let rest = COMPRESSION_SUFFIXES
+        .iter()
+        .find_map(|suffix| rest.strip_suffix(suffix))
+        .unwrap_or(rest);
+    if is_digits(rest) {
+        return Some("unknown".to_string());
+    }
+    if let Some(comm) = systemd_comm(rest) {
+        return Some(comm.to_string());
+    }
+    match rest.rsplit_once('.') {
+        Some((comm, pid)) if is_digits(pid) && !comm.is_empty() => Some(comm.to_string()),
+        _ => Some(rest.to_string()),
+    }
+}
+
+/// `<comm>.<uid>.<boot-id>.<pid>.<usec>` → `<comm>`. Parsed from the right
+/// because `comm` may itself contain dots.
+fn systemd_comm(rest: &str) -> Option<&str> {
+    let mut parts = rest.rsplitn(5, '.');
+    let usec = parts.next()?;
+    let pid = parts.next()?;
+    let boot_id = parts.next()?;
+    let uid = parts.next()?;
+    let comm = parts.next()?;
+    let boot_id_ok = boot_id.len() == 32 && boot_id.bytes().all(|b| b.is_ascii_hexdigit());
+    (is_digits(usec) && is_digits(pid) && boot_id_ok && is_digits(uid) && !comm.is_empty())
+        .then_some(comm)
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Directory of an absolute `kernel.core_pattern` such as `/var/cores/core.%e.%p`.
+/// Piped handlers (`|/usr/lib/systemd/...`) and relative patterns yield `None`.
+#[must_use]
+pub fn core_pattern_dir(pattern: &str) -> Option<PathBuf> {
+    let pattern = pattern.trim();
+    if !pattern.starts_with('/') {
+        return None;
+    }
+    let parent = Path::new(pattern).parent()?;
+    let parent_str = parent.to_str()?;
+    (parent != Path::new("/") && !parent_str.contains('%')).then(|| parent.to_path_buf())
+}
+
+/// List crash artifacts directly inside `dir`. Symlinks and subdirectories are ignored.
+#[must_use]
+pub fn collect_dumps(dir: &Path, now: SystemTime) -> Vec<CoreDumpFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.path().symlink_metadata().ok()?;
+            if !meta.file_type().is_file() {
+                return None;
+            }
+            let binary = crashed_binary(entry.file_name().to_str()?)?;
+            let age = meta
+                .modified()
+                .ok()
+                .and_then(|mtime| now.duration_since(mtime).ok())
+                .unwrap_or_default();
+            Some(CoreDumpFile {
+                path: entry.path(),
+                binary,
+                size_bytes: meta.len(),
+                age,
+            })
+        })
+        .collect()
+}
+
+/// Dumps the policy removes at `level`.
+#[must_use]
+pub fn select_for_removal(
+    dumps: Vec<CoreDumpFile>,
+    level: PressureLevel,
+    max_age: Duration,
+) -> Vec<CoreDumpFile> {
+    let min_age = match level {
+        PressureLevel::Green => return Vec::new(),
+        PressureLevel::Yellow | PressureLevel::Orange => max_age.max(WRITE_GRACE),
+        PressureLevel::Red | PressureLevel::Critical => WRITE_GRACE,
+    };
+    dumps.into_iter().filter(|d| d.age >= min_age).collect()
+}
+
+// ──────────────────── sweep ────────────────────
+
+/// Result of one sweep.
+#[derive(Debug, Clone, Default)]
+pub struct SweepOutcome {
+    pub removed: Vec<CoreDumpFile>,
+    pub bytes_freed: u64,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl SweepOutcome {
+    /// `"python3 x2, node"`: crashed binaries by removed-dump count, most first.
+    #[must_use]
+    pub fn crashed_binaries(&self) -> String {
+        let mut counts = BTreeMap::<&str, usize>::new();
+        for dump in &self.removed {
+            *counts.entry(dump.binary.as_str()).or_default() += 1;
+        }
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        counts
+            .into_iter()
+            .map(|(binary, n)| {
+                if n == 1 {
+                    binary.to_string()
+                } else {
+                    format!("{binary} x{n}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Sweeps the configured dump directories according to pressure.
+#[derive(Debug, Clone)]
+pub struct CoredumpPolicy {
+    config: CoredumpConfig,
+    dirs: Vec<PathBuf>,
+    last_sweep: Option<Instant>,
+}
+
+impl CoredumpPolicy {
+    #[must_use]
+    pub fn new(config: &CoredumpConfig) -> Self {
+        Self {
+            config: config.clone(),
+            dirs: resolve_dirs(config),
+            last_sweep: None,
+        }
+    }
+
+    pub fn update_config(&mut self, config: &CoredumpConfig) {
+        self.config = config.clone();
+        self.dirs = resolve_dirs(config);
+    }
+
+    #[must_use]
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Whether a sweep should run now at `level`.
+    #[must_use]
+    pub fn sweep_due(&self, level: PressureLevel, now: Instant) -> bool {
+        if !self.config.enabled || level == PressureLevel::Green {
+            return false;
+        }
+        let interval = if level >= PressureLevel::Red {
+            RED_SWEEP_INTERVAL
+        } else {
+            Duration::from_secs(self.config.sweep_interval_secs)
+        };
+        self.last_sweep
+            .is_none_or(|last| now.duration_since(last) >= interval)
+    }
+
+    /// Remove the dumps selected for `level`. With `dry_run`, nothing is
+    /// touched and the outcome lists what would have been removed.
+    pub fn sweep(&mut self, level: PressureLevel, dry_run: bool) -> SweepOutcome {
+        self.last_sweep = Some(Instant::now());
+        let now = SystemTime::now();
+        let max_age = Duration::from_secs(self.config.max_age_hours.saturating_mul(3600));
+        let mut outcome = SweepOutcome::default();
+
+        for dir in &self.dirs {
+            for dump in select_for_removal(collect_dumps(dir, now), level, max_age) {
+                if !dry_run && let Err(e) = fs::remove_file(&dump.path) {
+                    outcome.failed.push((dump.path.clone(), e.to_string()));
+                    continue;
+                }
+                outcome.bytes_freed += dump.size_bytes;
+                outcome.removed.push(dump);
+            }
+        }
+        outcome
+    }
+}
+
+fn resolve_dirs(config: &CoredumpConfig) -> Vec<PathBuf> {
+    let mut dirs = config.search_paths.clone();
+    if config.follow_core_pattern
+        && let Ok(pattern) = fs::read_to_string(CORE_PATTERN_PATH)
+        && let Some(dir) = core_pattern_dir(&pattern)
+        && !dirs.contains(&dir)
+    {
+        dirs.push(dir);
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn crashed_binary_parses_common_dump_names() {
+        let cases = [
+            (
+                "core.python3.11.1000.0123456789abcdef0123456789abcdef.4242.1760000000000000.zst",
+                Some("python3.11"),
+            ),
+            ("core.node.31337", Some("node")),
+            ("core.31337", Some("unknown")),
+            ("core", Some("unknown")),
+            ("cargo.core", Some("cargo")),
+            ("_usr_bin_python3.1000.crash", Some("/usr/bin/python3")),
+            ("corefile.txt", None),
+            ("build.log", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(crashed_binary(name).as_deref(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn core_pattern_dir_only_for_absolute_static_directories() {
+        assert_eq!(
+            core_pattern_dir("/var/cores/core.%e.%p\n"),
+            Some(PathBuf::from("/var/cores"))
+        );
+        assert_eq!(
+            core_pattern_dir("|/usr/lib/systemd/systemd-coredump %P %u"),
+            None
+        );
+        assert_eq!(core_pattern_dir("core"), None);
+        assert_eq!(core_pattern_dir("/cores/%u/core.%p"), None);
+    }
+
+    #[test]
+    fn removal_depends_on_pressure_and_age() {
+        let dump = |name: &str, age_secs: u64| CoreDumpFile {
+            path: PathBuf::from(name),
+            binary: "x".to_string(),
+            size_bytes: 1,
+            age: Duration::from_secs(age_secs),
+        };
+        let dumps = vec![
+            dump("fresh", 5),
+            dump("minutes", 600),
+            dump("days", 5 * 86_400),
+        ];
+        let max_age = Duration::from_secs(72 * 3600);
+        let names = |level| {
+            select_for_removal(dumps.clone(), level, max_age)
+                .into_iter()
+                .map(|d| d.path)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(names(PressureLevel::Green).is_empty());
+        assert_eq!(names(PressureLevel::Orange), vec![PathBuf::from("days")]);
+        assert_eq!(
+            names(PressureLevel::Red),
+            vec![PathBuf::from("minutes"), PathBuf::from("days")]
+        );
+    }
+
+    #[test]
+    fn sweep_removes_dumps_and_names_crashed_binaries() {
+        let tmp = TempDir::new().unwrap();
+        for name in ["core.node.1", "core.node.2", "core.python3.3", "notes.txt"] {
+            fs::write(tmp.path().join(name), b"dump").unwrap();
+        }
+        let config = CoredumpConfig {
+            search_paths: vec![tmp.path().to_path_buf()],
+            follow_core_pattern: false,
+            ..CoredumpConfig::default()
+        };
+        let mut policy = CoredumpPolicy::new(&config);
+        assert!(!policy.sweep_due(PressureLevel::Green, Instant::now()));
+        assert!(policy.sweep_due(PressureLevel::Red, Instant::now()));
+
+        // Freshly written files are inside the write grace, even at red.
+        assert!(policy.sweep(PressureLevel::Red, false).removed.is_empty());
+
+        let old = SystemTime::now() - Duration::from_secs(120);
+        for name in ["core.node.1", "core.node.2", "core.python3.3"] {
+            let file = fs::File::options()
+                .write(true)
+                .open(tmp.path().join(name))
+                .unwrap();
+            file.set_modified(old).unwrap();
+        }
+
+        let preview = policy.sweep(PressureLevel::Critical, true);
+        assert_eq!(preview.removed.len(), 3);
+        assert!(tmp.path().join("core.node.1").exists());
+
+        let outcome = policy.sweep(PressureLevel::Critical, false);
+        assert_eq!(outcome.removed.len(), 3);
+        assert_eq!(outcome.bytes_freed, 12);
+        assert_eq!(outcome.crashed_binaries(), "node x2, python3");
+        assert!(!tmp.path().join("core.node.1").exists());
+        assert!(tmp.path().join("notes.txt").exists());
+    }
+}
@@ -0,0 +1,293 @@
+//! Crash reports for the daemon.
+//!
+//! [`install`] adds a panic hook that keeps the default stderr message and
+//! then, for every panic in any daemon thread:
+//!
+//! 1. Writes a [`CrashReport`] (panic message and location, thread, version,
+//!    backtrace, and the last [`RECENT_EVENTS`] diagnostic lines) as JSON to
+//!    the `crashes/` directory beside the state file, keeping the newest
+//!    [`MAX_REPORTS`].
+//! 2. Sends an `SBH-3901` error through the configured notification channels.
+//! 3. Prints a GitHub issue URL with the report's summary filled in.
+//!
+//! A worker thread that panics is respawned by the main loop, and a main-loop
+//! panic ends the process for the service manager to restart; either way the
+//! report survives the restart.
+
+#![allow(missing_docs)]
+
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::Config;
+use crate::core::errors::{Result, SbhError};
+use crate::daemon::notifications::{NotificationEvent, NotificationManager};
+use crate::logger::diag;
+
+/// Diagnostic lines kept in memory for a crash report.
+pub const RECENT_EVENTS: usize = 200;
+
+/// Crash reports kept on disk; older ones are removed.
+pub const MAX_REPORTS: usize = 20;
+
+/// Where new issues are filed.
+pub const ISSUES_URL: &str =
+    "https://github.com/Dicklesworthstone/storage_ballast_helper/issues/new";
+
+/// Longest issue URL produced; browsers and GitHub reject much longer ones.
+const MAX_ISSUE_URL_LEN: usize = 7_500;
+
+/// Recent events quoted in the issue body; the file has all of them.
+const ISSUE_EVENTS: usize = 20;
+
+/// What the daemon knew when a thread panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub version: String,
+    /// `<os>/<arch>` the binary was built for.
+    pub platform: String,
+    /// RFC 3339 time of the panic.
+    pub at: String,
+    pub pid: u32,
+    pub thread: String,
+    pub message: String,
+    /// `file:line:column` of the panic, when known.
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Diagnostic lines leading up to the panic, oldest first.
+    pub recent_events: Vec<String>,
+}
+
+impl CrashReport {
+    /// A report for a panic in the current thread.
+    #[must_use]
+    pub fn new(message: String, location: Option<String>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{}/{}", std::env::consts::OS, std::env::consts::ARCH),
+            at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            pid: std::process::id(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            message,
+            location,
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            recent_events: diag::recent(),
+        }
+    }
+
+    /// A report for the panic `info` describes.
+    #[must_use]
+    pub fn from_panic(info: &PanicHookInfo<'_>) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        Self::new(message, location)
+    }
+
+    /// Write the report into `dir` and prune old ones. Returns its path.
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir).map_err(|e| SbhError::io(dir, e))?;
+        let stamp = self.at.replace([':', '.'], "-");
+        let path = dir.join(format!("crash-{stamp}-{}.json", self.pid));
+        let json = serde_json::to_string_pretty(self).map_err(|e| SbhError::Serialization {
+            context: "crash report",
+            details: e.to_string(),
+        })?;
+        fs::write(&path, json + "\n").map_err(|e| SbhError::io(&path, e))?;
+        prune(dir, MAX_REPORTS);
+        Ok(path)
+    }
+
+    /// A new-issue URL with the title and body filled in from this report.
+    /// Recent events are dropped, oldest first, until the URL fits.
+    #[must_use]
+    pub fn issue_url(&self, report_path: &Path) -> String {
+        let first_line = self.message.lines().next().unwrap_or_default();
+        let title: String = format!("Daemon crash: {first_line}")
+            .chars()
+            .take(120)
+            .collect();
+        let events = &self.recent_events[self.recent_events.len().saturating_sub(ISSUE_EVENTS)..];
+        let mut skip = 0;
+        loop {
+            let url = format!(
+                "{ISSUES_URL}?labels=crash&title={}&body={}",
+                encode_query(&title),
+                encode_query(&self.issue_body(&events[skip..], report_path))
+            );
+            if url.len() <= MAX_ISSUE_URL_LEN || skip == events.len() {
+                return url;
+            }
+            skip += 1;
+        }
+    }
+
+    fn issue_body(&self, events: &[String], report_path: &Path) -> String {
+        let mut body = String::new();
+        let _ = writeln!(
+            body,
+            "**Version:** sbh {} ({})",
+            self.version, self.platform
+        );
+        let _ = writeln!(body, "**Thread:** {}", self.thread);
+        if let Some(location) = &self.location {
+            let _ = writeln!(body, "**Location:** {location}");
+        }
+        let _ = write!(body, "\n**Panic:**\n```\n{}\n```\n", self.message);
+        if !events.is_empty() {
+            let _ = write!(
+                body,
+                "\n**Recent events:**\n```\n{}\n```\n",
+                events.join("\n")
+            );
+        }
+        let _ = write!(
+            body,
+            "\nFull report: `{}` (review it for private paths before attaching).\n",
+            report_path.display()
+        );
+        body
+    }
+}
+
+/// Directory crash reports are written to: `crashes/` beside the state file.
+#[must_use]
+pub fn reports_dir(state_file: &Path) -> PathBuf {
+    state_file.with_file_name("crashes")
+}
+
+/// Install the crash-report panic hook for this process.
+pub fn install(config: &Config) {
+    diag::keep_recent(RECENT_EVENTS);
+    let dir = reports_dir(&config.paths.state_file);
+    let notifications = config.notifications.clone();
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let report = CrashReport::from_panic(info);
+        let path = match report.write(&dir) {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("[SBH-CRASH] could not write crash report: {e}");
+                dir.clone()
+            }
+        };
+        NotificationManager::from_config(&notifications).notify(&NotificationEvent::Error {
+            code: "SBH-3901".to_string(),
+            message: format!(
+                "thread '{}' panicked: {}; crash report at {}",
+                report.thread,
+                report.message,
+                path.display()
+            ),
+        });
+        eprintln!(
+            "[SBH-CRASH] crash report written to {}\n[SBH-CRASH] please report it: {}",
+            path.display(),
+            report.issue_url(&path)
+        );
+    }));
+}
+
+/// Remove all but the newest `keep` reports in `dir`.
+fn prune(dir: &Path, keep: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    // Names embed the UTC timestamp, so they sort by age.
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("crash-"))
+        })
+        .collect();
+    reports.sort();
+    let excess = reports.len().saturating_sub(keep);
+    for old in &reports[..excess] {
+        let _ = fs::remove_file(old);
+    }
+}
+
+/// Percent-encode `value` for a URL query string.
+fn encode_query(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(message: &str, events: usize) -> CrashReport {
+        CrashReport {
+            recent_events: (0..events)
+                .map(|i| format!("2026-01-01T00:00:00.000Z INFO  [daemon] event {i}"))
+                .collect(),
+            backtrace: String::new(),
+            ..CrashReport::new(message.to_string(), Some("src/x.rs:1:2".to_string()))
+        }
+    }
+
+    #[test]
+    fn issue_url_is_encoded_and_bounded() {
+        let crash = report("index out of bounds: the len is 0 & the index is 3", 5);
+        let url = crash.issue_url(Path::new("/var/lib/sbh/crashes/crash-1.json"));
+        assert!(url.starts_with(ISSUES_URL));
+        assert!(url.contains("title=Daemon%20crash%3A%20index%20out%20of%20bounds"));
+        assert!(url.contains("event%204"));
+        assert!(!url.contains(' ') && !url.contains("& "));
+
+        let noisy = report(&"x".repeat(200), 2_000);
+        let url = noisy.issue_url(Path::new("/tmp/crash.json"));
+        assert!(url.len() <= MAX_ISSUE_URL_LEN, "{}", url.len());
+        assert!(url.contains("event%201999"), "the newest events are kept");
+    }
+
+    #[test]
+    fn reports_are_written_and_pruned_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..3 {
+            fs::write(dir.path().join(format!("crash-2020-0{i}.json")), "{}").unwrap();
+        }
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+        let crash = report("boom", 1);
+        let path = crash.write(dir.path()).unwrap();
+
+        let read: CrashReport = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(read, crash);
+
+        prune(dir.path(), 2);
+        let mut left: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left.len(), 3);
+        assert_eq!(left[0], "crash-2020-02.json");
+        assert_eq!(left[2], "notes.txt");
+    }
+}
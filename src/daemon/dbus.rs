@@ -0,0 +1,228 @@
+//! DBus service (`org.sbh.Daemon`) for desktop environment integration.
+//!
+//! Exposes the current pressure level and free percentage as properties,
+//! emits `PressureChanged` on every level transition, and accepts
+//! `TriggerScan` and `ReleaseBallast` calls, so GNOME/KDE extensions can
+//! integrate without parsing the state file. Requests are handed to the main
+//! loop rather than acted on from the bus thread: scans through the same
+//! flag SIGUSR1 sets, ballast releases through a bounded channel.
+//!
+//! Linux only, behind the `dbus` feature; elsewhere [`DbusService::start`]
+//! returns an error and the daemon runs without it.
+
+#![allow(missing_docs)]
+
+use crossbeam_channel::{Receiver, bounded};
+
+use crate::core::config::DbusBus;
+use crate::core::errors::{Result, SbhError};
+use crate::daemon::signals::SignalHandler;
+
+/// Well-known bus name.
+pub const BUS_NAME: &str = "org.sbh.Daemon";
+/// Object path the interface is served at.
+pub const OBJECT_PATH: &str = "/org/sbh/Daemon";
+
+/// Pending `ReleaseBallast` calls; more than this are refused as busy.
+const RELEASE_QUEUE_CAP: usize = 8;
+
+/// Free-percentage change below which no `PropertiesChanged` is emitted.
+const FREE_PCT_EPSILON: f64 = 0.1;
+
+/// A running DBus service plus the queue of ballast releases it accepted.
+pub struct DbusService {
+    #[cfg(all(target_os = "linux", feature = "dbus"))]
+    connection: zbus::blocking::Connection,
+    release_rx: Receiver<u32>,
+}
+
+impl DbusService {
+    /// Connect to `bus`, claim [`BUS_NAME`], and serve the interface.
+    pub fn start(bus: DbusBus, signal_handler: SignalHandler) -> Result<Self> {
+        let (release_tx, release_rx) = bounded::<u32>(RELEASE_QUEUE_CAP);
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        {
+            let interface = imp::DaemonInterface {
+                pressure_level: "Green".to_string(),
+                free_pct: 100.0,
+                signal_handler,
+                release_tx,
+            };
+            let builder = match bus {
+                DbusBus::System => zbus::blocking::connection::Builder::system(),
+                DbusBus::Session => zbus::blocking::connection::Builder::session(),
+            };
+            let connection = builder
+                .and_then(|b| b.name(BUS_NAME))
+                .and_then(|b| b.serve_at(OBJECT_PATH, interface))
+                .and_then(zbus::blocking::connection::Builder::build)
+                .map_err(|e| SbhError::Runtime {
+                    details: format!("dbus: cannot register {BUS_NAME}: {e}"),
+                })?;
+            Ok(Self {
+                connection,
+                release_rx,
+            })
+        }
+        #[cfg(not(all(target_os = "linux", feature = "dbus")))]
+        {
+            let _ = (bus, signal_handler, release_tx, release_rx);
+            Err(SbhError::UnsupportedPlatform {
+                details: "DBus service requires Linux and a build with --features dbus".to_string(),
+            })
+        }
+    }
+
+    /// Update the exported properties, emitting `PropertiesChanged` only for
+    /// values that actually moved.
+    pub fn publish(&self, pressure_level: &str, free_pct: f64) {
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        imp::publish(&self.connection, pressure_level, free_pct);
+        #[cfg(not(all(target_os = "linux", feature = "dbus")))]
+        let _ = (pressure_level, free_pct);
+    }
+
+    /// Emit the `PressureChanged` signal for a level transition.
+    pub fn pressure_changed(&self, from: &str, to: &str, free_pct: f64) {
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        imp::pressure_changed(&self.connection, from, to, free_pct);
+        #[cfg(not(all(target_os = "linux", feature = "dbus")))]
+        let _ = (from, to, free_pct);
+    }
+
+    /// Ballast release counts requested over the bus since the last call.
+    pub fn take_release_requests(&self) -> Vec<u32> {
+        self.release_rx.try_iter().collect()
+    }
+}
+
+/// Whether a new free percentage differs enough to announce.
+#[cfg_attr(not(all(target_os = "linux", feature = "dbus")), allow(dead_code))]
+fn free_pct_moved(old: f64, new: f64) -> bool {
+    (old - new).abs() >= FREE_PCT_EPSILON
+}
+
+#[cfg(all(target_os = "linux", feature = "dbus"))]
+mod imp {
+    use crossbeam_channel::{Sender, TrySendError};
+    use zbus::interface;
+    use zbus::object_server::SignalEmitter;
+
+    use super::{OBJECT_PATH, free_pct_moved};
+    use crate::daemon::signals::SignalHandler;
+
+    pub(super) struct DaemonInterface {
+        pub(super) pressure_level: String,
+        pub(super) free_pct: f64,
+        pub(super) signal_handler: SignalHandler,
+        pub(super) release_tx: Sender<u32>,
+    }
+
+    #[interface(name = "org.sbh.Daemon")]
+    impl DaemonInterface {
+        /// Current pressure level: Green, Yellow, Orange, Red, or Critical.
+        #[zbus(property)]
+        fn pressure_level(&self) -> String {
+            self.pressure_level.clone()
+        }
+
+        /// Free percentage on the mount driving the pressure level.
+        #[zbus(property)]
+        fn free_pct(&self) -> f64 {
+            self.free_pct
+        }
+
+        /// Start a full scan on the next loop iteration (same as SIGUSR1).
+        fn trigger_scan(&self) {
+            self.signal_handler.request_scan();
+        }
+
+        /// Release `count` ballast files on the pressured mount.
+        fn release_ballast(&self, count: u32) -> zbus::fdo::Result<()> {
+            if count == 0 {
+                return Err(zbus::fdo::Error::InvalidArgs(
+                    "count must be at least 1".to_string(),
+                ));
+            }
+            self.release_tx.try_send(count).map_err(|e| match e {
+                TrySendError::Full(_) => {
+                    zbus::fdo::Error::LimitsExceeded("release queue is full".to_string())
+                }
+                TrySendError::Disconnected(_) => {
+                    zbus::fdo::Error::Failed("daemon is shutting down".to_string())
+                }
+            })
+        }
+
+        /// Emitted on every pressure level transition.
+        #[zbus(signal)]
+        async fn pressure_changed(
+            emitter: &SignalEmitter<'_>,
+            from: &str,
+            to: &str,
+            free_pct: f64,
+        ) -> zbus::Result<()>;
+    }
+
+    fn interface(
+        connection: &zbus::blocking::Connection,
+    ) -> Option<zbus::blocking::object_server::InterfaceRef<DaemonInterface>> {
+        connection
+            .object_server()
+            .interface::<_, DaemonInterface>(OBJECT_PATH)
+            .ok()
+    }
+
+    pub(super) fn publish(connection: &zbus::blocking::Connection, level: &str, free_pct: f64) {
+        let Some(iface_ref) = interface(connection) else {
+            return;
+        };
+        let emitter = iface_ref.signal_emitter();
+        let mut iface = iface_ref.get_mut();
+        if iface.pressure_level != level {
+            iface.pressure_level = level.to_string();
+            let _ = zbus::block_on(iface.pressure_level_changed(emitter));
+        }
+        if free_pct_moved(iface.free_pct, free_pct) {
+            iface.free_pct = free_pct;
+            let _ = zbus::block_on(iface.free_pct_changed(emitter));
+        }
+    }
+
+    pub(super) fn pressure_changed(
+        connection: &zbus::blocking::Connection,
+        from: &str,
+        to: &str,
+        free_pct: f64,
+    ) {
+        if let Some(iface_ref) = interface(connection) {
+            let _ = zbus::block_on(DaemonInterface::pressure_changed(
+                iface_ref.signal_emitter(),
+                from,
+                to,
+                free_pct,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_pct_changes_below_epsilon_are_not_announced() {
+        assert!(!free_pct_moved(42.00, 42.05));
+        assert!(free_pct_moved(42.0, 42.1));
+        assert!(free_pct_moved(42.0, 41.0));
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "dbus")))]
+    #[test]
+    fn start_without_dbus_support_is_an_unsupported_platform_error() {
+        let err = DbusService::start(DbusBus::Session, SignalHandler::new())
+            .err()
+            .expect("start must fail without the dbus feature");
+        assert!(matches!(err, SbhError::UnsupportedPlatform { .. }));
+    }
+}
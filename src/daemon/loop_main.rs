@@ -14,41 +14,74 @@
 #![allow(clippy::cast_precision_loss)]
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+
 use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, TryRecvError, TrySendError, bounded};
 use parking_lot::{Mutex, RwLock};
 
-use crate::ballast::coordinator::BallastPoolCoordinator;
-use crate::ballast::release::BallastReleaseController;
-use crate::core::config::Config;
+use crate::ballast::coordinator::{BallastPool, BallastPoolCoordinator};
+use crate::ballast::manager::ReleaseReport;
+use crate::ballast::release::{BallastReleaseController, ReplenishForecast, ReplenishPlan};
+use crate::core::config::{Config, PressureConfig};
+use crate::core::container;
 use crate::core::errors::{Result, SbhError};
+use crate::core::maintenance::{
+    MaintenanceWindow, clear_maintenance, maintenance_path, read_maintenance,
+};
+use crate::core::probation::{
+    ConfigProbation, clear_probation, probation_path, read_probation, restore_previous_config,
+};
+use crate::core::schedule::CronSchedule;
+use crate::core::state::{CleanupCooldown, ConfigReloadState, DeepScanState, MountKey, MountRate};
+use crate::daemon::control::{ControlReply, ControlServer};
+use crate::daemon::coredump::CoredumpPolicy;
+use crate::daemon::crash;
+use crate::daemon::dbus::DbusService;
 use crate::daemon::notifications::{NotificationEvent, NotificationLevel, NotificationManager};
-use crate::daemon::policy::PolicyEngine;
-use crate::daemon::self_monitor::{SelfMonitor, ThreadHeartbeat};
+use crate::daemon::policy::{
+    BurnIn, FallbackReason, PolicyEngine, burn_in_path, read_burn_in, write_burn_in,
+};
+use crate::daemon::self_monitor::{
+    LoopProgress, SelfMonitor, ShedStage, ThreadHeartbeat, ThreadStatus, thread_snapshot,
+};
 use crate::daemon::signals::{SignalHandler, WatchdogHeartbeat};
+use crate::logger::audit::AuditLog;
+use crate::logger::diag::{self, DiagLevel};
 use crate::logger::dual::{ActivityEvent, ActivityLoggerHandle, DualLoggerConfig, spawn_logger};
-use crate::logger::jsonl::JsonlConfig;
+use crate::logger::jsonl::{JsonlConfig, ScoreFactorsRecord};
+use crate::monitor::disk_health::{self, DiskHealth, HealthVerdict};
 use crate::monitor::ewma::{DiskRateEstimator, RateEstimate};
 use crate::monitor::fs_stats::FsStatsCollector;
 use crate::monitor::guardrails::{
     AdaptiveGuard, CalibrationObservation, GuardDiagnostics, GuardStatus,
 };
+use crate::monitor::mount_watch::{MountClass, MountEvent, MountWatcher, classify_mount};
 use crate::monitor::pid::{PidPressureController, PressureLevel, PressureReading};
 use crate::monitor::predictive::{PredictiveAction, PredictiveActionPolicy};
-use crate::monitor::special_locations::SpecialLocationRegistry;
+use crate::monitor::special_locations::{SpecialLocation, SpecialLocationRegistry};
 use crate::monitor::voi_scheduler::VoiScheduler;
-use crate::platform::pal::{MemoryInfo, Platform, detect_platform};
-use crate::scanner::deletion::{DeletionConfig, DeletionExecutor};
+use crate::monitor::writers::{ATTRIBUTION_WINDOW, WriterHistory, collect_processes};
+use crate::platform::pal::{FsStats, MemoryInfo, Platform, detect_platform};
+use crate::scanner::archive::Relocation;
+use crate::scanner::deletion::{
+    AuditContext, DeletionConfig, DeletionExecutor, DeletionPlan, DeletionReport, ItemOutcome,
+    ReclaimVerification, verify_reclaim,
+};
+use crate::scanner::io_throttle::IoThrottle;
 use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry};
 use crate::scanner::protection::ProtectionRegistry;
+use crate::scanner::quarantine;
+use crate::scanner::score_history::ScoreHistory;
 use crate::scanner::scoring::{CandidacyScore, ScoringEngine};
-use crate::scanner::walker::{DirectoryWalker, WalkerConfig};
+use crate::scanner::walker::{DirectoryWalker, TreeWatcher, WalkEntry, WalkerConfig};
 
 // ──────────────────── channel capacities ────────────────────
 
@@ -81,6 +114,11 @@ const SWAP_THRASH_USED_PCT_THRESHOLD: f64 = 70.0;
 /// Minimum free RAM for high swap use to indicate thrash (anomalous paging
 /// despite ample memory). Per README: "at least 8 GiB of RAM remains free".
 const SWAP_THRASH_MIN_AVAILABLE_RAM_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+/// How often the daemon re-reads ballast headers to catch corruption.
+const BALLAST_VERIFY_INTERVAL: Duration = Duration::from_hours(1);
+/// How often quarantines are checked for expired entries. Under orange or
+/// worse pressure they are checked every tick.
+const QUARANTINE_CHECK_INTERVAL: Duration = Duration::from_mins(5);
 /// Even under high pressure, avoid deleting extremely fresh temp artifacts.
 const TEMP_FAST_TRACK_MIN_OBSERVED_AGE: Duration = Duration::from_secs(2 * 60);
 
@@ -91,10 +129,25 @@ const TEMP_FAST_TRACK_MIN_OBSERVED_AGE: Duration = Duration::from_secs(2 * 60);
 struct SharedExecutorConfig {
     dry_run: AtomicBool,
     max_batch_size: AtomicUsize,
+    /// Deletion worker threads (`scanner.delete_workers`).
+    delete_workers: AtomicUsize,
     /// f64 stored as u64 bits (to_bits/from_bits).
     min_score_bits: AtomicU64,
     repeat_base_cooldown_secs: AtomicU64,
     repeat_max_cooldown_secs: AtomicU64,
+    /// Categories truncated in place when held open (`scanner.truncate_*`).
+    truncate_categories: RwLock<Vec<ArtifactCategory>>,
+    /// Removable/transient mounts the executor must not delete from
+    /// (`mounts.auto_delete_removable = false`).
+    no_delete_mounts: RwLock<Vec<PathBuf>>,
+    /// Watched mounts currently mounted read-only; deletes there would fail.
+    readonly_mounts: RwLock<Vec<PathBuf>>,
+    /// Archival tiering settings (`archive.*`); `None` deletes everything.
+    relocation: RwLock<Option<Relocation>>,
+    /// IO pacing for removals (`scanner.io_throttle_*`); `None` never pauses.
+    io_throttle: RwLock<Option<IoThrottle>>,
+    /// A maintenance window (`sbh pause`) is in force; batches are dropped.
+    paused: AtomicBool,
 }
 
 impl SharedExecutorConfig {
@@ -104,13 +157,22 @@ impl SharedExecutorConfig {
         min_score: f64,
         repeat_base_cooldown: u64,
         repeat_max_cooldown: u64,
+        truncate_categories: Vec<ArtifactCategory>,
+        relocation: Option<Relocation>,
     ) -> Self {
         Self {
             dry_run: AtomicBool::new(dry_run),
             max_batch_size: AtomicUsize::new(max_batch_size),
+            delete_workers: AtomicUsize::new(1),
             min_score_bits: AtomicU64::new(min_score.to_bits()),
             repeat_base_cooldown_secs: AtomicU64::new(repeat_base_cooldown),
             repeat_max_cooldown_secs: AtomicU64::new(repeat_max_cooldown),
+            truncate_categories: RwLock::new(truncate_categories),
+            no_delete_mounts: RwLock::new(Vec::new()),
+            readonly_mounts: RwLock::new(Vec::new()),
+            relocation: RwLock::new(relocation),
+            io_throttle: RwLock::new(None),
+            paused: AtomicBool::new(false),
         }
     }
 
@@ -129,11 +191,38 @@ impl SharedExecutorConfig {
     fn repeat_max_cooldown_secs(&self) -> u64 {
         self.repeat_max_cooldown_secs.load(Ordering::Relaxed)
     }
+
+    fn truncate_categories(&self) -> Vec<ArtifactCategory> {
+        self.truncate_categories.read().clone()
+    }
+
+    fn no_delete_mounts(&self) -> Vec<PathBuf> {
+        self.no_delete_mounts.read().clone()
+    }
+
+    fn readonly_mounts(&self) -> Vec<PathBuf> {
+        self.readonly_mounts.read().clone()
+    }
+
+    fn relocation(&self) -> Option<Relocation> {
+        self.relocation.read().clone()
+    }
+
+    fn io_throttle(&self) -> Option<IoThrottle> {
+        *self.io_throttle.read()
+    }
 }
 
 // ──────────────────── thread panic tracking ────────────────────
 
 const MAX_RESPAWNS: u32 = 3;
+const MIN_STALL_THRESHOLD: Duration = Duration::from_secs(30);
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const STALL_RESTART_WINDOW: Duration = Duration::from_hours(1);
+/// Unix times of recent watchdog restarts, carried across the re-exec.
+const WATCHDOG_RESTARTS_ENV: &str = "SBH_WATCHDOG_RESTARTS";
+/// Why the previous image restarted; logged once by the new one.
+const WATCHDOG_NOTE_ENV: &str = "SBH_WATCHDOG_RESTART_NOTE";
 const RESPAWN_WINDOW: Duration = Duration::from_secs(300);
 const THREAD_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
@@ -167,6 +256,7 @@ pub struct ScanRequest {
     pub urgency: f64,
     pub pressure_level: PressureLevel,
     pub max_delete_batch: usize,
+    pub mode: ScanMode,
     /// When config is reloaded, this carries the updated scoring and scanner config.
     pub config_update: Option<(
         crate::core::config::ScoringConfig,
@@ -174,6 +264,30 @@ pub struct ScanRequest {
     )>,
 }
 
+/// How far a scan reaches (`scanner.deep_scan_schedule`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanMode {
+    /// The configured depth and age floor; the only mode without a schedule.
+    #[default]
+    Full,
+    /// Capped at `scanner.shallow_max_depth`, between deep-scan windows.
+    Shallow,
+    /// Full depth with `scanner.deep_scan_min_file_age_minutes`, in a
+    /// scheduled window.
+    Deep,
+}
+
+impl ScanMode {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Shallow => "shallow",
+            Self::Deep => "deep",
+        }
+    }
+}
+
 /// Scored candidates ready for deletion.
 #[derive(Debug, Clone)]
 pub struct DeletionBatch {
@@ -205,6 +319,13 @@ enum WorkerReport {
         deleted: u64,
         bytes_freed: u64,
         failed: u64,
+        /// Mounts the batch removed, truncated, or relocated anything on.
+        mounts: Vec<PathBuf>,
+    },
+    /// Executor re-checked a mount's free space after a batch (see [`verify_reclaim`]).
+    ReclaimVerified {
+        verification: ReclaimVerification,
+        elapsed: Duration,
     },
 }
 
@@ -222,6 +343,8 @@ pub struct DaemonArgs {
     pub pidfile: Option<PathBuf>,
     /// Systemd watchdog timeout in seconds (0 = disabled).
     pub watchdog_sec: u64,
+    /// Run a single loop iteration, wait for the work it queued, and exit.
+    pub once: bool,
 }
 
 impl Default for DaemonArgs {
@@ -230,10 +353,37 @@ impl Default for DaemonArgs {
             foreground: true,
             pidfile: None,
             watchdog_sec: 0,
+            once: false,
         }
     }
 }
 
+/// What one `sbh daemon --once` pass saw and did.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunOnceSummary {
+    /// Worst pressure level across watched mounts.
+    pub level: String,
+    pub urgency: f64,
+    /// Mount that drove `level`.
+    pub causing_mount: PathBuf,
+    /// Seconds until the causing mount reaches red, when a trend exists.
+    pub predicted_seconds: Option<f64>,
+    /// Predictive policy verdict for the pass.
+    pub predictive_action: String,
+    pub policy_mode: String,
+    pub dry_run: bool,
+    /// Enforcement was skipped for an `sbh pause` window.
+    pub paused: bool,
+    pub scans: u64,
+    pub candidates: usize,
+    pub deletions: u64,
+    pub bytes_freed: u64,
+    pub errors: u64,
+    pub ballast_available: usize,
+    pub ballast_total: usize,
+    pub elapsed_ms: u64,
+}
+
 struct MountMonitor {
     rate_estimator: DiskRateEstimator,
     pressure_controller: PidPressureController,
@@ -249,7 +399,9 @@ struct GuardSample {
 }
 
 impl MountMonitor {
-    fn new(config: &Config) -> Self {
+    /// A monitor for `mount` using its `[[pressure.mounts]]` thresholds.
+    fn new(config: &Config, mount: &Path) -> Self {
+        let pressure = config.pressure.for_mount_now(mount);
         let rate_estimator = DiskRateEstimator::new(
             config.telemetry.ewma_base_alpha,
             config.telemetry.ewma_min_alpha,
@@ -262,17 +414,17 @@ impl MountMonitor {
             0.08,  // ki
             0.02,  // kd
             100.0, // integral_cap
-            config.pressure.green_min_free_pct,
+            pressure.green_min_free_pct,
             1.0, // hysteresis_pct
-            config.pressure.green_min_free_pct,
-            config.pressure.yellow_min_free_pct,
-            config.pressure.orange_min_free_pct,
-            config.pressure.red_min_free_pct,
-            Duration::from_millis(config.pressure.poll_interval_ms),
+            pressure.green_min_free_pct,
+            pressure.yellow_min_free_pct,
+            pressure.orange_min_free_pct,
+            pressure.red_min_free_pct,
+            Duration::from_millis(pressure.poll_interval_ms),
         );
-        if config.pressure.prediction.enabled {
+        if pressure.prediction.enabled {
             pressure_controller
-                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+                .set_action_horizon_minutes(pressure.prediction.action_horizon_minutes);
         }
 
         Self {
@@ -283,7 +435,8 @@ impl MountMonitor {
         }
     }
 
-    fn update_config(&mut self, config: &Config) {
+    fn update_config(&mut self, config: &Config, mount: &Path) {
+        let pressure = config.pressure.for_mount_now(mount);
         self.rate_estimator.update_params(
             config.telemetry.ewma_base_alpha,
             config.telemetry.ewma_min_alpha,
@@ -292,24 +445,36 @@ impl MountMonitor {
         );
 
         self.pressure_controller
-            .set_target_free_pct(config.pressure.green_min_free_pct);
-        self.pressure_controller.set_pressure_thresholds(
-            config.pressure.green_min_free_pct,
-            config.pressure.yellow_min_free_pct,
-            config.pressure.orange_min_free_pct,
-            config.pressure.red_min_free_pct,
-        );
+            .set_target_free_pct(pressure.green_min_free_pct);
+        self.set_thresholds(&pressure);
         self.pressure_controller
-            .set_base_poll_interval(Duration::from_millis(config.pressure.poll_interval_ms));
+            .set_base_poll_interval(Duration::from_millis(pressure.poll_interval_ms));
 
-        if config.pressure.prediction.enabled {
+        if pressure.prediction.enabled {
             self.pressure_controller
-                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+                .set_action_horizon_minutes(pressure.prediction.action_horizon_minutes);
         } else {
             self.pressure_controller.disable_urgency_boost();
         }
     }
 
+    /// Track thresholds that move with the time of day. Small per-tick
+    /// changes keep the controller's accumulated state.
+    fn follow_thresholds(&mut self, pressure: &PressureConfig) {
+        self.pressure_controller
+            .follow_target_free_pct(pressure.green_min_free_pct);
+        self.set_thresholds(pressure);
+    }
+
+    fn set_thresholds(&mut self, pressure: &PressureConfig) {
+        self.pressure_controller.set_pressure_thresholds(
+            pressure.green_min_free_pct,
+            pressure.yellow_min_free_pct,
+            pressure.orange_min_free_pct,
+            pressure.red_min_free_pct,
+        );
+    }
+
     fn observe_guard(
         &mut self,
         now: Instant,
@@ -359,6 +524,19 @@ impl MountMonitor {
 
         self.guard.diagnostics()
     }
+
+    /// Feed a post-batch reclaim check to the guard as a rate observation:
+    /// the claimed bytes are the forecast, the `statvfs` gain the outcome.
+    #[allow(clippy::cast_precision_loss)]
+    fn observe_reclaim(&mut self, verification: &ReclaimVerification, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64().max(1e-3);
+        self.guard.observe(CalibrationObservation {
+            predicted_rate: -(verification.expected_bytes as f64) / seconds,
+            actual_rate: -(verification.observed_bytes as f64) / seconds,
+            predicted_tte: f64::INFINITY,
+            actual_tte: f64::INFINITY,
+        });
+    }
 }
 
 // ──────────────────── main daemon struct ────────────────────
@@ -371,10 +549,26 @@ pub struct MonitoringDaemon {
     logger_handle: ActivityLoggerHandle,
     logger_join: Option<thread::JoinHandle<()>>,
     signal_handler: SignalHandler,
+    /// `org.sbh.Daemon` on the bus, when `[dbus] enabled` and available.
+    dbus: Option<DbusService>,
+    /// CLI control socket, when `[control] enabled` and it could be bound.
+    control: Option<ControlServer>,
     watchdog: WatchdogHeartbeat,
     fs_collector: FsStatsCollector,
     mount_monitors: HashMap<PathBuf, MountMonitor>,
+    mount_watcher: MountWatcher,
+    mount_classes: HashMap<PathBuf, MountClass>,
+    /// Resolved identity per mount for `rates`; resolved once, since it
+    /// stats the mount point, and dropped whenever the mount table changes.
+    rate_keys: HashMap<PathBuf, MountKey>,
+    /// Stale mounts already alerted on, cleared when they answer again.
+    stale_alerted: HashSet<PathBuf>,
+    /// Read-only mounts already alerted on, cleared when they turn writable.
+    readonly_alerted: HashSet<PathBuf>,
+    /// Recent per-process writes, snapshotted when pressure escalates.
+    writer_history: WriterHistory,
     special_locations: SpecialLocationRegistry,
+    coredump_policy: CoredumpPolicy,
     ballast_coordinator: BallastPoolCoordinator,
     release_controller: BallastReleaseController,
     notification_manager: NotificationManager,
@@ -383,6 +577,7 @@ pub struct MonitoringDaemon {
     shared_executor_config: Arc<SharedExecutorConfig>,
     shared_scoring_config: Arc<RwLock<crate::core::config::ScoringConfig>>,
     shared_scanner_config: Arc<RwLock<crate::core::config::ScannerConfig>>,
+    shared_costs_config: Arc<RwLock<crate::core::config::CostsConfig>>,
     cached_primary_path: PathBuf,
     start_time: Instant,
     last_pressure_level: PressureLevel,
@@ -403,11 +598,146 @@ pub struct MonitoringDaemon {
     last_swap_thrash_warning: Option<Instant>,
     swap_thrash_active: bool,
     last_scan_channel_warn: Option<Instant>,
+    /// When each mount's latest automated cleanup pass finished
+    /// (`scanner.mount_cooldown_secs`).
+    last_cleanup_pass: HashMap<PathBuf, Instant>,
+    last_cleanup_deferral_log: Option<Instant>,
+    /// `scanner.deep_scan_schedule`; while set, routine scans stay shallow.
+    deep_scan_schedule: Option<CronSchedule>,
+    /// Local time the next deep scan is due.
+    next_deep_scan: Option<NaiveDateTime>,
+    last_deep_scan: Option<chrono::DateTime<chrono::Utc>>,
+    last_ballast_verify: Option<Instant>,
+    last_disk_health_probe: Option<Instant>,
+    /// Latest health of each disk backing a watched mount, with its mounts.
+    disk_health: Vec<(DiskHealth, Vec<PathBuf>)>,
+    /// Most severe (verdict, filling) already alerted per disk.
+    disk_health_alerted: HashMap<String, (HealthVerdict, bool)>,
+    last_quarantine_check: Option<Instant>,
+    /// Maintenance window in force (`sbh pause`); enforcement is skipped.
+    maintenance: Option<MaintenanceWindow>,
+    /// Marker files (maintenance, probation) whose unreadability was
+    /// already reported.
+    unreadable_markers: HashSet<PathBuf>,
+    /// When the running config was last read from disk.
+    config_loaded_at: chrono::DateTime<chrono::Utc>,
+    /// Policy fallback count when the daemon first ran the config on
+    /// probation, keyed by the probation's `started_at`.
+    probation_fallback_baseline: Option<(String, u64)>,
     self_monitor: SelfMonitor,
+    /// Set when RSS nears the memory budget; the scanner thread drops its
+    /// watch index and clears the flag.
+    shed_walker_index: Arc<AtomicBool>,
     policy_engine: Arc<Mutex<PolicyEngine>>,
     shared_guard_diagnostics: Arc<RwLock<Option<GuardDiagnostics>>>,
     scanner_heartbeat: Arc<ThreadHeartbeat>,
     executor_heartbeat: Arc<ThreadHeartbeat>,
+    loop_progress: Arc<LoopProgress>,
+    /// `sbh daemon --once`: leave the loop after its first pass.
+    once: bool,
+}
+
+/// Predicted seconds until a mount reaches its red threshold, when it is
+/// filling toward it.
+fn seconds_to_red(rate_estimate: &RateEstimate) -> Option<f64> {
+    (rate_estimate.seconds_to_threshold.is_finite() && rate_estimate.seconds_to_threshold > 0.0)
+        .then_some(rate_estimate.seconds_to_threshold)
+}
+
+/// Main-loop silence after which the stall watchdog restarts the loop:
+/// `watchdog.stall_intervals` poll intervals, never under 30 seconds.
+fn stall_threshold(config: &Config) -> Duration {
+    Duration::from_millis(
+        config
+            .pressure
+            .poll_interval_ms
+            .saturating_mul(config.watchdog.stall_intervals),
+    )
+    .max(MIN_STALL_THRESHOLD)
+}
+
+/// Watchdog restarts inherited through `SBH_WATCHDOG_RESTARTS` that fall
+/// inside the rate-limit window ending at `now` (Unix seconds).
+fn recent_watchdog_restarts(inherited: Option<&str>, now: i64) -> Vec<i64> {
+    let window = i64::try_from(STALL_RESTART_WINDOW.as_secs()).unwrap_or(i64::MAX);
+    inherited
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|at| at.trim().parse::<i64>().ok())
+        .filter(|at| now.saturating_sub(*at) < window)
+        .collect()
+}
+
+/// Replace this process with a fresh run of the same command line, passing
+/// the restart history and `note` through the environment. Returns only
+/// when the exec fails, with the reason.
+fn reexec_self(restarts: &[i64], note: &str) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => return format!("cannot locate the sbh binary: {e}"),
+        };
+        let restarts = restarts
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let err = std::process::Command::new(&exe)
+            .args(std::env::args_os().skip(1))
+            .env(WATCHDOG_RESTARTS_ENV, restarts)
+            .env(WATCHDOG_NOTE_ENV, note)
+            .exec();
+        format!("{}: {err}", exe.display())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (restarts, note);
+        "re-exec requires Unix".to_string()
+    }
+}
+
+/// Scan roots as the daemon watches them: `/` when none are configured.
+fn watch_roots(config: &Config) -> Vec<PathBuf> {
+    if config.scanner.root_paths.is_empty() {
+        vec![PathBuf::from("/")]
+    } else {
+        config.scanner.root_paths.clone()
+    }
+}
+
+/// This install's burn-in record, created on first start: a fresh install
+/// (no `state.json` yet) begins one, an existing install is waived so an
+/// upgrade never stops a working daemon from deleting.
+fn load_burn_in(config: &Config) -> Option<BurnIn> {
+    let path = burn_in_path(&config.paths.state_file);
+    match read_burn_in(&path) {
+        Ok(Some(burn_in)) => return Some(burn_in),
+        Ok(None) => {}
+        Err(e) => {
+            diag::warn(
+                "daemon",
+                format_args!("ignoring unreadable {}: {e}", path.display()),
+            );
+            return None;
+        }
+    }
+    let now = chrono::Utc::now();
+    let burn_in = if config.policy.burn_in_enabled() && !config.paths.state_file.exists() {
+        diag::info("daemon", "fresh install: starting burn-in in observe mode");
+        BurnIn::start(now)
+    } else {
+        BurnIn::waived(now, config.policy.initial_mode)
+    };
+    if let Err(e) = write_burn_in(&path, &burn_in) {
+        diag::warn(
+            "daemon",
+            format_args!("could not record burn-in at {}: {e}", path.display()),
+        );
+    }
+    Some(burn_in)
 }
 
 fn compute_primary_path(config: &Config) -> PathBuf {
@@ -430,6 +760,39 @@ fn bytes_to_pct(value: u64, total: u64) -> f64 {
     }
 }
 
+/// Mode for pressure-driven scans: shallow between deep-scan windows, except
+/// at Red and above where nothing may be left unscanned.
+fn routine_scan_mode(deep_scan_scheduled: bool, level: PressureLevel) -> ScanMode {
+    if deep_scan_scheduled && level < PressureLevel::Red {
+        ScanMode::Shallow
+    } else {
+        ScanMode::Full
+    }
+}
+
+/// Time left on a cleanup cooldown at `now`; `None` once `cooldown` has
+/// passed since `last_pass` (a zero cooldown never holds anything off).
+fn cleanup_cooldown_remaining(
+    last_pass: Instant,
+    cooldown: Duration,
+    now: Instant,
+) -> Option<Duration> {
+    cooldown
+        .checked_sub(now.saturating_duration_since(last_pass))
+        .filter(|left| !left.is_zero())
+}
+
+/// The next pressure level up, for releasing ballast during a cleanup
+/// cooldown.
+const fn escalated_level(level: PressureLevel) -> PressureLevel {
+    match level {
+        PressureLevel::Green => PressureLevel::Yellow,
+        PressureLevel::Yellow => PressureLevel::Orange,
+        PressureLevel::Orange => PressureLevel::Red,
+        PressureLevel::Red | PressureLevel::Critical => PressureLevel::Critical,
+    }
+}
+
 fn is_swap_thrash_risk(memory: &MemoryInfo) -> bool {
     is_swap_thrash_risk_inner(memory, is_swap_zram_backed())
 }
@@ -648,18 +1011,77 @@ impl MonitoringDaemon {
         // 2. Signal handler.
         let signal_handler = SignalHandler::new();
 
-        // 3. Watchdog.
-        let watchdog = if args.watchdog_sec > 0 {
+        // 2a. DBus service (best effort: the daemon runs fine without it).
+        // A one-shot run leaves the bus name and control socket to any
+        // long-lived daemon on the host.
+        let dbus = if config.dbus.enabled && !args.once {
+            match DbusService::start(config.dbus.bus, signal_handler.clone()) {
+                Ok(service) => Some(service),
+                Err(e) => {
+                    diag::warn("daemon", format_args!("DBus service disabled: {e}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // 2b. CLI control socket (best effort, like DBus).
+        let control = if config.control.enabled && !args.once {
+            let path = config.control.socket_path();
+            match ControlServer::start(&path, signal_handler.clone()) {
+                Ok(server) => {
+                    diag::info(
+                        "daemon",
+                        format_args!("control socket at {}", server.path().display()),
+                    );
+                    Some(server)
+                }
+                Err(e) => {
+                    diag::warn("daemon", format_args!("control socket disabled: {e}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // 3. Watchdog (no service manager to ping in a container).
+        let watchdog = if args.watchdog_sec > 0 && !container::is_enabled() {
             WatchdogHeartbeat::new(args.watchdog_sec)
         } else {
             WatchdogHeartbeat::disabled()
         };
+        for missing in container::missing_tools(&config) {
+            if let Some(fallback) = missing.fallback {
+                diag::info(
+                    "daemon",
+                    format_args!(
+                        "{} need `{}`, which is not on PATH; using {fallback} instead",
+                        missing.feature, missing.program
+                    ),
+                );
+                continue;
+            }
+            diag::warn(
+                "daemon",
+                format_args!(
+                    "{} need `{}`, which is not on PATH; they will fail until it is installed",
+                    missing.feature, missing.program
+                ),
+            );
+        }
 
         // 4. Filesystem collector.
-        let fs_collector = FsStatsCollector::new(
+        let mut fs_collector = FsStatsCollector::new(
             Arc::clone(&platform),
             Duration::from_millis(config.telemetry.fs_cache_ttl_ms),
         );
+        fs_collector.set_stats_timeout(config.telemetry.fs_stats_timeout());
+        let mount_watcher = MountWatcher::new(
+            &watch_roots(&config),
+            &platform.mount_points().unwrap_or_default(),
+        );
 
         // 5. Discover special locations.
         let special_locations = SpecialLocationRegistry::discover(
@@ -678,7 +1100,8 @@ impl MonitoringDaemon {
 
         // 8. Scoring engine.
         let scoring_engine =
-            ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes);
+            ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes)
+                .with_costs(&config.costs);
 
         // 9. VOI Scheduler.
         let mut voi_scheduler = VoiScheduler::new(config.scheduler.clone());
@@ -693,13 +1116,21 @@ impl MonitoringDaemon {
             config.scoring.min_score,
             config.scanner.repeat_deletion_base_cooldown_secs,
             config.scanner.repeat_deletion_max_cooldown_secs,
+            config.scanner.truncate_allowlist(),
+            config.archive.relocation(),
         ));
+        *shared_executor_config.io_throttle.write() = config.scanner.io_throttle();
+        shared_executor_config
+            .delete_workers
+            .store(config.scanner.delete_workers, Ordering::Relaxed);
 
         let shared_scoring_config = Arc::new(RwLock::new(config.scoring.clone()));
         let shared_scanner_config = Arc::new(RwLock::new(config.scanner.clone()));
+        let shared_costs_config = Arc::new(RwLock::new(config.costs.clone()));
 
         // 11. Self-monitor (writes state.json for CLI, tracks health).
-        let self_monitor = SelfMonitor::new(config.paths.state_file.clone());
+        let mut self_monitor = SelfMonitor::new(config.paths.state_file.clone());
+        self_monitor.set_memory_budget(config.watchdog.memory_budget_mb * 1024 * 1024);
 
         // 12. Thread heartbeats for worker health detection.
         let scanner_heartbeat = ThreadHeartbeat::new("sbh-scanner");
@@ -709,11 +1140,20 @@ impl MonitoringDaemon {
         let notification_manager = NotificationManager::from_config(&config.notifications);
 
         // 14. Policy engine (progressive delivery gates for deletion pipeline).
-        let policy_engine = Arc::new(Mutex::new(PolicyEngine::new(config.policy.clone())));
+        let mut policy_engine = PolicyEngine::new(config.policy.clone());
+        if let Some(burn_in) = load_burn_in(&config) {
+            policy_engine.begin_burn_in(burn_in);
+        }
+        let policy_engine = Arc::new(Mutex::new(policy_engine));
         let shared_guard_diagnostics = Arc::new(RwLock::new(None));
 
         let cached_primary_path = compute_primary_path(&config);
         let prediction_config = config.pressure.prediction.clone();
+        let coredump_policy = CoredumpPolicy::new(&config.coredumps);
+        let deep_scan_schedule = config.scanner.deep_scan_schedule();
+        let next_deep_scan = deep_scan_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.next_after(Local::now().naive_local()));
 
         Ok(Self {
             config,
@@ -722,10 +1162,19 @@ impl MonitoringDaemon {
             logger_handle,
             logger_join: Some(logger_join),
             signal_handler,
+            dbus,
+            control,
             watchdog,
             fs_collector,
             mount_monitors: HashMap::new(),
+            mount_watcher,
+            mount_classes: HashMap::new(),
+            rate_keys: HashMap::new(),
+            stale_alerted: HashSet::new(),
+            readonly_alerted: HashSet::new(),
+            writer_history: WriterHistory::new(ATTRIBUTION_WINDOW),
             special_locations,
+            coredump_policy,
             ballast_coordinator,
             release_controller,
             notification_manager,
@@ -735,6 +1184,7 @@ impl MonitoringDaemon {
             shared_executor_config,
             shared_scoring_config,
             shared_scanner_config,
+            shared_costs_config,
             start_time,
             last_pressure_level: PressureLevel::Green,
             last_notified_pressure_level: PressureLevel::Green,
@@ -749,39 +1199,188 @@ impl MonitoringDaemon {
             last_swap_thrash_warning: None,
             swap_thrash_active: false,
             last_scan_channel_warn: None,
+            last_cleanup_pass: HashMap::new(),
+            last_cleanup_deferral_log: None,
+            deep_scan_schedule,
+            next_deep_scan,
+            last_deep_scan: None,
+            last_ballast_verify: None,
+            last_disk_health_probe: None,
+            disk_health: Vec::new(),
+            disk_health_alerted: HashMap::new(),
+            last_quarantine_check: None,
+            maintenance: None,
+            unreadable_markers: HashSet::new(),
+            config_loaded_at: chrono::Utc::now(),
+            probation_fallback_baseline: None,
             self_monitor,
+            shed_walker_index: Arc::new(AtomicBool::new(false)),
             scanner_heartbeat,
             executor_heartbeat,
             shared_guard_diagnostics,
+            loop_progress: LoopProgress::new(),
+            once: args.once,
         })
     }
 
-    /// Run the monitoring loop until shutdown is requested.
+    /// Run one loop iteration, let the scanner and executor finish what it
+    /// queued, and report what happened. This is `sbh daemon --once`, for
+    /// cron and for checking a config on a new host.
+    pub fn run_once(config: Config, args: &DaemonArgs) -> Result<RunOnceSummary> {
+        let args = DaemonArgs {
+            once: true,
+            ..args.clone()
+        };
+        let mut daemon = Self::init(config, &args)?;
+        let response = daemon.run_loop()?.ok_or_else(|| SbhError::Runtime {
+            details: "one-shot run interrupted before completing a pass".to_string(),
+        })?;
+        Ok(daemon.once_summary(&response))
+    }
+
+    /// Run the daemon under the in-process stall watchdog (`[watchdog]`).
+    ///
+    /// The loop runs on its own thread. When it goes `stall_intervals` poll
+    /// intervals without a tick, the thread states are dumped to stderr and
+    /// the process re-executes itself with the same command line. Replacing
+    /// the image drops the wedged loop together with its workers, control
+    /// socket, and DBus name, so the fresh daemon never races the old one.
+    /// Past `max_restarts` per hour, or when the re-exec fails, this returns
+    /// an error so a service manager can restart the process.
     ///
     /// This is the main entry point for `sbh daemon`.
-    #[allow(clippy::too_many_lines)]
+    pub fn run_supervised(config: Config, args: &DaemonArgs) -> Result<()> {
+        crash::install(&config);
+        if !config.watchdog.enabled {
+            return Self::init(config, args)?.run();
+        }
+        let stall_after = stall_threshold(&config);
+        let progress = LoopProgress::new();
+        let loop_thread = {
+            let config = config.clone();
+            let args = args.clone();
+            let progress = Arc::clone(&progress);
+            let note = std::env::var(WATCHDOG_NOTE_ENV).ok();
+            thread::Builder::new()
+                .name("sbh-main".to_string())
+                .spawn(move || {
+                    let mut daemon = Self::init(config, &args)?;
+                    daemon.loop_progress = progress;
+                    if let Some(message) = note {
+                        daemon.logger_handle.send(ActivityEvent::Error {
+                            code: "SBH-3900".to_string(),
+                            message,
+                        });
+                    }
+                    daemon.run()
+                })
+                .map_err(|source| SbhError::Runtime {
+                    details: format!("failed to spawn main loop thread: {source}"),
+                })?
+        };
+
+        let stalled_for = loop {
+            if loop_thread.is_finished() {
+                return loop_thread.join().unwrap_or_else(|_| {
+                    Err(SbhError::Runtime {
+                        details: "main loop panicked".to_string(),
+                    })
+                });
+            }
+            if let Some(stalled_for) = progress
+                .stalled_for()
+                .filter(|stalled_for| *stalled_for >= stall_after)
+            {
+                break stalled_for;
+            }
+            thread::sleep(STALL_CHECK_INTERVAL);
+        };
+
+        let phase = progress.phase();
+        diag::error(
+            "daemon",
+            format_args!(
+                "main loop stalled for {}s in step '{phase}'; thread snapshot:",
+                stalled_for.as_secs()
+            ),
+        );
+        for line in thread_snapshot() {
+            diag::error("daemon", format_args!("  {line}"));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut restarts =
+            recent_watchdog_restarts(std::env::var(WATCHDOG_RESTARTS_ENV).ok().as_deref(), now);
+        if restarts.len() >= config.watchdog.max_restarts {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "main loop wedged in step '{phase}' after {} watchdog restarts this hour; \
+                     exiting so a service manager can restart sbh (run it under systemd or \
+                     launchd, or in a restart loop)",
+                    restarts.len()
+                ),
+            });
+        }
+        restarts.push(now);
+        diag::warn(
+            "daemon",
+            format_args!(
+                "restarting sbh in place ({}/{} this hour)",
+                restarts.len(),
+                config.watchdog.max_restarts
+            ),
+        );
+        let note = format!(
+            "main loop restarted after stalling {}s in step '{phase}'",
+            stalled_for.as_secs()
+        );
+        Err(SbhError::Runtime {
+            details: format!(
+                "main loop wedged in step '{phase}' and re-exec failed ({}); exiting so a \
+                 service manager can restart sbh (run it under systemd or launchd, or in a \
+                 restart loop)",
+                reexec_self(&restarts, &note)
+            ),
+        })
+    }
+
+    /// Run the monitoring loop until shutdown is requested.
     pub fn run(&mut self) -> Result<()> {
-        // Log startup.
-        let config_hash = self.config.stable_hash().unwrap_or_default();
-        self.logger_handle.send(ActivityEvent::DaemonStarted {
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            config_hash,
-        });
-        self.notification_manager
-            .notify(&NotificationEvent::DaemonStarted {
+        self.run_loop().map(|_| ())
+    }
+
+    /// The monitoring loop. Returns the last pressure response when a
+    /// one-shot run completes its pass.
+    #[allow(clippy::too_many_lines)]
+    fn run_loop(&mut self) -> Result<Option<crate::monitor::pid::PressureResponse>> {
+        // Log startup. A one-shot run from cron would flood the timeline and
+        // notification channels with start/stop pairs, so it stays quiet.
+        if !self.once {
+            let config_hash = self.config.stable_hash().unwrap_or_default();
+            self.logger_handle.send(ActivityEvent::DaemonStarted {
                 version: env!("CARGO_PKG_VERSION").to_string(),
-                volumes_monitored: self.ballast_coordinator.pool_count(),
+                config_hash,
             });
+            self.notification_manager
+                .notify(&NotificationEvent::DaemonStarted {
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    volumes_monitored: self.ballast_coordinator.pool_count(),
+                });
+        }
 
         // Provision ballast files (idempotent).
         self.provision_ballast()?;
 
         // Initial pressure check.
+        self.refresh_mount_classes();
         let initial_response = self.check_pressure()?;
         if initial_response.level != PressureLevel::Green {
-            eprintln!(
-                "[SBH-DAEMON] starting under pressure: {:?} (urgency={:.2})",
-                initial_response.level, initial_response.urgency
+            diag::warn(
+                "daemon",
+                format_args!(
+                    "starting under pressure: {:?} (urgency={:.2})",
+                    initial_response.level, initial_response.urgency
+                ),
             );
         }
 
@@ -809,21 +1408,42 @@ impl MonitoringDaemon {
         )?);
 
         let mut last_health_check = Instant::now();
+        let mut once_response = None;
 
         // ──────── main monitoring loop ────────
         loop {
             // 1. Check shutdown signal.
             if self.signal_handler.should_shutdown() {
-                eprintln!("[SBH-DAEMON] shutdown requested");
+                diag::info("daemon", "shutdown requested");
                 break;
             }
 
             // 2. Check config reload signal.
+            self.loop_progress.tick("config_reload");
             if self.signal_handler.should_reload() {
                 self.handle_config_reload(&scan_tx);
             }
 
+            // 2a. Maintenance window (`sbh pause`); expires on its own.
+            self.loop_progress.tick("maintenance");
+            self.check_maintenance();
+            let paused = self.maintenance.is_some();
+
+            // 2a'. Config on probation (`sbh tune --apply`); reverted if the
+            // policy engine falls back while it runs.
+            self.loop_progress.tick("config_probation");
+            self.check_config_probation();
+
+            // 2b. Pick up mounts that appeared or vanished under the roots.
+            self.loop_progress.tick("mount_table");
+            self.check_mounts();
+
+            // 2c. Sample per-process write counters for incident blame.
+            self.loop_progress.tick("writer_sample");
+            self.sample_writers();
+
             // 3. Collect filesystem stats and run pressure analysis.
+            self.loop_progress.tick("pressure_check");
             let response = match self.check_pressure() {
                 Ok(r) => r,
                 Err(e) => {
@@ -831,6 +1451,10 @@ impl MonitoringDaemon {
                         code: "SBH-2001".to_string(),
                         message: format!("pressure check failed: {e}"),
                     });
+                    if self.once {
+                        self.shutdown(scan_tx, del_tx, scanner_join, executor_join, &report_rx);
+                        return Err(e);
+                    }
                     // On error, sleep and retry.
                     thread::sleep(Duration::from_secs(1));
                     continue;
@@ -862,18 +1486,58 @@ impl MonitoringDaemon {
                         self.last_notified_pressure_level = response.level;
                     }
                 }
+                if response.level > self.last_pressure_level
+                    && response.level >= PressureLevel::Orange
+                {
+                    self.log_attribution_snapshot(&response);
+                }
+                self.announce_dbus_transition(&response);
                 self.last_pressure_level = response.level;
             }
 
-            // 5. Handle pressure response.
-            self.handle_pressure(&response, &scan_tx, &scan_rx);
+            // 5. Handle pressure response (skipped while paused).
+            self.loop_progress.tick("pressure_response");
+            if !paused {
+                self.handle_pressure(&response, &scan_tx, &scan_rx);
+            }
 
             // 6. Check special locations independently.
-            self.check_special_locations(&scan_tx, &scan_rx);
+            self.loop_progress.tick("special_locations");
+            if !paused {
+                self.check_special_locations(&scan_tx, &scan_rx);
+            }
+
+            // 6a. Start a scheduled deep scan once its window opens.
+            self.loop_progress.tick("deep_scan");
+            if !paused {
+                self.check_deep_scan(&response, &scan_tx, &scan_rx);
+            }
 
             // 7. Detect swap-thrash conditions and alert with cooldown.
+            self.loop_progress.tick("swap_thrash");
             self.check_swap_thrash();
 
+            // 7a. Sweep crash dumps according to pressure.
+            self.loop_progress.tick("core_dumps");
+            if !paused {
+                self.check_core_dumps(response.level);
+            }
+
+            // 7c. Verify ballast integrity and re-create corrupted files.
+            self.loop_progress.tick("ballast_verify");
+            self.check_ballast_integrity(response.level);
+
+            // 7d. Probe disk health and escalate failing disks that are filling.
+            self.loop_progress.tick("disk_health");
+            self.check_disk_health();
+
+            // 7e. Purge expired quarantine entries, or all of them on a mount
+            // at orange pressure.
+            self.loop_progress.tick("quarantine");
+            if !paused {
+                self.check_quarantine(response.level);
+            }
+
             // 8. Watchdog heartbeat.
             self.watchdog.maybe_notify(&format!(
                 "pressure={:?} urgency={:.2}",
@@ -881,97 +1545,30 @@ impl MonitoringDaemon {
             ));
 
             // 7b. Drain worker reports so counters are current for state write.
+            self.loop_progress.tick("worker_reports");
             while let Ok(report) = report_rx.try_recv() {
-                match report {
-                    WorkerReport::ScanCompleted {
-                        candidates,
-                        duration,
-                        root_stats,
-                    } => {
-                        self.self_monitor.record_scan(candidates, 0, duration);
-                        let now = Instant::now();
-                        #[allow(clippy::cast_possible_truncation)]
-                        for stat in root_stats {
-                            self.voi_scheduler.record_scan_result(
-                                &stat.path,
-                                stat.potential_bytes,
-                                stat.candidates_found as u32,
-                                stat.false_positives as u32,
-                                stat.duration.as_millis() as f64,
-                                now,
-                            );
-                        }
-                        self.voi_scheduler.end_window();
-                    }
-                    WorkerReport::DeletionCompleted {
-                        deleted,
-                        bytes_freed,
-                        failed,
-                    } => {
-                        self.self_monitor.record_deletions(deleted, bytes_freed);
-                        if deleted > 0 {
-                            // Best effort: we don't have the mount point here easily without tracking
-                            // it through the batch. Use "primary" or "various".
-                            let items_deleted = usize::try_from(deleted).unwrap_or(usize::MAX);
-                            self.notification_manager.notify(
-                                &NotificationEvent::CleanupCompleted {
-                                    items_deleted,
-                                    bytes_freed,
-                                    mount: "various".to_string(),
-                                },
-                            );
-                        }
-                        for _ in 0..failed {
-                            self.self_monitor.record_error();
-                        }
-                    }
-                }
+                self.apply_worker_report(report);
             }
 
             // 7c. Self-monitoring: write state file + check RSS.
-            {
-                // Use the causing mount from the worst response so the state
-                // file reflects the mount that actually drove the pressure
-                // level, not the primary path which may be healthy.
-                let state_path = &response.causing_mount;
-                let free_pct = self
-                    .fs_collector
-                    .collect(state_path)
-                    .map(|s| s.free_pct())
-                    .unwrap_or(0.0);
-                let mount_str = state_path.to_string_lossy().into_owned();
-                let ballast_available = self
-                    .ballast_coordinator
-                    .inventory()
-                    .iter()
-                    .map(|i| i.files_available)
-                    .sum();
-                let ballast_total = self
-                    .ballast_coordinator
-                    .inventory()
-                    .iter()
-                    .map(|i| i.files_total)
-                    .sum();
-                let dropped_log_events = self.logger_handle.dropped_events();
-
-                let policy_mode = self.policy_engine.lock().mode().to_string();
-                self.self_monitor.maybe_write_state(
-                    response.level,
-                    free_pct,
-                    &mount_str,
-                    ballast_available,
-                    ballast_total,
-                    dropped_log_events,
-                    &policy_mode,
-                );
-            }
+            self.loop_progress.tick("self_monitor");
+            self.publish_state(&response);
 
-            // 8. Forced scan signal (SIGUSR1).
+            // 8. Forced scan signal (SIGUSR1) and state dump (SIGUSR2).
             if self.signal_handler.should_scan() {
                 self.trigger_forced_scan(&scan_tx, &response);
             }
+            if self.signal_handler.should_dump() {
+                self.dump_state(&response);
+            }
+
+            // 8a. Ballast releases requested over DBus or the control socket.
+            self.handle_dbus_releases(&response);
+            self.handle_control_releases(&response);
+            self.log_control_annotations();
 
             // 9. Thread health check.
+            self.loop_progress.tick("thread_health");
             if last_health_check.elapsed() >= THREAD_HEALTH_CHECK_INTERVAL {
                 last_health_check = Instant::now();
 
@@ -979,12 +1576,12 @@ impl MonitoringDaemon {
                     .as_ref()
                     .is_some_and(std::thread::JoinHandle::is_finished);
                 if scanner_dead {
-                    eprintln!("[SBH-DAEMON] scanner thread exited unexpectedly");
+                    diag::error("daemon", "scanner thread exited unexpectedly");
                     if let Some(handle) = scanner_join.take() {
                         let _ = handle.join();
                     }
                     if scanner_health.record_panic() {
-                        eprintln!("[SBH-DAEMON] respawning scanner thread");
+                        diag::warn("daemon", "respawning scanner thread");
                         self.scanner_heartbeat = ThreadHeartbeat::new("sbh-scanner");
                         match self.spawn_scanner_thread(
                             scan_rx.clone(),
@@ -999,7 +1596,10 @@ impl MonitoringDaemon {
                                     code: err.code().to_string(),
                                     message: format!("failed to respawn scanner thread: {err}"),
                                 });
-                                eprintln!("[SBH-DAEMON] scanner respawn failed: {err}");
+                                diag::error(
+                                    "daemon",
+                                    format_args!("scanner respawn failed: {err}"),
+                                );
                                 break;
                             }
                         }
@@ -1008,7 +1608,7 @@ impl MonitoringDaemon {
                             code: "SBH-3900".to_string(),
                             message: "scanner thread exceeded respawn limit".to_string(),
                         });
-                        eprintln!("[SBH-DAEMON] scanner exceeded respawn limit, shutting down");
+                        diag::error("daemon", "scanner exceeded respawn limit, shutting down");
                         break;
                     }
                 }
@@ -1017,12 +1617,12 @@ impl MonitoringDaemon {
                     .as_ref()
                     .is_some_and(std::thread::JoinHandle::is_finished);
                 if executor_dead {
-                    eprintln!("[SBH-DAEMON] executor thread exited unexpectedly");
+                    diag::error("daemon", "executor thread exited unexpectedly");
                     if let Some(handle) = executor_join.take() {
                         let _ = handle.join();
                     }
                     if executor_health.record_panic() {
-                        eprintln!("[SBH-DAEMON] respawning executor thread");
+                        diag::warn("daemon", "respawning executor thread");
                         self.executor_heartbeat = ThreadHeartbeat::new("sbh-executor");
                         match self.spawn_executor_thread(
                             del_rx.clone(),
@@ -1036,7 +1636,10 @@ impl MonitoringDaemon {
                                     code: err.code().to_string(),
                                     message: format!("failed to respawn executor thread: {err}"),
                                 });
-                                eprintln!("[SBH-DAEMON] executor respawn failed: {err}");
+                                diag::error(
+                                    "daemon",
+                                    format_args!("executor respawn failed: {err}"),
+                                );
                                 break;
                             }
                         }
@@ -1045,23 +1648,200 @@ impl MonitoringDaemon {
                             code: "SBH-3900".to_string(),
                             message: "executor thread exceeded respawn limit".to_string(),
                         });
-                        eprintln!("[SBH-DAEMON] executor exceeded respawn limit, shutting down");
+                        diag::error("daemon", "executor exceeded respawn limit, shutting down");
                         break;
                     }
                 }
             }
 
+            if self.once {
+                once_response = Some(response);
+                break;
+            }
+
             // 10. Sleep for the PID-adjusted interval.
+            self.loop_progress.tick("sleep");
             thread::sleep(response.scan_interval);
         }
 
         // ──────── shutdown sequence ────────
-        self.shutdown(scan_tx, del_tx, scanner_join, executor_join);
-        Ok(())
+        self.shutdown(scan_tx, del_tx, scanner_join, executor_join, &report_rx);
+        if let Some(response) = &once_response {
+            // Publish the counters the workers reported while draining.
+            self.self_monitor.force_next_write();
+            self.publish_state(response);
+        }
+        Ok(once_response)
     }
 
     // ──────────────────── helpers ────────────────────
 
+    /// Fold a scanner/executor report into counters and notifications.
+    fn apply_worker_report(&mut self, report: WorkerReport) {
+        match report {
+            WorkerReport::ScanCompleted {
+                candidates,
+                duration,
+                root_stats,
+            } => {
+                self.self_monitor.record_scan(candidates, 0, duration);
+                let now = Instant::now();
+                #[allow(clippy::cast_possible_truncation)]
+                for stat in root_stats {
+                    self.voi_scheduler.record_scan_result(
+                        &stat.path,
+                        stat.potential_bytes,
+                        stat.candidates_found as u32,
+                        stat.false_positives as u32,
+                        stat.duration.as_millis() as f64,
+                        now,
+                    );
+                }
+                self.voi_scheduler.end_window();
+            }
+            WorkerReport::DeletionCompleted {
+                deleted,
+                bytes_freed,
+                failed,
+                mounts,
+            } => {
+                self.self_monitor.record_deletions(deleted, bytes_freed);
+                let now = Instant::now();
+                for mount in &mounts {
+                    self.last_cleanup_pass.insert(mount.clone(), now);
+                }
+                if deleted > 0 {
+                    let items_deleted = usize::try_from(deleted).unwrap_or(usize::MAX);
+                    let mount = if mounts.is_empty() {
+                        "various".to_string()
+                    } else {
+                        mounts
+                            .iter()
+                            .map(|m| m.to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    self.notification_manager
+                        .notify(&NotificationEvent::CleanupCompleted {
+                            items_deleted,
+                            bytes_freed,
+                            mount,
+                        });
+                }
+                for _ in 0..failed {
+                    self.self_monitor.record_error();
+                }
+            }
+            WorkerReport::ReclaimVerified {
+                verification,
+                elapsed,
+            } => {
+                if let Some(monitor) = self.mount_monitors.get_mut(&verification.mount) {
+                    monitor.observe_reclaim(&verification, elapsed);
+                }
+            }
+        }
+    }
+
+    fn once_summary(&self, response: &crate::monitor::pid::PressureResponse) -> RunOnceSummary {
+        let inventory = self.ballast_coordinator.inventory();
+        let monitor = &self.self_monitor;
+        RunOnceSummary {
+            level: format!("{:?}", response.level).to_lowercase(),
+            urgency: response.urgency,
+            causing_mount: response.causing_mount.clone(),
+            predicted_seconds: response.predicted_seconds,
+            predictive_action: self.last_predictive_action.event_name().to_string(),
+            policy_mode: self.policy_engine.lock().mode().to_string(),
+            dry_run: self.config.scanner.dry_run,
+            paused: self.maintenance.is_some(),
+            scans: monitor.scan_count,
+            candidates: monitor.last_scan_candidates,
+            deletions: monitor.deletions_total,
+            bytes_freed: monitor.bytes_freed_total,
+            errors: monitor.errors_total,
+            ballast_available: inventory.iter().map(|i| i.files_available).sum(),
+            ballast_total: inventory.iter().map(|i| i.files_total).sum(),
+            #[allow(clippy::cast_possible_truncation)]
+            elapsed_ms: self.start_time.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Write state.json (rate-limited) and publish pressure over DBus.
+    fn publish_state(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        // Use the causing mount from the worst response so the state
+        // file reflects the mount that actually drove the pressure
+        // level, not the primary path which may be healthy.
+        let state_path = &response.causing_mount;
+        let free_pct = self
+            .fs_collector
+            .collect(state_path)
+            .map_or(0.0, |s| s.free_pct());
+        let mount_str = state_path.to_string_lossy().into_owned();
+        if let Some(dbus) = &self.dbus {
+            dbus.publish(&format!("{:?}", response.level), free_pct);
+        }
+        let ballast_available = self
+            .ballast_coordinator
+            .inventory()
+            .iter()
+            .map(|i| i.files_available)
+            .sum();
+        let ballast_total = self
+            .ballast_coordinator
+            .inventory()
+            .iter()
+            .map(|i| i.files_total)
+            .sum();
+        let dropped_log_events = self.logger_handle.dropped_events();
+
+        let policy_mode = self.policy_engine.lock().mode().to_string();
+        self.self_monitor.cleanup_cooldowns = self.cleanup_cooldowns();
+        self.self_monitor.deep_scan = self.deep_scan_state();
+        let rss = self.self_monitor.maybe_write_state(
+            response.level,
+            free_pct,
+            &mount_str,
+            ballast_available,
+            ballast_total,
+            dropped_log_events,
+            &policy_mode,
+        );
+        self.shed_caches(rss);
+    }
+
+    /// Drop the next internal cache when RSS nears `watchdog.memory_budget_mb`,
+    /// so the daemon gives memory back before a cgroup OOM kill takes it.
+    fn shed_caches(&mut self, rss: u64) {
+        let Some(stage) = self.self_monitor.next_shed_stage(rss) else {
+            return;
+        };
+        match stage {
+            ShedStage::WalkerIndex => self.shed_walker_index.store(true, Ordering::Relaxed),
+            ShedStage::RateHistory => {
+                for monitor in self.mount_monitors.values_mut() {
+                    monitor.guard.shed_history();
+                }
+            }
+            ShedStage::TelemetryBuffers => self.writer_history.shed(),
+        }
+        let budget = self.self_monitor.memory_budget();
+        diag::warn(
+            "daemon",
+            format_args!(
+                "RSS {} MB near memory budget {} MB; shed {}",
+                rss / (1024 * 1024),
+                budget / (1024 * 1024),
+                stage.as_str()
+            ),
+        );
+        self.logger_handle.send(ActivityEvent::MemoryPressure {
+            rss_bytes: rss,
+            budget_bytes: budget,
+            shed: stage.as_str().to_string(),
+        });
+    }
+
     /// Return the first configured root path, or `/` as fallback.
     fn primary_path(&self) -> &Path {
         &self.cached_primary_path
@@ -1071,24 +1851,20 @@ impl MonitoringDaemon {
 
     fn check_pressure(&mut self) -> Result<crate::monitor::pid::PressureResponse> {
         // Collect stats for all root paths.
-        let default_paths;
         let paths = if self.config.scanner.root_paths.is_empty() {
-            default_paths = [PathBuf::from("/")];
-            &default_paths[..]
+            vec![PathBuf::from("/")]
         } else {
-            &self.config.scanner.root_paths
+            self.config.scanner.root_paths.clone()
         };
 
-        // Group paths by mount point to avoid redundant updates.
-        let mut stats_by_mount: HashMap<PathBuf, crate::platform::pal::FsStats> = HashMap::new();
-
-        for path in paths {
-            if let Ok(stats) = self.fs_collector.collect(path) {
-                // If multiple paths share a mount, we just need one valid reading.
-                stats_by_mount
-                    .entry(stats.mount_point.clone())
-                    .or_insert(stats);
-            }
+        let (stats_by_mount, readonly) = self.collect_mount_stats(&paths);
+        if stats_by_mount.is_empty()
+            && let Some((mount, _)) = readonly.first()
+        {
+            return Ok(crate::monitor::pid::PressureResponse::idle(
+                Duration::from_millis(self.config.pressure.poll_interval_ms),
+                mount.clone(),
+            ));
         }
 
         if stats_by_mount.is_empty() {
@@ -1101,20 +1877,26 @@ impl MonitoringDaemon {
         let now = Instant::now();
         let mut worst_response: Option<crate::monitor::pid::PressureResponse> = None;
         let mut worst_guard_diag: Option<GuardDiagnostics> = None;
+        // Mounts may poll at different rates; sleep for the shortest.
+        let mut shortest_interval: Option<Duration> = None;
         // Reset per-tick predictive action so we track the worst across mounts.
         self.last_predictive_action = PredictiveAction::Clear;
+        // Rates are republished each tick so vanished mounts drop out.
+        self.self_monitor.rates.clear();
 
         // Update monitors for each active mount.
         for (mount_path, stats) in stats_by_mount {
             let monitor = self
                 .mount_monitors
                 .entry(mount_path.clone())
-                .or_insert_with(|| MountMonitor::new(&self.config));
+                .or_insert_with(|| MountMonitor::new(&self.config, &mount_path));
+            let pressure = self.config.pressure.for_mount_now(&mount_path);
+            monitor.follow_thresholds(&pressure);
+            let red_min_free_pct = pressure.red_min_free_pct;
 
             // Update EWMA rate estimator.
             #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-            let red_threshold_bytes =
-                (stats.total_bytes as f64 * self.config.pressure.red_min_free_pct / 100.0) as u64;
+            let red_threshold_bytes = (stats.total_bytes as f64 * red_min_free_pct / 100.0) as u64;
 
             let rate_estimate =
                 monitor
@@ -1127,14 +1909,7 @@ impl MonitoringDaemon {
                 &rate_estimate,
             );
 
-            // Predicted time to red threshold.
-            let predicted_seconds = if rate_estimate.seconds_to_threshold.is_finite()
-                && rate_estimate.seconds_to_threshold > 0.0
-            {
-                Some(rate_estimate.seconds_to_threshold)
-            } else {
-                None
-            };
+            let predicted_seconds = seconds_to_red(&rate_estimate);
 
             // Run PID controller.
             let reading = PressureReading {
@@ -1145,24 +1920,14 @@ impl MonitoringDaemon {
             let response = monitor
                 .pressure_controller
                 .update(reading, predicted_seconds, now);
+            shortest_interval = Some(
+                shortest_interval.map_or(response.scan_interval, |shortest| {
+                    shortest.min(response.scan_interval)
+                }),
+            );
+            self.record_mount_rate(&mount_path, &rate_estimate, predicted_seconds);
 
-            // Evaluate predictive policy with full confidence/trend gating.
-            let free_pct = stats.free_pct();
-            let mut pred_action =
-                self.predictive_policy
-                    .evaluate(&rate_estimate, free_pct, mount_path.clone());
-
-            // Force low-confidence predictions to Clear so they don't trigger
-            // scans or other downstream actions (breaks scan saturation feedback loop).
-            if !matches!(pred_action, PredictiveAction::Clear)
-                && rate_estimate.confidence < self.config.pressure.prediction.min_confidence
-            {
-                pred_action = PredictiveAction::Clear;
-            }
-
-            if pred_action.severity() > self.last_predictive_action.severity() {
-                self.last_predictive_action = pred_action;
-            }
+            self.track_predictive_action(&rate_estimate, stats.free_pct(), &mount_path);
 
             // Track worst response (highest urgency/severity).
             match worst_response {
@@ -1186,40 +1951,334 @@ impl MonitoringDaemon {
         }
 
         if let Some(diag) = worst_guard_diag.as_ref() {
-            let mut policy = self.policy_engine.lock();
-            policy.observe_window(diag);
-
-            // Emergency escalation: break fallback_safe deadlock when pressure
-            // has been at RED/Critical for too long and recovery can't trigger.
-            if let Some(ref response) = worst_response {
-                let pressure_is_critical = response.level >= PressureLevel::Red;
-                if policy.check_emergency_escalation(pressure_is_critical) {
-                    eprintln!(
-                        "[SBH-DAEMON] emergency escalation: fallback_safe → enforce \
-                         (pressure deadlock broken after sustained RED/Critical)"
-                    );
-                }
-            }
+            self.advance_policy(diag, worst_response.as_ref());
         }
         *self.shared_guard_diagnostics.write() = worst_guard_diag;
 
-        // Clean up monitors for unmounted/disappeared volumes?
-        // For now we keep them; volume churn is rare in typical operation.
-
+        if let (Some(response), Some(shortest)) = (worst_response.as_mut(), shortest_interval) {
+            response.scan_interval = shortest;
+        }
         worst_response.ok_or_else(|| crate::core::errors::SbhError::FsStats {
             path: PathBuf::from("/"),
             details: "internal error: stats collected but no response generated".to_string(),
         })
     }
 
-    fn log_pressure_change(&mut self, response: &crate::monitor::pid::PressureResponse) {
-        // Use the causing mount so the log entry reflects the mount that
-        // actually drove the pressure level change, not the primary path.
-        let (free_pct, mount, total, free) =
-            if let Ok(stats) = self.fs_collector.collect(&response.causing_mount) {
-                #[allow(clippy::cast_possible_wrap)]
-                (
-                    stats.free_pct(),
+    /// Current stats of every mount under pressure watch, with read-only
+    /// mounts split out as `(mount, free_pct)`.
+    fn collect_mount_stats(
+        &mut self,
+        paths: &[PathBuf],
+    ) -> (
+        HashMap<PathBuf, crate::platform::pal::FsStats>,
+        Vec<(PathBuf, f64)>,
+    ) {
+        // Group paths by mount point to avoid redundant updates.
+        let mut stats_by_mount: HashMap<PathBuf, crate::platform::pal::FsStats> = HashMap::new();
+
+        for path in paths {
+            if let Ok(stats) = self.fs_collector.collect(path) {
+                // If multiple paths share a mount, we just need one valid reading.
+                stats_by_mount
+                    .entry(stats.mount_point.clone())
+                    .or_insert(stats);
+            }
+        }
+        // Mounts plugged in beneath a root are not reachable through it.
+        for mount in self.mount_watcher.nested_mounts() {
+            if let Ok(stats) = self.fs_collector.collect(mount) {
+                stats_by_mount
+                    .entry(stats.mount_point.clone())
+                    .or_insert(stats);
+            }
+        }
+
+        self.check_stale_mounts();
+
+        if self.config.mounts.exclude_removable_from_pressure {
+            let classes = &self.mount_classes;
+            stats_by_mount
+                .retain(|mount, _| classes.get(mount).is_none_or(|class| class.is_permanent()));
+        }
+
+        // Free space on a read-only mount cannot be reclaimed, so it stays
+        // out of the pressure math entirely.
+        let readonly: Vec<(PathBuf, f64)> = stats_by_mount
+            .iter()
+            .filter(|(_, stats)| stats.is_readonly)
+            .map(|(mount, stats)| (mount.clone(), stats.free_pct()))
+            .collect();
+        stats_by_mount.retain(|_, stats| !stats.is_readonly);
+        self.check_readonly_mounts(&readonly);
+        (stats_by_mount, readonly)
+    }
+
+    /// Evaluate the predictive policy for one mount, keeping the tick's
+    /// most severe action.
+    fn track_predictive_action(
+        &mut self,
+        rate_estimate: &RateEstimate,
+        free_pct: f64,
+        mount_path: &Path,
+    ) {
+        // Evaluate predictive policy with full confidence/trend gating.
+        let mut pred_action =
+            self.predictive_policy
+                .evaluate(rate_estimate, free_pct, mount_path.to_path_buf());
+
+        // Force low-confidence predictions to Clear so they don't trigger
+        // scans or other downstream actions (breaks scan saturation feedback loop).
+        if !matches!(pred_action, PredictiveAction::Clear)
+            && rate_estimate.confidence < self.config.pressure.prediction.min_confidence
+        {
+            pred_action = PredictiveAction::Clear;
+        }
+
+        if pred_action.severity() > self.last_predictive_action.severity() {
+            self.last_predictive_action = pred_action;
+        }
+    }
+
+    /// Publish `mount_path`'s fill rate for `sbh status`.
+    fn record_mount_rate(
+        &mut self,
+        mount_path: &Path,
+        rate_estimate: &RateEstimate,
+        predicted_seconds: Option<f64>,
+    ) {
+        if rate_estimate.bytes_per_second.is_finite() {
+            let key = self
+                .rate_keys
+                .entry(mount_path.to_path_buf())
+                .or_insert_with(|| MountKey::resolve(mount_path));
+            self.self_monitor.rates.insert(
+                key.rate_key(),
+                MountRate {
+                    device_id: key.device_id,
+                    bytes_per_sec: rate_estimate.bytes_per_second,
+                    seconds_to_threshold: predicted_seconds,
+                },
+            );
+        }
+    }
+
+    /// Feed the worst mount's guard window to the policy engine: burn-in
+    /// promotion and the emergency escape from fallback.
+    fn advance_policy(
+        &self,
+        diag: &GuardDiagnostics,
+        worst_response: Option<&crate::monitor::pid::PressureResponse>,
+    ) {
+        let mut policy = self.policy_engine.lock();
+        policy.observe_window(diag);
+        let stage = policy.burn_in().map(|burn_in| burn_in.stage);
+        if policy.advance_burn_in(diag, chrono::Utc::now())
+            && let Some(burn_in) = policy.burn_in()
+        {
+            if stage != Some(burn_in.stage) {
+                diag::info(
+                    "daemon",
+                    format_args!("burn-in: policy promoted to {}", burn_in.stage),
+                );
+            }
+            let path = burn_in_path(&self.config.paths.state_file);
+            if let Err(e) = write_burn_in(&path, burn_in) {
+                diag::warn(
+                    "daemon",
+                    format_args!("could not record burn-in at {}: {e}", path.display()),
+                );
+            }
+        }
+
+        // Emergency escalation: break fallback_safe deadlock when pressure
+        // has been at RED/Critical for too long and recovery can't trigger.
+        if let Some(response) = worst_response {
+            let pressure_is_critical = response.level >= PressureLevel::Red;
+            if policy.check_emergency_escalation(pressure_is_critical) {
+                diag::warn(
+                    "daemon",
+                    "emergency escalation: fallback_safe → enforce \
+                     (pressure deadlock broken after sustained RED/Critical)",
+                );
+            }
+        }
+    }
+
+    /// Diff the mount table against the watched set, log mounts that
+    /// appeared or vanished, and drop rate history for the ones gone.
+    fn check_mounts(&mut self) {
+        let now = Instant::now();
+        if !self.mount_watcher.poll_due(now) {
+            return;
+        }
+        let Ok(mounts) = self.platform.mount_points() else {
+            return;
+        };
+        let events = self.mount_watcher.refresh(&mounts, now);
+        if events.is_empty() {
+            return;
+        }
+        self.refresh_mount_classes();
+        self.rate_keys.clear();
+        for event in &events {
+            let mount = event.mount();
+            let mounted = matches!(event, MountEvent::Mounted(_));
+            let class = self
+                .mount_classes
+                .get(&mount.path)
+                .copied()
+                .unwrap_or_else(|| classify_mount(mount, &self.config.mounts));
+            diag::info(
+                "daemon",
+                format_args!(
+                    "mount {}: {} ({} on {}, {})",
+                    if mounted { "added" } else { "removed" },
+                    mount.path.display(),
+                    mount.fs_type,
+                    mount.device,
+                    class.as_str()
+                ),
+            );
+            self.logger_handle.send(ActivityEvent::MountChanged {
+                mount_point: mount.path.to_string_lossy().into_owned(),
+                device: mount.device.clone(),
+                fs_type: mount.fs_type.clone(),
+                mounted,
+            });
+        }
+        let watcher = &self.mount_watcher;
+        self.mount_monitors
+            .retain(|mount, _| watcher.is_watched(mount));
+    }
+
+    /// Fold a `/proc` write-counter sample into the incident window every
+    /// `telemetry.attribution_sample_secs`.
+    fn sample_writers(&mut self) {
+        let interval = Duration::from_secs(self.config.telemetry.attribution_sample_secs);
+        if interval.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .writer_history
+            .last_sample()
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return;
+        }
+        self.writer_history.record(now, &collect_processes());
+    }
+
+    /// Alert once when a watched mount's stats have been unresponsive for
+    /// `telemetry.stale_mount_alert_secs`, and note when it answers again.
+    fn check_stale_mounts(&mut self) {
+        let stale = self.fs_collector.stale_mounts();
+        self.stale_alerted.retain(|mount| {
+            let still_stale = stale.iter().any(|(path, _)| path == mount);
+            if !still_stale {
+                diag::info(
+                    "daemon",
+                    format_args!("mount responsive again: {}", mount.display()),
+                );
+            }
+            still_stale
+        });
+
+        let alert_after = Duration::from_secs(self.config.telemetry.stale_mount_alert_secs);
+        for (mount, unresponsive) in stale {
+            if unresponsive < alert_after
+                || !self.mount_watcher.is_watched(&mount)
+                || self.stale_alerted.contains(&mount)
+            {
+                continue;
+            }
+            let message = format!(
+                "mount {} unresponsive for {}s; its stats are stale and it is left out of pressure checks",
+                mount.display(),
+                unresponsive.as_secs(),
+            );
+            diag::warn("daemon", &message);
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-2011".to_string(),
+                message: message.clone(),
+            });
+            self.notification_manager.notify(&NotificationEvent::Error {
+                code: "SBH-2011".to_string(),
+                message,
+            });
+            self.stale_alerted.insert(mount);
+        }
+    }
+
+    /// Alert once when a watched mount turns read-only, keep the executor
+    /// off it, and note when it becomes writable again.
+    fn check_readonly_mounts(&mut self, readonly: &[(PathBuf, f64)]) {
+        self.readonly_alerted.retain(|mount| {
+            let still_readonly = readonly.iter().any(|(path, _)| path == mount);
+            if !still_readonly {
+                diag::info(
+                    "daemon",
+                    format_args!("mount writable again: {}", mount.display()),
+                );
+            }
+            still_readonly
+        });
+        self.self_monitor.readonly_mounts = readonly
+            .iter()
+            .map(|(mount, free_pct)| (mount.to_string_lossy().into_owned(), *free_pct))
+            .collect();
+        *self.shared_executor_config.readonly_mounts.write() =
+            readonly.iter().map(|(mount, _)| mount.clone()).collect();
+
+        for (mount, _) in readonly {
+            if !self.readonly_alerted.insert(mount.clone()) {
+                continue;
+            }
+            let message = format!(
+                "mount {} is read-only; it is left out of pressure checks and cleanup until it is writable",
+                mount.display(),
+            );
+            diag::warn("daemon", &message);
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-2014".to_string(),
+                message,
+            });
+            self.notification_manager
+                .notify(&NotificationEvent::MountReadOnly {
+                    mount: mount.to_string_lossy().into_owned(),
+                });
+        }
+    }
+
+    /// Re-classify the watched mounts and publish the ones the executor must
+    /// leave alone.
+    fn refresh_mount_classes(&mut self) {
+        let config = &self.config.mounts;
+        self.mount_classes = self
+            .mount_watcher
+            .mounts()
+            .map(|mount| (mount.path.clone(), classify_mount(mount, config)))
+            .collect();
+        let no_delete = if config.auto_delete_removable {
+            Vec::new()
+        } else {
+            self.mount_classes
+                .iter()
+                .filter(|(_, class)| !class.is_permanent())
+                .map(|(path, _)| path.clone())
+                .collect()
+        };
+        *self.shared_executor_config.no_delete_mounts.write() = no_delete;
+    }
+
+    fn log_pressure_change(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        // Use the causing mount so the log entry reflects the mount that
+        // actually drove the pressure level change, not the primary path.
+        let (free_pct, mount, total, free) =
+            if let Ok(stats) = self.fs_collector.collect(&response.causing_mount) {
+                #[allow(clippy::cast_possible_wrap)]
+                (
+                    stats.free_pct(),
                     stats.mount_point.to_string_lossy().to_string(),
                     stats.total_bytes as i64,
                     stats.available_bytes as i64,
@@ -1228,6 +2287,22 @@ impl MonitoringDaemon {
                 (0.0, "/".to_string(), 0, 0)
             };
 
+        diag::event(
+            DiagLevel::Info,
+            "daemon",
+            "pressure_transition",
+            serde_json::json!({
+                "mount": mount,
+                "from": format!("{:?}", self.last_pressure_level),
+                "to": format!("{:?}", response.level),
+                "free_pct": free_pct,
+                "urgency": response.urgency,
+            }),
+            format_args!(
+                "pressure {:?} -> {:?} on {mount} ({free_pct:.1}% free, urgency {:.2})",
+                self.last_pressure_level, response.level, response.urgency
+            ),
+        );
         self.logger_handle.send(ActivityEvent::PressureChanged {
             from: format!("{:?}", self.last_pressure_level),
             to: format!("{:?}", response.level),
@@ -1249,6 +2324,350 @@ impl MonitoringDaemon {
             });
     }
 
+    /// Emit the DBus `PressureChanged` signal. Unlike notifications this is
+    /// not cooled down: desktop tooling wants every transition.
+    fn announce_dbus_transition(&self, response: &crate::monitor::pid::PressureResponse) {
+        let Some(dbus) = &self.dbus else {
+            return;
+        };
+        let free_pct = self
+            .fs_collector
+            .collect(&response.causing_mount)
+            .map_or(0.0, |s| s.free_pct());
+        dbus.pressure_changed(
+            &format!("{:?}", self.last_pressure_level),
+            &format!("{:?}", response.level),
+            free_pct,
+        );
+    }
+
+    /// Release ballast on the pressured mount for each `ReleaseBallast` call.
+    fn handle_dbus_releases(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        let Some(requests) = self.dbus.as_ref().map(DbusService::take_release_requests) else {
+            return;
+        };
+        let mount = &response.causing_mount;
+        for count in requests {
+            diag::info(
+                "daemon",
+                format_args!("DBus ReleaseBallast({count}) on {}", mount.display()),
+            );
+            let count = usize::try_from(count).unwrap_or(usize::MAX);
+            match self.release_requested_ballast(mount, count) {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    diag::warn(
+                        "daemon",
+                        format_args!("no ballast pool on {}", mount.display()),
+                    );
+                }
+                Err(e) => {
+                    self.logger_handle.send(ActivityEvent::Error {
+                        code: e.code().to_string(),
+                        message: format!("DBus ballast release failed: {e}"),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Pick up `sbh pause` / `sbh resume` and resume on our own once the
+    /// window runs out, removing the expired marker.
+    fn check_config_probation(&mut self) {
+        let path = probation_path(&self.config.paths.state_file);
+        let probation = match read_probation(&path) {
+            Ok(probation) => {
+                self.unreadable_markers.remove(&path);
+                probation
+            }
+            Err(e) => {
+                if self.unreadable_markers.insert(path.clone()) {
+                    diag::warn(
+                        "daemon",
+                        format_args!(
+                            "ignoring unreadable config probation marker {}: {e}",
+                            path.display()
+                        ),
+                    );
+                }
+                None
+            }
+        };
+        let Some(probation) = probation else {
+            self.probation_fallback_baseline = None;
+            return;
+        };
+
+        if !probation.is_active(chrono::Utc::now()) {
+            if clear_probation(&path).unwrap_or(false) {
+                let message = format!(
+                    "config probation passed: keeping `sbh {}` changes ({})",
+                    probation.source,
+                    probation.keys.join(", ")
+                );
+                diag::info("daemon", &message);
+                self.logger_handle
+                    .send(ActivityEvent::Annotation { message });
+            }
+            self.probation_fallback_baseline = None;
+            return;
+        }
+        // Only judge the change once this daemon is actually running it.
+        if probation
+            .started()
+            .is_none_or(|started| self.config_loaded_at < started)
+        {
+            return;
+        }
+
+        let (fallback_entries, reason) = {
+            let policy = self.policy_engine.lock();
+            (
+                policy.total_fallback_entries(),
+                policy.fallback_reason().cloned(),
+            )
+        };
+        let baseline = match &self.probation_fallback_baseline {
+            Some((started_at, baseline)) if *started_at == probation.started_at => *baseline,
+            _ => {
+                self.probation_fallback_baseline =
+                    Some((probation.started_at.clone(), fallback_entries));
+                return;
+            }
+        };
+        // A kill-switch fallback is the operator's doing, not the change's.
+        if fallback_entries > baseline && reason != Some(FallbackReason::KillSwitch) {
+            let reason = reason.map_or_else(|| "policy fallback".to_string(), |r| r.to_string());
+            self.revert_config_probation(&path, &probation, &reason);
+        }
+    }
+
+    fn revert_config_probation(&mut self, path: &Path, probation: &ConfigProbation, reason: &str) {
+        let config_file = self.config.paths.config_file.clone();
+        if let Err(e) = restore_previous_config(&config_file, probation) {
+            diag::error(
+                "daemon",
+                format_args!(
+                    "config probation failed ({reason}) but restoring {} failed: {e}",
+                    config_file.display()
+                ),
+            );
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-1003".to_string(),
+                message: format!("config probation revert failed: {e}"),
+            });
+            return;
+        }
+        let _ = clear_probation(path);
+        self.probation_fallback_baseline = None;
+
+        let keys = probation.keys.join(", ");
+        let details = format!(
+            "config probation failed ({reason}): restored config from before `sbh {}` ({keys})",
+            probation.source
+        );
+        diag::warn("daemon", &details);
+        self.logger_handle
+            .send(ActivityEvent::ConfigReloaded { details });
+        self.notification_manager
+            .notify(&NotificationEvent::ConfigReverted {
+                source: probation.source.clone(),
+                keys,
+                reason: reason.to_string(),
+            });
+        self.signal_handler.request_reload();
+    }
+
+    fn check_maintenance(&mut self) {
+        let path = maintenance_path(&self.config.paths.state_file);
+        let now = chrono::Utc::now();
+        let marker = match read_maintenance(&path) {
+            Ok(marker) => {
+                self.unreadable_markers.remove(&path);
+                marker
+            }
+            Err(e) => {
+                if self.unreadable_markers.insert(path.clone()) {
+                    diag::warn(
+                        "daemon",
+                        format_args!(
+                            "ignoring unreadable maintenance marker {}: {e}",
+                            path.display()
+                        ),
+                    );
+                }
+                None
+            }
+        };
+        let active = marker.filter(|window| window.is_active(now));
+        if active.is_none() && path.exists() && !self.unreadable_markers.contains(&path) {
+            let _ = clear_maintenance(&path);
+        }
+        if active == self.maintenance {
+            return;
+        }
+
+        let message = active.as_ref().map_or_else(
+            || "maintenance ended: enforcement resumed".to_string(),
+            |window| {
+                format!(
+                    "maintenance: enforcement paused until {}{}{}",
+                    window.until,
+                    if window.mute_alerts {
+                        ", alerts muted"
+                    } else {
+                        ""
+                    },
+                    window
+                        .reason
+                        .as_deref()
+                        .map_or_else(String::new, |reason| format!(" ({reason})"))
+                )
+            },
+        );
+        diag::info("daemon", &message);
+        self.logger_handle
+            .send(ActivityEvent::Annotation { message });
+
+        self.shared_executor_config
+            .paused
+            .store(active.is_some(), Ordering::Relaxed);
+        self.notification_manager
+            .set_silenced(active.as_ref().is_some_and(|window| window.mute_alerts));
+        self.self_monitor.maintenance.clone_from(&active);
+        self.maintenance = active;
+    }
+
+    /// Write `sbh annotate` notes received over the control socket to the
+    /// activity timeline.
+    fn log_control_annotations(&self) {
+        let Some(control) = self.control.as_ref() else {
+            return;
+        };
+        for message in control.take_annotations() {
+            self.logger_handle
+                .send(ActivityEvent::Annotation { message });
+        }
+    }
+
+    /// Answer `sbh ballast release` requests from the control socket.
+    fn handle_control_releases(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        let Some(requests) = self
+            .control
+            .as_ref()
+            .map(ControlServer::take_release_requests)
+        else {
+            return;
+        };
+        let mount = &response.causing_mount;
+        for request in requests {
+            diag::info(
+                "daemon",
+                format_args!(
+                    "control release of {} ballast files on {}",
+                    request.count,
+                    mount.display()
+                ),
+            );
+            let reply = match self.release_requested_ballast(mount, request.count) {
+                Ok(Some(report)) => ControlReply::BallastReleased {
+                    mount: mount.to_string_lossy().into_owned(),
+                    files_released: report.files_released,
+                    bytes_freed: report.bytes_freed,
+                    remaining: self
+                        .ballast_coordinator
+                        .pool_for_mount(mount)
+                        .map_or(0, BallastPool::available_count),
+                    errors: report.errors,
+                },
+                Ok(None) => ControlReply::Error {
+                    message: format!(
+                        "no ballast files available to release on {}",
+                        mount.display()
+                    ),
+                },
+                Err(e) => {
+                    self.logger_handle.send(ActivityEvent::Error {
+                        code: e.code().to_string(),
+                        message: format!("control ballast release failed: {e}"),
+                    });
+                    ControlReply::Error {
+                        message: e.to_string(),
+                    }
+                }
+            };
+            request.respond(reply);
+        }
+    }
+
+    /// Release ballast on request from outside the pressure loop, keeping the
+    /// release controller and notifications in step. `None` when the mount
+    /// has no pool or no files left.
+    fn release_requested_ballast(
+        &mut self,
+        mount: &Path,
+        count: usize,
+    ) -> Result<Option<ReleaseReport>> {
+        let Some(report) = self.ballast_coordinator.release_for_mount(mount, count)? else {
+            return Ok(None);
+        };
+        self.record_ballast_release(mount, &report, "request");
+        Ok(Some(report))
+    }
+
+    /// Account for released ballast and announce it. `trigger` is `pressure`
+    /// or `request`.
+    fn record_ballast_release(&mut self, mount: &Path, report: &ReleaseReport, trigger: &str) {
+        self.release_controller
+            .on_released(mount, report.files_released);
+        diag::event(
+            DiagLevel::Info,
+            "daemon",
+            "ballast_released",
+            serde_json::json!({
+                "mount": mount.to_string_lossy(),
+                "files_released": report.files_released,
+                "bytes_freed": report.bytes_freed,
+                "trigger": trigger,
+            }),
+            format_args!(
+                "released {} ballast files ({} bytes) on {} ({trigger})",
+                report.files_released,
+                report.bytes_freed,
+                mount.display()
+            ),
+        );
+        self.notification_manager
+            .notify(&NotificationEvent::BallastReleased {
+                mount: mount.to_string_lossy().to_string(),
+                files_released: report.files_released,
+                bytes_freed: report.bytes_freed,
+            });
+    }
+
+    /// Persist who was writing in the run-up to a pressure escalation, for
+    /// `sbh blame --incident`.
+    fn log_attribution_snapshot(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        if self.config.telemetry.attribution_sample_secs == 0 {
+            return;
+        }
+        self.writer_history
+            .record(Instant::now(), &collect_processes());
+        let Ok(details) = serde_json::to_string(&self.writer_history.snapshot()) else {
+            return;
+        };
+        let free_pct = self
+            .fs_collector
+            .collect(&response.causing_mount)
+            .map_or(0.0, |s| s.free_pct());
+        self.logger_handle.send(ActivityEvent::AttributionSnapshot {
+            mount_point: response.causing_mount.to_string_lossy().into_owned(),
+            pressure: format!("{:?}", response.level),
+            free_pct,
+            details,
+        });
+    }
+
     // ──────────────────── pressure response ────────────────────
 
     #[allow(clippy::too_many_lines)]
@@ -1292,6 +2711,32 @@ impl MonitoringDaemon {
             scan_paths
         };
 
+        // A cleanup pass ran on this mount recently. Short of Critical, lean
+        // on ballast one level harder instead of starting another, so
+        // oscillating pressure does not hammer the same workspace.
+        let cooldown = self.cleanup_cooldown(&response.causing_mount);
+        if let Some(remaining) = cooldown
+            && response.level > PressureLevel::Green
+            && response.level < PressureLevel::Critical
+        {
+            let escalated = crate::monitor::pid::PressureResponse {
+                level: escalated_level(response.level),
+                ..response.clone()
+            };
+            let _ = self.release_ballast(&response.causing_mount, &escalated);
+            self.log_cleanup_deferred(&response.causing_mount, remaining, escalated.level);
+            return;
+        }
+
+        // With a deep-scan schedule, scans short of Red stay shallow and stick
+        // to the hot paths; the scheduled window covers everything else.
+        let mode = routine_scan_mode(self.deep_scan_schedule.is_some(), response.level);
+        let paths_to_scan = if mode == ScanMode::Shallow {
+            self.hot_scan_paths(response).unwrap_or(paths_to_scan)
+        } else {
+            paths_to_scan
+        };
+
         match response.level {
             PressureLevel::Green => {
                 // Maybe replenish ballast.
@@ -1309,6 +2754,29 @@ impl MonitoringDaemon {
                         pool_info.files_available,
                         pool_info.files_total,
                     ) {
+                        // Size the refill so it cannot push the mount back
+                        // into yellow within the forecast horizon.
+                        let forecast = self.replenish_forecast(&mount_path);
+                        let plan = self.release_controller.plan_replenish(
+                            &mount_path,
+                            forecast.as_ref(),
+                            pool_info
+                                .files_total
+                                .saturating_sub(pool_info.files_available),
+                            self.config
+                                .ballast
+                                .effective_file_size_bytes(&mount_path.to_string_lossy()),
+                            self.config.ballast.replenish_incremental,
+                        );
+                        if plan.files == 0 {
+                            if plan.report_deferral
+                                && let Some(forecast) = &forecast
+                            {
+                                self.log_replenish_deferred(&mount_path, forecast, &plan);
+                            }
+                            continue;
+                        }
+
                         let free_check = || {
                             collector
                                 .collect(&mount_path)
@@ -1317,20 +2785,27 @@ impl MonitoringDaemon {
                         };
 
                         // Try to replenish this pool.
-                        if let Ok(Some(report)) = self
-                            .ballast_coordinator
-                            .replenish_for_mount(&mount_path, Some(&free_check))
-                            && report.files_created > 0
-                        {
-                            self.release_controller
-                                .on_replenished(&mount_path, report.files_created);
+                        let mut created = 0;
+                        for _ in 0..plan.files {
+                            match self
+                                .ballast_coordinator
+                                .replenish_for_mount(&mount_path, Some(&free_check))
+                            {
+                                Ok(Some(report)) if report.files_created > 0 => {
+                                    created += report.files_created;
+                                }
+                                _ => break,
+                            }
+                        }
+                        if created > 0 {
+                            self.release_controller.on_replenished(&mount_path, created);
                             self.notification_manager.notify(
                                 &NotificationEvent::BallastReplenished {
                                     mount: mount_path.to_string_lossy().to_string(),
-                                    files_replenished: report.files_created,
+                                    files_replenished: created,
                                 },
                             );
-                            // One file replenished globally per tick is sufficient.
+                            // One pool replenished globally per tick is sufficient.
                             break;
                         }
                     }
@@ -1358,8 +2833,8 @@ impl MonitoringDaemon {
                 if let Some(ref mount) = predictive_ballast_mount {
                     let _ = self.release_ballast(mount, response);
                 }
-                if needs_scan {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
+                if needs_scan && cooldown.is_none() {
+                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan, mode);
                 }
             }
             PressureLevel::Yellow => {
@@ -1368,22 +2843,22 @@ impl MonitoringDaemon {
                 if response.release_ballast_files > 0 {
                     let _ = self.release_ballast(&response.causing_mount, response);
                 }
-                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
+                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan, mode);
             }
             PressureLevel::Orange => {
                 // Start scanning + gentle cleanup + early ballast release.
                 let _ = self.release_ballast(&response.causing_mount, response);
-                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
+                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan, mode);
             }
             PressureLevel::Red => {
                 // Release ballast + aggressive scan + delete.
                 let _ = self.release_ballast(&response.causing_mount, response);
-                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
+                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan, mode);
             }
             PressureLevel::Critical => {
                 // Emergency: release all ballast + delete everything safe.
                 let _ = self.release_ballast(&response.causing_mount, response);
-                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
+                self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan, mode);
 
                 let primary = self.primary_path();
                 let actual_free_pct = self
@@ -1401,6 +2876,119 @@ impl MonitoringDaemon {
         }
     }
 
+    /// Time left before automated cleanup may run on `mount` again.
+    fn cleanup_cooldown(&self, mount: &Path) -> Option<Duration> {
+        let last_pass = *self.last_cleanup_pass.get(mount)?;
+        cleanup_cooldown_remaining(
+            last_pass,
+            Duration::from_secs(self.config.scanner.mount_cooldown_secs),
+            Instant::now(),
+        )
+    }
+
+    /// Mounts still cooling down, as published in `state.json`.
+    fn cleanup_cooldowns(&mut self) -> BTreeMap<String, CleanupCooldown> {
+        let cooldown = Duration::from_secs(self.config.scanner.mount_cooldown_secs);
+        let now = Instant::now();
+        let wall_now = chrono::Utc::now();
+        self.last_cleanup_pass
+            .retain(|_, last_pass| cleanup_cooldown_remaining(*last_pass, cooldown, now).is_some());
+        self.last_cleanup_pass
+            .iter()
+            .filter_map(|(mount, last_pass)| {
+                let remaining = cleanup_cooldown_remaining(*last_pass, cooldown, now)?;
+                let eligible_at = wall_now + chrono::Duration::from_std(remaining).ok()?;
+                Some((
+                    mount.to_string_lossy().into_owned(),
+                    CleanupCooldown {
+                        eligible_at: eligible_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                        remaining_secs: remaining.as_secs(),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Note a cleanup held off by the mount cooldown, at most once a minute.
+    fn log_cleanup_deferred(
+        &mut self,
+        mount: &Path,
+        remaining: Duration,
+        ballast_level: PressureLevel,
+    ) {
+        let now = Instant::now();
+        if self
+            .last_cleanup_deferral_log
+            .is_some_and(|last| now.duration_since(last) < Duration::from_mins(1))
+        {
+            return;
+        }
+        self.last_cleanup_deferral_log = Some(now);
+        diag::info(
+            "daemon",
+            format_args!(
+                "cleanup on {} cooling down for {}s; releasing ballast as {ballast_level:?}",
+                mount.display(),
+                remaining.as_secs()
+            ),
+        );
+    }
+
+    /// Fill-rate forecast for replenishing ballast on `mount_path`, or `None`
+    /// when forecasting is disabled or the mount has no rate estimate yet.
+    fn replenish_forecast(&self, mount_path: &Path) -> Option<ReplenishForecast> {
+        let minutes = self.config.ballast.replenish_forecast_minutes;
+        if minutes == 0 {
+            return None;
+        }
+        let stats = self.fs_collector.collect(mount_path).ok()?;
+        let key = self.rate_keys.get(mount_path)?;
+        let rate = self.self_monitor.rates.get(&key.rate_key())?;
+        Some(ReplenishForecast {
+            free_bytes: stats.available_bytes,
+            total_bytes: stats.total_bytes,
+            fill_rate_bps: rate.bytes_per_sec,
+            horizon: Duration::from_secs(minutes * 60),
+            floor_pct: self
+                .config
+                .pressure
+                .for_mount_now(mount_path)
+                .yellow_min_free_pct,
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn log_replenish_deferred(
+        &self,
+        mount_path: &Path,
+        forecast: &ReplenishForecast,
+        plan: &ReplenishPlan,
+    ) {
+        let free_pct = if forecast.total_bytes == 0 {
+            0.0
+        } else {
+            forecast.free_bytes as f64 / forecast.total_bytes as f64 * 100.0
+        };
+        diag::info(
+            "daemon",
+            format_args!(
+                "ballast replenish deferred on {}: projected {:.1}% free in {} min, floor {:.1}%",
+                mount_path.display(),
+                plan.projected_free_pct,
+                self.config.ballast.replenish_forecast_minutes,
+                forecast.floor_pct
+            ),
+        );
+        self.logger_handle
+            .send(ActivityEvent::BallastReplenishDeferred {
+                mount_point: mount_path.to_string_lossy().into_owned(),
+                free_pct,
+                projected_free_pct: plan.projected_free_pct,
+                floor_pct: forecast.floor_pct,
+                fill_rate_bps: forecast.fill_rate_bps,
+            });
+    }
+
     /// Helper to release ballast from the causing mount using the global controller logic.
     fn release_ballast(
         &mut self,
@@ -1419,15 +3007,7 @@ impl MonitoringDaemon {
         if count > 0
             && let Some(report) = self.ballast_coordinator.release_for_mount(mount, count)?
         {
-            self.release_controller
-                .on_released(mount, report.files_released);
-
-            self.notification_manager
-                .notify(&NotificationEvent::BallastReleased {
-                    mount: mount.to_string_lossy().to_string(),
-                    files_released: report.files_released,
-                    bytes_freed: report.bytes_freed,
-                });
+            self.record_ballast_release(mount, &report, "pressure");
         }
         Ok(())
     }
@@ -1438,12 +3018,14 @@ impl MonitoringDaemon {
         scan_rx: &Receiver<ScanRequest>,
         response: &crate::monitor::pid::PressureResponse,
         paths: Vec<PathBuf>,
+        mode: ScanMode,
     ) {
         let request = ScanRequest {
             paths,
             urgency: response.urgency,
             pressure_level: response.level,
             max_delete_batch: response.max_delete_batch,
+            mode,
             config_update: None,
         };
 
@@ -1459,34 +3041,189 @@ impl MonitoringDaemon {
                     .is_none_or(|last| now.duration_since(last) >= Duration::from_secs(60));
                 if should_log {
                     self.last_scan_channel_warn = Some(now);
-                    eprintln!(
-                        "[SBH-DAEMON] scan channel saturated (request replaced or deferred)"
+                    diag::warn(
+                        "daemon",
+                        "scan channel saturated (request replaced or deferred)",
                     );
                 }
             }
             ScanEnqueueStatus::Disconnected => {
-                eprintln!("[SBH-DAEMON] scan channel disconnected, dropping scan request");
+                diag::warn("daemon", "scan channel disconnected, dropping scan request");
             }
         }
     }
 
+    /// `scanner.hot_paths` on the pressured mount (all of them at Green), or
+    /// `None` when none apply and the shallow pass covers the usual roots.
+    fn hot_scan_paths(
+        &self,
+        response: &crate::monitor::pid::PressureResponse,
+    ) -> Option<Vec<PathBuf>> {
+        let hot_paths: Vec<PathBuf> = self
+            .config
+            .scanner
+            .hot_paths
+            .iter()
+            .filter(|path| {
+                response.level == PressureLevel::Green
+                    || self
+                        .fs_collector
+                        .collect(path)
+                        .is_ok_and(|stats| stats.mount_point == response.causing_mount)
+            })
+            .cloned()
+            .collect();
+        (!hot_paths.is_empty()).then_some(hot_paths)
+    }
+
+    /// Queue a deep scan of every root once the scheduled window opens.
+    fn check_deep_scan(
+        &mut self,
+        response: &crate::monitor::pid::PressureResponse,
+        scan_tx: &Sender<ScanRequest>,
+        scan_rx: &Receiver<ScanRequest>,
+    ) {
+        let (Some(schedule), Some(due)) = (&self.deep_scan_schedule, self.next_deep_scan) else {
+            return;
+        };
+        let now = Local::now().naive_local();
+        if now < due {
+            return;
+        }
+        let request = ScanRequest {
+            paths: self.config.scanner.root_paths.clone(),
+            urgency: response.urgency,
+            pressure_level: response.level,
+            max_delete_batch: response.max_delete_batch,
+            mode: ScanMode::Deep,
+            config_update: None,
+        };
+        match enqueue_scan_request(scan_tx, scan_rx, request, true) {
+            ScanEnqueueStatus::Queued | ScanEnqueueStatus::ReplacedStale => {
+                self.next_deep_scan = schedule.next_after(now);
+                self.last_deep_scan = Some(chrono::Utc::now());
+                diag::info(
+                    "daemon",
+                    format_args!(
+                        "deep scan started over {} roots (schedule {schedule})",
+                        self.config.scanner.root_paths.len()
+                    ),
+                );
+            }
+            // Try again next tick; the window stays open until it runs.
+            ScanEnqueueStatus::DeferredFull => {}
+            ScanEnqueueStatus::Disconnected => {
+                diag::warn("daemon", "scan channel disconnected, dropping deep scan");
+            }
+        }
+    }
+
+    /// Deep-scan timing, as published in `state.json`.
+    fn deep_scan_state(&self) -> Option<DeepScanState> {
+        let schedule = self.deep_scan_schedule.as_ref()?;
+        let rfc3339 = |at: chrono::DateTime<chrono::Utc>| {
+            at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        };
+        Some(DeepScanState {
+            schedule: schedule.to_string(),
+            last_at: self.last_deep_scan.map(rfc3339),
+            next_at: self
+                .next_deep_scan
+                .and_then(|next| next.and_local_timezone(Local).earliest())
+                .map(|next| rfc3339(next.with_timezone(&chrono::Utc))),
+        })
+    }
+
     fn trigger_forced_scan(
         &self,
         scan_tx: &Sender<ScanRequest>,
         response: &crate::monitor::pid::PressureResponse,
     ) {
-        eprintln!("[SBH-DAEMON] forced scan triggered (SIGUSR1)");
+        diag::info("daemon", "forced scan triggered (SIGUSR1)");
         let request = ScanRequest {
             paths: self.config.scanner.root_paths.clone(),
             urgency: response.urgency.max(0.5), // at least moderate urgency for forced scans
             pressure_level: response.level,
             max_delete_batch: response.max_delete_batch,
+            mode: ScanMode::Full,
             config_update: None,
         };
         // For forced scans, block briefly to ensure delivery.
         let _ = scan_tx.send_timeout(request, Duration::from_millis(100));
     }
 
+    /// Log everything an operator would want from a wedged or puzzling
+    /// daemon: the state `state.json` holds (rewritten first, so it is
+    /// current), the live pressure response, policy and guard diagnostics,
+    /// every ballast pool, worker heartbeats, and the per-thread snapshot.
+    fn dump_state(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        self.self_monitor.force_next_write();
+        self.publish_state(response);
+
+        let stall_after = stall_threshold(&self.config);
+        let threads: Vec<_> = [&self.scanner_heartbeat, &self.executor_heartbeat]
+            .into_iter()
+            .map(|heartbeat| {
+                let status = heartbeat.status(stall_after);
+                serde_json::json!({
+                    "name": status.name(),
+                    "status": match status {
+                        ThreadStatus::Running { .. } => "running",
+                        ThreadStatus::Stalled { .. } => "stalled",
+                        ThreadStatus::Dead { .. } => "dead",
+                    },
+                    "since_heartbeat_secs": heartbeat.since_last_beat().as_secs(),
+                })
+            })
+            .collect();
+        let pools: Vec<_> = self
+            .ballast_coordinator
+            .inventory()
+            .into_iter()
+            .map(|pool| {
+                serde_json::json!({
+                    "mount": pool.mount_point,
+                    "dir": pool.ballast_dir,
+                    "fs_type": pool.fs_type,
+                    "strategy": format!("{:?}", pool.strategy),
+                    "available": pool.files_available,
+                    "total": pool.files_total,
+                    "releasable_bytes": pool.releasable_bytes,
+                    "skip_reason": pool.skip_reason,
+                })
+            })
+            .collect();
+        let dump = serde_json::json!({
+            "state": self.self_monitor.last_state(),
+            "pressure": {
+                "level": format!("{:?}", response.level).to_lowercase(),
+                "urgency": response.urgency,
+                "causing_mount": response.causing_mount,
+                "predicted_seconds": response.predicted_seconds,
+                "scan_interval_ms": response.scan_interval.as_millis(),
+                "max_delete_batch": response.max_delete_batch,
+                "release_ballast_files": response.release_ballast_files,
+                "fallback_active": response.fallback_active,
+            },
+            "policy": self.policy_engine.lock().diagnostics(),
+            "guard": *self.shared_guard_diagnostics.read(),
+            "ballast_pools": pools,
+            "threads": threads,
+            "config_file": self.config.paths.config_file,
+            "config_hash": self.config.stable_hash().unwrap_or_default(),
+        });
+        diag::event(
+            DiagLevel::Info,
+            "daemon",
+            "state_dump",
+            dump.clone(),
+            format_args!("state dump (SIGUSR2): {dump}"),
+        );
+        for line in thread_snapshot() {
+            diag::info("daemon", format_args!("  {line}"));
+        }
+    }
+
     fn check_predictive_warning(&mut self, response: &crate::monitor::pid::PressureResponse) {
         let Some(seconds) = response.predicted_seconds else {
             // Prediction cleared — do NOT reset cooldown state here.
@@ -1601,8 +3338,304 @@ impl MonitoringDaemon {
         });
     }
 
+    /// Hourly ballast integrity pass. Corrupted files are counted toward the
+    /// disk-health totals in state.json and, at green pressure, re-created
+    /// while the mount stays above the manager's free-space floor.
+    fn check_ballast_integrity(&mut self, level: PressureLevel) {
+        let now = Instant::now();
+        if self
+            .last_ballast_verify
+            .is_some_and(|last| now.duration_since(last) < BALLAST_VERIFY_INTERVAL)
+        {
+            return;
+        }
+        self.last_ballast_verify = Some(now);
+
+        for (mount_path, report) in self.ballast_coordinator.verify_all() {
+            let present = report.files_checked.saturating_sub(report.files_missing);
+            self.self_monitor.ballast_files_verified += present as u64;
+            if report.files_corrupted == 0 {
+                continue;
+            }
+            self.self_monitor.ballast_files_corrupted += report.files_corrupted as u64;
+
+            let mut repaired = 0;
+            if level == PressureLevel::Green {
+                let collector = &self.fs_collector;
+                let free_check = || collector.collect(&mount_path).map_or(0.0, |s| s.free_pct());
+                match self
+                    .ballast_coordinator
+                    .repair_for_mount(&mount_path, Some(&free_check))
+                {
+                    Ok(Some(repair)) => repaired = repair.files_created,
+                    Ok(None) => {}
+                    Err(e) => diag::warn(
+                        "daemon",
+                        format_args!("ballast repair failed on {}: {e}", mount_path.display()),
+                    ),
+                }
+            }
+            self.self_monitor.ballast_files_repaired += repaired as u64;
+
+            let mount = mount_path.to_string_lossy().into_owned();
+            let corrupted: Vec<&str> = report
+                .details
+                .iter()
+                .map(String::as_str)
+                .filter(|detail| !detail.ends_with(": missing"))
+                .collect();
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-2012".to_string(),
+                message: format!(
+                    "ballast integrity check on {mount}: {} corrupted, {repaired} re-created ({})",
+                    report.files_corrupted,
+                    corrupted.join("; "),
+                ),
+            });
+            self.notification_manager
+                .notify(&NotificationEvent::BallastCorrupted {
+                    mount,
+                    files_corrupted: report.files_corrupted,
+                    files_repaired: repaired,
+                });
+        }
+    }
+
+    /// Probe the disks behind watched mounts every
+    /// `telemetry.disk_health_interval_secs` and alert when one is degraded or
+    /// failing. A bad disk whose mounts are also below the green threshold
+    /// alerts again, one level louder.
+    fn check_disk_health(&mut self) {
+        let interval = self.config.telemetry.disk_health_interval_secs;
+        if interval == 0 {
+            return;
+        }
+        let now = Instant::now();
+        if self
+            .last_disk_health_probe
+            .is_none_or(|last| now.duration_since(last) >= Duration::from_secs(interval))
+        {
+            self.last_disk_health_probe = Some(now);
+            let mut disks: HashMap<String, Vec<PathBuf>> = HashMap::new();
+            for mount in self.mount_watcher.mounts() {
+                if let Some(disk) = disk_health::backing_disk(&mount.device) {
+                    disks.entry(disk).or_default().push(mount.path.clone());
+                }
+            }
+            self.disk_health = disks
+                .into_iter()
+                .map(|(disk, mounts)| (disk_health::probe(&disk), mounts))
+                .collect();
+        }
+
+        for (health, mounts) in &self.disk_health {
+            if !health.verdict.is_alarming() {
+                if self.disk_health_alerted.remove(&health.disk).is_some() {
+                    diag::info("daemon", format_args!("disk {} healthy again", health.disk));
+                }
+                continue;
+            }
+            let filling = mounts.iter().any(|mount| {
+                let green_min = self.config.pressure.for_mount_now(mount).green_min_free_pct;
+                self.fs_collector
+                    .collect(mount)
+                    .is_ok_and(|stats| stats.free_pct() < green_min)
+            });
+            let state = (health.verdict, filling);
+            if self
+                .disk_health_alerted
+                .get(&health.disk)
+                .is_some_and(|alerted| *alerted >= state)
+            {
+                continue;
+            }
+            self.disk_health_alerted.insert(health.disk.clone(), state);
+
+            let mount = mounts
+                .iter()
+                .map(|path| path.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let filling_note = if filling { "; mount is filling" } else { "" };
+            let message = format!(
+                "disk {} behind {mount} is {}: {}{filling_note}",
+                health.disk,
+                health.verdict.as_str(),
+                health.detail,
+            );
+            diag::warn("daemon", &message);
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-2013".to_string(),
+                message,
+            });
+            self.notification_manager
+                .notify(&NotificationEvent::DiskHealth {
+                    mount,
+                    disk: health.disk.clone(),
+                    verdict: health.verdict.as_str().to_string(),
+                    detail: health.detail.clone(),
+                    filling,
+                });
+        }
+    }
+
+    /// Purge quarantined artifacts (`sbh clean --quarantine`) on watched
+    /// mounts: expired entries always, and every entry in a quarantine whose
+    /// mount is below the orange threshold.
+    fn check_quarantine(&mut self, level: PressureLevel) {
+        let now = Instant::now();
+        if level < PressureLevel::Orange
+            && self
+                .last_quarantine_check
+                .is_some_and(|last| now.duration_since(last) < QUARANTINE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_quarantine_check = Some(now);
+        // Purging is permanent: observe/fallback policy modes (and burn-in,
+        // which holds the engine in them) never mutate the filesystem.
+        if self.config.scanner.dry_run || !self.policy_engine.lock().mode().allows_deletion() {
+            return;
+        }
+
+        let dirs: Vec<PathBuf> = self
+            .mount_watcher
+            .mounts()
+            .map(|mount| mount.path.clone())
+            .chain(self.config.scanner.root_paths.iter().cloned())
+            .collect();
+        let wall_now = chrono::Utc::now();
+        for root in quarantine::discover_roots(dirs.iter().map(PathBuf::as_path)) {
+            let under_pressure = self.fs_collector.collect(&root).is_ok_and(|stats| {
+                stats.free_pct()
+                    < self
+                        .config
+                        .pressure
+                        .for_mount_now(&stats.mount_point)
+                        .orange_min_free_pct
+            });
+            let reason = if under_pressure {
+                "pressure"
+            } else {
+                "expired"
+            };
+            // Offloaded entries hold no local space, so pressure spares them.
+            let report = match quarantine::purge(&root, |entry| {
+                (under_pressure && entry.object.is_none()) || entry.is_expired(wall_now)
+            }) {
+                Ok(report) => report,
+                Err(e) => {
+                    diag::warn(
+                        "daemon",
+                        format_args!("quarantine purge failed on {}: {e}", root.display()),
+                    );
+                    continue;
+                }
+            };
+            for entry in &report.purged {
+                self.logger_handle.send(ActivityEvent::QuarantinePurged {
+                    path: entry.original_path.to_string_lossy().to_string(),
+                    quarantined_path: entry.stored_at(),
+                    size_bytes: entry.local_bytes(),
+                    reason: reason.to_string(),
+                });
+            }
+            for (entry, error) in &report.failed {
+                self.logger_handle
+                    .send(ActivityEvent::ArtifactDeletionFailed {
+                        path: entry.quarantined_path.to_string_lossy().to_string(),
+                        error_code: "SBH-2003".to_string(),
+                        error_message: format!("quarantine purge failed: {error}"),
+                    });
+            }
+            if !report.purged.is_empty() {
+                diag::info(
+                    "daemon",
+                    format_args!(
+                        "purged {} quarantined items ({} bytes, {reason}) from {}",
+                        report.purged.len(),
+                        report.bytes_freed(),
+                        root.display()
+                    ),
+                );
+            }
+        }
+    }
+
     // ──────────────────── special locations ────────────────────
 
+    fn check_core_dumps(&mut self, level: PressureLevel) {
+        if !self.coredump_policy.sweep_due(level, Instant::now()) {
+            return;
+        }
+        // Observe/fallback policy modes never mutate the filesystem.
+        let dry_run =
+            self.config.scanner.dry_run || !self.policy_engine.lock().mode().allows_deletion();
+        let outcome = self.coredump_policy.sweep(level, dry_run);
+
+        for (path, error) in &outcome.failed {
+            self.logger_handle
+                .send(ActivityEvent::ArtifactDeletionFailed {
+                    path: path.to_string_lossy().to_string(),
+                    error_code: "SBH-2003".to_string(),
+                    error_message: format!("core dump removal failed: {error}"),
+                });
+        }
+        if outcome.removed.is_empty() {
+            return;
+        }
+
+        let binaries = outcome.crashed_binaries();
+        if dry_run {
+            diag::info(
+                "daemon",
+                format_args!(
+                    "dry-run: would remove {} core dumps ({} bytes) at {level:?}; \
+                     crashed: {binaries}",
+                    outcome.removed.len(),
+                    outcome.bytes_freed
+                ),
+            );
+            return;
+        }
+
+        for dump in &outcome.removed {
+            // Removed by policy rather than scoring, so the factors are empty.
+            self.logger_handle.send(ActivityEvent::ArtifactDeleted {
+                path: dump.path.to_string_lossy().to_string(),
+                size_bytes: dump.size_bytes,
+                score: 1.0,
+                factors: ScoreFactorsRecord {
+                    location: 0.0,
+                    name: 0.0,
+                    age: 0.0,
+                    size: 0.0,
+                    structure: 0.0,
+                },
+                pressure: format!("{level:?}"),
+                free_pct: 0.0,
+                duration_ms: 0,
+                owner_uid: None,
+            });
+        }
+        diag::info(
+            "daemon",
+            format_args!(
+                "removed {} core dumps ({} bytes) at {level:?}; crashed: {binaries}",
+                outcome.removed.len(),
+                outcome.bytes_freed
+            ),
+        );
+        self.self_monitor
+            .record_deletions(outcome.removed.len() as u64, outcome.bytes_freed);
+        self.notification_manager
+            .notify(&NotificationEvent::CoreDumpsCleaned {
+                items_deleted: outcome.removed.len(),
+                bytes_freed: outcome.bytes_freed,
+                binaries,
+            });
+    }
+
     fn check_special_locations(
         &mut self,
         scan_tx: &Sender<ScanRequest>,
@@ -1625,7 +3658,6 @@ impl MonitoringDaemon {
 
             if location.needs_attention(&stats) {
                 // Compute pressure level first so we can use it in the notification.
-                let urgency = f64::from(location.priority) / 255.0;
                 let free_ratio = stats.free_pct() / f64::from(location.buffer_pct);
                 let pressure_level = if free_ratio < 0.25 {
                     PressureLevel::Red
@@ -1634,107 +3666,130 @@ impl MonitoringDaemon {
                 } else {
                     PressureLevel::Yellow
                 };
+                self.report_special_location(location, &stats, pressure_level, now);
+                self.relieve_special_location(location, &stats, pressure_level, scan_tx, scan_rx);
+            }
+        }
+    }
 
-                self.logger_handle.send(ActivityEvent::Error {
-                    code: "SBH-2001".to_string(),
-                    message: format!(
-                        "special location {:?} ({}) at {:.1}% free (buffer={}%)",
-                        location.kind,
-                        location.path.display(),
-                        stats.free_pct(),
-                        location.buffer_pct,
-                    ),
+    /// Log a special location under pressure and notify on level changes.
+    fn report_special_location(
+        &mut self,
+        location: &SpecialLocation,
+        stats: &FsStats,
+        pressure_level: PressureLevel,
+        now: Instant,
+    ) {
+        self.logger_handle.send(ActivityEvent::Error {
+            code: "SBH-2001".to_string(),
+            message: format!(
+                "special location {:?} ({}) at {:.1}% free (buffer={}%)",
+                location.kind,
+                location.path.display(),
+                stats.free_pct(),
+                location.buffer_pct,
+            ),
+        });
+        // Only notify when the level for this location actually changes.
+        // Within a 5-minute cooldown, only escalation (higher level) fires.
+        // After cooldown, any level change fires. But the SAME level at the
+        // same location does NOT re-fire — the condition hasn't changed.
+        let should_notify_special = self
+            .last_special_notify
+            .get(&location.path)
+            .is_none_or(|(prev_level, _prev_time)| pressure_level != *prev_level);
+
+        if should_notify_special {
+            self.notification_manager
+                .notify(&NotificationEvent::PressureChanged {
+                    from: "Green".to_string(),
+                    to: format!("{pressure_level:?}"),
+                    mount: location.path.to_string_lossy().into_owned(),
+                    free_pct: stats.free_pct(),
                 });
-                // Only notify when the level for this location actually changes.
-                // Within a 5-minute cooldown, only escalation (higher level) fires.
-                // After cooldown, any level change fires. But the SAME level at the
-                // same location does NOT re-fire — the condition hasn't changed.
-                let should_notify_special = if let Some((prev_level, _prev_time)) =
-                    self.last_special_notify.get(&location.path)
-                {
-                    pressure_level != *prev_level
-                } else {
-                    true
-                };
-
-                if should_notify_special {
-                    self.notification_manager
-                        .notify(&NotificationEvent::PressureChanged {
-                            from: "Green".to_string(),
-                            to: format!("{pressure_level:?}"),
-                            mount: location.path.to_string_lossy().into_owned(),
-                            free_pct: stats.free_pct(),
-                        });
-                    self.last_special_notify
-                        .insert(location.path.clone(), (pressure_level, now));
-                }
+            self.last_special_notify
+                .insert(location.path.clone(), (pressure_level, now));
+        }
+    }
 
-                // Trigger root filesystem scan: special location pressure (e.g. /dev/shm
-                // full) indicates agent swarm activity that is likely also generating root
-                // filesystem artifacts. Proactively scan to clean up before root hits capacity.
-                let max_delete_batch = match pressure_level {
-                    PressureLevel::Red | PressureLevel::Critical => 100,
-                    PressureLevel::Orange => 60,
-                    _ => 40,
-                };
+    /// Release ballast and queue a scan for a special location under pressure.
+    fn relieve_special_location(
+        &mut self,
+        location: &SpecialLocation,
+        stats: &FsStats,
+        pressure_level: PressureLevel,
+        scan_tx: &Sender<ScanRequest>,
+        scan_rx: &Receiver<ScanRequest>,
+    ) {
+        let urgency = f64::from(location.priority) / 255.0;
+
+        // Trigger root filesystem scan: special location pressure (e.g. /dev/shm
+        // full) indicates agent swarm activity that is likely also generating root
+        // filesystem artifacts. Proactively scan to clean up before root hits capacity.
+        let max_delete_batch = match pressure_level {
+            PressureLevel::Red | PressureLevel::Critical => 100,
+            PressureLevel::Orange => 60,
+            _ => 40,
+        };
 
-                // Try immediate ballast release for the pressured mount.
-                // If that mount has no pool (common for /dev/shm tmpfs), fall back to the
-                // non-empty pool with highest releasable bytes to buy recovery time.
-                let release_mount = if self.ballast_coordinator.has_pool(&stats.mount_point) {
-                    Some(stats.mount_point.clone())
-                } else {
-                    self.ballast_coordinator
-                        .inventory()
-                        .into_iter()
-                        .filter(|item| !item.skipped && item.files_available > 0)
-                        .max_by_key(|item| item.releasable_bytes)
-                        .map(|item| item.mount_point)
-                };
+        // Try immediate ballast release for the pressured mount.
+        // If that mount has no pool (common for /dev/shm tmpfs), fall back to the
+        // non-empty pool with highest releasable bytes to buy recovery time.
+        let release_mount = if self.ballast_coordinator.has_pool(&stats.mount_point) {
+            Some(stats.mount_point.clone())
+        } else {
+            self.ballast_coordinator
+                .inventory()
+                .into_iter()
+                .filter(|item| !item.skipped && item.files_available > 0)
+                .max_by_key(|item| item.releasable_bytes)
+                .map(|item| item.mount_point)
+        };
 
-                if let Some(mount) = release_mount {
-                    let release_response = crate::monitor::pid::PressureResponse {
-                        level: pressure_level,
-                        urgency,
-                        scan_interval: Duration::from_secs(0),
-                        release_ballast_files: 0,
-                        max_delete_batch,
-                        fallback_active: false,
-                        causing_mount: mount.clone(),
-                        predicted_seconds: None,
-                    };
-                    let _ = self.release_ballast(&mount, &release_response);
-                }
+        if let Some(mount) = release_mount {
+            let release_response = crate::monitor::pid::PressureResponse {
+                level: pressure_level,
+                urgency,
+                scan_interval: Duration::from_secs(0),
+                release_ballast_files: 0,
+                max_delete_batch,
+                fallback_active: false,
+                causing_mount: mount.clone(),
+                predicted_seconds: None,
+            };
+            let _ = self.release_ballast(&mount, &release_response);
+        }
 
-                let mut scan_paths = Vec::with_capacity(self.config.scanner.root_paths.len() + 1);
-                scan_paths.push(location.path.clone());
-                for root in &self.config.scanner.root_paths {
-                    if root != &location.path {
-                        scan_paths.push(root.clone());
-                    }
-                }
+        let mut scan_paths = Vec::with_capacity(self.config.scanner.root_paths.len() + 1);
+        scan_paths.push(location.path.clone());
+        for root in &self.config.scanner.root_paths {
+            if root != &location.path {
+                scan_paths.push(root.clone());
+            }
+        }
 
-                let request = ScanRequest {
-                    paths: scan_paths,
-                    urgency,
-                    pressure_level,
-                    max_delete_batch,
-                    config_update: None,
-                };
+        let request = ScanRequest {
+            paths: scan_paths,
+            urgency,
+            pressure_level,
+            max_delete_batch,
+            mode: ScanMode::Full,
+            config_update: None,
+        };
 
-                match enqueue_scan_request(scan_tx, scan_rx, request, true) {
-                    ScanEnqueueStatus::Queued | ScanEnqueueStatus::ReplacedStale => {}
-                    ScanEnqueueStatus::DeferredFull => {
-                        eprintln!(
-                            "[SBH-DAEMON] scan channel full (special location trigger), deferred"
-                        );
-                    }
-                    ScanEnqueueStatus::Disconnected => {
-                        eprintln!(
-                            "[SBH-DAEMON] scan channel disconnected (special location trigger)"
-                        );
-                    }
-                }
+        match enqueue_scan_request(scan_tx, scan_rx, request, true) {
+            ScanEnqueueStatus::Queued | ScanEnqueueStatus::ReplacedStale => {}
+            ScanEnqueueStatus::DeferredFull => {
+                diag::warn(
+                    "daemon",
+                    "scan channel full (special location trigger), deferred",
+                );
+            }
+            ScanEnqueueStatus::Disconnected => {
+                diag::warn(
+                    "daemon",
+                    "scan channel disconnected (special location trigger)",
+                );
             }
         }
     }
@@ -1750,16 +3805,16 @@ impl MonitoringDaemon {
         let total_bytes = report.total_bytes();
 
         if total_files > 0 {
-            eprintln!(
-                "[SBH-DAEMON] provisioned {total_files} ballast files ({total_bytes} bytes total)"
+            diag::info(
+                "daemon",
+                format_args!("provisioned {total_files} ballast files ({total_bytes} bytes total)"),
             );
         }
 
         for (path, err) in &report.skipped_volumes {
-            eprintln!(
-                "[SBH-DAEMON] ballast provision skipped for {}: {}",
-                path.display(),
-                err
+            diag::warn(
+                "daemon",
+                format_args!("ballast provision skipped for {}: {}", path.display(), err),
             );
         }
 
@@ -1770,21 +3825,23 @@ impl MonitoringDaemon {
 
     #[allow(clippy::too_many_lines)]
     fn handle_config_reload(&mut self, _scan_tx: &Sender<ScanRequest>) {
-        eprintln!("[SBH-DAEMON] config reload requested (SIGHUP)");
+        diag::info("daemon", "config reload requested (SIGHUP)");
 
         match Config::load(Some(&self.config.paths.config_file)) {
             Ok(new_config) => {
+                self.config_loaded_at = chrono::Utc::now();
                 let old_hash = self.config.stable_hash().unwrap_or_default();
                 let new_hash = new_config.stable_hash().unwrap_or_default();
 
                 if old_hash == new_hash {
-                    eprintln!("[SBH-DAEMON] config unchanged, skipping reload");
+                    self.record_config_reload("unchanged", None);
                 } else {
                     // Update components that can be reconfigured at runtime.
                     self.scoring_engine = ScoringEngine::from_config(
                         &new_config.scoring,
                         new_config.scanner.min_file_age_minutes,
-                    );
+                    )
+                    .with_costs(&new_config.costs);
                     self.release_controller = BallastReleaseController::new(
                         new_config.ballast.replenish_cooldown_minutes,
                     );
@@ -1800,16 +3857,19 @@ impl MonitoringDaemon {
                             self.ballast_coordinator = coordinator;
                         }
                         Err(err) => {
-                            eprintln!(
-                                "[SBH-DAEMON] ballast coordinator rediscovery failed during reload: {err}"
+                            diag::warn(
+                                "daemon",
+                                format_args!(
+                                    "ballast coordinator rediscovery failed during reload: {err}"
+                                ),
                             );
                             self.ballast_coordinator.update_config(&new_config.ballast);
                         }
                     }
 
                     // Propagate pressure thresholds and EWMA params to all active monitors.
-                    for monitor in self.mount_monitors.values_mut() {
-                        monitor.update_config(&new_config);
+                    for (mount, monitor) in &mut self.mount_monitors {
+                        monitor.update_config(&new_config, mount);
                     }
 
                     // Propagate executor-critical settings via shared atomics.
@@ -1819,6 +3879,9 @@ impl MonitoringDaemon {
                     self.shared_executor_config
                         .max_batch_size
                         .store(new_config.scanner.max_delete_batch, Ordering::Relaxed);
+                    self.shared_executor_config
+                        .delete_workers
+                        .store(new_config.scanner.delete_workers, Ordering::Relaxed);
                     self.shared_executor_config
                         .set_min_score(new_config.scoring.min_score);
                     self.shared_executor_config.repeat_base_cooldown_secs.store(
@@ -1829,10 +3892,26 @@ impl MonitoringDaemon {
                         new_config.scanner.repeat_deletion_max_cooldown_secs,
                         Ordering::Relaxed,
                     );
+                    *self.shared_executor_config.truncate_categories.write() =
+                        new_config.scanner.truncate_allowlist();
+                    *self.shared_executor_config.relocation.write() =
+                        new_config.archive.relocation();
+                    *self.shared_executor_config.io_throttle.write() =
+                        new_config.scanner.io_throttle();
+
+                    self.coredump_policy.update_config(&new_config.coredumps);
+
+                    self.deep_scan_schedule = new_config.scanner.deep_scan_schedule();
+                    self.next_deep_scan = self
+                        .deep_scan_schedule
+                        .as_ref()
+                        .and_then(|schedule| schedule.next_after(Local::now().naive_local()));
 
                     // Update FS collector TTL.
                     self.fs_collector
                         .set_ttl(Duration::from_millis(new_config.telemetry.fs_cache_ttl_ms));
+                    self.fs_collector
+                        .set_stats_timeout(new_config.telemetry.fs_stats_timeout());
 
                     // Update VOI scheduler.
                     self.voi_scheduler
@@ -1844,6 +3923,7 @@ impl MonitoringDaemon {
                     // Update shared configs for scanner thread.
                     *self.shared_scoring_config.write() = new_config.scoring.clone();
                     *self.shared_scanner_config.write() = new_config.scanner.clone();
+                    *self.shared_costs_config.write() = new_config.costs.clone();
 
                     // Propagate policy config (kill_switch, budgets, loss values).
                     self.policy_engine
@@ -1858,24 +3938,69 @@ impl MonitoringDaemon {
                     self.notification_manager
                         .update_config(&new_config.notifications);
 
+                    self.self_monitor
+                        .set_memory_budget(new_config.watchdog.memory_budget_mb * 1024 * 1024);
+
                     self.logger_handle.send(ActivityEvent::ConfigReloaded {
                         details: format!("config hash: {old_hash} -> {new_hash}"),
                     });
                     self.config = new_config;
                     self.cached_primary_path = compute_primary_path(&self.config);
-                    eprintln!("[SBH-DAEMON] config reloaded successfully");
+                    self.mount_watcher.set_roots(
+                        &watch_roots(&self.config),
+                        &self.platform.mount_points().unwrap_or_default(),
+                    );
+                    let watcher = &self.mount_watcher;
+                    self.mount_monitors
+                        .retain(|mount, _| watcher.is_watched(mount));
+                    self.refresh_mount_classes();
+                    self.record_config_reload("applied", None);
                 }
             }
             Err(e) => {
-                eprintln!("[SBH-DAEMON] config reload failed: {e}");
-                self.logger_handle.send(ActivityEvent::Error {
-                    code: "SBH-1003".to_string(),
-                    message: format!("config reload failed: {e}"),
-                });
+                // The running config stays in force untouched.
+                self.logger_handle
+                    .send(ActivityEvent::ConfigReloadRejected {
+                        reason: e.to_string(),
+                    });
+                self.record_config_reload("rejected", Some(e.to_string()));
             }
         }
     }
 
+    /// Publish a reload outcome (`applied`, `unchanged`, `rejected`) in
+    /// `state.json` on the next loop pass and on the diagnostic stream.
+    fn record_config_reload(&mut self, outcome: &str, reason: Option<String>) {
+        let config_hash = self.config.stable_hash().unwrap_or_default();
+        diag::event(
+            if reason.is_some() {
+                DiagLevel::Error
+            } else {
+                DiagLevel::Info
+            },
+            "daemon",
+            "config_reload",
+            serde_json::json!({
+                "outcome": outcome,
+                "reason": reason,
+                "config_hash": config_hash,
+            }),
+            format_args!(
+                "config reload {outcome}{}",
+                reason
+                    .as_deref()
+                    .map_or_else(String::new, |reason| format!(": {reason}"))
+            ),
+        );
+        self.self_monitor.config_reload = Some(ConfigReloadState {
+            at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            outcome: outcome.to_string(),
+            reason,
+            config_hash,
+        });
+        self.self_monitor.force_next_write();
+    }
+
     // ──────────────────── worker threads ────────────────────
 
     fn spawn_scanner_thread(
@@ -1888,6 +4013,9 @@ impl MonitoringDaemon {
     ) -> Result<thread::JoinHandle<()>> {
         let scoring_config = Arc::clone(&self.shared_scoring_config);
         let scanner_config = Arc::clone(&self.shared_scanner_config);
+        let costs_config = Arc::clone(&self.shared_costs_config);
+        let score_history_path = ScoreHistory::path_for_state_file(&self.config.paths.state_file);
+        let shed_walker_index = Arc::clone(&self.shed_walker_index);
         thread::Builder::new()
             .name("sbh-scanner".to_string())
             .spawn(move || {
@@ -1897,8 +4025,11 @@ impl MonitoringDaemon {
                     &logger,
                     &scoring_config,
                     &scanner_config,
+                    &costs_config,
                     &heartbeat,
                     &report_tx,
+                    &score_history_path,
+                    &shed_walker_index,
                 );
             })
             .map_err(|source| SbhError::Runtime {
@@ -1916,6 +4047,9 @@ impl MonitoringDaemon {
         let shared_config = Arc::clone(&self.shared_executor_config);
         let policy_engine = Arc::clone(&self.policy_engine);
         let shared_guard_diagnostics = Arc::clone(&self.shared_guard_diagnostics);
+        let platform = Arc::clone(&self.platform);
+        let audit_log = AuditLog::for_paths(&self.config.paths);
+        let shutdown = self.signal_handler.shutdown_flag();
 
         thread::Builder::new()
             .name("sbh-executor".to_string())
@@ -1928,6 +4062,9 @@ impl MonitoringDaemon {
                     &report_tx,
                     &policy_engine,
                     &shared_guard_diagnostics,
+                    platform,
+                    &audit_log,
+                    &shutdown,
                 );
             })
             .map_err(|source| SbhError::Runtime {
@@ -1943,6 +4080,7 @@ impl MonitoringDaemon {
         del_tx: Sender<DeletionBatch>,
         scanner_join: Option<thread::JoinHandle<()>>,
         executor_join: Option<thread::JoinHandle<()>>,
+        report_rx: &Receiver<WorkerReport>,
     ) {
         let uptime_secs = self.start_time.elapsed().as_secs();
 
@@ -1957,17 +4095,23 @@ impl MonitoringDaemon {
         if let Some(h) = executor_join {
             let _ = h.join();
         }
+        // Workers drain queued scans and batches before exiting; count them.
+        while let Ok(report) = report_rx.try_recv() {
+            self.apply_worker_report(report);
+        }
 
         // 3. Log shutdown.
-        self.logger_handle.send(ActivityEvent::DaemonStopped {
-            reason: "clean shutdown".to_string(),
-            uptime_secs,
-        });
-        self.notification_manager
-            .notify(&NotificationEvent::DaemonStopped {
+        if !self.once {
+            self.logger_handle.send(ActivityEvent::DaemonStopped {
                 reason: "clean shutdown".to_string(),
                 uptime_secs,
             });
+            self.notification_manager
+                .notify(&NotificationEvent::DaemonStopped {
+                    reason: "clean shutdown".to_string(),
+                    uptime_secs,
+                });
+        }
 
         // 4. Shutdown logger thread.
         self.logger_handle.shutdown();
@@ -1975,7 +4119,10 @@ impl MonitoringDaemon {
             let _ = logger_join.join();
         }
 
-        eprintln!("[SBH-DAEMON] shutdown complete (uptime={uptime_secs}s)");
+        diag::info(
+            "daemon",
+            format_args!("shutdown complete (uptime={uptime_secs}s)"),
+        );
     }
 }
 
@@ -2014,9 +4161,12 @@ fn dispatch_top_candidates(
     match del_tx.try_send(batch) {
         Ok(()) => true,
         Err(TrySendError::Full(mut deferred)) => {
-            eprintln!(
-                "[SBH-SCANNER] executor channel full, deferring {} candidates",
-                deferred.candidates.len()
+            diag::warn(
+                "scanner",
+                format_args!(
+                    "executor channel full, deferring {} candidates",
+                    deferred.candidates.len()
+                ),
             );
             scored.append(&mut deferred.candidates);
             true
@@ -2025,46 +4175,362 @@ fn dispatch_top_candidates(
     }
 }
 
+/// How often the scanner thread folds watcher events into the candidate set
+/// while it has no scan request to serve.
+const WATCH_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Event-maintained candidate set for `scanner.watch`.
+///
+/// A full walk of a root refills that root's share of `entries` (every
+/// classified entry, qualifying or not) and marks the root verified. Until
+/// `watch_full_rescan_secs` runs out, passes over verified roots rescore
+/// `entries` instead of walking. Between passes the scanner thread folds in
+/// watcher changes every [`WATCH_REFRESH_INTERVAL`], so a new `target/`
+/// joins the set within seconds instead of at the next full walk.
+struct WatchedScan {
+    /// Configured roots the watch was set up for.
+    roots: Vec<PathBuf>,
+    watcher: TreeWatcher,
+    entries: HashMap<PathBuf, WalkEntry>,
+    /// Roots whose last full walk completed, and when.
+    verified: HashMap<PathBuf, Instant>,
+}
+
+impl WatchedScan {
+    fn new(roots: Vec<PathBuf>, watcher: TreeWatcher) -> Self {
+        Self {
+            roots,
+            watcher,
+            entries: HashMap::new(),
+            verified: HashMap::new(),
+        }
+    }
+
+    /// Whether a pass over `paths` can rescore `entries` instead of walking.
+    fn can_serve(&self, paths: &[PathBuf], full_rescan: Duration) -> bool {
+        !full_rescan.is_zero()
+            && paths.iter().all(|path| {
+                self.watcher.roots().iter().any(|root| {
+                    path.starts_with(root)
+                        && self
+                            .verified
+                            .get(root)
+                            .is_some_and(|at| at.elapsed() < full_rescan)
+                })
+            })
+    }
+
+    /// Forget what a full walk of `paths` is about to rediscover.
+    fn begin_walk(&mut self, paths: &[PathBuf]) {
+        self.entries
+            .retain(|path, _| !paths.iter().any(|p| path.starts_with(p)));
+    }
+
+    /// Keep an entry the full walk classified.
+    fn remember(&mut self, entry: &WalkEntry) {
+        if self
+            .watcher
+            .roots()
+            .iter()
+            .any(|root| entry.path.starts_with(root))
+        {
+            self.entries.insert(entry.path.clone(), entry.clone());
+        }
+    }
+
+    /// Record the outcome of a full walk of `paths`. An incomplete walk left
+    /// holes in `entries`, so the roots it touched need walking again.
+    fn finish_walk(&mut self, paths: &[PathBuf], complete: bool) {
+        for root in self.watcher.roots() {
+            if complete && paths.contains(root) {
+                self.verified.insert(root.clone(), Instant::now());
+            } else if !complete
+                && paths
+                    .iter()
+                    .any(|p| p.starts_with(root) || root.starts_with(p))
+            {
+                self.verified.remove(root);
+            }
+        }
+    }
+
+    /// Drop `entries` to free memory. Every root needs a full walk again.
+    fn shed(&mut self) {
+        self.entries = HashMap::new();
+        self.verified.clear();
+    }
+
+    /// Fold pending watcher changes into `entries`.
+    fn refresh(
+        &mut self,
+        config: &crate::core::config::ScannerConfig,
+        registry: &ArtifactPatternRegistry,
+    ) {
+        let changes = self.watcher.drain();
+        if changes.is_empty() {
+            return;
+        }
+        if changes.rescan {
+            diag::info(
+                "scanner",
+                "filesystem events were lost; next pass walks in full",
+            );
+            self.verified.clear();
+            return;
+        }
+        self.entries
+            .retain(|path, _| !path.ancestors().any(|a| changes.removed.contains(a)));
+
+        let Ok(protection) = ProtectionRegistry::new(Some(&config.protected_paths)) else {
+            self.verified.clear();
+            return;
+        };
+        let walker = DirectoryWalker::new(
+            WalkerConfig {
+                root_paths: self.watcher.roots().to_vec(),
+                max_depth: config.max_depth,
+                follow_symlinks: config.follow_symlinks,
+                cross_devices: config.cross_devices,
+                parallelism: config.parallelism,
+                excluded_paths: config.excluded_paths.iter().cloned().collect(),
+            },
+            protection,
+        )
+        .with_large_files(config.large_file_min_bytes)
+        .with_artifact_roots(config.artifact_root_sampling());
+        match walker.stream_changes(&changes) {
+            Ok(rx) => {
+                for entry in rx {
+                    let classification = registry.classify_entry(
+                        &entry.path,
+                        entry.metadata.is_dir,
+                        entry.structural_signals,
+                    );
+                    if classification.category == ArtifactCategory::Unknown {
+                        self.entries.remove(&entry.path);
+                    } else {
+                        self.entries.insert(entry.path.clone(), entry);
+                    }
+                }
+            }
+            Err(e) => {
+                diag::warn(
+                    "scanner",
+                    format_args!("incremental walk failed; next pass walks in full: {e}"),
+                );
+                self.verified.clear();
+                return;
+            }
+        }
+        // Markers found on the way now cover entries kept from earlier walks.
+        let protection = walker.protection().read();
+        self.entries
+            .retain(|path, _| !protection.is_protected(path));
+    }
+
+    /// Replay the entries under `paths` as if a walk had found them.
+    fn replay(&self, paths: &[PathBuf]) -> Receiver<WalkEntry> {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        for entry in self
+            .entries
+            .values()
+            .filter(|entry| paths.iter().any(|p| entry.path.starts_with(p)))
+        {
+            let _ = tx.send(entry.clone());
+        }
+        rx
+    }
+}
+
+/// Lifecycle of the `scanner.watch` candidate set: set up off-thread (a
+/// recursive watch on a large tree takes a while), replaced when the roots
+/// change, dropped when the option is turned off.
+#[derive(Default)]
+struct WatchSlot {
+    active: Option<WatchedScan>,
+    pending: Option<(Vec<PathBuf>, Receiver<Result<TreeWatcher>>)>,
+    /// Roots whose setup failed, so the failure is reported once.
+    failed_for: Option<Vec<PathBuf>>,
+}
+
+impl WatchSlot {
+    /// Bring the slot in line with `config`; returns a setup failure to log.
+    fn sync(&mut self, config: &crate::core::config::ScannerConfig) -> Option<SbhError> {
+        if !config.watch {
+            *self = Self::default();
+            return None;
+        }
+        let roots = &config.root_paths;
+        if self.active.as_ref().is_some_and(|w| &w.roots == roots)
+            || self.failed_for.as_ref() == Some(roots)
+        {
+            return None;
+        }
+        if let Some((pending_roots, rx)) = &self.pending
+            && pending_roots == roots
+        {
+            let failure = match rx.try_recv() {
+                Ok(Ok(watcher)) => {
+                    self.active = Some(WatchedScan::new(roots.clone(), watcher));
+                    None
+                }
+                Ok(Err(e)) => Some(e),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => Some(SbhError::Runtime {
+                    details: "watch setup thread exited".to_string(),
+                }),
+            };
+            self.pending = None;
+            if failure.is_some() {
+                self.failed_for = Some(roots.clone());
+            }
+            return failure;
+        }
+
+        // First use or new roots: start over.
+        self.active = None;
+        self.failed_for = None;
+        let (tx, rx) = bounded(1);
+        let setup_roots = roots.clone();
+        match thread::Builder::new()
+            .name("sbh-watch-setup".to_string())
+            .spawn(move || {
+                let _ = tx.send(TreeWatcher::new(&setup_roots));
+            }) {
+            Ok(_) => {
+                self.pending = Some((roots.clone(), rx));
+                None
+            }
+            Err(e) => {
+                self.failed_for = Some(roots.clone());
+                Some(SbhError::Runtime {
+                    details: format!("cannot spawn watch setup: {e}"),
+                })
+            }
+        }
+    }
+}
+
 /// Scanner thread: receives scan requests, walks directories, scores candidates,
 /// and sends deletion batches to the executor.
 ///
 /// Uses `DirectoryWalker` to perform parallel, depth-limited, safe traversals
-/// and `ScoringEngine` to rank candidates.
-#[allow(clippy::too_many_lines)]
+/// and `ScoringEngine` to rank candidates. With `scoring.stability_scans > 1`,
+/// candidates are held back until they qualify on that many consecutive
+/// passes (see `scanner::score_history`). With `scanner.watch`, passes over
+/// recently verified roots rescore the event-maintained set in
+/// [`WatchedScan`] instead of walking.
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn scanner_thread_main(
     scan_rx: &Receiver<ScanRequest>,
     del_tx: &Sender<DeletionBatch>,
     logger: &ActivityLoggerHandle,
     shared_scoring_config: &Arc<RwLock<crate::core::config::ScoringConfig>>,
     shared_scanner_config: &Arc<RwLock<crate::core::config::ScannerConfig>>,
+    shared_costs_config: &Arc<RwLock<crate::core::config::CostsConfig>>,
     heartbeat: &Arc<ThreadHeartbeat>,
     report_tx: &Sender<WorkerReport>,
+    score_history_path: &Path,
+    shed_walker_index: &AtomicBool,
 ) {
     // Initialize pattern registry (default built-ins).
     let pattern_registry = ArtifactPatternRegistry::default();
+    // Loaded on the first pass that needs it; dropped if stability is disabled.
+    let mut score_history: Option<ScoreHistory> = None;
+    let mut watch = WatchSlot::default();
+
+    loop {
+        let request = if let Some(watched) = watch.active.as_mut() {
+            match scan_rx.recv_timeout(WATCH_REFRESH_INTERVAL) {
+                Ok(request) => request,
+                Err(RecvTimeoutError::Timeout) => {
+                    heartbeat.beat();
+                    if shed_walker_index.swap(false, Ordering::Relaxed) {
+                        watched.shed();
+                    }
+                    let scanner_config = shared_scanner_config.read().clone();
+                    watched.refresh(&scanner_config, &pattern_registry);
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            match scan_rx.recv() {
+                Ok(request) => request,
+                Err(_) => break,
+            }
+        };
+
+        if shed_walker_index.swap(false, Ordering::Relaxed)
+            && let Some(watched) = watch.active.as_mut()
+        {
+            watched.shed();
+        }
 
-    while let Ok(request) = scan_rx.recv() {
         // Read latest config at the start of each scan.
         let current_scoring_config = shared_scoring_config.read().clone();
         let current_scanner_config = shared_scanner_config.read().clone();
+        let truncatable = current_scanner_config.truncate_allowlist();
 
-        let engine = ScoringEngine::from_config(
-            &current_scoring_config,
-            current_scanner_config.min_file_age_minutes,
-        );
+        if let Some(e) = watch.sync(&current_scanner_config) {
+            logger.send(ActivityEvent::Error {
+                code: e.code().to_string(),
+                message: format!("filesystem watch unavailable, using full walks: {e}"),
+            });
+        }
+
+        // Deep scans relax the age floor; shallow ones stop short.
+        let min_file_age_minutes = if request.mode == ScanMode::Deep {
+            current_scanner_config
+                .deep_scan_min_file_age_minutes
+                .min(current_scanner_config.min_file_age_minutes)
+        } else {
+            current_scanner_config.min_file_age_minutes
+        };
+        let max_depth = if request.mode == ScanMode::Shallow {
+            current_scanner_config
+                .max_depth
+                .min(current_scanner_config.shallow_max_depth)
+        } else {
+            current_scanner_config.max_depth
+        };
+        let engine = ScoringEngine::from_config(&current_scoring_config, min_file_age_minutes)
+            .with_costs(&shared_costs_config.read());
 
         // If no paths to scan, skip.
         if request.paths.is_empty() {
             continue;
         }
 
+        let stability_scans = current_scoring_config.stability_scans;
+        if stability_scans <= 1 {
+            score_history = None;
+        } else if score_history.is_none() {
+            score_history = Some(ScoreHistory::load(score_history_path).unwrap_or_else(|e| {
+                diag::warn(
+                    "scanner",
+                    format_args!("score history unreadable, starting fresh: {e}"),
+                );
+                ScoreHistory::new()
+            }));
+        }
+        if let Some(history) = score_history.as_mut() {
+            history.begin_pass();
+        }
+        // Red/critical pressure cannot afford to wait out the stability window.
+        let bypass_stability = matches!(
+            request.pressure_level,
+            PressureLevel::Red | PressureLevel::Critical
+        );
+        let mut held_for_stability = 0usize;
+
         heartbeat.beat();
         let scan_start = Instant::now();
 
         // Configure walker.
         let walker_config = WalkerConfig {
             root_paths: request.paths.clone(),
-            max_depth: current_scanner_config.max_depth,
+            max_depth,
             follow_symlinks: current_scanner_config.follow_symlinks,
             cross_devices: current_scanner_config.cross_devices,
             parallelism: current_scanner_config.parallelism,
@@ -2088,23 +4554,71 @@ fn scanner_thread_main(
                 }
             };
 
-        let walker = DirectoryWalker::new(walker_config, protection).with_heartbeat({
-            let hb = Arc::clone(heartbeat);
-            move || hb.beat()
-        });
+        let walker = DirectoryWalker::new(walker_config, protection)
+            .with_large_files(current_scanner_config.large_file_min_bytes)
+            .with_artifact_roots(current_scanner_config.artifact_root_sampling())
+            .with_device_limits(current_scanner_config.device_limits())
+            .with_heartbeat({
+                let hb = Arc::clone(heartbeat);
+                move || hb.beat()
+            });
         let cancel_token = walker.cancel_token();
 
-        // Perform the walk (streaming).
-        let rx = match walker.stream() {
-            Ok(r) => r,
-            Err(e) => {
-                logger.send(ActivityEvent::Error {
-                    code: e.code().to_string(),
-                    message: format!("walker failed: {e}"),
-                });
-                continue;
-            }
+        // Rescore the watched set when it is fresh enough, otherwise walk.
+        // Deep scans always walk, and a depth-capped shallow walk would leave
+        // the watched set incomplete, so only full scans touch it.
+        let incremental = request.mode == ScanMode::Full
+            && watch.active.as_mut().is_some_and(|watched| {
+                watched.refresh(&current_scanner_config, &pattern_registry);
+                watched.can_serve(
+                    &request.paths,
+                    Duration::from_secs(current_scanner_config.watch_full_rescan_secs),
+                )
+            });
+        diag::event(
+            DiagLevel::Info,
+            "scanner",
+            "scan_started",
+            serde_json::json!({
+                "paths": request.paths,
+                "urgency": request.urgency,
+                "pressure": format!("{:?}", request.pressure_level),
+                "incremental": incremental,
+                "mode": request.mode.as_str(),
+            }),
+            format_args!(
+                "{}{}scan started over {} roots at {:?} pressure (urgency {:.2})",
+                if incremental { "incremental " } else { "" },
+                match request.mode {
+                    ScanMode::Full => "",
+                    ScanMode::Shallow => "shallow ",
+                    ScanMode::Deep => "deep ",
+                },
+                request.paths.len(),
+                request.pressure_level,
+                request.urgency
+            ),
+        );
+        let records_walk = !incremental && request.mode != ScanMode::Shallow;
+        let rx = match watch.active.as_mut() {
+            Some(watched) if incremental => watched.replay(&request.paths),
+            watched => match walker.stream() {
+                Ok(r) => {
+                    if let Some(watched) = watched.filter(|_| records_walk) {
+                        watched.begin_walk(&request.paths);
+                    }
+                    r
+                }
+                Err(e) => {
+                    logger.send(ActivityEvent::Error {
+                        code: e.code().to_string(),
+                        message: format!("walker failed: {e}"),
+                    });
+                    continue;
+                }
+            },
         };
+        let mut walk_cut_short = false;
 
         let mut paths_scanned = 0;
         let mut candidates_found = 0;
@@ -2170,10 +4684,14 @@ fn scanner_thread_main(
                     // No entries for 2 seconds — check if budget is exhausted.
                     if Instant::now() >= scan_deadline {
                         cancel_token.store(true, Ordering::Relaxed);
-                        eprintln!(
-                            "[SBH-SCANNER] scan timed out ({paths_scanned} entries, \
-                             {candidates_found} candidates, {:.1}s) — cancelling walker threads",
-                            scan_start.elapsed().as_secs_f64()
+                        walk_cut_short = true;
+                        diag::warn(
+                            "scanner",
+                            format_args!(
+                                "scan timed out ({paths_scanned} entries, \
+                                 {candidates_found} candidates, {:.1}s) — cancelling walker threads",
+                                scan_start.elapsed().as_secs_f64()
+                            ),
                         );
                         break;
                     }
@@ -2186,10 +4704,14 @@ fn scanner_thread_main(
             // Budget check: stop processing if we've exceeded entry count or time limits.
             if paths_scanned >= SCAN_ENTRY_BUDGET || Instant::now() >= scan_deadline {
                 cancel_token.store(true, Ordering::Relaxed);
-                eprintln!(
-                    "[SBH-SCANNER] scan budget reached ({paths_scanned} entries, \
-                     {candidates_found} candidates, {:.1}s) — cancelling walker threads",
-                    scan_start.elapsed().as_secs_f64()
+                walk_cut_short = true;
+                diag::info(
+                    "scanner",
+                    format_args!(
+                        "scan budget reached ({paths_scanned} entries, \
+                         {candidates_found} candidates, {:.1}s) — cancelling walker threads",
+                        scan_start.elapsed().as_secs_f64()
+                    ),
                 );
                 break;
             }
@@ -2201,12 +4723,19 @@ fn scanner_thread_main(
                 .unwrap_or(Duration::ZERO);
 
             // Classify.
-            let classification = pattern_registry.classify(&entry.path, entry.structural_signals);
+            let classification = pattern_registry.classify_entry(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.structural_signals,
+            );
 
             // Skip unknown artifacts to save scoring cycles.
             if classification.category == crate::scanner::patterns::ArtifactCategory::Unknown {
                 continue;
             }
+            if records_walk && let Some(watched) = watch.active.as_mut() {
+                watched.remember(&entry);
+            }
 
             // Lazy-join the /proc scan thread on first classified entry.
             // This gives the /proc scan the full walker startup period to run
@@ -2217,14 +4746,17 @@ fn scanner_thread_main(
                     .and_then(|h| h.join().ok())
                     .unwrap_or_default()
             });
-            let is_open = crate::scanner::walker::is_path_open_by_ancestor(&entry.path, open_files);
+            // Allowlisted open files stay eligible; the executor truncates them.
+            let is_open = crate::scanner::walker::is_path_open_by_ancestor(&entry.path, open_files)
+                && (entry.metadata.is_dir || !truncatable.contains(&classification.category));
 
             let input = crate::scanner::scoring::CandidateInput {
                 path: entry.path.clone(), // Clone needed for input
                 size_bytes: entry.metadata.content_size_bytes,
+                disk_bytes: entry.metadata.content_disk_bytes,
                 age: adjusted_candidate_age(
                     age,
-                    current_scanner_config.min_file_age_minutes,
+                    min_file_age_minutes,
                     request.pressure_level,
                     &entry.path,
                     &classification,
@@ -2236,13 +4768,49 @@ fn scanner_thread_main(
             };
 
             let score = engine.score_candidate(&input, request.urgency);
+            if diag::enabled(DiagLevel::Debug) {
+                diag::event(
+                    DiagLevel::Debug,
+                    "scanner",
+                    "candidate_scored",
+                    serde_json::json!({
+                        "path": score.path,
+                        "score": score.total_score,
+                        "size_bytes": score.size_bytes,
+                        "category": format!("{:?}", score.classification.category),
+                        "action": format!("{:?}", score.decision.action),
+                        "vetoed": score.vetoed,
+                    }),
+                    format_args!(
+                        "scored {} at {:.3} ({:?}{})",
+                        score.path.display(),
+                        score.total_score,
+                        score.decision.action,
+                        if score.vetoed { ", vetoed" } else { "" }
+                    ),
+                );
+            }
 
             // Attribute to root.
             let root_path = request.paths.iter().find(|r| entry.path.starts_with(r));
 
-            if score.decision.action == crate::scanner::scoring::DecisionAction::Delete
-                && !score.vetoed
-            {
+            let qualifies = score.decision.action
+                == crate::scanner::scoring::DecisionAction::Delete
+                && !score.vetoed;
+            let stable = score_history.as_mut().is_none_or(|history| {
+                let verdict = history.observe(
+                    &score.path,
+                    score.total_score,
+                    qualifies,
+                    stability_scans,
+                    current_scoring_config.min_score,
+                );
+                verdict.is_stable() || bypass_stability
+            });
+
+            if qualifies && !stable {
+                held_for_stability += 1;
+            } else if qualifies {
                 candidates_found += 1;
                 scored.push(score);
                 if let Some(root) = root_path
@@ -2287,11 +4855,45 @@ fn scanner_thread_main(
         #[allow(clippy::cast_possible_truncation)]
         let scan_duration_ms = total_scan_duration.as_millis() as u64;
 
-        eprintln!(
-            "[SBH-SCANNER] scan complete: {paths_scanned} entries, \
-             {candidates_found} candidates, {:.1}s",
-            total_scan_duration.as_secs_f64()
+        diag::event(
+            DiagLevel::Info,
+            "scanner",
+            "scan_completed",
+            serde_json::json!({
+                "entries": paths_scanned,
+                "candidates": candidates_found,
+                "duration_ms": total_scan_duration.as_millis(),
+                "incremental": incremental,
+            }),
+            format_args!(
+                "{}scan complete: {paths_scanned} entries, \
+                 {candidates_found} candidates, {:.1}s",
+                if incremental { "incremental " } else { "" },
+                total_scan_duration.as_secs_f64()
+            ),
         );
+        if records_walk && let Some(watched) = watch.active.as_mut() {
+            watched.finish_walk(&request.paths, !walk_cut_short);
+        }
+
+        if let Some(history) = score_history.as_mut() {
+            history.finish_pass();
+            if held_for_stability > 0 {
+                diag::info(
+                    "scanner",
+                    format_args!(
+                        "{held_for_stability} candidates held until they qualify \
+                         for {stability_scans} consecutive scans"
+                    ),
+                );
+            }
+            if let Err(e) = history.save(score_history_path) {
+                logger.send(ActivityEvent::Error {
+                    code: e.code().to_string(),
+                    message: format!("score history save failed: {e}"),
+                });
+            }
+        }
 
         // Log scan completion.
         logger.send(ActivityEvent::ScanCompleted {
@@ -2316,9 +4918,12 @@ fn scanner_thread_main(
             }
             // No progress means executor channel stayed full; avoid busy-loop.
             if scored.len() >= pending_before {
-                eprintln!(
-                    "[SBH-SCANNER] executor backlog persisted at scan end; {} candidates will be rediscovered on next pass",
-                    scored.len()
+                diag::warn(
+                    "scanner",
+                    format_args!(
+                        "executor backlog persisted at scan end; {} candidates will be rediscovered on next pass",
+                        scored.len()
+                    ),
                 );
                 break;
             }
@@ -2443,7 +5048,7 @@ impl RepeatDeletionTracker {
 ///
 /// Reads `dry_run`, `max_batch_size`, and `min_score` from shared atomics on each
 /// batch, so config reloads (SIGHUP) take effect without respawning the thread.
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn executor_thread_main(
     del_rx: &Receiver<DeletionBatch>,
     logger: &ActivityLoggerHandle,
@@ -2452,7 +5057,12 @@ fn executor_thread_main(
     report_tx: &Sender<WorkerReport>,
     policy_engine: &Arc<Mutex<PolicyEngine>>,
     shared_guard_diagnostics: &Arc<RwLock<Option<GuardDiagnostics>>>,
+    platform: Arc<dyn Platform>,
+    audit_log: &AuditLog,
+    shutdown: &Arc<AtomicBool>,
 ) {
+    // Zero TTL: reclaim verification needs uncached before/after reads.
+    let fs_stats = FsStatsCollector::new(platform, Duration::ZERO);
     let mut tracker = RepeatDeletionTracker::new(
         Duration::from_secs(shared_config.repeat_base_cooldown_secs()),
         Duration::from_secs(shared_config.repeat_max_cooldown_secs()),
@@ -2470,9 +5080,12 @@ fn executor_thread_main(
         // tripped recently, skip this batch entirely and drain the channel.
         if let Some(trip_time) = last_circuit_breaker_trip {
             if trip_time.elapsed() < circuit_breaker_cooldown {
-                eprintln!(
-                    "[SBH-EXECUTOR] circuit breaker cooldown active ({:.0}s remaining), skipping batch",
-                    circuit_breaker_cooldown.as_secs_f64() - trip_time.elapsed().as_secs_f64(),
+                diag::warn(
+                    "executor",
+                    format_args!(
+                        "circuit breaker cooldown active ({:.0}s remaining), skipping batch",
+                        circuit_breaker_cooldown.as_secs_f64() - trip_time.elapsed().as_secs_f64()
+                    ),
                 );
                 continue;
             }
@@ -2480,6 +5093,18 @@ fn executor_thread_main(
             last_circuit_breaker_trip = None;
         }
 
+        // A maintenance window drops batches scanned before it began.
+        if shared_config.paused.load(Ordering::Relaxed) {
+            diag::info(
+                "executor",
+                format_args!(
+                    "maintenance window active, dropping batch of {} candidates",
+                    batch.candidates.len()
+                ),
+            );
+            continue;
+        }
+
         // Pick up live config reloads for repeat-deletion dampening.
         tracker.update_cooldowns(
             Duration::from_secs(shared_config.repeat_base_cooldown_secs()),
@@ -2488,7 +5113,7 @@ fn executor_thread_main(
 
         // Gate candidates through the policy engine. The lock is held only for
         // the duration of evaluate() (pure computation, no I/O).
-        let (approved_candidates, policy_mode) = {
+        let (approved_candidates, policy_mode, decision_ids) = {
             let guard_snapshot = shared_guard_diagnostics.read().clone();
             let guard_for_policy = guard_snapshot
                 .as_ref()
@@ -2496,15 +5121,23 @@ fn executor_thread_main(
             let decision = policy_engine
                 .lock()
                 .evaluate(&batch.candidates, guard_for_policy);
-            (decision.approved_for_deletion, decision.mode)
+            let decision_ids: HashMap<PathBuf, u64> = decision
+                .records
+                .iter()
+                .map(|record| (record.path.clone(), record.decision_id))
+                .collect();
+            (decision.approved_for_deletion, decision.mode, decision_ids)
         };
 
         if !approved_candidates.is_empty() {
-            eprintln!(
-                "[SBH-EXECUTOR] policy engine approved {}/{} candidates (mode={})",
-                approved_candidates.len(),
-                batch.candidates.len(),
-                policy_mode,
+            diag::info(
+                "executor",
+                format_args!(
+                    "policy engine approved {}/{} candidates (mode={})",
+                    approved_candidates.len(),
+                    batch.candidates.len(),
+                    policy_mode
+                ),
             );
         }
 
@@ -2515,11 +5148,14 @@ fn executor_thread_main(
                 .is_none_or(|last| now.duration_since(last) >= Duration::from_secs(300));
             if should_log {
                 last_policy_reject_log = Some(now);
-                eprintln!(
-                    "[SBH-EXECUTOR] policy rejected {}/{} candidates (mode={})",
-                    batch.candidates.len(),
-                    batch.candidates.len(),
-                    policy_mode,
+                diag::info(
+                    "executor",
+                    format_args!(
+                        "policy rejected {}/{} candidates (mode={})",
+                        batch.candidates.len(),
+                        batch.candidates.len(),
+                        policy_mode
+                    ),
                 );
             }
             continue;
@@ -2530,10 +5166,47 @@ fn executor_thread_main(
             tracker.filter_candidates(approved_candidates, batch.pressure_level);
 
         if !dampened.is_empty() {
-            eprintln!(
-                "[SBH-EXECUTOR] dampened {}/{} repeat-deletion candidates",
-                dampened.len(),
-                dampened.len() + approved_candidates.len(),
+            diag::info(
+                "executor",
+                format_args!(
+                    "dampened {}/{} repeat-deletion candidates",
+                    dampened.len(),
+                    dampened.len() + approved_candidates.len()
+                ),
+            );
+        }
+
+        // Never auto-delete on removable or transient media unless opted in.
+        let no_delete_mounts = shared_config.no_delete_mounts();
+        let (approved_candidates, on_removable): (Vec<_>, Vec<_>) =
+            approved_candidates.into_iter().partition(|candidate| {
+                !no_delete_mounts
+                    .iter()
+                    .any(|mount| candidate.path.starts_with(mount))
+            });
+        if !on_removable.is_empty() {
+            diag::info(
+                "executor",
+                format_args!(
+                    "skipped {} candidates on removable/transient mounts",
+                    on_removable.len()
+                ),
+            );
+        }
+        let readonly_mounts = shared_config.readonly_mounts();
+        let (approved_candidates, on_readonly): (Vec<_>, Vec<_>) =
+            approved_candidates.into_iter().partition(|candidate| {
+                !readonly_mounts
+                    .iter()
+                    .any(|mount| candidate.path.starts_with(mount))
+            });
+        if !on_readonly.is_empty() {
+            diag::info(
+                "executor",
+                format_args!(
+                    "skipped {} candidates on read-only mounts",
+                    on_readonly.len()
+                ),
             );
         }
 
@@ -2544,6 +5217,7 @@ fn executor_thread_main(
         // Read latest config from shared atomics (updated by config reload).
         let dry_run = shared_config.dry_run.load(Ordering::Relaxed);
         let max_batch_size = shared_config.max_batch_size.load(Ordering::Relaxed);
+        let workers = shared_config.delete_workers.load(Ordering::Relaxed);
         let min_score = shared_config.min_score();
 
         let executor = DeletionExecutor::new(
@@ -2552,10 +5226,30 @@ fn executor_thread_main(
                 dry_run,
                 min_score,
                 check_open_files: true,
+                truncate_categories: shared_config.truncate_categories(),
+                relocation: shared_config.relocation(),
+                // At Critical the disk is about to fill; the builds being
+                // protected fail either way, so removal runs flat out.
+                io_throttle: shared_config
+                    .io_throttle()
+                    .filter(|_| batch.pressure_level != PressureLevel::Critical),
+                workers,
                 ..Default::default()
             },
             Some(logger.clone()),
-        );
+        )
+        .with_cancel(Arc::clone(shutdown))
+        .with_audit(
+            audit_log.clone(),
+            AuditContext {
+                source: "daemon",
+                policy_mode: Some(policy_mode.to_string()),
+                decision_ids,
+            },
+        )
+        .with_observer(Box::new(move |candidate, outcome| {
+            log_item_outcome(candidate, outcome, dry_run);
+        }));
 
         let plan = executor.plan(approved_candidates);
 
@@ -2563,27 +5257,60 @@ fn executor_thread_main(
             continue;
         }
 
+        let plan_paths: Vec<PathBuf> = plan.candidates.iter().map(|c| c.path.clone()).collect();
+        let before = fs_stats.collect_many(&plan_paths).unwrap_or_default();
         let report = executor.execute(&plan, None);
+        verify_batch_reclaim(&plan, &report, &before, &fs_stats, logger, report_tx);
 
         // Record deletions for repeat-deletion dampening.
+        // Truncated logs regrow, so they share the same dampening.
         tracker.record_deletions(&report.deleted_paths);
+        tracker.record_deletions(&report.truncated_paths);
+        tracker.record_deletions(&report.relocated_paths);
 
-        if report.items_deleted > 0 || report.items_failed > 0 {
-            eprintln!(
-                "[SBH-EXECUTOR] deleted={} failed={} skipped={} freed={}B ({:?})",
-                report.items_deleted,
-                report.items_failed,
-                report.items_skipped,
-                report.bytes_freed,
-                report.duration,
+        if report.cancelled {
+            diag::info(
+                "executor",
+                "shutdown requested; rest of the batch left in place",
+            );
+        }
+        if report.items_deleted > 0
+            || report.items_truncated > 0
+            || report.items_relocated > 0
+            || report.items_failed > 0
+        {
+            diag::info(
+                "executor",
+                format_args!(
+                    "deleted={} truncated={} relocated={} failed={} skipped={} freed={}B ({:?}, {:?} paced)",
+                    report.items_deleted,
+                    report.items_truncated,
+                    report.items_relocated,
+                    report.items_failed,
+                    report.items_skipped,
+                    report.bytes_freed,
+                    report.duration,
+                    report.throttled
+                ),
             );
         }
 
-        // Report deletion stats back to main loop for SelfMonitor counters.
+        // Report deletion stats back to main loop for SelfMonitor counters
+        // and the per-mount cleanup cooldown.
+        let mut mounts: Vec<PathBuf> = report
+            .deleted_paths
+            .iter()
+            .chain(&report.truncated_paths)
+            .chain(&report.relocated_paths)
+            .filter_map(|path| before.get(path).map(|stats| stats.mount_point.clone()))
+            .collect();
+        mounts.sort();
+        mounts.dedup();
         let _ = report_tx.try_send(WorkerReport::DeletionCompleted {
             deleted: report.items_deleted as u64,
             bytes_freed: report.bytes_freed,
             failed: report.items_failed as u64,
+            mounts,
         });
 
         if report.circuit_breaker_tripped {
@@ -2604,19 +5331,97 @@ fn executor_thread_main(
     }
 }
 
+/// Report one executed (or, in a dry run, planned) item as a
+/// `deletion_executed` event. Skips are debug detail; failures are warnings.
+fn log_item_outcome(candidate: &CandidacyScore, outcome: &ItemOutcome, dry_run: bool) {
+    let (level, error) = match outcome {
+        ItemOutcome::Failed { error, .. } => (DiagLevel::Warn, Some(error.as_str())),
+        ItemOutcome::Skipped(_) => (DiagLevel::Debug, None),
+        _ => (DiagLevel::Info, None),
+    };
+    if !diag::enabled(level) {
+        return;
+    }
+    diag::event(
+        level,
+        "executor",
+        "deletion_executed",
+        serde_json::json!({
+            "path": candidate.path,
+            "outcome": outcome.label(),
+            "size_bytes": candidate.size_bytes,
+            "score": candidate.total_score,
+            "dry_run": dry_run,
+            "error": error,
+        }),
+        format_args!(
+            "{}{} {} ({} bytes, score {:.3}){}",
+            if dry_run { "dry-run: " } else { "" },
+            outcome.label(),
+            candidate.path.display(),
+            candidate.size_bytes,
+            candidate.total_score,
+            error.map_or_else(String::new, |e| format!(": {e}"))
+        ),
+    );
+}
+
+/// Re-stat the mounts a batch touched, log any that gained far less than the
+/// report claims, and hand every check to the main loop for guard calibration.
+fn verify_batch_reclaim(
+    plan: &DeletionPlan,
+    report: &DeletionReport,
+    before: &HashMap<PathBuf, FsStats>,
+    fs_stats: &FsStatsCollector,
+    logger: &ActivityLoggerHandle,
+    report_tx: &Sender<WorkerReport>,
+) {
+    let verified = verify_reclaim(plan, report, before, |mount| {
+        fs_stats
+            .collect(mount)
+            .ok()
+            .map(|stats| stats.available_bytes)
+    });
+    for verification in verified {
+        if verification.is_shortfall() {
+            diag::warn(
+                "executor",
+                format_args!(
+                    "reclaim shortfall on {}: claimed={}B observed={}B",
+                    verification.mount.display(),
+                    verification.expected_bytes,
+                    verification.observed_bytes
+                ),
+            );
+            logger.send(ActivityEvent::ReclaimShortfall {
+                mount_point: verification.mount.to_string_lossy().into_owned(),
+                expected_bytes: verification.expected_bytes,
+                observed_bytes: verification.observed_bytes,
+            });
+        }
+        let _ = report_tx.try_send(WorkerReport::ReclaimVerified {
+            verification,
+            elapsed: report.duration,
+        });
+    }
+}
+
 // ──────────────────── tests ────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::config::Config;
+    use crate::daemon::policy::ActiveMode;
     use crate::monitor::pid::PressureLevel;
     use crate::monitor::special_locations::{
         SpecialKind, SpecialLocation, SpecialLocationRegistry,
     };
     use crate::platform::pal::MemoryInfo;
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification};
-    use crate::scanner::scoring::{DecisionAction, DecisionOutcome, EvidenceLedger, ScoreFactors};
+    use crate::scanner::scoring::{
+        DecisionAction, DecisionOutcome, EvidenceLedger, ScoreFactors, ScoringInputs,
+    };
     use std::path::Path;
     use std::time::Duration;
 
@@ -2631,11 +5436,13 @@ mod tests {
                 size: 0.0,
                 structure: 0.0,
                 pressure_multiplier: 1.0,
+                regeneration_cost: 1.0,
             },
             vetoed: false,
             veto_reason: None,
             classification: ArtifactClassification::unknown(),
             size_bytes: 1,
+            disk_bytes: 1,
             age: Duration::from_secs(60),
             decision: DecisionOutcome {
                 action: DecisionAction::Delete,
@@ -2649,6 +5456,7 @@ mod tests {
                 terms: Vec::new(),
                 summary: "test".to_string(),
             },
+            inputs: ScoringInputs::default(),
         }
     }
 
@@ -2668,6 +5476,7 @@ mod tests {
             urgency: 0.7,
             pressure_level: PressureLevel::Orange,
             max_delete_batch: 10,
+            mode: ScanMode::Full,
             config_update: None,
         };
         assert_eq!(request.paths.len(), 2);
@@ -2682,6 +5491,27 @@ mod tests {
         assert_eq!(args.watchdog_sec, 0);
     }
 
+    #[test]
+    fn stall_threshold_scales_with_poll_interval_above_floor() {
+        let mut config = Config::default();
+        config.pressure.poll_interval_ms = 1_000;
+        config.watchdog.stall_intervals = 60;
+        assert_eq!(stall_threshold(&config), Duration::from_secs(60));
+
+        config.watchdog.stall_intervals = 5;
+        assert_eq!(stall_threshold(&config), MIN_STALL_THRESHOLD);
+    }
+
+    #[test]
+    fn watchdog_restarts_outside_the_window_are_forgotten() {
+        let now = 1_000_000;
+        assert!(recent_watchdog_restarts(None, now).is_empty());
+        assert_eq!(
+            recent_watchdog_restarts(Some("996000, 999000,junk,999999"), now),
+            vec![999_000, 999_999]
+        );
+    }
+
     #[test]
     fn scanner_and_executor_channel_integration() {
         // Test that scanner → executor channel works correctly.
@@ -2694,6 +5524,7 @@ mod tests {
             urgency: 0.5,
             pressure_level: PressureLevel::Orange,
             max_delete_batch: 10,
+            mode: ScanMode::Full,
             config_update: None,
         };
         // With capacity 0, send blocks until recv is called.
@@ -2773,6 +5604,7 @@ mod tests {
             urgency,
             pressure_level: PressureLevel::Critical,
             max_delete_batch: 40,
+            mode: ScanMode::Full,
             config_update: None,
         };
 
@@ -2797,6 +5629,7 @@ mod tests {
             urgency,
             pressure_level: PressureLevel::Critical,
             max_delete_batch: 40,
+            mode: ScanMode::Full,
             config_update: None,
         };
 
@@ -2863,6 +5696,7 @@ mod tests {
             urgency: 0.9,
             pressure_level: PressureLevel::Critical,
             max_delete_batch: 40,
+            mode: ScanMode::Full,
             config_update: None,
         };
         for _ in 0..SCANNER_CHANNEL_CAP {
@@ -2881,6 +5715,7 @@ mod tests {
             urgency: 1.0,
             pressure_level: PressureLevel::Critical,
             max_delete_batch: 1,
+            mode: ScanMode::Full,
             config_update: None,
         };
         let (del_tx, del_rx) = bounded::<DeletionBatch>(4);
@@ -2906,6 +5741,7 @@ mod tests {
             urgency: 1.0,
             pressure_level: PressureLevel::Critical,
             max_delete_batch: 1,
+            mode: ScanMode::Full,
             config_update: None,
         };
         let (del_tx, del_rx) = bounded::<DeletionBatch>(1);
@@ -3240,6 +6076,44 @@ mod tests {
         assert_eq!(tracker.history[&path].cycle_count, 3);
     }
 
+    #[test]
+    fn cleanup_cooldown_counts_down_and_escalates_ballast() {
+        let last_pass = Instant::now();
+        let cooldown = Duration::from_mins(10);
+        assert_eq!(
+            cleanup_cooldown_remaining(last_pass, cooldown, last_pass + Duration::from_mins(3)),
+            Some(Duration::from_mins(7))
+        );
+        assert_eq!(
+            cleanup_cooldown_remaining(last_pass, cooldown, last_pass + cooldown),
+            None
+        );
+        assert_eq!(
+            cleanup_cooldown_remaining(last_pass, Duration::ZERO, last_pass),
+            None,
+            "a zero cooldown never holds cleanup off"
+        );
+
+        assert_eq!(
+            escalated_level(PressureLevel::Yellow),
+            PressureLevel::Orange
+        );
+        assert_eq!(escalated_level(PressureLevel::Red), PressureLevel::Critical);
+    }
+
+    #[test]
+    fn scheduled_deep_scans_keep_routine_scans_shallow_below_red() {
+        assert_eq!(
+            routine_scan_mode(false, PressureLevel::Green),
+            ScanMode::Full
+        );
+        assert_eq!(
+            routine_scan_mode(true, PressureLevel::Orange),
+            ScanMode::Shallow
+        );
+        assert_eq!(routine_scan_mode(true, PressureLevel::Red), ScanMode::Full);
+    }
+
     #[test]
     fn test_swap_thrash_logic_correct_behavior() {
         use crate::platform::pal::MemoryInfo;
@@ -3283,4 +6157,126 @@ mod tests {
             "Low RAM with high zram swap is genuine thrash risk"
         );
     }
+
+    #[test]
+    fn watched_scan_serves_verified_roots_until_a_full_walk_is_due() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().canonicalize().unwrap();
+        let (walked, unwalked) = (base.join("a"), base.join("b"));
+        let proj = walked.join("proj");
+        std::fs::create_dir_all(proj.join("target")).unwrap();
+        std::fs::create_dir_all(walked.join("misc")).unwrap();
+        std::fs::create_dir_all(&unwalked).unwrap();
+        let roots = vec![walked.clone(), unwalked];
+        let mut watched = WatchedScan::new(roots.clone(), TreeWatcher::new(&roots).unwrap());
+        let hour = Duration::from_secs(3600);
+        assert!(!watched.can_serve(std::slice::from_ref(&walked), hour));
+
+        let walked_roots = vec![walked.clone()];
+        let dir_walker = DirectoryWalker::new(
+            WalkerConfig {
+                root_paths: walked_roots.clone(),
+                max_depth: 10,
+                follow_symlinks: false,
+                cross_devices: false,
+                parallelism: 1,
+                excluded_paths: HashSet::new(),
+            },
+            ProtectionRegistry::marker_only(),
+        );
+        watched.begin_walk(&walked_roots);
+        for entry in dir_walker.walk().unwrap() {
+            watched.remember(&entry);
+        }
+        watched.finish_walk(&walked_roots, true);
+
+        assert!(watched.can_serve(std::slice::from_ref(&walked), hour));
+        assert!(watched.can_serve(std::slice::from_ref(&proj), hour));
+        assert!(!watched.can_serve(&roots, hour), "b was never walked");
+        assert!(!watched.can_serve(std::slice::from_ref(&walked), Duration::ZERO));
+        let replayed: HashSet<PathBuf> = watched
+            .replay(std::slice::from_ref(&proj))
+            .iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(replayed, HashSet::from([proj.clone(), proj.join("target")]));
+
+        watched.finish_walk(std::slice::from_ref(&proj), false);
+        assert!(!watched.can_serve(std::slice::from_ref(&walked), hour));
+    }
+
+    /// A one-shot daemon whose state lives under `dir` and whose scan root is
+    /// `dir`, with one expired tree in `dir`'s quarantine.
+    fn daemon_with_expired_quarantine(
+        dir: &Path,
+        configure: impl FnOnce(&mut Config),
+    ) -> (MonitoringDaemon, PathBuf) {
+        let mut config = Config::default();
+        config.paths.state_file = dir.join("state/state.json");
+        config.paths.sqlite_db = dir.join("state/activity.sqlite3");
+        config.paths.jsonl_log = dir.join("state/activity.jsonl");
+        config.paths.ballast_dir = dir.join("state/ballast");
+        config.scanner.root_paths = vec![dir.to_path_buf()];
+        configure(&mut config);
+
+        let target = dir.join("proj/target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("out.o"), b"object").unwrap();
+        let root = dir.join(quarantine::QUARANTINE_DIRNAME);
+        quarantine::quarantine_in(
+            &root,
+            &target,
+            ArtifactCategory::RustTarget,
+            6,
+            Duration::ZERO,
+            None,
+        )
+        .unwrap();
+
+        let args = DaemonArgs {
+            once: true,
+            ..DaemonArgs::default()
+        };
+        (MonitoringDaemon::init(config, &args).unwrap(), root)
+    }
+
+    #[test]
+    fn observe_mode_leaves_expired_quarantine_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut daemon, root) = daemon_with_expired_quarantine(dir.path(), |config| {
+            config.policy.initial_mode = ActiveMode::Observe;
+        });
+        daemon.check_quarantine(PressureLevel::Red);
+        let entries = quarantine::read_index(&root).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].quarantined_path.exists());
+
+        let dir = tempfile::tempdir().unwrap();
+        let (mut daemon, root) = daemon_with_expired_quarantine(dir.path(), |config| {
+            config.policy.initial_mode = ActiveMode::Enforce;
+            config.policy.burn_in_observe_hours = 0;
+            config.policy.burn_in_canary_hours = 0;
+        });
+        daemon.check_quarantine(PressureLevel::Red);
+        assert!(quarantine::read_index(&root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn burn_in_holds_the_quarantine_of_a_fresh_enforcing_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut daemon, root) = daemon_with_expired_quarantine(dir.path(), |config| {
+            config.policy.initial_mode = ActiveMode::Enforce;
+        });
+        assert!(
+            daemon
+                .policy_engine
+                .lock()
+                .burn_in()
+                .is_some_and(BurnIn::is_active)
+        );
+        daemon.check_quarantine(PressureLevel::Critical);
+        let entries = quarantine::read_index(&root).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].quarantined_path.exists());
+    }
 }
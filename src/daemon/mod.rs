@@ -1,6 +1,14 @@
 //! Daemon subsystem: main monitoring loop, service integration, signal handling,
-//! self-monitoring, and multi-channel notifications.
+//! self-monitoring, crash reports, multi-channel notifications, core dump
+//! cleanup, and the CLI control socket.
 
+#[cfg(feature = "daemon")]
+pub mod control;
+pub mod coredump;
+#[cfg(feature = "daemon")]
+pub mod crash;
+#[cfg(feature = "daemon")]
+pub mod dbus;
 #[cfg(feature = "daemon")]
 pub mod loop_main;
 pub mod notifications;
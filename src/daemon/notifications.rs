@@ -7,17 +7,21 @@
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::container;
+use crate::logger::diag;
 use crate::monitor::pid::PressureLevel;
+use crate::scanner::object_store::quote;
 
 // ──────────────────── notification level ────────────────────
 
@@ -26,7 +30,9 @@ use crate::monitor::pid::PressureLevel;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum NotificationLevel {
+    #[serde(alias = "green")]
     Info,
+    #[serde(alias = "yellow")]
     Warning,
     Orange,
     Red,
@@ -47,6 +53,25 @@ impl NotificationLevel {
     }
 }
 
+impl FromStr for NotificationLevel {
+    type Err = String;
+
+    /// Parse a level name; pressure names `green` and `yellow` are accepted
+    /// for `info` and `warning`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" | "green" => Ok(Self::Info),
+            "warning" | "yellow" => Ok(Self::Warning),
+            "orange" => Ok(Self::Orange),
+            "red" => Ok(Self::Red),
+            "critical" => Ok(Self::Critical),
+            other => Err(format!(
+                "unknown notification level '{other}'; expected info, warning, orange, red, or critical"
+            )),
+        }
+    }
+}
+
 impl fmt::Display for NotificationLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -98,13 +123,106 @@ pub enum NotificationEvent {
         reason: String,
         uptime_secs: u64,
     },
+    /// Crash dumps removed by the core dump policy.
+    CoreDumpsCleaned {
+        items_deleted: usize,
+        bytes_freed: u64,
+        /// Crashed binaries with counts, e.g. `"node x2, python3"`.
+        binaries: String,
+    },
+    /// Ballast files that failed the periodic integrity check.
+    BallastCorrupted {
+        mount: String,
+        files_corrupted: usize,
+        files_repaired: usize,
+    },
+    /// A disk behind watched mounts reported degraded or failing health.
+    DiskHealth {
+        mount: String,
+        disk: String,
+        /// `degraded` or `failing`.
+        verdict: String,
+        detail: String,
+        /// One of the disk's mounts is below the green threshold.
+        filling: bool,
+    },
+    /// A watched mount went read-only; cleanup on it is suspended.
+    MountReadOnly {
+        mount: String,
+    },
+    /// A config change on probation was undone after a policy fallback.
+    ConfigReverted {
+        /// Command that made the change (`tune` or `config set`).
+        source: String,
+        /// Enforcement keys that were rolled back, comma-separated.
+        keys: String,
+        reason: String,
+    },
     Error {
         code: String,
         message: String,
     },
 }
 
+/// Every `type_key`, for validating `notifications.rules`.
+pub const EVENT_TYPES: &[&str] = &[
+    "pressure_changed",
+    "predictive_warning",
+    "cleanup_completed",
+    "ballast_released",
+    "ballast_replenished",
+    "daemon_started",
+    "daemon_stopped",
+    "core_dumps_cleaned",
+    "ballast_corrupted",
+    "disk_health",
+    "mount_readonly",
+    "config_reverted",
+    "error",
+];
+
+/// Channel names accepted in `notifications.channels` and rule `channels`.
+pub const CHANNEL_NAMES: &[&str] = &["desktop", "file", "journal", "webhook"];
+
+/// The parts of an event that notification rules match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventKey<'a> {
+    pub event: &'a str,
+    pub level: NotificationLevel,
+    pub mount: Option<&'a str>,
+}
+
 impl NotificationEvent {
+    /// Type, built-in level, and mount, for rule matching.
+    #[must_use]
+    pub fn key(&self) -> EventKey<'_> {
+        EventKey {
+            event: self.type_key(),
+            level: self.level(),
+            mount: self.mount(),
+        }
+    }
+
+    /// The mount this event concerns, if any.
+    #[must_use]
+    pub fn mount(&self) -> Option<&str> {
+        match self {
+            Self::PressureChanged { mount, .. }
+            | Self::PredictiveWarning { mount, .. }
+            | Self::CleanupCompleted { mount, .. }
+            | Self::BallastReleased { mount, .. }
+            | Self::BallastReplenished { mount, .. }
+            | Self::BallastCorrupted { mount, .. }
+            | Self::DiskHealth { mount, .. }
+            | Self::MountReadOnly { mount } => Some(mount),
+            Self::DaemonStarted { .. }
+            | Self::DaemonStopped { .. }
+            | Self::CoreDumpsCleaned { .. }
+            | Self::ConfigReverted { .. }
+            | Self::Error { .. } => None,
+        }
+    }
+
     /// The severity level of this event (for min-level filtering).
     #[must_use]
     pub fn level(&self) -> NotificationLevel {
@@ -174,7 +292,22 @@ impl NotificationEvent {
                 }
             }
 
-            Self::BallastReleased { .. } => NotificationLevel::Orange,
+            Self::BallastReleased { .. } | Self::ConfigReverted { .. } => NotificationLevel::Orange,
+
+            Self::CoreDumpsCleaned { .. } | Self::BallastCorrupted { .. } => {
+                NotificationLevel::Warning
+            }
+
+            // A disk that is both filling and failing is louder than either.
+            Self::DiskHealth {
+                verdict, filling, ..
+            } => match (verdict.as_str(), filling) {
+                ("failing", true) => NotificationLevel::Red,
+                ("failing", false) | (_, true) => NotificationLevel::Orange,
+                _ => NotificationLevel::Warning,
+            },
+
+            Self::MountReadOnly { .. } => NotificationLevel::Critical,
 
             Self::Error { .. } => NotificationLevel::Red,
         }
@@ -191,6 +324,11 @@ impl NotificationEvent {
             Self::BallastReplenished { .. } => "ballast_replenished",
             Self::DaemonStarted { .. } => "daemon_started",
             Self::DaemonStopped { .. } => "daemon_stopped",
+            Self::CoreDumpsCleaned { .. } => "core_dumps_cleaned",
+            Self::BallastCorrupted { .. } => "ballast_corrupted",
+            Self::DiskHealth { .. } => "disk_health",
+            Self::MountReadOnly { .. } => "mount_readonly",
+            Self::ConfigReverted { .. } => "config_reverted",
             Self::Error { .. } => "error",
         }
     }
@@ -247,6 +385,39 @@ impl NotificationEvent {
                 let minutes = (uptime_secs % 3600) / 60;
                 format!("sbh stopped ({reason}) after {hours}h {minutes}m")
             }
+            Self::CoreDumpsCleaned {
+                items_deleted,
+                bytes_freed,
+                binaries,
+            } => {
+                let gb = *bytes_freed as f64 / 1_073_741_824.0;
+                format!("Removed {items_deleted} core dumps ({gb:.1} GB); crashed: {binaries}")
+            }
+            Self::BallastCorrupted {
+                mount,
+                files_corrupted,
+                files_repaired,
+            } => format!(
+                "{files_corrupted} corrupted ballast files on {mount}; {files_repaired} re-created"
+            ),
+            Self::DiskHealth {
+                mount,
+                disk,
+                verdict,
+                detail,
+                filling,
+            } => {
+                let filling = if *filling { " and is low on space" } else { "" };
+                format!("Disk {disk} behind {mount} is {verdict} ({detail}){filling}")
+            }
+            Self::MountReadOnly { mount } => {
+                format!("{mount} is mounted read-only; cleanup there is suspended")
+            }
+            Self::ConfigReverted {
+                source,
+                keys,
+                reason,
+            } => format!("Reverted `sbh {source}` changes ({keys}) after {reason}"),
             Self::Error { code, message } => format!("[{code}] {message}"),
         }
     }
@@ -269,6 +440,8 @@ pub struct NotificationConfig {
     pub webhook: WebhookConfig,
     pub file: FileConfig,
     pub journal: JournalConfig,
+    /// Reclassify or mute events; the first matching rule per channel wins.
+    pub rules: Vec<NotificationRule>,
 }
 
 impl Default for NotificationConfig {
@@ -281,10 +454,63 @@ impl Default for NotificationConfig {
             webhook: WebhookConfig::default(),
             file: FileConfig::default(),
             journal: JournalConfig::default(),
+            rules: Vec::new(),
         }
     }
 }
 
+/// One `[[notifications.rules]]` entry. Unset match fields match anything;
+/// a rule with neither `mute` nor `set_level` passes the event through
+/// unchanged, shielding it from later rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NotificationRule {
+    /// Event type (`type_key`), or `*` for any.
+    pub event: String,
+    /// Match only events whose built-in level is this.
+    pub level: Option<NotificationLevel>,
+    /// Match only events about this mount point.
+    pub mount: Option<String>,
+    /// Channels the rule applies to; empty means all.
+    pub channels: Vec<String>,
+    /// Drop matching events on these channels.
+    pub mute: bool,
+    /// Deliver matching events at this level instead.
+    pub set_level: Option<NotificationLevel>,
+}
+
+impl NotificationRule {
+    /// Whether this rule applies to `key` on `channel`.
+    #[must_use]
+    pub fn matches(&self, key: &EventKey<'_>, channel: &str) -> bool {
+        (self.event == "*" || self.event == key.event)
+            && self.level.is_none_or(|level| level == key.level)
+            && self
+                .mount
+                .as_deref()
+                .is_none_or(|mount| key.mount.is_some_and(|m| Path::new(m) == Path::new(mount)))
+            && (self.channels.is_empty() || self.channels.iter().any(|c| c == channel))
+    }
+}
+
+/// What happens to an event on one channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteOutcome {
+    Muted,
+    /// The (possibly reclassified) level is under the channel's `min_level`.
+    BelowMinLevel(NotificationLevel),
+    Delivered(NotificationLevel),
+}
+
+/// How one active channel handles an event, and which rule decided it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route {
+    pub channel: &'static str,
+    /// Index into `notifications.rules` of the rule that matched.
+    pub rule: Option<usize>,
+    pub outcome: RouteOutcome,
+}
+
 /// Desktop notification settings (notify-send on Linux, osascript on macOS).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default)]
@@ -309,8 +535,17 @@ pub struct WebhookConfig {
     pub enabled: bool,
     pub url: String,
     pub min_level: NotificationLevel,
-    /// Template string with `${MOUNT}`, `${FREE_PCT}`, `${LEVEL}`, `${SUMMARY}` placeholders.
+    /// Template string with `${EVENT}`, `${MOUNT}`, `${FREE_PCT}`, `${LEVEL}`,
+    /// `${SUMMARY}` placeholders.
     pub template: String,
+    /// Extra request headers, such as `Authorization`. A `Content-Type`
+    /// here replaces the default `application/json`.
+    pub headers: BTreeMap<String, String>,
+    /// Further attempts after a connection failure, timeout, 408, 429, or
+    /// 5xx response.
+    pub retries: u32,
+    /// Wait before the first retry; each later retry waits twice as long.
+    pub retry_backoff_ms: u64,
 }
 
 impl Default for WebhookConfig {
@@ -320,6 +555,9 @@ impl Default for WebhookConfig {
             url: String::new(),
             min_level: NotificationLevel::Red,
             template: r#"{"text": "sbh: ${SUMMARY}"}"#.to_string(),
+            headers: BTreeMap::new(),
+            retries: 3,
+            retry_backoff_ms: 1_000,
         }
     }
 }
@@ -333,6 +571,11 @@ pub struct FileConfig {
 
 impl Default for FileConfig {
     fn default() -> Self {
+        if container::is_enabled() {
+            return Self {
+                path: Path::new(container::CONTAINER_DATA_DIR).join("notifications.jsonl"),
+            };
+        }
         let home = std::env::var_os("HOME").map_or_else(|| PathBuf::from("/tmp"), PathBuf::from);
         Self {
             path: home
@@ -376,7 +619,13 @@ struct NotificationRecord {
 /// A notification channel that can dispatch events.
 trait Channel: Send + Sync {
     fn name(&self) -> &'static str;
-    fn send(&self, event: &NotificationEvent);
+    /// Events below this level are not sent.
+    fn min_level(&self) -> NotificationLevel {
+        NotificationLevel::Info
+    }
+    /// Send `event`, presented at `level` (its own level unless a rule
+    /// reclassified it).
+    fn send(&self, event: &NotificationEvent, level: NotificationLevel);
 }
 
 // ──── Desktop (notify-send / osascript) ────
@@ -398,13 +647,17 @@ impl Channel for DesktopChannel {
         "desktop"
     }
 
-    fn send(&self, event: &NotificationEvent) {
-        if event.level() < self.min_level {
+    fn min_level(&self) -> NotificationLevel {
+        self.min_level
+    }
+
+    fn send(&self, event: &NotificationEvent, level: NotificationLevel) {
+        if level < self.min_level {
             return;
         }
 
         let summary = event.summary();
-        let urgency = match event.level() {
+        let urgency = match level {
             NotificationLevel::Critical | NotificationLevel::Red => "critical",
             NotificationLevel::Orange | NotificationLevel::Warning => "normal",
             NotificationLevel::Info => "low",
@@ -470,10 +723,10 @@ impl Channel for FileChannel {
         "file"
     }
 
-    fn send(&self, event: &NotificationEvent) {
+    fn send(&self, event: &NotificationEvent, level: NotificationLevel) {
         let record = NotificationRecord {
             ts: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-            level: event.level(),
+            level,
             summary: event.summary(),
             event: event.clone(),
         };
@@ -523,12 +776,15 @@ impl Channel for JournalChannel {
         "journal"
     }
 
-    fn send(&self, event: &NotificationEvent) {
-        if event.level() < self.min_level {
+    fn min_level(&self) -> NotificationLevel {
+        self.min_level
+    }
+
+    fn send(&self, event: &NotificationEvent, level: NotificationLevel) {
+        if level < self.min_level {
             return;
         }
 
-        let level = event.level();
         let summary = event.summary();
 
         // systemd captures stderr and annotates with PRIORITY via SyslogIdentifier.
@@ -547,10 +803,25 @@ impl Channel for JournalChannel {
 
 // ──── Webhook (HTTP POST via curl) ────
 
+/// Per-attempt limit on a webhook request.
+const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+
+/// Retry backoff stops doubling after this many retries.
+const WEBHOOK_MAX_BACKOFF_DOUBLINGS: u32 = 6;
+
 struct WebhookChannel {
     url: String,
     min_level: NotificationLevel,
     template: String,
+    headers: BTreeMap<String, String>,
+    retries: u32,
+    retry_backoff: Duration,
+}
+
+/// Whether a webhook attempt is worth repeating. `None` means no HTTP
+/// response at all (connection refused, DNS failure, timeout).
+fn webhook_retryable(status: Option<u16>) -> bool {
+    status.is_none_or(|code| code == 408 || code == 429 || code >= 500)
 }
 
 impl WebhookChannel {
@@ -559,10 +830,63 @@ impl WebhookChannel {
             url: config.url.clone(),
             min_level: config.min_level,
             template: config.template.clone(),
+            headers: config.headers.clone(),
+            retries: config.retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
         }
     }
 
-    fn render_body(&self, event: &NotificationEvent) -> String {
+    /// Wait before retry number `retry` (0-based).
+    fn retry_delay(&self, retry: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(1 << retry.min(WEBHOOK_MAX_BACKOFF_DOUBLINGS))
+    }
+
+    /// `curl` arguments for one POST; curl prints only the HTTP status code
+    /// (`000` without a response). The URL, headers, and body come from
+    /// [`Self::curl_config`] on stdin, so secrets in them never reach the
+    /// process list.
+    fn curl_args() -> Vec<String> {
+        [
+            "--silent",
+            "--output",
+            "/dev/null",
+            "--write-out",
+            "%{http_code}",
+            "--max-time",
+            &WEBHOOK_TIMEOUT_SECS.to_string(),
+            "--config",
+            "-",
+        ]
+        .map(str::to_string)
+        .to_vec()
+    }
+
+    /// curl `--config` text carrying the headers, body, and URL of one POST
+    /// of `body`.
+    fn curl_config(&self, body: &str) -> String {
+        use std::fmt::Write as _;
+        let mut config = String::new();
+        if !self
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("content-type"))
+        {
+            config.push_str("header = \"Content-Type: application/json\"\n");
+        }
+        for (name, value) in &self.headers {
+            let _ = writeln!(
+                config,
+                "header = \"{}\"",
+                quote(&format!("{name}: {value}"))
+            );
+        }
+        let _ = writeln!(config, "data-raw = \"{}\"", quote(body));
+        let _ = writeln!(config, "url = \"{}\"", quote(&self.url));
+        config
+    }
+
+    fn render_body(&self, event: &NotificationEvent, level: NotificationLevel) -> String {
         // JSON-escape values to prevent injection in webhook payloads.
         fn json_escape(s: &str) -> String {
             let mut out = String::with_capacity(s.len());
@@ -587,20 +911,14 @@ impl WebhookChannel {
         }
 
         let summary = event.summary();
-        let level = event.level().to_string();
+        let level = level.to_string();
+        let event_type = event.type_key();
 
         // Extract mount and free_pct from relevant events, or use defaults.
-        let (mount, free_pct) = match event {
-            NotificationEvent::PressureChanged {
-                mount, free_pct, ..
-            } => (mount.clone(), format!("{free_pct:.1}")),
-            NotificationEvent::PredictiveWarning { mount, .. }
-            | NotificationEvent::CleanupCompleted { mount, .. }
-            | NotificationEvent::BallastReleased { mount, .. }
-            | NotificationEvent::BallastReplenished { mount, .. } => {
-                (mount.clone(), "N/A".to_string())
-            }
-            _ => ("N/A".to_string(), "N/A".to_string()),
+        let mount = event.mount().unwrap_or("N/A").to_string();
+        let free_pct = match event {
+            NotificationEvent::PressureChanged { free_pct, .. } => format!("{free_pct:.1}"),
+            _ => "N/A".to_string(),
         };
 
         let summary_esc = json_escape(&summary);
@@ -617,6 +935,7 @@ impl WebhookChannel {
             if let Some(end) = rest.find('}') {
                 let key = &rest[..end];
                 match key {
+                    "EVENT" => result.push_str(event_type),
                     "SUMMARY" => result.push_str(&summary_esc),
                     "LEVEL" => result.push_str(&level_esc),
                     "MOUNT" => result.push_str(&mount_esc),
@@ -645,8 +964,12 @@ impl Channel for WebhookChannel {
         "webhook"
     }
 
-    fn send(&self, event: &NotificationEvent) {
-        if event.level() < self.min_level {
+    fn min_level(&self) -> NotificationLevel {
+        self.min_level
+    }
+
+    fn send(&self, event: &NotificationEvent, level: NotificationLevel) {
+        if level < self.min_level {
             return;
         }
 
@@ -654,31 +977,61 @@ impl Channel for WebhookChannel {
             return;
         }
 
-        let body = self.render_body(event);
-
-        // Fire-and-forget via curl. Timeout of 5 seconds to avoid blocking.
-        // Use "--" to prevent URL from being interpreted as a curl option.
-        // Reap the child in a detached thread to prevent zombie accumulation.
-        if let Ok(child) = Command::new("curl")
-            .arg("--silent")
-            .arg("--max-time")
-            .arg("5")
-            .arg("--header")
-            .arg("Content-Type: application/json")
-            .arg("--data-raw")
-            .arg(&body)
-            .arg("--")
-            .arg(&self.url)
-            .spawn()
-        {
-            std::thread::spawn(move || {
-                let mut c = child;
-                let _ = c.wait();
-            });
-        }
+        let config = self.curl_config(&self.render_body(event, level));
+        let delays: Vec<Duration> = (0..self.retries)
+            .map(|retry| self.retry_delay(retry))
+            .collect();
+
+        // Deliver on a detached thread so retries and backoff never hold up
+        // the monitoring loop.
+        std::thread::spawn(move || {
+            let mut attempts = 0;
+            let status = loop {
+                attempts += 1;
+                // curl missing or unrunnable: retrying cannot help.
+                let Ok(output) = run_curl(&config) else {
+                    return;
+                };
+                let status = std::str::from_utf8(&output.stdout)
+                    .ok()
+                    .and_then(|code| code.trim().parse::<u16>().ok())
+                    .filter(|code| *code != 0);
+                match delays.get(attempts - 1) {
+                    Some(delay) if webhook_retryable(status) => std::thread::sleep(*delay),
+                    _ => break status,
+                }
+            };
+            if status.is_none_or(|code| code >= 400) {
+                diag::warn(
+                    "notify",
+                    format_args!(
+                        "webhook delivery failed after {attempts} attempt(s): {}",
+                        status.map_or_else(
+                            || "no response".to_string(),
+                            |code| format!("HTTP {code}")
+                        )
+                    ),
+                );
+            }
+        });
     }
 }
 
+/// Run curl with `config` on stdin and collect its output.
+fn run_curl(config: &str) -> std::io::Result<std::process::Output> {
+    let mut child = Command::new("curl")
+        .args(WebhookChannel::curl_args())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Dropping stdin after the write is curl's end of config.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(config.as_bytes());
+    }
+    child.wait_with_output()
+}
+
 // ──────────────────── notification manager ────────────────────
 
 /// Coordinates dispatching notification events to all enabled channels.
@@ -693,6 +1046,9 @@ pub struct NotificationManager {
     /// Prevents a low-priority event type from blocking unrelated higher-priority ones.
     last_send_by_type: HashMap<&'static str, Instant>,
     min_interval: Duration,
+    rules: Vec<NotificationRule>,
+    /// Drop everything while a maintenance window mutes alerts.
+    silenced: bool,
 }
 
 impl NotificationManager {
@@ -705,6 +1061,8 @@ impl NotificationManager {
                 enabled: false,
                 last_send_by_type: HashMap::new(),
                 min_interval: Duration::ZERO,
+                rules: Vec::new(),
+                silenced: false,
             };
         }
 
@@ -735,6 +1093,8 @@ impl NotificationManager {
             enabled: true,
             last_send_by_type: HashMap::new(),
             min_interval: Duration::from_secs(config.min_notify_interval_secs),
+            rules: config.rules.clone(),
+            silenced: false,
         }
     }
 
@@ -744,8 +1104,15 @@ impl NotificationManager {
     /// a burst window where previously throttled events can fire immediately.
     pub fn update_config(&mut self, config: &NotificationConfig) {
         let throttle_state = std::mem::take(&mut self.last_send_by_type);
+        let silenced = self.silenced;
         *self = Self::from_config(config);
         self.last_send_by_type = throttle_state;
+        self.silenced = silenced;
+    }
+
+    /// Suppress (or resume) all notifications, e.g. for `sbh pause --mute-alerts`.
+    pub fn set_silenced(&mut self, silenced: bool) {
+        self.silenced = silenced;
     }
 
     /// Create a disabled (no-op) manager.
@@ -756,6 +1123,8 @@ impl NotificationManager {
             enabled: false,
             last_send_by_type: HashMap::new(),
             min_interval: Duration::ZERO,
+            rules: Vec::new(),
+            silenced: false,
         }
     }
 
@@ -765,13 +1134,26 @@ impl NotificationManager {
     /// 60s). Each event type has its own throttle window so a low-severity cleanup
     /// notification cannot block an unrelated pressure warning.
     /// Red and Critical events bypass throttling to ensure timely alerts.
+    /// Rules apply first, so a muted event does not start a throttle window
+    /// and one raised to Red bypasses it.
     pub fn notify(&mut self, event: &NotificationEvent) {
-        if !self.enabled {
+        if !self.enabled || self.silenced {
             return;
         }
 
+        let routes = self.routes(&event.key());
+        let Some(level) = routes
+            .iter()
+            .filter_map(|route| match route.outcome {
+                RouteOutcome::Muted => None,
+                RouteOutcome::BelowMinLevel(level) | RouteOutcome::Delivered(level) => Some(level),
+            })
+            .max()
+        else {
+            return;
+        };
+
         // Throttle: skip if we sent this event type recently, unless Red/Critical.
-        let level = event.level();
         let bypass_throttle = level >= NotificationLevel::Red;
         let type_key = event.type_key();
         if !bypass_throttle
@@ -784,11 +1166,47 @@ impl NotificationManager {
 
         self.last_send_by_type.insert(type_key, Instant::now());
 
-        for channel in &self.channels {
-            channel.send(event);
+        for (channel, route) in self.channels.iter().zip(&routes) {
+            if let RouteOutcome::Delivered(level) = route.outcome {
+                channel.send(event, level);
+            }
         }
     }
 
+    /// How each active channel would handle an event with `key`.
+    #[must_use]
+    pub fn routes(&self, key: &EventKey<'_>) -> Vec<Route> {
+        self.channels
+            .iter()
+            .map(|channel| {
+                let name = channel.name();
+                let matched = self
+                    .rules
+                    .iter()
+                    .enumerate()
+                    .find(|(_, rule)| rule.matches(key, name));
+                let outcome = match matched {
+                    Some((_, rule)) if rule.mute => RouteOutcome::Muted,
+                    _ => {
+                        let level = matched
+                            .and_then(|(_, rule)| rule.set_level)
+                            .unwrap_or(key.level);
+                        if level < channel.min_level() {
+                            RouteOutcome::BelowMinLevel(level)
+                        } else {
+                            RouteOutcome::Delivered(level)
+                        }
+                    }
+                };
+                Route {
+                    channel: name,
+                    rule: matched.map(|(index, _)| index),
+                    outcome,
+                }
+            })
+            .collect()
+    }
+
     /// Number of active channels.
     #[must_use]
     pub fn channel_count(&self) -> usize {
@@ -987,6 +1405,36 @@ mod tests {
         assert!(summary.contains("5.0 GB"));
     }
 
+    #[test]
+    fn event_level_disk_health_escalates_when_filling() {
+        let event = |verdict: &str, filling| NotificationEvent::DiskHealth {
+            mount: "/data".to_string(),
+            disk: "sda".to_string(),
+            verdict: verdict.to_string(),
+            detail: "12 reallocated sectors".to_string(),
+            filling,
+        };
+        assert_eq!(event("degraded", false).level(), NotificationLevel::Warning);
+        assert_eq!(event("degraded", true).level(), NotificationLevel::Orange);
+        assert_eq!(event("failing", false).level(), NotificationLevel::Orange);
+        assert_eq!(event("failing", true).level(), NotificationLevel::Red);
+        assert!(
+            event("failing", true)
+                .summary()
+                .contains("sda behind /data is failing")
+        );
+    }
+
+    #[test]
+    fn event_mount_readonly_is_critical() {
+        let event = NotificationEvent::MountReadOnly {
+            mount: "/data".to_string(),
+        };
+        assert_eq!(event.level(), NotificationLevel::Critical);
+        assert_eq!(event.type_key(), "mount_readonly");
+        assert!(event.summary().starts_with("/data is mounted read-only"));
+    }
+
     #[test]
     fn default_config_has_journal_and_file() {
         let config = NotificationConfig::default();
@@ -1069,8 +1517,8 @@ mod tests {
             volumes_monitored: 2,
         };
 
-        channel.send(&event);
-        channel.send(&event);
+        channel.send(&event, event.level());
+        channel.send(&event, event.level());
 
         let content = fs::read_to_string(&path).unwrap();
         let lines: Vec<&str> = content.lines().collect();
@@ -1102,7 +1550,7 @@ mod tests {
             message: "test error".to_string(),
         };
 
-        channel.send(&event);
+        channel.send(&event, event.level());
         assert!(path.exists());
     }
 
@@ -1119,22 +1567,23 @@ mod tests {
             version: "0.1.0".to_string(),
             volumes_monitored: 1,
         };
-        channel.send(&info_event); // Should be silently dropped.
+        channel.send(&info_event, info_event.level()); // Should be silently dropped.
 
         let red_event = NotificationEvent::Error {
             code: "SBH-TEST".to_string(),
             message: "test".to_string(),
         };
-        channel.send(&red_event); // Should output to stderr.
+        channel.send(&red_event, red_event.level()); // Should output to stderr.
     }
 
     #[test]
     fn webhook_channel_renders_template() {
-        let channel = WebhookChannel {
+        let channel = WebhookChannel::new(&WebhookConfig {
             url: "https://hooks.example.com/test".to_string(),
             min_level: NotificationLevel::Red,
             template: r#"{"text": "sbh: ${SUMMARY}", "level": "${LEVEL}", "mount": "${MOUNT}", "free": "${FREE_PCT}"}"#.to_string(),
-        };
+            ..WebhookConfig::default()
+        });
 
         let event = NotificationEvent::PressureChanged {
             from: "green".to_string(),
@@ -1143,7 +1592,7 @@ mod tests {
             free_pct: 4.5,
         };
 
-        let body = channel.render_body(&event);
+        let body = channel.render_body(&event, event.level());
         assert!(body.contains("red"));
         assert!(body.contains("/data"));
         assert!(body.contains("4.5"));
@@ -1152,12 +1601,13 @@ mod tests {
 
     #[test]
     fn webhook_channel_prevents_recursive_injection() {
-        let channel = WebhookChannel {
+        let channel = WebhookChannel::new(&WebhookConfig {
             url: "https://example.com".to_string(),
             min_level: NotificationLevel::Info,
             // Template uses LEVEL after SUMMARY
             template: r#"{"msg": "${SUMMARY}", "lvl": "${LEVEL}"}"#.to_string(),
-        };
+            ..WebhookConfig::default()
+        });
 
         let _event = NotificationEvent::PressureChanged {
             from: "green".to_string(),
@@ -1174,7 +1624,7 @@ mod tests {
             free_pct: 10.0,
         };
 
-        let body = channel.render_body(&malicious_event);
+        let body = channel.render_body(&malicious_event, malicious_event.level());
         // The summary will be: "Pressure green -> red on /data/${LEVEL} (10.0% free)"
         // In the body, we expect: "msg": "... /data/${LEVEL} ...", "lvl": "red"
         // We do NOT want: "msg": "... /data/red ...", "lvl": "red"
@@ -1189,11 +1639,12 @@ mod tests {
 
     #[test]
     fn webhook_channel_skips_empty_url() {
-        let channel = WebhookChannel {
+        let channel = WebhookChannel::new(&WebhookConfig {
             url: String::new(),
             min_level: NotificationLevel::Info,
             template: r#"{"text": "${SUMMARY}"}"#.to_string(),
-        };
+            ..WebhookConfig::default()
+        });
 
         let event = NotificationEvent::Error {
             code: "SBH-TEST".to_string(),
@@ -1201,7 +1652,73 @@ mod tests {
         };
 
         // Should not panic or spawn curl.
-        channel.send(&event);
+        channel.send(&event, event.level());
+    }
+
+    #[test]
+    fn webhook_channel_renders_event_type() {
+        let channel = WebhookChannel::new(&WebhookConfig {
+            template: r#"{"content": "[${EVENT}] ${SUMMARY}"}"#.to_string(),
+            ..WebhookConfig::default()
+        });
+        let event = NotificationEvent::Error {
+            code: "SBH-TEST".to_string(),
+            message: "test".to_string(),
+        };
+
+        let body = channel.render_body(&event, event.level());
+        assert!(body.starts_with(r#"{"content": "[error] "#), "{body}");
+    }
+
+    #[test]
+    fn webhook_secrets_go_to_curl_config_not_argv() {
+        let mut headers = BTreeMap::new();
+        headers.insert("Authorization".to_string(), "Bearer t0k".to_string());
+        let channel = WebhookChannel::new(&WebhookConfig {
+            url: "https://hooks.example.com/sbh?key=s3cr3t".to_string(),
+            headers,
+            ..WebhookConfig::default()
+        });
+        let body = "{\"text\": \"disk \\\"full\\\"\"}";
+        let args = WebhookChannel::curl_args();
+        for secret in ["t0k", "s3cr3t", "disk"] {
+            assert!(!args.iter().any(|arg| arg.contains(secret)), "{args:?}");
+        }
+        assert_eq!(&args[args.len() - 2..], ["--config", "-"]);
+        assert_eq!(
+            channel.curl_config(body),
+            "header = \"Content-Type: application/json\"\n\
+             header = \"Authorization: Bearer t0k\"\n\
+             data-raw = \"{\\\"text\\\": \\\"disk \\\\\\\"full\\\\\\\"\\\"}\"\n\
+             url = \"https://hooks.example.com/sbh?key=s3cr3t\"\n"
+        );
+
+        let mut headers = BTreeMap::new();
+        headers.insert("content-type".to_string(), "text/plain".to_string());
+        let channel = WebhookChannel::new(&WebhookConfig {
+            headers,
+            ..WebhookConfig::default()
+        });
+        let config = channel.curl_config("{}");
+        assert_eq!(config.matches("header = ").count(), 1);
+        assert!(config.contains("header = \"content-type: text/plain\"\n"));
+    }
+
+    #[test]
+    fn webhook_retries_transient_failures_with_doubling_backoff() {
+        assert!(webhook_retryable(None));
+        assert!(webhook_retryable(Some(429)));
+        assert!(webhook_retryable(Some(503)));
+        assert!(!webhook_retryable(Some(200)));
+        assert!(!webhook_retryable(Some(404)));
+
+        let channel = WebhookChannel::new(&WebhookConfig {
+            retry_backoff_ms: 500,
+            ..WebhookConfig::default()
+        });
+        assert_eq!(channel.retry_delay(0), Duration::from_millis(500));
+        assert_eq!(channel.retry_delay(2), Duration::from_secs(2));
+        assert_eq!(channel.retry_delay(40), Duration::from_secs(32));
     }
 
     #[test]
@@ -1236,6 +1753,53 @@ mod tests {
         assert_eq!(parsed["mount"], "/data");
     }
 
+    #[test]
+    fn rules_mute_and_reclassify_per_channel_and_mount() {
+        let config: NotificationConfig = toml::from_str(
+            r#"
+            channels = ["journal", "file"]
+
+            [[rules]]
+            event = "pressure_changed"
+            level = "yellow"
+            mount = "/tmp"
+            mute = true
+
+            [[rules]]
+            event = "*"
+            channels = ["journal"]
+            set_level = "red"
+            "#,
+        )
+        .unwrap();
+        let manager = NotificationManager::from_config(&config);
+        let key = |mount| EventKey {
+            event: "pressure_changed",
+            level: NotificationLevel::Warning,
+            mount: Some(mount),
+        };
+
+        let on_tmp = manager.routes(&key("/tmp"));
+        assert!(
+            on_tmp
+                .iter()
+                .all(|route| route.rule == Some(0) && route.outcome == RouteOutcome::Muted)
+        );
+
+        let on_data = manager.routes(&key("/data"));
+        assert_eq!(on_data[0].channel, "journal");
+        assert_eq!(on_data[0].rule, Some(1));
+        assert_eq!(
+            on_data[0].outcome,
+            RouteOutcome::Delivered(NotificationLevel::Red)
+        );
+        assert_eq!(on_data[1].rule, None);
+        assert_eq!(
+            on_data[1].outcome,
+            RouteOutcome::Delivered(NotificationLevel::Warning)
+        );
+    }
+
     #[test]
     fn manager_notify_noop_when_disabled() {
         let dir = tempfile::tempdir().unwrap();
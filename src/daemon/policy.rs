@@ -10,10 +10,14 @@
 #![allow(clippy::cast_precision_loss)]
 
 use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::core::marker;
 use crate::monitor::guardrails::{GuardDiagnostics, GuardStatus};
 use crate::scanner::decision_record::{DecisionRecord, DecisionRecordBuilder, PolicyMode};
 use crate::scanner::scoring::{CandidacyScore, DecisionAction};
@@ -135,6 +139,12 @@ pub struct PolicyConfig {
     pub min_fallback_secs: u64,
     /// Whether the kill-switch is active (forces fallback_safe).
     pub kill_switch: bool,
+    /// Hours a fresh install spends in observe mode, counted over clean
+    /// guard windows, before it may delete anything (0 = no burn-in).
+    pub burn_in_observe_hours: u64,
+    /// Hours of clean canary operation that follow before the engine
+    /// reaches `initial_mode` (0 = skip the canary stage).
+    pub burn_in_canary_hours: u64,
 }
 
 impl Default for PolicyConfig {
@@ -152,10 +162,21 @@ impl Default for PolicyConfig {
             loss_keep_abandoned: 30.0,
             loss_review: 5.0,
             kill_switch: false,
+            burn_in_observe_hours: 24,
+            burn_in_canary_hours: 6,
         }
     }
 }
 
+impl PolicyConfig {
+    /// Whether a fresh install should burn in before `initial_mode`.
+    #[must_use]
+    pub fn burn_in_enabled(&self) -> bool {
+        !burn_in_stages(self).is_empty()
+            && (self.burn_in_observe_hours > 0 || self.burn_in_canary_hours > 0)
+    }
+}
+
 // ──────────────────── policy decision ────────────────────
 
 /// The result of evaluating a batch of candidates through the policy engine.
@@ -224,6 +245,8 @@ pub struct PolicyEngine {
     total_decisions: u64,
     total_fallback_entries: u64,
     transition_log: Vec<TransitionEntry>,
+    /// Post-install burn-in in progress, if any.
+    burn_in: Option<BurnIn>,
 }
 
 /// Record of a mode transition.
@@ -261,6 +284,7 @@ impl PolicyEngine {
             total_decisions: 0,
             total_fallback_entries: 0,
             transition_log: Vec::new(),
+            burn_in: None,
         };
 
         if engine.config.kill_switch {
@@ -314,6 +338,59 @@ impl PolicyEngine {
         &self.transition_log
     }
 
+    /// Burn-in record the engine is following, if any.
+    #[must_use]
+    pub fn burn_in(&self) -> Option<&BurnIn> {
+        self.burn_in.as_ref()
+    }
+
+    /// Hold the engine at the burn-in's current stage until it has proven
+    /// itself over clean guard windows. A completed record is kept for
+    /// reporting only.
+    pub fn begin_burn_in(&mut self, burn_in: BurnIn) {
+        if burn_in.is_active() {
+            let stage = burn_in.stage;
+            if self.mode == ActiveMode::FallbackSafe {
+                self.pre_fallback_mode = stage;
+            } else if self.mode != stage {
+                self.log_transition(
+                    "burn_in",
+                    self.mode,
+                    stage,
+                    Some("post-install burn-in".to_string()),
+                );
+                self.mode = stage;
+            }
+        }
+        self.burn_in = Some(burn_in);
+    }
+
+    /// Feed one guard window to the burn-in clock and promote the engine
+    /// when its current stage has run clean for long enough.
+    ///
+    /// Returns `true` when the record changed in a way worth persisting:
+    /// a promotion, or a breach stopping or restarting the clock.
+    pub fn advance_burn_in(&mut self, guard: &GuardDiagnostics, now: DateTime<Utc>) -> bool {
+        let Some(burn_in) = self.burn_in.as_mut() else {
+            return false;
+        };
+        let clean = self.mode != ActiveMode::FallbackSafe
+            && guard.status != GuardStatus::Fail
+            && !guard.e_process_alarm;
+        let stage = burn_in.stage;
+        match burn_in.advance(clean, now, &self.config) {
+            BurnInProgress::Unchanged => false,
+            BurnInProgress::Clock => true,
+            BurnInProgress::Promoted(next) => {
+                if self.mode == stage && next != stage {
+                    self.apply_transition(next, "burn_in_promote");
+                    eprintln!("[SBH-POLICY] {stage} → {next} (burn-in)");
+                }
+                true
+            }
+        }
+    }
+
     // ──────────── core evaluation ────────────
 
     /// Evaluate a batch of scored candidates through the policy engine.
@@ -710,6 +787,183 @@ impl PolicyEngine {
     }
 }
 
+// ──────────────────── post-install burn-in ────────────────────
+
+/// File name of the burn-in record, a sibling of `state.json`.
+pub const BURN_IN_FILE_NAME: &str = "burn_in.json";
+
+/// Progress of a fresh install through its burn-in.
+///
+/// The engine runs in observe, then canary, then the configured
+/// `initial_mode`. Each stage must run for its configured hours without a
+/// failing guard or drift alarm; a breach stops the clock and the stage
+/// starts over once windows are clean again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurnIn {
+    /// When the daemon first started on this install.
+    pub started_at: DateTime<Utc>,
+    /// Stage in progress, or the final mode once complete.
+    pub stage: ActiveMode,
+    /// Start of the current run of clean windows; `None` during a breach.
+    pub clean_since: Option<DateTime<Utc>>,
+    /// Breaches that restarted a stage.
+    #[serde(default)]
+    pub restarts: u64,
+    /// When the engine reached its configured mode.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A promotion still ahead in the burn-in schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BurnInStep {
+    /// Mode the engine moves to.
+    pub mode: ActiveMode,
+    /// Earliest time it can happen; `None` while a breach holds the clock.
+    pub at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one window fed to [`BurnIn::advance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BurnInProgress {
+    Unchanged,
+    /// A breach stopped the clock or clean windows restarted it.
+    Clock,
+    Promoted(ActiveMode),
+}
+
+impl BurnIn {
+    /// A burn-in starting now in observe mode.
+    #[must_use]
+    pub fn start(now: DateTime<Utc>) -> Self {
+        Self {
+            started_at: now,
+            stage: ActiveMode::Observe,
+            clean_since: Some(now),
+            restarts: 0,
+            completed_at: None,
+        }
+    }
+
+    /// A record for an install that predates burn-in or opted out, so the
+    /// daemon never starts one later.
+    #[must_use]
+    pub fn waived(now: DateTime<Utc>, mode: ActiveMode) -> Self {
+        Self {
+            started_at: now,
+            stage: mode,
+            clean_since: None,
+            restarts: 0,
+            completed_at: Some(now),
+        }
+    }
+
+    /// Whether the engine is still being held below its configured mode.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.completed_at.is_none()
+    }
+
+    /// Promotions still ahead, earliest first. Times assume every window
+    /// from here on is clean.
+    #[must_use]
+    pub fn schedule(&self, config: &PolicyConfig) -> Vec<BurnInStep> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let stages = burn_in_stages(config);
+        let current = stages
+            .iter()
+            .position(|(mode, _)| *mode == self.stage)
+            .unwrap_or(stages.len());
+        let mut at = self.clean_since;
+        let mut steps = Vec::new();
+        for (index, (_, hours)) in stages.iter().enumerate().skip(current) {
+            at = at.map(|at| at + chrono::Duration::hours(hours_i64(*hours)));
+            let mode = stages
+                .get(index + 1)
+                .map_or(config.initial_mode, |(mode, _)| *mode);
+            steps.push(BurnInStep { mode, at });
+        }
+        if steps.is_empty() {
+            steps.push(BurnInStep {
+                mode: config.initial_mode,
+                at,
+            });
+        }
+        steps
+    }
+
+    fn advance(
+        &mut self,
+        clean: bool,
+        now: DateTime<Utc>,
+        config: &PolicyConfig,
+    ) -> BurnInProgress {
+        if !self.is_active() {
+            return BurnInProgress::Unchanged;
+        }
+        if self.clean_since.is_none() {
+            if !clean {
+                return BurnInProgress::Unchanged;
+            }
+            self.clean_since = Some(now);
+            return BurnInProgress::Clock;
+        }
+        if !clean {
+            self.clean_since = None;
+            self.restarts += 1;
+            return BurnInProgress::Clock;
+        }
+        let Some(next) = self.schedule(config).first().cloned() else {
+            return BurnInProgress::Unchanged;
+        };
+        if next.at.is_some_and(|at| now < at) {
+            return BurnInProgress::Unchanged;
+        }
+        self.stage = next.mode;
+        self.clean_since = Some(now);
+        if next.mode == config.initial_mode {
+            self.completed_at = Some(now);
+        }
+        BurnInProgress::Promoted(next.mode)
+    }
+}
+
+/// Stages before `initial_mode` with their required hours. A configured
+/// mode at or below observe needs no burn-in at all.
+fn burn_in_stages(config: &PolicyConfig) -> Vec<(ActiveMode, u64)> {
+    match config.initial_mode {
+        ActiveMode::Enforce if config.burn_in_canary_hours > 0 => vec![
+            (ActiveMode::Observe, config.burn_in_observe_hours),
+            (ActiveMode::Canary, config.burn_in_canary_hours),
+        ],
+        ActiveMode::Canary | ActiveMode::Enforce => {
+            vec![(ActiveMode::Observe, config.burn_in_observe_hours)]
+        }
+        ActiveMode::Observe | ActiveMode::FallbackSafe => Vec::new(),
+    }
+}
+
+fn hours_i64(hours: u64) -> i64 {
+    i64::try_from(hours).unwrap_or(i64::MAX / 3600)
+}
+
+/// Burn-in record path for the given `state.json` path.
+#[must_use]
+pub fn burn_in_path(state_file: &Path) -> PathBuf {
+    state_file.with_file_name(BURN_IN_FILE_NAME)
+}
+
+/// Read the record; `Ok(None)` when there is none.
+pub fn read_burn_in(path: &Path) -> io::Result<Option<BurnIn>> {
+    marker::read_marker(path)
+}
+
+/// Write the record, readable by `sbh tune` for any user.
+pub fn write_burn_in(path: &Path, burn_in: &BurnIn) -> io::Result<()> {
+    marker::write_marker(path, burn_in, 0o644)
+}
+
 // ──────────────────── diagnostics ────────────────────
 
 /// Snapshot of the policy engine state for status reporting.
@@ -744,7 +998,8 @@ mod tests {
     use crate::scanner::decision_record::ActionRecord;
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification};
     use crate::scanner::scoring::{
-        CandidacyScore, DecisionAction, DecisionOutcome, EvidenceLedger, EvidenceTerm, ScoreFactors,
+        CandidacyScore, DecisionAction, DecisionOutcome, EvidenceLedger, EvidenceTerm,
+        ScoreFactors, ScoringInputs,
     };
     use std::path::PathBuf;
     use std::time::Duration;
@@ -769,6 +1024,7 @@ mod tests {
                 size: 0.70,
                 structure: 0.95,
                 pressure_multiplier: 1.5,
+                regeneration_cost: 1.0,
             },
             vetoed: false,
             veto_reason: None,
@@ -780,6 +1036,7 @@ mod tests {
                 combined_confidence: 0.92,
             },
             size_bytes: 3_000_000_000,
+            disk_bytes: 3_000_000_000,
             age: Duration::from_secs(5 * 3600),
             decision: DecisionOutcome {
                 action,
@@ -798,6 +1055,7 @@ mod tests {
                 }],
                 summary: "test".to_string(),
             },
+            inputs: ScoringInputs::default(),
         }
     }
 
@@ -1336,4 +1594,96 @@ mod tests {
 
         assert_eq!(engine.config.max_canary_deletes_per_hour, 50);
     }
+
+    // ──── post-install burn-in ────
+
+    fn hours_after(start: DateTime<Utc>, hours: i64) -> DateTime<Utc> {
+        start + chrono::Duration::hours(hours)
+    }
+
+    #[test]
+    fn burn_in_holds_a_fresh_install_in_observe_then_promotes_on_schedule() {
+        let start = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut engine = PolicyEngine::new(PolicyConfig::default());
+        engine.begin_burn_in(BurnIn::start(start));
+        assert_eq!(engine.mode(), ActiveMode::Observe);
+
+        let schedule = engine.burn_in().unwrap().schedule(&engine.config);
+        assert_eq!(
+            schedule,
+            vec![
+                BurnInStep {
+                    mode: ActiveMode::Canary,
+                    at: Some(hours_after(start, 24)),
+                },
+                BurnInStep {
+                    mode: ActiveMode::Enforce,
+                    at: Some(hours_after(start, 30)),
+                },
+            ]
+        );
+
+        let guard = passing_guard();
+        assert!(!engine.advance_burn_in(&guard, hours_after(start, 23)));
+        assert_eq!(engine.mode(), ActiveMode::Observe);
+        assert!(engine.advance_burn_in(&guard, hours_after(start, 24)));
+        assert_eq!(engine.mode(), ActiveMode::Canary);
+        assert!(!engine.advance_burn_in(&guard, hours_after(start, 29)));
+        assert!(engine.advance_burn_in(&guard, hours_after(start, 30)));
+        assert_eq!(engine.mode(), ActiveMode::Enforce);
+        assert!(!engine.burn_in().unwrap().is_active());
+        assert!(!engine.advance_burn_in(&guard, hours_after(start, 48)));
+    }
+
+    #[test]
+    fn burn_in_breach_stops_the_clock_and_restarts_the_stage() {
+        let start = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut engine = PolicyEngine::new(PolicyConfig::default());
+        engine.begin_burn_in(BurnIn::start(start));
+
+        // Breach at hour 20; only its first and last windows change the record.
+        let bad = failing_guard();
+        assert!(engine.advance_burn_in(&bad, hours_after(start, 20)));
+        assert!(!engine.advance_burn_in(&bad, hours_after(start, 21)));
+        let burn_in = engine.burn_in().unwrap();
+        assert_eq!(burn_in.clean_since, None);
+        assert!(burn_in.schedule(&engine.config)[0].at.is_none());
+
+        let good = passing_guard();
+        assert!(engine.advance_burn_in(&good, hours_after(start, 22)));
+        assert!(!engine.advance_burn_in(&good, hours_after(start, 45)));
+        assert_eq!(engine.mode(), ActiveMode::Observe);
+        assert!(engine.advance_burn_in(&good, hours_after(start, 46)));
+        assert_eq!(engine.mode(), ActiveMode::Canary);
+        assert_eq!(engine.burn_in().unwrap().restarts, 1);
+    }
+
+    #[test]
+    fn burn_in_is_skipped_for_waived_installs_and_observe_configs() {
+        let now = Utc::now();
+        let mut engine = PolicyEngine::new(PolicyConfig::default());
+        engine.begin_burn_in(BurnIn::waived(now, ActiveMode::Enforce));
+        assert_eq!(engine.mode(), ActiveMode::Enforce);
+        let burn_in = engine.burn_in().unwrap();
+        assert!(burn_in.schedule(&engine.config).is_empty());
+
+        assert!(PolicyConfig::default().burn_in_enabled());
+        assert!(!default_config().burn_in_enabled());
+        let no_hours = PolicyConfig {
+            burn_in_observe_hours: 0,
+            burn_in_canary_hours: 0,
+            ..PolicyConfig::default()
+        };
+        assert!(!no_hours.burn_in_enabled());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = burn_in_path(&dir.path().join("state.json"));
+        assert_eq!(read_burn_in(&path).unwrap(), None);
+        write_burn_in(&path, &BurnIn::start(now)).unwrap();
+        assert_eq!(read_burn_in(&path).unwrap(), Some(BurnIn::start(now)));
+    }
 }
@@ -1,107 +1,70 @@
-//! Daemon self-monitoring: RSS tracking, thread health checks, state file for CLI,
-//! and sd_notify STATUS updates.
+//! Daemon self-monitoring: RSS tracking, thread health checks, main-loop stall
+//! detection, state file for CLI, and sd_notify STATUS updates.
+//!
+//! RSS is checked against `watchdog.memory_budget_mb` on every state write.
+//! Once it passes [`SHED_START_FRACTION`] of the budget, the daemon drops one
+//! internal cache per write, in [`ShedStage`] order, until it is back under;
+//! the order resets when RSS falls below [`SHED_RESET_FRACTION`].
 //!
 //! The state file (`state.json`) is the primary mechanism for CLI-to-daemon communication.
-//! Written atomically (write to `.tmp`, then `rename()`) every `DAEMON_STATE_WRITE_INTERVAL_SECS`
-//! seconds so `sbh status`
-//! can always read a consistent snapshot.
+//! Written atomically (write to a uniquely named `.tmp`, fsync, then `rename()`) every
+//! `DAEMON_STATE_WRITE_INTERVAL_SECS` seconds so `sbh status` can always read a consistent
+//! snapshot. Each file carries a `state_schema` number; readers accept the current and
+//! previous schema and refuse newer ones with an upgrade hint.
 
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
 
-use std::fs::{self, OpenOptions};
+use std::collections::BTreeMap;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
-use serde::{Deserialize, Serialize};
+use parking_lot::Mutex;
 
+use crate::core::maintenance::MaintenanceWindow;
+use crate::core::marker;
+use crate::core::state::{
+    self, BallastState, CleanupCooldown, ConfigReloadState, Counters,
+    DAEMON_STATE_STALE_THRESHOLD_SECS, DAEMON_STATE_WRITE_INTERVAL_SECS, DaemonState,
+    DeepScanState, LastScanState, MountPressure, MountRate, PressureState, STATE_SCHEMA_VERSION,
+};
 use crate::monitor::pid::PressureLevel;
 
-// ──────────────────── constants ────────────────────
-
-/// How often the daemon writes `state.json` (seconds).
-pub const DAEMON_STATE_WRITE_INTERVAL_SECS: u64 = 30;
-
-/// Floor for treating `state.json` as stale (seconds).
-///
-/// Must be `>= 2 × DAEMON_STATE_WRITE_INTERVAL_SECS` so that CLI commands
-/// (`sbh status`, `sbh check`, `read_daemon_prediction`) never report the
-/// daemon as absent simply because a write cycle hasn't completed yet.
-pub const DAEMON_STATE_STALE_THRESHOLD_SECS: u64 = 90;
-
-// ──────────────────── state file schema ────────────────────
-
-/// Top-level state written to `state.json` for CLI consumption.
-///
-/// All fields use `#[serde(default)]` so that minor schema evolution
-/// (new fields added by a newer daemon, or old fields removed) does not
-/// hard-fail deserialization. The dashboard adapter layer detects drift
-/// and surfaces warnings rather than crashing.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct DaemonState {
-    pub version: String,
-    pub pid: u32,
-    pub started_at: String,
-    pub uptime_seconds: u64,
-    pub last_updated: String,
-    pub pressure: PressureState,
-    pub ballast: BallastState,
-    pub last_scan: LastScanState,
-    pub counters: Counters,
-    pub memory_rss_bytes: u64,
-    /// Active policy engine mode (enforce/observe/canary/fallback_safe).
-    pub policy_mode: String,
-}
-
-/// Current pressure across monitored mounts.
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct PressureState {
-    pub overall: String,
-    pub mounts: Vec<MountPressure>,
+// ──────────────────── memory budget ────────────────────
+
+/// Share of the memory budget at which caches start being shed.
+pub const SHED_START_FRACTION: f64 = 0.9;
+
+/// Share of the memory budget below which shedding starts over from the
+/// first stage.
+pub const SHED_RESET_FRACTION: f64 = 0.75;
+
+/// Internal caches dropped as RSS nears the memory budget, cheapest to
+/// rebuild first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShedStage {
+    /// The watch-mode walker index; the next pass walks in full.
+    WalkerIndex,
+    /// Forecast calibration history beyond the minimum each guard needs.
+    RateHistory,
+    /// Per-process write samples kept for incident attribution.
+    TelemetryBuffers,
 }
 
-/// Pressure info for a single mount.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct MountPressure {
-    pub path: String,
-    #[serde(default)]
-    pub free_pct: f64,
-    pub level: String,
-    pub rate_bps: Option<f64>,
-}
+impl ShedStage {
+    pub const ORDER: [Self; 3] = [Self::WalkerIndex, Self::RateHistory, Self::TelemetryBuffers];
 
-/// Current ballast file state.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct BallastState {
-    pub available: usize,
-    pub total: usize,
-    pub released: usize,
-}
-
-/// Last scan summary.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct LastScanState {
-    pub at: Option<String>,
-    pub candidates: usize,
-    pub deleted: usize,
-}
-
-/// Cumulative counters since daemon start.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(default)]
-pub struct Counters {
-    pub scans: u64,
-    pub deletions: u64,
-    pub bytes_freed: u64,
-    pub errors: u64,
-    /// Log events silently dropped due to channel back-pressure.
-    pub dropped_log_events: u64,
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::WalkerIndex => "walker_index",
+            Self::RateHistory => "rate_history",
+            Self::TelemetryBuffers => "telemetry_buffers",
+        }
+    }
 }
 
 // ──────────────────── health tracking ────────────────────
@@ -166,6 +129,13 @@ impl ThreadHeartbeat {
         self.last_beat_epoch_ms.store(epoch_ms(), Ordering::Relaxed);
     }
 
+    /// Time since the last heartbeat.
+    #[must_use]
+    pub fn since_last_beat(&self) -> Duration {
+        let last = self.last_beat_epoch_ms.load(Ordering::Relaxed);
+        Duration::from_millis(epoch_ms().saturating_sub(last))
+    }
+
     /// Check thread status based on heartbeat staleness.
     #[must_use]
     pub fn status(&self, stall_threshold: Duration) -> ThreadStatus {
@@ -193,6 +163,106 @@ impl ThreadHeartbeat {
     }
 }
 
+/// Main-loop liveness shared with the stall watchdog: a heartbeat and the
+/// step last entered.
+#[derive(Debug)]
+pub struct LoopProgress {
+    heartbeat: Arc<ThreadHeartbeat>,
+    phase: Mutex<&'static str>,
+}
+
+impl LoopProgress {
+    /// Phase before the first tick. Startup (ballast provisioning) can
+    /// legitimately take minutes, so it is never treated as a stall.
+    pub const STARTUP: &'static str = "startup";
+
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            heartbeat: ThreadHeartbeat::new("sbh-main"),
+            phase: Mutex::new(Self::STARTUP),
+        })
+    }
+
+    /// Record progress into `phase` (called by the main loop at each step).
+    pub fn tick(&self, phase: &'static str) {
+        self.heartbeat.beat();
+        *self.phase.lock() = phase;
+    }
+
+    #[must_use]
+    pub fn phase(&self) -> &'static str {
+        *self.phase.lock()
+    }
+
+    /// How long the loop has gone without a tick, or `None` during startup.
+    #[must_use]
+    pub fn stalled_for(&self) -> Option<Duration> {
+        (self.phase() != Self::STARTUP).then(|| self.heartbeat.since_last_beat())
+    }
+}
+
+/// One line per thread of this process: name, scheduler state, kernel wait
+/// channel, and the kernel stack when readable (root only).
+///
+/// Another thread's user stack cannot be unwound from safe Rust, so this is
+/// where a wedged thread is blocked as the kernel sees it. Empty off Linux.
+#[must_use]
+pub fn thread_snapshot() -> Vec<String> {
+    #[cfg(target_os = "linux")]
+    {
+        thread_snapshot_linux()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn thread_snapshot_linux() -> Vec<String> {
+    let Ok(tasks) = fs::read_dir("/proc/self/task") else {
+        return Vec::new();
+    };
+    let mut lines: Vec<(u64, String)> = tasks
+        .flatten()
+        .filter_map(|task| {
+            let tid = task.file_name().to_str()?.parse::<u64>().ok()?;
+            let dir = task.path();
+            let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap_or_default();
+            let comm = read("comm");
+            let stat = read("stat");
+            let state = stat
+                .rsplit_once(") ")
+                .and_then(|(_, rest)| rest.split_whitespace().next())
+                .unwrap_or("?");
+            let wchan = read("wchan");
+            let mut line = format!(
+                "tid {tid} {} state={state} wchan={}",
+                comm.trim(),
+                if wchan.is_empty() || wchan == "0" {
+                    "-"
+                } else {
+                    wchan.trim()
+                }
+            );
+            let stack = read("stack");
+            if !stack.trim().is_empty() {
+                let frames: Vec<&str> = stack
+                    .lines()
+                    .filter_map(|frame| frame.split_whitespace().nth(1))
+                    .take(8)
+                    .collect();
+                line.push_str(" stack=");
+                line.push_str(&frames.join(" <- "));
+            }
+            Some((tid, line))
+        })
+        .collect();
+    lines.sort_by_key(|(tid, _)| *tid);
+    lines.into_iter().map(|(_, line)| line).collect()
+}
+
 /// Milliseconds since a process-local monotonic origin.
 ///
 /// Uses `Instant` (monotonic clock) instead of `SystemTime` to avoid
@@ -232,7 +302,12 @@ pub struct SelfMonitor {
     started_at_iso: String,
     write_interval: Duration,
     last_write: Option<Instant>,
-    rss_limit_bytes: u64,
+    /// The state most recently built for `state.json`, for state dumps.
+    last_state: Option<DaemonState>,
+    /// `watchdog.memory_budget_mb` in bytes; 0 disables shedding.
+    memory_budget_bytes: u64,
+    /// Stages of [`ShedStage::ORDER`] already shed since RSS was last low.
+    shed_stages: usize,
 
     // Mutable counters updated by the main loop.
     pub scan_count: u64,
@@ -242,6 +317,23 @@ pub struct SelfMonitor {
     pub deletions_total: u64,
     pub bytes_freed_total: u64,
     pub errors_total: u64,
+    /// Ballast integrity pass totals, published under `ballast`.
+    pub ballast_files_verified: u64,
+    pub ballast_files_corrupted: u64,
+    pub ballast_files_repaired: u64,
+    /// Latest EWMA fill rate per mount path, published as `rates`.
+    pub rates: BTreeMap<String, MountRate>,
+    /// Watched mounts currently read-only, with their free percentage;
+    /// published under `pressure.mounts` with level `readonly`.
+    pub readonly_mounts: BTreeMap<String, f64>,
+    /// Maintenance window in force, published as `maintenance`.
+    pub maintenance: Option<MaintenanceWindow>,
+    /// Latest config reload outcome, published as `config_reload`.
+    pub config_reload: Option<ConfigReloadState>,
+    /// Mounts holding off automated cleanup, published as `cleanup_cooldowns`.
+    pub cleanup_cooldowns: BTreeMap<String, CleanupCooldown>,
+    /// Deep-scan timing, published as `deep_scan`.
+    pub deep_scan: Option<DeepScanState>,
     /// Cumulative scan duration for averaging.
     scan_duration_total: Duration,
 }
@@ -256,7 +348,9 @@ impl SelfMonitor {
             started_at_iso: now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
             write_interval: Duration::from_secs(DAEMON_STATE_WRITE_INTERVAL_SECS),
             last_write: None,
-            rss_limit_bytes: 256 * 1024 * 1024, // 256 MB
+            last_state: None,
+            memory_budget_bytes: 256 * 1024 * 1024, // 256 MB
+            shed_stages: 0,
 
             scan_count: 0,
             last_scan_at: None,
@@ -265,10 +359,56 @@ impl SelfMonitor {
             deletions_total: 0,
             bytes_freed_total: 0,
             errors_total: 0,
+            ballast_files_verified: 0,
+            ballast_files_corrupted: 0,
+            ballast_files_repaired: 0,
+            rates: BTreeMap::new(),
+            readonly_mounts: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
             scan_duration_total: Duration::ZERO,
         }
     }
 
+    /// Set the RSS the daemon tries to stay under (0 disables shedding).
+    pub const fn set_memory_budget(&mut self, bytes: u64) {
+        self.memory_budget_bytes = bytes;
+    }
+
+    #[must_use]
+    pub const fn memory_budget(&self) -> u64 {
+        self.memory_budget_bytes
+    }
+
+    /// The next cache to shed at `rss`, or `None` when RSS is comfortably
+    /// under the budget or everything has been shed already. Each call sheds
+    /// at most one stage, so a stage gets a state-write interval to take
+    /// effect before the next is dropped.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn next_shed_stage(&mut self, rss: u64) -> Option<ShedStage> {
+        if self.memory_budget_bytes == 0 || rss == 0 {
+            return None;
+        }
+        let budget = self.memory_budget_bytes as f64;
+        if (rss as f64) < budget * SHED_RESET_FRACTION {
+            self.shed_stages = 0;
+            return None;
+        }
+        if (rss as f64) < budget * SHED_START_FRACTION {
+            return None;
+        }
+        let stage = ShedStage::ORDER.get(self.shed_stages).copied()?;
+        self.shed_stages += 1;
+        Some(stage)
+    }
+
+    /// Let the next `maybe_write_state` write regardless of the interval.
+    pub fn force_next_write(&mut self) {
+        self.last_write = None;
+    }
+
     /// Check if it's time to write the state file. If so, write it.
     ///
     /// Returns the current RSS in bytes (0 if unavailable).
@@ -292,15 +432,32 @@ impl SelfMonitor {
 
         let rss = read_rss_bytes();
 
-        // Check RSS limit.
-        if rss > self.rss_limit_bytes {
+        // Check the memory budget.
+        if self.memory_budget_bytes > 0 && rss > self.memory_budget_bytes {
             eprintln!(
-                "[SBH-SELFMON] WARNING: RSS {} MB exceeds limit {} MB",
+                "[SBH-SELFMON] WARNING: RSS {} MB exceeds budget {} MB",
                 rss / (1024 * 1024),
-                self.rss_limit_bytes / (1024 * 1024),
+                self.memory_budget_bytes / (1024 * 1024),
             );
         }
 
+        let mut mounts = vec![MountPressure {
+            path: mount_path.to_string(),
+            free_pct,
+            level: format!("{pressure_level:?}").to_lowercase(),
+            rate_bps: None,
+        }];
+        mounts.extend(
+            self.readonly_mounts
+                .iter()
+                .map(|(path, free_pct)| MountPressure {
+                    path: path.clone(),
+                    free_pct: *free_pct,
+                    level: "readonly".to_string(),
+                    rate_bps: None,
+                }),
+        );
+
         let state = DaemonState {
             version: env!("CARGO_PKG_VERSION").to_string(),
             pid: std::process::id(),
@@ -309,17 +466,15 @@ impl SelfMonitor {
             last_updated: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
             pressure: PressureState {
                 overall: format!("{pressure_level:?}").to_lowercase(),
-                mounts: vec![MountPressure {
-                    path: mount_path.to_string(),
-                    free_pct,
-                    level: format!("{pressure_level:?}").to_lowercase(),
-                    rate_bps: None,
-                }],
+                mounts,
             },
             ballast: BallastState {
                 available: ballast_available,
                 total: ballast_total,
                 released: ballast_total.saturating_sub(ballast_available),
+                files_verified: self.ballast_files_verified,
+                files_corrupted: self.ballast_files_corrupted,
+                files_repaired: self.ballast_files_repaired,
             },
             last_scan: LastScanState {
                 at: self.last_scan_at.clone(),
@@ -334,13 +489,20 @@ impl SelfMonitor {
                 dropped_log_events,
             },
             memory_rss_bytes: rss,
+            state_schema: STATE_SCHEMA_VERSION,
             policy_mode: policy_mode.to_string(),
+            rates: self.rates.clone(),
+            maintenance: self.maintenance.clone(),
+            config_reload: self.config_reload.clone(),
+            cleanup_cooldowns: self.cleanup_cooldowns.clone(),
+            deep_scan: self.deep_scan.clone(),
         };
 
         let result = write_state_atomic(&self.state_file_path, &state);
         if let Err(e) = &result {
             eprintln!("[SBH-SELFMON] failed to write state file: {e}");
         }
+        self.last_state = Some(state);
         // Update last_write regardless of success to respect the interval
         // and prevent log spam on persistent errors (e.g. permission denied).
         self.last_write = Some(now);
@@ -352,6 +514,13 @@ impl SelfMonitor {
         rss
     }
 
+    /// The state most recently built for `state.json`, whether or not the
+    /// write succeeded.
+    #[must_use]
+    pub const fn last_state(&self) -> Option<&DaemonState> {
+        self.last_state.as_ref()
+    }
+
     /// Build a status string suitable for sd_notify STATUS.
     #[must_use]
     pub fn status_line(
@@ -438,9 +607,7 @@ impl SelfMonitor {
 
     /// Read the state file (for `sbh status` CLI command).
     pub fn read_state(path: &Path) -> std::result::Result<DaemonState, String> {
-        let raw = fs::read_to_string(path).map_err(|e| format!("cannot read state file: {e}"))?;
-        let state: DaemonState =
-            serde_json::from_str(&raw).map_err(|e| format!("invalid state file: {e}"))?;
+        let state = state::read_state(path).map_err(|e| e.to_string())?.state;
 
         // Check staleness.
         if let Ok(updated) = chrono::DateTime::parse_from_rfc3339(&state.last_updated) {
@@ -475,44 +642,18 @@ fn parse_last_scan_instant(timestamp: &str) -> Option<Instant> {
 
 // ──────────────────── atomic state file write ────────────────────
 
-/// Write state.json atomically: write to .tmp, then rename.
+/// Write state.json atomically (see [`marker::replace_file`]).
 ///
-/// Sets 0o644 permissions on the temp file (Unix only) so the state file is
-/// world-readable. The state file contains only operational telemetry (pressure
-/// levels, uptime, counters) and must be readable by the CLI running as a
-/// non-root user (e.g. `sbh status` run by ubuntu while daemon runs as root).
+/// The file is 0o644 (Unix only) so it is world-readable. It contains only
+/// operational telemetry (pressure levels, uptime, counters) and must be
+/// readable by the CLI running as a non-root user (e.g. `sbh status` run by
+/// ubuntu while daemon runs as root).
 fn write_state_atomic(path: &Path, state: &DaemonState) -> std::io::Result<()> {
-    let tmp_path = path.with_extension("json.tmp");
-
-    // Ensure parent directory exists.
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-
     let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
-
-    let result = (|| {
-        {
-            use std::io::Write;
-            let mut opts = OpenOptions::new();
-            opts.write(true).create(true).truncate(true);
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::OpenOptionsExt as _;
-                opts.mode(0o644);
-            }
-            let mut file = opts.open(&tmp_path)?;
-            file.write_all(json.as_bytes())?;
-            file.sync_all()?;
-        }
-        fs::rename(&tmp_path, path)?;
-        Ok(())
-    })();
-
-    if result.is_err() {
-        let _ = fs::remove_file(&tmp_path);
-    }
-    result
+    marker::replace_file(path, json.as_bytes(), 0o644)
 }
 
 // ──────────────────── RSS reading ────────────────────
@@ -578,6 +719,9 @@ mod tests {
                 available: 8,
                 total: 10,
                 released: 2,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: Some("2026-02-14T10:59:55.000Z".to_string()),
@@ -592,6 +736,12 @@ mod tests {
                 dropped_log_events: 0,
             },
             memory_rss_bytes: 44_040_192,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
             policy_mode: "enforce".into(),
         };
 
@@ -627,6 +777,9 @@ mod tests {
                 available: 5,
                 total: 5,
                 released: 0,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: None,
@@ -641,6 +794,12 @@ mod tests {
                 dropped_log_events: 0,
             },
             memory_rss_bytes: 0,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
             policy_mode: String::new(),
         };
 
@@ -648,7 +807,7 @@ mod tests {
         assert!(path.exists());
 
         // No temp file left behind.
-        assert!(!dir.path().join("state.json.tmp").exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
 
         // Readable.
         let read_back: DaemonState =
@@ -678,6 +837,9 @@ mod tests {
                 available: 0,
                 total: 0,
                 released: 0,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: None,
@@ -692,6 +854,12 @@ mod tests {
                 dropped_log_events: 0,
             },
             memory_rss_bytes: 0,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
             policy_mode: String::new(),
         };
 
@@ -874,6 +1042,27 @@ mod tests {
         assert_eq!(health.thread_status[0].name(), "stalled-worker");
     }
 
+    #[test]
+    fn loop_progress_ignores_startup_and_tracks_phase() {
+        let progress = LoopProgress::new();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(progress.stalled_for(), None);
+
+        progress.tick("pressure");
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(progress.phase(), "pressure");
+        assert!(progress.stalled_for().unwrap() >= Duration::from_millis(5));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn thread_snapshot_lists_current_thread() {
+        let snapshot = thread_snapshot();
+        assert!(!snapshot.is_empty());
+        assert!(snapshot.iter().all(|line| line.starts_with("tid ")));
+        assert!(snapshot.iter().any(|line| line.contains("state=R")));
+    }
+
     #[test]
     fn health_snapshot_restores_last_scan_age_from_timestamp() {
         let dir = tempfile::tempdir().unwrap();
@@ -909,6 +1098,30 @@ mod tests {
         assert!(rss > 0, "RSS should be > 0 on Linux");
     }
 
+    #[test]
+    fn caches_are_shed_one_stage_at_a_time_near_the_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut monitor = SelfMonitor::new(dir.path().join("state.json"));
+        monitor.set_memory_budget(100);
+
+        assert_eq!(monitor.next_shed_stage(89), None);
+        assert_eq!(monitor.next_shed_stage(90), Some(ShedStage::WalkerIndex));
+        assert_eq!(monitor.next_shed_stage(95), Some(ShedStage::RateHistory));
+        // Between the reset and start marks, nothing more is shed...
+        assert_eq!(monitor.next_shed_stage(80), None);
+        assert_eq!(
+            monitor.next_shed_stage(120),
+            Some(ShedStage::TelemetryBuffers)
+        );
+        assert_eq!(monitor.next_shed_stage(120), None, "nothing left to shed");
+        // ...and once well under, the order starts over.
+        assert_eq!(monitor.next_shed_stage(70), None);
+        assert_eq!(monitor.next_shed_stage(90), Some(ShedStage::WalkerIndex));
+
+        monitor.set_memory_budget(0);
+        assert_eq!(monitor.next_shed_stage(u64::MAX), None);
+    }
+
     // ──────── failure-injection tests ────────
 
     #[test]
@@ -1063,8 +1276,13 @@ mod tests {
         monitor.maybe_write_state(PressureLevel::Green, 40.0, "/data", 10, 10, 0, "enforce");
 
         assert!(path.exists());
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
         assert!(
-            !dir.path().join("clean_state.json.tmp").exists(),
+            leftovers.is_empty(),
             "temp file must be cleaned up after atomic rename"
         );
     }
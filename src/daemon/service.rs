@@ -1,7 +1,9 @@
-//! Service integration: systemd (Type=notify, watchdog) and launchd (plist generation).
+//! Service integration: systemd (Type=notify, watchdog), launchd (plist generation),
+//! and Windows Task Scheduler.
 //!
 //! Generates unit files / plists from configuration, installs them in the correct
-//! system or user directory, and drives `systemctl` / `launchctl` for lifecycle.
+//! system or user directory, and drives `systemctl` / `launchctl` / `schtasks` for
+//! lifecycle.
 
 use std::env;
 use std::fmt::Write as _;
@@ -347,9 +349,10 @@ impl ServiceManager for SystemdServiceManager {
 pub struct ServiceActionResult {
     /// The action performed (`"install"` or `"uninstall"`).
     pub action: &'static str,
-    /// Service system type (e.g., `"systemd"`, `"launchd"`).
+    /// Service system type (e.g., `"systemd"`, `"launchd"`, `"scheduled_task"`).
     pub service_type: &'static str,
-    /// Service scope (`"system"` or `"user"`).
+    /// Service scope (`"system"` or `"user"`; `"any"` when removing a
+    /// scheduled task, which is the same task in either scope).
     pub scope: &'static str,
     /// Path to the generated/removed unit file.
     pub unit_path: PathBuf,
@@ -688,6 +691,158 @@ fn default_launchd_log_paths(user_scope: bool) -> (PathBuf, PathBuf) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Windows Task Scheduler
+// ---------------------------------------------------------------------------
+
+/// Task name registered with the Windows Task Scheduler.
+const SCHEDULED_TASK_NAME: &str = "sbh";
+
+/// Parameters controlling the Windows scheduled task.
+#[derive(Debug, Clone)]
+pub struct ScheduledTaskConfig {
+    /// Start at logon of the installing user instead of at boot as SYSTEM.
+    pub user_scope: bool,
+    /// Absolute path to the sbh binary the task runs.
+    pub binary_path: PathBuf,
+}
+
+impl ScheduledTaskConfig {
+    /// Build a config from the current environment.
+    pub fn from_env(user_scope: bool) -> Result<Self> {
+        Ok(Self {
+            user_scope,
+            binary_path: resolve_sbh_binary()?,
+        })
+    }
+
+    /// Task path as shown by the Task Scheduler (`\sbh`).
+    #[must_use]
+    pub fn task_path(&self) -> PathBuf {
+        PathBuf::from(format!("\\{SCHEDULED_TASK_NAME}"))
+    }
+}
+
+/// [`ServiceManager`] implementation that registers `sbh daemon` as a
+/// Windows scheduled task through `schtasks.exe`.
+///
+/// A scheduled task rather than a Windows service: the daemon does not speak
+/// the service control protocol, so the SCM would kill it as unresponsive.
+#[derive(Debug, Clone)]
+pub struct ScheduledTaskServiceManager {
+    config: ScheduledTaskConfig,
+}
+
+impl ScheduledTaskServiceManager {
+    /// Create a new manager with the given config.
+    #[must_use]
+    pub fn new(config: ScheduledTaskConfig) -> Self {
+        Self { config }
+    }
+
+    /// Create a manager from the current environment.
+    pub fn from_env(user_scope: bool) -> Result<Self> {
+        Ok(Self::new(ScheduledTaskConfig::from_env(user_scope)?))
+    }
+
+    /// Access the underlying config.
+    #[must_use]
+    pub fn config(&self) -> &ScheduledTaskConfig {
+        &self.config
+    }
+
+    /// Arguments for `schtasks /Create`. System scope runs at boot as SYSTEM
+    /// with highest privileges; user scope runs at the user's logon.
+    #[must_use]
+    pub fn create_args(&self) -> Vec<String> {
+        let command = format!("\"{}\" daemon", self.config.binary_path.display());
+        let mut args = vec![
+            "/Create".to_string(),
+            "/TN".to_string(),
+            SCHEDULED_TASK_NAME.to_string(),
+            "/TR".to_string(),
+            command,
+        ];
+        if self.config.user_scope {
+            args.extend(["/SC", "ONLOGON"].map(String::from));
+        } else {
+            args.extend(["/SC", "ONSTART", "/RU", "SYSTEM", "/RL", "HIGHEST"].map(String::from));
+        }
+        args.push("/F".to_string());
+        args
+    }
+
+    /// Run schtasks with the given arguments.
+    #[allow(clippy::unused_self)]
+    fn run_schtasks(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("schtasks")
+            .args(args)
+            .output()
+            .map_err(|source| SbhError::Io {
+                path: PathBuf::from("schtasks"),
+                source,
+            })?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if output.status.success() {
+            Ok(stdout.trim().to_string())
+        } else {
+            Err(SbhError::Runtime {
+                details: format!(
+                    "schtasks {} failed (exit {}): {}",
+                    args.join(" "),
+                    output.status.code().unwrap_or(-1),
+                    stderr.trim()
+                ),
+            })
+        }
+    }
+
+    /// Run schtasks without erroring on failure.
+    #[allow(clippy::unused_self)]
+    fn run_schtasks_lenient(&self, args: &[&str]) -> String {
+        let output = Command::new("schtasks").args(args).output();
+        match output {
+            Ok(o) => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+            Err(_) => String::new(),
+        }
+    }
+}
+
+impl ServiceManager for ScheduledTaskServiceManager {
+    fn install(&self) -> Result<()> {
+        let args = self.create_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run_schtasks(&args)?;
+        Ok(())
+    }
+
+    fn uninstall(&self) -> Result<()> {
+        // Stop a running daemon first (lenient — may not be running).
+        self.run_schtasks_lenient(&["/End", "/TN", SCHEDULED_TASK_NAME]);
+        self.run_schtasks(&["/Delete", "/TN", SCHEDULED_TASK_NAME, "/F"])?;
+        Ok(())
+    }
+
+    fn status(&self) -> Result<String> {
+        let output =
+            self.run_schtasks_lenient(&["/Query", "/TN", SCHEDULED_TASK_NAME, "/FO", "CSV", "/NH"]);
+        Ok(parse_schtasks_status(&output).unwrap_or_else(|| "not installed".to_string()))
+    }
+}
+
+/// Status column of `schtasks /Query /FO CSV /NH` output
+/// (`"\sbh","N/A","Running"` -> `running`).
+fn parse_schtasks_status(output: &str) -> Option<String> {
+    let line = output.lines().find(|line| !line.trim().is_empty())?;
+    let status = line.rsplit(',').next()?.trim().trim_matches('"');
+    if status.is_empty() {
+        None
+    } else {
+        Some(status.to_ascii_lowercase())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -968,4 +1123,38 @@ mod tests {
                 .ends_with("LaunchAgents/com.sbh.daemon.plist")
         );
     }
+
+    // -- Scheduled task tests ----------------------------------------------
+
+    fn test_task_manager(user_scope: bool) -> ScheduledTaskServiceManager {
+        ScheduledTaskServiceManager::new(ScheduledTaskConfig {
+            user_scope,
+            binary_path: PathBuf::from(r"C:\Program Files\sbh\sbh.exe"),
+        })
+    }
+
+    #[test]
+    fn scheduled_task_system_scope_runs_at_boot_as_system() {
+        let args = test_task_manager(false).create_args();
+        let joined = args.join(" ");
+        assert_eq!(args[4], r#""C:\Program Files\sbh\sbh.exe" daemon"#);
+        assert!(joined.contains("/SC ONSTART /RU SYSTEM /RL HIGHEST"));
+        assert_eq!(args.last().map(String::as_str), Some("/F"));
+    }
+
+    #[test]
+    fn scheduled_task_user_scope_runs_at_logon() {
+        let joined = test_task_manager(true).create_args().join(" ");
+        assert!(joined.contains("/SC ONLOGON"));
+        assert!(!joined.contains("SYSTEM"));
+    }
+
+    #[test]
+    fn schtasks_status_column_is_parsed() {
+        assert_eq!(
+            parse_schtasks_status("\r\n\"\\sbh\",\"N/A\",\"Running\"\r\n"),
+            Some("running".to_string())
+        );
+        assert_eq!(parse_schtasks_status(""), None);
+    }
 }
@@ -1,5 +1,6 @@
 //! Signal handling: SIGTERM/SIGINT graceful shutdown, SIGHUP config reload,
-//! SIGUSR1 immediate scan trigger, and systemd watchdog heartbeat.
+//! SIGUSR1 immediate scan trigger, SIGUSR2 state dump, and systemd watchdog
+//! heartbeat.
 //!
 //! Uses the `signal-hook` crate for safe signal registration. The main loop
 //! polls `SignalHandler` flags each iteration rather than blocking on signals.
@@ -24,12 +25,14 @@ pub struct SignalHandler {
     shutdown_flag: Arc<AtomicBool>,
     reload_flag: Arc<AtomicBool>,
     scan_flag: Arc<AtomicBool>,
+    dump_flag: Arc<AtomicBool>,
 }
 
 impl SignalHandler {
     /// Create a new handler and register OS signal hooks.
     ///
-    /// On Unix: SIGTERM/SIGINT -> shutdown, SIGHUP -> reload, SIGUSR1 -> scan.
+    /// On Unix: SIGTERM/SIGINT -> shutdown, SIGHUP -> reload, SIGUSR1 -> scan,
+    /// SIGUSR2 -> state dump.
     /// Registration is best-effort; failures are logged to stderr but not fatal.
     #[must_use]
     pub fn new() -> Self {
@@ -37,6 +40,7 @@ impl SignalHandler {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
 
         handler.register_signals();
@@ -61,6 +65,19 @@ impl SignalHandler {
         self.scan_flag.swap(false, Ordering::Relaxed)
     }
 
+    /// Check (and clear) whether a diagnostic state dump has been requested.
+    #[must_use]
+    pub fn should_dump(&self) -> bool {
+        self.dump_flag.swap(false, Ordering::Relaxed)
+    }
+
+    /// The shutdown flag itself, for work that should stop early when a
+    /// shutdown arrives (the deletion executor between items).
+    #[must_use]
+    pub fn shutdown_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.shutdown_flag)
+    }
+
     /// Programmatically request shutdown (e.g., from watchdog timeout or error escalation).
     pub fn request_shutdown(&self) {
         self.shutdown_flag.store(true, Ordering::Relaxed);
@@ -76,6 +93,11 @@ impl SignalHandler {
         self.scan_flag.store(true, Ordering::Relaxed);
     }
 
+    /// Programmatically request a diagnostic state dump.
+    pub fn request_dump(&self) {
+        self.dump_flag.store(true, Ordering::Relaxed);
+    }
+
     fn register_signals(&self) {
         // SIGTERM / SIGINT -> shutdown
         if let Err(e) = signal_hook::flag::register(SIGTERM, Arc::clone(&self.shutdown_flag)) {
@@ -102,6 +124,15 @@ impl SignalHandler {
                 eprintln!("[SBH-SIGNAL] failed to register SIGUSR1: {e}");
             }
         }
+
+        // SIGUSR2 -> diagnostic state dump (Unix only)
+        #[cfg(unix)]
+        {
+            use signal_hook::consts::SIGUSR2;
+            if let Err(e) = signal_hook::flag::register(SIGUSR2, Arc::clone(&self.dump_flag)) {
+                eprintln!("[SBH-SIGNAL] failed to register SIGUSR2: {e}");
+            }
+        }
     }
 }
 
@@ -272,11 +303,13 @@ mod tests {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
 
         assert!(!handler.should_shutdown());
         assert!(!handler.should_reload());
         assert!(!handler.should_scan());
+        assert!(!handler.should_dump());
     }
 
     #[test]
@@ -285,6 +318,7 @@ mod tests {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
 
         assert!(!handler.should_shutdown());
@@ -298,6 +332,7 @@ mod tests {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
 
         handler.request_reload();
@@ -311,6 +346,7 @@ mod tests {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
 
         handler.request_scan();
@@ -318,12 +354,28 @@ mod tests {
         assert!(!handler.should_scan());
     }
 
+    #[test]
+    fn dump_flag_clears_on_read() {
+        let handler = SignalHandler {
+            shutdown_flag: Arc::new(AtomicBool::new(false)),
+            reload_flag: Arc::new(AtomicBool::new(false)),
+            scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
+        };
+
+        handler.request_dump();
+        assert!(handler.should_dump());
+        assert!(!handler.should_dump());
+        assert!(!handler.should_scan());
+    }
+
     #[test]
     fn handler_is_clone_and_send() {
         let handler = SignalHandler {
             shutdown_flag: Arc::new(AtomicBool::new(false)),
             reload_flag: Arc::new(AtomicBool::new(false)),
             scan_flag: Arc::new(AtomicBool::new(false)),
+            dump_flag: Arc::new(AtomicBool::new(false)),
         };
         let h2 = handler.clone();
 
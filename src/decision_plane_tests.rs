@@ -8,22 +8,27 @@
 //! 4. Merkle incremental equivalence properties
 //! 5. Fallback dominance under uncertainty/error states
 //!
+//! plus PID pressure-controller properties over random free-space trajectories.
+//!
 //! Uses seeded RNG for reproducible randomized fixtures.
 
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::daemon::policy::{ActiveMode, FallbackReason, PolicyConfig, PolicyEngine};
 use crate::monitor::guardrails::{
     AdaptiveGuard, CalibrationObservation, GuardDiagnostics, GuardStatus, GuardrailConfig,
 };
+use crate::monitor::pid::{
+    PidPressureController, PressureLevel, PressureReading, PressureResponse,
+};
 use crate::scanner::decision_record::{
     ActionRecord, DecisionRecordBuilder, ExplainLevel, PolicyMode, format_explain,
 };
 use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
 use crate::scanner::scoring::{
     CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome, EvidenceLedger, EvidenceTerm,
-    ScoreFactors, ScoringEngine,
+    ScoreFactors, ScoringEngine, ScoringInputs,
 };
 
 /// Returns a PolicyConfig starting in Observe mode (for lifecycle tests).
@@ -86,6 +91,7 @@ fn make_candidate(
     CandidateInput {
         path: PathBuf::from(path),
         size_bytes: size_gib * 1_073_741_824,
+        disk_bytes: size_gib * 1_073_741_824,
         age: Duration::from_secs(age_hours * 3600),
         classification: ArtifactClassification {
             pattern_name: ".target*".into(),
@@ -781,6 +787,269 @@ fn property_policy_engine_invariants_under_random_operations() {
     }
 }
 
+// ════════════════════════════════════════════════════════════
+// PID PRESSURE CONTROLLER PROPERTIES over random trajectories
+// ════════════════════════════════════════════════════════════
+
+/// Default pressure thresholds: green, yellow, orange, red minimum free %.
+const PID_THRESHOLDS: [f64; 4] = [20.0, 14.0, 10.0, 6.0];
+
+/// Total bytes of the simulated mount; 1% is 10 000 bytes.
+const PID_TOTAL_BYTES: u64 = 1_000_000;
+
+fn pid_controller(hysteresis: f64) -> PidPressureController {
+    let [green, yellow, orange, red] = PID_THRESHOLDS;
+    PidPressureController::new(
+        0.25,
+        0.08,
+        0.02,
+        100.0,
+        18.0,
+        hysteresis,
+        green,
+        yellow,
+        orange,
+        red,
+        Duration::from_secs(1),
+    )
+}
+
+/// Feed one reading per second, returning each tick's response.
+fn run_pid_trajectory(
+    pid: &mut PidPressureController,
+    start: Instant,
+    first_tick: usize,
+    free_pcts: &[f64],
+) -> Vec<PressureResponse> {
+    free_pcts
+        .iter()
+        .enumerate()
+        .map(|(i, &pct)| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let free_bytes = (pct.clamp(0.0, 100.0) * 10_000.0).round() as u64;
+            let tick = u64::try_from(first_tick + i).expect("tick fits in u64");
+            pid.update(
+                PressureReading {
+                    free_bytes,
+                    total_bytes: PID_TOTAL_BYTES,
+                    mount: PathBuf::from("/data"),
+                },
+                None,
+                start + Duration::from_secs(tick),
+            )
+        })
+        .collect()
+}
+
+/// The level a reading alone calls for, with no hysteresis.
+fn level_for(free_pct: f64) -> PressureLevel {
+    let [green, yellow, orange, red] = PID_THRESHOLDS;
+    if free_pct < red {
+        PressureLevel::Critical
+    } else if free_pct < orange {
+        PressureLevel::Red
+    } else if free_pct < yellow {
+        PressureLevel::Orange
+    } else if free_pct < green {
+        PressureLevel::Yellow
+    } else {
+        PressureLevel::Green
+    }
+}
+
+/// Free % separating `level` from the next calmer one.
+fn upper_boundary(level: PressureLevel) -> f64 {
+    let [green, yellow, orange, red] = PID_THRESHOLDS;
+    match level {
+        PressureLevel::Green => f64::INFINITY,
+        PressureLevel::Yellow => green,
+        PressureLevel::Orange => yellow,
+        PressureLevel::Red => orange,
+        PressureLevel::Critical => red,
+    }
+}
+
+fn severity(level: PressureLevel) -> u8 {
+    match level {
+        PressureLevel::Green => 0,
+        PressureLevel::Yellow => 1,
+        PressureLevel::Orange => 2,
+        PressureLevel::Red => 3,
+        PressureLevel::Critical => 4,
+    }
+}
+
+/// Random walk over free %, with occasional sudden fills and frees.
+fn random_free_trajectory(rng: &mut SeededRng, len: usize) -> Vec<f64> {
+    let mut pct = rng.next_f64().mul_add(40.0, 1.0);
+    (0..len)
+        .map(|_| {
+            let roll = rng.next_f64();
+            if roll < 0.05 {
+                // A build dumps gigabytes at once.
+                pct -= rng.next_f64() * 25.0;
+            } else if roll < 0.10 {
+                // A cleanup pass or ballast release lands.
+                pct += rng.next_f64() * 25.0;
+            } else {
+                pct += (rng.next_f64() - 0.5) * 3.0;
+            }
+            pct = pct.clamp(0.0, 60.0);
+            pct
+        })
+        .collect()
+}
+
+fn random_hysteresis(rng: &mut SeededRng) -> f64 {
+    // Stays below the narrowest band (4%) so every level is reachable.
+    rng.next_f64().mul_add(2.5, 0.5)
+}
+
+#[test]
+fn property_pid_escalations_match_the_reading_and_relief_steps_once() {
+    for seed in 0..50 {
+        let mut rng = SeededRng::new(seed * 29 + 5);
+        let hysteresis = random_hysteresis(&mut rng);
+        let mut pid = pid_controller(hysteresis);
+        let free_pcts = random_free_trajectory(&mut rng, 400);
+        let responses = run_pid_trajectory(&mut pid, Instant::now(), 0, &free_pcts);
+
+        let mut prev = PressureLevel::Green;
+        for (step, (&pct, response)) in free_pcts.iter().zip(&responses).enumerate() {
+            let level = response.level;
+            let justified = level_for(pct);
+            let ctx = format!("seed={seed} step={step} free={pct:.3}% h={hysteresis:.2}");
+
+            // Never calmer than the reading: pressure is never under-reported.
+            assert!(level >= justified, "{ctx}: {level:?} < {justified:?}");
+            if level > prev {
+                // Any jump, Green -> Critical included, lands exactly where
+                // the reading puts it and no further.
+                assert_eq!(level, justified, "{ctx}: escalated past the data");
+            } else if level < prev {
+                assert_eq!(
+                    severity(level) + 1,
+                    severity(prev),
+                    "{ctx}: relief skipped a level ({prev:?} -> {level:?})"
+                );
+                assert!(
+                    pct >= upper_boundary(prev) + hysteresis,
+                    "{ctx}: left {prev:?} inside the hysteresis band"
+                );
+            }
+            assert!((0.0..=1.0).contains(&response.urgency), "{ctx}");
+            prev = level;
+        }
+    }
+}
+
+#[test]
+fn property_pid_level_cycles_span_at_least_the_hysteresis_window() {
+    for seed in 0..50 {
+        let mut rng = SeededRng::new(seed * 31 + 11);
+        let hysteresis = random_hysteresis(&mut rng);
+        let mut pid = pid_controller(hysteresis);
+        let free_pcts = random_free_trajectory(&mut rng, 400);
+        let responses = run_pid_trajectory(&mut pid, Instant::now(), 0, &free_pcts);
+
+        // Lowest free % seen since the current level was entered by escalation.
+        let mut low_since_escalation: Option<f64> = None;
+        let mut prev = PressureLevel::Green;
+        for (step, (&pct, response)) in free_pcts.iter().zip(&responses).enumerate() {
+            let level = response.level;
+            if level > prev {
+                low_since_escalation = Some(pct);
+            } else if level < prev {
+                if let Some(low) = low_since_escalation {
+                    assert!(
+                        pct - low >= hysteresis,
+                        "seed={seed} step={step}: {prev:?} -> {level:?} after a swing of \
+                         {:.3}% (< {hysteresis:.2}%)",
+                        pct - low
+                    );
+                }
+                low_since_escalation = None;
+            } else if let Some(low) = low_since_escalation.as_mut() {
+                *low = low.min(pct);
+            }
+            prev = level;
+        }
+    }
+}
+
+#[test]
+fn property_pid_jitter_inside_the_hysteresis_band_never_flaps() {
+    let [green, yellow, orange, red] = PID_THRESHOLDS;
+    for seed in 0..40 {
+        let mut rng = SeededRng::new(seed * 37 + 19);
+        let hysteresis = random_hysteresis(&mut rng);
+        let boundary = [green, yellow, orange, red][usize::try_from(seed % 4).unwrap()];
+        let mut pid = pid_controller(hysteresis);
+        let start = Instant::now();
+
+        // Settle at the level just above the boundary first.
+        let settled =
+            run_pid_trajectory(&mut pid, start, 0, &[0.45f64.mul_add(hysteresis, boundary)]);
+        let mut prev = settled[0].level;
+        assert_eq!(prev, level_for(boundary), "seed={seed}");
+
+        // Then hover around the boundary, never moving further than 45% of
+        // the hysteresis window from it in either direction.
+        let free_pcts: Vec<f64> = (0..300)
+            .map(|_| (rng.next_f64() - 0.5).mul_add(0.9 * hysteresis, boundary))
+            .collect();
+        let responses = run_pid_trajectory(&mut pid, start, 1, &free_pcts);
+
+        let mut changes = 0;
+        for response in &responses {
+            if response.level != prev {
+                changes += 1;
+            }
+            prev = response.level;
+        }
+        // At most the first dip below the boundary; the swing back up never
+        // clears boundary + hysteresis.
+        assert!(
+            changes <= 1,
+            "seed={seed}: {changes} level changes hovering at {boundary}% (h={hysteresis:.2})"
+        );
+    }
+}
+
+#[test]
+fn property_pid_recovers_to_green_under_sustained_free_space() {
+    let [green, ..] = PID_THRESHOLDS;
+    for seed in 0..50 {
+        let mut rng = SeededRng::new(seed * 41 + 23);
+        let hysteresis = random_hysteresis(&mut rng);
+        let mut pid = pid_controller(hysteresis);
+        let start = Instant::now();
+        let history = random_free_trajectory(&mut rng, 200);
+        let _ = run_pid_trajectory(&mut pid, start, 0, &history);
+
+        let healthy = rng.next_f64().mul_add(30.0, green + hysteresis);
+        let recovery = run_pid_trajectory(&mut pid, start, history.len(), &[healthy; 20]);
+
+        // Relief steps one level per tick, so four ticks reach Green from
+        // anywhere; after that it must stay there with nothing to release.
+        for (tick, response) in recovery.iter().enumerate().skip(4) {
+            assert_eq!(
+                response.level,
+                PressureLevel::Green,
+                "seed={seed} tick={tick}: stuck at {:?} with {healthy:.2}% free",
+                response.level
+            );
+            assert_eq!(response.release_ballast_files, 0, "seed={seed} tick={tick}");
+        }
+        let last = recovery.last().unwrap();
+        assert!(
+            last.urgency < 0.01,
+            "seed={seed}: urgency {:.4} after sustained free space",
+            last.urgency
+        );
+    }
+}
+
 // ════════════════════════════════════════════════════════════
 // PROOF HARNESS: Replay, Fault-Injection, and Reproducibility
 // (bd-izu.6)
@@ -2205,6 +2474,7 @@ fn make_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             size: 0.70,
             structure: 0.95,
             pressure_multiplier: 1.5,
+            regeneration_cost: 1.0,
         },
         vetoed: false,
         veto_reason: None,
@@ -2216,6 +2486,7 @@ fn make_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             combined_confidence: 0.92,
         },
         size_bytes: 3_000_000_000,
+        disk_bytes: 3_000_000_000,
         age: Duration::from_secs(5 * 3600),
         decision: DecisionOutcome {
             action,
@@ -2234,5 +2505,6 @@ fn make_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             }],
             summary: "test".to_string(),
         },
+        inputs: ScoringInputs::default(),
     }
 }
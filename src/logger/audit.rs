@@ -0,0 +1,550 @@
+//! Deletion audit trail: a manifest of every batch, written before the batch
+//! removes anything.
+//!
+//! A manifest records each path sbh is about to delete, relocate, or
+//! quarantine, with its size, score and factors, the policy mode and decision
+//! ID that approved it, and a fingerprint of its top-level entries. Manifests
+//! are appended to `audit.jsonl` next to the activity log and mirrored into
+//! the `deletion_manifests` SQLite tables, so `sbh audit list|show` can answer
+//! "what did sbh remove, and why?" long after the fact.
+//!
+//! The write is synchronous rather than going through the activity logger's
+//! channel, which drops events under back-pressure. A failed write is reported
+//! but never blocks the batch: a disk too full to take the audit line is
+//! exactly when sbh must still free space.
+
+#![allow(missing_docs)]
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::errors::{Result, SbhError};
+use crate::logger::jsonl::ScoreFactorsRecord;
+use crate::scanner::merkle::hash_hex;
+use crate::scanner::scoring::{CandidacyScore, ScoringInputs};
+
+/// File name of the audit log, a sibling of the activity JSONL log.
+pub const AUDIT_FILE_NAME: &str = "audit.jsonl";
+
+/// Top-level entries listed per item. The digest still covers all of them.
+pub const LISTED_ENTRIES: usize = 64;
+
+/// Listed regular files up to this size get a content hash.
+pub const HASHED_FILE_MAX_BYTES: u64 = 1024 * 1024;
+
+/// `audit.jsonl` is moved to `audit.jsonl.1` once it grows past this.
+const ROTATE_BYTES: u64 = 64 * 1024 * 1024;
+
+static BATCH_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Everything one deletion batch was about to do, captured before it started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletionManifest {
+    /// `<UTC timestamp>-<pid>-<sequence>`, unique per process run.
+    pub batch_id: String,
+    /// RFC 3339 time the manifest was captured.
+    pub created_at: String,
+    /// What ran the batch: `daemon`, `clean`, `runner-hook`, ...
+    pub source: String,
+    /// Policy mode in force (`observe`, `canary`, `enforce`), when a policy
+    /// engine approved the batch.
+    #[serde(default)]
+    pub policy_mode: Option<String>,
+    pub items: Vec<ManifestItem>,
+}
+
+impl DeletionManifest {
+    /// A manifest stamped with the current time and a fresh batch ID.
+    #[must_use]
+    pub fn new(source: &str, policy_mode: Option<String>, items: Vec<ManifestItem>) -> Self {
+        let now = Utc::now();
+        let seq = BATCH_SEQ.fetch_add(1, Ordering::Relaxed) + 1;
+        Self {
+            batch_id: format!(
+                "{}-{}-{seq}",
+                now.format("%Y%m%dT%H%M%SZ"),
+                std::process::id()
+            ),
+            created_at: now.to_rfc3339_opts(SecondsFormat::Millis, true),
+            source: source.to_string(),
+            policy_mode,
+            items,
+        }
+    }
+
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.items.iter().map(|item| item.size_bytes).sum()
+    }
+
+    /// Whether any item is `path`, one of its ancestors, or one of its
+    /// descendants.
+    #[must_use]
+    pub fn touches(&self, path: &Path) -> bool {
+        self.items
+            .iter()
+            .any(|item| item.path.starts_with(path) || path.starts_with(&item.path))
+    }
+}
+
+/// What the executor planned to do with an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestAction {
+    /// Remove outright. A held-open file in a truncate category is
+    /// truncated instead; the activity log records which happened.
+    Delete,
+    /// Copy to the archive, verify, then remove.
+    Relocate,
+    /// Rename into the mount's quarantine.
+    Quarantine,
+}
+
+impl ManifestAction {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Relocate => "relocate",
+            Self::Quarantine => "quarantine",
+        }
+    }
+}
+
+/// One candidate in a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestItem {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub score: f64,
+    pub factors: ScoreFactorsRecord,
+    /// Classifier pattern that matched, e.g. `target-dir`.
+    pub pattern: String,
+    /// Artifact category config name, e.g. `rust_target`.
+    pub category: String,
+    pub action: ManifestAction,
+    /// Policy decision record that approved this path, when there was one.
+    #[serde(default)]
+    pub decision_id: Option<u64>,
+    pub top_level: TopLevelListing,
+    /// What the score was computed from, for `sbh explain --rescore`.
+    /// Absent in manifests written before sbh recorded it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<ScoringInputs>,
+}
+
+impl ManifestItem {
+    /// Describe `candidate`, fingerprinting its top level as it is now.
+    #[must_use]
+    pub fn from_candidate(
+        candidate: &CandidacyScore,
+        action: ManifestAction,
+        decision_id: Option<u64>,
+    ) -> Self {
+        let f = &candidate.factors;
+        Self {
+            path: candidate.path.clone(),
+            size_bytes: candidate.size_bytes,
+            score: candidate.total_score,
+            factors: ScoreFactorsRecord {
+                location: f.location,
+                name: f.name,
+                age: f.age,
+                size: f.size,
+                structure: f.structure,
+            },
+            pattern: candidate.classification.pattern_name.to_string(),
+            category: candidate.classification.category.config_name().to_string(),
+            action,
+            decision_id,
+            top_level: TopLevelListing::capture(&candidate.path),
+            inputs: Some(candidate.inputs.clone()),
+        }
+    }
+}
+
+/// Fingerprint of a candidate's immediate contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopLevelListing {
+    /// Number of top-level entries (1 for a file candidate).
+    pub total: usize,
+    /// SHA-256 over every entry's kind, name, size, and content hash.
+    pub sha256: String,
+    /// The first [`LISTED_ENTRIES`] entries by name.
+    pub entries: Vec<TopLevelEntry>,
+    /// Why the listing is empty, when the candidate could not be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopLevelEntry {
+    pub name: String,
+    /// `file`, `dir`, `symlink`, or `other`.
+    pub kind: String,
+    /// Apparent size; directories are not walked.
+    pub size_bytes: u64,
+    /// Content hash of listed regular files up to [`HASHED_FILE_MAX_BYTES`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+impl TopLevelListing {
+    /// Fingerprint `path`: its immediate children for a directory, the file
+    /// itself otherwise. Symlinks are listed, never followed.
+    #[must_use]
+    pub fn capture(path: &Path) -> Self {
+        match read_top_level(path) {
+            Ok(mut raw) => {
+                raw.sort_by(|a, b| a.0.cmp(&b.0));
+                let mut digest = Sha256::new();
+                let mut entries = Vec::with_capacity(raw.len().min(LISTED_ENTRIES));
+                for (i, (name, meta, entry_path)) in raw.iter().enumerate() {
+                    let listed = i < LISTED_ENTRIES;
+                    let sha256 = (listed
+                        && meta.file_type().is_file()
+                        && meta.len() <= HASHED_FILE_MAX_BYTES)
+                        .then(|| hash_file(entry_path).ok())
+                        .flatten();
+                    let kind = entry_kind(meta);
+                    digest.update(
+                        format!(
+                            "{kind}\t{name}\t{}\t{}\n",
+                            meta.len(),
+                            sha256.as_deref().unwrap_or("-")
+                        )
+                        .as_bytes(),
+                    );
+                    if listed {
+                        entries.push(TopLevelEntry {
+                            name: name.clone(),
+                            kind: kind.to_string(),
+                            size_bytes: meta.len(),
+                            sha256,
+                        });
+                    }
+                }
+                Self {
+                    total: raw.len(),
+                    sha256: hash_hex(&digest.finalize().into()),
+                    entries,
+                    error: None,
+                }
+            }
+            Err(e) => Self {
+                sha256: hash_hex(&Sha256::digest(b"").into()),
+                error: Some(e.to_string()),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+type RawEntry = (String, fs::Metadata, PathBuf);
+
+fn read_top_level(path: &Path) -> io::Result<Vec<RawEntry>> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() {
+        let name = path
+            .file_name()
+            .map_or_else(|| path.to_string_lossy(), |n| n.to_string_lossy())
+            .into_owned();
+        return Ok(vec![(name, meta, path.to_path_buf())]);
+    }
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        // DirEntry::metadata does not traverse symlinks.
+        let meta = entry.metadata()?;
+        entries.push((
+            entry.file_name().to_string_lossy().into_owned(),
+            meta,
+            entry.path(),
+        ));
+    }
+    Ok(entries)
+}
+
+fn entry_kind(meta: &fs::Metadata) -> &'static str {
+    let ft = meta.file_type();
+    if ft.is_symlink() {
+        "symlink"
+    } else if ft.is_dir() {
+        "dir"
+    } else if ft.is_file() {
+        "file"
+    } else {
+        "other"
+    }
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hash_hex(&hasher.finalize().into()))
+}
+
+// ──────────────────── storage ────────────────────
+
+/// Audit log path for the given activity JSONL path.
+#[must_use]
+pub fn audit_path(jsonl_log: &Path) -> PathBuf {
+    jsonl_log.with_file_name(AUDIT_FILE_NAME)
+}
+
+/// Where manifests are written: the JSONL audit file, plus the activity
+/// database when one is configured.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    jsonl_path: PathBuf,
+    sqlite_path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub const fn new(jsonl_path: PathBuf, sqlite_path: Option<PathBuf>) -> Self {
+        Self {
+            jsonl_path,
+            sqlite_path,
+        }
+    }
+
+    /// The audit log for configured paths: `audit.jsonl` beside `jsonl_log`
+    /// and the `sqlite_db` activity database.
+    #[must_use]
+    pub fn for_paths(paths: &crate::core::config::PathsConfig) -> Self {
+        Self::new(audit_path(&paths.jsonl_log), Some(paths.sqlite_db.clone()))
+    }
+
+    #[must_use]
+    pub fn jsonl_path(&self) -> &Path {
+        &self.jsonl_path
+    }
+
+    /// Append `manifest` to the JSONL file (fsynced) and insert it into
+    /// SQLite. Both writes are attempted; the first failure is returned.
+    pub fn record(&self, manifest: &DeletionManifest) -> Result<()> {
+        let jsonl = append_jsonl(&self.jsonl_path, manifest);
+        #[cfg(feature = "sqlite")]
+        let sqlite = self.sqlite_path.as_deref().map_or(Ok(()), |path| {
+            crate::logger::sqlite::SqliteLogger::open(path)?.log_manifest(manifest)
+        });
+        #[cfg(not(feature = "sqlite"))]
+        let sqlite: Result<()> = Ok(());
+        jsonl.and(sqlite)
+    }
+
+    /// Manifests newest first, optionally only those touching `path`.
+    ///
+    /// Reads the database when it exists and opens, the JSONL file otherwise.
+    pub fn list(&self, limit: usize, path: Option<&Path>) -> Result<Vec<DeletionManifest>> {
+        #[cfg(feature = "sqlite")]
+        if let Some(db) = self.open_existing_db() {
+            return db.recent_manifests(limit, path);
+        }
+        let mut manifests = read_jsonl(&self.jsonl_path)?;
+        manifests.retain(|m| path.is_none_or(|p| m.touches(p)));
+        manifests.reverse();
+        manifests.truncate(limit);
+        Ok(manifests)
+    }
+
+    /// The manifest with `batch_id`, from the database or the JSONL file.
+    pub fn find(&self, batch_id: &str) -> Result<Option<DeletionManifest>> {
+        #[cfg(feature = "sqlite")]
+        if let Some(db) = self.open_existing_db()
+            && let Some(manifest) = db.manifest(batch_id)?
+        {
+            return Ok(Some(manifest));
+        }
+        Ok(read_jsonl(&self.jsonl_path)?
+            .into_iter()
+            .rev()
+            .find(|m| m.batch_id == batch_id))
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn open_existing_db(&self) -> Option<crate::logger::sqlite::SqliteLogger> {
+        let path = self.sqlite_path.as_deref().filter(|p| p.exists())?;
+        crate::logger::sqlite::SqliteLogger::open(path).ok()
+    }
+}
+
+fn append_jsonl(path: &Path, manifest: &DeletionManifest) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+    }
+    if fs::metadata(path).is_ok_and(|meta| meta.len() >= ROTATE_BYTES) {
+        let rotated = path.with_extension("jsonl.1");
+        fs::rename(path, &rotated).map_err(|e| SbhError::io(path, e))?;
+    }
+    let mut line = serde_json::to_string(manifest)?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| SbhError::io(path, e))?;
+    file.write_all(line.as_bytes())
+        .and_then(|()| file.sync_data())
+        .map_err(|e| SbhError::io(path, e))
+}
+
+/// Every manifest in a JSONL audit file, oldest first. Lines that do not
+/// parse (a torn final write) are skipped; a missing file is empty.
+pub fn read_jsonl(path: &Path) -> Result<Vec<DeletionManifest>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(SbhError::io(path, e)),
+    };
+    let mut manifests = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| SbhError::io(path, e))?;
+        if let Ok(manifest) = serde_json::from_str(&line) {
+            manifests.push(manifest);
+        }
+    }
+    Ok(manifests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &Path, size_bytes: u64) -> ManifestItem {
+        ManifestItem {
+            path: path.to_path_buf(),
+            size_bytes,
+            score: 0.9,
+            factors: ScoreFactorsRecord {
+                location: 0.8,
+                name: 0.9,
+                age: 1.0,
+                size: 0.5,
+                structure: 0.9,
+            },
+            pattern: "target-dir".to_string(),
+            category: "rust_target".to_string(),
+            action: ManifestAction::Delete,
+            decision_id: Some(7),
+            top_level: TopLevelListing::capture(path),
+            inputs: None,
+        }
+    }
+
+    #[test]
+    fn listing_fingerprints_names_sizes_and_small_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir_all(target.join("debug")).unwrap();
+        fs::write(
+            target.join("CACHEDIR.TAG"),
+            "Signature: 8a477f597d28d172789f06886806bc55",
+        )
+        .unwrap();
+        fs::write(target.join(".rustc_info.json"), "{}").unwrap();
+
+        let listing = TopLevelListing::capture(&target);
+        assert_eq!(listing.total, 3);
+        assert_eq!(listing.error, None);
+        let names: Vec<_> = listing.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, [".rustc_info.json", "CACHEDIR.TAG", "debug"]);
+        assert_eq!(listing.entries[2].kind, "dir");
+        assert_eq!(listing.entries[2].sha256, None);
+        assert_eq!(
+            listing.entries[0].sha256.as_deref(),
+            Some("44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a")
+        );
+        assert_eq!(TopLevelListing::capture(&target), listing);
+
+        fs::write(target.join(".rustc_info.json"), "{ }").unwrap();
+        assert_ne!(TopLevelListing::capture(&target).sha256, listing.sha256);
+
+        let file = TopLevelListing::capture(&target.join("CACHEDIR.TAG"));
+        assert_eq!(file.total, 1);
+        assert_eq!(file.entries[0].name, "CACHEDIR.TAG");
+
+        let gone = TopLevelListing::capture(&dir.path().join("missing"));
+        assert_eq!(gone.total, 0);
+        assert!(gone.error.is_some());
+    }
+
+    #[test]
+    fn listing_caps_entries_but_digests_all_of_them() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..=LISTED_ENTRIES {
+            fs::write(dir.path().join(format!("f{i:03}")), "x").unwrap();
+        }
+        let listing = TopLevelListing::capture(dir.path());
+        assert_eq!(listing.total, LISTED_ENTRIES + 1);
+        assert_eq!(listing.entries.len(), LISTED_ENTRIES);
+
+        fs::write(dir.path().join(format!("f{LISTED_ENTRIES:03}")), "xy").unwrap();
+        assert_ne!(TopLevelListing::capture(dir.path()).sha256, listing.sha256);
+    }
+
+    #[test]
+    fn manifests_round_trip_through_the_jsonl_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = audit_path(&dir.path().join("activity.jsonl"));
+        assert_eq!(path, dir.path().join("audit.jsonl"));
+        let log = AuditLog::new(path.clone(), None);
+        assert!(log.list(10, None).unwrap().is_empty());
+
+        let project = dir.path().join("proj");
+        fs::create_dir_all(project.join("target")).unwrap();
+        fs::create_dir_all(project.join("node_modules")).unwrap();
+        let first = DeletionManifest::new(
+            "daemon",
+            Some("enforce".to_string()),
+            vec![item(&project.join("target"), 4096)],
+        );
+        let second =
+            DeletionManifest::new("clean", None, vec![item(&project.join("node_modules"), 1)]);
+        assert_ne!(first.batch_id, second.batch_id);
+        log.record(&first).unwrap();
+        log.record(&second).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"batch_id\":\"torn").unwrap();
+
+        let listed = log.list(10, None).unwrap();
+        let ids: Vec<_> = listed.iter().map(|m| m.batch_id.as_str()).collect();
+        assert_eq!(ids, [second.batch_id.as_str(), first.batch_id.as_str()]);
+        assert_eq!(log.list(1, None).unwrap().len(), 1);
+
+        let under_target = project.join("target/debug/deps");
+        let touching = log.list(10, Some(&under_target)).unwrap();
+        assert_eq!(touching.len(), 1);
+        assert_eq!(touching[0].batch_id, first.batch_id);
+        assert_eq!(log.list(10, Some(&project)).unwrap().len(), 2);
+
+        let found = log.find(&first.batch_id).unwrap().unwrap();
+        assert_eq!(found.policy_mode.as_deref(), Some("enforce"));
+        assert_eq!(found.items[0].decision_id, Some(7));
+        assert_eq!(found.total_bytes(), 4096);
+        assert!(log.find("nope").unwrap().is_none());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn manifests_are_mirrored_into_sqlite() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("activity.sqlite3");
+        let log = AuditLog::new(dir.path().join("audit.jsonl"), Some(db));
+        let manifest = DeletionManifest::new("clean", None, vec![item(dir.path(), 10)]);
+        log.record(&manifest).unwrap();
+
+        // With the JSONL file gone, reads still come from the database.
+        fs::remove_file(log.jsonl_path()).unwrap();
+        let found = log.find(&manifest.batch_id).unwrap().unwrap();
+        assert_eq!(found.items[0].path, dir.path());
+        assert_eq!(log.list(10, Some(dir.path())).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,375 @@
+//! Process-wide diagnostic sink for internal debug detail.
+//!
+//! Separate from the activity logs (SQLite/JSONL): this carries developer-
+//! facing diagnostics such as config resolution and per-root scan timing.
+//! Nothing is emitted until [`init`] installs a sink, so library consumers
+//! stay silent by default. The CLI wires this to `--log-level`/`--log-file`;
+//! the daemon falls back to the `[logging]` config section and additionally
+//! reports its decisions as named [`event`]s.
+//!
+//! With [`keep_recent`], the last info-or-worse lines are also kept in
+//! memory whatever the sink's level, so a crash report can show what led up
+//! to a panic.
+
+#![allow(missing_docs)]
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::core::errors::{Result, SbhError};
+
+/// Diagnostic verbosity, most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl DiagLevel {
+    /// Every level, most to least severe.
+    pub const ALL: [Self; 5] = [
+        Self::Error,
+        Self::Warn,
+        Self::Info,
+        Self::Debug,
+        Self::Trace,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for DiagLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DiagLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warn" | "warning" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            other => Err(format!(
+                "invalid log level {other:?}: expected error, warn, info, debug, or trace"
+            )),
+        }
+    }
+}
+
+/// How each diagnostic line is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagFormat {
+    /// `<ts> <LEVEL> [<component>] <message>`.
+    #[default]
+    Human,
+    /// One JSON object per line, with event fields as top-level keys.
+    Json,
+}
+
+impl DiagFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Human => "human",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for DiagFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DiagFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "human" | "text" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "invalid log format {other:?}: expected human or json"
+            )),
+        }
+    }
+}
+
+/// Where diagnostics are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagTarget {
+    Stderr,
+    File(PathBuf),
+}
+
+enum Sink {
+    Stderr,
+    File(File),
+}
+
+/// 0 = disabled; otherwise the `DiagLevel` discriminant.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static JSON: AtomicBool = AtomicBool::new(false);
+static SINK: OnceLock<Mutex<Sink>> = OnceLock::new();
+/// Lines [`recent`] returns at most; 0 keeps none.
+static RECENT_CAPACITY: AtomicUsize = AtomicUsize::new(0);
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Install the process-wide sink. Only the first call takes effect.
+pub fn init(level: DiagLevel, format: DiagFormat, target: &DiagTarget) -> Result<()> {
+    let sink = match target {
+        DiagTarget::Stderr => Sink::Stderr,
+        DiagTarget::File(path) => Sink::File(open_log_file(path)?),
+    };
+    if SINK.set(Mutex::new(sink)).is_ok() {
+        JSON.store(format == DiagFormat::Json, Ordering::Relaxed);
+        MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+fn open_log_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| SbhError::io(path, e))
+}
+
+/// Keep the last `capacity` info, warn, and error lines for [`recent`].
+pub fn keep_recent(capacity: usize) {
+    RECENT_CAPACITY.store(capacity, Ordering::Relaxed);
+    let mut recent = RECENT.lock();
+    let excess = recent.len().saturating_sub(capacity);
+    recent.drain(..excess);
+}
+
+/// The kept lines, oldest first. Empty if the buffer is busy, so a panic
+/// hook never waits on it.
+#[must_use]
+pub fn recent() -> Vec<String> {
+    RECENT
+        .try_lock()
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn remember(recent: &mut VecDeque<String>, capacity: usize, line: &str) {
+    while recent.len() >= capacity.max(1) {
+        recent.pop_front();
+    }
+    recent.push_back(line.trim_end().to_string());
+}
+
+/// Whether a message at `level` would be written. Use to skip costly formatting.
+#[must_use]
+pub fn enabled(level: DiagLevel) -> bool {
+    (level as u8) <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Emit one diagnostic line: `<ts> <LEVEL> [<component>] <message>`.
+pub fn log(level: DiagLevel, component: &str, message: impl fmt::Display) {
+    emit(level, component, None, &message);
+}
+
+/// Emit a named event with structured `fields` (a JSON object). Human lines
+/// show only the message; JSON lines also carry `event` and every field.
+///
+/// Building `fields` is not free: guard per-item events with [`enabled`].
+pub fn event(
+    level: DiagLevel,
+    component: &str,
+    event: &str,
+    fields: Value,
+    message: impl fmt::Display,
+) {
+    emit(level, component, Some((event, fields)), &message);
+}
+
+fn emit(
+    level: DiagLevel,
+    component: &str,
+    event: Option<(&str, Value)>,
+    message: &dyn fmt::Display,
+) {
+    let capacity = RECENT_CAPACITY.load(Ordering::Relaxed);
+    let keep = level <= DiagLevel::Info && capacity > 0;
+    let sink = SINK.get().filter(|_| enabled(level));
+    if !keep && sink.is_none() {
+        return;
+    }
+    let ts = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    if keep {
+        let line = format_line(&ts, level, component, message);
+        remember(&mut RECENT.lock(), capacity, &line);
+    }
+    let Some(sink) = sink else {
+        return;
+    };
+    let line = if JSON.load(Ordering::Relaxed) {
+        format_json_line(&ts, level, component, event, message)
+    } else {
+        format_line(&ts, level, component, message)
+    };
+    // Diagnostics must never fail the command they describe.
+    let _ = match &mut *sink.lock() {
+        Sink::Stderr => io::stderr().lock().write_all(line.as_bytes()),
+        Sink::File(file) => file.write_all(line.as_bytes()),
+    };
+}
+
+pub fn error(component: &str, message: impl fmt::Display) {
+    log(DiagLevel::Error, component, message);
+}
+
+pub fn warn(component: &str, message: impl fmt::Display) {
+    log(DiagLevel::Warn, component, message);
+}
+
+pub fn info(component: &str, message: impl fmt::Display) {
+    log(DiagLevel::Info, component, message);
+}
+
+pub fn debug(component: &str, message: impl fmt::Display) {
+    log(DiagLevel::Debug, component, message);
+}
+
+pub fn trace(component: &str, message: impl fmt::Display) {
+    log(DiagLevel::Trace, component, message);
+}
+
+fn format_line(ts: &str, level: DiagLevel, component: &str, message: &dyn fmt::Display) -> String {
+    format!(
+        "{ts} {:<5} [{component}] {message}\n",
+        level.as_str().to_ascii_uppercase()
+    )
+}
+
+fn format_json_line(
+    ts: &str,
+    level: DiagLevel,
+    component: &str,
+    event: Option<(&str, Value)>,
+    message: &dyn fmt::Display,
+) -> String {
+    let mut line = Map::new();
+    line.insert("ts".to_string(), json!(ts));
+    line.insert("level".to_string(), json!(level.as_str()));
+    line.insert("component".to_string(), json!(component));
+    if let Some((name, fields)) = event {
+        line.insert("event".to_string(), json!(name));
+        if let Value::Object(fields) = fields {
+            for (key, value) in fields {
+                line.entry(key).or_insert(value);
+            }
+        }
+    }
+    line.insert("message".to_string(), json!(message.to_string()));
+    let mut out = Value::Object(line).to_string();
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_parse_and_order() {
+        for level in DiagLevel::ALL {
+            assert_eq!(level.as_str().parse::<DiagLevel>(), Ok(level));
+        }
+        assert_eq!("WARNING".parse::<DiagLevel>(), Ok(DiagLevel::Warn));
+        assert!("loud".parse::<DiagLevel>().is_err());
+        assert!(DiagLevel::Error < DiagLevel::Trace);
+    }
+
+    #[test]
+    fn recent_lines_keep_only_the_newest() {
+        let mut recent = VecDeque::new();
+        for line in ["first\n", "second\n", "third\n"] {
+            remember(&mut recent, 2, line);
+        }
+        assert_eq!(recent, ["second", "third"]);
+    }
+
+    #[test]
+    fn format_line_is_single_line_with_level_and_component() {
+        let line = format_line(
+            "2026-01-01T00:00:00.000Z",
+            DiagLevel::Debug,
+            "scan",
+            &"root done",
+        );
+        assert_eq!(line, "2026-01-01T00:00:00.000Z DEBUG [scan] root done\n");
+    }
+
+    #[test]
+    fn json_line_carries_event_fields_without_clobbering_the_envelope() {
+        assert_eq!("JSON".parse::<DiagFormat>(), Ok(DiagFormat::Json));
+        assert!("xml".parse::<DiagFormat>().is_err());
+
+        let line = format_json_line(
+            "2026-01-01T00:00:00.000Z",
+            DiagLevel::Info,
+            "daemon",
+            Some((
+                "pressure_transition",
+                json!({"from": "green", "to": "red", "level": "bogus"}),
+            )),
+            &"pressure green -> red",
+        );
+        assert!(line.ends_with('\n') && !line.trim_end().contains('\n'));
+        let parsed: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["event"], "pressure_transition");
+        assert_eq!(parsed["to"], "red");
+        assert_eq!(parsed["level"], "info");
+        assert_eq!(parsed["message"], "pressure green -> red");
+
+        let plain: Value = serde_json::from_str(&format_json_line(
+            "2026-01-01T00:00:00.000Z",
+            DiagLevel::Warn,
+            "scan",
+            None,
+            &"root skipped",
+        ))
+        .unwrap();
+        assert!(plain.get("event").is_none());
+        assert_eq!(plain["component"], "scan");
+    }
+}
@@ -63,6 +63,15 @@ pub enum ActivityEvent {
         path: String,
         size_bytes: u64,
     },
+    /// Replenishment held back because the fill-rate forecast would take the
+    /// mount below its floor within the horizon.
+    BallastReplenishDeferred {
+        mount_point: String,
+        free_pct: f64,
+        projected_free_pct: f64,
+        floor_pct: f64,
+        fill_rate_bps: f64,
+    },
     ArtifactDeleted {
         path: String,
         size_bytes: u64,
@@ -71,12 +80,50 @@ pub enum ActivityEvent {
         pressure: String,
         free_pct: f64,
         duration_ms: u64,
+        /// Uid that owned the deleted path, for per-user accounting.
+        owner_uid: Option<u32>,
     },
     ArtifactDeletionFailed {
         path: String,
         error_code: String,
         error_message: String,
     },
+    /// A held-open file was truncated in place instead of unlinked.
+    ArtifactTruncated {
+        path: String,
+        size_bytes: u64,
+        score: f64,
+    },
+    /// A candidate was moved to the cold-storage archive instead of deleted.
+    ArtifactRelocated {
+        path: String,
+        archived_path: String,
+        size_bytes: u64,
+        score: f64,
+    },
+    /// A candidate was moved into its mount's quarantine instead of deleted;
+    /// its space is not reclaimed until the quarantine is purged.
+    ArtifactQuarantined {
+        path: String,
+        quarantined_path: String,
+        size_bytes: u64,
+        score: f64,
+    },
+    /// A quarantined tree was deleted, on expiry, under pressure, or by
+    /// `sbh quarantine purge`.
+    QuarantinePurged {
+        path: String,
+        quarantined_path: String,
+        size_bytes: u64,
+        reason: String,
+    },
+    /// An archived or quarantined tree was moved back (`sbh restore`,
+    /// `sbh quarantine restore`).
+    ArtifactRestored {
+        path: String,
+        archived_path: String,
+        size_bytes: u64,
+    },
     ScanCompleted {
         paths_scanned: usize,
         candidates_found: usize,
@@ -85,6 +132,10 @@ pub enum ActivityEvent {
     ConfigReloaded {
         details: String,
     },
+    /// A config reload was refused; the running config stays in force.
+    ConfigReloadRejected {
+        reason: String,
+    },
     Error {
         code: String,
         message: String,
@@ -93,6 +144,54 @@ pub enum ActivityEvent {
         details: String,
         free_pct: f64,
     },
+    /// A mount gained far less free space than a cleanup batch claimed.
+    ReclaimShortfall {
+        mount_point: String,
+        expected_bytes: u64,
+        observed_bytes: u64,
+    },
+    /// RSS neared `watchdog.memory_budget_mb` and an internal cache was shed.
+    MemoryPressure {
+        rss_bytes: u64,
+        budget_bytes: u64,
+        /// `ShedStage::as_str` of the cache dropped.
+        shed: String,
+    },
+    /// Operational context added by external tooling (`sbh annotate`),
+    /// shown on the dashboard timeline.
+    Annotation {
+        message: String,
+    },
+    /// A watched mount appeared or disappeared at runtime.
+    MountChanged {
+        mount_point: String,
+        device: String,
+        fs_type: String,
+        mounted: bool,
+    },
+    /// Per-process write growth leading into a pressure escalation.
+    AttributionSnapshot {
+        mount_point: String,
+        pressure: String,
+        free_pct: f64,
+        /// JSON-encoded `monitor::writers::AttributionSnapshot`.
+        details: String,
+    },
+    /// An agent workspace was created with a lease (`sbh workspace create`).
+    WorkspaceCreated {
+        path: String,
+        /// JSON-encoded `cli::workspace::WorkspaceLease`.
+        lease: String,
+    },
+    /// A leased workspace was deleted without scoring (`sbh workspace destroy`).
+    WorkspaceDestroyed {
+        path: String,
+        size_bytes: u64,
+        /// Bytes used beyond the lease budget at destroy time.
+        overrun_bytes: u64,
+        /// JSON-encoded `cli::workspace::WorkspaceLease`.
+        lease: String,
+    },
     /// Sentinel to request graceful shutdown of the logger thread.
     Shutdown,
 }
@@ -212,6 +311,10 @@ fn logger_thread_main(
     const PRUNE_INTERVAL: u64 = 3600;
     #[cfg(feature = "sqlite")]
     const RETENTION_DAYS: u32 = 30;
+    // Deletion manifests outlive the activity log: forensics often starts
+    // weeks after the fact.
+    #[cfg(feature = "sqlite")]
+    const MANIFEST_RETENTION_DAYS: u32 = 90;
 
     // Open backends.
     #[cfg(feature = "sqlite")]
@@ -275,7 +378,7 @@ fn logger_thread_main(
                     .map(|row| db.log_pressure(row).is_ok());
                 // Only update the failure counter when at least one write was
                 // attempted.  Events that produce no SQLite rows (e.g.
-                // ConfigReloaded) must not reset the consecutive-failure
+                // DaemonStopped) must not reset the consecutive-failure
                 // counter, otherwise the circuit breaker can never trip.
                 let any_attempted = activity_ok.is_some() || pressure_ok.is_some();
                 let all_ok = activity_ok.unwrap_or(true) && pressure_ok.unwrap_or(true);
@@ -299,6 +402,7 @@ fn logger_thread_main(
                     if let Some(db) = &sqlite {
                         let _ = db.prune_pressure_history(RETENTION_DAYS);
                         let _ = db.prune_activity_log(RETENTION_DAYS);
+                        let _ = db.prune_manifests(MANIFEST_RETENTION_DAYS);
                     }
                 }
             } else {
@@ -389,6 +493,20 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.ok = Some(true);
             e
         }
+        ActivityEvent::BallastReplenishDeferred {
+            mount_point,
+            free_pct,
+            projected_free_pct,
+            floor_pct,
+            fill_rate_bps,
+        } => {
+            let mut e = LogEntry::new(EventType::BallastReplenishDeferred, Severity::Info);
+            e.mount_point = Some(mount_point.clone());
+            e.free_pct = Some(*free_pct);
+            e.rate_bps = Some(*fill_rate_bps);
+            e.details = Some(replenish_deferred_details(*projected_free_pct, *floor_pct));
+            e
+        }
         ActivityEvent::ArtifactDeleted {
             path,
             size_bytes,
@@ -397,6 +515,7 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             pressure,
             free_pct,
             duration_ms,
+            owner_uid,
         } => {
             let mut e = LogEntry::new(EventType::ArtifactDelete, Severity::Info);
             e.path = Some(path.clone());
@@ -407,6 +526,7 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.free_pct = Some(*free_pct);
             e.duration_ms = Some(*duration_ms);
             e.ok = Some(true);
+            e.details = owner_uid.map(owner_details);
             e
         }
         ActivityEvent::ArtifactDeletionFailed {
@@ -421,6 +541,72 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.error_message = Some(error_message.clone());
             e
         }
+        ActivityEvent::ArtifactTruncated {
+            path,
+            size_bytes,
+            score,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactDelete, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.score = Some(*score);
+            e.details = Some("action=truncate".to_string());
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::ArtifactRelocated {
+            path,
+            archived_path,
+            size_bytes,
+            score,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactDelete, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.score = Some(*score);
+            e.details = Some(relocate_details(archived_path));
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::ArtifactQuarantined {
+            path,
+            quarantined_path,
+            size_bytes,
+            score,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactQuarantine, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.score = Some(*score);
+            e.details = Some(format!("quarantined_path={quarantined_path}"));
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::QuarantinePurged {
+            path,
+            quarantined_path,
+            size_bytes,
+            reason,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactDelete, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.details = Some(purge_details(quarantined_path, reason));
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::ArtifactRestored {
+            path,
+            archived_path,
+            size_bytes,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactRestore, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.details = Some(format!("archived_path={archived_path}"));
+            e.ok = Some(true);
+            e
+        }
         ActivityEvent::ScanCompleted {
             paths_scanned,
             candidates_found,
@@ -440,6 +626,13 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.ok = Some(true);
             e
         }
+        ActivityEvent::ConfigReloadRejected { reason } => {
+            let mut e = LogEntry::new(EventType::ConfigReload, Severity::Warning);
+            e.error_code = Some("SBH-1003".to_string());
+            e.error_message = Some(reason.clone());
+            e.ok = Some(false);
+            e
+        }
         ActivityEvent::Error { code, message } => {
             let mut e = LogEntry::new(EventType::Error, Severity::Critical);
             e.error_code = Some(code.clone());
@@ -447,6 +640,90 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.ok = Some(false);
             e
         }
+        ActivityEvent::ReclaimShortfall {
+            mount_point,
+            expected_bytes,
+            observed_bytes,
+        } => {
+            let mut e = LogEntry::new(EventType::ReclaimCalibration, Severity::Warning);
+            e.path = Some(mount_point.clone());
+            e.size = Some(*observed_bytes);
+            e.details = Some(format!(
+                "expected_bytes={expected_bytes} observed_bytes={observed_bytes}"
+            ));
+            e.ok = Some(false);
+            e
+        }
+        ActivityEvent::MemoryPressure {
+            rss_bytes,
+            budget_bytes,
+            shed,
+        } => {
+            let mut e = LogEntry::new(EventType::MemoryPressure, Severity::Warning);
+            e.size = Some(*rss_bytes);
+            e.details = Some(format!("budget_bytes={budget_bytes} shed={shed}"));
+            e
+        }
+        ActivityEvent::Annotation { message } => {
+            let mut e = LogEntry::new(EventType::Annotation, Severity::Info);
+            e.details = Some(message.clone());
+            e
+        }
+        ActivityEvent::MountChanged {
+            mount_point,
+            device,
+            fs_type,
+            mounted,
+        } => {
+            let severity = if *mounted {
+                Severity::Info
+            } else {
+                Severity::Warning
+            };
+            let mut e = LogEntry::new(EventType::MountChange, severity);
+            e.path = Some(mount_point.clone());
+            e.details = Some(mount_change_details(device, fs_type, *mounted));
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::AttributionSnapshot {
+            mount_point,
+            pressure,
+            free_pct,
+            details,
+        } => {
+            let mut e = LogEntry::new(EventType::AttributionSnapshot, Severity::Info);
+            e.path = Some(mount_point.clone());
+            e.pressure = Some(pressure.clone());
+            e.free_pct = Some(*free_pct);
+            e.details = Some(details.clone());
+            e
+        }
+        ActivityEvent::WorkspaceCreated { path, lease } => {
+            let mut e = LogEntry::new(EventType::WorkspaceCreate, Severity::Info);
+            e.path = Some(path.clone());
+            e.details = Some(lease.clone());
+            e.ok = Some(true);
+            e
+        }
+        ActivityEvent::WorkspaceDestroyed {
+            path,
+            size_bytes,
+            overrun_bytes,
+            lease,
+        } => {
+            let severity = if *overrun_bytes > 0 {
+                Severity::Warning
+            } else {
+                Severity::Info
+            };
+            let mut e = LogEntry::new(EventType::WorkspaceDestroy, severity);
+            e.path = Some(path.clone());
+            e.size = Some(*size_bytes);
+            e.details = Some(workspace_destroy_details(lease, *overrun_bytes));
+            e.ok = Some(true);
+            e
+        }
         ActivityEvent::Emergency { details, free_pct } => {
             let mut e = LogEntry::new(EventType::Emergency, Severity::Critical);
             e.details = Some(details.clone());
@@ -460,6 +737,40 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
     }
 }
 
+/// Relocations share `artifact_delete` with deletions and truncations (the
+/// bytes left this mount either way); `action=relocate` tells them apart.
+fn relocate_details(archived_path: &str) -> String {
+    format!("action=relocate archived_path={archived_path}")
+}
+
+fn purge_details(quarantined_path: &str, reason: &str) -> String {
+    format!("action=purge_quarantine reason={reason} quarantined_path={quarantined_path}")
+}
+
+fn replenish_deferred_details(projected_free_pct: f64, floor_pct: f64) -> String {
+    format!("projected_free_pct={projected_free_pct:.1} floor_pct={floor_pct:.1}")
+}
+
+fn mount_change_details(device: &str, fs_type: &str, mounted: bool) -> String {
+    let action = if mounted { "mounted" } else { "unmounted" };
+    format!("action={action} device={device} fs_type={fs_type}")
+}
+
+/// The lease JSON with the budget overrun folded in.
+fn workspace_destroy_details(lease: &str, overrun_bytes: u64) -> String {
+    let mut details =
+        serde_json::from_str::<serde_json::Value>(lease).unwrap_or_else(|_| serde_json::json!({}));
+    if let Some(map) = details.as_object_mut() {
+        map.insert("overrun_bytes".to_string(), overrun_bytes.into());
+    }
+    details.to_string()
+}
+
+/// `{"owner_uid":N}` details for deletion rows; `sbh stats --by-user` groups on it.
+fn owner_details(owner_uid: u32) -> String {
+    serde_json::json!({ "owner_uid": owner_uid }).to_string()
+}
+
 #[cfg(feature = "sqlite")]
 #[allow(clippy::too_many_lines, clippy::cast_possible_wrap)]
 fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
@@ -493,6 +804,7 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             pressure,
             free_pct,
             duration_ms,
+            owner_uid,
         } => Some(ActivityRow {
             timestamp: ts,
             event_type: "artifact_delete".to_string(),
@@ -507,7 +819,7 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             success: 1,
             error_code: None,
             error_message: None,
-            details: None,
+            details: owner_uid.map(owner_details),
         }),
         ActivityEvent::ArtifactDeletionFailed {
             path,
@@ -529,6 +841,109 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             error_message: Some(error_message.clone()),
             details: None,
         }),
+        ActivityEvent::ArtifactTruncated {
+            path,
+            size_bytes,
+            score,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_delete".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(i64::try_from(*size_bytes).unwrap_or(i64::MAX)),
+            score: Some(*score),
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some("action=truncate".to_string()),
+        }),
+        ActivityEvent::ArtifactRelocated {
+            path,
+            archived_path,
+            size_bytes,
+            score,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_delete".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(i64::try_from(*size_bytes).unwrap_or(i64::MAX)),
+            score: Some(*score),
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(relocate_details(archived_path)),
+        }),
+        ActivityEvent::ArtifactQuarantined {
+            path,
+            quarantined_path,
+            size_bytes,
+            score,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_quarantine".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(i64::try_from(*size_bytes).unwrap_or(i64::MAX)),
+            score: Some(*score),
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(format!("quarantined_path={quarantined_path}")),
+        }),
+        ActivityEvent::QuarantinePurged {
+            path,
+            quarantined_path,
+            size_bytes,
+            reason,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_delete".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(i64::try_from(*size_bytes).unwrap_or(i64::MAX)),
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(purge_details(quarantined_path, reason)),
+        }),
+        ActivityEvent::ArtifactRestored {
+            path,
+            archived_path,
+            size_bytes,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_restore".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(i64::try_from(*size_bytes).unwrap_or(i64::MAX)),
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(format!("archived_path={archived_path}")),
+        }),
         ActivityEvent::BallastReleased {
             path,
             size_bytes,
@@ -620,6 +1035,28 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             error_message: None,
             details: None,
         }),
+        ActivityEvent::BallastReplenishDeferred {
+            mount_point,
+            free_pct,
+            projected_free_pct,
+            floor_pct,
+            fill_rate_bps: _,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "ballast_replenish_deferred".to_string(),
+            severity: "info".to_string(),
+            path: Some(mount_point.clone()),
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: Some(*free_pct),
+            duration_ms: None,
+            success: 0,
+            error_code: None,
+            error_message: None,
+            details: Some(replenish_deferred_details(*projected_free_pct, *floor_pct)),
+        }),
         ActivityEvent::Emergency { details, free_pct } => Some(ActivityRow {
             timestamp: ts,
             event_type: "emergency".to_string(),
@@ -636,6 +1073,180 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             error_message: None,
             details: Some(details.clone()),
         }),
+        ActivityEvent::ReclaimShortfall {
+            mount_point,
+            expected_bytes,
+            observed_bytes,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "reclaim_calibration".to_string(),
+            severity: "warning".to_string(),
+            path: Some(mount_point.clone()),
+            size_bytes: Some(i64::try_from(*observed_bytes).unwrap_or(i64::MAX)),
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 0,
+            error_code: None,
+            error_message: None,
+            details: Some(format!(
+                "expected_bytes={expected_bytes} observed_bytes={observed_bytes}"
+            )),
+        }),
+        ActivityEvent::ConfigReloaded { details } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "config_reload".to_string(),
+            severity: "info".to_string(),
+            path: None,
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(details.clone()),
+        }),
+        ActivityEvent::ConfigReloadRejected { reason } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "config_reload".to_string(),
+            severity: "warning".to_string(),
+            path: None,
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 0,
+            error_code: Some("SBH-1003".to_string()),
+            error_message: Some(reason.clone()),
+            details: None,
+        }),
+        ActivityEvent::MemoryPressure {
+            rss_bytes,
+            budget_bytes,
+            shed,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "memory_pressure".to_string(),
+            severity: "warning".to_string(),
+            path: None,
+            size_bytes: Some(i64::try_from(*rss_bytes).unwrap_or(i64::MAX)),
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(format!("budget_bytes={budget_bytes} shed={shed}")),
+        }),
+        ActivityEvent::Annotation { message } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "annotation".to_string(),
+            severity: "info".to_string(),
+            path: None,
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(message.clone()),
+        }),
+        ActivityEvent::MountChanged {
+            mount_point,
+            device,
+            fs_type,
+            mounted,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "mount_change".to_string(),
+            severity: if *mounted { "info" } else { "warning" }.to_string(),
+            path: Some(mount_point.clone()),
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(mount_change_details(device, fs_type, *mounted)),
+        }),
+        ActivityEvent::AttributionSnapshot {
+            mount_point,
+            pressure,
+            free_pct,
+            details,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "attribution_snapshot".to_string(),
+            severity: "info".to_string(),
+            path: Some(mount_point.clone()),
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: Some(pressure.clone()),
+            free_pct: Some(*free_pct),
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(details.clone()),
+        }),
+        ActivityEvent::WorkspaceCreated { path, lease } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "workspace_create".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(lease.clone()),
+        }),
+        ActivityEvent::WorkspaceDestroyed {
+            path,
+            size_bytes,
+            overrun_bytes,
+            lease,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "workspace_destroy".to_string(),
+            severity: if *overrun_bytes > 0 {
+                "warning"
+            } else {
+                "info"
+            }
+            .to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(*size_bytes as i64),
+            score: None,
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(workspace_destroy_details(lease, *overrun_bytes)),
+        }),
         // Events that only need JSONL logging (pressure goes to pressure_history table).
         _ => None,
     }
@@ -737,12 +1348,14 @@ mod tests {
             pressure: "orange".to_string(),
             free_pct: 8.3,
             duration_ms: 145,
+            owner_uid: Some(1000),
         });
         handle.shutdown();
         join.join().unwrap();
 
         let contents = std::fs::read_to_string(dir.path().join("test.jsonl")).unwrap();
         assert_eq!(contents.lines().count(), 3);
+        assert!(contents.contains("owner_uid"));
 
         // Check SQLite too.
         #[cfg(feature = "sqlite")]
@@ -978,6 +1591,80 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn workspace_destroy_row_carries_lease_and_overrun() {
+        let row = event_to_activity_row(&ActivityEvent::WorkspaceDestroyed {
+            path: "/data/swarm/agent-7".to_string(),
+            size_bytes: 6_000,
+            overrun_bytes: 1_000,
+            lease: r#"{"owner":"agent-7","budget_bytes":5000}"#.to_string(),
+        })
+        .unwrap();
+        assert_eq!(row.event_type, "workspace_destroy");
+        assert_eq!(row.severity, "warning");
+        let details: serde_json::Value = serde_json::from_str(&row.details.unwrap()).unwrap();
+        assert_eq!(details["owner"], "agent-7");
+        assert_eq!(details["overrun_bytes"], 1_000);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn relocation_is_an_artifact_delete_that_names_the_archive() {
+        let row = event_to_activity_row(&ActivityEvent::ArtifactRelocated {
+            path: "/data/proj/target".to_string(),
+            archived_path: "/cold/20261016T120000.000Z-1a2b3c4d/target".to_string(),
+            size_bytes: 4_096,
+            score: 0.9,
+        })
+        .unwrap();
+        assert_eq!(row.event_type, "artifact_delete");
+        assert_eq!(row.success, 1);
+        assert_eq!(
+            row.details.as_deref(),
+            Some("action=relocate archived_path=/cold/20261016T120000.000Z-1a2b3c4d/target")
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn config_reload_outcomes_reach_the_activity_log() {
+        let applied = event_to_activity_row(&ActivityEvent::ConfigReloaded {
+            details: "config hash: a -> b".to_string(),
+        })
+        .unwrap();
+        assert_eq!(applied.event_type, "config_reload");
+        assert_eq!(applied.success, 1);
+
+        let rejected = event_to_activity_row(&ActivityEvent::ConfigReloadRejected {
+            reason: "scoring.min_score must be in [0, 1]".to_string(),
+        })
+        .unwrap();
+        assert_eq!(rejected.event_type, "config_reload");
+        assert_eq!(rejected.success, 0);
+        assert_eq!(rejected.severity, "warning");
+        assert_eq!(
+            rejected.error_message.as_deref(),
+            Some("scoring.min_score must be in [0, 1]")
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn annotation_row_carries_the_note_as_details() {
+        let row = event_to_activity_row(&ActivityEvent::Annotation {
+            message: "started nightly build farm run".to_string(),
+        })
+        .unwrap();
+        assert_eq!(row.event_type, "annotation");
+        assert_eq!(row.severity, "info");
+        assert_eq!(row.path, None);
+        assert_eq!(
+            row.details.as_deref(),
+            Some("started nightly build farm run")
+        );
+    }
+
     #[cfg(feature = "sqlite")]
     #[test]
     fn sqlite_and_jsonl_consistency_under_mixed_events() {
@@ -1003,6 +1690,7 @@ mod tests {
             pressure: "red".to_string(),
             free_pct: 3.2,
             duration_ms: 200,
+            owner_uid: None,
         });
         handle.send(ActivityEvent::ArtifactDeletionFailed {
             path: "/data/protected/.target".to_string(),
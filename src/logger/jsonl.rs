@@ -45,6 +45,16 @@ pub enum EventType {
     ConfigReload,
     Error,
     Emergency,
+    ReclaimCalibration,
+    MountChange,
+    AttributionSnapshot,
+    WorkspaceCreate,
+    WorkspaceDestroy,
+    ArtifactRestore,
+    BallastReplenishDeferred,
+    ArtifactQuarantine,
+    Annotation,
+    MemoryPressure,
 }
 
 /// A single JSONL log entry — all fields optional except `ts`, `event`, `severity`.
@@ -968,6 +978,16 @@ mod tests {
             EventType::ConfigReload,
             EventType::Error,
             EventType::Emergency,
+            EventType::ReclaimCalibration,
+            EventType::MountChange,
+            EventType::AttributionSnapshot,
+            EventType::WorkspaceCreate,
+            EventType::WorkspaceDestroy,
+            EventType::ArtifactRestore,
+            EventType::BallastReplenishDeferred,
+            EventType::ArtifactQuarantine,
+            EventType::Annotation,
+            EventType::MemoryPressure,
         ];
 
         for et in &event_types {
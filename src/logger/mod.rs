@@ -1,5 +1,7 @@
 //! Dual-write logging: SQLite (WAL) + JSONL append-only with graceful degradation.
 
+pub mod audit;
+pub mod diag;
 pub mod dual;
 pub mod jsonl;
 #[cfg(feature = "sqlite")]
@@ -8,9 +8,10 @@
 use std::path::{Path, PathBuf};
 
 use rusqlite::functions::FunctionFlags;
-use rusqlite::{Connection, OpenFlags, params};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
 
 use crate::core::errors::{Result, SbhError};
+use crate::logger::audit::DeletionManifest;
 
 /// SQLite activity logger with WAL mode and prepared-statement patterns.
 pub struct SqliteLogger {
@@ -101,24 +102,40 @@ impl SqliteLogger {
              FROM activity_log ORDER BY id DESC LIMIT ?1",
         )?;
         let rows = stmt
-            .query_map(params![limit], |row| {
-                Ok(ActivityRow {
-                    timestamp: row.get(0)?,
-                    event_type: row.get(1)?,
-                    severity: row.get(2)?,
-                    path: row.get(3)?,
-                    size_bytes: row.get(4)?,
-                    score: row.get(5)?,
-                    score_factors: row.get(6)?,
-                    pressure_level: row.get(7)?,
-                    free_pct: row.get(8)?,
-                    duration_ms: row.get(9)?,
-                    success: row.get(10)?,
-                    error_code: row.get(11)?,
-                    error_message: row.get(12)?,
-                    details: row.get(13)?,
-                })
-            })?
+            .query_map(params![limit], activity_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Query recent activity entries of one `event_type`, newest first.
+    pub fn recent_activity_of_type(
+        &self,
+        event_type: &str,
+        limit: u32,
+    ) -> Result<Vec<ActivityRow>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT timestamp, event_type, severity, path, size_bytes, score,
+                    score_factors, pressure_level, free_pct, duration_ms,
+                    success, error_code, error_message, details
+             FROM activity_log WHERE event_type = ?1 ORDER BY id DESC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![event_type, limit], activity_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Query activity entries with `since <= timestamp <= until`, oldest first.
+    pub fn activity_between(&self, since: &str, until: &str) -> Result<Vec<ActivityRow>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT timestamp, event_type, severity, path, size_bytes, score,
+                    score_factors, pressure_level, free_pct, duration_ms,
+                    success, error_code, error_message, details
+             FROM activity_log WHERE timestamp >= ?1 AND timestamp <= ?2
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt
+            .query_map(params![since, until], activity_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
         Ok(rows)
     }
@@ -251,6 +268,125 @@ impl SqliteLogger {
         Ok(rows)
     }
 
+    // ──────────────────── deletion_manifests ────────────────────
+
+    /// Store a deletion manifest and index its item paths.
+    pub fn log_manifest(&self, manifest: &DeletionManifest) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.prepare_cached(
+            "INSERT OR REPLACE INTO deletion_manifests (
+                batch_id, created_at, source, policy_mode, item_count,
+                total_bytes, manifest
+            ) VALUES (?1,?2,?3,?4,?5,?6,?7)",
+        )?
+        .execute(params![
+            manifest.batch_id,
+            manifest.created_at,
+            manifest.source,
+            manifest.policy_mode,
+            i64::try_from(manifest.items.len()).unwrap_or(i64::MAX),
+            i64::try_from(manifest.total_bytes()).unwrap_or(i64::MAX),
+            serde_json::to_string(manifest)?,
+        ])?;
+        tx.execute(
+            "DELETE FROM deletion_manifest_items WHERE batch_id = ?1",
+            params![manifest.batch_id],
+        )?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO deletion_manifest_items (
+                    batch_id, path, size_bytes, score, action, decision_id,
+                    top_level_sha256
+                ) VALUES (?1,?2,?3,?4,?5,?6,?7)",
+            )?;
+            for item in &manifest.items {
+                insert.execute(params![
+                    manifest.batch_id,
+                    item.path.to_string_lossy(),
+                    i64::try_from(item.size_bytes).unwrap_or(i64::MAX),
+                    item.score,
+                    item.action.as_str(),
+                    item.decision_id.and_then(|id| i64::try_from(id).ok()),
+                    item.top_level.sha256,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Recent manifests, newest first. With `path`, only batches with an item
+    /// that is `path`, an ancestor of it, or a descendant of it.
+    pub fn recent_manifests(
+        &self,
+        limit: usize,
+        path: Option<&Path>,
+    ) -> Result<Vec<DeletionManifest>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let raw: Vec<String> = match path {
+            None => self
+                .conn
+                .prepare_cached(
+                    "SELECT manifest FROM deletion_manifests
+                     ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+                )?
+                .query_map(params![limit], |row| row.get(0))?
+                .collect::<std::result::Result<_, _>>()?,
+            Some(path) => self
+                .conn
+                .prepare_cached(
+                    "SELECT manifest FROM deletion_manifests WHERE batch_id IN (
+                         SELECT batch_id FROM deletion_manifest_items
+                         WHERE path = ?1
+                            OR substr(?1, 1, length(path) + 1) = path || '/'
+                            OR substr(path, 1, length(?1) + 1) = ?1 || '/'
+                     )
+                     ORDER BY created_at DESC, rowid DESC LIMIT ?2",
+                )?
+                .query_map(
+                    params![path.to_string_lossy().trim_end_matches('/'), limit],
+                    |row| row.get(0),
+                )?
+                .collect::<std::result::Result<_, _>>()?,
+        };
+        raw.iter()
+            .map(|json| serde_json::from_str(json).map_err(SbhError::from))
+            .collect()
+    }
+
+    /// The manifest with `batch_id`, if stored.
+    pub fn manifest(&self, batch_id: &str) -> Result<Option<DeletionManifest>> {
+        let raw: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT manifest FROM deletion_manifests WHERE batch_id = ?1",
+                params![batch_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        raw.map(|json| serde_json::from_str(&json).map_err(SbhError::from))
+            .transpose()
+    }
+
+    /// Delete manifests older than `retention_days`, with their items.
+    pub fn prune_manifests(&self, retention_days: u32) -> Result<usize> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+        let cutoff_str = cutoff.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM deletion_manifest_items WHERE batch_id IN (
+                 SELECT batch_id FROM deletion_manifests WHERE created_at < ?1
+             )",
+            params![cutoff_str],
+        )?;
+        let deleted = tx.execute(
+            "DELETE FROM deletion_manifests WHERE created_at < ?1",
+            params![cutoff_str],
+        )?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+
     // ──────────────────── aggregate helpers ────────────────────
 
     /// Count activity entries of a given event_type since a timestamp.
@@ -335,6 +471,25 @@ pub struct BallastRow {
     pub integrity_hash: Option<String>,
 }
 
+fn activity_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ActivityRow> {
+    Ok(ActivityRow {
+        timestamp: row.get(0)?,
+        event_type: row.get(1)?,
+        severity: row.get(2)?,
+        path: row.get(3)?,
+        size_bytes: row.get(4)?,
+        score: row.get(5)?,
+        score_factors: row.get(6)?,
+        pressure_level: row.get(7)?,
+        free_pct: row.get(8)?,
+        duration_ms: row.get(9)?,
+        success: row.get(10)?,
+        error_code: row.get(11)?,
+        error_message: row.get(12)?,
+        details: row.get(13)?,
+    })
+}
+
 // ──────────────────── schema & pragmas ────────────────────
 
 fn apply_pragmas(conn: &Connection) -> Result<()> {
@@ -414,13 +569,36 @@ fn apply_schema(conn: &Connection) -> Result<()> {
             integrity_hash TEXT
         );
 
+        CREATE TABLE IF NOT EXISTS deletion_manifests (
+            batch_id TEXT PRIMARY KEY,
+            created_at TEXT NOT NULL,
+            source TEXT NOT NULL,
+            policy_mode TEXT,
+            item_count INTEGER NOT NULL,
+            total_bytes INTEGER NOT NULL,
+            manifest TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS deletion_manifest_items (
+            batch_id TEXT NOT NULL,
+            path TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            score REAL NOT NULL,
+            action TEXT NOT NULL,
+            decision_id INTEGER,
+            top_level_sha256 TEXT NOT NULL
+        );
+
         CREATE INDEX IF NOT EXISTS idx_activity_timestamp ON activity_log(timestamp);
         CREATE INDEX IF NOT EXISTS idx_activity_event_type ON activity_log(event_type);
         CREATE INDEX IF NOT EXISTS idx_activity_type_time ON activity_log(event_type, timestamp);
         CREATE INDEX IF NOT EXISTS idx_pressure_timestamp ON pressure_history(timestamp);
         CREATE INDEX IF NOT EXISTS idx_pressure_mount ON pressure_history(mount_point);
         CREATE INDEX IF NOT EXISTS idx_pressure_mount_timestamp
-            ON pressure_history(mount_point, timestamp);",
+            ON pressure_history(mount_point, timestamp);
+        CREATE INDEX IF NOT EXISTS idx_manifests_created ON deletion_manifests(created_at);
+        CREATE INDEX IF NOT EXISTS idx_manifest_items_batch ON deletion_manifest_items(batch_id);
+        CREATE INDEX IF NOT EXISTS idx_manifest_items_path ON deletion_manifest_items(path);",
     )?;
     Ok(())
 }
@@ -592,6 +770,48 @@ mod tests {
         assert_eq!(count, 1000);
     }
 
+    #[test]
+    fn activity_queries_filter_by_type_and_time_range() {
+        let (_dir, logger) = temp_db();
+        for (minute, event_type) in [
+            (0, "attribution_snapshot"),
+            (5, "artifact_delete"),
+            (50, "artifact_delete"),
+        ] {
+            logger
+                .log_activity(&ActivityRow {
+                    timestamp: format!("2026-02-14T16:{minute:02}:00.000Z"),
+                    event_type: event_type.to_string(),
+                    severity: "info".to_string(),
+                    path: None,
+                    size_bytes: None,
+                    score: None,
+                    score_factors: None,
+                    pressure_level: Some("Red".to_string()),
+                    free_pct: None,
+                    duration_ms: None,
+                    success: 1,
+                    error_code: None,
+                    error_message: None,
+                    details: None,
+                })
+                .unwrap();
+        }
+
+        let snapshots = logger
+            .recent_activity_of_type("attribution_snapshot", 10)
+            .unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].pressure_level.as_deref(), Some("Red"));
+
+        let window = logger
+            .activity_between("2026-02-14T16:00:00.000Z", "2026-02-14T16:30:00.000Z")
+            .unwrap();
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].event_type, "attribution_snapshot");
+        assert_eq!(window[1].event_type, "artifact_delete");
+    }
+
     #[test]
     fn idempotent_schema_creation() {
         let dir = tempfile::tempdir().unwrap();
@@ -1,18 +1,24 @@
 //! Stats query engine: aggregation queries over logged events for the stats command.
 //!
 //! Provides time-window aggregation across `activity_log`, `pressure_history`,
-//! and `ballast_inventory` tables. All queries operate on a borrowed `SqliteLogger`
+//! `ballast_inventory`, and `deletion_manifests` tables. All queries operate on a borrowed `SqliteLogger`
 //! connection — the stats engine is a read-only view over the logging database.
 
 #![allow(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
 use std::time::Duration;
 
 use rusqlite::params;
+use serde::Serialize;
 
 use crate::core::errors::Result;
+use crate::logger::audit::DeletionManifest;
 use crate::logger::sqlite::SqliteLogger;
+use crate::monitor::writers::AttributionSnapshot;
 
 // ──────────────────── standard time windows ────────────────────
 
@@ -50,6 +56,8 @@ pub struct DeletionStats {
     pub avg_score: f64,
     pub avg_age_hours: f64,
     pub failures: u64,
+    /// Bytes reclaimed per artifact category, largest first.
+    pub top_categories: Vec<CategoryStat>,
 }
 
 /// Info about a specific deleted path (for "largest deletion" reporting).
@@ -142,6 +150,77 @@ impl std::fmt::Display for PressureLevel {
     }
 }
 
+/// Bytes reclaimed for one artifact category (`rust_target`, `node_modules`, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryStat {
+    pub category: String,
+    pub count: u64,
+    pub total_bytes: u64,
+}
+
+/// How `sbh stats --group-by` splits a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsGroupBy {
+    /// UTC calendar day.
+    Day,
+    /// UTC hour.
+    Hour,
+    /// Artifact category recorded in the deletion manifest.
+    Category,
+    /// Monitored mount point the path lives on.
+    Mount,
+}
+
+impl StatsGroupBy {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Hour => "hour",
+            Self::Category => "category",
+            Self::Mount => "mount",
+        }
+    }
+}
+
+impl fmt::Display for StatsGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StatsGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "hour" => Ok(Self::Hour),
+            "category" => Ok(Self::Category),
+            "mount" => Ok(Self::Mount),
+            other => Err(format!(
+                "invalid grouping {other:?}: expected day, hour, category, or mount"
+            )),
+        }
+    }
+}
+
+/// Activity in one `--group-by` bucket.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupStat {
+    /// `2026-10-16`, `2026-10-16T08:00Z`, a category, or a mount point.
+    pub key: String,
+    pub deletions: u64,
+    pub bytes_freed: u64,
+    pub failures: u64,
+    /// Ballast release events; always zero when grouping by category.
+    pub ballast_released: u64,
+}
+
+/// Label for deletions whose batch left no manifest.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
 /// A pattern with its deletion count.
 #[derive(Debug, Clone)]
 pub struct PatternStat {
@@ -159,6 +238,19 @@ pub struct DeletionDetail {
     pub timestamp: String,
 }
 
+/// One user's share of reclaim and write pressure (`sbh stats --by-user`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserStat {
+    /// Owning uid; `None` collects deletions logged before owners were
+    /// recorded and writers whose uid was unreadable.
+    pub uid: Option<u32>,
+    pub deletions: u64,
+    pub bytes_reclaimed: u64,
+    /// Bytes written by the user's processes, summed over the attribution
+    /// snapshots taken on pressure escalation.
+    pub bytes_written: u64,
+}
+
 // ──────────────────── stats engine ────────────────────
 
 /// Read-only query engine over the sbh activity database.
@@ -185,7 +277,7 @@ impl<'a> StatsEngine<'a> {
         let since = since_timestamp(window);
         Ok(WindowStats {
             window,
-            deletions: self.deletion_stats(&since)?,
+            deletions: self.deletion_stats(&since, window)?,
             ballast: self.ballast_stats(&since)?,
             pressure: self.pressure_stats(&since)?,
         })
@@ -247,6 +339,147 @@ impl<'a> StatsEngine<'a> {
         Ok(details)
     }
 
+    /// Per-user reclaim and write accounting, largest reclaim first.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    pub fn user_stats(&self, window: Duration) -> Result<Vec<UserStat>> {
+        let since = since_timestamp(window);
+        let conn = self.db.connection();
+        let mut by_uid: BTreeMap<Option<u32>, UserStat> = BTreeMap::new();
+
+        let mut stmt = conn.prepare(
+            "SELECT
+                CASE WHEN json_valid(details) THEN json_extract(details, '$.owner_uid') END AS uid,
+                COUNT(*),
+                SUM(COALESCE(size_bytes, 0))
+             FROM activity_log
+             WHERE event_type = 'artifact_delete' AND success = 1 AND timestamp >= ?1
+             GROUP BY uid",
+        )?;
+        let rows = stmt
+            .query_map(params![since], |row| {
+                Ok((
+                    row.get::<_, Option<i64>>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for (uid, count, bytes) in rows {
+            let uid = uid.and_then(|uid| u32::try_from(uid).ok());
+            let entry = by_uid.entry(uid).or_insert_with(|| UserStat {
+                uid,
+                ..UserStat::default()
+            });
+            entry.deletions += count as u64;
+            entry.bytes_reclaimed += bytes.unwrap_or(0) as u64;
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT details FROM activity_log
+             WHERE event_type = 'attribution_snapshot' AND timestamp >= ?1
+               AND details IS NOT NULL",
+        )?;
+        let snapshots = stmt
+            .query_map(params![since], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for details in snapshots {
+            let Ok(snapshot) = serde_json::from_str::<AttributionSnapshot>(&details) else {
+                continue;
+            };
+            for writer in snapshot.writers {
+                let entry = by_uid.entry(writer.uid).or_insert_with(|| UserStat {
+                    uid: writer.uid,
+                    ..UserStat::default()
+                });
+                entry.bytes_written = entry.bytes_written.saturating_add(writer.bytes_written);
+            }
+        }
+
+        let mut users: Vec<UserStat> = by_uid.into_values().collect();
+        users.sort_by(|a, b| {
+            b.bytes_reclaimed
+                .cmp(&a.bytes_reclaimed)
+                .then_with(|| b.bytes_written.cmp(&a.bytes_written))
+                .then_with(|| a.uid.cmp(&b.uid))
+        });
+        Ok(users)
+    }
+
+    /// Reclaimed bytes per artifact category, largest first.
+    pub fn top_categories(&self, n: usize, window: Duration) -> Result<Vec<CategoryStat>> {
+        let mut categories = self.category_stats(&since_timestamp(window), window)?;
+        categories.truncate(n);
+        Ok(categories)
+    }
+
+    /// Split a window's deletions and ballast releases into buckets. Time
+    /// buckets come back oldest first, the others by bytes freed.
+    pub fn grouped(&self, window: Duration, group_by: StatsGroupBy) -> Result<Vec<GroupStat>> {
+        let since = since_timestamp(window);
+        let rows = self.activity_points(&since)?;
+        let categories = if group_by == StatsGroupBy::Category {
+            self.manifest_categories(window)?
+        } else {
+            HashMap::new()
+        };
+        let mounts = if group_by == StatsGroupBy::Mount {
+            self.known_mounts()?
+        } else {
+            Vec::new()
+        };
+
+        let mut groups: BTreeMap<String, GroupStat> = BTreeMap::new();
+        for row in rows {
+            let is_ballast = row.event_type == "ballast_release";
+            let key = match group_by {
+                StatsGroupBy::Day | StatsGroupBy::Hour => {
+                    let Ok(at) = chrono::DateTime::parse_from_rfc3339(&row.timestamp) else {
+                        continue;
+                    };
+                    let at = at.with_timezone(&chrono::Utc);
+                    if group_by == StatsGroupBy::Day {
+                        at.format("%Y-%m-%d").to_string()
+                    } else {
+                        at.format("%Y-%m-%dT%H:00Z").to_string()
+                    }
+                }
+                StatsGroupBy::Category if is_ballast => continue,
+                StatsGroupBy::Category => row
+                    .path
+                    .as_ref()
+                    .and_then(|path| categories.get(path))
+                    .map_or_else(|| UNCATEGORIZED.to_string(), Clone::clone),
+                StatsGroupBy::Mount => row
+                    .path
+                    .as_deref()
+                    .and_then(|path| mount_for(path, &mounts))
+                    .map_or_else(|| "(unknown)".to_string(), str::to_string),
+            };
+            let group = groups.entry(key.clone()).or_insert_with(|| GroupStat {
+                key,
+                ..GroupStat::default()
+            });
+            match (is_ballast, row.success) {
+                (true, _) => group.ballast_released += 1,
+                (false, true) => {
+                    group.deletions += 1;
+                    group.bytes_freed = group.bytes_freed.saturating_add(row.size_bytes);
+                }
+                (false, false) => group.failures += 1,
+            }
+        }
+
+        let mut groups: Vec<GroupStat> = groups.into_values().collect();
+        if matches!(group_by, StatsGroupBy::Category | StatsGroupBy::Mount) {
+            groups.sort_by(|a, b| {
+                b.bytes_freed
+                    .cmp(&a.bytes_freed)
+                    .then_with(|| a.key.cmp(&b.key))
+            });
+        }
+        Ok(groups)
+    }
+
     /// Export all standard-window stats as JSON for agent consumption.
     pub fn export_json(&self) -> Result<serde_json::Value> {
         let windows = self.summary()?;
@@ -267,6 +500,7 @@ impl<'a> StatsEngine<'a> {
                         "most_common_category": w.deletions.most_common_category,
                         "avg_score": w.deletions.avg_score,
                         "failures": w.deletions.failures,
+                        "top_categories": categories_json(&w.deletions.top_categories),
                     },
                     "ballast": {
                         "files_released": w.ballast.files_released,
@@ -295,7 +529,7 @@ impl<'a> StatsEngine<'a> {
     // ──────────────────── private helpers ────────────────────
 
     #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-    fn deletion_stats(&self, since: &str) -> Result<DeletionStats> {
+    fn deletion_stats(&self, since: &str, window: Duration) -> Result<DeletionStats> {
         let conn = self.db.connection();
 
         // Aggregate successful deletions.
@@ -368,9 +602,101 @@ impl<'a> StatsEngine<'a> {
             avg_score,
             avg_age_hours: 0.0, // Age at deletion not stored in current schema
             failures: failures.max(0) as u64,
+            top_categories: self.category_stats(since, window)?,
         })
     }
 
+    fn category_stats(&self, since: &str, window: Duration) -> Result<Vec<CategoryStat>> {
+        let categories = self.manifest_categories(window)?;
+        let mut by_category: HashMap<&str, CategoryStat> = HashMap::new();
+        for row in self.activity_points(since)? {
+            if row.event_type != "artifact_delete" || !row.success {
+                continue;
+            }
+            let category = row
+                .path
+                .as_ref()
+                .and_then(|path| categories.get(path))
+                .map_or(UNCATEGORIZED, String::as_str);
+            let stat = by_category.entry(category).or_insert_with(|| CategoryStat {
+                category: category.to_string(),
+                count: 0,
+                total_bytes: 0,
+            });
+            stat.count += 1;
+            stat.total_bytes = stat.total_bytes.saturating_add(row.size_bytes);
+        }
+        let mut categories: Vec<CategoryStat> = by_category.into_values().collect();
+        categories.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+        Ok(categories)
+    }
+
+    /// Deletions (successful or not) and ballast releases since `since`.
+    #[allow(clippy::cast_sign_loss)]
+    fn activity_points(&self, since: &str) -> Result<Vec<ActivityPoint>> {
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare(
+            "SELECT timestamp, event_type, path, COALESCE(size_bytes, 0), success
+             FROM activity_log
+             WHERE event_type IN ('artifact_delete', 'ballast_release')
+               AND timestamp >= ?1
+             ORDER BY timestamp ASC",
+        )?;
+        let rows = stmt
+            .query_map(params![since], |row| {
+                Ok(ActivityPoint {
+                    timestamp: row.get(0)?,
+                    event_type: row.get(1)?,
+                    path: row.get(2)?,
+                    size_bytes: row.get::<_, i64>(3)?.max(0) as u64,
+                    success: row.get::<_, i64>(4)? != 0,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Category of each path in the audit manifests written during `window`.
+    /// A manifest is written just before its batch runs, so the lookup
+    /// reaches an hour further back to catch batches that straddle the start.
+    fn manifest_categories(&self, window: Duration) -> Result<HashMap<String, String>> {
+        let since = since_timestamp(window.saturating_add(MANIFEST_LOOKBACK));
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare(
+            "SELECT manifest FROM deletion_manifests
+             WHERE created_at >= ?1
+             ORDER BY created_at ASC",
+        )?;
+        let manifests = stmt
+            .query_map(params![since], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut categories = HashMap::new();
+        for raw in manifests {
+            let Ok(manifest) = serde_json::from_str::<DeletionManifest>(&raw) else {
+                continue;
+            };
+            for item in manifest.items {
+                categories.insert(item.path.to_string_lossy().into_owned(), item.category);
+            }
+        }
+        Ok(categories)
+    }
+
+    /// Mount points the daemon has recorded pressure for.
+    fn known_mounts(&self) -> Result<Vec<String>> {
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare("SELECT DISTINCT mount_point FROM pressure_history")?;
+        let mounts = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(mounts)
+    }
+
     fn most_common_deleted_pattern(&self, since: &str) -> Result<Option<String>> {
         let conn = self.db.connection();
         match conn.query_row(
@@ -535,6 +861,42 @@ impl<'a> StatsEngine<'a> {
 
 // ──────────────────── utility functions ────────────────────
 
+/// How far before a window's start to look for deletion manifests.
+const MANIFEST_LOOKBACK: Duration = Duration::from_hours(1);
+
+/// One deletion or ballast release row.
+struct ActivityPoint {
+    timestamp: String,
+    event_type: String,
+    path: Option<String>,
+    size_bytes: u64,
+    success: bool,
+}
+
+/// The longest known mount point containing `path`.
+fn mount_for<'m>(path: &str, mounts: &'m [String]) -> Option<&'m str> {
+    mounts
+        .iter()
+        .filter(|mount| Path::new(path).starts_with(mount.as_str()))
+        .max_by_key(|mount| mount.len())
+        .map(String::as_str)
+}
+
+/// `top_categories` entries as JSON.
+#[must_use]
+pub fn categories_json(categories: &[CategoryStat]) -> serde_json::Value {
+    categories
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "category": c.category,
+                "count": c.count,
+                "total_bytes": c.total_bytes,
+            })
+        })
+        .collect()
+}
+
 /// Compute an ISO 8601 timestamp for "now minus duration".
 #[allow(clippy::cast_possible_wrap)]
 fn since_timestamp(window: Duration) -> String {
@@ -677,6 +1039,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn user_stats_group_reclaim_and_writes_by_uid() {
+        let (_dir, db) = temp_db();
+        let delete = |minutes_ago: i64, size: i64, details: Option<&str>| ActivityRow {
+            timestamp: ts(minutes_ago),
+            event_type: "artifact_delete".to_string(),
+            severity: "info".to_string(),
+            path: Some(format!("/data/p{minutes_ago}/target")),
+            size_bytes: Some(size),
+            score: Some(0.9),
+            score_factors: None,
+            pressure_level: Some("orange".to_string()),
+            free_pct: Some(8.0),
+            duration_ms: Some(100),
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: details.map(str::to_string),
+        };
+        for row in [
+            delete(1, 4_000_000, Some(r#"{"owner_uid":1000}"#)),
+            delete(2, 6_000_000, Some(r#"{"owner_uid":1000}"#)),
+            delete(3, 1_000_000, Some(r#"{"owner_uid":1001}"#)),
+            delete(4, 500_000, None),
+            delete(120, 9_000_000, Some(r#"{"owner_uid":1001}"#)),
+        ] {
+            db.log_activity(&row).unwrap();
+        }
+        db.log_activity(&ActivityRow {
+            timestamp: ts(5),
+            event_type: "attribution_snapshot".to_string(),
+            severity: "info".to_string(),
+            path: Some("/data".to_string()),
+            size_bytes: None,
+            score: None,
+            score_factors: None,
+            pressure_level: Some("Orange".to_string()),
+            free_pct: Some(9.0),
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(
+                r#"{"window_secs":600,"writers":[
+                    {"pid":7,"comm":"cargo","cwd":"/data/a","uid":1001,"bytes_written":700,"peak_bytes_per_sec":1.0},
+                    {"pid":8,"comm":"rsync","cwd":"/data/b","uid":1001,"bytes_written":300,"peak_bytes_per_sec":1.0}
+                ]}"#
+                .to_string(),
+            ),
+        })
+        .unwrap();
+
+        let engine = StatsEngine::new(&db);
+        let users = engine.user_stats(Duration::from_secs(60 * 60)).unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(users[0].uid, Some(1000));
+        assert_eq!(users[0].deletions, 2);
+        assert_eq!(users[0].bytes_reclaimed, 10_000_000);
+        assert_eq!(users[0].bytes_written, 0);
+        assert_eq!(users[1].uid, Some(1001));
+        assert_eq!(users[1].bytes_reclaimed, 1_000_000);
+        assert_eq!(users[1].bytes_written, 1_000);
+        assert_eq!(users[2].uid, None);
+        assert_eq!(users[2].bytes_reclaimed, 500_000);
+    }
+
     #[test]
     fn ballast_stats_from_inventory() {
         let (_dir, db) = temp_db();
@@ -842,6 +1270,176 @@ mod tests {
         assert_eq!(top[2].size_bytes, 3_000_000);
     }
 
+    fn delete_row(minutes_ago: i64, path: &str, size: i64, success: bool) -> ActivityRow {
+        ActivityRow {
+            timestamp: ts(minutes_ago),
+            event_type: "artifact_delete".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.to_string()),
+            size_bytes: Some(size),
+            score: Some(0.9),
+            score_factors: None,
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: i32::from(success),
+            error_code: None,
+            error_message: None,
+            details: None,
+        }
+    }
+
+    fn log_manifest(db: &SqliteLogger, items: &[(&str, &str)]) {
+        use crate::logger::audit::{ManifestAction, ManifestItem, TopLevelListing};
+        use crate::logger::jsonl::ScoreFactorsRecord;
+
+        let items = items
+            .iter()
+            .map(|&(path, category)| ManifestItem {
+                path: path.into(),
+                size_bytes: 0,
+                score: 0.9,
+                factors: ScoreFactorsRecord {
+                    location: 0.8,
+                    name: 0.9,
+                    age: 1.0,
+                    size: 0.5,
+                    structure: 0.9,
+                },
+                pattern: "target/".to_string(),
+                category: category.to_string(),
+                action: ManifestAction::Delete,
+                decision_id: None,
+                top_level: TopLevelListing::default(),
+                inputs: None,
+            })
+            .collect();
+        db.log_manifest(&DeletionManifest::new("daemon", None, items))
+            .unwrap();
+    }
+
+    #[test]
+    fn categories_come_from_manifests_with_uncategorized_fallback() {
+        let (_dir, db) = temp_db();
+        log_manifest(
+            &db,
+            &[
+                ("/data/a/target", "rust_target"),
+                ("/data/b/node_modules", "node_modules"),
+            ],
+        );
+        db.log_activity(&delete_row(5, "/data/a/target", 3_000, true))
+            .unwrap();
+        db.log_activity(&delete_row(4, "/data/b/node_modules", 1_000, true))
+            .unwrap();
+        db.log_activity(&delete_row(3, "/data/c/build", 500, true))
+            .unwrap();
+        db.log_activity(&delete_row(2, "/data/b/node_modules", 9_000, false))
+            .unwrap();
+
+        let engine = StatsEngine::new(&db);
+        let window = Duration::from_secs(60 * 60);
+        let categories = engine.top_categories(10, window).unwrap();
+        let summary: Vec<_> = categories
+            .iter()
+            .map(|c| (c.category.as_str(), c.count, c.total_bytes))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("rust_target", 1, 3_000),
+                ("node_modules", 1, 1_000),
+                (UNCATEGORIZED, 1, 500),
+            ]
+        );
+        assert_eq!(engine.top_categories(1, window).unwrap().len(), 1);
+
+        let groups = engine.grouped(window, StatsGroupBy::Category).unwrap();
+        let node = groups.iter().find(|g| g.key == "node_modules").unwrap();
+        assert_eq!((node.deletions, node.failures), (1, 1));
+        assert_eq!(groups[0].key, "rust_target");
+    }
+
+    #[test]
+    fn grouping_by_mount_uses_the_longest_known_mount() {
+        let (_dir, db) = temp_db();
+        for mount in ["/", "/data", "/data/fast"] {
+            db.log_pressure(&PressureRow {
+                timestamp: ts(10),
+                mount_point: mount.to_string(),
+                total_bytes: 1_000,
+                free_bytes: 500,
+                free_pct: 50.0,
+                rate_bytes_per_sec: None,
+                pressure_level: "green".to_string(),
+                ewma_rate: None,
+                pid_output: None,
+            })
+            .unwrap();
+        }
+        db.log_activity(&delete_row(3, "/data/fast/x/target", 4_000, true))
+            .unwrap();
+        db.log_activity(&delete_row(2, "/data/y/target", 1_000, true))
+            .unwrap();
+        db.log_activity(&delete_row(1, "/database/z/target", 2_000, true))
+            .unwrap();
+
+        let engine = StatsEngine::new(&db);
+        let groups = engine
+            .grouped(Duration::from_secs(60 * 60), StatsGroupBy::Mount)
+            .unwrap();
+        let keys: Vec<_> = groups
+            .iter()
+            .map(|g| (g.key.as_str(), g.bytes_freed))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![("/data/fast", 4_000), ("/", 2_000), ("/data", 1_000)]
+        );
+    }
+
+    #[test]
+    fn grouping_by_hour_buckets_deletions_and_ballast_oldest_first() {
+        let (_dir, db) = temp_db();
+        db.log_activity(&delete_row(3 * 60, "/data/a/target", 100, true))
+            .unwrap();
+        db.log_activity(&delete_row(1, "/data/b/target", 200, true))
+            .unwrap();
+        db.log_activity(&ActivityRow {
+            event_type: "ballast_release".to_string(),
+            path: None,
+            size_bytes: None,
+            ..delete_row(1, "", 0, true)
+        })
+        .unwrap();
+
+        let engine = StatsEngine::new(&db);
+        let window = Duration::from_secs(6 * 60 * 60);
+        let hours = engine.grouped(window, StatsGroupBy::Hour).unwrap();
+        assert_eq!(hours.len(), 2);
+        assert!(hours[0].key < hours[1].key);
+        assert!(hours[1].key.ends_with(":00Z"));
+        assert_eq!((hours[0].deletions, hours[0].bytes_freed), (1, 100));
+        assert_eq!(hours[1].ballast_released, 1);
+
+        let days = engine.grouped(window, StatsGroupBy::Day).unwrap();
+        let total: u64 = days.iter().map(|g| g.bytes_freed).sum();
+        assert_eq!(total, 300);
+        assert_eq!(days[0].key.len(), "2026-01-01".len());
+
+        // Ballast releases have no category.
+        let categories = engine.grouped(window, StatsGroupBy::Category).unwrap();
+        assert!(categories.iter().all(|g| g.ballast_released == 0));
+    }
+
+    #[test]
+    fn group_by_parses_case_insensitively() {
+        assert_eq!("Day".parse::<StatsGroupBy>(), Ok(StatsGroupBy::Day));
+        assert_eq!("mount".parse::<StatsGroupBy>(), Ok(StatsGroupBy::Mount));
+        assert!("week".parse::<StatsGroupBy>().is_err());
+        assert_eq!(StatsGroupBy::Category.to_string(), "category");
+    }
+
     #[test]
     fn export_json_well_formed() {
         let (_dir, db) = temp_db();
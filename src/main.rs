@@ -2,14 +2,24 @@
 
 //! sbh — Storage Ballast Helper CLI entry point.
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 
 mod cli_app;
 
 fn main() {
-    let args = cli_app::Cli::parse();
+    // Answer dynamic completion requests (`COMPLETE=<shell> sbh ...`) before
+    // normal parsing; this is a no-op when the variable is unset.
+    CompleteEnv::with_factory(cli_app::Cli::command)
+        .var(cli_app::COMPLETE_ENV_VAR)
+        .complete();
+
+    let args = match cli_app::Cli::try_parse() {
+        Ok(args) => args,
+        Err(e) => cli_app::exit_with_parse_error(&e),
+    };
     if let Err(e) = cli_app::run(&args) {
-        eprintln!("sbh: {e}");
+        cli_app::report_error(&args, &e);
         std::process::exit(e.exit_code());
     }
 }
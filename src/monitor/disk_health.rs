@@ -0,0 +1,294 @@
+//! Disk health for the block devices behind watched mounts.
+//!
+//! SMART status comes from `smartctl --json` when it is installed and can
+//! open the device (usually root only). Otherwise the kernel's per-device
+//! I/O error counter (`/sys/block/<disk>/device/ioerr_cnt`) is used. Mount
+//! devices are resolved through sysfs to the whole disk, so partitions and
+//! device-mapper volumes report the disk underneath them.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// sysfs directory of block devices.
+const SYS_CLASS_BLOCK: &str = "/sys/class/block";
+/// Device-mapper stacks deeper than this are not followed.
+const MAX_STACK_DEPTH: usize = 4;
+
+/// ATA attributes whose raw value counts damaged sectors.
+const ATA_SECTOR_ATTRIBUTES: &[(u64, &str)] =
+    &[(5, "reallocated"), (197, "pending"), (198, "uncorrectable")];
+
+/// Overall health, ordered from least to most alarming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthVerdict {
+    /// Neither SMART nor kernel counters were readable.
+    Unknown,
+    Ok,
+    /// Still working, but with damaged sectors or I/O errors.
+    Degraded,
+    /// SMART reports the disk as failing.
+    Failing,
+}
+
+impl HealthVerdict {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Ok => "ok",
+            Self::Degraded => "degraded",
+            Self::Failing => "failing",
+        }
+    }
+
+    /// Whether the verdict warrants an alert.
+    #[must_use]
+    pub const fn is_alarming(self) -> bool {
+        matches!(self, Self::Degraded | Self::Failing)
+    }
+}
+
+/// Where a verdict came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSource {
+    Smartctl,
+    KernelCounters,
+    None,
+}
+
+/// Health of one whole disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskHealth {
+    /// Kernel disk name, e.g. `sda` or `nvme0n1`.
+    pub disk: String,
+    pub verdict: HealthVerdict,
+    pub source: HealthSource,
+    /// Human-readable reason, e.g. `12 reallocated, 3 pending sectors`.
+    pub detail: String,
+}
+
+/// Whole-disk name behind a mount's source device, e.g. `/dev/sda2` ->
+/// `sda`. `None` for sources that are not block devices (tmpfs, NFS, ...).
+#[must_use]
+pub fn backing_disk(device: &str) -> Option<String> {
+    if !device.starts_with("/dev/") {
+        return None;
+    }
+    // Resolves /dev/mapper/* and /dev/disk/by-* links to the kernel name.
+    let resolved = fs::canonicalize(device).ok()?;
+    let name = resolved.file_name()?.to_str()?;
+    backing_disk_in(Path::new(SYS_CLASS_BLOCK), name, 0)
+}
+
+fn backing_disk_in(sys_class_block: &Path, name: &str, depth: usize) -> Option<String> {
+    let entry = sys_class_block.join(name);
+    if !entry.exists() || depth > MAX_STACK_DEPTH {
+        return None;
+    }
+    // Device-mapper and md volumes: follow the first underlying device.
+    if let Ok(slaves) = fs::read_dir(entry.join("slaves")) {
+        let mut slaves: Vec<String> = slaves
+            .filter_map(|slave| slave.ok()?.file_name().into_string().ok())
+            .collect();
+        slaves.sort();
+        if let Some(slave) = slaves.first() {
+            return backing_disk_in(sys_class_block, slave, depth + 1);
+        }
+    }
+    if entry.join("partition").exists() {
+        // A partition's sysfs directory sits inside its disk's.
+        let disk = fs::canonicalize(&entry).ok()?;
+        return Some(disk.parent()?.file_name()?.to_str()?.to_string());
+    }
+    Some(name.to_string())
+}
+
+/// Probe `disk` with smartctl, falling back to kernel counters.
+#[must_use]
+pub fn probe(disk: &str) -> DiskHealth {
+    if let Some((verdict, detail)) = smartctl_report(disk) {
+        return DiskHealth {
+            disk: disk.to_string(),
+            verdict,
+            source: HealthSource::Smartctl,
+            detail,
+        };
+    }
+    let counter = fs::read_to_string(Path::new("/sys/block").join(disk).join("device/ioerr_cnt"));
+    let Some(errors) = counter.ok().as_deref().and_then(parse_ioerr_cnt) else {
+        return DiskHealth {
+            disk: disk.to_string(),
+            verdict: HealthVerdict::Unknown,
+            source: HealthSource::None,
+            detail: "smartctl unavailable and no kernel error counter".to_string(),
+        };
+    };
+    DiskHealth {
+        disk: disk.to_string(),
+        verdict: if errors > 0 {
+            HealthVerdict::Degraded
+        } else {
+            HealthVerdict::Ok
+        },
+        source: HealthSource::KernelCounters,
+        detail: format!("{errors} I/O errors since boot"),
+    }
+}
+
+fn smartctl_report(disk: &str) -> Option<(HealthVerdict, String)> {
+    let output = Command::new("smartctl")
+        .args(["--health", "--attributes", "--json"])
+        .arg(format!("/dev/{disk}"))
+        .output()
+        .ok()?;
+    // smartctl's exit status is a bitmask that is non-zero for failing
+    // disks too, so the JSON decides.
+    let report: Value = serde_json::from_slice(&output.stdout).ok()?;
+    parse_smartctl(&report)
+}
+
+/// Verdict from `smartctl --json` output; `None` when it carries no health
+/// data (no permission, unsupported device).
+#[must_use]
+pub fn parse_smartctl(report: &Value) -> Option<(HealthVerdict, String)> {
+    let passed = report["smart_status"]["passed"].as_bool();
+    let mut problems = Vec::new();
+
+    if let Some(table) = report["ata_smart_attributes"]["table"].as_array() {
+        for (id, label) in ATA_SECTOR_ATTRIBUTES {
+            let raw = table
+                .iter()
+                .find(|attr| attr["id"].as_u64() == Some(*id))
+                .and_then(|attr| attr["raw"]["value"].as_u64())
+                .unwrap_or(0);
+            if raw > 0 {
+                problems.push(format!("{raw} {label}"));
+            }
+        }
+        if !problems.is_empty() {
+            let last = problems.len() - 1;
+            problems[last].push_str(" sectors");
+        }
+    }
+    let nvme = &report["nvme_smart_health_information_log"];
+    let critical_warning = nvme["critical_warning"].as_u64().unwrap_or(0);
+    if critical_warning != 0 {
+        problems.push(format!("NVMe critical warning {critical_warning:#04x}"));
+    }
+    if let Some(media_errors) = nvme["media_errors"].as_u64().filter(|&n| n > 0) {
+        problems.push(format!("{media_errors} media errors"));
+    }
+
+    let verdict = if passed == Some(false) || critical_warning != 0 {
+        HealthVerdict::Failing
+    } else if !problems.is_empty() {
+        HealthVerdict::Degraded
+    } else if passed == Some(true) {
+        HealthVerdict::Ok
+    } else {
+        return None;
+    };
+    let mut detail = match passed {
+        Some(false) => "SMART overall-health FAILED".to_string(),
+        Some(true) => "SMART overall-health passed".to_string(),
+        None => "no SMART overall-health status".to_string(),
+    };
+    if !problems.is_empty() {
+        detail = format!("{detail}; {}", problems.join(", "));
+    }
+    Some((verdict, detail))
+}
+
+/// Parse a SCSI `ioerr_cnt` value (hex, e.g. `0x1f`).
+#[must_use]
+pub fn parse_ioerr_cnt(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let hex = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X"))?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn smartctl_json_maps_to_verdicts() {
+        let healthy = json!({
+            "smart_status": {"passed": true},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "raw": {"value": 0}},
+                {"id": 9, "raw": {"value": 12000}},
+            ]},
+        });
+        assert_eq!(
+            parse_smartctl(&healthy),
+            Some((HealthVerdict::Ok, "SMART overall-health passed".to_string()))
+        );
+
+        let worn = json!({
+            "smart_status": {"passed": true},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "raw": {"value": 12}},
+                {"id": 197, "raw": {"value": 3}},
+            ]},
+        });
+        assert_eq!(
+            parse_smartctl(&worn),
+            Some((
+                HealthVerdict::Degraded,
+                "SMART overall-health passed; 12 reallocated, 3 pending sectors".to_string()
+            ))
+        );
+
+        let nvme = json!({
+            "smart_status": {"passed": true},
+            "nvme_smart_health_information_log": {"critical_warning": 4, "media_errors": 0},
+        });
+        assert_eq!(parse_smartctl(&nvme).unwrap().0, HealthVerdict::Failing);
+
+        let failed = json!({"smart_status": {"passed": false}});
+        assert_eq!(parse_smartctl(&failed).unwrap().0, HealthVerdict::Failing);
+
+        // Permission denied: smartctl still prints JSON, without a status.
+        let denied = json!({"smartctl": {"exit_status": 2}});
+        assert_eq!(parse_smartctl(&denied), None);
+    }
+
+    #[test]
+    fn ioerr_cnt_is_hex() {
+        assert_eq!(parse_ioerr_cnt("0x1f\n"), Some(31));
+        assert_eq!(parse_ioerr_cnt("0x0"), Some(0));
+        assert_eq!(parse_ioerr_cnt("17"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn partitions_and_mapper_volumes_resolve_to_the_disk() {
+        let sys = tempfile::tempdir().unwrap();
+        let devices = sys.path().join("devices");
+        let class = sys.path().join("class");
+        fs::create_dir_all(devices.join("sda/sda2")).unwrap();
+        fs::write(devices.join("sda/sda2/partition"), "2\n").unwrap();
+        fs::create_dir_all(devices.join("dm-0/slaves/sda2")).unwrap();
+        fs::create_dir_all(&class).unwrap();
+        for name in ["sda", "dm-0"] {
+            std::os::unix::fs::symlink(devices.join(name), class.join(name)).unwrap();
+        }
+        std::os::unix::fs::symlink(devices.join("sda/sda2"), class.join("sda2")).unwrap();
+
+        assert_eq!(backing_disk_in(&class, "sda2", 0).as_deref(), Some("sda"));
+        assert_eq!(backing_disk_in(&class, "dm-0", 0).as_deref(), Some("sda"));
+        assert_eq!(backing_disk_in(&class, "sda", 0).as_deref(), Some("sda"));
+        assert_eq!(backing_disk_in(&class, "loop9", 0), None);
+        assert_eq!(backing_disk("tmpfs"), None);
+    }
+}
@@ -1,29 +1,48 @@
 //! Filesystem statistics collector: statvfs wrapper, usage percentages, inode tracking.
+//!
+//! A hung network mount can block `statvfs` indefinitely, so each query runs
+//! on its own thread and is abandoned after a timeout. The mount is then
+//! reported stale. Later queries return at once instead of blocking again,
+//! until the outstanding probe answers.
 
 #![allow(missing_docs)]
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::thread;
 use std::time::{Duration, Instant};
 
-use parking_lot::RwLock;
+use crossbeam_channel::{Receiver, RecvTimeoutError, bounded};
+use parking_lot::{Mutex, RwLock};
 
 use crate::core::errors::{Result, SbhError};
 use crate::platform::pal::{FsStats, MountPoint, Platform};
 
+/// How long a single `statvfs` may take before its mount is reported stale.
+pub const DEFAULT_STATS_TIMEOUT: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Clone)]
 struct CachedStats {
     stats: FsStats,
     collected_at: Instant,
 }
 
+/// A `statvfs` running on its own thread. Kept until it answers, so a hung
+/// mount never has more than one thread stuck on it.
+struct Probe {
+    started: Instant,
+    rx: Receiver<Result<FsStats>>,
+}
+
 /// Cache-aware, mount-deduplicating filesystem statistics collector.
 pub struct FsStatsCollector {
     platform: Arc<dyn Platform>,
     cache_ttl: Duration,
     cache: RwLock<HashMap<PathBuf, CachedStats>>,
     mount_cache: RwLock<Option<(Vec<MountPoint>, Instant)>>,
+    stats_timeout: Option<Duration>,
+    probes: Mutex<HashMap<PathBuf, Probe>>,
 }
 
 impl FsStatsCollector {
@@ -34,6 +53,8 @@ impl FsStatsCollector {
             cache_ttl,
             cache: RwLock::new(HashMap::new()),
             mount_cache: RwLock::new(None),
+            stats_timeout: Some(DEFAULT_STATS_TIMEOUT),
+            probes: Mutex::new(HashMap::new()),
         }
     }
 
@@ -112,6 +133,47 @@ impl FsStatsCollector {
         self.cache_ttl = ttl;
     }
 
+    /// `None` queries the platform inline, with no stale detection.
+    pub fn set_stats_timeout(&mut self, timeout: Option<Duration>) {
+        self.stats_timeout = timeout;
+    }
+
+    /// Stats for each mount path, probed concurrently so several hung mounts
+    /// cost one timeout rather than one each.
+    #[must_use]
+    pub fn probe_mounts(&self, mount_paths: &[PathBuf]) -> Vec<(PathBuf, Result<FsStats>)> {
+        if self.stats_timeout.is_some() {
+            for mount_path in mount_paths {
+                if self.cache_hit(mount_path).is_none() {
+                    // Spawn failures resurface from collect_for_mount below.
+                    let _ = self.start_probe(mount_path);
+                }
+            }
+        }
+        mount_paths
+            .iter()
+            .map(|mount_path| (mount_path.clone(), self.collect_for_mount(mount_path)))
+            .collect()
+    }
+
+    /// Mounts whose stats probe has been outstanding longer than the timeout,
+    /// with how long each has been unresponsive.
+    #[must_use]
+    pub fn stale_mounts(&self) -> Vec<(PathBuf, Duration)> {
+        let Some(timeout) = self.stats_timeout else {
+            return Vec::new();
+        };
+        let mut stale: Vec<(PathBuf, Duration)> = self
+            .probes
+            .lock()
+            .iter()
+            .filter(|(_, probe)| probe.rx.is_empty() && probe.started.elapsed() > timeout)
+            .map(|(path, probe)| (path.clone(), probe.started.elapsed()))
+            .collect();
+        stale.sort_by(|a, b| a.0.cmp(&b.0));
+        stale
+    }
+
     fn cached_mounts(&self) -> Result<Vec<MountPoint>> {
         {
             let mc = self.mount_cache.read();
@@ -131,7 +193,10 @@ impl FsStatsCollector {
             return Ok(hit);
         }
 
-        let fresh = self.platform.fs_stats(mount_path)?;
+        let fresh = match self.stats_timeout {
+            Some(timeout) => self.probe(mount_path, timeout)?,
+            None => self.platform.fs_stats(mount_path)?,
+        };
         self.cache.write().insert(
             mount_path.to_path_buf(),
             CachedStats {
@@ -142,6 +207,69 @@ impl FsStatsCollector {
         Ok(fresh)
     }
 
+    fn probe(&self, mount_path: &Path, timeout: Duration) -> Result<FsStats> {
+        let (started, rx) = self.start_probe(mount_path)?;
+        if started.elapsed() > timeout && !rx.is_empty() {
+            // A probe given up on earlier has answered: the mount is back,
+            // but that reading is old. Take a fresh one.
+            self.probes.lock().remove(mount_path);
+            return self.probe(mount_path, timeout);
+        }
+        match rx.recv_deadline(started + timeout) {
+            Ok(result) => {
+                self.probes.lock().remove(mount_path);
+                result
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                // A concurrent caller may have taken the answer.
+                self.cache_hit(mount_path).ok_or_else(|| SbhError::FsStats {
+                    path: mount_path.to_path_buf(),
+                    details: format!(
+                        "stale: no response for {:.1}s",
+                        started.elapsed().as_secs_f64()
+                    ),
+                })
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.probes.lock().remove(mount_path);
+                Err(SbhError::FsStats {
+                    path: mount_path.to_path_buf(),
+                    details: "stats probe exited without a result".to_string(),
+                })
+            }
+        }
+    }
+
+    /// Join the outstanding probe for `mount_path`, or start one.
+    fn start_probe(&self, mount_path: &Path) -> Result<(Instant, Receiver<Result<FsStats>>)> {
+        let mut probes = self.probes.lock();
+        if let Some(probe) = probes.get(mount_path) {
+            return Ok((probe.started, probe.rx.clone()));
+        }
+        let (tx, rx) = bounded(1);
+        let platform = Arc::clone(&self.platform);
+        let path = mount_path.to_path_buf();
+        thread::Builder::new()
+            .name("sbh-statvfs".to_string())
+            .spawn(move || {
+                let _ = tx.send(platform.fs_stats(&path));
+            })
+            .map_err(|source| SbhError::Io {
+                path: mount_path.to_path_buf(),
+                source,
+            })?;
+        let started = Instant::now();
+        probes.insert(
+            mount_path.to_path_buf(),
+            Probe {
+                started,
+                rx: rx.clone(),
+            },
+        );
+        drop(probes);
+        Ok((started, rx))
+    }
+
     fn cache_hit(&self, mount_path: &Path) -> Option<FsStats> {
         let cache = self.cache.read();
         let entry = cache.get(mount_path)?;
@@ -171,8 +299,8 @@ mod tests {
     use std::collections::HashMap;
     use std::path::{Path, PathBuf};
     use std::sync::Arc;
-    use std::sync::atomic::{AtomicUsize, Ordering};
-    use std::time::Duration;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
 
     #[derive(Default)]
     struct TestServiceManager;
@@ -193,6 +321,8 @@ mod tests {
         stats: HashMap<PathBuf, FsStats>,
         fs_stats_calls: AtomicUsize,
         mount_points_calls: AtomicUsize,
+        /// While set, `fs_stats` blocks like a hung NFS mount.
+        hung: AtomicBool,
     }
 
     impl CountingPlatform {
@@ -202,6 +332,7 @@ mod tests {
                 stats,
                 fs_stats_calls: AtomicUsize::new(0),
                 mount_points_calls: AtomicUsize::new(0),
+                hung: AtomicBool::new(false),
             }
         }
     }
@@ -209,6 +340,9 @@ mod tests {
     impl Platform for CountingPlatform {
         fn fs_stats(&self, path: &Path) -> Result<FsStats> {
             self.fs_stats_calls.fetch_add(1, Ordering::SeqCst);
+            while self.hung.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
             self.stats
                 .get(path)
                 .cloned()
@@ -484,4 +618,55 @@ mod tests {
         assert_eq!(observed, stats);
         assert_eq!(platform.fs_stats_calls.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn hung_mount_is_reported_stale_without_blocking() {
+        let mounts = vec![MountPoint {
+            path: PathBuf::from("/nfs"),
+            device: "server:/export".to_string(),
+            fs_type: "nfs4".to_string(),
+            is_ram_backed: false,
+        }];
+        let stats = FsStats {
+            total_bytes: 100,
+            free_bytes: 50,
+            available_bytes: 50,
+            fs_type: "nfs4".to_string(),
+            mount_point: PathBuf::from("/nfs"),
+            is_readonly: false,
+        };
+        let platform = Arc::new(CountingPlatform::new(
+            mounts,
+            HashMap::from([(PathBuf::from("/nfs"), stats.clone())]),
+        ));
+        platform.hung.store(true, Ordering::SeqCst);
+        let mut collector = FsStatsCollector::new(platform.clone(), Duration::ZERO);
+        collector.set_stats_timeout(Some(Duration::from_millis(50)));
+
+        let err = collector.collect(Path::new("/nfs/a")).unwrap_err();
+        assert!(err.to_string().contains("stale"), "{err}");
+        let stale = collector.stale_mounts();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].0, PathBuf::from("/nfs"));
+
+        // The stuck probe is reused: no second thread, no second wait.
+        let started = Instant::now();
+        assert!(collector.collect(Path::new("/nfs/a")).is_err());
+        assert!(started.elapsed() < Duration::from_millis(50));
+        assert_eq!(platform.fs_stats_calls.load(Ordering::SeqCst), 1);
+
+        // Once the mount answers, it is no longer stale and a fresh reading
+        // is taken.
+        platform.hung.store(false, Ordering::SeqCst);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !collector.stale_mounts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(collector.stale_mounts().is_empty());
+        assert_eq!(
+            collector.collect(Path::new("/nfs/a")).expect("recovered"),
+            stats
+        );
+        assert_eq!(platform.fs_stats_calls.load(Ordering::SeqCst), 2);
+    }
 }
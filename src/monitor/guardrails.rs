@@ -215,6 +215,17 @@ impl AdaptiveGuard {
         self.recompute_status(obs_good);
     }
 
+    /// Drop all but the newest `min_observations` observations to save
+    /// memory. The status and e-process are kept.
+    pub fn shed_history(&mut self) {
+        let excess = self
+            .observations
+            .len()
+            .saturating_sub(self.config.min_observations);
+        self.observations.drain(..excess);
+        self.observations.shrink_to_fit();
+    }
+
     /// Current guard status.
     #[must_use]
     pub fn status(&self) -> GuardStatus {
@@ -612,6 +623,25 @@ mod tests {
         assert!((diag.conservative_fraction - 1.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn shedding_history_keeps_the_minimum_and_the_status() {
+        let config = GuardrailConfig {
+            min_observations: 3,
+            window_size: 10,
+            ..Default::default()
+        };
+        let mut guard = AdaptiveGuard::new(config);
+        for _ in 0..10 {
+            guard.observe(good_obs());
+        }
+        let status = guard.status();
+
+        guard.shed_history();
+        let diag = guard.diagnostics();
+        assert_eq!(diag.observation_count, 3);
+        assert_eq!(guard.status(), status);
+    }
+
     #[test]
     fn window_rolls_correctly() {
         let config = GuardrailConfig {
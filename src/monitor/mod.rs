@@ -1,10 +1,14 @@
 //! Filesystem monitoring: stats collection, EWMA rate estimation, PID pressure control,
-//! special location registry, predictive action pipeline, VOI scan scheduling.
+//! special location registry, predictive action pipeline, VOI scan scheduling,
+//! mount-table change detection, per-process write attribution, disk health.
 
+pub mod disk_health;
 pub mod ewma;
 pub mod fs_stats;
 pub mod guardrails;
+pub mod mount_watch;
 pub mod pid;
 pub mod predictive;
 pub mod special_locations;
 pub mod voi_scheduler;
+pub mod writers;
@@ -0,0 +1,390 @@
+//! Mount-table change detection: watched mounts that appear or vanish at runtime.
+//!
+//! The daemon polls the platform mount table (re-read from `/proc/self/mounts`
+//! at most every few seconds) and diffs it against the previous snapshot. Only
+//! mounts relevant to the scan roots are tracked: the mount holding each root,
+//! and disk-backed mounts beneath a root, such as a scratch disk plugged in
+//! under `/data`. Pseudo and RAM-backed filesystems below a root are ignored;
+//! `/tmp`-style locations have their own registry.
+//!
+//! Each mount is also classified as permanent, removable (USB or sysfs
+//! `removable` block devices), or transient (network FUSE mounts and the
+//! configured paths), so the daemon can leave media that may vanish alone.
+
+#![allow(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::core::config::MountsConfig;
+use crate::platform::pal::MountPoint;
+
+/// How often the daemon re-diffs the mount table.
+pub const MOUNT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Kernel and image filesystems that never hold user data worth watching.
+const PSEUDO_FS_TYPES: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "securityfs",
+    "squashfs",
+    "sysfs",
+    "tracefs",
+];
+
+/// How long-lived a mount is expected to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountClass {
+    Permanent,
+    /// Backed by a removable or USB-attached block device.
+    Removable,
+    /// Network FUSE mount or under a configured transient path.
+    Transient,
+}
+
+impl MountClass {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Permanent => "permanent",
+            Self::Removable => "removable",
+            Self::Transient => "transient",
+        }
+    }
+
+    #[must_use]
+    pub const fn is_permanent(self) -> bool {
+        matches!(self, Self::Permanent)
+    }
+}
+
+/// Classify a mount. `permanent_paths` wins over everything else; configured
+/// transient types and paths are checked before sysfs.
+#[must_use]
+pub fn classify_mount(mount: &MountPoint, config: &MountsConfig) -> MountClass {
+    classify_with(mount, config, is_removable_device)
+}
+
+fn classify_with(
+    mount: &MountPoint,
+    config: &MountsConfig,
+    removable: impl Fn(&str) -> bool,
+) -> MountClass {
+    if config.permanent_paths.contains(&mount.path) {
+        return MountClass::Permanent;
+    }
+    if config.transient_fs_types.contains(&mount.fs_type)
+        || config
+            .transient_paths
+            .iter()
+            .any(|path| mount.path.starts_with(path))
+    {
+        return MountClass::Transient;
+    }
+    if removable(&mount.device) {
+        return MountClass::Removable;
+    }
+    MountClass::Permanent
+}
+
+/// Whether sysfs reports the block device as removable or USB-attached.
+/// Partitions inherit the flag from their parent disk.
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &str) -> bool {
+    if !device.starts_with("/dev/") {
+        return false;
+    }
+    let Ok(device) = std::fs::canonicalize(device) else {
+        return false;
+    };
+    let Some(name) = device.file_name() else {
+        return false;
+    };
+    let Ok(sys) = std::fs::canonicalize(Path::new("/sys/class/block").join(name)) else {
+        return false;
+    };
+    if sys.components().any(|component| {
+        component
+            .as_os_str()
+            .to_str()
+            .is_some_and(|c| c.starts_with("usb"))
+    }) {
+        return true;
+    }
+    [Some(sys.as_path()), sys.parent()]
+        .into_iter()
+        .flatten()
+        .any(|dir| {
+            std::fs::read_to_string(dir.join("removable")).is_ok_and(|flag| flag.trim() == "1")
+        })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_removable_device(_device: &str) -> bool {
+    false
+}
+
+/// A change in the set of watched mounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MountEvent {
+    Mounted(MountPoint),
+    Unmounted(MountPoint),
+}
+
+impl MountEvent {
+    #[must_use]
+    pub const fn mount(&self) -> &MountPoint {
+        match self {
+            Self::Mounted(mount) | Self::Unmounted(mount) => mount,
+        }
+    }
+}
+
+/// Tracks the watched mounts for a set of scan roots.
+#[derive(Debug)]
+pub struct MountWatcher {
+    roots: Vec<PathBuf>,
+    watched: BTreeMap<PathBuf, MountPoint>,
+    last_poll: Option<Instant>,
+}
+
+impl MountWatcher {
+    /// Seed from the current mount table without emitting events.
+    #[must_use]
+    pub fn new(roots: &[PathBuf], mounts: &[MountPoint]) -> Self {
+        Self {
+            roots: roots.to_vec(),
+            watched: relevant_mounts(roots, mounts),
+            last_poll: None,
+        }
+    }
+
+    /// Replace the scan roots (config reload) and re-seed silently.
+    pub fn set_roots(&mut self, roots: &[PathBuf], mounts: &[MountPoint]) {
+        self.roots = roots.to_vec();
+        self.watched = relevant_mounts(roots, mounts);
+    }
+
+    #[must_use]
+    pub fn poll_due(&self, now: Instant) -> bool {
+        self.last_poll
+            .is_none_or(|last| now.saturating_duration_since(last) >= MOUNT_POLL_INTERVAL)
+    }
+
+    /// Diff `mounts` against the last snapshot. A different device at the
+    /// same path reports the old mount gone and the new one added.
+    pub fn refresh(&mut self, mounts: &[MountPoint], now: Instant) -> Vec<MountEvent> {
+        self.last_poll = Some(now);
+        let current = relevant_mounts(&self.roots, mounts);
+        let mut events = Vec::new();
+        for (path, old) in &self.watched {
+            if current.get(path).is_none_or(|new| new.device != old.device) {
+                events.push(MountEvent::Unmounted(old.clone()));
+            }
+        }
+        for (path, new) in &current {
+            if self
+                .watched
+                .get(path)
+                .is_none_or(|old| old.device != new.device)
+            {
+                events.push(MountEvent::Mounted(new.clone()));
+            }
+        }
+        self.watched = current;
+        events
+    }
+
+    /// Watched mounts mounted beneath a scan root. Their stats are not
+    /// reachable through the roots themselves, so pressure checks add them.
+    pub fn nested_mounts(&self) -> impl Iterator<Item = &Path> {
+        self.watched
+            .keys()
+            .filter(|path| {
+                self.roots
+                    .iter()
+                    .any(|root| path.starts_with(root) && path.as_path() != root)
+            })
+            .map(PathBuf::as_path)
+    }
+
+    #[must_use]
+    pub fn is_watched(&self, mount: &Path) -> bool {
+        self.watched.contains_key(mount)
+    }
+
+    pub fn mounts(&self) -> impl Iterator<Item = &MountPoint> {
+        self.watched.values()
+    }
+}
+
+/// The mount holding each root, plus disk-backed mounts beneath a root.
+fn relevant_mounts(roots: &[PathBuf], mounts: &[MountPoint]) -> BTreeMap<PathBuf, MountPoint> {
+    let mut relevant = BTreeMap::new();
+    for root in roots {
+        if let Some(holder) = mounts
+            .iter()
+            .filter(|mount| root.starts_with(&mount.path))
+            .max_by_key(|mount| mount.path.as_os_str().len())
+        {
+            relevant.insert(holder.path.clone(), holder.clone());
+        }
+        for mount in mounts {
+            if mount.path.starts_with(root)
+                && mount.path != *root
+                && !mount.is_ram_backed
+                && !PSEUDO_FS_TYPES.contains(&mount.fs_type.as_str())
+            {
+                relevant.insert(mount.path.clone(), mount.clone());
+            }
+        }
+    }
+    relevant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mount(path: &str, device: &str, fs_type: &str) -> MountPoint {
+        MountPoint {
+            path: PathBuf::from(path),
+            device: device.to_string(),
+            fs_type: fs_type.to_string(),
+            is_ram_backed: fs_type == "tmpfs",
+        }
+    }
+
+    #[test]
+    fn tracks_holding_and_nested_disk_mounts_only() {
+        let roots = vec![PathBuf::from("/data/projects")];
+        let mounts = vec![
+            mount("/", "/dev/sda1", "ext4"),
+            mount("/data", "/dev/sdb1", "xfs"),
+            mount("/data/projects/scratch", "/dev/sdc1", "ext4"),
+            mount("/data/projects/tmp", "tmpfs", "tmpfs"),
+            mount("/proc", "proc", "proc"),
+        ];
+        let watcher = MountWatcher::new(&roots, &mounts);
+        assert!(watcher.is_watched(Path::new("/data")));
+        assert!(watcher.is_watched(Path::new("/data/projects/scratch")));
+        assert!(!watcher.is_watched(Path::new("/")));
+        assert!(!watcher.is_watched(Path::new("/data/projects/tmp")));
+        assert_eq!(
+            watcher.nested_mounts().collect::<Vec<_>>(),
+            vec![Path::new("/data/projects/scratch")]
+        );
+    }
+
+    #[test]
+    fn refresh_reports_mounts_and_unmounts() {
+        let roots = vec![PathBuf::from("/data")];
+        let start = Instant::now();
+        let mut watcher = MountWatcher::new(
+            &roots,
+            &[
+                mount("/", "/dev/sda1", "ext4"),
+                mount("/data", "/dev/sdb1", "xfs"),
+            ],
+        );
+        assert!(watcher.poll_due(start));
+
+        // Scratch disk plugged in under the root.
+        let events = watcher.refresh(
+            &[
+                mount("/", "/dev/sda1", "ext4"),
+                mount("/data", "/dev/sdb1", "xfs"),
+                mount("/data/scratch", "/dev/sdc1", "ext4"),
+            ],
+            start,
+        );
+        assert_eq!(
+            events,
+            vec![MountEvent::Mounted(mount(
+                "/data/scratch",
+                "/dev/sdc1",
+                "ext4"
+            ))]
+        );
+        assert!(!watcher.poll_due(start + Duration::from_secs(1)));
+
+        // /data unmounted: the root now falls through to /, and the scratch
+        // disk went with it.
+        let events = watcher.refresh(
+            &[mount("/", "/dev/sda1", "ext4")],
+            start + MOUNT_POLL_INTERVAL,
+        );
+        assert_eq!(events.len(), 3);
+        assert!(events.contains(&MountEvent::Unmounted(mount("/data", "/dev/sdb1", "xfs"))));
+        assert!(events.contains(&MountEvent::Unmounted(mount(
+            "/data/scratch",
+            "/dev/sdc1",
+            "ext4"
+        ))));
+        assert!(events.contains(&MountEvent::Mounted(mount("/", "/dev/sda1", "ext4"))));
+    }
+
+    #[test]
+    fn classification_honours_overrides_then_types_then_sysfs() {
+        let mut config = MountsConfig::default();
+        let usb = |device: &str| device == "/dev/sdd1";
+
+        assert_eq!(
+            classify_with(&mount("/", "/dev/sda1", "ext4"), &config, usb),
+            MountClass::Permanent
+        );
+        assert_eq!(
+            classify_with(&mount("/mnt/usb", "/dev/sdd1", "vfat"), &config, usb),
+            MountClass::Removable
+        );
+        assert_eq!(
+            classify_with(
+                &mount("/home/me/remote", "me@host:", "fuse.sshfs"),
+                &config,
+                usb
+            ),
+            MountClass::Transient
+        );
+        assert_eq!(
+            classify_with(
+                &mount("/run/media/me/disk", "/dev/sda2", "ext4"),
+                &config,
+                usb
+            ),
+            MountClass::Transient
+        );
+
+        config.permanent_paths.push(PathBuf::from("/mnt/usb"));
+        assert_eq!(
+            classify_with(&mount("/mnt/usb", "/dev/sdd1", "vfat"), &config, usb),
+            MountClass::Permanent
+        );
+    }
+
+    #[test]
+    fn device_swap_at_same_path_is_unmount_then_mount() {
+        let roots = vec![PathBuf::from("/mnt/usb")];
+        let mut watcher = MountWatcher::new(&roots, &[mount("/mnt/usb", "/dev/sdd1", "vfat")]);
+        let events = watcher.refresh(&[mount("/mnt/usb", "/dev/sde1", "exfat")], Instant::now());
+        assert_eq!(
+            events,
+            vec![
+                MountEvent::Unmounted(mount("/mnt/usb", "/dev/sdd1", "vfat")),
+                MountEvent::Mounted(mount("/mnt/usb", "/dev/sde1", "exfat")),
+            ]
+        );
+    }
+}
@@ -47,6 +47,25 @@ pub struct PressureResponse {
     pub predicted_seconds: Option<f64>,
 }
 
+impl PressureResponse {
+    /// Green, zero-urgency response for ticks with no writable mount to
+    /// measure, so nothing downstream scans, deletes, or releases ballast.
+    #[must_use]
+    pub fn idle(base_poll_interval: Duration, mount: PathBuf) -> Self {
+        let (scan_interval, _, _) = response_policy(base_poll_interval, PressureLevel::Green, 0.0);
+        Self {
+            level: PressureLevel::Green,
+            urgency: 0.0,
+            scan_interval,
+            release_ballast_files: 0,
+            max_delete_batch: 0,
+            fallback_active: false,
+            causing_mount: mount,
+            predicted_seconds: None,
+        }
+    }
+}
+
 /// PID controller with hysteresis and anti-windup.
 #[derive(Debug, Clone)]
 pub struct PidPressureController {
@@ -134,6 +153,13 @@ impl PidPressureController {
         self.target_free_pct = target;
     }
 
+    /// Move the target without resetting controller state, for targets that
+    /// drift a little each tick (a time-of-day profile ramping in or out).
+    pub fn follow_target_free_pct(&mut self, target: f64) {
+        self.target_free_pct = target;
+        self.prev_target_free_pct = target;
+    }
+
     /// Disable prediction-based urgency boost (set thresholds to infinity).
     ///
     /// Call when `prediction.enabled` is toggled to `false` during config reload,
@@ -377,7 +403,7 @@ fn response_policy(
 
 #[cfg(test)]
 mod tests {
-    use super::{PidPressureController, PressureLevel, PressureReading};
+    use super::{PidPressureController, PressureLevel, PressureReading, PressureResponse};
     use std::path::PathBuf;
     use std::time::{Duration, Instant};
 
@@ -605,6 +631,15 @@ mod tests {
         assert_eq!(response.level, PressureLevel::Critical);
     }
 
+    #[test]
+    fn idle_response_requests_no_cleanup() {
+        let response = PressureResponse::idle(Duration::from_secs(1), PathBuf::from("/data"));
+        assert_eq!(response.level, PressureLevel::Green);
+        assert_eq!(response.release_ballast_files, 0);
+        assert_eq!(response.max_delete_batch, 0);
+        assert_eq!(response.causing_mount, PathBuf::from("/data"));
+    }
+
     #[test]
     fn scan_interval_decreases_with_severity() {
         let mut pid = PidPressureController::new(
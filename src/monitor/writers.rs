@@ -0,0 +1,713 @@
+//! Per-process write attribution: which processes are filling the disk.
+//!
+//! Processes are sampled from `/proc` (working directory, command name, and
+//! the cumulative `write_bytes` counter from `/proc/<pid>/io`). Two samples
+//! give each process a write rate; `sbh blame --watch` renders those live.
+//! The daemon keeps the last [`ATTRIBUTION_WINDOW`] of rates in a
+//! [`WriterHistory`] and persists it as an `attribution_snapshot` activity row
+//! when pressure escalates, so `sbh blame --incident` can name the writers
+//! after the fact. Plain `sbh blame` takes two samples a few seconds apart
+//! and ranks processes by [`blame_processes`], naming the coding agent behind
+//! each one from its command line or an ancestor's.
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Rows marked per refresh: the largest rate changes.
+pub const WRITER_HIGHLIGHTS: usize = 3;
+
+/// How much write history the daemon keeps for incident snapshots.
+pub const ATTRIBUTION_WINDOW: Duration = Duration::from_mins(10);
+
+/// Writers kept in a persisted snapshot.
+const SNAPSHOT_TOP_WRITERS: usize = 20;
+
+/// One process as seen in `/proc`.
+#[derive(Debug, Clone)]
+pub struct ProcessIo {
+    pub pid: u32,
+    pub comm: String,
+    pub cwd: PathBuf,
+    /// Owner of `/proc/<pid>` (the process's effective uid).
+    pub uid: Option<u32>,
+    /// Cumulative bytes sent to storage (`/proc/<pid>/io`); `None` when the
+    /// counter is unreadable (another user's process without root).
+    pub write_bytes: Option<u64>,
+    /// Parent PID from `/proc/<pid>/stat`.
+    pub ppid: Option<u32>,
+    /// Arguments from `/proc/<pid>/cmdline`, space-joined.
+    pub cmdline: String,
+}
+
+/// Scan `/proc` for processes with a readable working directory.
+///
+/// Returns an empty list on platforms without procfs.
+#[allow(unused_mut)]
+#[must_use]
+pub fn collect_processes() -> Vec<ProcessIo> {
+    let mut procs = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(proc_dir) = std::fs::read_dir("/proc") else {
+            return procs;
+        };
+
+        for entry in proc_dir {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let name = entry.file_name();
+            let name_str = name.to_string_lossy();
+
+            if !name_str.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            let Ok(pid) = name_str.parse::<u32>() else {
+                continue;
+            };
+
+            let proc_path = entry.path();
+
+            // Read cwd symlink.
+            let Ok(cwd) = std::fs::read_link(proc_path.join("cwd")) else {
+                continue;
+            };
+            if !cwd.is_absolute() {
+                continue;
+            }
+
+            // Read comm (process name).
+            let comm = std::fs::read_to_string(proc_path.join("comm"))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            if comm.is_empty() {
+                continue;
+            }
+
+            let write_bytes = std::fs::read_to_string(proc_path.join("io"))
+                .ok()
+                .and_then(|io| {
+                    io.lines()
+                        .find_map(|line| line.strip_prefix("write_bytes:"))
+                        .and_then(|value| value.trim().parse().ok())
+                });
+
+            let uid = std::fs::metadata(&proc_path).ok().map(|meta| meta.uid());
+
+            // The comm field in stat is parenthesized and may hold spaces, so
+            // fields are counted from the closing paren.
+            let parent_pid = std::fs::read_to_string(proc_path.join("stat"))
+                .ok()
+                .and_then(|stat| {
+                    let (_, rest) = stat.rsplit_once(')')?;
+                    rest.split_whitespace().nth(1)?.parse().ok()
+                });
+
+            let cmdline = std::fs::read(proc_path.join("cmdline"))
+                .map(|raw| {
+                    raw.split(|&b| b == 0)
+                        .filter(|arg| !arg.is_empty())
+                        .map(String::from_utf8_lossy)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            procs.push(ProcessIo {
+                pid,
+                comm,
+                cwd,
+                uid,
+                write_bytes,
+                ppid: parent_pid,
+                cmdline,
+            });
+        }
+    }
+
+    procs
+}
+
+/// Per-process counter carried between samples.
+#[derive(Debug, Clone)]
+pub struct WriterSample {
+    comm: String,
+    write_bytes: u64,
+    bytes_per_sec: f64,
+}
+
+/// One process's write throughput over the last sample interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriterRate {
+    pub pid: u32,
+    pub comm: String,
+    pub cwd: PathBuf,
+    pub uid: Option<u32>,
+    /// Bytes written during the interval.
+    pub bytes_written: u64,
+    pub bytes_per_sec: f64,
+    /// Change since the previous sample.
+    pub change_bytes_per_sec: f64,
+    pub highlighted: bool,
+}
+
+/// Turn two process samples into per-writer rates, fastest first.
+///
+/// Processes seen for the first time (or whose PID was reused by another
+/// command) get no rate until the next sample. Writers below
+/// `threshold_bytes_per_sec` or idle are dropped, then the
+/// [`WRITER_HIGHLIGHTS`] largest rate changes are marked.
+#[must_use]
+pub fn compute_writer_rates<S: std::hash::BuildHasher>(
+    previous: &HashMap<u32, WriterSample, S>,
+    current: &[ProcessIo],
+    elapsed: Duration,
+    threshold_bytes_per_sec: f64,
+) -> (Vec<WriterRate>, HashMap<u32, WriterSample>) {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let mut samples = HashMap::with_capacity(current.len());
+    let mut writers = Vec::new();
+
+    for proc in current {
+        let Some(write_bytes) = proc.write_bytes else {
+            continue;
+        };
+        let prev = previous
+            .get(&proc.pid)
+            .filter(|prev| prev.comm == proc.comm && prev.write_bytes <= write_bytes);
+        let bytes_written = prev.map_or(0, |prev| write_bytes - prev.write_bytes);
+        #[allow(clippy::cast_precision_loss)]
+        let bytes_per_sec = bytes_written as f64 / secs;
+        samples.insert(
+            proc.pid,
+            WriterSample {
+                comm: proc.comm.clone(),
+                write_bytes,
+                bytes_per_sec,
+            },
+        );
+
+        let Some(prev) = prev else {
+            continue;
+        };
+        if bytes_written == 0 || bytes_per_sec < threshold_bytes_per_sec {
+            continue;
+        }
+        writers.push(WriterRate {
+            pid: proc.pid,
+            comm: proc.comm.clone(),
+            cwd: proc.cwd.clone(),
+            uid: proc.uid,
+            bytes_written,
+            bytes_per_sec,
+            change_bytes_per_sec: bytes_per_sec - prev.bytes_per_sec,
+            highlighted: false,
+        });
+    }
+
+    let mut by_change: Vec<usize> = (0..writers.len())
+        .filter(|&i| writers[i].change_bytes_per_sec != 0.0)
+        .collect();
+    by_change.sort_by(|&a, &b| {
+        writers[b]
+            .change_bytes_per_sec
+            .abs()
+            .total_cmp(&writers[a].change_bytes_per_sec.abs())
+    });
+    for &i in by_change.iter().take(WRITER_HIGHLIGHTS) {
+        writers[i].highlighted = true;
+    }
+
+    writers.sort_by(|a, b| b.bytes_per_sec.total_cmp(&a.bytes_per_sec));
+    (writers, samples)
+}
+
+// ──────────────────── point-in-time blame ────────────────────
+
+/// Command-line fragments that identify a coding agent, checked in order
+/// against the lowercased command line.
+const AGENT_SIGNATURES: &[(&str, &str)] = &[
+    ("claude-code", "claude-code"),
+    ("claude", "claude-code"),
+    ("codex", "codex"),
+    ("aider", "aider"),
+    ("gemini", "gemini-cli"),
+    ("opencode", "opencode"),
+    ("goose", "goose"),
+    ("cursor-agent", "cursor"),
+    ("cursor", "cursor"),
+    ("copilot", "copilot"),
+    ("cline", "cline"),
+    ("amp", "amp"),
+];
+
+/// How many parents to climb looking for an agent.
+const MAX_AGENT_DEPTH: usize = 16;
+
+/// The agent named by one process's own command line, if any.
+///
+/// Only the executable and script arguments count, so a `cargo build` inside a
+/// directory called `codex` is not mistaken for the agent. A signature must
+/// be a whole path or name component, or its `-`-prefix (`codex-cli`).
+#[must_use]
+pub fn agent_from_cmdline(comm: &str, cmdline: &str) -> Option<&'static str> {
+    let program_args = cmdline
+        .split_whitespace()
+        .take_while(|arg| !arg.starts_with('-'))
+        .take(2)
+        .map(str::to_ascii_lowercase);
+    let haystacks: Vec<String> = std::iter::once(comm.to_ascii_lowercase())
+        .chain(program_args)
+        .collect();
+    AGENT_SIGNATURES.iter().find_map(|(needle, agent)| {
+        haystacks
+            .iter()
+            .flat_map(|hay| hay.split(['/', '.', '@', '_']))
+            .any(|part| {
+                part.strip_prefix(needle)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+            })
+            .then_some(*agent)
+    })
+}
+
+/// Attribute each process to an agent, by its own command line or that of
+/// the nearest ancestor naming one (a `cargo` spawned by an agent counts
+/// toward that agent).
+#[must_use]
+pub fn attribute_agents(processes: &[ProcessIo]) -> HashMap<u32, &'static str> {
+    let by_pid: HashMap<u32, &ProcessIo> = processes.iter().map(|p| (p.pid, p)).collect();
+    let mut agents = HashMap::new();
+    for proc in processes {
+        let mut current = Some(proc);
+        for _ in 0..MAX_AGENT_DEPTH {
+            let Some(candidate) = current else {
+                break;
+            };
+            if let Some(agent) = agent_from_cmdline(&candidate.comm, &candidate.cmdline) {
+                agents.insert(proc.pid, agent);
+                break;
+            }
+            current = candidate
+                .ppid
+                .filter(|&ppid| ppid != candidate.pid)
+                .and_then(|ppid| by_pid.get(&ppid).copied());
+        }
+    }
+    agents
+}
+
+/// One process's writes: its rate over the sampling window and what it
+/// has written since it started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessBlame {
+    pub pid: u32,
+    pub comm: String,
+    pub cwd: PathBuf,
+    pub agent: Option<&'static str>,
+    pub bytes_per_sec: f64,
+    /// Cumulative `write_bytes` at the second sample.
+    pub total_bytes: u64,
+}
+
+/// Writes summed over every process attributed to one agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentBlame {
+    pub agent: &'static str,
+    pub processes: usize,
+    pub bytes_per_sec: f64,
+    pub total_bytes: u64,
+}
+
+/// Rank processes that have written anything by current rate, then by
+/// cumulative bytes, and total them per agent. Processes missing from
+/// `before` or whose PID was reused get a rate of zero.
+#[must_use]
+pub fn blame_processes(
+    before: &[ProcessIo],
+    after: &[ProcessIo],
+    elapsed: Duration,
+) -> (Vec<ProcessBlame>, Vec<AgentBlame>) {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let earlier: HashMap<u32, &ProcessIo> = before.iter().map(|p| (p.pid, p)).collect();
+    let agents = attribute_agents(after);
+
+    let mut processes: Vec<ProcessBlame> = after
+        .iter()
+        .filter_map(|proc| {
+            let total_bytes = proc.write_bytes.filter(|&bytes| bytes > 0)?;
+            let delta = earlier
+                .get(&proc.pid)
+                .filter(|prev| prev.comm == proc.comm)
+                .and_then(|prev| prev.write_bytes)
+                .and_then(|prev| total_bytes.checked_sub(prev))
+                .unwrap_or(0);
+            #[allow(clippy::cast_precision_loss)]
+            let bytes_per_sec = delta as f64 / secs;
+            Some(ProcessBlame {
+                pid: proc.pid,
+                comm: proc.comm.clone(),
+                cwd: proc.cwd.clone(),
+                agent: agents.get(&proc.pid).copied(),
+                bytes_per_sec,
+                total_bytes,
+            })
+        })
+        .collect();
+    processes.sort_by(|a, b| {
+        b.bytes_per_sec
+            .total_cmp(&a.bytes_per_sec)
+            .then_with(|| b.total_bytes.cmp(&a.total_bytes))
+            .then_with(|| a.pid.cmp(&b.pid))
+    });
+
+    let mut by_agent: HashMap<&'static str, AgentBlame> = HashMap::new();
+    for proc in &processes {
+        let Some(agent) = proc.agent else {
+            continue;
+        };
+        let entry = by_agent.entry(agent).or_insert_with(|| AgentBlame {
+            agent,
+            processes: 0,
+            bytes_per_sec: 0.0,
+            total_bytes: 0,
+        });
+        entry.processes += 1;
+        entry.bytes_per_sec += proc.bytes_per_sec;
+        entry.total_bytes = entry.total_bytes.saturating_add(proc.total_bytes);
+    }
+    let mut agents: Vec<AgentBlame> = by_agent.into_values().collect();
+    agents.sort_by(|a, b| {
+        b.bytes_per_sec
+            .total_cmp(&a.bytes_per_sec)
+            .then_with(|| b.total_bytes.cmp(&a.total_bytes))
+            .then_with(|| a.agent.cmp(b.agent))
+    });
+    (processes, agents)
+}
+
+// ──────────────────── incident history ────────────────────
+
+/// Rolling record of per-process writes over the last `window`.
+#[derive(Debug)]
+pub struct WriterHistory {
+    window: Duration,
+    samples: HashMap<u32, WriterSample>,
+    last_sample: Option<Instant>,
+    intervals: VecDeque<(Instant, Vec<WriterRate>)>,
+}
+
+impl WriterHistory {
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: HashMap::new(),
+            last_sample: None,
+            intervals: VecDeque::new(),
+        }
+    }
+
+    /// When the last sample was recorded.
+    #[must_use]
+    pub const fn last_sample(&self) -> Option<Instant> {
+        self.last_sample
+    }
+
+    /// Forget the window and the last sample, releasing their memory.
+    /// Attribution starts over from the next sample.
+    pub fn shed(&mut self) {
+        self.samples = HashMap::new();
+        self.intervals = VecDeque::new();
+        self.last_sample = None;
+    }
+
+    /// Fold a fresh process sample into the window.
+    pub fn record(&mut self, now: Instant, processes: &[ProcessIo]) {
+        let elapsed = self
+            .last_sample
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        let (writers, samples) = compute_writer_rates(&self.samples, processes, elapsed, 0.0);
+        self.samples = samples;
+        self.last_sample = Some(now);
+        if !writers.is_empty() {
+            self.intervals.push_back((now, writers));
+        }
+        while self
+            .intervals
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) > self.window)
+        {
+            self.intervals.pop_front();
+        }
+    }
+
+    /// Summarize the window: total bytes and peak rate per process, largest
+    /// writers first.
+    #[must_use]
+    pub fn snapshot(&self) -> AttributionSnapshot {
+        let mut growth: HashMap<(u32, &str), WriterGrowth> = HashMap::new();
+        for (_, writers) in &self.intervals {
+            for writer in writers {
+                let entry = growth
+                    .entry((writer.pid, writer.comm.as_str()))
+                    .or_insert_with(|| WriterGrowth {
+                        pid: writer.pid,
+                        comm: writer.comm.clone(),
+                        cwd: writer.cwd.to_string_lossy().into_owned(),
+                        uid: writer.uid,
+                        bytes_written: 0,
+                        peak_bytes_per_sec: 0.0,
+                    });
+                entry.bytes_written = entry.bytes_written.saturating_add(writer.bytes_written);
+                entry.peak_bytes_per_sec = entry.peak_bytes_per_sec.max(writer.bytes_per_sec);
+            }
+        }
+        let mut writers: Vec<WriterGrowth> = growth.into_values().collect();
+        writers.sort_by(|a, b| {
+            b.bytes_written
+                .cmp(&a.bytes_written)
+                .then_with(|| a.pid.cmp(&b.pid))
+        });
+        writers.truncate(SNAPSHOT_TOP_WRITERS);
+        AttributionSnapshot {
+            window_secs: self.window.as_secs(),
+            writers,
+        }
+    }
+}
+
+/// Bytes one process wrote over a snapshot window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WriterGrowth {
+    pub pid: u32,
+    pub comm: String,
+    pub cwd: String,
+    /// Absent in snapshots written before uids were sampled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    pub bytes_written: u64,
+    pub peak_bytes_per_sec: f64,
+}
+
+/// Bytes written from one working directory over a snapshot window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceGrowth {
+    pub cwd: String,
+    pub bytes_written: u64,
+    pub processes: usize,
+}
+
+/// Writers leading into a pressure event, as stored in `activity_log.details`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributionSnapshot {
+    pub window_secs: u64,
+    pub writers: Vec<WriterGrowth>,
+}
+
+impl AttributionSnapshot {
+    /// Group writers by working directory, largest first.
+    #[must_use]
+    pub fn workspaces(&self) -> Vec<WorkspaceGrowth> {
+        let mut by_cwd: HashMap<&str, WorkspaceGrowth> = HashMap::new();
+        for writer in &self.writers {
+            let entry = by_cwd
+                .entry(writer.cwd.as_str())
+                .or_insert_with(|| WorkspaceGrowth {
+                    cwd: writer.cwd.clone(),
+                    bytes_written: 0,
+                    processes: 0,
+                });
+            entry.bytes_written = entry.bytes_written.saturating_add(writer.bytes_written);
+            entry.processes += 1;
+        }
+        let mut workspaces: Vec<WorkspaceGrowth> = by_cwd.into_values().collect();
+        workspaces.sort_by(|a, b| {
+            b.bytes_written
+                .cmp(&a.bytes_written)
+                .then_with(|| a.cwd.cmp(&b.cwd))
+        });
+        workspaces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1_048_576;
+
+    fn proc(pid: u32, comm: &str, cwd: &str, write_bytes: Option<u64>) -> ProcessIo {
+        ProcessIo {
+            pid,
+            comm: comm.to_string(),
+            cwd: PathBuf::from(cwd),
+            uid: Some(1000),
+            write_bytes,
+            ppid: Some(1),
+            cmdline: comm.to_string(),
+        }
+    }
+
+    #[test]
+    fn writer_rates_apply_threshold_highlight_changes_and_ignore_pid_reuse() {
+        let second = Duration::from_secs(1);
+
+        let (writers, samples) = compute_writer_rates(
+            &HashMap::new(),
+            &[
+                proc(1, "cargo", "/data/a", Some(0)),
+                proc(2, "agent", "/data/b", Some(0)),
+            ],
+            second,
+            0.0,
+        );
+        assert!(writers.is_empty(), "first sample has no rate yet");
+
+        let (writers, samples) = compute_writer_rates(
+            &samples,
+            &[
+                proc(1, "cargo", "/data/a", Some(10 * MIB)),
+                proc(2, "agent", "/data/b", Some(MIB)),
+                proc(3, "hidden", "/data/c", None),
+            ],
+            second,
+            0.0,
+        );
+        assert_eq!(writers.len(), 2);
+        assert_eq!(writers[0].pid, 1);
+        assert_eq!(writers[0].bytes_written, 10 * MIB);
+        assert!(writers.iter().all(|w| w.highlighted));
+
+        // PID 2 was reused by another command: no rate until the next sample.
+        let (writers, _) = compute_writer_rates(
+            &samples,
+            &[
+                proc(1, "cargo", "/data/a", Some(40 * MIB)),
+                proc(2, "rsync", "/data/b", Some(90 * MIB)),
+            ],
+            second,
+            5.0 * 1_048_576.0,
+        );
+        assert_eq!(writers.len(), 1);
+        assert_eq!(writers[0].pid, 1);
+        assert!((writers[0].change_bytes_per_sec / 1_048_576.0 - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn blame_ranks_by_rate_and_credits_children_to_their_agent() {
+        let mut agent = proc(10, "node", "/data/a", Some(MIB));
+        agent.cmdline = "node /usr/lib/node_modules/@openai/codex/bin/codex.js".to_string();
+        let mut cargo = proc(11, "cargo", "/data/codex-fork", Some(0));
+        cargo.ppid = Some(10);
+        let before = [
+            agent.clone(),
+            cargo.clone(),
+            proc(12, "rsync", "/b", Some(0)),
+        ];
+
+        cargo.write_bytes = Some(20 * MIB);
+        let after = [
+            agent,
+            cargo,
+            proc(12, "rsync", "/b", Some(2 * MIB)),
+            proc(13, "idle", "/c", Some(0)),
+            proc(14, "new", "/d", Some(900 * MIB)),
+        ];
+        let (processes, agents) = blame_processes(&before, &after, Duration::from_secs(2));
+
+        let order: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+        assert_eq!(order, [11, 12, 14, 10], "rate first, then cumulative bytes");
+        assert_eq!(processes[0].agent, Some("codex"));
+        assert!((processes[0].bytes_per_sec / 1_048_576.0 - 10.0).abs() < 1e-6);
+        assert_eq!(processes[1].agent, None);
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].processes, 2);
+        assert_eq!(agents[0].total_bytes, 21 * MIB);
+
+        assert_eq!(
+            agent_from_cmdline("cargo", "cargo build --target-dir codex"),
+            None
+        );
+        assert_eq!(
+            agent_from_cmdline("claude", "claude --resume"),
+            Some("claude-code")
+        );
+    }
+
+    #[test]
+    fn history_sums_writes_inside_the_window_only() {
+        let start = Instant::now();
+        let mut history = WriterHistory::new(Duration::from_secs(60));
+        history.record(start, &[proc(1, "cargo", "/data/a", Some(0))]);
+        history.record(
+            start + Duration::from_secs(30),
+            &[
+                proc(1, "cargo", "/data/a", Some(100 * MIB)),
+                proc(2, "agent", "/data/a", Some(0)),
+            ],
+        );
+        history.record(
+            start + Duration::from_secs(60),
+            &[
+                proc(1, "cargo", "/data/a", Some(110 * MIB)),
+                proc(2, "agent", "/data/a", Some(50 * MIB)),
+            ],
+        );
+
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.window_secs, 60);
+        assert_eq!(snapshot.writers.len(), 2);
+        assert_eq!(snapshot.writers[0].pid, 1);
+        assert_eq!(snapshot.writers[0].bytes_written, 110 * MIB);
+        assert_eq!(snapshot.workspaces().len(), 1);
+        assert_eq!(snapshot.workspaces()[0].bytes_written, 160 * MIB);
+        assert_eq!(snapshot.workspaces()[0].processes, 2);
+
+        // The first interval ages out of the window.
+        history.record(
+            start + Duration::from_secs(100),
+            &[
+                proc(1, "cargo", "/data/a", Some(110 * MIB)),
+                proc(2, "agent", "/data/a", Some(50 * MIB)),
+            ],
+        );
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.writers[0].pid, 2);
+        assert_eq!(snapshot.writers[1].bytes_written, 10 * MIB);
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let snapshot = AttributionSnapshot {
+            window_secs: 600,
+            writers: vec![WriterGrowth {
+                pid: 7,
+                comm: "cargo".to_string(),
+                cwd: "/data/a".to_string(),
+                uid: Some(1000),
+                bytes_written: 42,
+                peak_bytes_per_sec: 1.5,
+            }],
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let parsed: AttributionSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, snapshot);
+
+        let legacy: AttributionSnapshot = serde_json::from_str(
+            r#"{"window_secs":600,"writers":[{"pid":7,"comm":"cargo","cwd":"/data/a","bytes_written":42,"peak_bytes_per_sec":1.5}]}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.writers[0].uid, None);
+    }
+}
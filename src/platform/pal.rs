@@ -209,6 +209,131 @@ impl Platform for LinuxPlatform {
     }
 }
 
+/// Windows platform implementation.
+///
+/// Volumes and memory come from `sysinfo`, which wraps `GetLogicalDrives`,
+/// `GetDiskFreeSpaceExW`, and `GlobalMemoryStatusEx`; the crate forbids
+/// `unsafe`, so it does not call them directly. Only drive-letter volumes
+/// are reported, and `free_bytes` equals `available_bytes` (the space left
+/// to the calling user).
+#[cfg(windows)]
+#[derive(Debug)]
+pub struct WindowsPlatform {
+    mounts_cache: RwLock<Option<(Vec<MountPoint>, Instant)>>,
+    cache_ttl: Duration,
+}
+
+#[cfg(windows)]
+impl Default for WindowsPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(windows)]
+impl WindowsPlatform {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            mounts_cache: RwLock::new(None),
+            cache_ttl: Duration::from_secs(5),
+        }
+    }
+
+    fn get_cached_mounts(&self) -> Result<Vec<MountPoint>> {
+        {
+            let cache = self.mounts_cache.read();
+            if let Some((mounts, collected_at)) = &*cache
+                && collected_at.elapsed() < self.cache_ttl
+            {
+                return Ok(mounts.clone());
+            }
+        }
+
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let mut mounts: Vec<MountPoint> = disks
+            .list()
+            .iter()
+            .map(|disk| {
+                let fs_type = disk.file_system().to_string_lossy().into_owned();
+                MountPoint {
+                    path: normalize_drive_root(disk.mount_point()),
+                    device: disk.name().to_string_lossy().into_owned(),
+                    is_ram_backed: is_ram_fs(&fs_type),
+                    fs_type,
+                }
+            })
+            .collect();
+        mounts.sort_by(|left, right| {
+            right
+                .path
+                .as_os_str()
+                .len()
+                .cmp(&left.path.as_os_str().len())
+        });
+
+        *self.mounts_cache.write() = Some((mounts.clone(), Instant::now()));
+        Ok(mounts)
+    }
+}
+
+#[cfg(windows)]
+impl Platform for WindowsPlatform {
+    fn fs_stats(&self, path: &Path) -> Result<FsStats> {
+        let target = normalize_drive_root(path);
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let disk = disks
+            .list()
+            .iter()
+            .filter(|disk| target.starts_with(normalize_drive_root(disk.mount_point())))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .ok_or_else(|| SbhError::FsStats {
+                path: path.to_path_buf(),
+                details: "could not map path to a volume".to_string(),
+            })?;
+        Ok(FsStats {
+            total_bytes: disk.total_space(),
+            free_bytes: disk.available_space(),
+            available_bytes: disk.available_space(),
+            fs_type: disk.file_system().to_string_lossy().into_owned(),
+            mount_point: normalize_drive_root(disk.mount_point()),
+            is_readonly: disk.is_read_only(),
+        })
+    }
+
+    fn mount_points(&self) -> Result<Vec<MountPoint>> {
+        self.get_cached_mounts()
+    }
+
+    fn is_ram_backed(&self, path: &Path) -> Result<bool> {
+        let mounts = self.mount_points()?;
+        Ok(find_mount(&normalize_drive_root(path), &mounts)
+            .is_some_and(|mount| mount.is_ram_backed))
+    }
+
+    fn default_paths(&self) -> PlatformPaths {
+        PlatformPaths::default()
+    }
+
+    fn memory_info(&self) -> Result<MemoryInfo> {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        Ok(MemoryInfo {
+            total_bytes: system.total_memory(),
+            available_bytes: system.available_memory(),
+            swap_total_bytes: system.total_swap(),
+            swap_free_bytes: system.free_swap(),
+        })
+    }
+
+    fn service_manager(&self) -> Box<dyn ServiceManager> {
+        match crate::daemon::service::ScheduledTaskServiceManager::from_env(false) {
+            Ok(mgr) => Box::new(mgr),
+            Err(_) => Box::<NoopServiceManager>::default(),
+        }
+    }
+}
+
 /// In-memory mock implementation for deterministic tests.
 #[derive(Debug, Clone)]
 pub struct MockPlatform {
@@ -277,14 +402,32 @@ pub fn detect_platform() -> Result<Arc<dyn Platform>> {
     {
         Ok(Arc::new(LinuxPlatform::new()))
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(windows)]
+    {
+        Ok(Arc::new(WindowsPlatform::new()))
+    }
+    #[cfg(not(any(target_os = "linux", windows)))]
     {
         Err(SbhError::UnsupportedPlatform {
-            details: "only Linux is currently implemented".to_string(),
+            details: "only Linux and Windows are currently implemented".to_string(),
         })
     }
 }
 
+/// Upper-case a leading drive letter (`c:\x` -> `C:\x`) so prefix matching
+/// against volume roots is not defeated by how the user typed the path.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn normalize_drive_root(path: &Path) -> PathBuf {
+    let raw = path.to_string_lossy();
+    let bytes = raw.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_lowercase() && bytes[1] == b':' {
+        let mut fixed = raw.into_owned();
+        fixed[..1].make_ascii_uppercase();
+        return PathBuf::from(fixed);
+    }
+    path.to_path_buf()
+}
+
 fn parse_proc_mounts(raw: &str) -> Vec<MountPoint> {
     let mut mounts = Vec::new();
     for line in raw.lines() {
@@ -385,7 +528,7 @@ fn unescape_mount_field(raw: &str) -> String {
 
 /// Decode octal escape sequences (`\NNN`) used by the Linux kernel.
 /// Returns a PathBuf via OsString to preserve raw bytes (e.g. invalid UTF-8).
-fn unescape_mount_path(raw: &str) -> PathBuf {
+pub(crate) fn unescape_mount_path(raw: &str) -> PathBuf {
     let mut bytes = Vec::with_capacity(raw.len());
     let raw_bytes = raw.as_bytes();
     let mut i = 0;
@@ -426,8 +569,8 @@ mod tests {
     use crate::core::errors::SbhError;
 
     use super::{
-        MountPoint, find_mount, is_ram_fs, parse_meminfo, parse_proc_mounts, unescape_mount_field,
-        unescape_mount_path,
+        MountPoint, find_mount, is_ram_fs, normalize_drive_root, parse_meminfo, parse_proc_mounts,
+        unescape_mount_field, unescape_mount_path,
     };
     use std::path::Path;
 
@@ -513,6 +656,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn drive_letters_are_upper_cased_for_volume_matching() {
+        assert_eq!(
+            normalize_drive_root(Path::new(r"c:\builds\agent")),
+            Path::new(r"C:\builds\agent")
+        );
+        assert_eq!(normalize_drive_root(Path::new(r"D:\")), Path::new(r"D:\"));
+        assert_eq!(
+            normalize_drive_root(Path::new("/tmp/work")),
+            Path::new("/tmp/work")
+        );
+    }
+
     #[test]
     fn ram_fs_detection_matches_expected_types() {
         assert!(is_ram_fs("tmpfs"));
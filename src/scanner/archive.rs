@@ -0,0 +1,562 @@
+//! Archival tiering: relocate cold artifacts to another filesystem instead of
+//! deleting them.
+//!
+//! A relocation copies the candidate tree under `<archive>/<id>/`, verifies
+//! every file by SHA-256 against its source, and only then lets the executor
+//! remove the original. Each relocation is recorded in an append-only index
+//! at the archive root, so `sbh restore` can put the tree back and the
+//! archive stays self-describing if the disk moves to another host.
+//!
+//! With an object store configured, the tree goes to a bucket as a tarball
+//! instead (see [`crate::scanner::object_store`]) and only the index stays
+//! under the archive root.
+
+#![allow(missing_docs)]
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::errors::{Result, SbhError};
+use crate::core::marker;
+use crate::platform::pal::detect_platform;
+use crate::scanner::object_store::{self, ObjectStore, StoredObject};
+use crate::scanner::patterns::ArtifactCategory;
+
+/// Index of relocated trees, one JSON object per line, at the archive root.
+pub const INDEX_FILENAME: &str = "sbh-archive-index.jsonl";
+
+/// Where and what to relocate (`[archive]` in the config).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relocation {
+    pub archive_root: PathBuf,
+    pub categories: Vec<ArtifactCategory>,
+    /// Free-space floor on the archive filesystem, in percent.
+    pub min_free_pct: f64,
+    /// Upload to this bucket; the archive root then only holds the index.
+    pub object_store: Option<ObjectStore>,
+}
+
+impl Relocation {
+    #[must_use]
+    pub fn applies_to(&self, category: ArtifactCategory) -> bool {
+        self.categories.contains(&category)
+    }
+}
+
+/// One relocated tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub id: String,
+    pub original_path: PathBuf,
+    pub archived_path: PathBuf,
+    pub size_bytes: u64,
+    pub files: u64,
+    pub category: String,
+    /// RFC 3339 time the copy was verified.
+    pub archived_at: String,
+    /// Set when the copy lives in an object store; `archived_path` is then
+    /// its URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<StoredObject>,
+}
+
+impl ArchiveEntry {
+    /// Directory holding this entry's copy (`<archive>/<id>`).
+    #[must_use]
+    pub fn entry_dir(&self) -> &Path {
+        self.archived_path
+            .parent()
+            .unwrap_or(self.archived_path.as_path())
+    }
+}
+
+/// Copy `src` into the archive and verify it, without touching `src`.
+///
+/// Refuses a local archive on the same filesystem (moving would free
+/// nothing) and one that `size_bytes` would push below its free-space floor.
+/// Neither applies to an object store, which stages nothing locally. On any
+/// failure the partial copy is removed.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn archive_copy(
+    src: &Path,
+    relocation: &Relocation,
+    category: ArtifactCategory,
+    size_bytes: u64,
+) -> Result<ArchiveEntry> {
+    let root = &relocation.archive_root;
+    fs::create_dir_all(root).map_err(|e| SbhError::io(root, e))?;
+    if let Some(store) = &relocation.object_store {
+        return upload(src, store, entry_id(src), category);
+    }
+    if same_device(src, root) {
+        return Err(SbhError::SafetyVeto {
+            path: src.to_path_buf(),
+            reason: format!(
+                "archive {} is on the same filesystem; relocating would free nothing",
+                root.display()
+            ),
+        });
+    }
+    let stats = detect_platform()?.fs_stats(root)?;
+    let floor = (relocation.min_free_pct / 100.0 * stats.total_bytes as f64) as u64;
+    if stats.available_bytes.saturating_sub(size_bytes) < floor {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "archive {} lacks room for {size_bytes} bytes above its {}% floor",
+                root.display(),
+                relocation.min_free_pct
+            ),
+        });
+    }
+
+    let id = entry_id(src);
+    let entry_dir = root.join(&id);
+    let name = src.file_name().ok_or_else(|| SbhError::Runtime {
+        details: format!("cannot archive {}: no file name", src.display()),
+    })?;
+    let archived_path = entry_dir.join(name);
+    fs::create_dir_all(&entry_dir).map_err(|e| SbhError::io(&entry_dir, e))?;
+    let (size_bytes, files) = match copy_verified(src, &archived_path) {
+        Ok(copied) => copied,
+        Err(err) => {
+            let _ = fs::remove_dir_all(&entry_dir);
+            return Err(err);
+        }
+    };
+    Ok(ArchiveEntry {
+        id,
+        original_path: src.to_path_buf(),
+        archived_path,
+        size_bytes,
+        files,
+        category: category.config_name().to_string(),
+        archived_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        object: None,
+    })
+}
+
+/// Stream `src` to the object store.
+fn upload(
+    src: &Path,
+    store: &ObjectStore,
+    id: String,
+    category: ArtifactCategory,
+) -> Result<ArchiveEntry> {
+    let object = store.put_tree(src, &id)?;
+    let (size_bytes, files) = tree_totals(src);
+    Ok(ArchiveEntry {
+        id,
+        original_path: src.to_path_buf(),
+        archived_path: PathBuf::from(&object.url),
+        size_bytes,
+        files,
+        category: category.config_name().to_string(),
+        archived_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        object: Some(object),
+    })
+}
+
+/// Drop an archived copy whose original could not be removed.
+pub fn discard(entry: &ArchiveEntry) -> Result<()> {
+    if let Some(object) = &entry.object {
+        return object.delete();
+    }
+    let dir = entry.entry_dir();
+    fs::remove_dir_all(dir).map_err(|e| SbhError::io(dir, e))
+}
+
+/// Record a completed relocation in the archive index.
+pub fn append_index(root: &Path, entry: &ArchiveEntry) -> Result<()> {
+    let path = root.join(INDEX_FILENAME);
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| SbhError::io(&path, e))
+}
+
+/// All relocations recorded at `root`, oldest first. Unparseable lines are
+/// skipped so one bad write does not hide the rest of the archive.
+pub fn read_index(root: &Path) -> Result<Vec<ArchiveEntry>> {
+    let path = root.join(INDEX_FILENAME);
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(SbhError::io(&path, err)),
+    };
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(std::io::Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+/// Find an entry by id or by the path it was relocated from; the most recent
+/// relocation of a path wins.
+#[must_use]
+pub fn find_entry<'a>(entries: &'a [ArchiveEntry], key: &str) -> Option<&'a ArchiveEntry> {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.id == key || entry.original_path == Path::new(key))
+}
+
+/// Move an archived tree back to `dest` (its original path unless
+/// overridden) and drop it from the index.
+///
+/// Refuses to overwrite anything already at `dest`, and an object the
+/// bucket lifecycle has already expired.
+pub fn restore(root: &Path, entry: &ArchiveEntry, dest: &Path) -> Result<()> {
+    if fs::symlink_metadata(dest).is_ok() {
+        return Err(SbhError::SafetyVeto {
+            path: dest.to_path_buf(),
+            reason: "restore target already exists".to_string(),
+        });
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+    }
+    if let Some(object) = &entry.object {
+        if object.is_expired(chrono::Utc::now()) {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "{} expired from the object store at {}",
+                    entry.id,
+                    object.expires_at.as_deref().unwrap_or("unknown")
+                ),
+            });
+        }
+        object.fetch_into(dest)?;
+        let _ = object.delete();
+    } else {
+        if fs::rename(&entry.archived_path, dest).is_err() {
+            // Different filesystem: copy back with the same verification.
+            if let Err(err) = copy_verified(&entry.archived_path, dest) {
+                let _ = remove_tree(dest);
+                return Err(err);
+            }
+        }
+        let _ = fs::remove_dir_all(entry.entry_dir());
+    }
+
+    let remaining: Vec<ArchiveEntry> = read_index(root)?
+        .into_iter()
+        .filter(|other| other.id != entry.id)
+        .collect();
+    rewrite_index(root, &remaining)
+}
+
+fn rewrite_index(root: &Path, entries: &[ArchiveEntry]) -> Result<()> {
+    write_index(&root.join(INDEX_FILENAME), entries)
+}
+
+/// Atomically replace a JSON-lines index with `entries`, one per line.
+/// Shared with the quarantine index, which has the same layout.
+pub(crate) fn write_index<T: Serialize>(path: &Path, entries: &[T]) -> Result<()> {
+    let mut raw = String::new();
+    for entry in entries {
+        raw.push_str(&serde_json::to_string(entry)?);
+        raw.push('\n');
+    }
+    marker::replace_file(path, raw.as_bytes(), 0o644).map_err(|e| SbhError::io(path, e))
+}
+
+/// `<UTC timestamp>-<first 8 hex of SHA-256(path)>`: sortable and unique
+/// enough that two relocations of one path never collide.
+pub(crate) fn entry_id(src: &Path) -> String {
+    let digest = Sha256::digest(src.as_os_str().as_encoded_bytes());
+    format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+        object_store::hex(&digest[..4])
+    )
+}
+
+/// Recursively copy `src` to `dst`, checking each file's SHA-256 after the
+/// copy. Returns bytes and files copied. Symlinks are recreated, not followed;
+/// FIFOs, sockets, and devices are refused. Copies keep the source's owner
+/// and are fsynced, so the original may be removed once this returns.
+fn copy_verified(src: &Path, dst: &Path) -> Result<(u64, u64)> {
+    let meta = fs::symlink_metadata(src).map_err(|e| SbhError::io(src, e))?;
+    let file_type = meta.file_type();
+    if file_type.is_symlink() {
+        let target = fs::read_link(src).map_err(|e| SbhError::io(src, e))?;
+        copy_symlink(&target, dst)?;
+        copy_owner(&meta, dst)?;
+        return Ok((0, 0));
+    }
+    if file_type.is_dir() {
+        fs::create_dir(dst).map_err(|e| SbhError::io(dst, e))?;
+        let mut totals = (0, 0);
+        for entry in fs::read_dir(src).map_err(|e| SbhError::io(src, e))? {
+            let entry = entry.map_err(|e| SbhError::io(src, e))?;
+            let (bytes, files) = copy_verified(&entry.path(), &dst.join(entry.file_name()))?;
+            totals.0 += bytes;
+            totals.1 += files;
+        }
+        copy_owner(&meta, dst)?;
+        fs::set_permissions(dst, meta.permissions()).map_err(|e| SbhError::io(dst, e))?;
+        File::open(dst)
+            .and_then(|dir| dir.sync_all())
+            .map_err(|e| SbhError::io(dst, e))?;
+        return Ok(totals);
+    }
+    if !file_type.is_file() {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "cannot archive {}: not a regular file, directory, or symlink",
+                src.display()
+            ),
+        });
+    }
+
+    let bytes = fs::copy(src, dst).map_err(|e| SbhError::io(dst, e))?;
+    if file_digest(src)? != file_digest(dst)? {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "verification failed: {} differs from its copy",
+                src.display()
+            ),
+        });
+    }
+    // chown may clear set-id bits, so the mode is reapplied after it.
+    copy_owner(&meta, dst)?;
+    fs::set_permissions(dst, meta.permissions()).map_err(|e| SbhError::io(dst, e))?;
+    let file = File::options()
+        .write(true)
+        .open(dst)
+        .map_err(|e| SbhError::io(dst, e))?;
+    if let Ok(modified) = meta.modified() {
+        let _ = file.set_modified(modified);
+    }
+    file.sync_all().map_err(|e| SbhError::io(dst, e))?;
+    Ok((bytes, 1))
+}
+
+/// Give `dst` the owner and group of the source described by `meta`.
+#[cfg(unix)]
+fn copy_owner(meta: &fs::Metadata, dst: &Path) -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    std::os::unix::fs::lchown(dst, Some(meta.uid()), Some(meta.gid()))
+        .map_err(|e| SbhError::io(dst, e))
+}
+
+#[cfg(not(unix))]
+fn copy_owner(_meta: &fs::Metadata, _dst: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(target: &Path, dst: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, dst).map_err(|e| SbhError::io(dst, e))
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(_target: &Path, dst: &Path) -> Result<()> {
+    Err(SbhError::UnsupportedPlatform {
+        details: format!("cannot archive symlink {}", dst.display()),
+    })
+}
+
+fn file_digest(path: &Path) -> Result<[u8; 32]> {
+    let mut file = File::open(path).map_err(|e| SbhError::io(path, e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0_u8; 1 << 16];
+    loop {
+        let read = file.read(&mut buf).map_err(|e| SbhError::io(path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Bytes and regular files under `path`, counted as `copy_verified` would.
+fn tree_totals(path: &Path) -> (u64, u64) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if meta.is_file() {
+        return (meta.len(), 1);
+    }
+    if !meta.is_dir() {
+        return (0, 0);
+    }
+    fs::read_dir(path).map_or((0, 0), |entries| {
+        entries.flatten().fold((0, 0), |(bytes, files), entry| {
+            let (b, f) = tree_totals(&entry.path());
+            (bytes + b, files + f)
+        })
+    })
+}
+
+fn remove_tree(path: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Whether two paths share a filesystem (device id). Unknown counts as
+/// different so non-Unix hosts are not blocked.
+fn same_device(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (fs::symlink_metadata(a), fs::metadata(b)) {
+            (Ok(a), Ok(b)) => a.dev() == b.dev(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (a, b);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, original: &str, root: &Path) -> ArchiveEntry {
+        ArchiveEntry {
+            id: id.to_string(),
+            original_path: PathBuf::from(original),
+            archived_path: root.join(id).join("target"),
+            size_bytes: 10,
+            files: 1,
+            category: "rust_target".to_string(),
+            archived_at: "2026-10-16T12:00:00.000Z".to_string(),
+            object: None,
+        }
+    }
+
+    #[test]
+    fn copy_verified_reproduces_the_tree() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("target");
+        fs::create_dir_all(src.join("debug/deps")).unwrap();
+        fs::write(src.join("debug/deps/libfoo.rlib"), vec![7_u8; 100_000]).unwrap();
+        fs::write(src.join("CACHEDIR.TAG"), "tag").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("debug", src.join("latest")).unwrap();
+
+        let dst = tmp.path().join("copy");
+        let (bytes, files) = copy_verified(&src, &dst).unwrap();
+        assert_eq!((bytes, files), (100_003, 2));
+        assert_eq!(
+            fs::read(dst.join("debug/deps/libfoo.rlib")).unwrap(),
+            vec![7_u8; 100_000]
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(dst.join("latest")).unwrap(),
+            PathBuf::from("debug")
+        );
+        assert_eq!(tree_totals(&src), (bytes, files));
+        // Source untouched.
+        assert!(src.join("CACHEDIR.TAG").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_verified_keeps_owners_and_refuses_fifos() {
+        use std::os::unix::fs::MetadataExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("target");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("out.o"), "obj").unwrap();
+        if nix::unistd::geteuid().is_root() {
+            std::os::unix::fs::lchown(src.join("out.o"), Some(4242), Some(4343)).unwrap();
+        }
+        let dst = tmp.path().join("copy");
+        copy_verified(&src, &dst).unwrap();
+        let (from, to) = (
+            fs::metadata(src.join("out.o")).unwrap(),
+            fs::metadata(dst.join("out.o")).unwrap(),
+        );
+        assert_eq!((to.uid(), to.gid()), (from.uid(), from.gid()));
+
+        nix::unistd::mkfifo(&src.join("pipe"), nix::sys::stat::Mode::S_IRWXU).unwrap();
+        let err = copy_verified(&src, &tmp.path().join("again")).unwrap_err();
+        assert!(err.to_string().contains("not a regular file"));
+    }
+
+    #[test]
+    fn index_round_trips_and_finds_latest_relocation_of_a_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        assert!(read_index(root).unwrap().is_empty());
+        append_index(root, &entry("a", "/w/p/target", root)).unwrap();
+        append_index(root, &entry("b", "/w/q/target", root)).unwrap();
+        append_index(root, &entry("c", "/w/p/target", root)).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(root.join(INDEX_FILENAME))
+            .unwrap()
+            .write_all(b"{not json\n")
+            .unwrap();
+
+        let entries = read_index(root).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(find_entry(&entries, "/w/p/target").unwrap().id, "c");
+        assert_eq!(find_entry(&entries, "b").unwrap().id, "b");
+        assert!(find_entry(&entries, "/w/r/target").is_none());
+    }
+
+    #[test]
+    fn restore_moves_the_tree_back_and_drops_the_index_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("archive");
+        let archived = entry("a", "/unused", &root);
+        fs::create_dir_all(&archived.archived_path).unwrap();
+        fs::write(archived.archived_path.join("out.o"), "obj").unwrap();
+        append_index(&root, &archived).unwrap();
+        append_index(&root, &entry("b", "/w/q/target", &root)).unwrap();
+
+        let dest = tmp.path().join("project/target");
+        restore(&root, &archived, &dest).unwrap();
+        assert_eq!(fs::read_to_string(dest.join("out.o")).unwrap(), "obj");
+        assert!(!root.join("a").exists());
+        let remaining = read_index(&root).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "b");
+
+        // Never overwrites.
+        let again = entry("b", "/w/q/target", &root);
+        fs::create_dir_all(&again.archived_path).unwrap();
+        let err = restore(&root, &again, &dest).unwrap_err();
+        assert!(matches!(err, SbhError::SafetyVeto { .. }));
+    }
+
+    #[test]
+    fn same_filesystem_archive_is_refused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("target");
+        fs::create_dir(&src).unwrap();
+        let relocation = Relocation {
+            archive_root: tmp.path().join("archive"),
+            categories: vec![ArtifactCategory::RustTarget],
+            min_free_pct: 0.0,
+            object_store: None,
+        };
+        let err = archive_copy(&src, &relocation, ArtifactCategory::RustTarget, 0).unwrap_err();
+        assert!(matches!(err, SbhError::SafetyVeto { .. }));
+        assert!(src.exists());
+    }
+}
@@ -18,7 +18,9 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use crate::scanner::scoring::{CandidacyScore, DecisionAction, EvidenceLedger, ScoreFactors};
+use crate::scanner::scoring::{
+    CandidacyScore, DecisionAction, EvidenceLedger, ScoreFactors, ScoringInputs,
+};
 
 // ──────────────────── explain level ────────────────────
 
@@ -110,6 +112,10 @@ pub struct DecisionRecord {
     pub comparator_action: Option<ActionRecord>,
     /// Human-readable summary from the evidence ledger.
     pub summary: String,
+    /// What the score was computed from, for re-scoring it later. Absent in
+    /// records written before sbh kept it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<ScoringInputs>,
 }
 
 /// Policy mode active when a decision was made.
@@ -163,6 +169,14 @@ pub struct FactorsRecord {
     pub structure: f64,
     /// Disk pressure urgency multiplier (≥1.0).
     pub pressure_multiplier: f64,
+    /// Regeneration cost relative to the flat delete loss (0.0 = free
+    /// re-fetch, 1.0 = full local rebuild). Older records read back as 1.0.
+    #[serde(default = "default_regeneration_cost")]
+    pub regeneration_cost: f64,
+}
+
+const fn default_regeneration_cost() -> f64 {
+    1.0
 }
 
 impl From<ScoreFactors> for FactorsRecord {
@@ -174,6 +188,7 @@ impl From<ScoreFactors> for FactorsRecord {
             size: f.size,
             structure: f.structure,
             pressure_multiplier: f.pressure_multiplier,
+            regeneration_cost: f.regeneration_cost,
         }
     }
 }
@@ -318,6 +333,7 @@ impl DecisionRecordBuilder {
             guard_status: guard_status.map(GuardStatusRecord::from_diagnostics),
             comparator_action: comparator_action.map(ActionRecord::from),
             summary: score.ledger.summary.clone(),
+            inputs: Some(score.inputs.clone()),
         }
     }
 }
@@ -394,6 +410,13 @@ fn format_l1(r: &DecisionRecord) -> String {
         "  pressure_mul  ─────   {pm:.3}   (applied to base)",
         pm = r.factors.pressure_multiplier,
     );
+    if r.factors.regeneration_cost < 1.0 {
+        let _ = writeln!(
+            out,
+            "  regen_cost    ─────   {rc:.3}   (cheap to re-fetch)",
+            rc = r.factors.regeneration_cost,
+        );
+    }
     let _ = writeln!(
         out,
         "  Category: {cat} ({conf:.0}% confidence)",
@@ -619,7 +642,7 @@ mod tests {
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
     use crate::scanner::scoring::{
         CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome, EvidenceLedger,
-        EvidenceTerm, ScoreFactors, ScoringEngine,
+        EvidenceTerm, ScoreFactors, ScoringEngine, ScoringInputs,
     };
     use std::path::PathBuf;
     use std::time::Duration;
@@ -635,6 +658,7 @@ mod tests {
                 size: 0.70,
                 structure: 0.95,
                 pressure_multiplier: 1.5,
+                regeneration_cost: 1.0,
             },
             vetoed: false,
             veto_reason: None,
@@ -646,6 +670,7 @@ mod tests {
                 combined_confidence: 0.92,
             },
             size_bytes: 3_500_000_000,
+            disk_bytes: 3_500_000_000,
             age: Duration::from_secs(5 * 3600),
             decision: DecisionOutcome {
                 action: DecisionAction::Delete,
@@ -692,6 +717,7 @@ mod tests {
                            calibration=0.820; action=Delete"
                     .to_string(),
             },
+            inputs: ScoringInputs::default(),
         }
     }
 
@@ -719,6 +745,7 @@ mod tests {
                 size: 0.0,
                 structure: 0.0,
                 pressure_multiplier: 1.0,
+                regeneration_cost: 1.0,
             },
             vetoed: true,
             veto_reason: Some("path contains .git".into()),
@@ -730,6 +757,7 @@ mod tests {
                 combined_confidence: 0.25,
             },
             size_bytes: 1024,
+            disk_bytes: 1024,
             age: Duration::from_secs(3600),
             decision: DecisionOutcome {
                 action: DecisionAction::Keep,
@@ -743,6 +771,7 @@ mod tests {
                 terms: Vec::new(),
                 summary: "hard veto applied".to_string(),
             },
+            inputs: ScoringInputs::default(),
         }
     }
 
@@ -1031,6 +1060,7 @@ mod tests {
             size: 0.6,
             structure: 0.95,
             pressure_multiplier: 1.3,
+            regeneration_cost: 1.0,
         };
         let record = FactorsRecord::from(factors);
         assert!((record.location - 0.8).abs() < f64::EPSILON);
@@ -1051,6 +1081,7 @@ mod tests {
         let input = CandidateInput {
             path: PathBuf::from("/tmp/cargo-target-test"),
             size_bytes: 2_000_000_000,
+            disk_bytes: 2_000_000_000,
             age: Duration::from_secs(4 * 3600),
             classification: ArtifactClassification {
                 pattern_name: "cargo-target-*".into(),
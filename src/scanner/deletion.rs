@@ -10,18 +10,67 @@
 //! 4. Directory does not contain .git/ (final safety net)
 //!
 //! Circuit breaker: 3 consecutive failures -> halt batch (daemon retries next cycle).
+//!
+//! With `DeletionConfig::workers` above one, a batch is worked by a bounded
+//! pool: each worker takes the next candidate in score order and keeps its
+//! own consecutive-failure count, and a worker whose breaker trips halts the
+//! others before their next candidate. Workers the batch cannot keep busy
+//! split large directories instead, removing their top-level entries in
+//! parallel. A cancel flag ([`DeletionExecutor::with_cancel`]) stops the
+//! pool the same way once in-flight removals finish.
+//!
+//! Held-open regular files in an allowlisted category (`DeletionConfig::truncate_categories`)
+//! are truncated to zero bytes instead of skipped: unlinking them would free
+//! nothing while the writer keeps its descriptor.
+//!
+//! Candidates in a relocated category (`DeletionConfig::relocation`) are
+//! copied to the archive and verified before the original is removed; see
+//! [`crate::scanner::archive`].
+//!
+//! With `DeletionConfig::quarantine_ttl` set, other candidates are renamed
+//! into their mount's quarantine instead of removed; see
+//! [`crate::scanner::quarantine`]. Their bytes are reported separately since
+//! nothing is reclaimed until the quarantine is purged, unless
+//! `DeletionConfig::quarantine_offload` moves them to a bucket right away.
+//!
+//! With an audit log attached ([`DeletionExecutor::with_audit`]), a real
+//! batch first writes a manifest of everything it is about to touch; see
+//! [`crate::logger::audit`].
+//!
+//! An observer ([`DeletionExecutor::with_observer`]) hears each item's
+//! outcome as soon as it is known, dry runs included, for streaming output.
+//!
+//! With `DeletionConfig::io_throttle` set, a real batch pauses before each
+//! removal, and between the top-level entries of a directory, while the
+//! device is stalled on IO; see [`crate::scanner::io_throttle`]. The time
+//! spent paused is reported as `DeletionReport::throttled`.
+//!
+//! After a real batch, [`verify_reclaim`] re-stats the touched mounts so
+//! callers can flag batches that freed far less than they claimed.
 
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
 
-use std::collections::HashSet;
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
+use sha2::{Digest, Sha256};
+
 use crate::core::errors::{Result, SbhError};
+use crate::logger::audit::{AuditLog, DeletionManifest, ManifestAction, ManifestItem};
 use crate::logger::dual::{ActivityEvent, ActivityLoggerHandle};
 use crate::logger::jsonl::ScoreFactorsRecord;
+use crate::platform::pal::FsStats;
+use crate::scanner::archive::{self, ArchiveEntry, Relocation};
+use crate::scanner::io_throttle::{IoThrottle, Pacer};
+use crate::scanner::merkle::hash_hex;
+use crate::scanner::object_store::ObjectStore;
+use crate::scanner::patterns::ArtifactCategory;
+use crate::scanner::quarantine;
 use crate::scanner::scoring::{CandidacyScore, DecisionAction, ScoreFactors};
 use crate::scanner::walker;
 
@@ -32,6 +81,8 @@ use crate::scanner::walker;
 pub struct DeletionConfig {
     /// Maximum candidates to delete in one batch before re-checking pressure.
     pub max_batch_size: usize,
+    /// Threads removing candidates at once. 1 deletes serially.
+    pub workers: usize,
     /// Whether to skip actual deletion (log what would be deleted).
     pub dry_run: bool,
     /// Minimum score threshold for deletion eligibility.
@@ -42,17 +93,37 @@ pub struct DeletionConfig {
     pub circuit_breaker_cooldown: Duration,
     /// Whether to check /proc for open files before deleting (Linux only).
     pub check_open_files: bool,
+    /// Categories whose held-open regular files are truncated in place rather
+    /// than skipped. Empty disables truncation.
+    pub truncate_categories: Vec<ArtifactCategory>,
+    /// Archive that candidates in its categories are moved to instead of
+    /// being deleted. `None` deletes everything.
+    pub relocation: Option<Relocation>,
+    /// Quarantine candidates for this long instead of deleting them
+    /// (`sbh clean --quarantine`). `None` deletes.
+    pub quarantine_ttl: Option<Duration>,
+    /// Offload quarantined trees to this bucket, reclaiming their space at
+    /// once. `None` keeps them on local disk.
+    pub quarantine_offload: Option<ObjectStore>,
+    /// Pace removals while the device is stalled on IO. `None` never pauses.
+    pub io_throttle: Option<IoThrottle>,
 }
 
 impl Default for DeletionConfig {
     fn default() -> Self {
         Self {
             max_batch_size: 10,
+            workers: 1,
             dry_run: false,
             min_score: 0.5,
             circuit_breaker_threshold: 3,
             circuit_breaker_cooldown: Duration::from_secs(30),
             check_open_files: true,
+            truncate_categories: Vec::new(),
+            relocation: None,
+            quarantine_ttl: None,
+            quarantine_offload: None,
+            io_throttle: None,
         }
     }
 }
@@ -64,7 +135,42 @@ impl Default for DeletionConfig {
 pub struct DeletionPlan {
     pub candidates: Vec<CandidacyScore>,
     pub total_reclaimable_bytes: u64,
+    /// On-disk counterpart of `total_reclaimable_bytes`.
+    pub total_reclaimable_disk_bytes: u64,
     pub estimated_items: usize,
+    /// Candidates dropped because a selected ancestor already covers them.
+    /// Their bytes are folded into that ancestor's `size_bytes` and `disk_bytes`.
+    pub subsumed: Vec<PathBuf>,
+    /// Eligible candidates left out because the reclaim target was met
+    /// without them (see [`DeletionExecutor::plan_for_target`]).
+    pub deferred: usize,
+}
+
+impl DeletionPlan {
+    /// SHA-256 over each planned path and size, in execution order. Scores
+    /// are left out so the hash stays put while candidates age; anything
+    /// that changes what would be removed, or in which order, changes it.
+    #[must_use]
+    pub fn plan_hash(&self) -> String {
+        hash_planned(
+            self.candidates
+                .iter()
+                .map(|candidate| (candidate.path.as_path(), candidate.size_bytes)),
+        )
+    }
+}
+
+/// [`DeletionPlan::plan_hash`] over bare `(path, size)` pairs, so a plan
+/// read back from a file can be checked against the hash it was approved by.
+#[must_use]
+pub fn hash_planned<'a>(items: impl IntoIterator<Item = (&'a Path, u64)>) -> String {
+    let mut digest = Sha256::new();
+    for (path, size_bytes) in items {
+        digest.update(path.as_os_str().as_encoded_bytes());
+        digest.update(b"\0");
+        digest.update(size_bytes.to_le_bytes());
+    }
+    hash_hex(&digest.finalize().into())
 }
 
 /// Summary after a deletion batch completes.
@@ -73,12 +179,95 @@ pub struct DeletionReport {
     pub items_deleted: usize,
     pub items_failed: usize,
     pub items_skipped: usize,
+    /// Apparent size of everything removed, as `du --apparent-size` counts it.
     pub bytes_freed: u64,
+    /// Allocated blocks released (`st_blocks`), as `du` and `df` count them.
+    /// Less than `bytes_freed` for sparse or compressed files, more for
+    /// trees of small files.
+    pub disk_bytes_freed: u64,
     pub duration: Duration,
     pub errors: Vec<DeletionError>,
     pub dry_run: bool,
     pub circuit_breaker_tripped: bool,
     pub deleted_paths: Vec<PathBuf>,
+    /// Held-open files truncated in place; their bytes count toward `bytes_freed`.
+    pub items_truncated: usize,
+    pub truncated_paths: Vec<PathBuf>,
+    /// Candidates moved to the archive; their bytes count toward `bytes_freed`.
+    pub items_relocated: usize,
+    pub relocated_paths: Vec<PathBuf>,
+    /// Candidates moved into quarantine. Their bytes are in
+    /// `bytes_quarantined`, not `bytes_freed`, unless they were offloaded
+    /// to an object store.
+    pub items_quarantined: usize,
+    pub bytes_quarantined: u64,
+    pub quarantined_paths: Vec<PathBuf>,
+    /// Audit manifest written before the batch started, if any.
+    pub audit_batch_id: Option<String>,
+    /// Time spent paused for IO pressure; included in `duration`. With
+    /// several workers, the longest any one of them paused.
+    pub throttled: Duration,
+    /// The cancel flag stopped the batch before every candidate was tried.
+    pub cancelled: bool,
+}
+
+impl DeletionReport {
+    fn empty(dry_run: bool) -> Self {
+        Self {
+            items_deleted: 0,
+            items_failed: 0,
+            items_skipped: 0,
+            bytes_freed: 0,
+            disk_bytes_freed: 0,
+            duration: Duration::ZERO,
+            errors: Vec::new(),
+            dry_run,
+            circuit_breaker_tripped: false,
+            deleted_paths: Vec::new(),
+            items_truncated: 0,
+            truncated_paths: Vec::new(),
+            items_relocated: 0,
+            relocated_paths: Vec::new(),
+            items_quarantined: 0,
+            bytes_quarantined: 0,
+            quarantined_paths: Vec::new(),
+            audit_batch_id: None,
+            throttled: Duration::ZERO,
+            cancelled: false,
+        }
+    }
+
+    /// Fold one worker's results into the batch report.
+    fn absorb(&mut self, worker: Self) {
+        self.items_deleted += worker.items_deleted;
+        self.items_failed += worker.items_failed;
+        self.items_skipped += worker.items_skipped;
+        self.bytes_freed += worker.bytes_freed;
+        self.disk_bytes_freed += worker.disk_bytes_freed;
+        self.errors.extend(worker.errors);
+        self.circuit_breaker_tripped |= worker.circuit_breaker_tripped;
+        self.deleted_paths.extend(worker.deleted_paths);
+        self.items_truncated += worker.items_truncated;
+        self.truncated_paths.extend(worker.truncated_paths);
+        self.items_relocated += worker.items_relocated;
+        self.relocated_paths.extend(worker.relocated_paths);
+        self.items_quarantined += worker.items_quarantined;
+        self.bytes_quarantined += worker.bytes_quarantined;
+        self.quarantined_paths.extend(worker.quarantined_paths);
+        self.throttled = self.throttled.max(worker.throttled);
+        self.cancelled |= worker.cancelled;
+    }
+
+    /// Count a quarantined candidate; an offloaded one has freed its space.
+    fn record_quarantined(&mut self, candidate: &CandidacyScore, offloaded: bool) {
+        self.items_quarantined += 1;
+        if offloaded {
+            self.bytes_freed += candidate.size_bytes;
+            self.disk_bytes_freed += candidate.disk_bytes;
+        } else {
+            self.bytes_quarantined += candidate.size_bytes;
+        }
+    }
 }
 
 /// A single deletion failure record.
@@ -102,18 +291,126 @@ pub enum SkipReason {
     Symlink,
 }
 
+impl SkipReason {
+    /// Stable lowercase name for machine-readable output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PathGone => "path_gone",
+            Self::FileOpen => "file_open",
+            Self::ContainsGit => "contains_git",
+            Self::NotWritable => "not_writable",
+            Self::Vetoed => "vetoed",
+            Self::BelowThreshold => "below_threshold",
+            Self::Symlink => "symlink",
+        }
+    }
+}
+
+/// What [`DeletionExecutor::execute`] did with one candidate. In a dry run
+/// this is what it would have done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemOutcome {
+    Deleted,
+    /// Held-open file emptied in place.
+    Truncated {
+        freed_bytes: u64,
+    },
+    Relocated,
+    Quarantined,
+    /// Pre-flight check failed, or `None` when the caller's `should_skip`
+    /// asked for it (e.g. the free-space target was already met).
+    Skipped(Option<SkipReason>),
+    Failed {
+        error_code: String,
+        error: String,
+    },
+}
+
+impl ItemOutcome {
+    /// Stable lowercase name for machine-readable output.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Deleted => "deleted",
+            Self::Truncated { .. } => "truncated",
+            Self::Relocated => "relocated",
+            Self::Quarantined => "quarantined",
+            Self::Skipped(_) => "skipped",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
+/// Callback told about every item outcome during a batch.
+pub type ItemObserver = Box<dyn Fn(&CandidacyScore, &ItemOutcome) + Send + Sync>;
+
+/// Who is deleting and on whose authority, recorded in each batch manifest.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    /// Manifest `source`: `daemon`, `clean`, ...
+    pub source: &'static str,
+    pub policy_mode: Option<String>,
+    /// Policy decision record IDs by candidate path.
+    pub decision_ids: HashMap<PathBuf, u64>,
+}
+
 // ──────────────────── executor ────────────────────
 
 /// The deletion executor: takes scored candidates and deletes them safely.
 pub struct DeletionExecutor {
     config: DeletionConfig,
     logger: Option<ActivityLoggerHandle>,
+    audit: Option<(AuditLog, AuditContext)>,
+    observer: Option<ItemObserver>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+/// What the workers of one batch share.
+struct BatchQueue<'a> {
+    candidates: &'a [CandidacyScore],
+    /// Index of the next candidate to take.
+    next: AtomicUsize,
+    /// Set by a worker whose circuit breaker tripped.
+    halted: AtomicBool,
+    should_skip: Option<&'a (dyn Fn(&Path) -> bool + Sync)>,
+    open_paths: Option<HashSet<PathBuf>>,
+    /// Threads each worker may use to remove one directory.
+    tree_workers: usize,
 }
 
 impl DeletionExecutor {
     /// Create a new executor with the given config and optional logger handle.
     pub fn new(config: DeletionConfig, logger: Option<ActivityLoggerHandle>) -> Self {
-        Self { config, logger }
+        Self {
+            config,
+            logger,
+            audit: None,
+            observer: None,
+            cancel: None,
+        }
+    }
+
+    /// Stop the batch once `cancel` is set (e.g. on daemon shutdown).
+    /// Removals already under way finish; later candidates are not tried.
+    #[must_use]
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Write a manifest to `log` before every real batch.
+    #[must_use]
+    pub fn with_audit(mut self, log: AuditLog, context: AuditContext) -> Self {
+        self.audit = Some((log, context));
+        self
+    }
+
+    /// Report each item's outcome to `observer` as the batch runs.
+    #[must_use]
+    pub fn with_observer(mut self, observer: ItemObserver) -> Self {
+        self.observer = Some(observer);
+        self
     }
 
     /// Build a deletion plan from scored candidates.
@@ -128,6 +425,11 @@ impl DeletionExecutor {
                 && c.total_score >= self.config.min_score
         });
 
+        // Deleting `target/` removes `target/debug/` too: keep only the
+        // outermost candidate so its bytes aren't counted twice and the
+        // descendant's deletion doesn't fail on an already-missing path.
+        let subsumed = subsume_nested(&mut candidates);
+
         // Sort by score descending (most obvious artifacts first).
         candidates.sort_by(|a, b| {
             b.total_score
@@ -136,39 +438,53 @@ impl DeletionExecutor {
         });
 
         let total_reclaimable_bytes: u64 = candidates.iter().map(|c| c.size_bytes).sum();
+        let total_reclaimable_disk_bytes: u64 = candidates.iter().map(|c| c.disk_bytes).sum();
         let estimated_items = candidates.len();
 
         DeletionPlan {
             candidates,
             total_reclaimable_bytes,
+            total_reclaimable_disk_bytes,
             estimated_items,
+            subsumed,
+            deferred: 0,
         }
     }
 
+    /// Plan the cheapest set of candidates that reclaims `bytes_needed`.
+    ///
+    /// Candidates are taken greedily by bytes per unit of risk, where risk is
+    /// `1 - score`, until the projection covers the target plus
+    /// [`TARGET_RECLAIM_MARGIN`]. Picks that became unnecessary once larger
+    /// ones were added are then dropped, riskiest first. One large, confident
+    /// cache therefore beats dozens of small high-score directories.
+    pub fn plan_for_target(
+        &self,
+        candidates: Vec<CandidacyScore>,
+        bytes_needed: u64,
+    ) -> DeletionPlan {
+        let mut plan = self.plan(candidates);
+        let eligible = plan.candidates.len();
+        plan.candidates = select_for_target(std::mem::take(&mut plan.candidates), bytes_needed);
+        plan.deferred = eligible - plan.candidates.len();
+        plan.total_reclaimable_bytes = plan.candidates.iter().map(|c| c.size_bytes).sum();
+        plan.total_reclaimable_disk_bytes = plan.candidates.iter().map(|c| c.disk_bytes).sum();
+        plan.estimated_items = plan.candidates.len();
+        plan
+    }
+
     /// Execute a deletion plan, deleting up to `max_batch_size` candidates.
     ///
     /// Returns a report summarizing what was deleted, skipped, or failed.
     /// If `should_skip` returns `true` for a candidate path, it is skipped.
-    #[allow(clippy::too_many_lines)]
     pub fn execute(
         &self,
         plan: &DeletionPlan,
-        should_skip: Option<&dyn Fn(&Path) -> bool>,
+        should_skip: Option<&(dyn Fn(&Path) -> bool + Sync)>,
     ) -> DeletionReport {
         let start = Instant::now();
-        let mut report = DeletionReport {
-            items_deleted: 0,
-            items_failed: 0,
-            items_skipped: 0,
-            bytes_freed: 0,
-            duration: Duration::ZERO,
-            errors: Vec::new(),
-            dry_run: self.config.dry_run,
-            circuit_breaker_tripped: false,
-            deleted_paths: Vec::new(),
-        };
+        let mut report = DeletionReport::empty(self.config.dry_run);
 
-        let mut consecutive_failures: u32 = 0;
         let limit = plan.candidates.len().min(self.config.max_batch_size);
         // Build an open-path ancestor index once per batch to avoid deep per-candidate
         // inode-tree scans on large artifact directories.
@@ -194,12 +510,20 @@ impl DeletionExecutor {
                 // Mark all candidates as skipped/failed due to safety check.
                 report.items_failed = limit;
                 for candidate in plan.candidates.iter().take(limit) {
-                    report.errors.push(DeletionError {
+                    let error = DeletionError {
                         path: candidate.path.clone(),
                         error: "safety check incomplete".to_string(),
                         error_code: "SBH-3003".to_string(),
                         recoverable: true,
-                    });
+                    };
+                    self.notify(
+                        candidate,
+                        &ItemOutcome::Failed {
+                            error_code: error.error_code.clone(),
+                            error: error.error.clone(),
+                        },
+                    );
+                    report.errors.push(error);
                 }
                 return report;
             }
@@ -208,11 +532,82 @@ impl DeletionExecutor {
             None
         };
 
-        for candidate in plan.candidates.iter().take(limit) {
+        // Record the whole batch up front: the activity log says what then
+        // happened to each item (deleted, skipped, truncated, failed).
+        if !self.config.dry_run {
+            report.audit_batch_id = self.write_manifest(&plan.candidates[..limit]);
+        }
+        // One worker per candidate at most; any left over split directories.
+        let workers = self.config.workers.clamp(1, limit.max(1));
+        let queue = BatchQueue {
+            candidates: &plan.candidates[..limit],
+            next: AtomicUsize::new(0),
+            halted: AtomicBool::new(false),
+            should_skip,
+            open_paths,
+            tree_workers: (self.config.workers / workers).max(1),
+        };
+        let partials = if workers == 1 {
+            vec![self.run_worker(&queue)]
+        } else {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..workers)
+                    .map(|_| scope.spawn(|| self.run_worker(&queue)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        for partial in partials {
+            report.absorb(partial);
+        }
+        report.duration = start.elapsed();
+        report
+    }
+
+    /// Take candidates off `queue` in score order until it is empty, this
+    /// worker's circuit breaker trips, another worker's has, or the batch is
+    /// cancelled.
+    #[allow(clippy::too_many_lines)]
+    fn run_worker(&self, queue: &BatchQueue<'_>) -> DeletionReport {
+        let mut report = DeletionReport::empty(self.config.dry_run);
+        let mut consecutive_failures: u32 = 0;
+        let mut pacer = self
+            .config
+            .io_throttle
+            .filter(|_| !self.config.dry_run)
+            .map(Pacer::new);
+
+        loop {
+            if queue.halted.load(Ordering::Relaxed) {
+                break;
+            }
+            if self
+                .cancel
+                .as_ref()
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            {
+                report.cancelled = true;
+                break;
+            }
+            let Some(candidate) = queue
+                .candidates
+                .get(queue.next.fetch_add(1, Ordering::Relaxed))
+            else {
+                break;
+            };
+
             // Circuit breaker: stop immediately on consecutive failures.
             // The daemon's next scan cycle can retry with fresh candidates.
             if consecutive_failures >= self.config.circuit_breaker_threshold {
                 report.circuit_breaker_tripped = true;
+                queue.halted.store(true, Ordering::Relaxed);
                 self.log_event(ActivityEvent::Error {
                     code: "SBH-2003".to_string(),
                     message: format!(
@@ -224,17 +619,53 @@ impl DeletionExecutor {
             }
 
             // Dynamic skip check (e.g. target free space met).
-            if let Some(skip) = should_skip
+            if let Some(skip) = queue.should_skip
                 && skip(&candidate.path)
             {
                 report.items_skipped += 1;
                 // We don't log an error event for this skip as it's a success condition (target met).
+                self.notify(candidate, &ItemOutcome::Skipped(None));
                 continue;
             }
 
             // Pre-flight safety checks.
-            match self.preflight_check(&candidate.path, open_paths.as_ref()) {
+            match self.preflight_check(&candidate.path, queue.open_paths.as_ref()) {
                 Ok(()) => {}
+                Err(SkipReason::FileOpen) if self.may_truncate(candidate) => {
+                    if self.config.dry_run {
+                        report.items_truncated += 1;
+                        report.bytes_freed += candidate.size_bytes;
+                        report.disk_bytes_freed += candidate.disk_bytes;
+                        Self::log_dry_run(candidate);
+                        self.notify(
+                            candidate,
+                            &ItemOutcome::Truncated {
+                                freed_bytes: candidate.size_bytes,
+                            },
+                        );
+                        continue;
+                    }
+                    match truncate_file(&candidate.path) {
+                        Ok(freed) => {
+                            report.items_truncated += 1;
+                            report.bytes_freed += freed;
+                            report.disk_bytes_freed += candidate.disk_bytes;
+                            report.truncated_paths.push(candidate.path.clone());
+                            consecutive_failures = 0;
+                            self.log_event(ActivityEvent::ArtifactTruncated {
+                                path: candidate.path.to_string_lossy().to_string(),
+                                size_bytes: freed,
+                                score: candidate.total_score,
+                            });
+                            self.notify(candidate, &ItemOutcome::Truncated { freed_bytes: freed });
+                        }
+                        Err(e) => {
+                            consecutive_failures += 1;
+                            self.record_failure(&mut report, candidate, &e);
+                        }
+                    }
+                    continue;
+                }
                 Err(skip) => {
                     report.items_skipped += 1;
                     self.log_event(ActivityEvent::ArtifactDeletionFailed {
@@ -242,55 +673,145 @@ impl DeletionExecutor {
                         error_code: "SBH-2003".to_string(),
                         error_message: format!("skipped: {skip:?}"),
                     });
+                    self.notify(candidate, &ItemOutcome::Skipped(Some(skip)));
                     continue;
                 }
             }
 
+            let relocation = self.relocation_for(candidate);
+            let quarantine_ttl = self.config.quarantine_ttl.filter(|_| relocation.is_none());
             if self.config.dry_run {
-                report.items_deleted += 1;
-                report.bytes_freed += candidate.size_bytes;
+                let outcome = if quarantine_ttl.is_some() {
+                    report.record_quarantined(candidate, self.config.quarantine_offload.is_some());
+                    ItemOutcome::Quarantined
+                } else {
+                    report.bytes_freed += candidate.size_bytes;
+                    report.disk_bytes_freed += candidate.disk_bytes;
+                    if relocation.is_some() {
+                        report.items_relocated += 1;
+                        ItemOutcome::Relocated
+                    } else {
+                        report.items_deleted += 1;
+                        ItemOutcome::Deleted
+                    }
+                };
                 Self::log_dry_run(candidate);
+                self.notify(candidate, &outcome);
+                continue;
+            }
+
+            if let Some(pacer) = pacer.as_mut() {
+                pacer.next_item();
+            }
+
+            if let Some(relocation) = relocation {
+                match self.relocate(candidate, relocation, pacer.as_mut(), queue.tree_workers) {
+                    Ok(entry) => {
+                        report.items_relocated += 1;
+                        report.bytes_freed += candidate.size_bytes;
+                        report.disk_bytes_freed += candidate.disk_bytes;
+                        report.relocated_paths.push(candidate.path.clone());
+                        consecutive_failures = 0;
+                        self.log_event(ActivityEvent::ArtifactRelocated {
+                            path: candidate.path.to_string_lossy().to_string(),
+                            archived_path: entry.archived_path.to_string_lossy().to_string(),
+                            size_bytes: candidate.size_bytes,
+                            score: candidate.total_score,
+                        });
+                        self.notify(candidate, &ItemOutcome::Relocated);
+                    }
+                    Err(e) => {
+                        consecutive_failures += 1;
+                        self.record_failure(&mut report, candidate, &e);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(ttl) = quarantine_ttl {
+                match quarantine::quarantine(
+                    &candidate.path,
+                    candidate.classification.category,
+                    candidate.size_bytes,
+                    ttl,
+                    self.config.quarantine_offload.as_ref(),
+                ) {
+                    Ok(entry) => {
+                        report.record_quarantined(candidate, entry.object.is_some());
+                        report.quarantined_paths.push(candidate.path.clone());
+                        consecutive_failures = 0;
+                        self.log_event(ActivityEvent::ArtifactQuarantined {
+                            path: candidate.path.to_string_lossy().to_string(),
+                            quarantined_path: entry.stored_at(),
+                            size_bytes: candidate.size_bytes,
+                            score: candidate.total_score,
+                        });
+                        self.notify(candidate, &ItemOutcome::Quarantined);
+                    }
+                    Err(e) => {
+                        consecutive_failures += 1;
+                        self.record_failure(&mut report, candidate, &e);
+                    }
+                }
                 continue;
             }
 
             // Actual deletion.
             let del_start = Instant::now();
-            match self.delete_path(&candidate.path) {
-                Ok(()) => {
+            match self.delete_path(&candidate.path, pacer.as_mut(), queue.tree_workers) {
+                Ok(owner_uid) => {
                     #[allow(clippy::cast_possible_truncation)]
                     let duration_ms = del_start.elapsed().as_millis() as u64;
                     report.items_deleted += 1;
                     report.bytes_freed += candidate.size_bytes;
+                    report.disk_bytes_freed += candidate.disk_bytes;
                     report.deleted_paths.push(candidate.path.clone());
                     consecutive_failures = 0;
 
-                    self.log_deletion_success(candidate, duration_ms);
+                    self.log_deletion_success(candidate, duration_ms, owner_uid);
+                    self.notify(candidate, &ItemOutcome::Deleted);
                 }
                 Err(e) => {
-                    report.items_failed += 1;
                     consecutive_failures += 1;
-                    let error = DeletionError {
-                        path: candidate.path.clone(),
-                        error: e.to_string(),
-                        error_code: e.code().to_string(),
-                        recoverable: e.is_retryable(),
-                    };
-
-                    self.log_event(ActivityEvent::ArtifactDeletionFailed {
-                        path: candidate.path.to_string_lossy().to_string(),
-                        error_code: error.error_code.clone(),
-                        error_message: error.error.clone(),
-                    });
-
-                    report.errors.push(error);
+                    self.record_failure(&mut report, candidate, &e);
                 }
             }
         }
 
-        report.duration = start.elapsed();
+        report.throttled = pacer.map_or(Duration::ZERO, |pacer| pacer.throttled());
         report
     }
 
+    fn record_failure(
+        &self,
+        report: &mut DeletionReport,
+        candidate: &CandidacyScore,
+        e: &SbhError,
+    ) {
+        report.items_failed += 1;
+        let error = DeletionError {
+            path: candidate.path.clone(),
+            error: e.to_string(),
+            error_code: e.code().to_string(),
+            recoverable: e.is_retryable(),
+        };
+
+        self.log_event(ActivityEvent::ArtifactDeletionFailed {
+            path: candidate.path.to_string_lossy().to_string(),
+            error_code: error.error_code.clone(),
+            error_message: error.error.clone(),
+        });
+        self.notify(
+            candidate,
+            &ItemOutcome::Failed {
+                error_code: error.error_code.clone(),
+                error: error.error.clone(),
+            },
+        );
+
+        report.errors.push(error);
+    }
+
     // ──────────────────── pre-flight checks ────────────────────
 
     #[allow(clippy::unused_self)]
@@ -334,8 +855,16 @@ impl DeletionExecutor {
 
     // ──────────────────── deletion ────────────────────
 
+    /// Delete `path`, returning the uid that owned it for per-user accounting.
+    /// With a pacer, a directory is removed one top-level entry at a time;
+    /// otherwise its top-level entries are spread over `threads` threads.
     #[allow(clippy::unused_self)]
-    fn delete_path(&self, path: &Path) -> Result<()> {
+    fn delete_path(
+        &self,
+        path: &Path,
+        pacer: Option<&mut Pacer>,
+        threads: usize,
+    ) -> Result<Option<u32>> {
         // Re-check with symlink_metadata (not metadata/is_dir which follow symlinks)
         // to close the TOCTOU window between preflight_check and actual deletion.
         let meta = fs::symlink_metadata(path).map_err(|e| SbhError::io(path, e))?;
@@ -344,9 +873,18 @@ impl DeletionExecutor {
                 details: format!("path became a symlink before deletion: {}", path.display()),
             });
         }
+        #[cfg(unix)]
+        let owner_uid = Some(owner_uid(&meta));
+        #[cfg(not(unix))]
+        let owner_uid = None;
 
         if meta.is_dir() {
-            fs::remove_dir_all(path).map_err(|e| SbhError::io(path, e))?;
+            match pacer {
+                Some(pacer) => remove_dir_paced(path, pacer),
+                None if threads > 1 => remove_dir_parallel(path, threads),
+                None => fs::remove_dir_all(path),
+            }
+            .map_err(|e| SbhError::io(path, e))?;
         } else {
             fs::remove_file(path).map_err(|e| SbhError::io(path, e))?;
         }
@@ -358,7 +896,95 @@ impl DeletionExecutor {
             });
         }
 
-        Ok(())
+        Ok(owner_uid)
+    }
+
+    // ──────────────────── audit ────────────────────
+
+    /// Write the batch manifest and return its ID. A failed write is logged,
+    /// not fatal: refusing to free space because the audit line didn't fit
+    /// would defeat the point under pressure.
+    fn write_manifest(&self, candidates: &[CandidacyScore]) -> Option<String> {
+        let (log, context) = self.audit.as_ref()?;
+        if candidates.is_empty() {
+            return None;
+        }
+        let items = candidates
+            .iter()
+            .map(|candidate| {
+                let action = if self.relocation_for(candidate).is_some() {
+                    ManifestAction::Relocate
+                } else if self.config.quarantine_ttl.is_some() {
+                    ManifestAction::Quarantine
+                } else {
+                    ManifestAction::Delete
+                };
+                let decision_id = context.decision_ids.get(&candidate.path).copied();
+                ManifestItem::from_candidate(candidate, action, decision_id)
+            })
+            .collect();
+        let manifest = DeletionManifest::new(context.source, context.policy_mode.clone(), items);
+        if let Err(e) = log.record(&manifest) {
+            self.log_event(ActivityEvent::Error {
+                code: e.code().to_string(),
+                message: format!(
+                    "deletion manifest {} not fully written: {e}",
+                    manifest.batch_id
+                ),
+            });
+        }
+        Some(manifest.batch_id)
+    }
+
+    // ──────────────────── relocation ────────────────────
+
+    fn relocation_for(&self, candidate: &CandidacyScore) -> Option<&Relocation> {
+        self.config
+            .relocation
+            .as_ref()
+            .filter(|relocation| relocation.applies_to(candidate.classification.category))
+    }
+
+    /// Copy `candidate` to the archive, then remove the original. The copy is
+    /// discarded if the original cannot be removed, so a tree is never left
+    /// in both places.
+    fn relocate(
+        &self,
+        candidate: &CandidacyScore,
+        relocation: &Relocation,
+        pacer: Option<&mut Pacer>,
+        threads: usize,
+    ) -> Result<ArchiveEntry> {
+        let entry = archive::archive_copy(
+            &candidate.path,
+            relocation,
+            candidate.classification.category,
+            candidate.size_bytes,
+        )?;
+        if let Err(e) = self.delete_path(&candidate.path, pacer, threads) {
+            let _ = archive::discard(&entry);
+            return Err(e);
+        }
+        // The copy is verified and the original gone; an unindexed copy can
+        // still be restored by hand, so report it rather than fail.
+        if let Err(e) = archive::append_index(&relocation.archive_root, &entry) {
+            self.log_event(ActivityEvent::Error {
+                code: e.code().to_string(),
+                message: format!("archive index not updated for {}: {e}", entry.id),
+            });
+        }
+        Ok(entry)
+    }
+
+    // ──────────────────── truncation ────────────────────
+
+    /// A held-open candidate may be truncated when its category is allowlisted
+    /// and it is a plain file (never a directory or symlink).
+    fn may_truncate(&self, candidate: &CandidacyScore) -> bool {
+        self.config
+            .truncate_categories
+            .contains(&candidate.classification.category)
+            && fs::symlink_metadata(&candidate.path).is_ok_and(|meta| meta.file_type().is_file())
     }
 
     // ──────────────────── logging helpers ────────────────────
@@ -369,7 +995,18 @@ impl DeletionExecutor {
         }
     }
 
-    fn log_deletion_success(&self, candidate: &CandidacyScore, duration_ms: u64) {
+    fn notify(&self, candidate: &CandidacyScore, outcome: &ItemOutcome) {
+        if let Some(observer) = &self.observer {
+            observer(candidate, outcome);
+        }
+    }
+
+    fn log_deletion_success(
+        &self,
+        candidate: &CandidacyScore,
+        duration_ms: u64,
+        owner_uid: Option<u32>,
+    ) {
         self.log_event(ActivityEvent::ArtifactDeleted {
             path: candidate.path.to_string_lossy().to_string(),
             size_bytes: candidate.size_bytes,
@@ -378,6 +1015,7 @@ impl DeletionExecutor {
             pressure: String::new(), // Caller doesn't pass pressure level here
             free_pct: 0.0,
             duration_ms,
+            owner_uid,
         });
     }
 
@@ -387,6 +1025,93 @@ impl DeletionExecutor {
     }
 }
 
+// ──────────────────── truncation ────────────────────
+
+/// Truncate a regular file to zero bytes and return its previous length.
+///
+/// Writers keep their descriptors; an `O_APPEND` writer continues at the new
+/// end, the same contract as logrotate's `copytruncate` minus the copy.
+///
+/// The path is opened without following a final symlink and without
+/// blocking on a FIFO, and the type check runs on the opened handle, so a
+/// swap between scan and truncation cannot redirect it to another file.
+fn truncate_file(path: &Path) -> Result<u64> {
+    let mut opts = OpenOptions::new();
+    opts.write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        opts.custom_flags(libc::O_NOFOLLOW | libc::O_NONBLOCK);
+    }
+    let file = opts.open(path).map_err(|e| SbhError::io(path, e))?;
+    let meta = file.metadata().map_err(|e| SbhError::io(path, e))?;
+    if !meta.is_file() {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "not a regular file, refusing to truncate: {}",
+                path.display()
+            ),
+        });
+    }
+    file.set_len(0).map_err(|e| SbhError::io(path, e))?;
+    Ok(meta.len())
+}
+
+// ──────────────────── paced removal ────────────────────
+
+/// `remove_dir_all`, one top-level entry at a time with a pacing check
+/// between entries, so one huge tree is not a single uninterrupted burst.
+fn remove_dir_paced(path: &Path, pacer: &mut Pacer) -> std::io::Result<()> {
+    for (index, entry) in fs::read_dir(path)?.enumerate() {
+        let entry = entry?;
+        if index > 0 {
+            pacer.pace();
+        }
+        // `file_type` does not follow symlinks: a link is removed, not its target.
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    fs::remove_dir(path)
+}
+
+/// `remove_dir_all` with the top-level entries shared out among `threads`
+/// threads, so one huge tree (a 200k-file `node_modules`) is not removed
+/// one file at a time. The first error stops the remaining entries.
+fn remove_dir_parallel(path: &Path, threads: usize) -> std::io::Result<()> {
+    let entries = fs::read_dir(path)?.collect::<std::io::Result<Vec<_>>>()?;
+    let next = AtomicUsize::new(0);
+    let first_error = parking_lot::Mutex::new(None);
+    let remove_some = || {
+        while first_error.lock().is_none()
+            && let Some(entry) = entries.get(next.fetch_add(1, Ordering::Relaxed))
+        {
+            // `file_type` does not follow symlinks: a link is removed, not its target.
+            let removed = entry.file_type().and_then(|kind| {
+                if kind.is_dir() {
+                    fs::remove_dir_all(entry.path())
+                } else {
+                    fs::remove_file(entry.path())
+                }
+            });
+            if let Err(e) = removed {
+                first_error.lock().get_or_insert(e);
+            }
+        }
+    };
+    std::thread::scope(|scope| {
+        for _ in 0..threads.min(entries.len()) {
+            scope.spawn(remove_some);
+        }
+    });
+    if let Some(e) = first_error.into_inner() {
+        return Err(e);
+    }
+    fs::remove_dir(path)
+}
+
 // ──────────────────── writable check ────────────────────
 
 /// Check if the current process can write to the given path.
@@ -394,7 +1119,7 @@ impl DeletionExecutor {
 /// Uses `access(W_OK)` on Unix which checks effective permissions (owner, group,
 /// ACLs, and mount flags) — more reliable than `permissions().readonly()` which
 /// only checks if any write bit is set.
-fn is_writable(path: &Path) -> bool {
+pub(crate) fn is_writable(path: &Path) -> bool {
     #[cfg(unix)]
     {
         nix::unistd::access(path, nix::unistd::AccessFlags::W_OK).is_ok()
@@ -408,6 +1133,13 @@ fn is_writable(path: &Path) -> bool {
     }
 }
 
+/// Owning uid of a path, for per-user reclaim accounting.
+#[cfg(unix)]
+fn owner_uid(meta: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::MetadataExt;
+    meta.uid()
+}
+
 /// Shallow recursive check for `.git` directories within `path`.
 ///
 /// Walks at most `max_depth` levels to catch nested git repositories
@@ -443,13 +1175,203 @@ fn factors_to_record(f: &ScoreFactors) -> ScoreFactorsRecord {
     }
 }
 
+// ──────────────────── planning ────────────────────
+
+/// Extra reclaim planned beyond a target, as a fraction of the shortfall, to
+/// absorb size-estimate error and writes that land while deleting.
+pub const TARGET_RECLAIM_MARGIN: f64 = 0.10;
+
+/// Risk floor so a perfect score doesn't make efficiency infinite.
+const MIN_CANDIDATE_RISK: f64 = 0.05;
+
+/// Bytes reclaimed per unit of risk.
+fn reclaim_efficiency(c: &CandidacyScore) -> f64 {
+    c.size_bytes as f64 / (1.0 - c.total_score).max(MIN_CANDIDATE_RISK)
+}
+
+/// Greedy bytes-per-risk selection for [`DeletionExecutor::plan_for_target`].
+/// Returns the chosen candidates in execution order (most efficient first).
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn select_for_target(
+    mut candidates: Vec<CandidacyScore>,
+    bytes_needed: u64,
+) -> Vec<CandidacyScore> {
+    if bytes_needed == 0 {
+        return Vec::new();
+    }
+    let goal = bytes_needed.saturating_add((bytes_needed as f64 * TARGET_RECLAIM_MARGIN) as u64);
+    candidates.sort_by(|a, b| {
+        reclaim_efficiency(b)
+            .partial_cmp(&reclaim_efficiency(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let mut projected = 0u64;
+    let take = candidates
+        .iter()
+        .position(|c| {
+            projected = projected.saturating_add(c.size_bytes);
+            projected >= goal
+        })
+        .map_or(candidates.len(), |i| i + 1);
+    candidates.truncate(take);
+
+    // Prune: a late large pick can make earlier small ones redundant.
+    // Try dropping the riskiest first.
+    let mut by_risk: Vec<usize> = (0..candidates.len()).collect();
+    by_risk.sort_by(|&a, &b| {
+        candidates[a]
+            .total_score
+            .partial_cmp(&candidates[b].total_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let mut keep = vec![true; candidates.len()];
+    for i in by_risk {
+        let size = candidates[i].size_bytes;
+        if projected.saturating_sub(size) >= goal {
+            projected -= size;
+            keep[i] = false;
+        }
+    }
+    let mut keep = keep.into_iter();
+    candidates.retain(|_| keep.next().unwrap_or(true));
+    candidates
+}
+
+/// Drop candidates that live under another candidate, merging their bytes
+/// into the outermost covering candidate. Returns the dropped paths.
+///
+/// Walker entries never share bytes: a directory counts its immediate files
+/// (a classified artifact root its whole unwalked subtree), minus any large
+/// file emitted as an entry of its own. A descendant's bytes are therefore
+/// genuinely additional to its ancestor's.
+fn subsume_nested(candidates: &mut Vec<CandidacyScore>) -> Vec<PathBuf> {
+    let selected: HashSet<PathBuf> = candidates.iter().map(|c| c.path.clone()).collect();
+    // ancestor -> (apparent, on-disk) bytes merged into it
+    let mut merged: HashMap<PathBuf, (u64, u64)> = HashMap::new();
+    let mut subsumed = Vec::new();
+
+    candidates.retain(|c| {
+        let outermost = c
+            .path
+            .ancestors()
+            .skip(1)
+            .filter(|a| selected.contains(*a))
+            .last();
+        let Some(ancestor) = outermost else {
+            return true;
+        };
+        let bytes = merged.entry(ancestor.to_path_buf()).or_default();
+        bytes.0 = bytes.0.saturating_add(c.size_bytes);
+        bytes.1 = bytes.1.saturating_add(c.disk_bytes);
+        subsumed.push(c.path.clone());
+        false
+    });
+
+    for c in candidates.iter_mut() {
+        if let Some((extra, extra_disk)) = merged.get(&c.path) {
+            c.size_bytes = c.size_bytes.saturating_add(*extra);
+            c.disk_bytes = c.disk_bytes.saturating_add(*extra_disk);
+        }
+    }
+    subsumed.sort();
+    subsumed
+}
+
+// ──────────────────── post-clean verification ────────────────────
+
+/// Observed reclaim below this fraction of the claim counts as a shortfall.
+pub const RECLAIM_SHORTFALL_RATIO: f64 = 0.5;
+
+/// Claims smaller than this sit inside concurrent-write noise and are never judged.
+pub const RECLAIM_VERIFY_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Free space one mount actually gained from a batch, against the executor's claim.
+///
+/// `statvfs` sees less than `disk_bytes_freed` when snapshots pin the blocks,
+/// hardlinks keep another name alive, or a writer holds an unlinked file open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReclaimVerification {
+    pub mount: PathBuf,
+    pub expected_bytes: u64,
+    pub observed_bytes: u64,
+}
+
+impl ReclaimVerification {
+    #[must_use]
+    pub const fn shortfall_bytes(&self) -> u64 {
+        self.expected_bytes.saturating_sub(self.observed_bytes)
+    }
+
+    /// Whether the mount gained far less than the batch claimed.
+    #[must_use]
+    pub fn is_shortfall(&self) -> bool {
+        self.expected_bytes >= RECLAIM_VERIFY_MIN_BYTES
+            && (self.observed_bytes as f64) < self.expected_bytes as f64 * RECLAIM_SHORTFALL_RATIO
+    }
+}
+
+/// Compare each mount's free-space gain with what `report` claims for it.
+///
+/// `before` maps planned candidates to their mount's stats, collected before
+/// `execute`; `available_now` re-reads a mount's available bytes uncached.
+/// Claims are the planned on-disk sizes of deleted and truncated paths, so
+/// sparse files do not read as shortfalls. Dry runs
+/// yield nothing.
+pub fn verify_reclaim<S: std::hash::BuildHasher>(
+    plan: &DeletionPlan,
+    report: &DeletionReport,
+    before: &HashMap<PathBuf, FsStats, S>,
+    available_now: impl Fn(&Path) -> Option<u64>,
+) -> Vec<ReclaimVerification> {
+    if report.dry_run {
+        return Vec::new();
+    }
+    let removed: HashSet<&Path> = report
+        .deleted_paths
+        .iter()
+        .chain(&report.truncated_paths)
+        .map(PathBuf::as_path)
+        .collect();
+
+    // mount -> (available before, claimed bytes)
+    let mut claims: HashMap<&Path, (u64, u64)> = HashMap::new();
+    for candidate in &plan.candidates {
+        if !removed.contains(candidate.path.as_path()) {
+            continue;
+        }
+        let Some(stats) = before.get(&candidate.path) else {
+            continue;
+        };
+        let claim = claims
+            .entry(stats.mount_point.as_path())
+            .or_insert((stats.available_bytes, 0));
+        claim.1 = claim.1.saturating_add(candidate.disk_bytes);
+    }
+
+    let mut verified: Vec<ReclaimVerification> = claims
+        .into_iter()
+        .filter_map(|(mount, (available_before, expected_bytes))| {
+            let available_after = available_now(mount)?;
+            Some(ReclaimVerification {
+                mount: mount.to_path_buf(),
+                expected_bytes,
+                observed_bytes: available_after.saturating_sub(available_before),
+            })
+        })
+        .collect();
+    verified.sort_by(|a, b| a.mount.cmp(&b.mount));
+    verified
+}
+
 // ──────────────────── tests ────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification};
-    use crate::scanner::scoring::{DecisionOutcome, EvidenceLedger, ScoreFactors};
+    use crate::scanner::scoring::{DecisionOutcome, EvidenceLedger, ScoreFactors, ScoringInputs};
     use std::borrow::Cow;
 
     fn make_candidate(path: &Path, size: u64, score: f64) -> CandidacyScore {
@@ -463,6 +1385,7 @@ mod tests {
                 size: 0.6,
                 structure: 0.85,
                 pressure_multiplier: 1.0,
+                regeneration_cost: 1.0,
             },
             vetoed: false,
             veto_reason: None,
@@ -474,6 +1397,7 @@ mod tests {
                 combined_confidence: 0.92,
             },
             size_bytes: size,
+            disk_bytes: size,
             age: Duration::from_secs(3600),
             decision: DecisionOutcome {
                 action: DecisionAction::Delete,
@@ -487,6 +1411,7 @@ mod tests {
                 terms: Vec::new(),
                 summary: "test candidate".to_string(),
             },
+            inputs: ScoringInputs::default(),
         }
     }
 
@@ -519,6 +1444,47 @@ mod tests {
         assert_eq!(plan.total_reclaimable_bytes, 2000);
     }
 
+    #[test]
+    fn plan_subsumes_nested_candidates_and_merges_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("project/target");
+        let debug = target.join("debug");
+        let deps = debug.join("deps");
+        let sibling = dir.path().join("project/node_modules");
+
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        // The descendant scores higher than its ancestor; the ancestor still wins.
+        let plan = executor.plan(vec![
+            make_candidate(&debug, 300, 0.95),
+            make_candidate(&target, 100, 0.70),
+            make_candidate(&deps, 50, 0.90),
+            make_candidate(&sibling, 400, 0.80),
+        ]);
+
+        let paths: Vec<_> = plan.candidates.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(paths, vec![sibling, target]);
+        assert_eq!(plan.candidates[1].size_bytes, 450);
+        assert_eq!(plan.total_reclaimable_bytes, 850);
+        assert_eq!(plan.estimated_items, 2);
+        assert_eq!(plan.subsumed, vec![debug, deps]);
+    }
+
+    #[test]
+    fn plan_does_not_subsume_name_prefix_siblings() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("target");
+        let b = dir.path().join("target-old");
+
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let plan = executor.plan(vec![
+            make_candidate(&a, 10, 0.9),
+            make_candidate(&b, 20, 0.8),
+        ]);
+
+        assert_eq!(plan.candidates.len(), 2);
+        assert!(plan.subsumed.is_empty());
+    }
+
     #[test]
     fn plan_sorts_by_score_descending() {
         let dir = tempfile::tempdir().unwrap();
@@ -539,6 +1505,40 @@ mod tests {
         assert_eq!(plan.candidates[2].path, p1); // score 0.7
     }
 
+    #[test]
+    fn plan_hash_tracks_paths_sizes_and_order_but_not_scores() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let hash = |candidates| executor.plan(candidates).plan_hash();
+
+        let approved = hash(vec![
+            make_candidate(&a, 1000, 0.9),
+            make_candidate(&b, 2000, 0.8),
+        ]);
+        assert_eq!(approved.len(), 64);
+        assert_eq!(
+            approved,
+            hash(vec![
+                make_candidate(&b, 2000, 0.75),
+                make_candidate(&a, 1000, 0.95),
+            ])
+        );
+        let reordered = hash(vec![
+            make_candidate(&a, 1000, 0.8),
+            make_candidate(&b, 2000, 0.9),
+        ]);
+        let grown = hash(vec![
+            make_candidate(&a, 1001, 0.9),
+            make_candidate(&b, 2000, 0.8),
+        ]);
+        let dropped = hash(vec![make_candidate(&a, 1000, 0.9)]);
+        assert_ne!(approved, reordered);
+        assert_ne!(approved, grown);
+        assert_ne!(approved, dropped);
+    }
+
     #[test]
     fn execute_deletes_files_and_dirs() {
         let dir = tempfile::tempdir().unwrap();
@@ -566,6 +1566,98 @@ mod tests {
         assert!(!dir_path.exists());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn paced_removal_deletes_the_tree_but_not_link_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = dir.path().join("outside");
+        fs::create_dir(&outside).unwrap();
+        fs::write(outside.join("keep.txt"), "keep").unwrap();
+
+        let tree = dir.path().join("node_modules");
+        fs::create_dir_all(tree.join("pkg/lib")).unwrap();
+        fs::write(tree.join("pkg/lib/index.js"), "js").unwrap();
+        fs::write(tree.join(".package-lock.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(&outside, tree.join("linked")).unwrap();
+
+        let throttle = IoThrottle {
+            stall_pct: 10.0,
+            pause: Duration::from_millis(1),
+            max_wait: Duration::from_millis(5),
+        };
+        let mut pacer = Pacer::with_source(throttle, dir.path().join("no-psi"));
+        remove_dir_paced(&tree, &mut pacer).unwrap();
+
+        assert!(!tree.exists());
+        assert!(outside.join("keep.txt").exists());
+        assert_eq!(pacer.throttled(), Duration::ZERO);
+    }
+
+    #[test]
+    fn worker_pool_deletes_the_batch_and_splits_large_trees() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut candidates = Vec::new();
+        for i in 0..6 {
+            let p = dir.path().join(format!("file_{i}.txt"));
+            fs::write(&p, "x").unwrap();
+            candidates.push(make_candidate(&p, 1, 0.8));
+        }
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                workers: 4,
+                ..Default::default()
+            },
+            None,
+        );
+        let plan = executor.plan(candidates);
+        let report = executor.execute(&plan, None);
+        assert_eq!(report.items_deleted, 6);
+        assert_eq!(report.deleted_paths.len(), 6);
+        assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+
+        // A lone candidate leaves three workers spare for its tree.
+        let tree = dir.path().join("node_modules");
+        for i in 0..8 {
+            fs::create_dir_all(tree.join(format!("pkg_{i}/lib"))).unwrap();
+            fs::write(tree.join(format!("pkg_{i}/lib/index.js")), "js").unwrap();
+        }
+        fs::write(tree.join(".package-lock.json"), "{}").unwrap();
+        let plan = executor.plan(vec![make_candidate(&tree, 100, 0.9)]);
+        let report = executor.execute(&plan, None);
+        assert_eq!(report.items_deleted, 1);
+        assert!(!tree.exists());
+    }
+
+    #[test]
+    fn cancelled_batch_leaves_remaining_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut candidates = Vec::new();
+        for i in 0..3 {
+            let p = dir.path().join(format!("file_{i}.txt"));
+            fs::write(&p, "x").unwrap();
+            candidates.push(make_candidate(&p, 1, 0.8));
+        }
+        let cancel = Arc::new(AtomicBool::new(true));
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                workers: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .with_cancel(Arc::clone(&cancel));
+        let plan = executor.plan(candidates);
+        let report = executor.execute(&plan, None);
+        assert!(report.cancelled);
+        assert_eq!(report.items_deleted, 0);
+        assert!(plan.candidates.iter().all(|c| c.path.exists()));
+
+        cancel.store(false, Ordering::Relaxed);
+        let report = executor.execute(&plan, None);
+        assert!(!report.cancelled);
+        assert_eq!(report.items_deleted, 3);
+    }
+
     #[test]
     fn dry_run_does_not_delete() {
         let dir = tempfile::tempdir().unwrap();
@@ -588,6 +1680,144 @@ mod tests {
         assert!(file_path.exists(), "file should still exist in dry-run");
     }
 
+    #[test]
+    fn observer_hears_each_item_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("present.o");
+        fs::write(&present, "obj").unwrap();
+        let missing = dir.path().join("missing.o");
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = std::sync::Arc::clone(&seen);
+
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None).with_observer(
+            Box::new(move |candidate, outcome| {
+                sink.lock()
+                    .unwrap()
+                    .push((candidate.path.clone(), outcome.clone()));
+            }),
+        );
+        let plan = executor.plan(vec![
+            make_candidate(&present, 3, 0.9),
+            make_candidate(&missing, 3, 0.8),
+        ]);
+        executor.execute(&plan, None);
+
+        let seen = std::mem::take(&mut *seen.lock().unwrap());
+        assert_eq!(
+            seen,
+            [
+                (present, ItemOutcome::Deleted),
+                (missing, ItemOutcome::Skipped(Some(SkipReason::PathGone))),
+            ]
+        );
+    }
+
+    #[test]
+    fn real_batches_write_a_manifest_before_deleting() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("proj/target");
+        fs::create_dir_all(target.join("debug")).unwrap();
+        fs::write(target.join("CACHEDIR.TAG"), "tag").unwrap();
+        let log = AuditLog::new(dir.path().join("audit.jsonl"), None);
+        let context = AuditContext {
+            source: "daemon",
+            policy_mode: Some("enforce".to_string()),
+            decision_ids: HashMap::from([(target.clone(), 42)]),
+        };
+
+        let dry = DeletionExecutor::new(
+            DeletionConfig {
+                dry_run: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .with_audit(log.clone(), context.clone());
+        let report = dry.execute(&dry.plan(vec![make_candidate(&target, 100, 0.9)]), None);
+        assert_eq!(report.audit_batch_id, None);
+        assert!(!log.jsonl_path().exists());
+
+        let executor =
+            DeletionExecutor::new(DeletionConfig::default(), None).with_audit(log.clone(), context);
+        let plan = executor.plan(vec![make_candidate(&target, 100, 0.9)]);
+        let report = executor.execute(&plan, None);
+        assert_eq!(report.items_deleted, 1);
+        assert!(!target.exists());
+
+        let batch_id = report.audit_batch_id.unwrap();
+        let manifest = log.find(&batch_id).unwrap().unwrap();
+        assert_eq!(manifest.source, "daemon");
+        assert_eq!(manifest.policy_mode.as_deref(), Some("enforce"));
+        let item = &manifest.items[0];
+        assert_eq!(item.path, target);
+        assert_eq!(item.decision_id, Some(42));
+        assert_eq!(item.action, ManifestAction::Delete);
+        assert_eq!(item.category, "rust_target");
+        // Captured while the tree still existed.
+        assert_eq!(item.top_level.total, 2);
+        assert_eq!(item.top_level.entries[0].name, "CACHEDIR.TAG");
+    }
+
+    #[test]
+    fn quarantined_bytes_are_not_counted_as_freed() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("app.log");
+        fs::write(&file_path, "log").unwrap();
+
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                dry_run: true,
+                quarantine_ttl: Some(Duration::from_secs(3600)),
+                ..Default::default()
+            },
+            None,
+        );
+        let plan = executor.plan(vec![make_candidate(&file_path, 3, 0.85)]);
+        let report = executor.execute(&plan, None);
+
+        assert_eq!((report.items_quarantined, report.items_deleted), (1, 0));
+        assert_eq!((report.bytes_quarantined, report.bytes_freed), (3, 0));
+        assert!(file_path.exists());
+    }
+
+    #[test]
+    fn relocation_that_cannot_archive_keeps_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("build.o"), "object file").unwrap();
+
+        // The archive shares the tempdir's filesystem, so the copy is refused.
+        let relocation = Relocation {
+            archive_root: dir.path().join("archive"),
+            categories: vec![ArtifactCategory::RustTarget],
+            min_free_pct: 0.0,
+            object_store: None,
+        };
+        let config = DeletionConfig {
+            check_open_files: false,
+            relocation: Some(relocation),
+            ..Default::default()
+        };
+        let executor = DeletionExecutor::new(config.clone(), None);
+        let plan = executor.plan(vec![make_candidate(&target, 11, 0.9)]);
+        let report = executor.execute(&plan, None);
+        assert_eq!((report.items_relocated, report.items_failed), (0, 1));
+        assert_eq!(report.items_deleted, 0);
+        assert!(target.join("build.o").exists());
+
+        let dry = DeletionExecutor::new(
+            DeletionConfig {
+                dry_run: true,
+                ..config
+            },
+            None,
+        );
+        let report = dry.execute(&plan, None);
+        assert_eq!((report.items_relocated, report.items_deleted), (1, 0));
+        assert_eq!(report.bytes_freed, 11);
+    }
+
     #[test]
     fn skips_path_with_dot_git() {
         let dir = tempfile::tempdir().unwrap();
@@ -765,6 +1995,20 @@ mod tests {
         // Remaining 2 candidates were never attempted.
         assert_eq!(report.items_deleted, 0);
 
+        // Two workers each count their own failures; one trip halts both.
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                circuit_breaker_threshold: 2,
+                check_open_files: false,
+                workers: 2,
+                ..Default::default()
+            },
+            None,
+        );
+        let report = executor.execute(&plan, None);
+        assert!(report.circuit_breaker_tripped);
+        assert!(report.items_failed >= 2 && report.items_failed < 5);
+
         // Restore permissions so tempdir cleanup works.
         for i in 0..5 {
             let sub = dir.path().join(format!("dir_{i}")).join("locked_sub");
@@ -822,6 +2066,48 @@ mod tests {
         drop(handle);
     }
 
+    #[test]
+    fn plan_for_target_prefers_one_large_cache_over_many_small_dirs() {
+        let gib = 1024 * 1024 * 1024;
+        let mut candidates: Vec<CandidacyScore> = (0..40)
+            .map(|i| make_candidate(Path::new(&format!("/data/p{i}/target")), gib / 4, 0.95))
+            .collect();
+        candidates.push(make_candidate(Path::new("/data/cache"), 80 * gib, 0.80));
+
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let plan = executor.plan_for_target(candidates, 50 * gib);
+        assert_eq!(plan.estimated_items, 1);
+        assert_eq!(plan.candidates[0].path, Path::new("/data/cache"));
+        assert_eq!(plan.deferred, 40);
+        assert_eq!(plan.total_reclaimable_bytes, 80 * gib);
+    }
+
+    #[test]
+    fn plan_for_target_stops_at_target_plus_margin_and_prunes_redundant_picks() {
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let candidates = vec![
+            // Efficiency (bytes / max(1 - score, 0.05)): x 10_000, y 9_500, z 6_000.
+            make_candidate(Path::new("/data/x"), 500, 0.99),
+            make_candidate(Path::new("/data/y"), 1_900, 0.80),
+            make_candidate(Path::new("/data/z"), 3_000, 0.50),
+        ];
+        // Goal is 1_100 bytes: x then y reach it, after which x is redundant.
+        let plan = executor.plan_for_target(candidates.clone(), 1_000);
+        let paths: Vec<&Path> = plan.candidates.iter().map(|c| c.path.as_path()).collect();
+        assert_eq!(paths, vec![Path::new("/data/y")]);
+        assert_eq!(plan.deferred, 2);
+
+        assert!(
+            executor
+                .plan_for_target(candidates.clone(), 0)
+                .candidates
+                .is_empty()
+        );
+        let everything = executor.plan_for_target(candidates, 1_000_000);
+        assert_eq!(everything.estimated_items, 3);
+        assert_eq!(everything.deferred, 0);
+    }
+
     #[test]
     fn deletion_report_tracks_deleted_paths() {
         let dir = tempfile::tempdir().unwrap();
@@ -847,4 +2133,149 @@ mod tests {
         assert!(report.deleted_paths.contains(&file2));
         assert!(report.deleted_paths.contains(&file3));
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn open_allowlisted_file_is_truncated_instead_of_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("app.log");
+        fs::write(&log, vec![b'x'; 4096]).unwrap();
+        let handle = fs::OpenOptions::new().append(true).open(&log).unwrap();
+
+        let (open_paths, _) = walker::collect_open_path_ancestors(std::slice::from_ref(&log));
+        if !open_paths.contains(&log) {
+            drop(handle);
+            return;
+        }
+
+        let mut candidate = make_candidate(&log, 4096, 0.8);
+        candidate.classification.category = ArtifactCategory::LogFile;
+
+        // Without the allowlist the open file is skipped untouched.
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let report = executor.execute(&executor.plan(vec![candidate.clone()]), None);
+        assert_eq!(report.items_skipped, 1);
+        assert_eq!(fs::metadata(&log).unwrap().len(), 4096);
+
+        // Dry run counts the bytes but leaves the file alone.
+        let config = DeletionConfig {
+            truncate_categories: vec![ArtifactCategory::LogFile],
+            dry_run: true,
+            ..DeletionConfig::default()
+        };
+        let executor = DeletionExecutor::new(config.clone(), None);
+        let report = executor.execute(&executor.plan(vec![candidate.clone()]), None);
+        assert_eq!(report.items_truncated, 1);
+        assert_eq!(report.bytes_freed, 4096);
+        assert_eq!(fs::metadata(&log).unwrap().len(), 4096);
+
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                dry_run: false,
+                ..config
+            },
+            None,
+        );
+        let report = executor.execute(&executor.plan(vec![candidate]), None);
+        assert_eq!(report.items_truncated, 1);
+        assert_eq!(report.items_deleted, 0);
+        assert_eq!(report.bytes_freed, 4096);
+        assert_eq!(report.truncated_paths, vec![log.clone()]);
+        assert!(log.exists());
+        assert_eq!(fs::metadata(&log).unwrap().len(), 0);
+        drop(handle);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn truncate_file_refuses_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target.log");
+        fs::write(&target, vec![b'x'; 64]).unwrap();
+        let link = dir.path().join("link.log");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(truncate_file(&link).is_err());
+        assert_eq!(fs::metadata(&target).unwrap().len(), 64);
+        assert_eq!(truncate_file(&target).unwrap(), 64);
+        assert_eq!(fs::metadata(&target).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn verify_reclaim_flags_mounts_that_gained_far_less_than_claimed() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let plan = executor.plan(vec![
+            make_candidate(Path::new("/data/snap/target"), 4 * GIB, 0.9),
+            make_candidate(Path::new("/home/u/target"), 2 * GIB, 0.9),
+            make_candidate(Path::new("/home/u/skipped"), 8 * GIB, 0.9),
+        ]);
+        let stats = |mount: &str, available_bytes: u64| FsStats {
+            total_bytes: 100 * GIB,
+            free_bytes: available_bytes,
+            available_bytes,
+            fs_type: "ext4".to_string(),
+            mount_point: PathBuf::from(mount),
+            is_readonly: false,
+        };
+        let before: HashMap<PathBuf, FsStats> = [
+            ("/data/snap/target", stats("/data", 10 * GIB)),
+            ("/home/u/target", stats("/home", 20 * GIB)),
+            ("/home/u/skipped", stats("/home", 20 * GIB)),
+        ]
+        .into_iter()
+        .map(|(path, s)| (PathBuf::from(path), s))
+        .collect();
+        let mut report = DeletionReport {
+            items_deleted: 2,
+            items_failed: 0,
+            items_skipped: 1,
+            bytes_freed: 6 * GIB,
+            disk_bytes_freed: 6 * GIB,
+            duration: Duration::ZERO,
+            errors: Vec::new(),
+            dry_run: false,
+            circuit_breaker_tripped: false,
+            deleted_paths: vec![
+                PathBuf::from("/data/snap/target"),
+                PathBuf::from("/home/u/target"),
+            ],
+            items_truncated: 0,
+            truncated_paths: Vec::new(),
+            items_relocated: 0,
+            relocated_paths: Vec::new(),
+            items_quarantined: 0,
+            bytes_quarantined: 0,
+            quarantined_paths: Vec::new(),
+            audit_batch_id: None,
+            throttled: Duration::ZERO,
+            cancelled: false,
+        };
+
+        // /data sits under a snapshot and gained almost nothing; /home got it all.
+        let after = |mount: &Path| match mount.to_str() {
+            Some("/data") => Some(10 * GIB + 100 * 1024 * 1024),
+            Some("/home") => Some(22 * GIB),
+            _ => None,
+        };
+        let verified = verify_reclaim(&plan, &report, &before, after);
+        assert_eq!(verified.len(), 2);
+        assert_eq!(verified[0].mount, PathBuf::from("/data"));
+        assert_eq!(verified[0].expected_bytes, 4 * GIB);
+        assert!(verified[0].is_shortfall());
+        assert_eq!(verified[1].mount, PathBuf::from("/home"));
+        assert_eq!(verified[1].expected_bytes, 2 * GIB);
+        assert_eq!(verified[1].observed_bytes, 2 * GIB);
+        assert!(!verified[1].is_shortfall());
+
+        // Small claims are never judged, and dry runs are not verified at all.
+        let tiny = ReclaimVerification {
+            mount: PathBuf::from("/tmp"),
+            expected_bytes: 1024,
+            observed_bytes: 0,
+        };
+        assert!(!tiny.is_shortfall());
+        report.dry_run = true;
+        assert!(verify_reclaim(&plan, &report, &before, after).is_empty());
+    }
 }
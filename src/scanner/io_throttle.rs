@@ -0,0 +1,197 @@
+//! IO-pressure pacing for the deletion executor.
+//!
+//! Removing a large `node_modules/` or `target/` tree is a burst of metadata
+//! writes that can stall everything else on the device, including the build
+//! jobs the cleanup is meant to rescue. Before each deletion, and between the
+//! top-level entries of a directory being removed, the executor asks a
+//! [`Pacer`] whether the device is congested.
+//!
+//! Congestion comes from Linux pressure stall information
+//! (`/proc/pressure/io`): the share of wall time in which some task waited on
+//! IO since the previous check. Above [`IoThrottle::stall_pct`] the pacer
+//! sleeps in [`IoThrottle::pause`] steps until the share drops. Each
+//! candidate may be held up for at most [`IoThrottle::max_wait`] in total, so
+//! cleanup slows down but never stalls outright. Without PSI (older kernels,
+//! other platforms, containers that hide it) pacing does nothing.
+
+#![allow(clippy::cast_precision_loss)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Kernel IO pressure stall information.
+pub const PSI_IO_PATH: &str = "/proc/pressure/io";
+
+/// Stall shares measured over shorter windows than this are mostly noise;
+/// the previous measurement stands until a longer window has passed.
+const MIN_WINDOW: Duration = Duration::from_millis(50);
+
+/// Pacing settings (`scanner.io_throttle_*` in the config).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IoThrottle {
+    /// Share of wall time, in percent, that tasks may spend stalled on IO
+    /// before deletion pauses.
+    pub stall_pct: f64,
+    /// Sleep between re-checks while paused.
+    pub pause: Duration,
+    /// Longest one candidate's removal may be held up before it proceeds
+    /// regardless.
+    pub max_wait: Duration,
+}
+
+/// Cumulative microseconds in which some task was stalled on IO: the
+/// `total=` field of the `some` line.
+#[must_use]
+pub fn parse_psi_some_total(raw: &str) -> Option<u64> {
+    raw.lines()
+        .find_map(|line| line.strip_prefix("some "))?
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("total="))?
+        .parse()
+        .ok()
+}
+
+/// Paces one deletion batch and accounts for the time it spent paused.
+#[derive(Debug)]
+pub struct Pacer {
+    throttle: IoThrottle,
+    source: PathBuf,
+    /// Last sample taken over at least [`MIN_WINDOW`], as (when, stall total).
+    last: Option<(Instant, u64)>,
+    stall_pct: f64,
+    /// Paused so far for the current candidate.
+    item_waited: Duration,
+    throttled: Duration,
+}
+
+impl Pacer {
+    /// A pacer reading [`PSI_IO_PATH`].
+    #[must_use]
+    pub fn new(throttle: IoThrottle) -> Self {
+        Self::with_source(throttle, PSI_IO_PATH)
+    }
+
+    /// A pacer reading PSI from `source`, for tests.
+    #[must_use]
+    pub fn with_source(throttle: IoThrottle, source: impl Into<PathBuf>) -> Self {
+        let mut pacer = Self {
+            throttle,
+            source: source.into(),
+            last: None,
+            stall_pct: 0.0,
+            item_waited: Duration::ZERO,
+            throttled: Duration::ZERO,
+        };
+        pacer.last = pacer.sample();
+        pacer
+    }
+
+    /// Total time this pacer has spent paused.
+    #[must_use]
+    pub const fn throttled(&self) -> Duration {
+        self.throttled
+    }
+
+    /// Start pacing a new candidate, with a fresh wait budget, and wait
+    /// while the device is congested.
+    pub fn next_item(&mut self) -> Duration {
+        self.item_waited = Duration::ZERO;
+        self.pace()
+    }
+
+    /// Wait while the device is congested, out of the current candidate's
+    /// budget. Returns the time spent paused.
+    pub fn pace(&mut self) -> Duration {
+        let mut waited = Duration::ZERO;
+        while self.item_waited < self.throttle.max_wait
+            && self
+                .measure()
+                .is_some_and(|stall_pct| stall_pct > self.throttle.stall_pct)
+        {
+            let step = self
+                .throttle
+                .pause
+                .min(self.throttle.max_wait.saturating_sub(self.item_waited));
+            thread::sleep(step);
+            waited += step;
+            self.item_waited += step;
+        }
+        self.throttled += waited;
+        waited
+    }
+
+    fn sample(&self) -> Option<(Instant, u64)> {
+        let raw = fs::read_to_string(&self.source).ok()?;
+        Some((Instant::now(), parse_psi_some_total(&raw)?))
+    }
+
+    /// Stall share since the last sample, or `None` without PSI.
+    fn measure(&mut self) -> Option<f64> {
+        let (now, total) = self.sample()?;
+        let Some((then, last_total)) = self.last else {
+            self.last = Some((now, total));
+            return None;
+        };
+        let window = now.duration_since(then);
+        if window >= MIN_WINDOW {
+            let stalled = Duration::from_micros(total.saturating_sub(last_total));
+            self.stall_pct = stalled.as_secs_f64() * 100.0 / window.as_secs_f64();
+            self.last = Some((now, total));
+        }
+        Some(self.stall_pct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THROTTLE: IoThrottle = IoThrottle {
+        stall_pct: 20.0,
+        pause: Duration::from_millis(10),
+        max_wait: Duration::from_millis(30),
+    };
+
+    fn psi(some_total: u64) -> String {
+        format!(
+            "some avg10=0.00 avg60=0.00 avg300=0.00 total={some_total}\n\
+             full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n"
+        )
+    }
+
+    #[test]
+    fn parses_the_some_line_total() {
+        assert_eq!(parse_psi_some_total(&psi(123_456)), Some(123_456));
+        assert_eq!(parse_psi_some_total("full total=5\n"), None);
+        assert_eq!(parse_psi_some_total(""), None);
+    }
+
+    #[test]
+    fn pauses_while_stalled_up_to_each_items_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("io");
+        fs::write(&source, psi(0)).unwrap();
+        let mut pacer = Pacer::with_source(THROTTLE, &source);
+
+        thread::sleep(MIN_WINDOW);
+        assert_eq!(pacer.next_item(), Duration::ZERO);
+
+        // A full second of stalls since the last sample is congestion. The
+        // budget is shorter than a measurement window, so the pacer spends
+        // all of it; further checks for the same item no longer wait.
+        thread::sleep(MIN_WINDOW);
+        fs::write(&source, psi(1_000_000)).unwrap();
+        assert_eq!(pacer.next_item(), THROTTLE.max_wait);
+        assert_eq!(pacer.pace(), Duration::ZERO);
+        assert_eq!(pacer.throttled(), THROTTLE.max_wait);
+    }
+
+    #[test]
+    fn missing_psi_never_pauses() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pacer = Pacer::with_source(THROTTLE, dir.path().join("absent"));
+        assert_eq!(pacer.pace(), Duration::ZERO);
+    }
+}
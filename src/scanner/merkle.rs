@@ -789,6 +789,8 @@ mod tests {
             metadata: EntryMetadata {
                 size_bytes: size,
                 content_size_bytes: size,
+                disk_bytes: size,
+                content_disk_bytes: size,
                 modified: UNIX_EPOCH + Duration::from_secs(modified_secs),
                 created: None,
                 is_dir: true,
@@ -1,9 +1,22 @@
-//! Artifact scanner: directory walker, pattern matching, multi-factor scoring, deletion.
+//! Artifact scanner: directory walker, pattern matching, multi-factor scoring, IO-paced deletion,
+//! quarantine, curated tool-cache cleanup, content previews, and open-but-deleted
+//! file detection.
 
+pub mod archive;
 pub mod decision_record;
 pub mod deletion;
+pub mod io_throttle;
 pub mod merkle;
+pub mod object_store;
+pub mod orphaned_fds;
 pub mod patterns;
+pub mod preview;
 pub mod protection;
+pub mod quarantine;
+pub mod score_history;
 pub mod scoring;
+#[cfg(target_os = "linux")]
+pub mod statx;
+pub mod synthetic;
+pub mod tool_caches;
 pub mod walker;
@@ -0,0 +1,535 @@
+//! S3-compatible object storage backend for the artifact archive and the
+//! quarantine.
+//!
+//! With a bucket configured, a relocated or quarantined tree is streamed
+//! from `tar` straight into the upload as one tarball, with its SHA-256 as
+//! the signed payload hash (so the store rejects a corrupted body), and
+//! checked by size. Nothing is staged on the pressured disk; only the index
+//! stays local. Expiry belongs to the bucket's lifecycle rule;
+//! `retention_days` just records when that rule will have removed the
+//! object.
+//!
+//! Transfers shell out to `curl --aws-sigv4` and `tar`, as the updater and
+//! webhook channel do, so no HTTP or signing stack is linked in. Credentials
+//! come from `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` (plus an optional
+//! `AWS_SESSION_TOKEN`) and reach curl as a `--config` file on an inherited
+//! pipe, never on its command line.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::errors::{Result, SbhError};
+
+/// Bucket settings (`[archive.s3]` or `[quarantine.s3]` in the config).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectStore {
+    /// Base URL, e.g. `https://s3.amazonaws.com` or a MinIO host.
+    pub endpoint: String,
+    pub bucket: String,
+    /// Key prefix; the bucket lifecycle rule should match it.
+    pub prefix: String,
+    pub region: String,
+    /// Days the lifecycle rule keeps objects; 0 means until restored.
+    pub retention_days: u32,
+}
+
+/// An uploaded tarball, as recorded in the archive index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredObject {
+    pub url: String,
+    pub region: String,
+    /// Hex SHA-256 of the tarball.
+    pub sha256: String,
+    pub bytes: u64,
+    /// RFC 3339 time the lifecycle rule removes the object, if it does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+impl ObjectStore {
+    /// Path-style URL of entry `id`, which every S3-compatible store accepts.
+    #[must_use]
+    pub fn object_url(&self, id: &str) -> String {
+        format!(
+            "{}/{}/{}{id}.tar",
+            self.endpoint.trim_end_matches('/'),
+            self.bucket,
+            self.prefix
+        )
+    }
+
+    /// Upload the tree at `src` as entry `id`, then confirm the stored size.
+    /// A mismatched upload is deleted again.
+    ///
+    /// `tar` runs twice: once to hash the stream for the signed payload hash,
+    /// then piped into curl. A tree that changed in between no longer
+    /// matches the hash, so the store rejects it.
+    pub fn put_tree(&self, src: &Path, id: &str) -> Result<StoredObject> {
+        let (sha256, bytes) = digest_tree(src)?;
+        let object = StoredObject {
+            url: self.object_url(id),
+            region: self.region.clone(),
+            sha256,
+            bytes,
+            expires_at: (self.retention_days > 0).then(|| {
+                (Utc::now() + chrono::Duration::days(i64::from(self.retention_days)))
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+            }),
+        };
+        let (mut tar, body) = spawn_tar(src)?;
+        let mut cmd = object.curl();
+        // With a length, curl sends the stream as-is; S3 refuses chunked PUTs.
+        cmd.arg("--header")
+            .arg(format!("x-amz-content-sha256: {}", object.sha256))
+            .arg("--header")
+            .arg(format!("Content-Length: {bytes}"))
+            .arg("--header")
+            .arg("Transfer-Encoding:")
+            .arg("--upload-file")
+            .arg("-");
+        let uploaded = spawn_curl(cmd, &object.url, Stdio::from(body), Stdio::piped())
+            .and_then(|child| wait_curl(child, &object.url));
+        let packed = wait_tar(&mut tar, src);
+        uploaded?;
+        if let Err(err) = packed {
+            let _ = object.delete();
+            return Err(err);
+        }
+
+        let stored = object.stored_len()?;
+        if stored != bytes {
+            let _ = object.delete();
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "object store holds {stored} bytes for {}, uploaded {bytes}",
+                    object.url
+                ),
+            });
+        }
+        Ok(object)
+    }
+}
+
+impl StoredObject {
+    /// Whether the lifecycle rule has removed the object by `now`.
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .as_deref()
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .is_some_and(|expires| expires <= now)
+    }
+
+    pub fn delete(&self) -> Result<()> {
+        let mut cmd = self.curl();
+        cmd.arg("--request").arg("DELETE");
+        run_curl(cmd, &self.url).map(drop)
+    }
+
+    /// Download the tarball and unpack its single tree at `dest`, verifying
+    /// the SHA-256 before anything appears there.
+    pub fn fetch_into(&self, dest: &Path) -> Result<()> {
+        let mut child = spawn_curl(self.curl(), &self.url, Stdio::null(), Stdio::piped())?;
+        let body = child.stdout.take().ok_or_else(|| SbhError::Runtime {
+            details: "curl stdout unavailable".to_string(),
+        })?;
+        let unpacked = unpack(body, dest, &self.sha256);
+        let output = child.wait_with_output().map_err(|e| SbhError::Runtime {
+            details: format!("failed to wait for curl: {e}"),
+        })?;
+        if !output.status.success() {
+            if unpacked.is_ok() {
+                let _ = fs::remove_dir_all(dest);
+            }
+            return Err(curl_failure(&self.url, &output.stderr));
+        }
+        unpacked
+    }
+
+    fn stored_len(&self) -> Result<u64> {
+        let mut cmd = self.curl();
+        cmd.arg("--head");
+        let headers = run_curl(cmd, &self.url)?;
+        content_length(&headers).ok_or_else(|| SbhError::Runtime {
+            details: format!("no Content-Length for {}", self.url),
+        })
+    }
+
+    fn curl(&self) -> Command {
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--fail"])
+            .arg("--aws-sigv4")
+            .arg(format!("aws:amz:{}:s3", self.region));
+        cmd
+    }
+}
+
+/// Start `tar` writing `src` to its stdout as a single `./<name>` member.
+fn spawn_tar(src: &Path) -> Result<(Child, ChildStdout)> {
+    let (parent, name) = split(src)?;
+    // `./name` keeps a name that starts with `-` from reading as an option.
+    let mut tar = Command::new("tar")
+        .args(["-cf", "-", "-C"])
+        .arg(parent)
+        .arg(Path::new(".").join(name))
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| SbhError::Runtime {
+            details: format!("failed to run tar: {e}"),
+        })?;
+    let stdout = tar.stdout.take().ok_or_else(|| SbhError::Runtime {
+        details: "tar stdout unavailable".to_string(),
+    })?;
+    Ok((tar, stdout))
+}
+
+fn wait_tar(tar: &mut Child, src: &Path) -> Result<()> {
+    let status = tar.wait().map_err(|e| SbhError::Runtime {
+        details: format!("failed to wait for tar: {e}"),
+    })?;
+    if !status.success() {
+        return Err(SbhError::Runtime {
+            details: format!("tar failed to pack {}", src.display()),
+        });
+    }
+    Ok(())
+}
+
+/// Hex SHA-256 and size of the tarball `tar` makes of `src`, without
+/// writing it anywhere.
+fn digest_tree(src: &Path) -> Result<(String, u64)> {
+    let (mut tar, mut stdout) = spawn_tar(src)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0_u8; 1 << 16];
+    let mut bytes = 0_u64;
+    let read: std::io::Result<()> = loop {
+        match stdout.read(&mut buf) {
+            Ok(0) => break Ok(()),
+            Ok(read) => {
+                hasher.update(&buf[..read]);
+                bytes += read as u64;
+            }
+            Err(e) => break Err(e),
+        }
+    };
+    drop(stdout);
+    wait_tar(&mut tar, src)?;
+    read.map_err(|e| SbhError::io(src, e))?;
+    Ok((hex(&hasher.finalize()), bytes))
+}
+
+/// Unpack a tarball stream holding one tree to `dest`. The tree is staged
+/// beside `dest` and only renamed into place once the stream's SHA-256
+/// matches `expected_sha256`.
+pub fn unpack(body: impl Read, dest: &Path, expected_sha256: &str) -> Result<()> {
+    let (parent, name) = split(dest)?;
+    let staging = parent.join(format!(".sbh-restore-{}", name.to_string_lossy()));
+    fs::create_dir_all(&staging).map_err(|e| SbhError::io(&staging, e))?;
+    let result = unpack_staged(body, &staging, dest, expected_sha256);
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn unpack_staged(mut body: impl Read, staging: &Path, dest: &Path, expected: &str) -> Result<()> {
+    let mut tar = Command::new("tar")
+        .args(["-xf", "-", "-C"])
+        .arg(staging)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| SbhError::Runtime {
+            details: format!("failed to run tar: {e}"),
+        })?;
+    let mut hasher = Sha256::new();
+    let copied: std::io::Result<()> = tar.stdin.take().map_or(Ok(()), |mut stdin| {
+        let mut buf = vec![0_u8; 1 << 16];
+        loop {
+            let read = body.read(&mut buf)?;
+            if read == 0 {
+                return Ok(());
+            }
+            hasher.update(&buf[..read]);
+            stdin.write_all(&buf[..read])?;
+        }
+    });
+    let status = tar.wait().map_err(|e| SbhError::Runtime {
+        details: format!("failed to wait for tar: {e}"),
+    })?;
+    copied.map_err(|e| SbhError::io(staging, e))?;
+    if !status.success() {
+        return Err(SbhError::Runtime {
+            details: format!("tar failed to unpack into {}", staging.display()),
+        });
+    }
+    let actual = hex(&hasher.finalize());
+    if actual != expected {
+        return Err(SbhError::Runtime {
+            details: format!("archive checksum mismatch: expected {expected}, got {actual}"),
+        });
+    }
+
+    let members: Vec<_> = fs::read_dir(staging)
+        .map_err(|e| SbhError::io(staging, e))?
+        .flatten()
+        .collect();
+    let [member] = members.as_slice() else {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "archive holds {} top-level entries, expected one",
+                members.len()
+            ),
+        });
+    };
+    fs::rename(member.path(), dest).map_err(|e| SbhError::io(dest, e))
+}
+
+fn split(path: &Path) -> Result<(&Path, &std::ffi::OsStr)> {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => Ok((parent, name)),
+        _ => Err(SbhError::Runtime {
+            details: format!("{} has no parent directory or name", path.display()),
+        }),
+    }
+}
+
+fn spawn_curl(mut cmd: Command, url: &str, stdin: Stdio, stdout: Stdio) -> Result<Child> {
+    let config = credentials_config(|name| std::env::var(name).ok())?;
+    // Held open until curl has started and inherited it.
+    let _config = attach_config(&mut cmd, &config)?;
+    cmd.arg("--")
+        .arg(url)
+        .stdin(stdin)
+        .stdout(stdout)
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| SbhError::Runtime {
+            details: format!("failed to run curl: {e}"),
+        })
+}
+
+/// Point curl's `--config` at a pipe it inherits, leaving stdin free for an
+/// upload body. The text fits in the pipe buffer, so it is written before
+/// curl starts and the write end closed to mark its end.
+#[cfg(unix)]
+fn attach_config(cmd: &mut Command, config: &str) -> Result<std::io::PipeReader> {
+    use nix::fcntl::{FcntlArg, FdFlag, fcntl};
+    use std::os::fd::AsRawFd;
+
+    let piped = std::io::pipe().and_then(|(reader, mut writer)| {
+        writer.write_all(config.as_bytes())?;
+        fcntl(reader.as_raw_fd(), FcntlArg::F_SETFD(FdFlag::empty()))?;
+        Ok(reader)
+    });
+    let reader = piped.map_err(|e| SbhError::Runtime {
+        details: format!("failed to pass curl its config: {e}"),
+    })?;
+    cmd.arg("--config")
+        .arg(format!("/dev/fd/{}", reader.as_raw_fd()));
+    Ok(reader)
+}
+
+#[cfg(not(unix))]
+fn attach_config(_cmd: &mut Command, _config: &str) -> Result<()> {
+    Err(SbhError::UnsupportedPlatform {
+        details: "object storage transfers require Unix pipes".to_string(),
+    })
+}
+
+fn run_curl(cmd: Command, url: &str) -> Result<String> {
+    wait_curl(spawn_curl(cmd, url, Stdio::null(), Stdio::piped())?, url)
+}
+
+fn wait_curl(child: Child, url: &str) -> Result<String> {
+    let output = child.wait_with_output().map_err(|e| SbhError::Runtime {
+        details: format!("failed to wait for curl: {e}"),
+    })?;
+    if !output.status.success() {
+        return Err(curl_failure(url, &output.stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn curl_failure(url: &str, stderr: &[u8]) -> SbhError {
+    SbhError::Runtime {
+        details: format!(
+            "object store request to {url} failed: {}",
+            String::from_utf8_lossy(stderr).trim()
+        ),
+    }
+}
+
+/// curl `--config` text carrying the signing credentials.
+fn credentials_config(lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let (Some(key), Some(secret)) = (
+        lookup("AWS_ACCESS_KEY_ID").filter(|v| !v.is_empty()),
+        lookup("AWS_SECRET_ACCESS_KEY").filter(|v| !v.is_empty()),
+    ) else {
+        return Err(SbhError::Runtime {
+            details: "AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY must be set for S3 uploads"
+                .to_string(),
+        });
+    };
+    let mut config = format!("user = \"{}:{}\"\n", quote(&key), quote(&secret));
+    if let Some(token) = lookup("AWS_SESSION_TOKEN").filter(|v| !v.is_empty()) {
+        use std::fmt::Write as _;
+        let _ = writeln!(
+            config,
+            "header = \"x-amz-security-token: {}\"",
+            quote(&token)
+        );
+    }
+    Ok(config)
+}
+
+/// Escape `value` for a double-quoted curl `--config` string.
+pub(crate) fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Content-Length of the final response in a `curl --head` dump (earlier
+/// blocks belong to redirects).
+fn content_length(headers: &str) -> Option<u64> {
+    headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .filter_map(|(_, value)| value.trim().parse().ok())
+        .next_back()
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut acc, b| {
+            let _ = write!(acc, "{b:02x}");
+            acc
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn store() -> ObjectStore {
+        ObjectStore {
+            endpoint: "https://minio.internal:9000/".to_string(),
+            bucket: "cold".to_string(),
+            prefix: "sbh-archive/".to_string(),
+            region: "us-east-1".to_string(),
+            retention_days: 30,
+        }
+    }
+
+    #[test]
+    fn object_urls_are_path_style_under_the_prefix() {
+        assert_eq!(
+            store().object_url("20261016T120000.000Z-1a2b3c4d"),
+            "https://minio.internal:9000/cold/sbh-archive/20261016T120000.000Z-1a2b3c4d.tar"
+        );
+    }
+
+    #[test]
+    fn credentials_go_to_curl_config_escaped_and_are_required() {
+        let env = |name: &str| match name {
+            "AWS_ACCESS_KEY_ID" => Some("AKIA123".to_string()),
+            "AWS_SECRET_ACCESS_KEY" => Some("se\"cr\\et".to_string()),
+            "AWS_SESSION_TOKEN" => Some("tok".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            credentials_config(env).unwrap(),
+            "user = \"AKIA123:se\\\"cr\\\\et\"\nheader = \"x-amz-security-token: tok\"\n"
+        );
+        assert!(credentials_config(|_| None).is_err());
+    }
+
+    #[test]
+    fn head_dump_uses_the_last_content_length_and_expiry_is_parsed() {
+        let dump = "HTTP/1.1 307 Temporary Redirect\r\nContent-Length: 0\r\n\r\n\
+                    HTTP/1.1 200 OK\r\ncontent-length: 4096\r\n\r\n";
+        assert_eq!(content_length(dump), Some(4096));
+        assert_eq!(content_length("HTTP/1.1 200 OK\r\n"), None);
+
+        let object = StoredObject {
+            url: store().object_url("a"),
+            region: "us-east-1".to_string(),
+            sha256: String::new(),
+            bytes: 0,
+            expires_at: Some("2026-10-16T12:00:00.000Z".to_string()),
+        };
+        let at = |raw: &str| {
+            DateTime::parse_from_rfc3339(raw)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert!(!object.is_expired(at("2026-10-15T12:00:00Z")));
+        assert!(object.is_expired(at("2026-10-17T12:00:00Z")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn curl_config_arrives_on_an_inherited_pipe_with_stdin_free() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "cat \"$2\"; cat", "sh"]);
+        let _config = attach_config(&mut cmd, "user = \"k:s\"\n").unwrap();
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"body").unwrap();
+        drop(stdin);
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(output.stdout, b"user = \"k:s\"\nbody");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tar_stream_and_unpack_round_trip_with_checksum() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("target");
+        fs::create_dir_all(src.join("debug")).unwrap();
+        fs::write(src.join("debug/out.o"), vec![3_u8; 5_000]).unwrap();
+        let (sha256, bytes) = digest_tree(&src).unwrap();
+        let tarball = tmp.path().join("a.tar");
+        let (mut tar, mut stdout) = spawn_tar(&src).unwrap();
+        std::io::copy(&mut stdout, &mut File::create(&tarball).unwrap()).unwrap();
+        wait_tar(&mut tar, &src).unwrap();
+        assert_eq!(bytes, fs::metadata(&tarball).unwrap().len());
+
+        let bad = tmp.path().join("restore/bad");
+        fs::create_dir_all(tmp.path().join("restore")).unwrap();
+        let err = unpack(File::open(&tarball).unwrap(), &bad, "00").unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+        assert!(!bad.exists());
+
+        let dest = tmp.path().join("restore/target");
+        unpack(File::open(&tarball).unwrap(), &dest, &sha256).unwrap();
+        assert_eq!(
+            fs::read(dest.join("debug/out.o")).unwrap(),
+            vec![3_u8; 5_000]
+        );
+        assert!(!tmp.path().join("restore/.sbh-restore-target").exists());
+    }
+}
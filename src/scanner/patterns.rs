@@ -5,6 +5,9 @@
 
 use std::borrow::Cow;
 use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 
 /// High-level artifact category used by the scorer and CLI reports.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,12 +19,82 @@ pub enum ArtifactCategory {
     CacheDir,
     TempDir,
     AgentWorkspace,
+    /// Single-file process core dump (`core`, `core.<pid>`, `*.core`).
+    CoreDump,
+    /// Single-file virtual machine disk image.
+    VmImage,
+    /// Single large log file.
+    LogFile,
+    /// Compiled GPU kernel cache (Triton, torch inductor, TensorRT engines,
+    /// NVIDIA `ComputeCache`). Safe to delete, but rebuilt slowly by jobs
+    /// that are still running.
+    GpuCache,
     Unknown,
 }
 
+impl ArtifactCategory {
+    /// Parse the snake_case name used in config files (`"log_file"`, `"core_dump"`).
+    #[must_use]
+    pub fn from_config_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "rust_target" => Self::RustTarget,
+            "node_modules" => Self::NodeModules,
+            "python_cache" => Self::PythonCache,
+            "build_output" => Self::BuildOutput,
+            "cache_dir" => Self::CacheDir,
+            "temp_dir" => Self::TempDir,
+            "agent_workspace" => Self::AgentWorkspace,
+            "core_dump" => Self::CoreDump,
+            "vm_image" => Self::VmImage,
+            "log_file" => Self::LogFile,
+            "gpu_cache" => Self::GpuCache,
+            _ => return None,
+        })
+    }
+
+    /// The snake_case config name; inverse of [`Self::from_config_name`].
+    #[must_use]
+    pub const fn config_name(self) -> &'static str {
+        match self {
+            Self::RustTarget => "rust_target",
+            Self::NodeModules => "node_modules",
+            Self::PythonCache => "python_cache",
+            Self::BuildOutput => "build_output",
+            Self::CacheDir => "cache_dir",
+            Self::TempDir => "temp_dir",
+            Self::AgentWorkspace => "agent_workspace",
+            Self::CoreDump => "core_dump",
+            Self::VmImage => "vm_image",
+            Self::LogFile => "log_file",
+            Self::GpuCache => "gpu_cache",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Whether a held-open file of this category may be truncated in place.
+    /// Only categories whose contents are disposable qualify.
+    #[must_use]
+    pub const fn supports_truncation(self) -> bool {
+        matches!(self, Self::LogFile | Self::CoreDump)
+    }
+
+    /// Category floor on candidate age, on top of `min_file_age_minutes`.
+    ///
+    /// A training run recompiles every kernel it loses, so GPU caches must sit
+    /// unused for a while before they qualify, even under pressure.
+    #[must_use]
+    pub const fn min_age(self) -> Duration {
+        match self {
+            Self::GpuCache => Duration::from_hours(2),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
 /// Structural features collected from a directory tree.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StructuralSignals {
     pub has_incremental: bool,
     pub has_deps: bool,
@@ -73,8 +146,9 @@ impl ArtifactClassification {
 enum MatchKind {
     Exact(&'static str),
     Prefix(&'static str),
-    #[allow(dead_code)] // variant exists for future pattern use
     Suffix(&'static str),
+    /// Prefix followed only by ASCII digits (e.g. `core.12345`).
+    NumberedPrefix(&'static str),
     #[allow(dead_code)] // match arm exists but no patterns use this variant yet
     Contains(&'static str),
 }
@@ -106,6 +180,7 @@ struct NormalizedCustomPattern {
 #[derive(Debug, Clone)]
 pub struct ArtifactPatternRegistry {
     builtins: Vec<ArtifactPattern>,
+    file_builtins: Vec<ArtifactPattern>,
     custom: Vec<NormalizedCustomPattern>,
 }
 
@@ -113,6 +188,7 @@ impl Default for ArtifactPatternRegistry {
     fn default() -> Self {
         Self {
             builtins: builtin_patterns(),
+            file_builtins: file_patterns(),
             custom: Vec::new(),
         }
     }
@@ -131,6 +207,53 @@ impl ArtifactPatternRegistry {
         self
     }
 
+    /// Classify a walker entry: directories by [`Self::classify`], regular
+    /// files (emitted by large-file discovery) by [`Self::classify_file`].
+    #[must_use]
+    pub fn classify_entry(
+        &self,
+        path: &Path,
+        is_dir: bool,
+        signals: StructuralSignals,
+    ) -> ArtifactClassification {
+        if is_dir {
+            self.classify(path, signals)
+        } else {
+            self.classify_file(path)
+        }
+    }
+
+    /// Classify a single large file by name.
+    ///
+    /// Files only reach the scanner once they pass the walker's large-file
+    /// size threshold, so patterns here are names alone (`*.log` means "a
+    /// log of at least that size"). Directory patterns never apply to files.
+    #[must_use]
+    pub fn classify_file(&self, path: &Path) -> ArtifactClassification {
+        let Some(name_os) = path.file_name() else {
+            return ArtifactClassification::unknown();
+        };
+        let normalized = name_os.to_string_lossy().to_lowercase();
+        let Some(pattern) = self
+            .file_builtins
+            .iter()
+            .filter(|p| matches_builtin(p.kind, &normalized))
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+        else {
+            return ArtifactClassification::unknown();
+        };
+        let structural = structural_score(pattern.category, StructuralSignals::default());
+        ArtifactClassification {
+            pattern_name: Cow::Borrowed(pattern.name),
+            category: pattern.category,
+            name_confidence: pattern.confidence,
+            structural_confidence: structural,
+            combined_confidence: 0.70f64
+                .mul_add(pattern.confidence, 0.30 * structural)
+                .clamp(0.0, 1.0),
+        }
+    }
+
     /// Classify one path name with optional structural evidence.
     #[must_use]
     pub fn classify(&self, path: &Path, signals: StructuralSignals) -> ArtifactClassification {
@@ -206,6 +329,9 @@ fn matches_builtin(kind: MatchKind, normalized: &str) -> bool {
         MatchKind::Exact(token) => normalized == token,
         MatchKind::Prefix(token) => normalized.starts_with(token),
         MatchKind::Suffix(token) => normalized.ends_with(token),
+        MatchKind::NumberedPrefix(token) => normalized
+            .strip_prefix(token)
+            .is_some_and(|rest| !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit())),
         MatchKind::Contains(token) => normalized.contains(token),
     }
 }
@@ -230,7 +356,8 @@ fn structural_score(category: ArtifactCategory, signals: StructuralSignals) -> f
                 0.40
             }
         }
-        ArtifactCategory::NodeModules => 0.80,
+        // Kernel caches are regenerated on demand and never hold sources.
+        ArtifactCategory::NodeModules | ArtifactCategory::GpuCache => 0.80,
         ArtifactCategory::PythonCache => 0.75,
         ArtifactCategory::BuildOutput | ArtifactCategory::CacheDir | ArtifactCategory::TempDir => {
             if signals.mostly_object_files {
@@ -240,6 +367,11 @@ fn structural_score(category: ArtifactCategory, signals: StructuralSignals) -> f
             }
         }
         ArtifactCategory::AgentWorkspace => 0.78,
+        // Files carry no layout evidence; these reflect how disposable the
+        // file type is once it is large and old.
+        ArtifactCategory::CoreDump => 0.85,
+        ArtifactCategory::LogFile => 0.60,
+        ArtifactCategory::VmImage => 0.30,
         ArtifactCategory::Unknown => {
             if signals.has_fingerprint || (signals.has_incremental && signals.has_deps) {
                 0.75
@@ -385,6 +517,20 @@ fn builtin_patterns() -> Vec<ArtifactPattern> {
             confidence: 0.90,
             category: ArtifactCategory::BuildOutput,
         },
+        // Bazel output bases (`~/.cache/bazel/_bazel_<user>`) and sccache's
+        // local store; both refetch cheaply when a remote cache is configured.
+        ArtifactPattern {
+            name: "bazel-output-base",
+            kind: MatchKind::Prefix("_bazel_"),
+            confidence: 0.85,
+            category: ArtifactCategory::BuildOutput,
+        },
+        ArtifactPattern {
+            name: "sccache",
+            kind: MatchKind::Exact("sccache"),
+            confidence: 0.82,
+            category: ArtifactCategory::CacheDir,
+        },
         ArtifactPattern {
             name: "python-pycache",
             kind: MatchKind::Exact("__pycache__"),
@@ -477,6 +623,93 @@ fn builtin_patterns() -> Vec<ArtifactPattern> {
             confidence: 0.88,
             category: ArtifactCategory::RustTarget,
         },
+        // GPU toolchain kernel caches; ML agents fill these fast.
+        ArtifactPattern {
+            name: "torchinductor",
+            kind: MatchKind::Prefix("torchinductor_"),
+            confidence: 0.90,
+            category: ArtifactCategory::GpuCache,
+        },
+        ArtifactPattern {
+            name: "triton-cache",
+            kind: MatchKind::Exact(".triton"),
+            confidence: 0.86,
+            category: ArtifactCategory::GpuCache,
+        },
+        ArtifactPattern {
+            name: "torch-tensorrt-engine-cache",
+            kind: MatchKind::Prefix("torch_tensorrt_engine_cache"),
+            confidence: 0.88,
+            category: ArtifactCategory::GpuCache,
+        },
+        ArtifactPattern {
+            name: "tensorrt-cache",
+            kind: MatchKind::Prefix("tensorrt_cache"),
+            confidence: 0.84,
+            category: ArtifactCategory::GpuCache,
+        },
+        ArtifactPattern {
+            name: "nv-compute-cache",
+            kind: MatchKind::Exact("computecache"),
+            confidence: 0.88,
+            category: ArtifactCategory::GpuCache,
+        },
+        ArtifactPattern {
+            name: "torch-extensions",
+            kind: MatchKind::Exact("torch_extensions"),
+            confidence: 0.84,
+            category: ArtifactCategory::GpuCache,
+        },
+    ]
+}
+
+/// Name patterns for single files surfaced by large-file discovery.
+fn file_patterns() -> Vec<ArtifactPattern> {
+    vec![
+        ArtifactPattern {
+            name: "core-dump-pid",
+            kind: MatchKind::NumberedPrefix("core."),
+            confidence: 0.88,
+            category: ArtifactCategory::CoreDump,
+        },
+        ArtifactPattern {
+            name: "core-dump-suffix",
+            kind: MatchKind::Suffix(".core"),
+            confidence: 0.85,
+            category: ArtifactCategory::CoreDump,
+        },
+        ArtifactPattern {
+            name: "core-dump",
+            kind: MatchKind::Exact("core"),
+            confidence: 0.80,
+            category: ArtifactCategory::CoreDump,
+        },
+        ArtifactPattern {
+            name: "large-log",
+            kind: MatchKind::Suffix(".log"),
+            confidence: 0.70,
+            category: ArtifactCategory::LogFile,
+        },
+        // VM images may be the only copy of a guest; keep them low so they
+        // need age and size to qualify.
+        ArtifactPattern {
+            name: "vm-image-qcow2",
+            kind: MatchKind::Suffix(".qcow2"),
+            confidence: 0.55,
+            category: ArtifactCategory::VmImage,
+        },
+        ArtifactPattern {
+            name: "vm-image-vmdk",
+            kind: MatchKind::Suffix(".vmdk"),
+            confidence: 0.55,
+            category: ArtifactCategory::VmImage,
+        },
+        ArtifactPattern {
+            name: "vm-image-vdi",
+            kind: MatchKind::Suffix(".vdi"),
+            confidence: 0.55,
+            category: ArtifactCategory::VmImage,
+        },
     ]
 }
 
@@ -541,6 +774,9 @@ pub fn extract_pattern_label(path: &str) -> String {
     if lower == "node_modules" {
         return "node_modules/".to_string();
     }
+    if lower.starts_with("torchinductor_") {
+        return "torchinductor_*".to_string();
+    }
 
     // Fallback: use the directory name.
     name.to_string()
@@ -642,4 +878,79 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn gpu_kernel_caches_classify_with_an_age_floor() {
+        let registry = ArtifactPatternRegistry::default();
+        for name in [
+            "torchinductor_alice",
+            ".triton",
+            "torch_tensorrt_engine_cache",
+            "ComputeCache",
+            "torch_extensions",
+        ] {
+            let got = registry.classify(Path::new(name), StructuralSignals::default());
+            assert_eq!(got.category, ArtifactCategory::GpuCache, "{name}");
+            assert!(got.combined_confidence > 0.80, "{name}");
+        }
+        assert_eq!(
+            ArtifactCategory::from_config_name("gpu_cache"),
+            Some(ArtifactCategory::GpuCache)
+        );
+        assert!(ArtifactCategory::GpuCache.min_age() >= std::time::Duration::from_secs(3600));
+        assert!(ArtifactCategory::RustTarget.min_age().is_zero());
+    }
+
+    #[test]
+    fn remote_cacheable_outputs_classify_and_config_names_round_trip() {
+        let registry = ArtifactPatternRegistry::default();
+        let bazel = registry.classify(Path::new("_bazel_alice"), StructuralSignals::default());
+        assert_eq!(bazel.pattern_name, "bazel-output-base");
+        assert_eq!(bazel.category, ArtifactCategory::BuildOutput);
+        let sccache = registry.classify(Path::new("sccache"), StructuralSignals::default());
+        assert_eq!(sccache.pattern_name, "sccache");
+        assert_eq!(sccache.category, ArtifactCategory::CacheDir);
+
+        for category in [
+            ArtifactCategory::RustTarget,
+            ArtifactCategory::BuildOutput,
+            ArtifactCategory::CacheDir,
+            ArtifactCategory::GpuCache,
+        ] {
+            assert_eq!(
+                ArtifactCategory::from_config_name(category.config_name()),
+                Some(category)
+            );
+        }
+    }
+
+    #[test]
+    fn large_file_patterns_classify_files_only() {
+        let registry = ArtifactPatternRegistry::default();
+        for (name, category) in [
+            ("core", ArtifactCategory::CoreDump),
+            ("core.48213", ArtifactCategory::CoreDump),
+            ("firefox.core", ArtifactCategory::CoreDump),
+            ("build-output.log", ArtifactCategory::LogFile),
+            ("ubuntu-24.04.qcow2", ArtifactCategory::VmImage),
+            ("win11.vmdk", ArtifactCategory::VmImage),
+        ] {
+            let got = registry.classify_file(Path::new(name));
+            assert_eq!(got.category, category, "{name}");
+            assert!(got.combined_confidence > 0.0, "{name}");
+        }
+        for name in ["core.tar.gz", "core.", "report.pdf", "target"] {
+            let got = registry.classify_file(Path::new(name));
+            assert_eq!(got.category, ArtifactCategory::Unknown, "{name}");
+        }
+        // Directory classification never sees file patterns, and vice versa.
+        let dir = registry.classify_entry(Path::new("app.log"), true, StructuralSignals::default());
+        assert_eq!(dir.category, ArtifactCategory::Unknown);
+        let file = registry.classify_entry(
+            Path::new("node_modules"),
+            false,
+            StructuralSignals::default(),
+        );
+        assert_eq!(file.category, ArtifactCategory::Unknown);
+    }
 }
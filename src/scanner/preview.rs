@@ -0,0 +1,229 @@
+//! Candidate content previews for human review.
+//!
+//! Before approving a deletion, an operator wants to see that a candidate
+//! really holds what its name suggests: object files and rlibs in a
+//! `target/`, packages in a `node_modules/`. A preview reads a bounded,
+//! breadth-first sample of the tree (so every top-level subdirectory gets a
+//! look before anything deep) and reports the largest files seen plus a
+//! histogram of file types. It is only gathered for items actually being
+//! reviewed, never during a scan.
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// Entries read before a preview stops and reports itself truncated.
+pub const PREVIEW_MAX_ENTRIES: usize = 5_000;
+
+/// Largest files listed in a preview.
+pub const PREVIEW_TOP_FILES: usize = 5;
+
+/// File types listed in a preview histogram; the rest fold into "other".
+pub const PREVIEW_TOP_TYPES: usize = 6;
+
+/// Histogram key for files without an extension.
+pub const NO_EXTENSION: &str = "(none)";
+
+/// A bounded sample of what a candidate contains.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ContentPreview {
+    /// Regular files read.
+    pub files: u64,
+    /// Directories read (the candidate itself included).
+    pub dirs: u64,
+    /// Bytes across the files read.
+    pub bytes: u64,
+    /// Stopped at the entry budget; counts cover only part of the tree.
+    pub truncated: bool,
+    /// Largest files read, biggest first, relative to the candidate.
+    pub largest: Vec<PreviewFile>,
+    /// Files per lowercase extension, most bytes first.
+    pub types: Vec<TypeCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreviewFile {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeCount {
+    /// Lowercase extension without the dot, or [`NO_EXTENSION`].
+    pub extension: String,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+impl ContentPreview {
+    /// Sample `path` within the default entry budget.
+    pub fn gather(path: &Path) -> io::Result<Self> {
+        Self::gather_with_budget(path, PREVIEW_MAX_ENTRIES)
+    }
+
+    /// Sample `path`, reading at most `max_entries` directory entries.
+    ///
+    /// Symlinks are never followed. Unreadable subdirectories are skipped;
+    /// only a failure to stat `path` itself is an error.
+    pub fn gather_with_budget(path: &Path, max_entries: usize) -> io::Result<Self> {
+        let meta = fs::symlink_metadata(path)?;
+        let mut preview = Self::default();
+        let mut largest: Vec<PreviewFile> = Vec::new();
+        let mut types: HashMap<String, TypeCount> = HashMap::new();
+
+        if !meta.is_dir() {
+            let name = path.file_name().map_or_else(PathBuf::new, PathBuf::from);
+            preview.record_file(&name, meta.len(), &mut largest, &mut types);
+            return Ok(preview.finish(largest, types));
+        }
+
+        let mut queue = VecDeque::from([path.to_path_buf()]);
+        let mut seen = 0usize;
+        'walk: while let Some(dir) = queue.pop_front() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            preview.dirs += 1;
+            for entry in entries.flatten() {
+                if seen >= max_entries {
+                    preview.truncated = true;
+                    break 'walk;
+                }
+                seen += 1;
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    queue.push_back(entry.path());
+                } else if file_type.is_file()
+                    && let Ok(meta) = entry.metadata()
+                {
+                    let child = entry.path();
+                    let relative = child.strip_prefix(path).unwrap_or(&child);
+                    preview.record_file(relative, meta.len(), &mut largest, &mut types);
+                }
+            }
+        }
+
+        Ok(preview.finish(largest, types))
+    }
+
+    fn record_file(
+        &mut self,
+        relative: &Path,
+        size_bytes: u64,
+        largest: &mut Vec<PreviewFile>,
+        types: &mut HashMap<String, TypeCount>,
+    ) {
+        self.files += 1;
+        self.bytes = self.bytes.saturating_add(size_bytes);
+
+        let extension = relative.extension().map_or_else(
+            || NO_EXTENSION.to_string(),
+            |ext| ext.to_string_lossy().to_lowercase(),
+        );
+        let count = types.entry(extension.clone()).or_insert(TypeCount {
+            extension,
+            files: 0,
+            bytes: 0,
+        });
+        count.files += 1;
+        count.bytes = count.bytes.saturating_add(size_bytes);
+
+        // Keep only the top few; a full sort per file is not worth it.
+        if largest.len() < PREVIEW_TOP_FILES
+            || largest
+                .last()
+                .is_some_and(|smallest| size_bytes > smallest.size_bytes)
+        {
+            largest.push(PreviewFile {
+                path: relative.to_path_buf(),
+                size_bytes,
+            });
+            largest.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.path.cmp(&b.path)));
+            largest.truncate(PREVIEW_TOP_FILES);
+        }
+    }
+
+    fn finish(mut self, largest: Vec<PreviewFile>, types: HashMap<String, TypeCount>) -> Self {
+        self.largest = largest;
+        let mut types: Vec<TypeCount> = types.into_values().collect();
+        types.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then(b.files.cmp(&a.files))
+                .then(a.extension.cmp(&b.extension))
+        });
+        if types.len() > PREVIEW_TOP_TYPES {
+            let rest = types.split_off(PREVIEW_TOP_TYPES - 1);
+            types.push(TypeCount {
+                extension: "other".to_string(),
+                files: rest.iter().map(|t| t.files).sum(),
+                bytes: rest.iter().map(|t| t.bytes).sum(),
+            });
+        }
+        self.types = types;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, len: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn preview_lists_largest_files_and_type_histogram() {
+        let tmp = TempDir::new().unwrap();
+        let target = tmp.path().join("target");
+        write(&target.join("debug/deps/libfoo.rlib"), 5000);
+        write(&target.join("debug/deps/foo.o"), 3000);
+        write(&target.join("debug/deps/bar.o"), 1000);
+        write(&target.join("debug/deps/foo.d"), 10);
+        write(&target.join(".cargo-lock"), 5);
+
+        let preview = ContentPreview::gather(&target).unwrap();
+        assert_eq!(preview.files, 5);
+        assert_eq!(preview.bytes, 9015);
+        assert!(!preview.truncated);
+        assert_eq!(
+            preview.largest[0].path,
+            PathBuf::from("debug/deps/libfoo.rlib")
+        );
+        assert_eq!(preview.largest.len(), 5);
+        let kinds: Vec<(&str, u64)> = preview
+            .types
+            .iter()
+            .map(|t| (t.extension.as_str(), t.files))
+            .collect();
+        assert_eq!(kinds, [("rlib", 1), ("o", 2), ("d", 1), (NO_EXTENSION, 1)]);
+    }
+
+    #[test]
+    fn preview_stops_at_budget_and_covers_a_single_file() {
+        let tmp = TempDir::new().unwrap();
+        for i in 0..20 {
+            write(&tmp.path().join(format!("pkg-{i}/index.js")), 10);
+        }
+        let preview = ContentPreview::gather_with_budget(tmp.path(), 25).unwrap();
+        assert!(preview.truncated);
+        assert!(preview.files < 20);
+
+        let core = tmp.path().join("core.4242");
+        write(&core, 64);
+        let preview = ContentPreview::gather(&core).unwrap();
+        assert_eq!(preview.files, 1);
+        assert_eq!(preview.largest[0].path, PathBuf::from("core.4242"));
+        assert_eq!(preview.types[0].extension, "4242");
+    }
+}
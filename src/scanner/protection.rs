@@ -8,7 +8,7 @@
 
 #![allow(missing_docs)]
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
@@ -17,6 +17,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::core::errors::{Result, SbhError};
+use crate::scanner::walker::{allocated_bytes, device_id};
 
 /// Filename placed in directories to protect them from sbh cleanup.
 pub const MARKER_FILENAME: &str = ".sbh-protect";
@@ -49,13 +50,80 @@ pub enum ProtectionSource {
     ConfigPattern(String),
 }
 
+/// Disk usage under one root, split by the protection covering it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectionCoverage {
+    /// Bytes allocated on disk by everything walked.
+    pub total_bytes: u64,
+    /// Directories walked, the root included.
+    pub total_dirs: u64,
+    /// One entry per protection that covers anything, largest first.
+    pub protected: Vec<CoveredProtection>,
+}
+
+impl ProtectionCoverage {
+    /// Bytes under any protection.
+    #[must_use]
+    pub fn protected_bytes(&self) -> u64 {
+        self.protected.iter().map(|p| p.bytes).sum()
+    }
+
+    /// Directories under any protection.
+    #[must_use]
+    pub fn protected_dirs(&self) -> u64 {
+        self.protected.iter().map(|p| p.dirs).sum()
+    }
+}
+
+/// What a single protection shields under a root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoveredProtection {
+    /// Marker directory, or the pattern for a config protection.
+    pub path: PathBuf,
+    pub source: ProtectionSource,
+    pub bytes: u64,
+    pub dirs: u64,
+}
+
 /// Compiled glob pattern for path matching.
 #[derive(Debug, Clone)]
-struct GlobPattern {
+pub struct GlobPattern {
     original: String,
     compiled: Regex,
 }
 
+impl GlobPattern {
+    /// Compile a shell-style glob (see [`ProtectionRegistry::new`]).
+    pub fn new(pattern: &str) -> Result<Self> {
+        Ok(Self {
+            original: pattern.to_string(),
+            compiled: glob_to_regex(pattern)?,
+        })
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// Whether the glob matches `path`, also tried as a directory with a
+    /// trailing `/` so `**/release/**` covers `.../release` itself.
+    #[must_use]
+    pub fn matches(&self, path: &Path) -> bool {
+        let p_str = normalize_path_for_matching(path);
+        self.compiled.is_match(&p_str) || self.compiled.is_match(&format!("{p_str}/"))
+    }
+
+    /// Whether the glob [`matches`](Self::matches) `path` or one of its
+    /// ancestors.
+    #[must_use]
+    pub fn matches_within(&self, path: &Path) -> bool {
+        path.ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| self.matches(p))
+    }
+}
+
 /// Registry of protected paths from marker files and config-level glob patterns.
 ///
 /// The registry supports two modes:
@@ -77,13 +145,7 @@ impl ProtectionRegistry {
         let compiled = match config_patterns {
             Some(patterns) => patterns
                 .iter()
-                .map(|pat| {
-                    let re = glob_to_regex(pat)?;
-                    Ok(GlobPattern {
-                        original: pat.clone(),
-                        compiled: re,
-                    })
-                })
+                .map(|pat| GlobPattern::new(pat))
                 .collect::<Result<Vec<_>>>()?,
             None => Vec::new(),
         };
@@ -193,6 +255,84 @@ impl ProtectionRegistry {
         Ok(found)
     }
 
+    /// Walk `root` without leaving its filesystem and attribute every byte
+    /// and directory to the protection covering it, if any.
+    ///
+    /// Markers met on the way are registered. A subtree counts towards the
+    /// outermost protection over it; unreadable directories are skipped.
+    pub fn measure_coverage(&mut self, root: &Path) -> Result<ProtectionCoverage> {
+        let root = normalize_path_for_protection(root);
+        let device = fs::symlink_metadata(&root)
+            .map(|meta| device_id(&meta))
+            .map_err(|source| SbhError::Io {
+                path: root.clone(),
+                source,
+            })?;
+        let mut coverage = ProtectionCoverage::default();
+        let mut covered = HashMap::<PathBuf, CoveredProtection>::new();
+        let mut queue: Vec<(PathBuf, Option<PathBuf>)> = vec![(root, None)];
+
+        while let Some((dir, inherited)) = queue.pop() {
+            if fs::symlink_metadata(dir.join(MARKER_FILENAME)).is_ok() {
+                self.register_marker(&dir);
+            }
+            let shield = inherited.or_else(|| {
+                let (path, source) = self.protecting_entry(&dir)?;
+                covered
+                    .entry(path.clone())
+                    .or_insert_with(|| CoveredProtection {
+                        path: path.clone(),
+                        source,
+                        bytes: 0,
+                        dirs: 0,
+                    });
+                Some(path)
+            });
+
+            let mut bytes = 0u64;
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    let Ok(meta) = fs::symlink_metadata(entry.path()) else {
+                        continue;
+                    };
+                    if meta.is_dir() {
+                        if device_id(&meta) == device {
+                            queue.push((entry.path(), shield.clone()));
+                        }
+                    } else {
+                        bytes = bytes.saturating_add(allocated_bytes(&meta));
+                    }
+                }
+            }
+
+            coverage.total_dirs += 1;
+            coverage.total_bytes = coverage.total_bytes.saturating_add(bytes);
+            if let Some(entry) = shield.as_ref().and_then(|path| covered.get_mut(path)) {
+                entry.dirs += 1;
+                entry.bytes = entry.bytes.saturating_add(bytes);
+            }
+        }
+
+        coverage.protected = covered.into_values().collect();
+        coverage
+            .protected
+            .sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+        Ok(coverage)
+    }
+
+    /// The marker directory or config pattern protecting `path`, if any.
+    fn protecting_entry(&self, path: &Path) -> Option<(PathBuf, ProtectionSource)> {
+        if let Some(marker_dir) = self.find_marker_ancestor(path) {
+            return Some((marker_dir.clone(), ProtectionSource::MarkerFile));
+        }
+        self.config_pattern_for(path).map(|pattern| {
+            (
+                PathBuf::from(&pattern.original),
+                ProtectionSource::ConfigPattern(pattern.original.clone()),
+            )
+        })
+    }
+
     /// Register a single marker directory (used when walker encounters a marker
     /// during normal traversal, without full discovery).
     pub fn register_marker(&mut self, dir: &Path) -> bool {
@@ -258,8 +398,12 @@ impl ProtectionRegistry {
     }
 
     fn matches_config_pattern(&self, path: &Path) -> bool {
+        self.config_pattern_for(path).is_some()
+    }
+
+    fn config_pattern_for(&self, path: &Path) -> Option<&GlobPattern> {
         if self.config_patterns.is_empty() {
-            return false;
+            return None;
         }
         // Check the path itself and all its ancestor prefixes so that
         // a pattern protecting "/data/projects/production-app" also
@@ -267,16 +411,16 @@ impl ProtectionRegistry {
         let mut current = Some(path);
         while let Some(p) = current {
             let p_str = normalize_path_for_matching(p);
-            if self
+            if let Some(pattern) = self
                 .config_patterns
                 .iter()
-                .any(|pat| pat.compiled.is_match(&p_str))
+                .find(|pat| pat.compiled.is_match(&p_str))
             {
-                return true;
+                return Some(pattern);
             }
             current = p.parent();
         }
-        false
+        None
     }
 }
 
@@ -616,6 +760,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn coverage_splits_bytes_by_protection() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        for (dir, len) in [
+            ("kept/src", 64 * 1024),
+            ("prod-app", 32 * 1024),
+            ("scratch", 16 * 1024),
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("data.bin"), vec![7u8; len]).unwrap();
+        }
+        create_marker(&root.join("kept"), None).unwrap();
+        let pattern = format!("{}/prod-*", root.display());
+        let mut reg = ProtectionRegistry::new(Some(std::slice::from_ref(&pattern))).unwrap();
+
+        let coverage = reg.measure_coverage(&root).unwrap();
+
+        assert_eq!(coverage.total_dirs, 5);
+        assert_eq!(coverage.protected.len(), 2);
+        let kept = &coverage.protected[0];
+        assert_eq!(kept.path, root.join("kept"));
+        assert_eq!(kept.source, ProtectionSource::MarkerFile);
+        assert_eq!(kept.dirs, 2);
+        let prod = &coverage.protected[1];
+        assert_eq!(prod.source, ProtectionSource::ConfigPattern(pattern));
+        assert_eq!(prod.dirs, 1);
+        assert!(kept.bytes > prod.bytes);
+        assert!(coverage.total_bytes > coverage.protected_bytes());
+        assert!(reg.is_protected(&root.join("kept/src")));
+    }
+
     #[test]
     fn glob_star_matches_within_component() {
         let patterns = vec!["/tmp/cargo-target-*".to_string()];
@@ -658,6 +834,18 @@ mod tests {
         assert!(!reg.is_protected(Path::new(r"C:\Users\alice\other-builds")));
     }
 
+    #[test]
+    fn glob_pattern_matches_a_path_or_its_ancestors() {
+        let release = GlobPattern::new("**/release/**").unwrap();
+        assert!(release.matches_within(Path::new("/w/proj/target/release")));
+        assert!(release.matches_within(Path::new("/w/proj/target/release/deps")));
+        assert!(!release.matches_within(Path::new("/w/proj/target/debug")));
+
+        let target = GlobPattern::new("/w/*/target").unwrap();
+        assert!(target.matches_within(Path::new("/w/proj/target/debug")));
+        assert!(!target.matches_within(Path::new("/w/proj/sub/target")));
+    }
+
     #[test]
     fn marker_file_with_json_metadata_is_read() {
         let tmp = TempDir::new().unwrap();
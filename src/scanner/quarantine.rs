@@ -0,0 +1,642 @@
+//! Quarantine: stage deletion candidates instead of removing them, so there
+//! is an undo window before the space is reclaimed.
+//!
+//! A quarantined tree is renamed, never copied, into
+//! `<quarantine>/<id>/<name>`. It stays on its own filesystem and the move is
+//! atomic. Each entry records its original path and expiry in an append-only
+//! index inside the quarantine. `sbh quarantine restore` renames a tree back.
+//! The daemon purges entries once they expire, and purges a whole quarantine
+//! when its mount reaches orange pressure.
+//!
+//! The quarantine is `.sbh-quarantine/` at the mount root when that is
+//! writable. Otherwise it goes in the topmost writable directory above the
+//! candidate on the same filesystem, e.g. `$HOME` on `/` for an
+//! unprivileged user.
+//!
+//! With an object store configured, a tree moved in is then streamed to
+//! the store (see [`crate::scanner::object_store`]) and removed locally;
+//! only its index line stays. Such an entry expires with the bucket's
+//! lifecycle rule instead of the quarantine TTL, restores by download, and
+//! is purged by deleting the object. Pressure purges skip it, since
+//! deleting it frees nothing locally.
+
+#![allow(missing_docs)]
+
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+use crate::core::state::device_id;
+use crate::scanner::archive;
+use crate::scanner::deletion::is_writable;
+use crate::scanner::object_store::{ObjectStore, StoredObject};
+use crate::scanner::patterns::ArtifactCategory;
+
+/// Name of the per-mount staging directory. The walker never descends into it.
+pub const QUARANTINE_DIRNAME: &str = ".sbh-quarantine";
+/// Index of quarantined trees, one JSON object per line.
+pub const INDEX_FILENAME: &str = "index.jsonl";
+
+/// One quarantined tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub id: String,
+    pub original_path: PathBuf,
+    pub quarantined_path: PathBuf,
+    pub size_bytes: u64,
+    pub category: String,
+    /// RFC 3339 time the tree was moved in.
+    pub quarantined_at: String,
+    /// RFC 3339 time after which the daemon purges it.
+    pub expires_at: String,
+    /// Set when the tree was offloaded to an object store; nothing is left
+    /// at `quarantined_path` then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<StoredObject>,
+}
+
+impl QuarantineEntry {
+    /// Directory holding this entry's tree (`<quarantine>/<id>`).
+    #[must_use]
+    pub fn entry_dir(&self) -> &Path {
+        self.quarantined_path
+            .parent()
+            .unwrap_or(self.quarantined_path.as_path())
+    }
+
+    /// [`Self::entry_dir`], provided the index line names a single-component
+    /// id directly under `root` and a tree inside that directory. The index
+    /// is plain JSON, so a damaged or planted line must not steer a purge or
+    /// restore at anything outside the quarantine.
+    fn checked_entry_dir(&self, root: &Path) -> Result<&Path> {
+        let mut id = Path::new(&self.id).components();
+        let plain_id = matches!((id.next(), id.next()), (Some(Component::Normal(_)), None));
+        let dir = self.entry_dir();
+        if plain_id && self.quarantined_path.file_name().is_some() && dir == root.join(&self.id) {
+            Ok(dir)
+        } else {
+            Err(SbhError::SafetyVeto {
+                path: self.quarantined_path.clone(),
+                reason: format!("index entry {:?} is not inside {}", self.id, root.display()),
+            })
+        }
+    }
+
+    /// Whether `key` is this entry's id or the path it was quarantined from.
+    #[must_use]
+    pub fn matches(&self, key: &str) -> bool {
+        self.id == key || self.original_path == Path::new(key)
+    }
+
+    /// Whether the entry is past its expiry. An unparseable expiry never
+    /// expires, so a damaged index line cannot trigger a purge.
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.expires_at).is_ok_and(|expires| expires <= now)
+    }
+
+    /// Where the tree is kept: its object URL once offloaded, otherwise
+    /// `quarantined_path`.
+    #[must_use]
+    pub fn stored_at(&self) -> String {
+        self.object.as_ref().map_or_else(
+            || self.quarantined_path.to_string_lossy().into_owned(),
+            |object| object.url.clone(),
+        )
+    }
+
+    /// Bytes the entry holds on local disk: none once offloaded.
+    #[must_use]
+    pub const fn local_bytes(&self) -> u64 {
+        if self.object.is_some() {
+            0
+        } else {
+            self.size_bytes
+        }
+    }
+}
+
+/// Entries removed by [`purge`], and those whose removal failed.
+#[derive(Debug, Clone, Default)]
+pub struct PurgeReport {
+    pub purged: Vec<QuarantineEntry>,
+    pub failed: Vec<(QuarantineEntry, String)>,
+}
+
+impl PurgeReport {
+    /// Local disk space reclaimed; offloaded entries free none.
+    #[must_use]
+    pub fn bytes_freed(&self) -> u64 {
+        self.purged.iter().map(QuarantineEntry::local_bytes).sum()
+    }
+}
+
+/// Quarantine directory for `path`: `.sbh-quarantine` in the topmost
+/// writable ancestor on the same filesystem. `None` when no ancestor is
+/// writable.
+#[must_use]
+pub fn quarantine_root(path: &Path) -> Option<PathBuf> {
+    // Off Unix there are no device ids and every path counts as one
+    // filesystem.
+    let device = device_id(path);
+    if device.is_none() && cfg!(unix) {
+        return None;
+    }
+    let mut best = None;
+    for dir in path.ancestors().skip(1) {
+        if device_id(dir) != device {
+            break;
+        }
+        if is_writable(dir) {
+            best = Some(dir);
+        }
+    }
+    best.map(|dir| dir.join(QUARANTINE_DIRNAME))
+}
+
+/// Move `src` into its mount's quarantine until `ttl` has passed, offloading
+/// it to `offload` when given.
+pub fn quarantine(
+    src: &Path,
+    category: ArtifactCategory,
+    size_bytes: u64,
+    ttl: Duration,
+    offload: Option<&ObjectStore>,
+) -> Result<QuarantineEntry> {
+    let root = quarantine_root(src).ok_or_else(|| SbhError::Runtime {
+        details: format!(
+            "no writable directory for a quarantine above {}",
+            src.display()
+        ),
+    })?;
+    quarantine_in(&root, src, category, size_bytes, ttl, offload)
+}
+
+/// Move `src` into the quarantine at `root`. The entry is indexed before
+/// this returns; if the upload or the index write fails the tree is moved
+/// back, since an unindexed tree would never be purged.
+///
+/// With `offload`, the tree is streamed to the store, and the local copy is
+/// removed only once the entry is indexed.
+pub fn quarantine_in(
+    root: &Path,
+    src: &Path,
+    category: ArtifactCategory,
+    size_bytes: u64,
+    ttl: Duration,
+    offload: Option<&ObjectStore>,
+) -> Result<QuarantineEntry> {
+    if src.starts_with(root) {
+        return Err(SbhError::SafetyVeto {
+            path: src.to_path_buf(),
+            reason: "already in quarantine".to_string(),
+        });
+    }
+    let name = src.file_name().ok_or_else(|| SbhError::Runtime {
+        details: format!("cannot quarantine {}: no file name", src.display()),
+    })?;
+    create_private_dir(root)?;
+    let id = archive::entry_id(src);
+    let entry_dir = root.join(&id);
+    fs::create_dir(&entry_dir).map_err(|e| SbhError::io(&entry_dir, e))?;
+    let quarantined_path = entry_dir.join(name);
+    if let Err(e) = fs::rename(src, &quarantined_path) {
+        let _ = fs::remove_dir(&entry_dir);
+        return Err(SbhError::io(src, e));
+    }
+
+    let move_back = || {
+        if fs::rename(&quarantined_path, src).is_ok() {
+            let _ = fs::remove_dir(&entry_dir);
+        }
+    };
+    let object = match offload.map(|store| store.put_tree(&quarantined_path, &id)) {
+        Some(Err(err)) => {
+            move_back();
+            return Err(err);
+        }
+        Some(Ok(object)) => Some(object),
+        None => None,
+    };
+
+    let now = Utc::now();
+    // An offloaded tree lasts as long as the bucket keeps it.
+    let expires = object.as_ref().map_or_else(
+        || {
+            chrono::Duration::from_std(ttl)
+                .ok()
+                .and_then(|ttl| now.checked_add_signed(ttl))
+        },
+        |object| {
+            object
+                .expires_at
+                .as_deref()
+                .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+                .map(|at| at.with_timezone(&Utc))
+        },
+    );
+    let expires = expires.unwrap_or(DateTime::<Utc>::MAX_UTC);
+    let entry = QuarantineEntry {
+        id,
+        original_path: src.to_path_buf(),
+        quarantined_path: quarantined_path.clone(),
+        size_bytes,
+        category: category.config_name().to_string(),
+        quarantined_at: now.to_rfc3339_opts(SecondsFormat::Millis, true),
+        expires_at: expires.to_rfc3339_opts(SecondsFormat::Millis, true),
+        object,
+    };
+    if let Err(err) = append_index(root, &entry) {
+        if let Some(object) = &entry.object {
+            let _ = object.delete();
+        }
+        move_back();
+        return Err(err);
+    }
+    if entry.object.is_some() {
+        // A leftover is still removed by the next purge of this entry.
+        let _ = fs::remove_dir_all(&entry_dir);
+    }
+    Ok(entry)
+}
+
+/// All entries in the quarantine at `root`, oldest first. Unparseable lines
+/// are skipped.
+pub fn read_index(root: &Path) -> Result<Vec<QuarantineEntry>> {
+    let path = root.join(INDEX_FILENAME);
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(SbhError::io(&path, err)),
+    };
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(std::io::Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+/// Find an entry by id or by the path it was quarantined from; the most
+/// recent quarantine of a path wins.
+#[must_use]
+pub fn find_entry<'a>(entries: &'a [QuarantineEntry], key: &str) -> Option<&'a QuarantineEntry> {
+    entries.iter().rev().find(|entry| entry.matches(key))
+}
+
+/// Move a quarantined tree back to `dest`, downloading it if it was
+/// offloaded, and drop it from the index. Refuses to overwrite anything
+/// already at `dest`, and an object the bucket lifecycle has expired.
+pub fn restore(root: &Path, entry: &QuarantineEntry, dest: &Path) -> Result<()> {
+    check_root(root)?;
+    let entry_dir = entry.checked_entry_dir(root)?;
+    if fs::symlink_metadata(dest).is_ok() {
+        return Err(SbhError::SafetyVeto {
+            path: dest.to_path_buf(),
+            reason: "restore target already exists".to_string(),
+        });
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+    }
+    if let Some(object) = &entry.object {
+        if object.is_expired(Utc::now()) {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "{} expired from the object store at {}",
+                    entry.id,
+                    object.expires_at.as_deref().unwrap_or("unknown")
+                ),
+            });
+        }
+        object.fetch_into(dest)?;
+        let _ = object.delete();
+        let _ = fs::remove_dir_all(entry_dir);
+    } else {
+        fs::rename(&entry.quarantined_path, dest).map_err(|e| SbhError::io(dest, e))?;
+        let _ = fs::remove_dir(entry_dir);
+    }
+
+    let remaining: Vec<QuarantineEntry> = read_index(root)?
+        .into_iter()
+        .filter(|other| other.id != entry.id)
+        .collect();
+    rewrite_index(root, &remaining)
+}
+
+/// Delete every entry at `root` that `select` picks and rewrite the index
+/// with the rest. An offloaded entry's object is deleted from the bucket.
+/// Entries that fail to delete stay indexed for a retry.
+pub fn purge(root: &Path, select: impl Fn(&QuarantineEntry) -> bool) -> Result<PurgeReport> {
+    check_root(root)?;
+    let mut report = PurgeReport::default();
+    let mut kept = Vec::new();
+    for entry in read_index(root)? {
+        if !select(&entry) {
+            kept.push(entry);
+            continue;
+        }
+        let dir = match entry.checked_entry_dir(root) {
+            Ok(dir) => dir,
+            Err(err) => {
+                report.failed.push((entry.clone(), err.to_string()));
+                kept.push(entry);
+                continue;
+            }
+        };
+        if let Some(object) = &entry.object
+            && let Err(err) = object.delete()
+        {
+            report.failed.push((entry.clone(), err.to_string()));
+            kept.push(entry);
+            continue;
+        }
+        match fs::remove_dir_all(dir) {
+            Ok(()) => report.purged.push(entry),
+            // Already gone (removed by hand): just drop it from the index.
+            Err(err) if err.kind() == ErrorKind::NotFound => report.purged.push(entry),
+            Err(err) => {
+                report.failed.push((entry.clone(), err.to_string()));
+                kept.push(entry);
+            }
+        }
+    }
+    if !report.purged.is_empty() {
+        rewrite_index(root, &kept)?;
+    }
+    Ok(report)
+}
+
+/// Existing quarantines at or above any of `dirs`, deduplicated.
+#[must_use]
+pub fn discover_roots<'a>(dirs: impl IntoIterator<Item = &'a Path>) -> Vec<PathBuf> {
+    let mut roots = BTreeSet::new();
+    for dir in dirs {
+        for ancestor in dir.ancestors() {
+            let candidate = ancestor.join(QUARANTINE_DIRNAME);
+            if candidate.is_dir() {
+                roots.insert(candidate);
+            }
+        }
+    }
+    roots.into_iter().collect()
+}
+
+fn append_index(root: &Path, entry: &QuarantineEntry) -> Result<()> {
+    let path = root.join(INDEX_FILENAME);
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| SbhError::io(&path, e))
+}
+
+fn rewrite_index(root: &Path, entries: &[QuarantineEntry]) -> Result<()> {
+    archive::write_index(&root.join(INDEX_FILENAME), entries)
+}
+
+/// Quarantined trees keep their owners' permissions, but the staging
+/// directory itself is only listable by its creator. An existing directory
+/// must pass [`check_root`].
+fn create_private_dir(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        match fs::DirBuilder::new().mode(0o700).create(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => check_root(path),
+            Err(err) => Err(SbhError::io(path, err)),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        fs::create_dir_all(path).map_err(|e| SbhError::io(path, e))
+    }
+}
+
+/// Refuse a quarantine that is not a real directory owned by this user with
+/// mode 0700. Quarantines are found by looking for `.sbh-quarantine` above
+/// watched paths, so anyone who can create one there could otherwise feed
+/// the daemon an index of directories to delete.
+fn check_root(root: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let meta = fs::symlink_metadata(root).map_err(|e| SbhError::io(root, e))?;
+        let euid = nix::unistd::geteuid().as_raw();
+        if !meta.is_dir() || meta.uid() != euid || meta.permissions().mode() & 0o777 != 0o700 {
+            return Err(SbhError::SafetyVeto {
+                path: root.to_path_buf(),
+                reason: format!(
+                    "quarantine must be a directory owned by uid {euid} with mode 0700"
+                ),
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = root;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn project(tmp: &Path, name: &str) -> PathBuf {
+        let target = tmp.join(name).join("target");
+        fs::create_dir_all(target.join("debug")).unwrap();
+        fs::write(target.join("debug/app"), vec![1_u8; 512]).unwrap();
+        target
+    }
+
+    #[test]
+    fn quarantine_and_restore_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIRNAME);
+        let target = project(tmp.path(), "app");
+
+        let entry = quarantine_in(
+            &root,
+            &target,
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap();
+        assert!(!target.exists());
+        assert!(entry.quarantined_path.join("debug/app").exists());
+        assert!(!entry.is_expired(Utc::now()));
+        assert_eq!(read_index(&root).unwrap(), vec![entry.clone()]);
+        assert_eq!(
+            find_entry(std::slice::from_ref(&entry), &target.to_string_lossy()),
+            Some(&entry)
+        );
+
+        restore(&root, &entry, &target).unwrap();
+        assert_eq!(fs::read(target.join("debug/app")).unwrap().len(), 512);
+        assert!(!entry.entry_dir().exists());
+        assert!(read_index(&root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn purge_removes_selected_entries_and_keeps_the_rest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIRNAME);
+        let old = quarantine_in(
+            &root,
+            &project(tmp.path(), "old"),
+            ArtifactCategory::RustTarget,
+            512,
+            Duration::ZERO,
+            None,
+        )
+        .unwrap();
+        let fresh = quarantine_in(
+            &root,
+            &project(tmp.path(), "fresh"),
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap();
+
+        let report = purge(&root, |entry| entry.is_expired(Utc::now())).unwrap();
+        assert_eq!(report.purged, vec![old.clone()]);
+        assert_eq!(report.bytes_freed(), 512);
+        assert!(!old.entry_dir().exists());
+        assert_eq!(read_index(&root).unwrap(), vec![fresh.clone()]);
+
+        let report = purge(&root, |_| true).unwrap();
+        assert_eq!(report.purged, vec![fresh]);
+        assert!(read_index(&root).unwrap().is_empty());
+        assert_eq!(
+            discover_roots([tmp.path().join("old").as_path()]),
+            vec![root]
+        );
+    }
+
+    #[test]
+    fn purge_refuses_foreign_roots_and_entries_outside_the_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIRNAME);
+        let entry = quarantine_in(
+            &root,
+            &project(tmp.path(), "app"),
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap();
+
+        // A planted line naming a directory outside the quarantine.
+        let victim = project(tmp.path(), "victim");
+        let planted = QuarantineEntry {
+            id: "../app".to_string(),
+            quarantined_path: victim.clone(),
+            ..entry.clone()
+        };
+        append_index(&root, &planted).unwrap();
+        let report = purge(&root, |_| true).unwrap();
+        assert_eq!(report.purged, vec![entry]);
+        assert_eq!(report.failed.len(), 1);
+        assert!(victim.join("debug/app").exists());
+        assert!(restore(&root, &planted, &tmp.path().join("elsewhere")).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&root, fs::Permissions::from_mode(0o777)).unwrap();
+            let err = purge(&root, |_| true).unwrap_err();
+            assert!(matches!(err, SbhError::SafetyVeto { .. }));
+            assert!(victim.join("debug/app").exists());
+        }
+    }
+
+    #[test]
+    fn offloaded_entries_hold_no_local_bytes_and_expired_objects_stay_put() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIRNAME);
+        let target = project(tmp.path(), "app");
+        let local = quarantine_in(
+            &root,
+            &target,
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap();
+        // Index lines written before offloading existed have no object.
+        assert!(!serde_json::to_string(&local).unwrap().contains("object"));
+
+        let offloaded = QuarantineEntry {
+            object: Some(StoredObject {
+                url: "https://minio.internal:9000/cold/sbh-quarantine/a.tar".to_string(),
+                region: "us-east-1".to_string(),
+                sha256: String::new(),
+                bytes: 300,
+                expires_at: Some("2026-01-01T00:00:00.000Z".to_string()),
+            }),
+            ..local.clone()
+        };
+        assert_eq!((local.local_bytes(), offloaded.local_bytes()), (512, 0));
+        let report = PurgeReport {
+            purged: vec![local, offloaded.clone()],
+            failed: Vec::new(),
+        };
+        assert_eq!(report.bytes_freed(), 512);
+
+        let dest = tmp.path().join("restored");
+        let err = restore(&root, &offloaded, &dest).unwrap_err();
+        assert!(err.to_string().contains("expired from the object store"));
+        assert!(!dest.exists());
+        assert_eq!(read_index(&root).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn restore_never_overwrites_and_quarantine_refuses_itself() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIRNAME);
+        let target = project(tmp.path(), "app");
+        let entry = quarantine_in(
+            &root,
+            &target,
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap();
+
+        fs::create_dir_all(&target).unwrap();
+        let err = restore(&root, &entry, &target).unwrap_err();
+        assert!(matches!(err, SbhError::SafetyVeto { .. }));
+        assert!(entry.quarantined_path.exists());
+
+        let err = quarantine_in(
+            &root,
+            &entry.quarantined_path,
+            ArtifactCategory::RustTarget,
+            512,
+            HOUR,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SbhError::SafetyVeto { .. }));
+    }
+}
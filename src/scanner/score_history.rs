@@ -0,0 +1,327 @@
+//! Per-path score history for hysteresis between scan passes.
+//!
+//! A path whose score hovers around `scoring.min_score` can qualify for
+//! deletion on one pass and miss on the next. Acting on the first qualifying
+//! pass turns that noise into churn. `ScoreHistory` remembers the last few
+//! scores per path and how many consecutive passes the path qualified. The
+//! daemon only dispatches a candidate once that streak reaches
+//! `scoring.stability_scans` and the mean of the retained scores is also at
+//! or above `min_score`, so a single spike after a run of low scores is not
+//! enough. The history is persisted as JSON next to
+//! `state.json`, so a daemon restart does not reset every streak.
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+
+/// Scores kept per path for smoothing.
+pub const MAX_RECENT_SCORES: usize = 8;
+/// Passes a path may go unobserved before its history is dropped.
+pub const IDLE_PASSES_BEFORE_PRUNE: u64 = 16;
+/// Hard cap on tracked paths; the least recently observed are evicted first.
+pub const MAX_TRACKED_PATHS: usize = 50_000;
+
+/// File name of the persisted history, placed beside `paths.state_file`.
+pub const SCORE_HISTORY_FILE_NAME: &str = "score_history.json";
+
+/// Recent observations of one path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PathScoreHistory {
+    /// Most recent scores, oldest first.
+    pub recent: VecDeque<f64>,
+    /// Consecutive observed passes in which the path qualified for deletion.
+    pub qualifying_streak: usize,
+    /// Pass number of the latest observation.
+    pub last_pass: u64,
+}
+
+impl PathScoreHistory {
+    /// Mean of the retained scores.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn smoothed(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        Some(self.recent.iter().sum::<f64>() / self.recent.len() as f64)
+    }
+}
+
+/// Whether a qualifying candidate has been stable long enough to act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StabilityVerdict {
+    Stable,
+    Pending { streak: usize, required: usize },
+}
+
+impl StabilityVerdict {
+    #[must_use]
+    pub const fn is_stable(self) -> bool {
+        matches!(self, Self::Stable)
+    }
+}
+
+/// Score history for every path the scanner has recently scored as a candidate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreHistory {
+    pass: u64,
+    paths: HashMap<PathBuf, PathScoreHistory>,
+}
+
+impl ScoreHistory {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default location: `score_history.json` in the state file's directory.
+    #[must_use]
+    pub fn path_for_state_file(state_file: &Path) -> PathBuf {
+        state_file.with_file_name(SCORE_HISTORY_FILE_NAME)
+    }
+
+    /// Load persisted history. A missing file yields an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(SbhError::io(path, e)),
+        }
+    }
+
+    /// Persist atomically: write to `.json.tmp`, then rename over `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+        }
+        let json = serde_json::to_string(self)?;
+        let tmp_path = path.with_extension("json.tmp");
+        let result = (|| {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&tmp_path)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, path)
+        })();
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(SbhError::io(path, e));
+        }
+        Ok(())
+    }
+
+    /// Start a new scan pass. Call once before the pass's first [`Self::observe`].
+    pub const fn begin_pass(&mut self) {
+        self.pass += 1;
+    }
+
+    /// Record `score` for `path` in the current pass and report whether the
+    /// path has now qualified for `required` consecutive passes with a
+    /// smoothed score of at least `min_score`.
+    ///
+    /// Non-qualifying paths without prior history are not tracked, so the
+    /// file only grows with paths that were candidates at least once. A
+    /// repeated observation within one pass does not extend the streak.
+    pub fn observe(
+        &mut self,
+        path: &Path,
+        score: f64,
+        qualifies: bool,
+        required: usize,
+        min_score: f64,
+    ) -> StabilityVerdict {
+        let required = required.max(1);
+        let pass = self.pass;
+        let entry = if qualifies {
+            self.paths.entry(path.to_path_buf()).or_default()
+        } else if let Some(entry) = self.paths.get_mut(path) {
+            entry
+        } else {
+            return StabilityVerdict::Pending {
+                streak: 0,
+                required,
+            };
+        };
+
+        if entry.last_pass != pass || entry.recent.is_empty() {
+            entry.recent.push_back(score);
+            if entry.recent.len() > MAX_RECENT_SCORES {
+                entry.recent.pop_front();
+            }
+            entry.qualifying_streak = if qualifies {
+                entry.qualifying_streak.saturating_add(1)
+            } else {
+                0
+            };
+            entry.last_pass = pass;
+        }
+
+        if qualifies
+            && entry.qualifying_streak >= required
+            && entry.smoothed().is_some_and(|mean| mean >= min_score)
+        {
+            StabilityVerdict::Stable
+        } else {
+            StabilityVerdict::Pending {
+                streak: entry.qualifying_streak,
+                required,
+            }
+        }
+    }
+
+    /// Drop paths idle for too long and enforce [`MAX_TRACKED_PATHS`].
+    ///
+    /// Paths skipped by a pass (budget cut-off, root timeout) keep their
+    /// streak; only an observed non-qualifying score resets it.
+    pub fn finish_pass(&mut self) {
+        let pass = self.pass;
+        self.paths
+            .retain(|_, h| pass.saturating_sub(h.last_pass) <= IDLE_PASSES_BEFORE_PRUNE);
+        if self.paths.len() > MAX_TRACKED_PATHS {
+            let mut by_age: Vec<(u64, PathBuf)> = self
+                .paths
+                .iter()
+                .map(|(p, h)| (h.last_pass, p.clone()))
+                .collect();
+            by_age.sort_unstable();
+            let excess = self.paths.len() - MAX_TRACKED_PATHS;
+            for (_, path) in by_age.into_iter().take(excess) {
+                self.paths.remove(&path);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&PathScoreHistory> {
+        self.paths.get(path)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run_pass(history: &mut ScoreHistory, path: &Path, score: f64, qualifies: bool) -> bool {
+        history.begin_pass();
+        let verdict = history.observe(path, score, qualifies, 3, 0.5);
+        history.finish_pass();
+        verdict.is_stable()
+    }
+
+    #[test]
+    fn flapping_path_never_becomes_stable() {
+        let mut history = ScoreHistory::new();
+        let path = Path::new("/data/projects/a/target");
+        for i in 0..10 {
+            let qualifies = i % 2 == 0;
+            assert!(!run_pass(&mut history, path, 0.36, qualifies));
+        }
+        let entry = history.get(path).unwrap();
+        assert_eq!(entry.recent.len(), MAX_RECENT_SCORES);
+        assert!(entry.qualifying_streak <= 1);
+    }
+
+    #[test]
+    fn stable_after_required_consecutive_passes_and_reset_on_miss() {
+        let mut history = ScoreHistory::new();
+        let path = Path::new("/data/projects/b/target");
+        assert!(!run_pass(&mut history, path, 0.8, true));
+        assert!(!run_pass(&mut history, path, 0.7, true));
+        assert!(run_pass(&mut history, path, 0.9, true));
+        assert!((history.get(path).unwrap().smoothed().unwrap() - 0.8).abs() < 1e-9);
+
+        assert!(!run_pass(&mut history, path, 0.2, false));
+        assert_eq!(history.get(path).unwrap().qualifying_streak, 0);
+        assert!(!run_pass(&mut history, path, 0.8, true));
+    }
+
+    #[test]
+    fn low_smoothed_score_holds_a_fresh_streak() {
+        let mut history = ScoreHistory::new();
+        let path = Path::new("/data/projects/c/target");
+        assert!(!run_pass(&mut history, path, 0.55, true));
+        for _ in 0..4 {
+            assert!(!run_pass(&mut history, path, 0.1, false));
+        }
+        // Three qualifying passes meet the streak, but the mean is still low.
+        assert!(!run_pass(&mut history, path, 0.9, true));
+        assert!(!run_pass(&mut history, path, 0.9, true));
+        assert!(!run_pass(&mut history, path, 0.9, true));
+        assert!(history.get(path).unwrap().smoothed().unwrap() < 0.5);
+        assert!(run_pass(&mut history, path, 0.9, true));
+    }
+
+    #[test]
+    fn repeat_observation_in_one_pass_does_not_extend_streak() {
+        let mut history = ScoreHistory::new();
+        let path = Path::new("/tmp/cargo-target-1");
+        history.begin_pass();
+        for _ in 0..5 {
+            assert!(!history.observe(path, 0.9, true, 2, 0.5).is_stable());
+        }
+        assert_eq!(history.get(path).unwrap().qualifying_streak, 1);
+    }
+
+    #[test]
+    fn non_candidates_are_not_tracked_and_idle_paths_are_pruned() {
+        let mut history = ScoreHistory::new();
+        run_pass(&mut history, Path::new("/tmp/never"), 0.1, false);
+        assert!(history.is_empty());
+
+        run_pass(&mut history, Path::new("/tmp/once"), 0.9, true);
+        for _ in 0..=IDLE_PASSES_BEFORE_PRUNE {
+            history.begin_pass();
+            history.finish_pass();
+        }
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn history_round_trips_through_disk() {
+        let tmp = TempDir::new().unwrap();
+        let file = ScoreHistory::path_for_state_file(&tmp.path().join("state.json"));
+        assert_eq!(ScoreHistory::load(&file).unwrap(), ScoreHistory::new());
+
+        let mut history = ScoreHistory::new();
+        run_pass(&mut history, Path::new("/tmp/x/node_modules"), 0.75, true);
+        history.save(&file).unwrap();
+        assert!(!file.with_extension("json.tmp").exists());
+
+        let mut loaded = ScoreHistory::load(&file).unwrap();
+        assert_eq!(loaded, history);
+        assert!(!run_pass(
+            &mut loaded,
+            Path::new("/tmp/x/node_modules"),
+            0.8,
+            true
+        ));
+        assert_eq!(
+            loaded
+                .get(Path::new("/tmp/x/node_modules"))
+                .unwrap()
+                .qualifying_streak,
+            2
+        );
+    }
+}
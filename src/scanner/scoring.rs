@@ -6,12 +6,19 @@
 
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::path::{Component, Path, PathBuf};
 use std::time::Duration;
 
-use crate::core::config::ScoringConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::{CostsConfig, ScoringConfig};
 use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
 
+/// Bumped whenever the engine can score the same inputs and config
+/// differently, so a re-score can tell a code change from a config change.
+pub const SCORING_ENGINE_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ScoringWeights {
     pub location: f64,
@@ -29,6 +36,9 @@ pub struct ScoreFactors {
     pub size: f64,
     pub structure: f64,
     pub pressure_multiplier: f64,
+    /// Regeneration cost relative to `false_positive_loss` (0.0 = free
+    /// re-fetch, 1.0 = full local rebuild or dearer).
+    pub regeneration_cost: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -70,16 +80,22 @@ pub struct CandidacyScore {
     pub vetoed: bool,
     pub veto_reason: Option<Cow<'static, str>>,
     pub classification: ArtifactClassification,
+    /// Apparent size: what `du --apparent-size` would report.
     pub size_bytes: u64,
+    /// Allocated size: what deleting the candidate frees on disk.
+    pub disk_bytes: u64,
     pub age: Duration,
     pub decision: DecisionOutcome,
     pub ledger: EvidenceLedger,
+    pub inputs: ScoringInputs,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CandidateInput {
     pub path: PathBuf,
     pub size_bytes: u64,
+    /// Allocated size (`st_blocks`); see [`CandidacyScore::disk_bytes`].
+    pub disk_bytes: u64,
     pub age: Duration,
     pub classification: ArtifactClassification,
     pub signals: StructuralSignals,
@@ -87,6 +103,56 @@ pub struct CandidateInput {
     pub excluded: bool,
 }
 
+/// Snapshot of everything a score was computed from: the candidate's
+/// [`CandidateInput`] minus its path, the urgency, and which engine and
+/// scoring parameters produced it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoringInputs {
+    /// [`SCORING_ENGINE_VERSION`] of the engine that scored it.
+    pub engine_version: u32,
+    /// [`ScoringEngine::config_hash`] of the parameters it was scored with.
+    pub config_hash: String,
+    pub urgency: f64,
+    pub size_bytes: u64,
+    /// Zero in records written before on-disk sizes were tracked.
+    #[serde(default)]
+    pub disk_bytes: u64,
+    pub age_secs: u64,
+    pub pattern_name: String,
+    /// Artifact category config name, e.g. `rust_target`.
+    pub category: String,
+    pub name_confidence: f64,
+    pub structural_confidence: f64,
+    pub combined_confidence: f64,
+    pub signals: StructuralSignals,
+    pub is_open: bool,
+    pub excluded: bool,
+}
+
+impl ScoringInputs {
+    /// The candidate input these were taken from, at `path`.
+    #[must_use]
+    pub fn to_candidate(&self, path: &Path) -> CandidateInput {
+        CandidateInput {
+            path: path.to_path_buf(),
+            size_bytes: self.size_bytes,
+            disk_bytes: self.disk_bytes,
+            age: Duration::from_secs(self.age_secs),
+            classification: ArtifactClassification {
+                pattern_name: Cow::Owned(self.pattern_name.clone()),
+                category: ArtifactCategory::from_config_name(&self.category)
+                    .unwrap_or(ArtifactCategory::Unknown),
+                name_confidence: self.name_confidence,
+                structural_confidence: self.structural_confidence,
+                combined_confidence: self.combined_confidence,
+            },
+            signals: self.signals,
+            is_open: self.is_open,
+            excluded: self.excluded,
+        }
+    }
+}
+
 /// Deterministic score engine with expected-loss decision layer.
 #[derive(Debug, Clone)]
 pub struct ScoringEngine {
@@ -96,8 +162,14 @@ pub struct ScoringEngine {
     false_positive_loss: f64,
     false_negative_loss: f64,
     calibration_floor: f64,
+    costs: CostsConfig,
+    config_hash: String,
 }
 
+/// Score boost for an artifact that costs nothing to regenerate; scaled down
+/// linearly as the relative regeneration cost approaches 1.0.
+const REFETCH_BOOST: f64 = 0.5;
+
 impl ScoringEngine {
     #[must_use]
     pub fn from_config(scoring: &ScoringConfig, min_file_age_minutes: u64) -> Self {
@@ -114,16 +186,83 @@ impl ScoringEngine {
             false_positive_loss: scoring.false_positive_loss,
             false_negative_loss: scoring.false_negative_loss,
             calibration_floor: scoring.calibration_floor,
+            costs: CostsConfig::default(),
+            config_hash: String::new(),
         }
+        .rehashed()
+    }
+
+    /// Price wrong deletions with the site's `[costs]` table.
+    #[must_use]
+    pub fn with_costs(mut self, costs: &CostsConfig) -> Self {
+        self.costs = costs.clone();
+        self.rehashed()
+    }
+
+    /// Hash of every parameter that feeds a score: weights, thresholds,
+    /// losses, minimum age, and costs. Two engines with the same hash and
+    /// [`SCORING_ENGINE_VERSION`] score the same inputs identically.
+    #[must_use]
+    pub fn config_hash(&self) -> &str {
+        &self.config_hash
+    }
+
+    fn rehashed(mut self) -> Self {
+        let canonical = format!(
+            "{:?}|{:?}|{}|{}|{}|{}|{:?}",
+            self.weights,
+            self.min_file_age,
+            self.min_score,
+            self.false_positive_loss,
+            self.false_negative_loss,
+            self.calibration_floor,
+            self.costs,
+        );
+        // FNV-1a, as `Config::stable_hash`.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in canonical.as_bytes() {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        self.config_hash = format!("{hash:016x}");
+        self
+    }
+
+    fn snapshot(&self, input: &CandidateInput, urgency: f64) -> ScoringInputs {
+        ScoringInputs {
+            engine_version: SCORING_ENGINE_VERSION,
+            config_hash: self.config_hash.clone(),
+            urgency,
+            size_bytes: input.size_bytes,
+            disk_bytes: input.disk_bytes,
+            age_secs: input.age.as_secs(),
+            pattern_name: input.classification.pattern_name.to_string(),
+            category: input.classification.category.config_name().to_string(),
+            name_confidence: input.classification.name_confidence,
+            structural_confidence: input.classification.structural_confidence,
+            combined_confidence: input.classification.combined_confidence,
+            signals: input.signals,
+            is_open: input.is_open,
+            excluded: input.excluded,
+        }
+    }
+
+    /// Loss of wrongly deleting this artifact: its `[costs]` entry, or the
+    /// flat `false_positive_loss` when unlisted.
+    fn delete_cost(&self, classification: &ArtifactClassification) -> f64 {
+        self.costs
+            .regeneration_cost(&classification.pattern_name, classification.category)
+            .map_or(self.false_positive_loss, |cost| cost.max(0.0))
     }
 
     /// Score one candidate deterministically.
     #[must_use]
     pub fn score_candidate(&self, input: &CandidateInput, urgency: f64) -> CandidacyScore {
         if let Some(reason) = self.veto_reason(input) {
-            return self.vetoed(input, reason);
+            return self.vetoed(input, reason, urgency);
         }
 
+        let delete_cost = self.delete_cost(&input.classification);
         let factors = ScoreFactors {
             location: factor_location(&input.path),
             name: factor_name(&input.path, &input.classification),
@@ -131,6 +270,7 @@ impl ScoringEngine {
             size: factor_size(input.size_bytes),
             structure: factor_structure(input.signals),
             pressure_multiplier: pressure_multiplier(urgency),
+            regeneration_cost: relative_cost(delete_cost, self.false_positive_loss),
         };
 
         let base = self.weights.structure.mul_add(
@@ -145,12 +285,13 @@ impl ScoringEngine {
                 ),
             ),
         );
-        let total = (base * factors.pressure_multiplier).clamp(0.0, 3.0);
+        let refetch_multiplier = REFETCH_BOOST.mul_add(1.0 - factors.regeneration_cost, 1.0);
+        let total = (base * factors.pressure_multiplier * refetch_multiplier).clamp(0.0, 3.0);
 
         let posterior_abandoned =
             posterior_from_score(total, input.classification.combined_confidence);
         let base_expected_loss_keep = posterior_abandoned * self.false_negative_loss;
-        let base_expected_loss_delete = (1.0 - posterior_abandoned) * self.false_positive_loss;
+        let base_expected_loss_delete = (1.0 - posterior_abandoned) * delete_cost;
         let calibration = calibration_score(input.classification.combined_confidence, factors);
         let fallback_active = calibration < self.calibration_floor;
         let uncertainty = epistemic_uncertainty(posterior_abandoned, calibration);
@@ -194,6 +335,7 @@ impl ScoringEngine {
             veto_reason: None,
             classification: input.classification.clone(),
             size_bytes: input.size_bytes,
+            disk_bytes: input.disk_bytes,
             age: input.age,
             decision: DecisionOutcome {
                 action,
@@ -204,6 +346,7 @@ impl ScoringEngine {
                 fallback_active,
             },
             ledger,
+            inputs: self.snapshot(input, urgency),
         }
     }
 
@@ -216,13 +359,7 @@ impl ScoringEngine {
             .iter()
             .map(|candidate| self.score_candidate(candidate, urgency))
             .collect::<Vec<_>>();
-        scores.sort_unstable_by(|left, right| {
-            right
-                .total_score
-                .partial_cmp(&left.total_score)
-                .unwrap_or(Ordering::Equal)
-                .then_with(|| left.path.cmp(&right.path))
-        });
+        scores.sort_unstable_by(rank_order);
         scores
     }
 
@@ -233,11 +370,14 @@ impl ScoringEngine {
         if is_system_path(&input.path) {
             return Some(Cow::Borrowed("system path is never deletable"));
         }
-        if input.age < self.min_file_age {
+        let min_age = self
+            .min_file_age
+            .max(input.classification.category.min_age());
+        if input.age < min_age {
             return Some(Cow::Owned(format!(
                 "age {}s below minimum {}s",
                 input.age.as_secs(),
-                self.min_file_age.as_secs()
+                min_age.as_secs()
             )));
         }
         if input.excluded {
@@ -249,7 +389,12 @@ impl ScoringEngine {
         None
     }
 
-    fn vetoed(&self, input: &CandidateInput, reason: Cow<'static, str>) -> CandidacyScore {
+    fn vetoed(
+        &self,
+        input: &CandidateInput,
+        reason: Cow<'static, str>,
+        urgency: f64,
+    ) -> CandidacyScore {
         CandidacyScore {
             path: input.path.clone(),
             total_score: 0.0,
@@ -260,17 +405,19 @@ impl ScoringEngine {
                 size: 0.0,
                 structure: 0.0,
                 pressure_multiplier: 1.0,
+                regeneration_cost: 1.0,
             },
             vetoed: true,
             veto_reason: Some(reason),
             classification: input.classification.clone(),
             size_bytes: input.size_bytes,
+            disk_bytes: input.disk_bytes,
             age: input.age,
             decision: DecisionOutcome {
                 action: DecisionAction::Keep,
                 posterior_abandoned: 0.0,
                 expected_loss_keep: 0.0,
-                expected_loss_delete: self.false_positive_loss,
+                expected_loss_delete: self.delete_cost(&input.classification),
                 calibration_score: 0.0,
                 fallback_active: true,
             },
@@ -278,10 +425,120 @@ impl ScoringEngine {
                 terms: Vec::new(),
                 summary: "hard veto applied".to_string(),
             },
+            inputs: self.snapshot(input, urgency),
         }
     }
 }
 
+/// Rank order used by [`ScoringEngine::score_batch`]: score descending, then
+/// path ascending for determinism. `Less` means "ranks higher".
+fn rank_order(left: &CandidacyScore, right: &CandidacyScore) -> Ordering {
+    right
+        .total_score
+        .partial_cmp(&left.total_score)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| left.path.cmp(&right.path))
+}
+
+/// Heap entry whose `Ord` puts the *worst*-ranked candidate on top, so a
+/// `BinaryHeap` of these is a min-heap over rank and evicts cheaply.
+struct WorstFirst(CandidacyScore);
+
+impl PartialEq for WorstFirst {
+    fn eq(&self, other: &Self) -> bool {
+        rank_order(&self.0, &other.0) == Ordering::Equal
+    }
+}
+
+impl Eq for WorstFirst {}
+
+impl PartialOrd for WorstFirst {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WorstFirst {
+    fn cmp(&self, other: &Self) -> Ordering {
+        rank_order(&self.0, &other.0)
+    }
+}
+
+/// Memory-bounded top-N selection over a stream of scored candidates.
+///
+/// Keeps at most `capacity` candidates in a min-heap keyed by rank; everything
+/// offered (kept or evicted) is folded into summary counters. Memory is
+/// O(capacity) and cost O(n log capacity) rather than a full O(n log n) sort
+/// of every candidate.
+pub struct TopCandidates {
+    capacity: usize,
+    heap: BinaryHeap<WorstFirst>,
+    offered: usize,
+    offered_bytes: u64,
+}
+
+impl TopCandidates {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: BinaryHeap::new(),
+            offered: 0,
+            offered_bytes: 0,
+        }
+    }
+
+    /// Offer a candidate; it is kept only if it ranks within the top `capacity`.
+    pub fn push(&mut self, score: CandidacyScore) {
+        self.offered += 1;
+        self.offered_bytes = self.offered_bytes.saturating_add(score.size_bytes);
+        if self.capacity == 0 {
+            return;
+        }
+        if self.heap.len() < self.capacity {
+            self.heap.push(WorstFirst(score));
+            return;
+        }
+        if let Some(mut worst) = self.heap.peek_mut()
+            && rank_order(&score, &worst.0) == Ordering::Less
+        {
+            *worst = WorstFirst(score);
+        }
+    }
+
+    /// Fold another selection (e.g. from a parallel worker) into this one.
+    pub fn merge(&mut self, other: Self) {
+        let offered = self.offered + other.offered;
+        let offered_bytes = self.offered_bytes.saturating_add(other.offered_bytes);
+        for WorstFirst(score) in other.heap {
+            self.push(score);
+        }
+        // `push` re-counted the retained entries; restore exact totals.
+        self.offered = offered;
+        self.offered_bytes = offered_bytes;
+    }
+
+    /// Number of candidates offered, including evicted ones.
+    #[must_use]
+    pub const fn offered(&self) -> usize {
+        self.offered
+    }
+
+    /// Total `size_bytes` of every candidate offered, including evicted ones.
+    #[must_use]
+    pub const fn offered_bytes(&self) -> u64 {
+        self.offered_bytes
+    }
+
+    /// Retained candidates, best first (same order as `score_batch`).
+    #[must_use]
+    pub fn into_sorted_vec(self) -> Vec<CandidacyScore> {
+        let mut kept: Vec<_> = self.heap.into_iter().map(|WorstFirst(s)| s).collect();
+        kept.sort_unstable_by(rank_order);
+        kept
+    }
+}
+
 fn factor_location(path: &Path) -> f64 {
     let raw = path.to_string_lossy();
     let text = if std::path::MAIN_SEPARATOR == '\\' {
@@ -388,6 +645,15 @@ fn factor_structure(signals: StructuralSignals) -> f64 {
     0.40
 }
 
+/// Regeneration cost as a fraction of the flat delete loss, capped at 1.0 so
+/// expensive artifacts keep the default score.
+fn relative_cost(cost: f64, false_positive_loss: f64) -> f64 {
+    if false_positive_loss <= 0.0 {
+        return 1.0;
+    }
+    (cost / false_positive_loss).clamp(0.0, 1.0)
+}
+
 fn pressure_multiplier(urgency: f64) -> f64 {
     // Piecewise linear function that scales the score based on system urgency.
     // - Low urgency (0.0 - 0.3): Gentle scaling (1.0x to 1.3x).
@@ -568,6 +834,12 @@ fn build_ledger(
             value: factors.pressure_multiplier,
             contribution: factors.pressure_multiplier,
         },
+        EvidenceTerm {
+            name: "regeneration_cost",
+            weight: REFETCH_BOOST,
+            value: factors.regeneration_cost,
+            contribution: REFETCH_BOOST.mul_add(1.0 - factors.regeneration_cost, 1.0),
+        },
         EvidenceTerm {
             name: "calibration",
             weight: 1.0,
@@ -661,8 +933,10 @@ fn is_system_path(path: &Path) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{CandidateInput, DecisionAction, ScoringEngine};
-    use crate::core::config::ScoringConfig;
+    use super::{
+        CandidateInput, DecisionAction, SCORING_ENGINE_VERSION, ScoringEngine, TopCandidates,
+    };
+    use crate::core::config::{CostsConfig, ScoringConfig};
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
     use std::borrow::Cow;
     use std::path::PathBuf;
@@ -689,6 +963,7 @@ mod tests {
             &CandidateInput {
                 path: PathBuf::from("/data/projects/repo/.git/objects"),
                 size_bytes: 1024,
+                disk_bytes: 1024,
                 age: Duration::from_secs(3600),
                 classification: classification(0.9, ArtifactCategory::RustTarget),
                 signals: StructuralSignals::default(),
@@ -702,6 +977,111 @@ mod tests {
         assert_eq!(score.decision.action, DecisionAction::Keep);
     }
 
+    #[test]
+    fn recorded_inputs_rescore_to_the_same_result() {
+        let engine = default_engine();
+        let input = CandidateInput {
+            path: PathBuf::from("/data/projects/app/target"),
+            size_bytes: 2 * 1_073_741_824,
+            disk_bytes: 2 * 1_073_741_824,
+            age: Duration::from_secs(6 * 3600),
+            classification: classification(0.85, ArtifactCategory::RustTarget),
+            signals: StructuralSignals {
+                has_incremental: true,
+                has_deps: true,
+                ..StructuralSignals::default()
+            },
+            is_open: false,
+            excluded: false,
+        };
+        let score = engine.score_candidate(&input, 0.6);
+        assert_eq!(score.inputs.engine_version, SCORING_ENGINE_VERSION);
+        assert_eq!(score.inputs.config_hash, engine.config_hash());
+
+        let json = serde_json::to_string(&score.inputs).unwrap();
+        let recorded: super::ScoringInputs = serde_json::from_str(&json).unwrap();
+        let rescored =
+            engine.score_candidate(&recorded.to_candidate(&input.path), recorded.urgency);
+        assert_eq!(rescored, score);
+
+        let reweighted = ScoringEngine::from_config(
+            &ScoringConfig {
+                age_weight: 0.5,
+                ..ScoringConfig::default()
+            },
+            30,
+        );
+        assert_ne!(reweighted.config_hash(), engine.config_hash());
+    }
+
+    #[test]
+    fn gpu_caches_need_their_category_age_floor() {
+        let engine = default_engine();
+        let input = |age_secs| CandidateInput {
+            path: PathBuf::from("/tmp/torchinductor_alice"),
+            size_bytes: 4 * 1_073_741_824,
+            disk_bytes: 4 * 1_073_741_824,
+            age: Duration::from_secs(age_secs),
+            classification: classification(0.9, ArtifactCategory::GpuCache),
+            signals: StructuralSignals::default(),
+            is_open: false,
+            excluded: false,
+        };
+
+        // Past the global 30-minute minimum, but a run may still be using it.
+        let fresh = engine.score_candidate(&input(3600), 0.8);
+        assert!(fresh.vetoed);
+        assert_eq!(
+            fresh.veto_reason.as_deref(),
+            Some("age 3600s below minimum 7200s")
+        );
+
+        let stale = engine.score_candidate(&input(3 * 3600), 0.8);
+        assert!(!stale.vetoed);
+    }
+
+    #[test]
+    fn costs_table_prices_deletion_per_category() {
+        let input = CandidateInput {
+            path: PathBuf::from("/home/ci/.cache/bazel/_bazel_ci"),
+            size_bytes: 8 * 1_073_741_824,
+            disk_bytes: 8 * 1_073_741_824,
+            age: Duration::from_secs(6 * 3600),
+            classification: classification(0.85, ArtifactCategory::BuildOutput),
+            signals: StructuralSignals::default(),
+            is_open: false,
+            excluded: false,
+        };
+        let baseline = default_engine().score_candidate(&input, 0.2);
+        assert!((baseline.factors.regeneration_cost - 1.0).abs() < f64::EPSILON);
+
+        // 5.0 against the default false_positive_loss of 50.0.
+        let mut costs = CostsConfig::default();
+        costs.0.insert("build_output".to_string(), 5.0);
+        let cheap = default_engine()
+            .with_costs(&costs)
+            .score_candidate(&input, 0.2);
+        assert!((cheap.factors.regeneration_cost - 0.1).abs() < 1e-9);
+        assert!(cheap.total_score > baseline.total_score);
+        assert!(cheap.decision.expected_loss_delete < baseline.decision.expected_loss_delete);
+        assert!(
+            cheap
+                .ledger
+                .terms
+                .iter()
+                .any(|term| term.name == "regeneration_cost")
+        );
+
+        // A pattern-name entry overrides its category; dearer-than-default
+        // artifacts keep their score but are costlier to get wrong.
+        costs.0.insert("test".to_string(), 120.0);
+        let dear = default_engine()
+            .with_costs(&costs)
+            .score_candidate(&input, 0.2);
+        assert!((dear.total_score - baseline.total_score).abs() < f64::EPSILON);
+        assert!(dear.decision.expected_loss_delete > baseline.decision.expected_loss_delete);
+    }
+
     #[test]
     fn system_paths_are_vetoed() {
         let engine = default_engine();
@@ -718,6 +1098,7 @@ mod tests {
                 &CandidateInput {
                     path: PathBuf::from(system_dir),
                     size_bytes: 1_073_741_824,
+                    disk_bytes: 1_073_741_824,
                     age: Duration::from_secs(6 * 3600),
                     classification: classification(0.95, ArtifactCategory::RustTarget),
                     signals: StructuralSignals::default(),
@@ -740,6 +1121,7 @@ mod tests {
             &CandidateInput {
                 path: PathBuf::from("/var/tmp"),
                 size_bytes: 4096,
+                disk_bytes: 4096,
                 age: Duration::from_secs(24 * 3600 * 30), // Old
                 classification: classification(0.0, ArtifactCategory::Unknown), // No pattern
                 signals: StructuralSignals::default(),
@@ -766,6 +1148,7 @@ mod tests {
                 &CandidateInput {
                     path: PathBuf::from(user_dir),
                     size_bytes: 1_073_741_824,
+                    disk_bytes: 1_073_741_824,
                     age: Duration::from_secs(6 * 3600),
                     classification: classification(0.95, ArtifactCategory::RustTarget),
                     signals: StructuralSignals::default(),
@@ -788,6 +1171,7 @@ mod tests {
             &CandidateInput {
                 path: PathBuf::from("/tmp/cargo-target-quietwillow"),
                 size_bytes: 5 * 1_073_741_824,
+                disk_bytes: 5 * 1_073_741_824,
                 age: Duration::from_secs(6 * 3600),
                 classification: classification(0.95, ArtifactCategory::RustTarget),
                 signals: StructuralSignals {
@@ -821,6 +1205,7 @@ mod tests {
         let input = CandidateInput {
             path: PathBuf::from("/tmp/cargo-target-same"),
             size_bytes: 2 * 1_073_741_824,
+            disk_bytes: 2 * 1_073_741_824,
             age: Duration::from_secs(5 * 3600),
             classification: classification(0.9, ArtifactCategory::RustTarget),
             signals: StructuralSignals {
@@ -848,6 +1233,7 @@ mod tests {
         let input = CandidateInput {
             path: PathBuf::from("/tmp/cargo-target-pressure"),
             size_bytes: 1_073_741_824,
+            disk_bytes: 1_073_741_824,
             age: Duration::from_secs(4 * 3600),
             classification: classification(0.9, ArtifactCategory::RustTarget),
             signals: StructuralSignals {
@@ -946,6 +1332,7 @@ mod tests {
         let input = CandidateInput {
             path: PathBuf::from("/data/projects/myapp/target"),
             size_bytes: 2 * 1_073_741_824,
+            disk_bytes: 2 * 1_073_741_824,
             age: Duration::from_secs(4 * 3600),
             classification: classification(0.85, ArtifactCategory::RustTarget),
             signals: StructuralSignals {
@@ -1028,6 +1415,7 @@ mod tests {
             let input = CandidateInput {
                 path: PathBuf::from(path),
                 size_bytes: *size,
+                disk_bytes: *size,
                 age: Duration::from_secs(*age_secs),
                 classification: classification(*confidence, ArtifactCategory::RustTarget),
                 signals: StructuralSignals {
@@ -1054,4 +1442,69 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn top_candidates_matches_full_sort_and_counts_everything() {
+        let engine = default_engine();
+        let inputs: Vec<CandidateInput> = (0..200u32)
+            .map(|i| CandidateInput {
+                path: PathBuf::from(format!("/data/projects/p{i}/target")),
+                size_bytes: u64::from(i % 17 + 1) * 1_000_000_000,
+                disk_bytes: u64::from(i % 17 + 1) * 1_000_000_000,
+                age: Duration::from_secs(3600 * u64::from(i % 48 + 1)),
+                classification: classification(
+                    0.5 + f64::from(i % 5) / 10.0,
+                    ArtifactCategory::RustTarget,
+                ),
+                signals: StructuralSignals::default(),
+                is_open: false,
+                excluded: false,
+            })
+            .collect();
+        let full = engine.score_batch(&inputs, 0.3);
+
+        // Split across two "workers" and merge, as the scan pipeline does.
+        let mut left = TopCandidates::new(10);
+        let mut right = TopCandidates::new(10);
+        for (i, input) in inputs.iter().enumerate() {
+            let score = engine.score_candidate(input, 0.3);
+            if i % 2 == 0 {
+                left.push(score);
+            } else {
+                right.push(score);
+            }
+        }
+        left.merge(right);
+
+        assert_eq!(left.offered(), 200);
+        assert_eq!(
+            left.offered_bytes(),
+            inputs.iter().map(|c| c.size_bytes).sum::<u64>()
+        );
+        let top: Vec<_> = left.into_sorted_vec().into_iter().map(|s| s.path).collect();
+        let expected: Vec<_> = full.into_iter().take(10).map(|s| s.path).collect();
+        assert_eq!(top, expected);
+    }
+
+    #[test]
+    fn top_candidates_zero_capacity_only_counts() {
+        let engine = default_engine();
+        let mut top = TopCandidates::new(0);
+        top.push(engine.score_candidate(
+            &CandidateInput {
+                path: PathBuf::from("/data/projects/a/target"),
+                size_bytes: 42,
+                disk_bytes: 42,
+                age: Duration::from_secs(7200),
+                classification: classification(0.9, ArtifactCategory::RustTarget),
+                signals: StructuralSignals::default(),
+                is_open: false,
+                excluded: false,
+            },
+            0.0,
+        ));
+        assert_eq!(top.offered(), 1);
+        assert_eq!(top.offered_bytes(), 42);
+        assert!(top.into_sorted_vec().is_empty());
+    }
 }
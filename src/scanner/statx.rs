@@ -0,0 +1,127 @@
+//! Minimal-mask `statx` metadata reads for the walker's reduced-syscall mode.
+//!
+//! `std::fs::metadata` asks the kernel for every field and forces attribute
+//! revalidation on network filesystems. The walker only needs a handful of
+//! fields, so these helpers request exactly those with `AT_STATX_DONT_SYNC`,
+//! which lets NFS/FUSE answer from cached attributes instead of a round trip.
+//! Callers fall back to the portable path on any error (e.g. `ENOSYS` on
+//! pre-4.11 kernels).
+
+#![allow(missing_docs)]
+
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use rustix::fs::{AtFlags, CWD, Statx, StatxFlags, StatxTimestamp};
+
+use crate::scanner::walker::EntryMetadata;
+
+const S_IFMT: u32 = 0o170_000;
+const S_IFDIR: u32 = 0o040_000;
+
+/// Fields needed to emit a `WalkEntry` and run the cross-device guard.
+const ENTRY_MASK: StatxFlags = StatxFlags::TYPE
+    .union(StatxFlags::MODE)
+    .union(StatxFlags::INO)
+    .union(StatxFlags::SIZE)
+    .union(StatxFlags::BLOCKS)
+    .union(StatxFlags::MTIME)
+    .union(StatxFlags::BTIME);
+
+fn statx_flags(follow_symlinks: bool) -> AtFlags {
+    let base = AtFlags::STATX_DONT_SYNC;
+    if follow_symlinks {
+        base
+    } else {
+        base | AtFlags::SYMLINK_NOFOLLOW
+    }
+}
+
+/// Apparent and allocated size in bytes of a (non-directory) child,
+/// requesting only `STATX_SIZE` and `STATX_BLOCKS`.
+pub fn file_size(path: &Path, follow_symlinks: bool) -> io::Result<(u64, u64)> {
+    let stx = rustix::fs::statx(
+        CWD,
+        path,
+        statx_flags(follow_symlinks),
+        StatxFlags::SIZE | StatxFlags::BLOCKS,
+    )?;
+    Ok((stx.stx_size, allocated(&stx)))
+}
+
+/// Entry metadata and device ID, requesting only the fields the walker uses.
+pub fn entry_metadata(path: &Path, follow_symlinks: bool) -> io::Result<(EntryMetadata, u64)> {
+    let stx = rustix::fs::statx(CWD, path, statx_flags(follow_symlinks), ENTRY_MASK)?;
+    let device_id = rustix::fs::makedev(stx.stx_dev_major, stx.stx_dev_minor);
+    Ok((metadata_from_statx(&stx, device_id), device_id))
+}
+
+fn metadata_from_statx(stx: &Statx, device_id: u64) -> EntryMetadata {
+    let returned = StatxFlags::from_bits_retain(stx.stx_mask);
+    let mode = u32::from(stx.stx_mode);
+    let modified = timestamp(&stx.stx_mtime).unwrap_or(SystemTime::UNIX_EPOCH);
+    // Birth time is optional: many filesystems (and all NFS) don't report it.
+    let created = returned
+        .contains(StatxFlags::BTIME)
+        .then(|| timestamp(&stx.stx_btime))
+        .flatten();
+    EntryMetadata {
+        size_bytes: stx.stx_size,
+        content_size_bytes: stx.stx_size,
+        disk_bytes: allocated(stx),
+        content_disk_bytes: allocated(stx),
+        modified,
+        created,
+        is_dir: mode & S_IFMT == S_IFDIR,
+        inode: stx.stx_ino,
+        device_id,
+        permissions: mode,
+    }
+}
+
+/// `stx_blocks` in bytes, or the apparent size when the filesystem did not
+/// report blocks.
+fn allocated(stx: &Statx) -> u64 {
+    if StatxFlags::from_bits_retain(stx.stx_mask).contains(StatxFlags::BLOCKS) {
+        stx.stx_blocks.saturating_mul(512)
+    } else {
+        stx.stx_size
+    }
+}
+
+fn timestamp(ts: &StatxTimestamp) -> Option<SystemTime> {
+    let secs = u64::try_from(ts.tv_sec).ok()?;
+    SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs, ts.tv_nsec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+    use tempfile::TempDir;
+
+    #[test]
+    fn statx_matches_std_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("blob.bin");
+        fs::write(&file, vec![0u8; 4321]).unwrap();
+
+        // statx may be unavailable (old kernel, seccomp); the walker falls back then.
+        let Ok(sizes) = file_size(&file, false) else {
+            return;
+        };
+        let std_file = fs::symlink_metadata(&file).unwrap();
+        assert_eq!(sizes, (4321, std_file.blocks() * 512));
+
+        let (meta, dev) = entry_metadata(tmp.path(), false).unwrap();
+        let std_meta = fs::symlink_metadata(tmp.path()).unwrap();
+        assert!(meta.is_dir);
+        assert_eq!(meta.inode, std_meta.ino());
+        assert_eq!(dev, std_meta.dev());
+        assert_eq!(meta.device_id, std_meta.dev());
+        assert_eq!(meta.modified, std_meta.modified().unwrap());
+        assert_eq!(meta.disk_bytes, std_meta.blocks() * 512);
+    }
+}
@@ -0,0 +1,565 @@
+//! Curated home-directory cache cleanup (`sbh clean --caches`).
+//!
+//! Tool caches under `~/.cache`, `~/.cargo`, `~/.npm`, and `~/.local/share`
+//! are not build artifacts: the scorer cannot tell a pnpm store from a
+//! project, and a raw delete is wrong for some of them.
+//!
+//! Registry and store caches prefer the tool's own garbage collector, which
+//! knows what is still referenced (`cargo cache --autoclean`, `npm cache clean`,
+//! `pnpm store prune`). It is used when the program is on `PATH` and enabled
+//! in `[caches]`. Otherwise the cache falls back to its direct cleanup:
+//!
+//! - `ClearContents`: everything inside is re-fetched on demand (pip wheels,
+//!   yarn tarballs, extracted cargo sources that are re-unpacked from
+//!   `registry/cache`).
+//! - `KeepNewestRevision`: versioned installs where only the newest revision of
+//!   each product is used (Playwright browsers).
+//! - `OlderThan`: large downloads worth keeping while in use (Hugging Face
+//!   model snapshots), and compiled GPU kernels (Triton, torch extensions)
+//!   that a running job would have to rebuild.
+//! - `LeaveAlone`: no safe direct cleanup exists (the pnpm store is hardlinked
+//!   into every `node_modules`, so deleting it frees almost nothing).
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use crate::core::config::CachesConfig;
+use crate::core::errors::{Result, SbhError};
+use crate::core::paths::find_on_path;
+
+/// Base directory a cache path is relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheBase {
+    /// `$XDG_CACHE_HOME`, default `~/.cache`.
+    Cache,
+    /// `$XDG_DATA_HOME`, default `~/.local/share`.
+    Data,
+    /// `$CARGO_HOME`, default `~/.cargo`.
+    Cargo,
+    /// `$HOME`.
+    Home,
+}
+
+/// Direct cleanup, used when no native prune runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    ClearContents,
+    KeepNewestRevision,
+    OlderThan(Duration),
+    LeaveAlone,
+}
+
+/// A tool's own garbage collector for its cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativePrune {
+    /// Executable that must be on `PATH` (`cargo-cache` for `cargo cache`).
+    pub program: &'static str,
+    pub argv: &'static [&'static str],
+}
+
+/// One curated cache location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolCache {
+    pub tool: &'static str,
+    pub base: CacheBase,
+    pub rel_path: &'static str,
+    pub native: Option<NativePrune>,
+    pub action: CacheAction,
+    pub description: &'static str,
+}
+
+const HF_UNUSED_AFTER: Duration = Duration::from_hours(30 * 24);
+
+/// Compiled kernels are cheap to rebuild once nothing has touched them for a week.
+const GPU_KERNEL_UNUSED_AFTER: Duration = Duration::from_hours(7 * 24);
+
+/// Known tool caches, in the order they are reported.
+pub const KNOWN_CACHES: &[ToolCache] = &[
+    ToolCache {
+        tool: "pip",
+        base: CacheBase::Cache,
+        rel_path: "pip",
+        native: None,
+        action: CacheAction::ClearContents,
+        description: "wheel and HTTP cache, re-downloaded on demand",
+    },
+    ToolCache {
+        tool: "yarn",
+        base: CacheBase::Cache,
+        rel_path: "yarn",
+        native: None,
+        action: CacheAction::ClearContents,
+        description: "package tarball cache, re-downloaded on demand",
+    },
+    ToolCache {
+        tool: "pnpm",
+        base: CacheBase::Data,
+        rel_path: "pnpm/store",
+        native: Some(NativePrune {
+            program: "pnpm",
+            argv: &["pnpm", "store", "prune"],
+        }),
+        action: CacheAction::LeaveAlone,
+        description: "content-addressed store; only unreferenced packages are pruned",
+    },
+    ToolCache {
+        tool: "pnpm",
+        base: CacheBase::Cache,
+        rel_path: "pnpm",
+        native: None,
+        action: CacheAction::ClearContents,
+        description: "registry metadata cache",
+    },
+    ToolCache {
+        tool: "playwright",
+        base: CacheBase::Cache,
+        rel_path: "ms-playwright",
+        native: None,
+        action: CacheAction::KeepNewestRevision,
+        description: "browser builds; only the newest revision of each browser is kept",
+    },
+    ToolCache {
+        tool: "huggingface",
+        base: CacheBase::Cache,
+        rel_path: "huggingface/hub",
+        native: None,
+        action: CacheAction::OlderThan(HF_UNUSED_AFTER),
+        description: "model and dataset snapshots unused for 30 days",
+    },
+    ToolCache {
+        tool: "triton",
+        base: CacheBase::Home,
+        rel_path: ".triton/cache",
+        native: None,
+        action: CacheAction::OlderThan(GPU_KERNEL_UNUSED_AFTER),
+        description: "compiled Triton kernels unused for 7 days",
+    },
+    ToolCache {
+        tool: "torch",
+        base: CacheBase::Cache,
+        rel_path: "torch_extensions",
+        native: None,
+        action: CacheAction::OlderThan(GPU_KERNEL_UNUSED_AFTER),
+        description: "JIT-built torch C++/CUDA extensions unused for 7 days",
+    },
+    ToolCache {
+        tool: "nvidia",
+        base: CacheBase::Home,
+        rel_path: ".nv/ComputeCache",
+        native: None,
+        action: CacheAction::ClearContents,
+        description: "CUDA driver JIT cache, rebuilt from PTX on demand",
+    },
+    ToolCache {
+        tool: "cargo",
+        base: CacheBase::Cargo,
+        rel_path: "registry/src",
+        native: Some(NativePrune {
+            program: "cargo-cache",
+            argv: &["cargo", "cache", "--autoclean"],
+        }),
+        action: CacheAction::ClearContents,
+        description: "extracted crate sources, re-extracted from registry/cache",
+    },
+    ToolCache {
+        tool: "cargo",
+        base: CacheBase::Cargo,
+        rel_path: "git/checkouts",
+        native: None,
+        action: CacheAction::ClearContents,
+        description: "git dependency checkouts, re-created from git/db",
+    },
+    ToolCache {
+        tool: "npm",
+        base: CacheBase::Home,
+        rel_path: ".npm/_cacache",
+        native: Some(NativePrune {
+            program: "npm",
+            argv: &["npm", "cache", "clean", "--force"],
+        }),
+        action: CacheAction::ClearContents,
+        description: "content-addressable tarball cache, re-downloaded on demand",
+    },
+];
+
+/// Resolved base directories for the current user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheRoots {
+    pub home: PathBuf,
+    pub cache: PathBuf,
+    pub data: PathBuf,
+    pub cargo: PathBuf,
+}
+
+impl CacheRoots {
+    /// Default locations under `home`, ignoring environment overrides.
+    #[must_use]
+    pub fn under_home(home: &Path) -> Self {
+        Self {
+            home: home.to_path_buf(),
+            cache: home.join(".cache"),
+            data: home.join(".local/share"),
+            cargo: home.join(".cargo"),
+        }
+    }
+
+    /// Resolve from `HOME`, honoring `XDG_CACHE_HOME`, `XDG_DATA_HOME`, and
+    /// `CARGO_HOME`. Returns `None` when `HOME` is unset.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let home = std::env::var_os("HOME").map(PathBuf::from)?;
+        let mut roots = Self::under_home(&home);
+        let absolute = |var: &str| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .filter(|p| p.is_absolute())
+        };
+        if let Some(cache) = absolute("XDG_CACHE_HOME") {
+            roots.cache = cache;
+        }
+        if let Some(data) = absolute("XDG_DATA_HOME") {
+            roots.data = data;
+        }
+        if let Some(cargo) = absolute("CARGO_HOME") {
+            roots.cargo = cargo;
+        }
+        Some(roots)
+    }
+
+    #[must_use]
+    pub fn resolve(&self, cache: &ToolCache) -> PathBuf {
+        let base = match cache.base {
+            CacheBase::Cache => &self.cache,
+            CacheBase::Data => &self.data,
+            CacheBase::Cargo => &self.cargo,
+            CacheBase::Home => &self.home,
+        };
+        base.join(cache.rel_path)
+    }
+}
+
+// ──────────────────── planning ────────────────────
+
+/// What cleaning one cache would do.
+#[derive(Debug, Clone)]
+pub struct CachePlanItem {
+    pub cache: ToolCache,
+    pub path: PathBuf,
+    /// Total size of the cache directory.
+    pub total_bytes: u64,
+    /// Native prune chosen for this cache; `None` means direct cleanup.
+    pub native: Option<NativePrune>,
+    /// Entries removed directly. Empty when `native` is set.
+    pub targets: Vec<PathBuf>,
+    /// Bytes held by `targets`. Unknown (0) for a native prune until it runs.
+    pub reclaimable_bytes: u64,
+    /// Why a native prune was not used, when the cache has one.
+    pub fallback_reason: Option<String>,
+}
+
+/// Plan cleanup for every known cache that exists under `roots`.
+///
+/// Cache directories that are symlinks are skipped: the target may be shared
+/// or live outside the user's home.
+#[must_use]
+pub fn plan(roots: &CacheRoots, now: SystemTime, config: &CachesConfig) -> Vec<CachePlanItem> {
+    KNOWN_CACHES
+        .iter()
+        .filter_map(|cache| {
+            let path = roots.resolve(cache);
+            let meta = fs::symlink_metadata(&path).ok()?;
+            if !meta.is_dir() {
+                return None;
+            }
+            let (native, fallback_reason) = match cache.native {
+                None => (None, None),
+                Some(_) if !config.native_enabled(cache.tool) => (
+                    None,
+                    Some(format!("native prune disabled for {}", cache.tool)),
+                ),
+                Some(native) if find_on_path(native.program).is_none() => {
+                    (None, Some(format!("{} not found on PATH", native.program)))
+                }
+                Some(native) => (Some(native), None),
+            };
+            let action = if native.is_some() {
+                CacheAction::LeaveAlone
+            } else {
+                cache.action
+            };
+            let targets = match action {
+                CacheAction::ClearContents => children(&path),
+                CacheAction::KeepNewestRevision => stale_revisions(&children(&path)),
+                CacheAction::OlderThan(max_age) => children(&path)
+                    .into_iter()
+                    .filter(|child| {
+                        fs::symlink_metadata(child).is_ok_and(|m| m.is_dir())
+                            && unused_for(child, now) >= max_age
+                    })
+                    .collect(),
+                CacheAction::LeaveAlone => Vec::new(),
+            };
+            let reclaimable_bytes = targets.iter().map(|t| tree_size(t)).sum();
+            Some(CachePlanItem {
+                cache: *cache,
+                total_bytes: tree_size(&path),
+                path,
+                native,
+                targets,
+                reclaimable_bytes,
+                fallback_reason,
+            })
+        })
+        .collect()
+}
+
+/// Playwright installs browsers as `<browser>-<revision>`; every revision but
+/// the highest of each browser is stale.
+#[must_use]
+pub fn stale_revisions(entries: &[PathBuf]) -> Vec<PathBuf> {
+    let mut newest: HashMap<&str, u64> = HashMap::new();
+    let parsed: Vec<(&PathBuf, &str, u64)> = entries
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let (product, revision) = name.rsplit_once('-')?;
+            Some((path, product, revision.parse::<u64>().ok()?))
+        })
+        .collect();
+    for &(_, product, revision) in &parsed {
+        let entry = newest.entry(product).or_insert(revision);
+        *entry = (*entry).max(revision);
+    }
+    parsed
+        .into_iter()
+        .filter(|(_, product, revision)| newest.get(product).is_some_and(|n| revision < n))
+        .map(|(path, _, _)| path.clone())
+        .collect()
+}
+
+fn children(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    paths
+}
+
+/// Time since the entry was last used: the later of atime and mtime, since
+/// `noatime` mounts never update atime.
+fn unused_for(path: &Path, now: SystemTime) -> Duration {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return Duration::ZERO;
+    };
+    let last_use = [meta.accessed().ok(), meta.modified().ok()]
+        .into_iter()
+        .flatten()
+        .max();
+    last_use
+        .and_then(|t| now.duration_since(t).ok())
+        .unwrap_or_default()
+}
+
+/// Apparent size of a tree, without following symlinks.
+#[must_use]
+pub fn tree_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path).map_or(0, |entries| {
+        entries.flatten().map(|e| tree_size(&e.path())).sum()
+    })
+}
+
+// ──────────────────── execution ────────────────────
+
+/// Result of cleaning one cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheCleanOutcome {
+    pub bytes_freed: u64,
+    pub items_removed: usize,
+}
+
+/// Apply a planned cleanup. Removal errors abort this cache only.
+pub fn execute(item: &CachePlanItem) -> Result<CacheCleanOutcome> {
+    if let Some(native) = item.native {
+        return run_native_prune(item, native.argv);
+    }
+    let mut outcome = CacheCleanOutcome::default();
+    for target in &item.targets {
+        let meta = match fs::symlink_metadata(target) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(SbhError::io(target, e)),
+        };
+        let size = tree_size(target);
+        if meta.is_dir() {
+            fs::remove_dir_all(target).map_err(|e| SbhError::io(target, e))?;
+        } else {
+            fs::remove_file(target).map_err(|e| SbhError::io(target, e))?;
+        }
+        outcome.bytes_freed += size;
+        outcome.items_removed += 1;
+    }
+    Ok(outcome)
+}
+
+fn run_native_prune(item: &CachePlanItem, command_line: &[&str]) -> Result<CacheCleanOutcome> {
+    let Some((program, args)) = command_line.split_first() else {
+        return Ok(CacheCleanOutcome::default());
+    };
+    let status = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => Ok(CacheCleanOutcome {
+            bytes_freed: item.total_bytes.saturating_sub(tree_size(&item.path)),
+            ..CacheCleanOutcome::default()
+        }),
+        Ok(status) => Err(SbhError::Runtime {
+            details: format!("`{}` exited with {status}", command_line.join(" ")),
+        }),
+        Err(e) => Err(SbhError::Runtime {
+            details: format!("failed to run `{}`: {e}", command_line.join(" ")),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, bytes: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; bytes]).unwrap();
+    }
+
+    #[test]
+    fn stale_revisions_keep_newest_per_browser() {
+        let entries: Vec<PathBuf> = [
+            "chromium-1097",
+            "chromium-1105",
+            "firefox-1424",
+            "ffmpeg-1009",
+            "ffmpeg-1008",
+            ".links",
+        ]
+        .iter()
+        .map(|n| PathBuf::from("/c/ms-playwright").join(n))
+        .collect();
+        let mut stale = stale_revisions(&entries);
+        stale.sort();
+        assert_eq!(
+            stale,
+            vec![
+                PathBuf::from("/c/ms-playwright/chromium-1097"),
+                PathBuf::from("/c/ms-playwright/ffmpeg-1008"),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_and_execute_apply_per_tool_semantics() {
+        let tmp = TempDir::new().unwrap();
+        let roots = CacheRoots::under_home(tmp.path());
+        write(&roots.cache.join("pip/http/a"), 100);
+        write(&roots.cache.join("ms-playwright/chromium-1097/chrome"), 50);
+        write(&roots.cache.join("ms-playwright/chromium-1105/chrome"), 70);
+        write(
+            &roots.cargo.join("registry/src/index/serde-1.0.0/lib.rs"),
+            30,
+        );
+        write(
+            &roots.cargo.join("registry/cache/index/serde-1.0.0.crate"),
+            10,
+        );
+        write(&roots.cache.join("huggingface/hub/models--tiny/blob"), 40);
+        write(&roots.home.join(".triton/cache/0a1b2c/kernel.cubin"), 60);
+        write(&roots.home.join(".nv/ComputeCache/index"), 15);
+        write(&roots.home.join(".npm/_cacache/index-v5/aa"), 20);
+
+        let no_native = CachesConfig {
+            native_cargo: false,
+            native_npm: false,
+            native_pnpm: false,
+        };
+        let items = plan(&roots, SystemTime::now(), &no_native);
+        let by_path = |rel: &str| {
+            items
+                .iter()
+                .find(|i| i.path.ends_with(rel))
+                .unwrap_or_else(|| panic!("missing plan item for {rel}"))
+        };
+
+        assert_eq!(by_path("pip").reclaimable_bytes, 100);
+        assert_eq!(by_path("ms-playwright").reclaimable_bytes, 50);
+        assert_eq!(by_path("registry/src").reclaimable_bytes, 30);
+        // Freshly used snapshots are kept.
+        assert!(by_path("huggingface/hub").targets.is_empty());
+        assert!(by_path(".triton/cache").targets.is_empty());
+        assert_eq!(by_path(".nv/ComputeCache").reclaimable_bytes, 15);
+        // Native prune disabled: npm falls back to direct cleanup and says why.
+        let npm = by_path(".npm/_cacache");
+        assert!(npm.native.is_none());
+        assert_eq!(npm.reclaimable_bytes, 20);
+        assert_eq!(
+            npm.fallback_reason.as_deref(),
+            Some("native prune disabled for npm")
+        );
+        assert!(items.iter().all(|i| !i.path.ends_with("pnpm/store")));
+
+        for item in &items {
+            execute(item).unwrap();
+        }
+        assert!(roots.cache.join("pip").is_dir());
+        assert!(!roots.cache.join("pip/http").exists());
+        assert!(!roots.cache.join("ms-playwright/chromium-1097").exists());
+        assert!(roots.cache.join("ms-playwright/chromium-1105").exists());
+        assert!(
+            roots
+                .cargo
+                .join("registry/cache/index/serde-1.0.0.crate")
+                .exists()
+        );
+        assert!(!roots.cargo.join("registry/src/index").exists());
+
+        // A year later the unused snapshot qualifies.
+        let later = SystemTime::now() + Duration::from_secs(365 * 24 * 3600);
+        let hub = plan(&roots, later, &no_native)
+            .into_iter()
+            .find(|i| i.path.ends_with("huggingface/hub"))
+            .unwrap();
+        assert_eq!(hub.reclaimable_bytes, 40);
+        let triton = plan(&roots, later, &no_native)
+            .into_iter()
+            .find(|i| i.path.ends_with(".triton/cache"))
+            .unwrap();
+        assert_eq!(triton.reclaimable_bytes, 60);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_cache_directories_are_skipped() {
+        let tmp = TempDir::new().unwrap();
+        let roots = CacheRoots::under_home(tmp.path());
+        let shared = tmp.path().join("shared-pip");
+        write(&shared.join("wheel"), 10);
+        fs::create_dir_all(&roots.cache).unwrap();
+        std::os::unix::fs::symlink(&shared, roots.cache.join("pip")).unwrap();
+
+        assert!(plan(&roots, SystemTime::now(), &CachesConfig::default()).is_empty());
+    }
+}
@@ -3,24 +3,42 @@
 //! The walker is the "eyes" of the scanner: it discovers candidate files and
 //! directories for cleanup, collects structural markers for the scoring engine,
 //! and integrates with the protection system to skip `.sbh-protect`ed subtrees.
+//!
+//! Parallel walking thrashes a spinning disk, so roots on a rotational device
+//! (or under a configured override) get their own small worker pool while the
+//! rest share the full one.
+//!
+//! A directory that classifies confidently as an artifact (`node_modules/`, a
+//! `target/` profile dir with its `.fingerprint`) is kept or deleted whole, so
+//! the walker sizes its subtree in place instead of emitting every descendant.
 
 #![allow(missing_docs)]
 #![allow(clippy::cast_possible_truncation)]
 
+#[cfg(feature = "daemon")]
+use std::collections::BTreeSet;
 use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::thread;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use crossbeam_channel as channel;
+#[cfg(feature = "daemon")]
+use notify::event::{CreateKind, EventKind, ModifyKind, RenameMode};
+#[cfg(feature = "daemon")]
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
 
 use crate::core::errors::{Result, SbhError};
-use crate::scanner::patterns::StructuralSignals;
-use crate::scanner::protection::ProtectionRegistry;
+use crate::logger::diag;
+use crate::scanner::patterns::{ArtifactCategory, ArtifactPatternRegistry, StructuralSignals};
+use crate::scanner::protection::{MARKER_FILENAME, ProtectionRegistry};
+use crate::scanner::quarantine::QUARANTINE_DIRNAME;
 
 /// Walker configuration derived from `ScannerConfig`.
 #[derive(Debug, Clone)]
@@ -33,6 +51,34 @@ pub struct WalkerConfig {
     pub excluded_paths: HashSet<PathBuf>,
 }
 
+/// Per-device walker thread caps.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceLimits {
+    /// Threads for roots on a spinning disk; `None` skips detection.
+    pub rotational: Option<usize>,
+    /// Caps for roots under these paths; the longest match wins.
+    pub overrides: Vec<(PathBuf, usize)>,
+}
+
+impl DeviceLimits {
+    /// Thread cap for `root`, or `None` to walk it in the shared pool.
+    /// `rotational` is only consulted when no override matches.
+    #[must_use]
+    pub fn cap_for(&self, root: &Path, rotational: impl FnOnce() -> bool) -> Option<usize> {
+        if let Some((_, threads)) = self
+            .overrides
+            .iter()
+            .filter(|(path, _)| root.starts_with(path))
+            .max_by_key(|(path, _)| path.as_os_str().len())
+        {
+            return Some((*threads).max(1));
+        }
+        self.rotational
+            .filter(|_| rotational())
+            .map(|threads| threads.max(1))
+    }
+}
+
 /// Metadata collected for each filesystem entry.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EntryMetadata {
@@ -42,7 +88,16 @@ pub struct EntryMetadata {
     /// This is a lower bound (capped at `MAX_ENTRIES_PER_DIR` children, does not
     /// recurse into subdirectories), but far more useful for scoring than the
     /// inode entry size (~4096) that `size_bytes` returns for directories.
+    /// Classified artifact roots instead carry their whole subtree, sampled
+    /// past the walker's `with_artifact_roots` budget.
     pub content_size_bytes: u64,
+    /// Space allocated on disk (`st_blocks` × 512). Less than `size_bytes`
+    /// for sparse and compressed files, more for small files padded to a
+    /// whole block.
+    pub disk_bytes: u64,
+    /// `content_size_bytes` counted in allocated blocks instead of apparent
+    /// size: what deleting the entry actually returns to the filesystem.
+    pub content_disk_bytes: u64,
     pub modified: SystemTime,
     pub created: Option<SystemTime>,
     pub is_dir: bool,
@@ -83,6 +138,260 @@ pub struct WalkEntry {
 /// Item in the internal work queue: (directory_path, depth, root_device_id).
 type WorkItem = (PathBuf, usize, u64);
 
+/// How the walker reads per-entry metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatMode {
+    /// `std::fs` metadata for everything (full field set, forced revalidation).
+    Portable,
+    /// Linux: minimal-mask `statx` with `AT_STATX_DONT_SYNC`; falls back to
+    /// portable reads per call when `statx` is unavailable. Elsewhere this
+    /// behaves like `Portable`.
+    #[default]
+    Reduced,
+}
+
+/// Metadata syscall counters for `scan --profile-syscalls`.
+#[derive(Debug, Default)]
+pub struct SyscallProfile {
+    dir_reads: AtomicU64,
+    dir_stats: AtomicU64,
+    file_stats: AtomicU64,
+    symlink_stats: AtomicU64,
+    entries: AtomicU64,
+}
+
+/// Point-in-time copy of a [`SyscallProfile`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SyscallCounts {
+    pub dir_reads: u64,
+    pub dir_stats: u64,
+    pub file_stats: u64,
+    pub symlink_stats: u64,
+    pub entries: u64,
+}
+
+impl SyscallProfile {
+    #[must_use]
+    pub fn snapshot(&self) -> SyscallCounts {
+        SyscallCounts {
+            dir_reads: self.dir_reads.load(Ordering::Relaxed),
+            dir_stats: self.dir_stats.load(Ordering::Relaxed),
+            file_stats: self.file_stats.load(Ordering::Relaxed),
+            symlink_stats: self.symlink_stats.load(Ordering::Relaxed),
+            entries: self.entries.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl SyscallCounts {
+    /// Metadata syscalls (stats plus directory opens) issued.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.dir_reads + self.dir_stats + self.file_stats + self.symlink_stats
+    }
+
+    /// Metadata syscalls per 1 000 emitted entries.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn per_1k_entries(&self) -> f64 {
+        if self.entries == 0 {
+            return 0.0;
+        }
+        self.total() as f64 * 1000.0 / self.entries as f64
+    }
+}
+
+/// Stat mode, optional counters, and per-walk read options, shared by every
+/// worker thread.
+#[derive(Debug, Clone, Default)]
+struct StatStrategy {
+    mode: StatMode,
+    profile: Option<Arc<SyscallProfile>>,
+    /// Also emit regular files at least this large (large-file discovery).
+    large_file_min_bytes: Option<u64>,
+    /// Size classified artifact roots in place instead of walking them.
+    artifact_roots: Option<Arc<ArtifactRoots>>,
+}
+
+/// Classified-root short-circuit settings (see
+/// [`DirectoryWalker::with_artifact_roots`]).
+#[derive(Debug)]
+struct ArtifactRoots {
+    registry: ArtifactPatternRegistry,
+    /// Entries read exactly before subtree sizing switches to sampling;
+    /// 0 never samples.
+    sample_after: u64,
+}
+
+impl ArtifactRoots {
+    /// Whether `dir` classifies confidently enough to stop descending.
+    fn is_root(&self, dir: &Path, signals: StructuralSignals) -> bool {
+        let classification = self.registry.classify(dir, signals);
+        classification.category != ArtifactCategory::Unknown
+            && classification.combined_confidence >= ARTIFACT_ROOT_MIN_CONFIDENCE
+    }
+}
+
+/// Structural signals accumulated from child names, without any stat calls.
+#[derive(Debug, Default)]
+struct SignalTally {
+    signals: StructuralSignals,
+    object_count: u32,
+    total_count: u32,
+}
+
+impl SignalTally {
+    fn observe(&mut self, name: &OsStr) {
+        self.total_count += 1;
+        match name.to_string_lossy().as_ref() {
+            "incremental" => self.signals.has_incremental = true,
+            "deps" => self.signals.has_deps = true,
+            "build" => self.signals.has_build = true,
+            ".fingerprint" => self.signals.has_fingerprint = true,
+            ".git" => self.signals.has_git = true,
+            "cargo.toml" | "Cargo.toml" => self.signals.has_cargo_toml = true,
+            _ => {}
+        }
+
+        // Check extension for object file heuristics.
+        if let Some(ext) = Path::new(name).extension() {
+            let ext_str = ext.to_string_lossy();
+            if ext_str.eq_ignore_ascii_case("o")
+                || ext_str.eq_ignore_ascii_case("rlib")
+                || ext_str.eq_ignore_ascii_case("rmeta")
+                || ext_str.eq_ignore_ascii_case("d")
+            {
+                self.object_count += 1;
+            }
+        }
+    }
+
+    fn finish(self) -> StructuralSignals {
+        let mut signals = self.signals;
+        if self.total_count > 0 && self.object_count > 0 {
+            signals.mostly_object_files = self.object_count * 2 >= self.total_count;
+        }
+        signals
+    }
+}
+
+impl StatStrategy {
+    fn count(&self, field: fn(&SyscallProfile) -> &AtomicU64) {
+        if let Some(profile) = &self.profile {
+            field(profile).fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Metadata and device ID of a directory about to be walked.
+    fn dir_metadata(
+        &self,
+        path: &Path,
+        follow_symlinks: bool,
+    ) -> std::io::Result<(EntryMetadata, u64)> {
+        self.count(|p| &p.dir_stats);
+        #[cfg(target_os = "linux")]
+        if self.mode == StatMode::Reduced
+            && let Ok(found) = crate::scanner::statx::entry_metadata(path, follow_symlinks)
+        {
+            return Ok(found);
+        }
+        let meta = metadata_for_path(path, follow_symlinks)?;
+        Ok((entry_metadata(&meta), device_id(&meta)))
+    }
+
+    /// Full metadata for a large file about to be emitted as its own entry.
+    fn large_file_metadata(&self, path: &Path) -> std::io::Result<EntryMetadata> {
+        self.count(|p| &p.file_stats);
+        #[cfg(target_os = "linux")]
+        if self.mode == StatMode::Reduced
+            && let Ok((meta, _)) = crate::scanner::statx::entry_metadata(path, false)
+        {
+            return Ok(meta);
+        }
+        metadata_for_path(path, false).map(|meta| entry_metadata(&meta))
+    }
+
+    /// Apparent and allocated size of a non-directory child (symlinks are
+    /// not followed).
+    fn file_size(&self, entry: &fs::DirEntry) -> Option<(u64, u64)> {
+        self.count(|p| &p.file_stats);
+        #[cfg(target_os = "linux")]
+        if self.mode == StatMode::Reduced
+            && let Ok(sizes) = crate::scanner::statx::file_size(&entry.path(), false)
+        {
+            return Some(sizes);
+        }
+        entry
+            .metadata()
+            .ok()
+            .map(|m| (m.len(), allocated_bytes(&m)))
+    }
+
+    /// Whether a symlinked child resolves to a directory (only when following symlinks).
+    fn symlink_is_dir(&self, path: &Path) -> bool {
+        self.count(|p| &p.symlink_stats);
+        metadata_for_path(path, true).is_ok_and(|m| m.is_dir())
+    }
+}
+
+/// How one root fared in an isolated multi-root walk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RootStatus {
+    /// Walked to completion.
+    Complete,
+    /// Stopped early (root timeout or cancellation); entries found so far are kept.
+    Partial { reason: String },
+    /// Missing, unreadable, or not a directory; nothing to walk.
+    Skipped { reason: String },
+    /// Failed with an I/O error other than missing/permission denied.
+    Failed { error: String },
+}
+
+/// Per-root outcome and timing from [`DirectoryWalker::walk_isolated`].
+#[derive(Debug, Clone)]
+pub struct RootWalkReport {
+    pub root: PathBuf,
+    pub status: RootStatus,
+    pub entries: usize,
+    pub elapsed: Duration,
+}
+
+impl RootWalkReport {
+    /// True when the root completed or was legitimately skipped.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        matches!(
+            self.status,
+            RootStatus::Complete | RootStatus::Skipped { .. }
+        )
+    }
+}
+
+/// Result of an isolated multi-root walk: all entries plus one report per root.
+#[derive(Debug)]
+pub struct MultiRootWalk {
+    pub entries: Vec<WalkEntry>,
+    pub roots: Vec<RootWalkReport>,
+}
+
+impl MultiRootWalk {
+    /// True when every root completed or was legitimately skipped.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.roots.iter().all(RootWalkReport::is_complete)
+    }
+}
+
+/// Message from a per-root walk thread to the collector.
+enum RootEvent {
+    Entry(WalkEntry),
+    Skipped(String),
+    Failed(String),
+    Done,
+}
+
 /// Parallel directory walker with safety guards.
 ///
 /// Safety invariants:
@@ -94,6 +403,8 @@ pub struct DirectoryWalker {
     config: WalkerConfig,
     protection: Arc<parking_lot::RwLock<ProtectionRegistry>>,
     heartbeat: Option<Arc<dyn Fn() + Send + Sync>>,
+    stat: StatStrategy,
+    device_limits: DeviceLimits,
     /// Shared cancellation flag. When set to `true`, walker threads exit promptly
     /// instead of blocking on full channels. This prevents thread leaks when the
     /// scanner times out a scan pass.
@@ -106,10 +417,59 @@ impl DirectoryWalker {
             config,
             protection: Arc::new(parking_lot::RwLock::new(protection)),
             heartbeat: None,
+            stat: StatStrategy::default(),
+            device_limits: DeviceLimits::default(),
             cancel: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Choose how entry metadata is read (default [`StatMode::Reduced`]).
+    #[must_use]
+    pub const fn with_stat_mode(mut self, mode: StatMode) -> Self {
+        self.stat.mode = mode;
+        self
+    }
+
+    /// Also emit regular files of at least `min_bytes` as entries, so single
+    /// huge files (core dumps, VM images, logs) are scored alongside
+    /// directories. `0` leaves discovery off.
+    #[must_use]
+    pub const fn with_large_files(mut self, min_bytes: u64) -> Self {
+        self.stat.large_file_min_bytes = if min_bytes > 0 { Some(min_bytes) } else { None };
+        self
+    }
+
+    /// Count metadata syscalls into `profile` (for `scan --profile-syscalls`).
+    #[must_use]
+    pub fn with_syscall_profile(mut self, profile: Arc<SyscallProfile>) -> Self {
+        self.stat.profile = Some(profile);
+        self
+    }
+
+    /// Stop descending into directories that classify as artifacts with high
+    /// confidence; each is emitted once with its whole subtree size, and
+    /// nothing beneath it is emitted. After `sample_after` entries (`0` =
+    /// never) the size is extrapolated from a sample. `None` leaves the
+    /// walk exhaustive.
+    #[must_use]
+    pub fn with_artifact_roots(mut self, sample_after: Option<u64>) -> Self {
+        self.stat.artifact_roots = sample_after.map(|sample_after| {
+            Arc::new(ArtifactRoots {
+                registry: ArtifactPatternRegistry::default(),
+                sample_after,
+            })
+        });
+        self
+    }
+
+    /// Cap walker threads per device (`scanner.rotational_parallelism`,
+    /// `scanner.device_parallelism`).
+    #[must_use]
+    pub fn with_device_limits(mut self, limits: DeviceLimits) -> Self {
+        self.device_limits = limits;
+        self
+    }
+
     /// Return a handle to the cancellation flag.
     ///
     /// The caller (scanner) keeps this and sets it to `true` when the scan
@@ -144,22 +504,28 @@ impl DirectoryWalker {
     pub fn stream(&self) -> Result<channel::Receiver<WalkEntry>> {
         let parallelism = self.config.parallelism.max(1);
 
-        // Channels: work items (bounded) and results (unbounded for throughput).
-        // Queue sized to hold children from multiple root paths without starvation.
-        // Per-directory iteration cap (MAX_ENTRIES_PER_DIR) prevents any single huge
-        // directory (e.g. /data/tmp with 60K+ children) from monopolizing the queue.
-        let (work_tx, work_rx) = channel::bounded::<WorkItem>(4096);
+        // Results are unbounded for throughput; each pool has its own work queue.
         let (result_tx, result_rx) = channel::unbounded::<WalkEntry>();
 
-        // Track in-flight work items so workers know when to stop.
-        let in_flight = Arc::new(AtomicUsize::new(0));
-
-        // Seed work queue with root paths.
+        // Group root paths into worker pools, keyed by device for capped
+        // devices. Uncapped roots share one pool at full parallelism.
+        let mut pools: HashMap<Option<u64>, (usize, Vec<WorkItem>)> = HashMap::new();
         for root in &self.config.root_paths {
+            self.stat.count(|p| &p.dir_stats);
             let meta = match metadata_for_path(root, self.config.follow_symlinks) {
                 Ok(m) => m,
-                Err(err) if err.kind() == ErrorKind::NotFound => continue,
-                Err(err) if err.kind() == ErrorKind::PermissionDenied => continue,
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        ErrorKind::NotFound | ErrorKind::PermissionDenied
+                    ) =>
+                {
+                    diag::warn(
+                        "walker",
+                        format_args!("skipping root {}: {err}", root.display()),
+                    );
+                    continue;
+                }
                 Err(err) => {
                     return Err(SbhError::Io {
                         path: root.clone(),
@@ -171,19 +537,214 @@ impl DirectoryWalker {
                 continue;
             }
             let dev = device_id(&meta);
+            let cap = self.device_limits.cap_for(root, || is_rotational(dev));
+            if let Some(cap) = cap {
+                diag::debug(
+                    "walker",
+                    format_args!("root {} capped at {cap} walker threads", root.display()),
+                );
+            }
+            let threads = cap.map_or(parallelism, |cap| cap.min(parallelism));
+            pools
+                .entry(cap.map(|_| dev))
+                .or_insert_with(|| (threads, Vec::new()))
+                .1
+                .push((root.clone(), 0, dev));
+        }
+
+        for (threads, seeds) in pools.into_values() {
+            self.spawn_pool(threads, seeds, &self.config, &result_tx);
+        }
+
+        Ok(result_rx)
+    }
+
+    /// Stream entries for what changed since the last walk instead of
+    /// re-walking every root.
+    ///
+    /// Directories in `changes.created` are walked like roots, down to
+    /// `max_depth` below their configured root; directories in
+    /// `changes.touched` are re-read without recursion, so only their own
+    /// entry (and any large files in them) is emitted. Entries carry the
+    /// depth a full walk would have given them. `changes.removed` and
+    /// `changes.rescan` are left to the caller.
+    #[cfg(feature = "daemon")]
+    pub fn stream_changes(&self, changes: &WatchChanges) -> Result<channel::Receiver<WalkEntry>> {
+        let (result_tx, result_rx) = channel::unbounded::<WalkEntry>();
+        let mut root_devs: HashMap<PathBuf, Option<u64>> = HashMap::new();
+
+        // A created directory's walk already covers everything beneath it.
+        let under_created =
+            |dir: &Path| dir.ancestors().skip(1).any(|a| changes.created.contains(a));
+        let deep: Vec<WorkItem> = changes
+            .created
+            .iter()
+            .filter(|dir| !under_created(dir))
+            .filter_map(|dir| self.change_seed(dir, &mut root_devs))
+            .collect();
+        let shallow: Vec<WorkItem> = changes
+            .touched
+            .iter()
+            .filter(|dir| !changes.created.contains(*dir) && !under_created(dir))
+            .filter_map(|dir| self.change_seed(dir, &mut root_devs))
+            .collect();
+        let (deep, shallow) = self.lift_to_artifact_roots(deep, shallow);
+
+        let parallelism = self.config.parallelism.max(1);
+        if !deep.is_empty() {
+            let threads = parallelism.min(deep.len());
+            self.spawn_pool(threads, deep, &self.config, &result_tx);
+        }
+        if !shallow.is_empty() {
+            // max_depth 0 stops process_directory from queueing children.
+            let listing_only = WalkerConfig {
+                max_depth: 0,
+                ..self.config.clone()
+            };
+            let threads = parallelism.min(shallow.len());
+            self.spawn_pool(threads, shallow, &listing_only, &result_tx);
+        }
+
+        Ok(result_rx)
+    }
+
+    /// Replace seeds beneath a classified artifact root with that root, walked
+    /// deep, because a full walk emits the root (sized whole) and nothing
+    /// under it. The outermost classified ancestor wins.
+    #[cfg(feature = "daemon")]
+    fn lift_to_artifact_roots(
+        &self,
+        deep: Vec<WorkItem>,
+        shallow: Vec<WorkItem>,
+    ) -> (Vec<WorkItem>, Vec<WorkItem>) {
+        let Some(roots) = &self.stat.artifact_roots else {
+            return (deep, shallow);
+        };
+        let mut classified: HashMap<PathBuf, bool> = HashMap::new();
+        let mut lifted: HashSet<PathBuf> = HashSet::new();
+        let (mut out_deep, mut out_shallow) = (Vec::new(), Vec::new());
+        let seeds = deep
+            .into_iter()
+            .map(|item| (item, true))
+            .chain(shallow.into_iter().map(|item| (item, false)));
+        for ((dir, depth, dev), is_deep) in seeds {
+            let mut root = None;
+            for (up, ancestor) in dir.ancestors().enumerate().skip(1).take(depth - 1) {
+                let is_root = *classified
+                    .entry(ancestor.to_path_buf())
+                    .or_insert_with(|| self.is_artifact_root(roots, ancestor));
+                if is_root {
+                    root = Some((ancestor.to_path_buf(), depth - up));
+                }
+            }
+            match root {
+                Some((root, root_depth)) => {
+                    if lifted.insert(root.clone()) {
+                        out_deep.push((root, root_depth, dev));
+                    }
+                }
+                None if is_deep => out_deep.push((dir, depth, dev)),
+                None => out_shallow.push((dir, depth, dev)),
+            }
+        }
+        (out_deep, out_shallow)
+    }
+
+    /// Classify `dir` from its listing the way `process_directory` would.
+    #[cfg(feature = "daemon")]
+    fn is_artifact_root(&self, roots: &ArtifactRoots, dir: &Path) -> bool {
+        self.stat.count(|p| &p.dir_reads);
+        let Ok(entries) = fs::read_dir(dir) else {
+            return false;
+        };
+        let mut tally = SignalTally::default();
+        for entry in entries.flatten().take(MAX_ENTRIES_PER_DIR as usize) {
+            tally.observe(&entry.file_name());
+        }
+        roots.is_root(dir, tally.finish())
+    }
+
+    /// Work item for a changed directory, or `None` when a full walk would
+    /// never have reached it: outside every root, deeper than `max_depth`,
+    /// or beneath an excluded, quarantined, or protected directory.
+    ///
+    /// Ancestors are checked on disk for `.sbh-protect` because the marker
+    /// may predate this walker or have been created since the last walk.
+    #[cfg(feature = "daemon")]
+    fn change_seed(
+        &self,
+        dir: &Path,
+        root_devs: &mut HashMap<PathBuf, Option<u64>>,
+    ) -> Option<WorkItem> {
+        let root = self
+            .config
+            .root_paths
+            .iter()
+            .filter(|root| dir.starts_with(root) && dir != root.as_path())
+            .max_by_key(|root| root.components().count())?;
+        let depth = dir.components().count() - root.components().count();
+        if depth > self.config.max_depth {
+            return None;
+        }
+        if dir
+            .ancestors()
+            .take(depth)
+            .any(|a| a.file_name().is_some_and(|name| name == QUARANTINE_DIRNAME))
+        {
+            return None;
+        }
+        for ancestor in dir.ancestors().skip(1).take(depth) {
+            if self.config.excluded_paths.contains(ancestor)
+                || self.protection.read().is_protected(ancestor)
+            {
+                return None;
+            }
+            self.stat.count(|p| &p.file_stats);
+            if fs::symlink_metadata(ancestor.join(MARKER_FILENAME)).is_ok() {
+                self.protection.write().register_marker(ancestor);
+                return None;
+            }
+        }
+
+        let root_dev = *root_devs.entry(root.clone()).or_insert_with(|| {
+            self.stat.count(|p| &p.dir_stats);
+            metadata_for_path(root, self.config.follow_symlinks)
+                .ok()
+                .map(|meta| device_id(&meta))
+        });
+        Some((dir.to_path_buf(), depth, root_dev?))
+    }
+
+    /// Start `threads` workers on a fresh work queue seeded with `seeds`.
+    fn spawn_pool(
+        &self,
+        threads: usize,
+        seeds: Vec<WorkItem>,
+        config: &WalkerConfig,
+        result_tx: &channel::Sender<WalkEntry>,
+    ) {
+        // Queue sized to hold children from multiple root paths without starvation.
+        // Per-directory iteration cap (MAX_ENTRIES_PER_DIR) prevents any single huge
+        // directory (e.g. /data/tmp with 60K+ children) from monopolizing the queue.
+        let (work_tx, work_rx) = channel::bounded::<WorkItem>(4096);
+
+        // Track in-flight work items so workers know when to stop.
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        for seed in seeds {
             in_flight.fetch_add(1, Ordering::Release);
-            let _ = work_tx.send((root.clone(), 0, dev));
+            let _ = work_tx.send(seed);
         }
 
         // Clone sender for workers; drop original so channel closes when workers finish.
-        for _ in 0..parallelism {
+        for _ in 0..threads {
             let work_rx = work_rx.clone();
             let work_tx = work_tx.clone();
             let result_tx = result_tx.clone();
             let in_flight = Arc::clone(&in_flight);
-            let config = self.config.clone();
+            let config = config.clone();
             let protection = Arc::clone(&self.protection);
             let heartbeat = self.heartbeat.clone();
+            let stat = self.stat.clone();
             let cancel = Arc::clone(&self.cancel);
 
             thread::spawn(move || {
@@ -195,12 +756,193 @@ impl DirectoryWalker {
                     &config,
                     &protection,
                     heartbeat.as_ref(),
+                    &stat,
                     &cancel,
                 );
             });
         }
+    }
 
-        Ok(result_rx)
+    /// Walk each root concurrently in its own error domain.
+    ///
+    /// Every root gets an independent worker pool and cancellation flag, so a
+    /// permission error or a hung network mount on one root cannot stall or
+    /// fail the others. When `root_timeout` elapses for a root, its workers
+    /// are cancelled and it is reported as [`RootStatus::Partial`] with the
+    /// entries found so far. The collector never blocks on a root, so even a
+    /// root whose initial `stat` hangs is abandoned on time (its threads are
+    /// detached and exit once the syscall returns).
+    pub fn walk_isolated(&self, root_timeout: Option<Duration>) -> MultiRootWalk {
+        let mut entries = Vec::new();
+        let roots = self.walk_isolated_with(root_timeout, |entry| entries.push(entry));
+        MultiRootWalk { entries, roots }
+    }
+
+    /// Like [`walk_isolated`](Self::walk_isolated), but hands each entry to
+    /// `on_entry` as it arrives instead of buffering, so callers can overlap
+    /// downstream work (classification, scoring) with the walk itself.
+    #[allow(clippy::too_many_lines)]
+    pub fn walk_isolated_with<F>(
+        &self,
+        root_timeout: Option<Duration>,
+        mut on_entry: F,
+    ) -> Vec<RootWalkReport>
+    where
+        F: FnMut(WalkEntry),
+    {
+        let roots = self.config.root_paths.clone();
+        let per_root_parallelism = self.config.parallelism.max(1).div_ceil(roots.len().max(1));
+        let (event_tx, event_rx) = channel::unbounded::<(usize, RootEvent)>();
+
+        let mut cancels = Vec::with_capacity(roots.len());
+        for (idx, root) in roots.iter().enumerate() {
+            let cancel = Arc::new(AtomicBool::new(false));
+            cancels.push(Arc::clone(&cancel));
+            let child = Self {
+                config: WalkerConfig {
+                    root_paths: vec![root.clone()],
+                    parallelism: per_root_parallelism,
+                    ..self.config.clone()
+                },
+                protection: Arc::clone(&self.protection),
+                heartbeat: self.heartbeat.clone(),
+                stat: self.stat.clone(),
+                device_limits: self.device_limits.clone(),
+                cancel,
+            };
+            let event_tx = event_tx.clone();
+            thread::spawn(move || walk_one_root(&child, idx, &event_tx));
+        }
+        drop(event_tx);
+
+        let start = Instant::now();
+        let mut reports: Vec<RootWalkReport> = roots
+            .iter()
+            .map(|root| RootWalkReport {
+                root: root.clone(),
+                status: RootStatus::Complete,
+                entries: 0,
+                elapsed: Duration::ZERO,
+            })
+            .collect();
+        let mut done = vec![false; roots.len()];
+
+        while done.iter().any(|d| !d) {
+            let event = event_rx.recv_timeout(Duration::from_millis(50));
+            let elapsed = start.elapsed();
+            match event {
+                Ok((idx, _)) if done[idx] => {}
+                Ok((idx, RootEvent::Entry(entry))) => {
+                    reports[idx].entries += 1;
+                    on_entry(entry);
+                }
+                Ok((idx, RootEvent::Skipped(reason))) => {
+                    reports[idx].status = RootStatus::Skipped { reason };
+                }
+                Ok((idx, RootEvent::Failed(error))) => {
+                    reports[idx].status = RootStatus::Failed { error };
+                }
+                Ok((idx, RootEvent::Done)) => {
+                    done[idx] = true;
+                    reports[idx].elapsed = elapsed;
+                }
+                Err(channel::RecvTimeoutError::Timeout) => {}
+                Err(channel::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let parent_cancelled = self.cancel.load(Ordering::Relaxed);
+            let timed_out = root_timeout.is_some_and(|limit| elapsed >= limit);
+            if !parent_cancelled && !timed_out {
+                continue;
+            }
+            let reason = if parent_cancelled {
+                "cancelled".to_string()
+            } else {
+                format!(
+                    "timed out after {:.1}s",
+                    root_timeout.unwrap_or_default().as_secs_f64()
+                )
+            };
+            for ((report, finished), cancel) in reports.iter_mut().zip(&mut done).zip(&cancels) {
+                if *finished {
+                    continue;
+                }
+                cancel.store(true, Ordering::Relaxed);
+                *finished = true;
+                report.elapsed = elapsed;
+                report.status = RootStatus::Partial {
+                    reason: reason.clone(),
+                };
+                diag::warn(
+                    "walker",
+                    format_args!(
+                        "root {} {reason} with {} entries",
+                        report.root.display(),
+                        report.entries
+                    ),
+                );
+            }
+        }
+
+        for report in &reports {
+            diag::debug(
+                "walker",
+                format_args!(
+                    "root {} walked {} entries in {:.2}s",
+                    report.root.display(),
+                    report.entries,
+                    report.elapsed.as_secs_f64()
+                ),
+            );
+        }
+
+        reports
+    }
+
+    /// Read one path the way a walk reaching it would, without touching its
+    /// siblings: a directory gets its structural signals and content size (a
+    /// classified artifact root sized whole), a file its own metadata.
+    /// Returns `None` when the path is excluded, protected, or unreadable.
+    pub fn inspect(&self, path: &Path) -> Option<WalkEntry> {
+        if self.config.excluded_paths.contains(path) || self.protection.read().is_protected(path) {
+            return None;
+        }
+        let (metadata, dev) = self
+            .stat
+            .dir_metadata(path, self.config.follow_symlinks)
+            .ok()?;
+        if !metadata.is_dir {
+            return Some(WalkEntry {
+                path: path.to_path_buf(),
+                metadata,
+                depth: 1,
+                structural_signals: StructuralSignals::default(),
+                is_open: false,
+            });
+        }
+
+        // Children are listed (so an artifact root can be sized) but the
+        // queue they land on is never drained.
+        let (work_tx, _work_rx) = channel::unbounded::<WorkItem>();
+        let (result_tx, result_rx) = channel::unbounded::<WalkEntry>();
+        let config = WalkerConfig {
+            max_depth: 2,
+            ..self.config.clone()
+        };
+        process_directory(
+            path,
+            1,
+            dev,
+            &work_tx,
+            &result_tx,
+            &AtomicUsize::new(0),
+            &config,
+            &self.protection,
+            &self.stat,
+            &AtomicBool::new(false),
+        );
+        drop(result_tx);
+        result_rx.into_iter().find(|entry| entry.path == path)
     }
 
     /// Access the protection registry (e.g. to list discovered markers).
@@ -209,8 +951,49 @@ impl DirectoryWalker {
     }
 }
 
+/// Per-root driver for [`DirectoryWalker::walk_isolated`]: classifies the
+/// root up front so skips and failures are reported, then forwards entries.
+fn walk_one_root(
+    walker: &DirectoryWalker,
+    idx: usize,
+    event_tx: &channel::Sender<(usize, RootEvent)>,
+) {
+    let root = &walker.config.root_paths[0];
+    match metadata_for_path(root, walker.config.follow_symlinks) {
+        Ok(meta) if !meta.is_dir() => {
+            let _ = event_tx.send((idx, RootEvent::Skipped("not a directory".to_string())));
+        }
+        Ok(_) => match walker.stream() {
+            Ok(rx) => {
+                for entry in rx {
+                    if event_tx.send((idx, RootEvent::Entry(entry))).is_err() {
+                        walker.cancel.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            }
+            Err(err) => {
+                let _ = event_tx.send((idx, RootEvent::Failed(err.to_string())));
+            }
+        },
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::NotFound | ErrorKind::PermissionDenied
+            ) =>
+        {
+            let _ = event_tx.send((idx, RootEvent::Skipped(err.to_string())));
+        }
+        Err(err) => {
+            let _ = event_tx.send((idx, RootEvent::Failed(err.to_string())));
+        }
+    }
+    let _ = event_tx.send((idx, RootEvent::Done));
+}
+
 /// Worker thread function: pulls directories from work channel, processes them,
 /// sends results and new subdirectories back.
+#[allow(clippy::too_many_arguments)]
 fn walker_thread(
     work_rx: &channel::Receiver<WorkItem>,
     work_tx: &channel::Sender<WorkItem>,
@@ -219,6 +1002,7 @@ fn walker_thread(
     config: &WalkerConfig,
     protection: &parking_lot::RwLock<ProtectionRegistry>,
     heartbeat: Option<&Arc<dyn Fn() + Send + Sync>>,
+    stat: &StatStrategy,
     cancel: &AtomicBool,
 ) {
     loop {
@@ -236,7 +1020,7 @@ fn walker_thread(
             Ok((dir_path, depth, root_dev)) => {
                 process_directory(
                     &dir_path, depth, root_dev, work_tx, result_tx, in_flight, config, protection,
-                    cancel,
+                    stat, cancel,
                 );
                 // Mark this work item as completed.
                 let remaining = in_flight.fetch_sub(1, Ordering::AcqRel);
@@ -263,6 +1047,10 @@ fn walker_thread(
 /// dispatched without stat — the device check is deferred to when each child is
 /// processed. On directories with 60K+ children (e.g. /data/tmp), this eliminates
 /// tens of thousands of syscalls per scan pass.
+///
+/// Listing is two-pass: names and `d_type` first (no syscalls per child), then
+/// file sizes. A `.sbh-protect` marker anywhere in the listing therefore costs
+/// no child stats at all, and roots (depth 0, never emitted) skip size reads.
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 fn process_directory(
     dir_path: &Path,
@@ -273,6 +1061,7 @@ fn process_directory(
     in_flight: &AtomicUsize,
     config: &WalkerConfig,
     protection: &parking_lot::RwLock<ProtectionRegistry>,
+    stat: &StatStrategy,
     cancel: &AtomicBool,
 ) {
     // Check exclusion list.
@@ -291,8 +1080,8 @@ fn process_directory(
     // and WalkEntry emission. At depth 0 (root paths), device was already checked
     // at seed time in stream().
     let dir_meta = if depth > 0 {
-        match metadata_for_path(dir_path, config.follow_symlinks) {
-            Ok(m) => Some(m),
+        match stat.dir_metadata(dir_path, config.follow_symlinks) {
+            Ok(found) => Some(found),
             Err(_) => return,
         }
     } else {
@@ -302,31 +1091,39 @@ fn process_directory(
     // Cross-device guard: if this directory is on a different filesystem than
     // its root path, skip the entire subtree. This catches mount points that
     // were queued by the parent (which doesn't per-child stat for device).
+    // `root_dev` travels with the work item, so no ancestor is ever re-stat'd.
     if !config.cross_devices
-        && let Some(ref meta) = dir_meta
-        && device_id(meta) != root_dev
+        && let Some((_, dev)) = dir_meta
+        && dev != root_dev
     {
         return;
     }
 
     // Read directory entries, gracefully handling permission errors.
+    stat.count(|p| &p.dir_reads);
     let entries = match fs::read_dir(dir_path) {
         Ok(entries) => entries,
-        Err(err) if err.kind() == ErrorKind::PermissionDenied => return,
         Err(err) if err.kind() == ErrorKind::NotFound => return,
-        Err(_) => return, // Other errors: skip gracefully.
+        Err(err) => {
+            // Permission and other errors: skip gracefully.
+            diag::debug(
+                "walker",
+                format_args!("cannot read {}: {err}", dir_path.display()),
+            );
+            return;
+        }
     };
 
     // State for structural signals (incremental accumulation).
-    let mut signals = StructuralSignals::default();
-    let mut object_count = 0u32;
-    let mut total_count = 0u32;
-    let mut content_size: u64 = 0;
+    let mut tally = SignalTally::default();
 
     // Collect child directories during iteration; queue them AFTER the loop.
     // This prevents a race where a child dir is queued and processed by another
     // thread before we discover a .sbh-protect marker later in the listing.
     let mut pending_children: Vec<PathBuf> = Vec::new();
+    // Files whose sizes feed content_size_bytes; stat'd only after the whole
+    // listing is known to be unprotected.
+    let mut pending_files: Vec<fs::DirEntry> = Vec::new();
 
     for entry_result in entries {
         let Ok(entry) = entry_result else {
@@ -338,42 +1135,24 @@ fn process_directory(
         // ─── Incremental Signal Collection ───
         // We can check signals purely from the name without stat-ing the file.
         if let Some(name_os) = child_path.file_name() {
-            let name = name_os.to_string_lossy();
-            total_count += 1;
+            tally.observe(name_os);
 
             // Per-directory iteration budget: avoid spending seconds iterating
             // directories with tens of thousands of entries. Structural markers
             // appear early; MAX_ENTRIES_PER_DIR (65 536) is generous.
-            if total_count >= MAX_ENTRIES_PER_DIR {
+            if tally.total_count >= MAX_ENTRIES_PER_DIR {
                 break;
             }
 
-            match name.as_ref() {
-                // Detect .sbh-protect marker during iteration. If found,
-                // register the marker and bail — no children get queued.
-                ".sbh-protect" => {
-                    protection.write().register_marker(dir_path);
-                    return; // Skip rest of directory — protected subtree.
-                }
-                "incremental" => signals.has_incremental = true,
-                "deps" => signals.has_deps = true,
-                "build" => signals.has_build = true,
-                ".fingerprint" => signals.has_fingerprint = true,
-                ".git" => signals.has_git = true,
-                "cargo.toml" | "Cargo.toml" => signals.has_cargo_toml = true,
-                _ => {}
+            // Detect .sbh-protect marker during iteration. If found,
+            // register the marker and bail — no children get queued.
+            if name_os == MARKER_FILENAME {
+                protection.write().register_marker(dir_path);
+                return; // Skip rest of directory — protected subtree.
             }
-
-            // Check extension for object file heuristics.
-            if let Some(ext) = Path::new(name_os).extension() {
-                let ext_str = ext.to_string_lossy();
-                if ext_str.eq_ignore_ascii_case("o")
-                    || ext_str.eq_ignore_ascii_case("rlib")
-                    || ext_str.eq_ignore_ascii_case("rmeta")
-                    || ext_str.eq_ignore_ascii_case("d")
-                {
-                    object_count += 1;
-                }
+            // Quarantined trees are already staged for removal.
+            if name_os == QUARANTINE_DIRNAME {
+                continue;
             }
         }
 
@@ -393,34 +1172,85 @@ fn process_directory(
         // Determine if we should recurse.
         // If following symlinks, we must stat to see if the target is a dir.
         let is_dir = if config.follow_symlinks && ft.is_symlink() {
-            metadata_for_path(&child_path, true)
-                .map(|m| m.is_dir())
-                .unwrap_or(false)
+            stat.symlink_is_dir(&child_path)
         } else {
             ft.is_dir()
         };
 
-        // ─── Accumulate Content Size ───
-        // For files: lstat to get actual size. On ext4 this is ~1μs per call,
-        // so 2000 children ≈ 2ms — acceptable for accurate scoring.
-        // For child dirs: skip (their recursive size will be computed when they
-        // are processed as their own WalkEntry).
-        if !is_dir && let Ok(child_meta) = entry.metadata() {
-            content_size = content_size.saturating_add(child_meta.len());
+        // ─── Defer Content Size ───
+        // Files: size read after the listing (see pending_files). Only needed
+        // when this directory's own WalkEntry will be emitted (depth > 0), or
+        // when large-file discovery wants root-level files too.
+        // Child dirs: skip (their size is computed as their own WalkEntry).
+        if !is_dir {
+            if depth > 0 || stat.large_file_min_bytes.is_some() {
+                pending_files.push(entry);
+            }
+            continue;
         }
 
         // ─── Collect Child Dirs ───
         // Deferred dispatch: collect child dirs but don't queue yet. Queueing
         // happens after the loop, ensuring .sbh-protect markers are discovered
         // before any children are dispatched to other worker threads.
-        if depth < config.max_depth && is_dir && !config.excluded_paths.contains(&child_path) {
+        if depth < config.max_depth && !config.excluded_paths.contains(&child_path) {
             pending_children.push(child_path);
         }
     }
 
+    // ─── Accumulate Content Size ───
+    // For files: one size-only stat each. On ext4 this is ~1μs per call,
+    // so 2000 children ≈ 2ms — acceptable for accurate scoring.
+    // Files over the large-file threshold are remembered for emission and
+    // only counted here if they end up not being emitted (see below).
+    let mut content_size = 0u64;
+    let mut content_disk = 0u64;
+    let mut large_files: Vec<(PathBuf, u64, u64)> = Vec::new();
+    for entry in &pending_files {
+        let Some((size, disk)) = stat.file_size(entry) else {
+            continue;
+        };
+        if stat.large_file_min_bytes.is_some_and(|min| size >= min) {
+            large_files.push((entry.path(), size, disk));
+            continue;
+        }
+        content_size = content_size.saturating_add(size);
+        content_disk = content_disk.saturating_add(disk);
+    }
+
+    let listed = u64::from(tally.total_count);
+    let signals = tally.finish();
+
+    // ─── Classified Artifact Roots ───
+    // An artifact this confidently classified is kept or deleted whole, so
+    // walking its descendants one by one would change nothing but the cost.
+    // Size them here instead; anything that argues against treating the tree
+    // as a unit (a marker, a `.git`, a protected path) falls back to a walk.
+    if depth > 0
+        && !pending_children.is_empty()
+        && let Some(roots) = &stat.artifact_roots
+        && roots.is_root(dir_path, signals)
+        && let Some((subtree, subtree_disk)) = artifact_subtree_bytes(
+            &pending_children,
+            listed,
+            root_dev,
+            config,
+            protection,
+            stat,
+            roots.sample_after,
+            cancel,
+        )
+    {
+        content_size = content_size.saturating_add(subtree);
+        content_disk = content_disk.saturating_add(subtree_disk);
+        pending_children.clear();
+    }
+
     // ─── Deferred Recursion Dispatch ───
     // Now that we've confirmed no .sbh-protect marker exists (we would have
     // returned above), queue collected child dirs for worker threads.
+    // Pattern-protected children are dropped here rather than after a queue
+    // round-trip and a stat in another worker.
     //
     // Use send_timeout + cancel check instead of blocking send to prevent
     // thread leaks: when the scanner times out and sets the cancel flag,
@@ -429,6 +1259,9 @@ fn process_directory(
         if cancel.load(Ordering::Relaxed) {
             return;
         }
+        if protection.read().is_protected(&child_path) {
+            continue;
+        }
         in_flight.fetch_add(1, Ordering::Release);
         loop {
             match work_tx.send_timeout((child_path.clone(), depth + 1, root_dev), Duration::from_millis(100)) {
@@ -448,30 +1281,186 @@ fn process_directory(
         }
     }
 
-    // Finalize structural signals.
-    if total_count > 0 && object_count > 0 {
-        signals.mostly_object_files = object_count * 2 >= total_count;
+    // ─── Large Files ───
+    // A large file emitted as an entry of its own keeps its bytes out of this
+    // directory's size, so no two entries count the same bytes and a plan
+    // holding both can simply add them up. Skipped ones stay in the total.
+    let mut large_entries = Vec::new();
+    for (file_path, size, disk) in large_files {
+        let meta = if config.excluded_paths.contains(&file_path)
+            || protection.read().is_protected(&file_path)
+        {
+            None
+        } else {
+            stat.large_file_metadata(&file_path).ok()
+        };
+        if let Some(meta) = meta {
+            large_entries.push((file_path, meta));
+            continue;
+        }
+        content_size = content_size.saturating_add(size);
+        content_disk = content_disk.saturating_add(disk);
+    }
+
+    // Emit a WalkEntry for this directory itself (reuse stat from top of function).
+    if depth > 0
+        && let Some((mut emeta, _)) = dir_meta
+    {
+        // Override content_size_bytes with the sum of immediate children's file
+        // sizes. This is a lower bound (doesn't recurse into subdirs, capped at
+        // MAX_ENTRIES_PER_DIR children) but vastly better than the inode entry
+        // size (~4096) for scoring purposes. Classified roots add their subtree;
+        // large files emitted separately are left out.
+        if emeta.is_dir && content_size > 0 {
+            emeta.content_size_bytes = content_size;
+        }
+        if emeta.is_dir && content_disk > 0 {
+            emeta.content_disk_bytes = content_disk;
+        }
+        stat.count(|p| &p.entries);
+        let _ = result_tx.send(WalkEntry {
+            path: dir_path.to_path_buf(),
+            metadata: emeta,
+            depth,
+            structural_signals: signals,
+            is_open: false, // Caller sets this after walk using /proc scan.
+        });
+    }
+
+    // Emit large files as entries of their own (full stat only for these).
+    for (file_path, meta) in large_entries {
+        stat.count(|p| &p.entries);
+        let _ = result_tx.send(WalkEntry {
+            path: file_path,
+            metadata: meta,
+            depth: depth + 1,
+            structural_signals: StructuralSignals::default(),
+            is_open: false,
+        });
+    }
+}
+
+/// Total file bytes beneath the child directories of a classified artifact
+/// root, read in place rather than queued.
+///
+/// Stays on `root_dev` (unless crossing devices) and never follows symlinks.
+/// Once `sample_after` entries have been listed (`listed` of them in the root
+/// itself), only every `ARTIFACT_ROOT_SAMPLE_STRIDE`-th file and subdirectory
+/// of each further directory is read, weighted to stand in for the rest, so
+/// the total becomes an estimate. Returns `None` when a `.sbh-protect`
+/// marker, a `.git`, or a protected path turns up (the caller then walks the
+/// root normally) or when the walk is cancelled.
+#[allow(
+    clippy::too_many_arguments,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn artifact_subtree_bytes(
+    dirs: &[PathBuf],
+    mut listed: u64,
+    root_dev: u64,
+    config: &WalkerConfig,
+    protection: &parking_lot::RwLock<ProtectionRegistry>,
+    stat: &StatStrategy,
+    sample_after: u64,
+    cancel: &AtomicBool,
+) -> Option<(u64, u64)> {
+    let stride = sample_stride(listed, sample_after);
+    let weight = sample_scale(dirs.len(), stride);
+    let mut stack: Vec<(PathBuf, f64)> = dirs
+        .iter()
+        .step_by(stride)
+        .map(|dir| (dir.clone(), weight))
+        .collect();
+    let mut bytes = 0.0f64;
+    let mut disk = 0.0f64;
+
+    while let Some((dir, weight)) = stack.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if config.excluded_paths.contains(&dir) {
+            continue;
+        }
+        if protection.read().is_protected(&dir) {
+            return None;
+        }
+        let Ok((_, dev)) = stat.dir_metadata(&dir, false) else {
+            continue;
+        };
+        if !config.cross_devices && dev != root_dev {
+            continue;
+        }
+        stat.count(|p| &p.dir_reads);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        let mut subdirs = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if name == MARKER_FILENAME || name == ".git" {
+                return None;
+            }
+            listed += 1;
+            let Ok(ft) = entry.file_type() else {
+                continue;
+            };
+            if ft.is_dir() {
+                subdirs.push(entry.path());
+            } else if !ft.is_symlink() {
+                files.push(entry);
+            }
+        }
+
+        let stride = sample_stride(listed, sample_after);
+        let file_weight = weight * sample_scale(files.len(), stride);
+        for entry in files.iter().step_by(stride) {
+            if let Some((size, allocated)) = stat.file_size(entry) {
+                bytes += size as f64 * file_weight;
+                disk += allocated as f64 * file_weight;
+            }
+        }
+        let dir_weight = weight * sample_scale(subdirs.len(), stride);
+        stack.extend(
+            subdirs
+                .into_iter()
+                .step_by(stride)
+                .map(|subdir| (subdir, dir_weight)),
+        );
+    }
+
+    if sample_stride(listed, sample_after) > 1 {
+        diag::debug(
+            "walker",
+            format_args!(
+                "sampled subtree size under {} after {listed} entries",
+                dirs[0].parent().unwrap_or(&dirs[0]).display()
+            ),
+        );
+    }
+    Some((bytes as u64, disk as u64))
+}
+
+/// Read every n-th entry once more than `sample_after` (0 = never) are listed.
+const fn sample_stride(listed: u64, sample_after: u64) -> usize {
+    if sample_after > 0 && listed > sample_after {
+        ARTIFACT_ROOT_SAMPLE_STRIDE
+    } else {
+        1
     }
+}
 
-    // Emit a WalkEntry for this directory itself (reuse stat from top of function).
-    if depth > 0
-        && let Some(meta) = dir_meta
-    {
-        let mut emeta = entry_metadata(&meta);
-        // Override content_size_bytes with the sum of immediate children's file
-        // sizes. This is a lower bound (doesn't recurse into subdirs, capped at
-        // MAX_ENTRIES_PER_DIR children) but vastly better than the inode entry
-        // size (~4096) for scoring purposes.
-        if emeta.is_dir && content_size > 0 {
-            emeta.content_size_bytes = content_size;
-        }
-        let _ = result_tx.send(WalkEntry {
-            path: dir_path.to_path_buf(),
-            metadata: emeta,
-            depth,
-            structural_signals: signals,
-            is_open: false, // Caller sets this after walk using /proc scan.
-        });
+/// How many items each one read out of `len` stands for when every
+/// `stride`-th item is read.
+#[allow(clippy::cast_precision_loss)]
+fn sample_scale(len: usize, stride: usize) -> f64 {
+    let read = len.div_ceil(stride);
+    if read == 0 {
+        1.0
+    } else {
+        len as f64 / read as f64
     }
 }
 
@@ -515,9 +1504,12 @@ fn entry_metadata(meta: &fs::Metadata) -> EntryMetadata {
     {
         use std::os::unix::fs::MetadataExt;
         let size = meta.len();
+        let disk = allocated_bytes(meta);
         EntryMetadata {
             size_bytes: size,
             content_size_bytes: size, // Overridden for directories in process_directory.
+            disk_bytes: disk,
+            content_disk_bytes: disk,
             modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
             created: meta.created().ok(),
             is_dir: meta.is_dir(),
@@ -532,6 +1524,8 @@ fn entry_metadata(meta: &fs::Metadata) -> EntryMetadata {
         EntryMetadata {
             size_bytes: size,
             content_size_bytes: size,
+            disk_bytes: size,
+            content_disk_bytes: size,
             modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
             created: meta.created().ok(),
             is_dir: meta.is_dir(),
@@ -551,7 +1545,37 @@ fn metadata_for_path(path: &Path, follow_symlinks: bool) -> std::io::Result<fs::
 }
 
 /// Get device ID from metadata (for cross-device detection).
-fn device_id(meta: &fs::Metadata) -> u64 {
+/// Whether the block device behind `dev` (an `st_dev`) is a spinning disk.
+/// Partitions report through their parent disk.
+#[cfg(target_os = "linux")]
+fn is_rotational(dev: u64) -> bool {
+    let (major, minor) = dev_major_minor(dev);
+    let Ok(sys) = fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")) else {
+        return false;
+    };
+    [Some(sys.as_path()), sys.parent()]
+        .into_iter()
+        .flatten()
+        .find_map(|dir| fs::read_to_string(dir.join("queue/rotational")).ok())
+        .is_some_and(|flag| flag.trim() == "1")
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn is_rotational(_dev: u64) -> bool {
+    false
+}
+
+/// Split a Linux `dev_t` like glibc's `major()`/`minor()`.
+#[cfg(target_os = "linux")]
+const fn dev_major_minor(dev: u64) -> (u64, u64) {
+    let major = ((dev >> 32) & 0xffff_f000) | ((dev >> 8) & 0x0fff);
+    let minor = ((dev >> 12) & 0xffff_ff00) | (dev & 0x00ff);
+    (major, minor)
+}
+
+/// Device `meta` lives on (0 where unsupported).
+#[must_use]
+pub fn device_id(meta: &fs::Metadata) -> u64 {
     #[cfg(unix)]
     {
         use std::os::unix::fs::MetadataExt;
@@ -564,6 +1588,21 @@ fn device_id(meta: &fs::Metadata) -> u64 {
     }
 }
 
+/// Bytes allocated on disk for `meta` (`st_blocks` is in 512-byte units
+/// whatever the filesystem block size). Apparent size where unsupported.
+#[must_use]
+pub fn allocated_bytes(meta: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        meta.blocks().saturating_mul(512)
+    }
+    #[cfg(not(unix))]
+    {
+        meta.len()
+    }
+}
+
 /// Collect all open files as (device, inode) pairs.
 ///
 /// On Linux, scans /proc. Returns an empty set on non-Linux
@@ -634,6 +1673,15 @@ fn collect_open_files_linux() -> HashSet<(u64, u64)> {
 /// entries causes permanent blind spots.
 const MAX_ENTRIES_PER_DIR: u32 = 65_536;
 
+/// Combined classification confidence at which the walker stops descending
+/// and sizes the subtree in place. High enough for `node_modules/` or a cargo
+/// profile dir with `.fingerprint`; a bare `target/` (0.61) is still walked.
+const ARTIFACT_ROOT_MIN_CONFIDENCE: f64 = 0.65;
+
+/// Past the exact-entry budget, one in this many files and subdirectories of
+/// a classified root is read and weighted to stand in for the rest.
+const ARTIFACT_ROOT_SAMPLE_STRIDE: usize = 8;
+
 /// Maximum time to spend scanning /proc for open file ancestors.
 /// On agent swarms with many processes, /proc scanning can take minutes.
 /// A 5-second budget captures enough data for reliable veto decisions.
@@ -650,12 +1698,19 @@ const OPEN_FILES_MAX_PIDS: usize = 50_000;
 ///
 /// Returns `(ancestors, is_complete)`. If `is_complete` is false, scanning was
 /// truncated due to budget limits, and open checks may yield false negatives.
+///
+/// Windows has no `/proc` to enumerate, so there every file under the roots is
+/// probed instead; an empty `root_paths` finds nothing.
 pub fn collect_open_path_ancestors(root_paths: &[PathBuf]) -> (HashSet<PathBuf>, bool) {
     #[cfg(target_os = "linux")]
     {
         collect_open_path_ancestors_linux(root_paths)
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(windows)]
+    {
+        collect_open_path_ancestors_windows(root_paths)
+    }
+    #[cfg(not(any(target_os = "linux", windows)))]
     {
         let _ = root_paths;
         (HashSet::new(), true)
@@ -753,6 +1808,59 @@ fn collect_open_path_ancestors_linux(root_paths: &[PathBuf]) -> (HashSet<PathBuf
     (ancestors, !incomplete)
 }
 
+/// Probe each file under `root_paths` with an exclusive (share mode 0) open:
+/// Windows refuses it with a sharing violation while another process holds
+/// the file open for reading, writing, or deletion.
+#[cfg(windows)]
+fn collect_open_path_ancestors_windows(root_paths: &[PathBuf]) -> (HashSet<PathBuf>, bool) {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::time::Instant;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+
+    let mut ancestors = HashSet::new();
+    let deadline = Instant::now() + OPEN_FILES_SCAN_BUDGET;
+
+    for root in root_paths {
+        let root = crate::core::paths::resolve_absolute_path(root);
+        let mut pending = vec![root.clone()];
+        while let Some(path) = pending.pop() {
+            if Instant::now() >= deadline {
+                return (ancestors, false);
+            }
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_dir() {
+                if let Ok(entries) = fs::read_dir(&path) {
+                    pending.extend(entries.flatten().map(|entry| entry.path()));
+                }
+                continue;
+            }
+            if !meta.is_file() {
+                continue;
+            }
+            let in_use = fs::OpenOptions::new()
+                .read(true)
+                .share_mode(0)
+                .open(&path)
+                .is_err_and(|e| e.raw_os_error() == Some(ERROR_SHARING_VIOLATION));
+            if !in_use {
+                continue;
+            }
+            let mut current = Some(path.as_path());
+            while let Some(open) = current {
+                if !ancestors.insert(open.to_path_buf()) || open == root {
+                    break;
+                }
+                current = open.parent();
+            }
+        }
+    }
+
+    (ancestors, true)
+}
+
 /// Check if `path` is present in the open-ancestor index.
 #[must_use]
 pub fn is_path_open_by_ancestor<S: std::hash::BuildHasher>(
@@ -869,6 +1977,229 @@ pub fn is_path_open<S: std::hash::BuildHasher>(
     checker.is_path_open(path)
 }
 
+/// Longest a [`TreeWatcher::wait`] batch keeps collecting a busy tree.
+#[cfg(feature = "daemon")]
+const WATCH_SETTLE_CAP: Duration = Duration::from_secs(5);
+
+/// Filesystem changes under watched roots, reduced to the directories an
+/// incremental walk has to revisit ([`DirectoryWalker::stream_changes`]).
+#[cfg(feature = "daemon")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatchChanges {
+    /// Directories that appeared (created or renamed in). Walked in full:
+    /// their contents may predate the kernel watch on them.
+    pub created: BTreeSet<PathBuf>,
+    /// Directories whose listing or file sizes changed; re-read in place.
+    pub touched: BTreeSet<PathBuf>,
+    /// Paths that disappeared (deleted or renamed away). A path that came
+    /// back stays listed here and in `created`, so consumers drop it first.
+    pub removed: BTreeSet<PathBuf>,
+    /// Events were lost (queue overflow or a watcher error); only a full
+    /// walk can be trusted now.
+    pub rescan: bool,
+}
+
+#[cfg(feature = "daemon")]
+impl WatchChanges {
+    pub fn is_empty(&self) -> bool {
+        !self.rescan
+            && self.created.is_empty()
+            && self.touched.is_empty()
+            && self.removed.is_empty()
+    }
+
+    /// Fold one watcher event into the change set.
+    pub fn record(&mut self, event: &notify::Event) {
+        if event.need_rescan() {
+            self.rescan = true;
+            return;
+        }
+        match event.kind {
+            EventKind::Create(kind) => {
+                for path in &event.paths {
+                    let is_dir = match kind {
+                        CreateKind::Folder => true,
+                        CreateKind::File => false,
+                        _ => path_is_dir(path),
+                    };
+                    self.appeared(path, is_dir);
+                }
+            }
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in &event.paths {
+                    self.disappeared(path);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let [from, to] = event.paths.as_slice() {
+                    self.disappeared(from);
+                    self.appeared(to, path_is_dir(to));
+                }
+            }
+            // `To`, and backends (FSEvents) that only say "renamed": the
+            // path's existence tells which side of the rename it is.
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &event.paths {
+                    match fs::symlink_metadata(path) {
+                        Ok(meta) => self.appeared(path, meta.is_dir()),
+                        Err(_) => self.disappeared(path),
+                    }
+                }
+            }
+            EventKind::Modify(_) => {
+                for path in &event.paths {
+                    self.touch_parent(path);
+                }
+            }
+            EventKind::Access(_) | EventKind::Any | EventKind::Other => {}
+        }
+    }
+
+    fn absorb(&mut self, event: notify::Result<notify::Event>) {
+        match event {
+            Ok(event) => self.record(&event),
+            Err(err) => {
+                diag::warn(
+                    "walker",
+                    format_args!("watcher error, next pass walks in full: {err}"),
+                );
+                self.rescan = true;
+            }
+        }
+    }
+
+    fn appeared(&mut self, path: &Path, is_dir: bool) {
+        if is_dir {
+            self.created.insert(path.to_path_buf());
+        }
+        self.touch_parent(path);
+    }
+
+    fn disappeared(&mut self, path: &Path) {
+        remove_subtree(&mut self.created, path);
+        remove_subtree(&mut self.touched, path);
+        if !path.ancestors().skip(1).any(|a| self.removed.contains(a)) {
+            remove_subtree(&mut self.removed, path);
+            self.removed.insert(path.to_path_buf());
+        }
+        self.touch_parent(path);
+    }
+
+    fn touch_parent(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            self.touched.insert(parent.to_path_buf());
+        }
+    }
+}
+
+#[cfg(feature = "daemon")]
+fn path_is_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir())
+}
+
+/// Drop `path` and everything beneath it. Path ordering is component-wise,
+/// so a subtree is one contiguous range starting at `path`.
+#[cfg(feature = "daemon")]
+fn remove_subtree(set: &mut BTreeSet<PathBuf>, path: &Path) {
+    let doomed: Vec<PathBuf> = set
+        .range(path.to_path_buf()..)
+        .take_while(|p| p.starts_with(path))
+        .cloned()
+        .collect();
+    for p in doomed {
+        set.remove(&p);
+    }
+}
+
+/// Recursive kernel watch (inotify on Linux, FSEvents on macOS) over the
+/// scan roots, so later passes only revisit what changed.
+///
+/// Start it *before* the initial full walk: anything that changes while the
+/// walk runs is then reported again instead of missed.
+#[cfg(feature = "daemon")]
+pub struct TreeWatcher {
+    roots: Vec<PathBuf>,
+    events: channel::Receiver<notify::Result<notify::Event>>,
+    // Dropping the watcher removes the kernel watches.
+    _watcher: notify::RecommendedWatcher,
+}
+
+#[cfg(feature = "daemon")]
+impl TreeWatcher {
+    /// Watch every root recursively. Missing roots are skipped; any other
+    /// failure (typically the inotify limit, `fs.inotify.max_user_watches`)
+    /// is an error so callers can fall back to full walks.
+    pub fn new(roots: &[PathBuf]) -> Result<Self> {
+        let (tx, events) = channel::unbounded();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let _ = tx.send(event);
+            })
+            .map_err(|err| SbhError::Runtime {
+                details: format!("filesystem watcher unavailable: {err}"),
+            })?;
+        let mut live_roots = Vec::with_capacity(roots.len());
+        for root in roots {
+            match watcher.watch(root, RecursiveMode::Recursive) {
+                Ok(()) => live_roots.push(root.clone()),
+                Err(err) if matches!(err.kind, notify::ErrorKind::PathNotFound) => {
+                    diag::warn(
+                        "walker",
+                        format_args!("not watching missing root {}", root.display()),
+                    );
+                }
+                Err(err) => {
+                    return Err(SbhError::Runtime {
+                        details: format!("cannot watch {}: {err}", root.display()),
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            roots: live_roots,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Roots actually under watch.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Everything reported since the last call, without blocking.
+    pub fn drain(&self) -> WatchChanges {
+        let mut changes = WatchChanges::default();
+        for event in self.events.try_iter() {
+            changes.absorb(event);
+        }
+        changes
+    }
+
+    /// Wait up to `timeout` for activity, then keep collecting until the
+    /// tree has been quiet for `settle`, so a build writing thousands of
+    /// files arrives as one batch rather than thousands.
+    pub fn wait(&self, timeout: Duration, settle: Duration) -> WatchChanges {
+        let mut changes = WatchChanges::default();
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => changes.absorb(event),
+            Err(channel::RecvTimeoutError::Timeout) => return changes,
+            Err(channel::RecvTimeoutError::Disconnected) => {
+                changes.rescan = true;
+                return changes;
+            }
+        }
+        let deadline = Instant::now() + WATCH_SETTLE_CAP;
+        while Instant::now() < deadline {
+            match self.events.recv_timeout(settle) {
+                Ok(event) => changes.absorb(event),
+                Err(_) => break,
+            }
+        }
+        changes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -909,6 +2240,51 @@ mod tests {
         assert!(paths.contains(&tmp.path().join("c")));
     }
 
+    #[test]
+    fn device_limits_prefer_longest_override_then_rotational() {
+        let limits = DeviceLimits {
+            rotational: Some(2),
+            overrides: vec![(PathBuf::from("/data"), 4), (PathBuf::from("/data/hdd"), 1)],
+        };
+        assert_eq!(limits.cap_for(Path::new("/data/hdd/x"), || false), Some(1));
+        assert_eq!(limits.cap_for(Path::new("/data/nvme"), || true), Some(4));
+        assert_eq!(limits.cap_for(Path::new("/home"), || true), Some(2));
+        assert_eq!(limits.cap_for(Path::new("/home"), || false), None);
+        assert_eq!(
+            DeviceLimits::default().cap_for(Path::new("/home"), || true),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn dev_major_minor_matches_glibc_encoding() {
+        assert_eq!(dev_major_minor(0x0801), (8, 1));
+        assert_eq!(dev_major_minor(0x0101_0300), (259, 4096));
+    }
+
+    #[test]
+    fn capped_and_shared_pools_walk_every_root() {
+        let tmp = TempDir::new().unwrap();
+        let slow = tmp.path().join("slow");
+        let fast = tmp.path().join("fast");
+        fs::create_dir_all(slow.join("a").join("b")).unwrap();
+        fs::create_dir_all(fast.join("c")).unwrap();
+
+        let mut config = test_config(&slow);
+        config.root_paths.push(fast.clone());
+        let walker = DirectoryWalker::new(config, ProtectionRegistry::marker_only())
+            .with_device_limits(DeviceLimits {
+                rotational: None,
+                overrides: vec![(slow.clone(), 1)],
+            });
+        let paths: Vec<_> = walker.walk().unwrap().into_iter().map(|e| e.path).collect();
+
+        assert!(paths.contains(&slow.join("a")));
+        assert!(paths.contains(&slow.join("a").join("b")));
+        assert!(paths.contains(&fast.join("c")));
+    }
+
     #[test]
     fn respects_max_depth() {
         let tmp = TempDir::new().unwrap();
@@ -1064,6 +2440,313 @@ mod tests {
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn isolated_walk_reports_each_root_independently() {
+        let tmp = TempDir::new().unwrap();
+        let good = tmp.path().join("good");
+        fs::create_dir_all(good.join("target/debug")).unwrap();
+        let file_root = tmp.path().join("plain.txt");
+        fs::write(&file_root, "x").unwrap();
+        let missing = tmp.path().join("missing");
+
+        let config = WalkerConfig {
+            root_paths: vec![good.clone(), file_root, missing],
+            ..test_config(tmp.path())
+        };
+        let walker = DirectoryWalker::new(config, ProtectionRegistry::marker_only());
+        let walk = walker.walk_isolated(None);
+
+        assert_eq!(walk.roots.len(), 3);
+        assert_eq!(walk.roots[0].status, RootStatus::Complete);
+        assert!(walk.roots[0].entries >= 2);
+        assert!(matches!(walk.roots[1].status, RootStatus::Skipped { .. }));
+        assert!(matches!(walk.roots[2].status, RootStatus::Skipped { .. }));
+        assert_eq!(walk.entries.len(), walk.roots[0].entries);
+        assert!(walk.entries.iter().all(|e| e.path.starts_with(&good)));
+        assert!(walk.is_complete());
+    }
+
+    #[test]
+    fn isolated_walk_streams_entries_to_callback() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("a/b/c")).unwrap();
+        fs::write(tmp.path().join("a/file.txt"), "x").unwrap();
+        let walker =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only());
+
+        let mut seen = Vec::new();
+        let reports = walker.walk_isolated_with(None, |entry| seen.push(entry.path));
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].entries, seen.len());
+        assert!(seen.contains(&tmp.path().join("a/b/c")));
+    }
+
+    #[test]
+    fn isolated_walk_marks_roots_partial_on_timeout() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("a/b")).unwrap();
+        let walker =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only());
+        let walk = walker.walk_isolated(Some(Duration::ZERO));
+
+        assert_eq!(walk.roots.len(), 1);
+        assert!(matches!(walk.roots[0].status, RootStatus::Partial { .. }));
+        assert!(!walk.is_complete());
+    }
+
+    #[test]
+    fn syscall_profile_skips_stats_in_protected_dirs_and_roots() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("root-file.txt"), "x").unwrap();
+        let protected = tmp.path().join("keep");
+        fs::create_dir_all(&protected).unwrap();
+        for name in ["a.bin", "b.bin", ".sbh-protect", "c.bin"] {
+            fs::write(protected.join(name), "x").unwrap();
+        }
+        let other = tmp.path().join("other");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join("data.bin"), "xyz").unwrap();
+
+        for mode in [StatMode::Portable, StatMode::Reduced] {
+            let profile = Arc::new(SyscallProfile::default());
+            let walker =
+                DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                    .with_stat_mode(mode)
+                    .with_syscall_profile(Arc::clone(&profile));
+            let entries = walker.walk().unwrap();
+            let counts = profile.snapshot();
+
+            // Only other/data.bin is size-stat'd: root files are never needed and
+            // the protected listing bails before any stats.
+            assert_eq!(counts.file_stats, 1, "{mode:?}");
+            assert_eq!(counts.entries, entries.len() as u64, "{mode:?}");
+            let other_entry = entries.iter().find(|e| e.path == other).unwrap();
+            assert_eq!(other_entry.metadata.content_size_bytes, 3, "{mode:?}");
+            assert!(counts.per_1k_entries() > 0.0);
+        }
+    }
+
+    #[test]
+    fn large_file_discovery_emits_big_files_only_when_enabled() {
+        let tmp = TempDir::new().unwrap();
+        let big_root = tmp.path().join("core.4242");
+        fs::File::create(&big_root).unwrap().set_len(8192).unwrap();
+        let logs = tmp.path().join("logs");
+        fs::create_dir_all(&logs).unwrap();
+        fs::File::create(logs.join("huge.log"))
+            .unwrap()
+            .set_len(8192)
+            .unwrap();
+        fs::write(logs.join("small.log"), "x").unwrap();
+        let keep = tmp.path().join("keep");
+        fs::create_dir_all(&keep).unwrap();
+        fs::write(keep.join(".sbh-protect"), "").unwrap();
+        fs::File::create(keep.join("vm.qcow2"))
+            .unwrap()
+            .set_len(8192)
+            .unwrap();
+
+        let files = |walker: DirectoryWalker| -> Vec<PathBuf> {
+            let mut found: Vec<PathBuf> = walker
+                .walk()
+                .unwrap()
+                .into_iter()
+                .filter(|e| !e.metadata.is_dir)
+                .map(|e| e.path)
+                .collect();
+            found.sort();
+            found
+        };
+        let off = DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only());
+        assert!(files(off).is_empty());
+
+        for mode in [StatMode::Portable, StatMode::Reduced] {
+            let on =
+                DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                    .with_stat_mode(mode)
+                    .with_large_files(4096);
+            assert_eq!(
+                files(on),
+                vec![big_root.clone(), logs.join("huge.log")],
+                "{mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn large_files_under_target_are_not_counted_twice() {
+        let tmp = TempDir::new().unwrap();
+        let target = tmp.path().join("proj").join("target");
+        for (dir, len) in [("deps", 50), ("incremental", 30), (".fingerprint", 20)] {
+            fs::create_dir_all(target.join(dir)).unwrap();
+            fs::write(target.join(dir).join("out"), vec![b'x'; len]).unwrap();
+        }
+        fs::write(target.join("CACHEDIR.TAG"), vec![b'x'; 100]).unwrap();
+        fs::File::create(target.join("app.tar"))
+            .unwrap()
+            .set_len(8192)
+            .unwrap();
+        let total = 100 + 8192 + 100;
+
+        for sampling in [None, Some(0)] {
+            let entries =
+                DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                    .with_artifact_roots(sampling)
+                    .with_large_files(4096)
+                    .walk()
+                    .unwrap();
+            let size_of = |path: &Path| {
+                entries
+                    .iter()
+                    .find(|e| e.path == path)
+                    .map(|e| e.metadata.content_size_bytes)
+            };
+            assert_eq!(size_of(&target.join("app.tar")), Some(8192), "{sampling:?}");
+            let expected = if sampling.is_some() { 200 } else { 100 };
+            assert_eq!(size_of(&target), Some(expected), "{sampling:?}");
+            // A plan holding every entry under target/ adds them up.
+            let sum: u64 = entries
+                .iter()
+                .filter(|e| e.path.starts_with(&target))
+                .map(|e| e.metadata.content_size_bytes)
+                .sum();
+            assert_eq!(sum, total, "{sampling:?}");
+        }
+    }
+
+    #[test]
+    fn classified_artifact_roots_are_sized_whole_without_descending() {
+        let tmp = TempDir::new().unwrap();
+        let modules = tmp.path().join("app").join("node_modules");
+        for pkg in ["left-pad", "react"] {
+            let lib = modules.join(pkg).join("lib");
+            fs::create_dir_all(&lib).unwrap();
+            fs::write(lib.join("index.js"), vec![b'x'; 100]).unwrap();
+        }
+        fs::write(modules.join(".package-lock.json"), vec![b'x'; 10]).unwrap();
+        let src = tmp.path().join("app").join("src").join("nested");
+        fs::create_dir_all(&src).unwrap();
+
+        let walk = |sampling: Option<u64>| {
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                .with_artifact_roots(sampling)
+                .walk()
+                .unwrap()
+        };
+
+        let entries = walk(Some(0));
+        let root = entries.iter().find(|e| e.path == modules).unwrap();
+        assert_eq!(root.metadata.content_size_bytes, 210);
+        assert!(
+            !entries
+                .iter()
+                .any(|e| e.path.starts_with(modules.join("react")))
+        );
+        assert!(
+            entries.iter().any(|e| e.path == src),
+            "unclassified dirs still walked"
+        );
+
+        let exhaustive = walk(None);
+        assert!(
+            exhaustive
+                .iter()
+                .any(|e| e.path == modules.join("react").join("lib"))
+        );
+
+        // A marker anywhere inside means the tree is not one unit.
+        fs::write(modules.join("react").join("lib").join(".sbh-protect"), "").unwrap();
+        let entries = walk(Some(0));
+        assert!(
+            entries
+                .iter()
+                .any(|e| e.path == modules.join("left-pad").join("lib"))
+        );
+        assert!(
+            !entries
+                .iter()
+                .any(|e| e.path == modules.join("react").join("lib"))
+        );
+    }
+
+    #[test]
+    fn directories_count_allocated_blocks_beside_apparent_size() {
+        let tmp = TempDir::new().unwrap();
+        let cache = tmp.path().join("cache");
+        fs::create_dir_all(&cache).unwrap();
+        let sparse = fs::File::create(cache.join("sparse.img")).unwrap();
+        sparse.set_len(64 * 1024 * 1024).unwrap();
+        fs::write(cache.join("small.txt"), b"0123456789").unwrap();
+        let allocated: u64 = ["sparse.img", "small.txt"]
+            .iter()
+            .map(|name| allocated_bytes(&fs::symlink_metadata(cache.join(name)).unwrap()))
+            .sum();
+
+        let entries =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                .walk()
+                .unwrap();
+        let dir = entries.iter().find(|e| e.path == cache).unwrap();
+        assert_eq!(dir.metadata.content_size_bytes, 64 * 1024 * 1024 + 10);
+        assert_eq!(dir.metadata.content_disk_bytes, allocated);
+        assert!(dir.metadata.content_disk_bytes < dir.metadata.content_size_bytes);
+    }
+
+    #[test]
+    fn inspect_reads_one_path_like_the_walk_does() {
+        let tmp = TempDir::new().unwrap();
+        let modules = tmp.path().join("app").join("node_modules");
+        let lib = modules.join("react").join("lib");
+        fs::create_dir_all(&lib).unwrap();
+        fs::write(lib.join("index.js"), vec![b'x'; 100]).unwrap();
+        fs::write(modules.join(".package-lock.json"), vec![b'x'; 10]).unwrap();
+        let sibling = tmp.path().join("app").join("src");
+        fs::create_dir_all(&sibling).unwrap();
+
+        let walker =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                .with_artifact_roots(Some(0));
+        let entries = walker.walk().unwrap();
+        let expected = entries.iter().find(|e| e.path == modules).unwrap();
+
+        let inspected = walker.inspect(&modules).unwrap();
+        assert_eq!(inspected.metadata, expected.metadata);
+        assert_eq!(inspected.structural_signals, expected.structural_signals);
+        assert_eq!(inspected.metadata.content_size_bytes, 110);
+
+        let file = walker.inspect(&lib.join("index.js")).unwrap();
+        assert!(!file.metadata.is_dir);
+        assert_eq!(file.metadata.size_bytes, 100);
+
+        fs::write(sibling.join(".sbh-protect"), "").unwrap();
+        assert!(walker.inspect(&sibling).is_none());
+        assert!(walker.inspect(&tmp.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn classified_root_sampling_extrapolates_past_budget() {
+        let tmp = TempDir::new().unwrap();
+        let modules = tmp.path().join("node_modules");
+        for pkg in 0..64 {
+            let dir = modules.join(format!("pkg-{pkg}"));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("index.js"), vec![b'x'; 1000]).unwrap();
+        }
+
+        let profile = Arc::new(SyscallProfile::default());
+        let entries =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                .with_artifact_roots(Some(1))
+                .with_syscall_profile(Arc::clone(&profile))
+                .walk()
+                .unwrap();
+        let root = entries.iter().find(|e| e.path == modules).unwrap();
+        assert_eq!(root.metadata.content_size_bytes, 64_000);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(profile.snapshot().file_stats, 8);
+    }
+
     #[test]
     fn signals_from_children_detects_rust_markers() {
         let names = vec![
@@ -1259,4 +2942,124 @@ mod tests {
             "should detect symlinked .git"
         );
     }
+
+    #[cfg(feature = "daemon")]
+    #[test]
+    fn watch_changes_collapse_events_to_dirs_to_revisit() {
+        use notify::event::{Event, Flag, RemoveKind};
+
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let target = root.join("proj").join("target");
+        let mut changes = WatchChanges::default();
+
+        changes.record(&Event::new(EventKind::Create(CreateKind::Folder)).add_path(target.clone()));
+        changes
+            .record(&Event::new(EventKind::Create(CreateKind::File)).add_path(target.join("a.o")));
+        assert!(changes.created.contains(&target));
+        assert!(changes.touched.contains(&root.join("proj")));
+        assert!(changes.touched.contains(&target));
+
+        // Deleting the tree again cancels the walk it would have needed.
+        changes.record(&Event::new(EventKind::Remove(RemoveKind::Folder)).add_path(target.clone()));
+        assert!(changes.created.is_empty());
+        assert!(!changes.touched.contains(&target));
+        assert!(changes.removed.contains(&target));
+        changes
+            .record(&Event::new(EventKind::Remove(RemoveKind::File)).add_path(target.join("a.o")));
+        assert_eq!(changes.removed.len(), 1, "covered by the removed parent");
+        assert!(!changes.rescan);
+
+        changes.record(&Event::new(EventKind::Other).set_flag(Flag::Rescan));
+        assert!(changes.rescan);
+    }
+
+    #[cfg(feature = "daemon")]
+    #[test]
+    fn stream_changes_walks_new_dirs_at_full_walk_depth() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let proj = root.join("proj");
+        fs::create_dir_all(&proj).unwrap();
+        fs::create_dir_all(root.join("keep")).unwrap();
+        protection::create_marker(&root.join("keep"), None).unwrap();
+
+        let target = proj.join("target");
+        fs::create_dir_all(target.join("debug").join("deps")).unwrap();
+        fs::write(target.join("debug").join("app"), b"binary").unwrap();
+        let kept = root.join("keep").join("target");
+        fs::create_dir_all(&kept).unwrap();
+
+        let changes = WatchChanges {
+            created: [target.clone(), target.join("debug"), kept].into(),
+            touched: [proj.clone()].into(),
+            ..WatchChanges::default()
+        };
+        let mut config = test_config(root);
+        config.max_depth = 3;
+        let walker = DirectoryWalker::new(config, ProtectionRegistry::marker_only());
+        let entries: Vec<WalkEntry> = walker.stream_changes(&changes).unwrap().iter().collect();
+        let depth_of = |path: &Path| {
+            let found: Vec<_> = entries.iter().filter(|e| e.path == path).collect();
+            assert!(found.len() <= 1, "{} emitted twice", path.display());
+            found.first().map(|e| e.depth)
+        };
+
+        assert_eq!(depth_of(&proj), Some(1));
+        assert_eq!(depth_of(&target), Some(2));
+        assert_eq!(depth_of(&target.join("debug")), Some(3));
+        // One level past max_depth, as a full walk would stop.
+        assert_eq!(depth_of(&target.join("debug").join("deps")), None);
+        assert!(
+            !entries
+                .iter()
+                .any(|e| e.path.starts_with(root.join("keep"))),
+            "marker above a changed dir must be honored"
+        );
+    }
+
+    #[cfg(feature = "daemon")]
+    #[test]
+    fn stream_changes_resizes_the_enclosing_artifact_root() {
+        let tmp = TempDir::new().unwrap();
+        let modules = tmp.path().join("app").join("node_modules");
+        let pkg = modules.join("react").join("lib");
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(pkg.join("index.js"), vec![b'x'; 100]).unwrap();
+
+        let changes = WatchChanges {
+            created: [pkg].into(),
+            touched: [modules.join("react")].into(),
+            ..WatchChanges::default()
+        };
+        let walker =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only())
+                .with_artifact_roots(Some(0));
+        let entries: Vec<WalkEntry> = walker.stream_changes(&changes).unwrap().iter().collect();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, modules);
+        assert_eq!(entries[0].depth, 2);
+        assert_eq!(entries[0].metadata.content_size_bytes, 100);
+    }
+
+    #[cfg(feature = "daemon")]
+    #[test]
+    fn tree_watcher_reports_new_artifact_dirs() {
+        let tmp = TempDir::new().unwrap();
+        // FSEvents reports resolved paths (/private/var on macOS).
+        let root = tmp.path().canonicalize().unwrap();
+        let watcher = TreeWatcher::new(std::slice::from_ref(&root)).unwrap();
+        assert_eq!(watcher.roots(), std::slice::from_ref(&root));
+
+        let target = root.join("target");
+        fs::create_dir(&target).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut changes = WatchChanges::default();
+        while !changes.created.contains(&target) && Instant::now() < deadline {
+            let batch = watcher.wait(Duration::from_secs(1), Duration::from_millis(100));
+            changes.created.extend(batch.created);
+        }
+        assert!(changes.created.contains(&target));
+    }
 }
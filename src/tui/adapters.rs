@@ -2,14 +2,16 @@
 
 #![allow(missing_docs)]
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
 use crate::core::errors::Result;
-use crate::daemon::self_monitor::DAEMON_STATE_STALE_THRESHOLD_SECS;
-use crate::daemon::self_monitor::DaemonState;
+use crate::core::state::{
+    DAEMON_STATE_STALE_THRESHOLD_SECS, DaemonState, StateParseError, StateReadError, StateSnapshot,
+    read_state,
+};
 use crate::monitor::fs_stats::FsStatsCollector;
 use crate::platform::pal::{Platform, detect_platform};
 
@@ -65,6 +67,7 @@ pub enum StateFreshness {
     Stale { age: Duration },
     Missing,
     Malformed,
+    NewerSchema { schema: u32 },
     ReadError(String),
 }
 
@@ -201,6 +204,17 @@ impl DashboardStateAdapter {
                     warnings: SchemaWarnings::default(),
                 }
             }
+            StateReadOutcome::NewerSchema { schema } => {
+                let fallback_mounts = self.collect_fallback_mounts(monitor_paths);
+                let source = fallback_source(&fallback_mounts);
+                DashboardSnapshot {
+                    daemon_state: None,
+                    mounts: fallback_mounts,
+                    freshness: StateFreshness::NewerSchema { schema },
+                    source,
+                    warnings: SchemaWarnings::default(),
+                }
+            }
             StateReadOutcome::ReadError(details) => {
                 let fallback_mounts = self.collect_fallback_mounts(monitor_paths);
                 let source = fallback_source(&fallback_mounts);
@@ -216,42 +230,22 @@ impl DashboardStateAdapter {
     }
 
     fn read_state_outcome(&self, state_file: &Path) -> StateReadOutcome {
-        let metadata = match std::fs::metadata(state_file) {
-            Ok(metadata) => metadata,
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
-                return StateReadOutcome::Missing;
-            }
-            Err(error) => return StateReadOutcome::ReadError(error.to_string()),
-        };
-
-        let raw = match std::fs::read_to_string(state_file) {
-            Ok(raw) => raw,
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
-                return StateReadOutcome::Missing;
+        // `DaemonState` uses `#[serde(default)]` so that missing fields
+        // get zero-values rather than hard-failing deserialization.
+        let StateSnapshot { state, age, keys } = match read_state(state_file) {
+            Ok(snapshot) => snapshot,
+            Err(StateReadError::Missing) => return StateReadOutcome::Missing,
+            Err(StateReadError::Io(details)) => return StateReadOutcome::ReadError(details),
+            Err(StateReadError::Parse(StateParseError::NewerSchema(schema))) => {
+                return StateReadOutcome::NewerSchema { schema };
             }
-            // Binary garbage / invalid UTF-8 is malformed content, not a read error.
-            Err(error) if error.kind() == std::io::ErrorKind::InvalidData => {
+            Err(StateReadError::Parse(StateParseError::Malformed(_))) => {
                 return StateReadOutcome::Malformed;
             }
-            Err(error) => return StateReadOutcome::ReadError(error.to_string()),
         };
 
-        // Detect schema drift: parse as untyped Value first to compare keys.
-        let warnings = detect_schema_drift(&raw);
-
-        // `DaemonState` uses `#[serde(default)]` so that missing fields
-        // get zero-values rather than hard-failing deserialization.
-        let Ok(state) = serde_json::from_str::<DaemonState>(&raw) else {
-            return StateReadOutcome::Malformed;
-        };
-
-        let modified = match metadata.modified() {
-            Ok(modified) => modified,
-            Err(error) => return StateReadOutcome::ReadError(error.to_string()),
-        };
-        let age = SystemTime::now()
-            .duration_since(modified)
-            .unwrap_or_default();
+        // Detect schema drift by comparing the keys the file carried.
+        let warnings = detect_schema_drift(&keys);
 
         if age > self.stale_threshold {
             StateReadOutcome::Stale {
@@ -295,6 +289,7 @@ impl StateAdapter for DashboardStateAdapter {
             StateReadOutcome::Stale { .. }
             | StateReadOutcome::Missing
             | StateReadOutcome::Malformed
+            | StateReadOutcome::NewerSchema { .. }
             | StateReadOutcome::ReadError(_) => None,
         }
     }
@@ -317,11 +312,15 @@ enum StateReadOutcome {
     },
     Missing,
     Malformed,
+    NewerSchema {
+        schema: u32,
+    },
     ReadError(String),
 }
 
 /// Expected top-level keys in `DaemonState`. Used for drift detection.
 const EXPECTED_STATE_KEYS: &[&str] = &[
+    "state_schema",
     "version",
     "pid",
     "started_at",
@@ -333,29 +332,22 @@ const EXPECTED_STATE_KEYS: &[&str] = &[
     "counters",
     "memory_rss_bytes",
     "policy_mode",
+    "rates",
 ];
 
 /// Compare JSON keys against expected `DaemonState` fields.
-fn detect_schema_drift(raw: &str) -> SchemaWarnings {
-    let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) else {
-        return SchemaWarnings::default();
-    };
-    let Some(obj) = value.as_object() else {
-        return SchemaWarnings::default();
-    };
-
-    let actual_keys: std::collections::BTreeSet<&str> = obj.keys().map(String::as_str).collect();
-    let expected_keys: std::collections::BTreeSet<&str> =
-        EXPECTED_STATE_KEYS.iter().copied().collect();
-
+fn detect_schema_drift(actual_keys: &BTreeSet<String>) -> SchemaWarnings {
     let unknown_fields: Vec<String> = actual_keys
-        .difference(&expected_keys)
-        .map(|k| (*k).to_string())
+        .iter()
+        .filter(|key| !EXPECTED_STATE_KEYS.contains(&key.as_str()))
+        .cloned()
         .collect();
-    let missing_fields: Vec<String> = expected_keys
-        .difference(&actual_keys)
-        .map(|k| (*k).to_string())
+    let mut missing_fields: Vec<String> = EXPECTED_STATE_KEYS
+        .iter()
+        .filter(|key| !actual_keys.contains(**key))
+        .map(|key| (*key).to_string())
         .collect();
+    missing_fields.sort_unstable();
 
     SchemaWarnings {
         unknown_fields,
@@ -405,14 +397,12 @@ mod tests {
     use std::collections::HashMap;
     use std::path::{Path, PathBuf};
     use std::sync::Arc;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
 
     use filetime::{FileTime, set_file_mtime};
     use tempfile::TempDir;
 
-    use crate::daemon::self_monitor::{
-        BallastState, Counters, LastScanState, MountPressure, PressureState,
-    };
+    use crate::core::state::{BallastState, Counters, LastScanState, MountPressure, PressureState};
     use crate::platform::pal::{FsStats, MemoryInfo, MockPlatform, MountPoint, PlatformPaths};
 
     use super::DashboardStateAdapter;
@@ -680,6 +670,9 @@ mod tests {
                 available: 9,
                 total: 10,
                 released: 1,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: Some("2026-02-16T00:00:30Z".to_string()),
@@ -695,6 +688,12 @@ mod tests {
             },
             policy_mode: "enforce".into(),
             memory_rss_bytes: 1024 * 1024,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
         }
     }
 
@@ -858,6 +857,32 @@ mod tests {
         assert!(snapshot.daemon_state.is_some());
     }
 
+    #[test]
+    fn newer_state_schema_falls_back_instead_of_misreading() {
+        let tmp = TempDir::new().expect("tempdir");
+        let state_path = tmp.path().join("state.json");
+
+        let mut value: serde_json::Value =
+            serde_json::to_value(sample_daemon_state()).expect("to value");
+        value["state_schema"] = serde_json::json!(99);
+        std::fs::write(&state_path, serde_json::to_string(&value).expect("json")).expect("write");
+
+        let adapter = DashboardStateAdapter::new(
+            mock_platform(),
+            Duration::from_secs(90),
+            Duration::from_secs(1),
+        );
+        let snapshot = adapter.load_snapshot(&state_path, &[PathBuf::from("/tmp/work")]);
+
+        assert_eq!(
+            snapshot.freshness,
+            StateFreshness::NewerSchema { schema: 99 }
+        );
+        assert!(snapshot.daemon_state.is_none());
+        assert_eq!(snapshot.source, SnapshotSource::FilesystemFallback);
+        assert!(adapter.read_state(&state_path).is_none());
+    }
+
     #[test]
     fn schema_warnings_default_is_no_drift() {
         let w = SchemaWarnings::default();
@@ -10,7 +10,7 @@
 //! All shortcut actions are deterministic and keyboard-first. The feature can be
 //! disabled via [`HintVerbosity::Off`] for operators who prefer a minimal dashboard.
 
-use crate::daemon::self_monitor::DaemonState;
+use crate::core::state::DaemonState;
 use crate::tui::model::Screen;
 use crate::tui::preferences::HintVerbosity;
 
@@ -13,7 +13,8 @@ use std::time::{Duration, Instant};
 
 use ftui::{KeyEvent, MouseEvent};
 
-use crate::daemon::self_monitor::DaemonState;
+use crate::core::state::DaemonState;
+use crate::scanner::preview::ContentPreview;
 use crate::tui::layout::OverviewPane;
 use crate::tui::preferences::{DensityMode, HintVerbosity, StartScreen};
 use crate::tui::telemetry::{
@@ -248,6 +249,20 @@ impl BallastVolume {
     }
 }
 
+// ──────────────────── candidate preview ────────────────────
+
+/// Content sample for the decision whose detail pane is open.
+///
+/// Gathered lazily by the runtime so only paths an operator actually opens
+/// are read. A path that no longer exists (already deleted) carries an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidatePreview {
+    /// Decision path the sample belongs to.
+    pub path: String,
+    /// The sample, or why it could not be taken.
+    pub result: Result<ContentPreview, String>,
+}
+
 // ──────────────────── timeline filter ────────────────────
 
 /// Severity-level filter for the timeline screen (S2).
@@ -532,6 +547,8 @@ pub struct DashboardModel {
     pub explainability_partial: bool,
     /// Diagnostic message from the telemetry adapter.
     pub explainability_diagnostics: String,
+    /// Content sample for the open detail pane, keyed by path.
+    pub explainability_preview: Option<CandidatePreview>,
 
     // ── Candidates screen (S4) state ──
     /// Cached candidate ranking for the candidates screen.
@@ -636,6 +653,7 @@ impl DashboardModel {
             explainability_source: DataSource::None,
             explainability_partial: false,
             explainability_diagnostics: String::new(),
+            explainability_preview: None,
             candidates_list: Vec::new(),
             candidates_selected: 0,
             candidates_detail: false,
@@ -798,6 +816,35 @@ impl DashboardModel {
             .get(self.explainability_selected)
     }
 
+    /// Content preview for the selected decision, once it has been loaded.
+    #[must_use]
+    pub fn explainability_selected_preview(&self) -> Option<&CandidatePreview> {
+        let decision = self.explainability_selected_decision()?;
+        self.explainability_preview
+            .as_ref()
+            .filter(|preview| preview.path == decision.path)
+    }
+
+    /// Request a content preview when the detail pane is open on a path
+    /// that has not been sampled yet.
+    #[must_use]
+    pub fn explainability_preview_cmd(&self) -> DashboardCmd {
+        if !self.explainability_detail {
+            return DashboardCmd::None;
+        }
+        match self.explainability_selected_decision() {
+            Some(decision)
+                if self
+                    .explainability_preview
+                    .as_ref()
+                    .is_none_or(|preview| preview.path != decision.path) =>
+            {
+                DashboardCmd::FetchPreview(decision.path.clone())
+            }
+            _ => DashboardCmd::None,
+        }
+    }
+
     // ── Candidates (S4) methods ──
 
     /// Move the candidates cursor up. Returns `true` if the cursor moved.
@@ -1033,6 +1080,8 @@ pub enum DashboardMsg {
     TelemetryCandidates(TelemetryResult<Vec<DecisionEvidence>>),
     /// Per-volume ballast inventory arrived.
     TelemetryBallast(TelemetryResult<Vec<BallastVolume>>),
+    /// A content preview for a reviewed decision finished.
+    PreviewLoaded(CandidatePreview),
     /// Frame metrics reported by the runtime after each render cycle.
     FrameMetrics { duration_ms: f64 },
 }
@@ -1061,6 +1110,8 @@ pub enum DashboardCmd {
     ScheduleNotificationExpiry { id: u64, after: Duration },
     /// Execute a preference mutation and apply updated profile values.
     ExecutePreferenceAction(PreferenceAction),
+    /// Sample a decision path's contents and deliver `PreviewLoaded`.
+    FetchPreview(String),
 }
 
 // ──────────────────── tests ────────────────────
@@ -1525,6 +1576,31 @@ mod tests {
         assert!(!model.explainability_detail);
     }
 
+    #[test]
+    fn explainability_preview_requested_once_per_open_path() {
+        let mut model = test_model();
+        model.explainability_decisions = vec![sample_decision(1)];
+        assert!(matches!(
+            model.explainability_preview_cmd(),
+            DashboardCmd::None
+        ));
+
+        model.explainability_toggle_detail();
+        assert!(matches!(
+            model.explainability_preview_cmd(),
+            DashboardCmd::FetchPreview(ref path) if path == "/test"
+        ));
+
+        model.explainability_preview = Some(CandidatePreview {
+            path: String::from("/test"),
+            result: Err(String::from("no longer on disk")),
+        });
+        assert!(matches!(
+            model.explainability_preview_cmd(),
+            DashboardCmd::None
+        ));
+    }
+
     #[test]
     fn explainability_selected_decision_returns_correct() {
         let mut model = test_model();
@@ -11,13 +11,14 @@
 
 #![allow(dead_code)] // API surface used by downstream test and signoff beads.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
 use super::model::DashboardModel;
 use super::render;
 use super::test_harness::{DashboardHarness, sample_healthy_state, sample_pressured_state};
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -245,6 +246,9 @@ fn multi_mount_state() -> DaemonState {
             available: 1,
             total: 4,
             released: 3,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:45:30.123Z".into()),
@@ -260,6 +264,12 @@ fn multi_mount_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 104_857_600,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -284,6 +294,9 @@ fn state_without_rates() -> DaemonState {
             available: 5,
             total: 5,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: None,
@@ -293,6 +306,12 @@ fn state_without_rates() -> DaemonState {
         counters: Counters::default(),
         policy_mode: "enforce".into(),
         memory_rss_bytes: 16_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -317,6 +336,9 @@ fn state_no_scan_history() -> DaemonState {
             available: 10,
             total: 10,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: None,
@@ -326,6 +348,12 @@ fn state_no_scan_history() -> DaemonState {
         counters: Counters::default(),
         policy_mode: "enforce".into(),
         memory_rss_bytes: 8_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -16,7 +16,8 @@ use super::layout::{
     build_timeline_layout, is_terminal_too_small,
 };
 use super::model::{
-    BallastVolume, DashboardModel, NotificationLevel, PreferenceProfileMode, Screen,
+    BallastVolume, CandidatePreview, DashboardModel, NotificationLevel, PreferenceProfileMode,
+    Screen,
 };
 use super::preferences::{DensityMode, HintVerbosity, StartScreen};
 use super::theme::{AccessibilityProfile, PaletteEntry, SpacingScale, Theme, ThemePalette};
@@ -25,6 +26,7 @@ use super::widgets::{
     mini_bar_chart, progress_indicator, section_header, segmented_gauge, separator_line, sparkline,
     status_badge, styled_badge, styled_status_strip, trend_label,
 };
+use crate::core::maintenance::MaintenanceWindow;
 use crate::tui::telemetry::{DataSource, DecisionEvidence, TimelineEvent};
 
 use ftui::core::geometry::Rect;
@@ -567,6 +569,16 @@ fn styled_pressure_summary<'a>(model: &'a DashboardModel, theme: &'a Theme, pane
             header.push(progress_indicator(model.tick, level_color));
         }
         lines.push(Line::from_spans(header));
+        if let Some(label) = state.maintenance.as_ref().and_then(maintenance_label) {
+            lines.push(Line::from_spans([
+                Span::raw("  "),
+                styled_badge("PAUSED", theme.palette.warning_color()),
+                Span::styled(
+                    format!(" {label}"),
+                    Style::default().fg(theme.palette.warning_color()),
+                ),
+            ]));
+        }
 
         // Mount rows with segmented gauges.
         let gauge_w = gauge_width_for(pane_width).max(8);
@@ -1369,7 +1381,7 @@ fn frame_timeline_list_styled<'a>(model: &'a DashboardModel, theme: &'a Theme, r
         };
         let time = extract_time(&event.timestamp);
         let sev_color = severity_styled_color(&event.severity, theme);
-        let path_str = event.path.as_deref().map_or("-", |p| truncate_path(p, 24));
+        let path_str = event_subject(event, 24);
         let mut row = vec![
             cursor_span,
             Span::styled(
@@ -1680,6 +1692,12 @@ fn frame_explainability_detail_text(
     if let Some(decision) = model.explainability_selected_decision() {
         if model.explainability_detail {
             render_decision_detail(decision, theme, usize::from(pane_width).max(40), &mut out);
+            if let Some(preview) = model.explainability_selected_preview() {
+                let _ = writeln!(out);
+                for line in preview_lines(preview) {
+                    let _ = writeln!(out, "{line}");
+                }
+            }
         } else {
             let _ = writeln!(out, "path={}", decision.path);
             let _ = writeln!(out, "action={}", decision.action);
@@ -1814,7 +1832,12 @@ fn frame_explainability_detail_styled<'a>(
     let secondary = theme.palette.text_secondary();
     if let Some(decision) = model.explainability_selected_decision() {
         if model.explainability_detail {
-            styled_decision_detail(decision, theme, usize::from(pane_width).max(40))
+            styled_decision_detail(
+                decision,
+                model.explainability_selected_preview(),
+                theme,
+                usize::from(pane_width).max(40),
+            )
         } else {
             let (dir, file) = split_path_dir_file(&decision.path);
             let score_color = theme.palette.gauge_gradient(decision.total_score);
@@ -1877,7 +1900,12 @@ fn frame_explainability_detail_styled<'a>(
     }
 }
 
-fn styled_decision_detail<'a>(decision: &'a DecisionEvidence, theme: &'a Theme, width: usize) -> Text<'a> {
+fn styled_decision_detail<'a>(
+    decision: &'a DecisionEvidence,
+    preview: Option<&CandidatePreview>,
+    theme: &'a Theme,
+    width: usize,
+) -> Text<'a> {
     let muted = theme.palette.muted_color();
     let primary = theme.palette.text_primary();
     let secondary = theme.palette.text_secondary();
@@ -2051,6 +2079,19 @@ fn styled_decision_detail<'a>(decision: &'a DecisionEvidence, theme: &'a Theme,
             Span::styled(&*decision.summary, Style::default().fg(primary)),
         ]));
     }
+    if let Some(preview) = preview {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Contents",
+            Style::default().fg(primary).bold(),
+        )));
+        for line in preview_lines(preview) {
+            lines.push(Line::from(Span::styled(
+                line,
+                Style::default().fg(secondary),
+            )));
+        }
+    }
     Text::from_lines(lines)
 }
 
@@ -2288,7 +2329,7 @@ fn frame_candidates_detail_styled<'a>(model: &'a DashboardModel, theme: &'a Them
     if let Some(candidate) = model.candidates_selected_item() {
         if model.candidates_detail {
             // Full detail reuses the decision detail renderer.
-            styled_decision_detail(candidate, theme, usize::from(pane_width).max(40))
+            styled_decision_detail(candidate, None, theme, usize::from(pane_width).max(40))
         } else {
             let (dir, file) = split_path_dir_file(&candidate.path);
             let score_color = theme.palette.gauge_gradient(candidate.total_score);
@@ -2704,7 +2745,7 @@ fn frame_log_search_list_text(model: &DashboardModel, theme: &Theme, rows: usize
             extract_time(&event.timestamp),
             severity_badge(&event.severity, theme),
             event.event_type,
-            event.path.as_deref().map_or("-", |p| truncate_path(p, 24)),
+            event_subject(event, 24),
         );
     }
     out
@@ -2777,7 +2818,7 @@ fn frame_log_search_list_styled<'a>(model: &'a DashboardModel, theme: &'a Theme,
         };
         let time = extract_time(&event.timestamp);
         let sev_color = severity_styled_color(&event.severity, theme);
-        let path_str = event.path.as_deref().map_or("-", |p| truncate_path(p, 24));
+        let path_str = event_subject(event, 24);
         let row = vec![
             cursor_span,
             Span::styled(
@@ -3883,6 +3924,10 @@ fn render_pressure_summary(model: &DashboardModel, theme: &Theme, pane_width: u1
             state.pressure.mounts.len(),
         );
 
+        if let Some(label) = state.maintenance.as_ref().and_then(maintenance_label) {
+            let _ = write!(out, "\n  maintenance PAUSED {label}");
+        }
+
         let pane_w = usize::from(pane_width);
         let path_w = pane_w.saturating_sub(40).clamp(8, 26);
         let _ = write!(
@@ -3938,6 +3983,26 @@ fn render_pressure_summary(model: &DashboardModel, theme: &Theme, pane_width: u1
     }
 }
 
+/// Countdown for an active maintenance window, e.g. `1h 20m left, alerts
+/// muted (storage migration)`; `None` once the window has lapsed.
+fn maintenance_label(window: &MaintenanceWindow) -> Option<String> {
+    let left = window.remaining(chrono::Utc::now())?;
+    let muted = if window.mute_alerts {
+        ", alerts muted"
+    } else {
+        ""
+    };
+    let reason = window
+        .reason
+        .as_deref()
+        .map(|reason| format!(" ({reason})"))
+        .unwrap_or_default();
+    Some(format!(
+        "{} left{muted}{reason}",
+        human_duration(left.as_secs())
+    ))
+}
+
 fn gauge_width_for(pane_width: u16) -> usize {
     usize::from(pane_width).clamp(28, 64) / 3
 }
@@ -4334,7 +4399,7 @@ fn render_event_row(cursor: &str, event: &TimelineEvent, theme: &Theme, out: &mu
     use std::fmt::Write as _;
     let time = extract_time(&event.timestamp);
     let sev_badge = severity_badge(&event.severity, theme);
-    let path_short = event.path.as_deref().map_or("-", |p| truncate_path(p, 30));
+    let path_short = event_subject(event, 30);
     let size_str = event.size_bytes.map(human_bytes).unwrap_or_default();
     let success_marker = match event.success {
         Some(true) => " \u{2713}",
@@ -4696,6 +4761,40 @@ fn render_decision_detail(
     }
 }
 
+/// Plain lines describing a candidate's sampled contents.
+fn preview_lines(preview: &CandidatePreview) -> Vec<String> {
+    let sample = match &preview.result {
+        Err(reason) => return vec![format!("  contents:    ({reason})")],
+        Ok(sample) if sample.files == 0 => {
+            return vec![format!(
+                "  contents:    no files in {} directories",
+                sample.dirs
+            )];
+        }
+        Ok(sample) => sample,
+    };
+    let mut lines = vec![format!(
+        "  contents:    {} files, {}{}",
+        sample.files,
+        human_bytes(sample.bytes),
+        if sample.truncated { " (sampled)" } else { "" }
+    )];
+    for file in &sample.largest {
+        lines.push(format!(
+            "    {:>9}  {}",
+            human_bytes(file.size_bytes),
+            file.path.display()
+        ));
+    }
+    let types: Vec<String> = sample
+        .types
+        .iter()
+        .map(|t| format!("{} x{}", t.extension, t.files))
+        .collect();
+    lines.push(format!("  types:       {}", types.join(", ")));
+    lines
+}
+
 /// Render a horizontal bar for a single scoring factor (0.0..=1.0).
 fn render_factor_bar(out: &mut String, label: &str, value: f64, width: usize, _theme: &Theme) {
     use std::fmt::Write as _;
@@ -4726,6 +4825,16 @@ fn action_badge(action: &str, theme: &Theme) -> String {
     status_badge(label, palette, theme.accessibility)
 }
 
+/// Text shown after a timeline row's event type: the note itself for
+/// annotations, otherwise the tail of the affected path.
+fn event_subject(event: &TimelineEvent, max_len: usize) -> &str {
+    if event.event_type == "annotation" {
+        let note = event.details.as_deref().unwrap_or("-");
+        return &note[..note.floor_char_boundary(max_len)];
+    }
+    event.path.as_deref().map_or("-", |p| truncate_path(p, max_len))
+}
+
 /// Truncate a path string for display, keeping the tail end.
 fn truncate_path(path: &str, max_len: usize) -> &str {
     if path.len() <= max_len {
@@ -5469,11 +5578,12 @@ fn notification_badge(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
     use std::time::Duration;
 
     use super::*;
-    use crate::daemon::self_monitor::{
+    use crate::core::state::{
         BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
     };
     use crate::tui::model::{Overlay, SeverityFilter};
@@ -5498,6 +5608,9 @@ mod tests {
                 available: 2,
                 total: 4,
                 released: 2,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: Some(String::from("2026-02-16T00:59:00Z")),
@@ -5513,6 +5626,12 @@ mod tests {
             },
             policy_mode: "enforce".into(),
             memory_rss_bytes: 52_428_800,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
         }
     }
 
@@ -5662,6 +5781,9 @@ mod tests {
                 available: 1,
                 total: 4,
                 released: 3,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: Some(String::from("2026-02-16T01:45:30.123Z")),
@@ -5677,6 +5799,12 @@ mod tests {
             },
             policy_mode: "enforce".into(),
             memory_rss_bytes: 104_857_600,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
         }
     }
 
@@ -17,8 +17,8 @@ use serde_json::json;
 use sha2::{Digest, Sha256};
 
 use super::model::{
-    DashboardCmd, DashboardModel, DashboardMsg, NotificationLevel, Overlay, PreferenceAction,
-    PreferenceProfileMode, Screen,
+    CandidatePreview, DashboardCmd, DashboardModel, DashboardMsg, NotificationLevel, Overlay,
+    PreferenceAction, PreferenceProfileMode, Screen,
 };
 use super::preferences::{self, ResolvedPreferences, UserPreferences};
 use super::telemetry::{
@@ -28,7 +28,8 @@ use super::telemetry::{
 use super::theme::AccessibilityProfile;
 use super::{input, render, update};
 use crate::cli::dashboard::{self, DashboardConfig as LegacyDashboardConfig};
-use crate::daemon::self_monitor::DaemonState;
+use crate::core::state::{DaemonState, StateParseError, StateReadError, read_state};
+use crate::scanner::preview::ContentPreview;
 
 /// Which runtime path to execute.
 ///
@@ -372,6 +373,13 @@ fn run_new_cockpit(config: &DashboardRuntimeConfig) -> io::Result<()> {
         notification_timers.push((id, Instant::now() + Duration::from_secs(8)));
     }
 
+    // A state file from a newer daemon stays unreadable for the whole
+    // session; say why once instead of silently showing no daemon data.
+    if let Some(notice) = newer_schema_notice(&config.state_file) {
+        let id = model.push_notification(NotificationLevel::Warning, notice);
+        notification_timers.push((id, Instant::now() + Duration::from_secs(8)));
+    }
+
     // Initial data fetch.
     let initial = read_state_file(&config.state_file);
     update::update(&mut model, DashboardMsg::DataUpdate(initial));
@@ -572,14 +580,43 @@ fn execute_cmd(
                 }
             }
         }
+        DashboardCmd::FetchPreview(path) => {
+            // Bounded by the preview entry budget, so a synchronous read is
+            // short enough to stay on the event loop.
+            let result = ContentPreview::gather(Path::new(&path)).map_err(|err| {
+                if err.kind() == io::ErrorKind::NotFound {
+                    "no longer on disk".to_string()
+                } else {
+                    err.to_string()
+                }
+            });
+            let preview = CandidatePreview { path, result };
+            let inner_cmd = update::update(model, DashboardMsg::PreviewLoaded(preview));
+            execute_cmd(
+                model,
+                state_file,
+                inner_cmd,
+                timers,
+                preference_state,
+                telemetry,
+            );
+        }
     }
 }
 
 /// Read and parse the daemon state file. Returns `None` on any error.
 fn read_state_file(path: &Path) -> Option<Box<DaemonState>> {
-    let content = std::fs::read_to_string(path).ok()?;
-    let state: DaemonState = serde_json::from_str(&content).ok()?;
-    Some(Box::new(state))
+    read_state(path)
+        .ok()
+        .map(|snapshot| Box::new(snapshot.state))
+}
+
+/// Upgrade hint when the state file was written with a newer schema.
+fn newer_schema_notice(path: &Path) -> Option<String> {
+    match read_state(path) {
+        Err(err @ StateReadError::Parse(StateParseError::NewerSchema(_))) => Some(err.to_string()),
+        _ => None,
+    }
 }
 
 fn run_legacy_fallback(config: &DashboardRuntimeConfig) -> io::Result<()> {
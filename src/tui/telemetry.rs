@@ -1013,6 +1013,18 @@ impl TelemetryQueryAdapter for CompositeTelemetryAdapter {
     }
 }
 
+/// Decode one activity-log line as the dashboard timeline would, including
+/// schema-shield recovery of legacy field names. `None` for a dropped line.
+#[must_use]
+pub fn parse_timeline_line(line: &str) -> Option<TimelineEvent> {
+    match parse_jsonl_entry_with_schema_shield(line) {
+        ParseOutcome::Exact(entry) | ParseOutcome::Recovered(entry) => {
+            Some(logentry_to_timeline(&entry))
+        }
+        ParseOutcome::Dropped => None,
+    }
+}
+
 fn parse_jsonl_entry_with_schema_shield(line: &str) -> ParseOutcome {
     if let Ok(entry) = serde_json::from_str::<crate::logger::jsonl::LogEntry>(line) {
         return ParseOutcome::Exact(entry);
@@ -1093,6 +1105,18 @@ fn parse_event_type(input: &str) -> Option<crate::logger::jsonl::EventType> {
         "config_reload" => Some(crate::logger::jsonl::EventType::ConfigReload),
         "error" => Some(crate::logger::jsonl::EventType::Error),
         "emergency" => Some(crate::logger::jsonl::EventType::Emergency),
+        "reclaim_calibration" => Some(crate::logger::jsonl::EventType::ReclaimCalibration),
+        "mount_change" => Some(crate::logger::jsonl::EventType::MountChange),
+        "attribution_snapshot" => Some(crate::logger::jsonl::EventType::AttributionSnapshot),
+        "workspace_create" => Some(crate::logger::jsonl::EventType::WorkspaceCreate),
+        "workspace_destroy" => Some(crate::logger::jsonl::EventType::WorkspaceDestroy),
+        "artifact_restore" => Some(crate::logger::jsonl::EventType::ArtifactRestore),
+        "ballast_replenish_deferred" => {
+            Some(crate::logger::jsonl::EventType::BallastReplenishDeferred)
+        }
+        "artifact_quarantine" => Some(crate::logger::jsonl::EventType::ArtifactQuarantine),
+        "annotation" => Some(crate::logger::jsonl::EventType::Annotation),
+        "memory_pressure" => Some(crate::logger::jsonl::EventType::MemoryPressure),
         _ => match compact.as_str() {
             "artifactdelete" => Some(crate::logger::jsonl::EventType::ArtifactDelete),
             "ballastrelease" => Some(crate::logger::jsonl::EventType::BallastRelease),
@@ -1103,6 +1127,17 @@ fn parse_event_type(input: &str) -> Option<crate::logger::jsonl::EventType> {
             "daemonstart" => Some(crate::logger::jsonl::EventType::DaemonStart),
             "daemonstop" => Some(crate::logger::jsonl::EventType::DaemonStop),
             "configreload" => Some(crate::logger::jsonl::EventType::ConfigReload),
+            "reclaimcalibration" => Some(crate::logger::jsonl::EventType::ReclaimCalibration),
+            "mountchange" => Some(crate::logger::jsonl::EventType::MountChange),
+            "attributionsnapshot" => Some(crate::logger::jsonl::EventType::AttributionSnapshot),
+            "workspacecreate" => Some(crate::logger::jsonl::EventType::WorkspaceCreate),
+            "workspacedestroy" => Some(crate::logger::jsonl::EventType::WorkspaceDestroy),
+            "artifactrestore" => Some(crate::logger::jsonl::EventType::ArtifactRestore),
+            "ballastreplenishdeferred" => {
+                Some(crate::logger::jsonl::EventType::BallastReplenishDeferred)
+            }
+            "artifactquarantine" => Some(crate::logger::jsonl::EventType::ArtifactQuarantine),
+            "memorypressure" => Some(crate::logger::jsonl::EventType::MemoryPressure),
             _ => None,
         },
     }
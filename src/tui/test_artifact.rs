@@ -276,7 +276,7 @@ impl ArtifactRecorder {
     }
 
     /// Feed a daemon state, recording the step.
-    pub fn feed_state(&mut self, state: crate::daemon::self_monitor::DaemonState) {
+    pub fn feed_state(&mut self, state: crate::core::state::DaemonState) {
         let screen_before = screen_name(self.harness.screen());
         self.harness.feed_state(state);
         self.record_step_from_last("FeedState".to_string(), screen_before);
@@ -297,7 +297,7 @@ impl ArtifactRecorder {
     }
 
     /// Run the standard startup sequence.
-    pub fn startup_with_state(&mut self, state: crate::daemon::self_monitor::DaemonState) {
+    pub fn startup_with_state(&mut self, state: crate::core::state::DaemonState) {
         self.tick();
         self.feed_state(state);
         self.tick();
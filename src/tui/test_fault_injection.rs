@@ -11,7 +11,7 @@
 
 #![allow(clippy::too_many_lines)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -27,7 +27,7 @@ use super::telemetry::{
     NullTelemetryAdapter, TelemetryQueryAdapter,
 };
 use super::test_harness::{DashboardHarness, HarnessStep, sample_healthy_state};
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 use crate::platform::pal::{FsStats, MemoryInfo, MockPlatform, MountPoint, PlatformPaths};
@@ -97,6 +97,9 @@ fn sample_state() -> DaemonState {
             available: 9,
             total: 10,
             released: 1,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T00:00:30Z".to_string()),
@@ -112,6 +115,12 @@ fn sample_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 1_048_576,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -16,6 +16,7 @@
 
 #![allow(dead_code)] // Harness API surface — methods/fields used by future test modules.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -25,7 +26,7 @@ use sha2::{Digest, Sha256};
 use super::model::{DashboardCmd, DashboardError, DashboardModel, DashboardMsg, Overlay, Screen};
 use super::render;
 use super::update;
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -396,6 +397,9 @@ pub fn sample_healthy_state() -> DaemonState {
             available: 10,
             total: 10,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T00:59:00Z".into()),
@@ -411,6 +415,12 @@ pub fn sample_healthy_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 32_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -435,6 +445,9 @@ pub fn sample_pressured_state() -> DaemonState {
             available: 2,
             total: 10,
             released: 8,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:59:00Z".into()),
@@ -450,6 +463,12 @@ pub fn sample_pressured_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 64_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -6,6 +6,7 @@
 //!
 //! **Bead:** bd-xzt.4.11
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -17,7 +18,7 @@ use super::model::{
     Screen,
 };
 use super::update;
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 use crate::tui::telemetry::{
@@ -87,6 +88,9 @@ fn arb_daemon_state() -> impl Strategy<Value = DaemonState> {
             available: 5,
             total: 10,
             released: 5,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-01-01T00:30:00Z".into()),
@@ -102,6 +106,12 @@ fn arb_daemon_state() -> impl Strategy<Value = DaemonState> {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 32_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     })
 }
 
@@ -438,7 +448,10 @@ proptest! {
                             rate_bps: Some(0.0),
                         }],
                     },
-                    ballast: BallastState { available: 5, total: 10, released: 5 },
+                    ballast: BallastState {
+                        available: 5, total: 10, released: 5,
+                        files_verified: 0, files_corrupted: 0, files_repaired: 0,
+                    },
                     last_scan: LastScanState { at: None, candidates: 0, deleted: 0 },
                     counters: Counters {
                         scans: 0, deletions: 0, bytes_freed: 0, errors: 0,
@@ -446,6 +459,12 @@ proptest! {
                     },
                     policy_mode: "enforce".into(),
         memory_rss_bytes: 0,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
                 };
                 update::update(&mut model, DashboardMsg::DataUpdate(Some(Box::new(state))));
             } else {
@@ -1045,6 +1064,9 @@ fn degraded_state_toggles_with_data_updates() {
                 available: 0,
                 total: 0,
                 released: 0,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: None,
@@ -1060,6 +1082,12 @@ fn degraded_state_toggles_with_data_updates() {
             },
             policy_mode: "enforce".into(),
             memory_rss_bytes: 0,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
         }))),
     );
     assert!(!model.degraded);
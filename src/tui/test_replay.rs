@@ -17,6 +17,8 @@
 
 #![allow(clippy::too_many_lines)] // Test fixtures are verbose by nature.
 
+use std::collections::BTreeMap;
+
 use super::model::{DashboardMsg, Screen};
 use super::telemetry::{
     DataSource, DecisionEvidence, FactorBreakdown, TelemetryResult, TimelineEvent,
@@ -24,7 +26,7 @@ use super::telemetry::{
 use super::test_harness::{
     DashboardHarness, HarnessStep, sample_healthy_state, sample_pressured_state,
 };
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -51,6 +53,9 @@ fn yellow_pressure_state() -> DaemonState {
             available: 8,
             total: 10,
             released: 2,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:29:00Z".into()),
@@ -66,6 +71,12 @@ fn yellow_pressure_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 48_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -90,6 +101,9 @@ fn red_critical_state() -> DaemonState {
             available: 1,
             total: 10,
             released: 9,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:59:00Z".into()),
@@ -105,6 +119,12 @@ fn red_critical_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 72_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -129,6 +149,9 @@ fn recovery_state() -> DaemonState {
             available: 10,
             total: 10,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T02:29:00Z".into()),
@@ -144,6 +167,12 @@ fn recovery_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 40_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -168,6 +197,9 @@ fn ballast_depleted_state() -> DaemonState {
             available: 0,
             total: 10,
             released: 10,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:39:00Z".into()),
@@ -183,6 +215,12 @@ fn ballast_depleted_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 80_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -215,6 +253,9 @@ fn multi_mount_divergent_state() -> DaemonState {
             available: 3,
             total: 10,
             released: 7,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:19:00Z".into()),
@@ -230,6 +271,12 @@ fn multi_mount_divergent_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 52_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
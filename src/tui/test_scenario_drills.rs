@@ -14,6 +14,8 @@
 
 #![allow(clippy::too_many_lines)]
 
+use std::collections::BTreeMap;
+
 use super::e2e_artifact::{
     ArtifactCollector, AssertionRecord, CaseStatus, DiagnosticEntry, FrameCapture,
 };
@@ -22,7 +24,7 @@ use super::telemetry::{
     DataSource, DecisionEvidence, FactorBreakdown, TelemetryResult, TimelineEvent,
 };
 use super::test_harness::{DashboardHarness, sample_healthy_state};
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -49,6 +51,9 @@ fn yellow_state() -> DaemonState {
             available: 8,
             total: 10,
             released: 2,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:29:00Z".into()),
@@ -64,6 +69,12 @@ fn yellow_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 48_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -88,6 +99,9 @@ fn red_state() -> DaemonState {
             available: 1,
             total: 10,
             released: 9,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:59:00Z".into()),
@@ -103,6 +117,12 @@ fn red_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 72_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -127,6 +147,9 @@ fn recovery_state() -> DaemonState {
             available: 10,
             total: 10,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T02:29:00Z".into()),
@@ -142,6 +165,12 @@ fn recovery_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 40_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -166,6 +195,9 @@ fn depleted_state() -> DaemonState {
             available: 0,
             total: 10,
             released: 10,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:39:00Z".into()),
@@ -181,6 +213,12 @@ fn depleted_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 80_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -213,6 +251,9 @@ fn multi_mount_state() -> DaemonState {
             available: 3,
             total: 10,
             released: 7,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:19:00Z".into()),
@@ -228,6 +269,12 @@ fn multi_mount_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 52_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -24,6 +24,7 @@
 
 #![allow(clippy::too_many_lines)]
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -32,7 +33,7 @@ use sha2::{Digest, Sha256};
 use super::model::{DashboardModel, DashboardMsg, Screen};
 use super::render;
 use super::update;
-use crate::daemon::self_monitor::{
+use crate::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -169,6 +170,9 @@ fn healthy_state() -> DaemonState {
             available: 10,
             total: 10,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T00:59:00Z".into()),
@@ -184,6 +188,12 @@ fn healthy_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 32_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
@@ -216,6 +226,9 @@ fn pressured_state() -> DaemonState {
             available: 2,
             total: 10,
             released: 8,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:59:00Z".into()),
@@ -231,6 +244,12 @@ fn pressured_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 64_000_000,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
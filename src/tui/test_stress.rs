@@ -454,19 +454,19 @@ fn rate_histories_bounded_after_many_mount_updates() {
         // Alternate between 1 and 3 mounts to trigger prune logic.
         if i % 5 == 0 {
             state.pressure.mounts = vec![
-                crate::daemon::self_monitor::MountPressure {
+                crate::core::state::MountPressure {
                     path: "/data".into(),
                     free_pct: 50.0,
                     level: "green".into(),
                     rate_bps: Some(100.0),
                 },
-                crate::daemon::self_monitor::MountPressure {
+                crate::core::state::MountPressure {
                     path: "/tmp".into(),
                     free_pct: 80.0,
                     level: "green".into(),
                     rate_bps: Some(50.0),
                 },
-                crate::daemon::self_monitor::MountPressure {
+                crate::core::state::MountPressure {
                     path: "/home".into(),
                     free_pct: 60.0,
                     level: "green".into(),
@@ -845,7 +845,9 @@ fn navigate_deep_history() {
 
 #[test]
 fn rapid_data_updates_track_counters_correctly() {
-    use crate::daemon::self_monitor::{
+    use std::collections::BTreeMap;
+
+    use crate::core::state::{
         BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
     };
 
@@ -868,6 +870,9 @@ fn rapid_data_updates_track_counters_correctly() {
             available: 5,
             total: 5,
             released: 0,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: None,
@@ -883,6 +888,12 @@ fn rapid_data_updates_track_counters_correctly() {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 0,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     };
 
     let mut model = test_model();
@@ -277,7 +277,7 @@ impl ThemePalette {
             "yellow" => self.warning_color(),
             "orange" => self.orange_color(),
             "red" => self.danger_color(),
-            "critical" => self.critical_color(),
+            "critical" | "readonly" => self.critical_color(),
             _ => self.muted_color(),
         }
     }
@@ -303,7 +303,7 @@ impl ThemePalette {
             "green" => self.success,
             "yellow" => self.warning,
             "orange" | "red" => self.danger,
-            "critical" => self.critical,
+            "critical" | "readonly" => self.critical,
             _ => self.neutral,
         }
     }
@@ -207,6 +207,17 @@ pub fn update(model: &mut DashboardModel, msg: DashboardMsg) -> DashboardCmd {
             } else if model.explainability_selected >= model.explainability_decisions.len() {
                 model.explainability_selected = model.explainability_decisions.len() - 1;
             }
+            model.explainability_preview_cmd()
+        }
+
+        DashboardMsg::PreviewLoaded(preview) => {
+            // Drop samples that arrive after the operator has moved on.
+            if model
+                .explainability_selected_decision()
+                .is_some_and(|decision| decision.path == preview.path)
+            {
+                model.explainability_preview = Some(preview);
+            }
             DashboardCmd::None
         }
 
@@ -495,17 +506,17 @@ fn handle_explainability_key(model: &mut DashboardModel, key: ftui::KeyEvent) ->
         // Up/k: move cursor up in the decisions list.
         KeyCode::Up | KeyCode::Char('k') => {
             model.explainability_cursor_up();
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         // Down/j: move cursor down in the decisions list.
         KeyCode::Down | KeyCode::Char('j') => {
             model.explainability_cursor_down();
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         // Enter/Space: toggle detail pane for selected decision.
         KeyCode::Enter | KeyCode::Char(' ') => {
             model.explainability_toggle_detail();
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         // d: close detail pane (if open).
         KeyCode::Char('d') => {
@@ -680,15 +691,15 @@ fn handle_explainability_mouse(model: &mut DashboardModel, event: MouseEvent) ->
                 model.explainability_selected = idx;
                 model.explainability_detail = true;
             }
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         MouseEventKind::ScrollUp if point_in_body(model, event.x, event.y) => {
             model.explainability_cursor_up();
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         MouseEventKind::ScrollDown if point_in_body(model, event.x, event.y) => {
             model.explainability_cursor_down();
-            DashboardCmd::None
+            model.explainability_preview_cmd()
         }
         _ => DashboardCmd::None,
     }
@@ -994,12 +1005,15 @@ mod tests {
         KeyCode, KeyEvent, KeyEventKind, Modifiers, MouseButton, MouseEvent, MouseEventKind,
     };
 
+    use std::collections::BTreeMap;
+
     use super::*;
-    use crate::daemon::self_monitor::{
+    use crate::core::state::{
         BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
     };
+    use crate::scanner::preview::ContentPreview;
     use crate::tui::layout::{OverviewPane, PaneRect};
-    use crate::tui::model::{DashboardError, Overlay};
+    use crate::tui::model::{CandidatePreview, DashboardError, Overlay};
     use crate::tui::telemetry::DataSource;
 
     fn test_model() -> DashboardModel {
@@ -1152,6 +1166,9 @@ mod tests {
                 available: 3,
                 total: 5,
                 released: 2,
+                files_verified: 0,
+                files_corrupted: 0,
+                files_repaired: 0,
             },
             last_scan: LastScanState {
                 at: Some(String::from("2026-02-16T00:59:00Z")),
@@ -1167,6 +1184,12 @@ mod tests {
             },
             policy_mode: "enforce".into(),
             memory_rss_bytes: 52_428_800,
+            state_schema: 1,
+            rates: BTreeMap::new(),
+            maintenance: None,
+            config_reload: None,
+            cleanup_cooldowns: BTreeMap::new(),
+            deep_scan: None,
         }
     }
 
@@ -2041,6 +2064,32 @@ mod tests {
         assert!(!model.explainability_detail);
     }
 
+    #[test]
+    fn explainability_detail_fetches_preview_and_drops_stale_results() {
+        let mut model = test_model();
+        model.screen = Screen::Explainability;
+        model.explainability_decisions = vec![sample_decision(1)];
+
+        let cmd = update(&mut model, DashboardMsg::Key(make_key(KeyCode::Enter)));
+        assert!(matches!(cmd, DashboardCmd::FetchPreview(ref path) if path == "/test"));
+
+        let stale = CandidatePreview {
+            path: String::from("/elsewhere"),
+            result: Err(String::from("no longer on disk")),
+        };
+        update(&mut model, DashboardMsg::PreviewLoaded(stale));
+        assert!(model.explainability_preview.is_none());
+
+        let fresh = CandidatePreview {
+            path: String::from("/test"),
+            result: Ok(ContentPreview::default()),
+        };
+        update(&mut model, DashboardMsg::PreviewLoaded(fresh));
+        assert!(model.explainability_preview.is_some());
+        let cmd = update(&mut model, DashboardMsg::Key(make_key(KeyCode::Char('j'))));
+        assert!(matches!(cmd, DashboardCmd::None));
+    }
+
     #[test]
     fn explainability_keys_noop_on_other_screens() {
         let mut model = test_model();
@@ -7,13 +7,15 @@
 
 mod common;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
-use storage_ballast_helper::daemon::self_monitor::{
+use storage_ballast_helper::core::state::{
     BallastState, Counters, DAEMON_STATE_STALE_THRESHOLD_SECS, DaemonState, LastScanState,
-    MountPressure, PressureState, SelfMonitor,
+    MountPressure, PressureState,
 };
+use storage_ballast_helper::daemon::self_monitor::SelfMonitor;
 
 // ══════════════════════════════════════════════════════════════════
 // Section 1: Dashboard CLI Argument Semantics
@@ -160,6 +162,9 @@ fn sample_daemon_state() -> DaemonState {
             available: 5,
             total: 10,
             released: 2,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T01:00:00Z".to_string()),
@@ -175,6 +180,12 @@ fn sample_daemon_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 52_428_800, // 50 MB
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
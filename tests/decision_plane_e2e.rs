@@ -70,6 +70,7 @@ fn make_candidate(
     CandidateInput {
         path: PathBuf::from(format!("/data/p{idx}/.target_{suffix}")),
         size_bytes: size_gib * 1_073_741_824,
+        disk_bytes: size_gib * 1_073_741_824,
         age: Duration::from_secs(age_hours * 3600),
         classification: ArtifactClassification {
             pattern_name: ".target*".into(),
@@ -9,14 +9,14 @@
 
 mod common;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use storage_ballast_helper::core::config::{Config, DashboardConfig, DashboardMode};
-use storage_ballast_helper::daemon::self_monitor::{
+use storage_ballast_helper::core::state::{
     BallastState, Counters, DaemonState, LastScanState, MountPressure, PressureState,
 };
 
@@ -52,6 +52,9 @@ fn sample_daemon_state() -> DaemonState {
             available: 9,
             total: 10,
             released: 1,
+            files_verified: 0,
+            files_corrupted: 0,
+            files_repaired: 0,
         },
         last_scan: LastScanState {
             at: Some("2026-02-16T00:30:00Z".to_string()),
@@ -67,6 +70,12 @@ fn sample_daemon_state() -> DaemonState {
         },
         policy_mode: "enforce".into(),
         memory_rss_bytes: 1_048_576,
+        state_schema: 1,
+        rates: BTreeMap::new(),
+        maintenance: None,
+        config_reload: None,
+        cleanup_cooldowns: BTreeMap::new(),
+        deep_scan: None,
     }
 }
 
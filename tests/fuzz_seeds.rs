@@ -0,0 +1,96 @@
+//! Replays the fuzz seed corpora under `fuzz/seeds/` on stable.
+//!
+//! The cargo-fuzz targets need nightly, so CI runs their seeds here through the
+//! same entry points. Seeds taken from real daemon output must keep decoding;
+//! a seed that stops parsing means a format changed without its readers.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use storage_ballast_helper::core::config::Config;
+use storage_ballast_helper::core::state::parse_state;
+use storage_ballast_helper::scanner::decision_record::{
+    ExplainLevel, decision_summary_line, format_explain, parse_decision_from_details,
+};
+
+fn seeds(target: &str) -> Vec<(PathBuf, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz/seeds")
+        .join(target);
+    let mut seeds: Vec<_> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("read {}: {e}", dir.display()))
+        .map(|entry| {
+            let path = entry.expect("seed entry").path();
+            let raw = fs::read_to_string(&path).expect("seed is UTF-8");
+            (path, raw)
+        })
+        .collect();
+    seeds.sort();
+    assert!(!seeds.is_empty(), "no seeds in {}", dir.display());
+    seeds
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+#[test]
+fn config_seeds_load_except_the_readme_sketch() {
+    for (path, raw) in seeds("config_toml") {
+        let result = Config::from_toml_str(&raw);
+        // The README example sketches the layout and predates the schema.
+        if file_name(&path) == "readme_example.toml" {
+            assert!(result.is_err(), "{} now parses", path.display());
+            continue;
+        }
+        let config = result.unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        config.stable_hash().expect("valid config hashes");
+    }
+}
+
+#[test]
+fn state_seeds_decode() {
+    for (path, raw) in seeds("state_json") {
+        parse_state(&raw).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    }
+    assert!(parse_state(r#"{"state_schema": 99}"#).is_err());
+}
+
+#[test]
+fn decision_record_seeds_render_at_every_level() {
+    for (path, raw) in seeds("decision_record") {
+        let record = parse_decision_from_details(&raw)
+            .unwrap_or_else(|| panic!("{} does not decode", path.display()));
+        assert!(!decision_summary_line(&record).is_empty());
+        for level in [
+            ExplainLevel::L0,
+            ExplainLevel::L1,
+            ExplainLevel::L2,
+            ExplainLevel::L3,
+        ] {
+            assert!(!format_explain(&record, level).is_empty());
+            let _ = record.to_json_at_level(level);
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn jsonl_seeds_keep_every_complete_line() {
+    use storage_ballast_helper::tui::telemetry::parse_timeline_line;
+
+    for (path, raw) in seeds("jsonl_timeline") {
+        for line in raw.lines() {
+            // A line cut off mid-write is the one thing the shield drops.
+            let complete = line.ends_with('}');
+            assert_eq!(
+                parse_timeline_line(line).is_some(),
+                complete,
+                "{}: {line}",
+                path.display()
+            );
+        }
+    }
+}
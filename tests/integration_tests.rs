@@ -36,8 +36,9 @@ use storage_ballast_helper::scanner::patterns::{
 use storage_ballast_helper::scanner::protection::ProtectionRegistry;
 use storage_ballast_helper::scanner::scoring::{
     CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome, EvidenceLedger, EvidenceTerm,
-    ScoreFactors, ScoringEngine,
+    ScoreFactors, ScoringEngine, ScoringInputs,
 };
+use storage_ballast_helper::scanner::synthetic::{self, SyntheticTreeSpec};
 use storage_ballast_helper::scanner::walker::{DirectoryWalker, WalkerConfig};
 
 #[test]
@@ -123,6 +124,237 @@ fn json_flag_accepted_by_status() {
     );
 }
 
+#[test]
+fn json_failure_emits_structured_error_object() {
+    let result = common::run_cli_case(
+        "json_failure_emits_structured_error_object",
+        &["dashboard", "--json"],
+    );
+    assert!(
+        !result.status.success(),
+        "dashboard --json should fail; log: {}",
+        result.log_path.display()
+    );
+
+    let last_line = result.stdout.lines().last().unwrap_or_default();
+    let payload: Value = serde_json::from_str(last_line).unwrap_or_else(|err| {
+        panic!(
+            "expected JSON error object, parse failed: {err}; stdout={:?}; log={}",
+            result.stdout,
+            result.log_path.display()
+        )
+    });
+    assert_eq!(payload["command"], "dashboard");
+    assert_eq!(payload["error"]["code"], "user_error");
+    assert_eq!(
+        payload["error"]["exit_code"].as_i64(),
+        result.status.code().map(i64::from)
+    );
+    assert!(payload["error"]["remediation"].is_string());
+    assert_eq!(
+        result.stdout.matches("\"error\"").count(),
+        1,
+        "exactly one error object expected; stdout={:?}",
+        result.stdout
+    );
+}
+
+#[test]
+fn json_usage_error_emits_structured_error_object() {
+    let result = common::run_cli_case(
+        "json_usage_error_emits_structured_error_object",
+        &["--json", "scan", "--no-such-flag"],
+    );
+    assert!(!result.status.success());
+    let payload: Value = serde_json::from_str(result.stdout.trim()).unwrap_or_else(|err| {
+        panic!(
+            "expected JSON usage error, parse failed: {err}; stdout={:?}; log={}",
+            result.stdout,
+            result.log_path.display()
+        )
+    });
+    assert_eq!(payload["error"]["code"], "usage_error");
+    assert_eq!(payload["error"]["exit_code"], 2);
+}
+
+#[test]
+fn exit_codes_command_lists_contract_json() {
+    let result = common::run_cli_case(
+        "exit_codes_command_lists_contract_json",
+        &["exit-codes", "--json"],
+    );
+    assert!(
+        result.status.success(),
+        "log: {}",
+        result.log_path.display()
+    );
+    let payload: Value = serde_json::from_str(result.stdout.trim()).expect("exit-codes JSON");
+    let codes: Vec<i64> = payload["exit_codes"]
+        .as_array()
+        .expect("exit_codes array")
+        .iter()
+        .filter_map(|row| row["code"].as_i64())
+        .collect();
+    assert_eq!(codes, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn synthetic_tree_walk_surfaces_every_artifact_root() {
+    let tmp = tempfile::TempDir::new().expect("tempdir");
+    let spec = SyntheticTreeSpec {
+        projects: 12,
+        ..SyntheticTreeSpec::default()
+    };
+    let stats = synthetic::generate(tmp.path(), &spec).expect("generate synthetic tree");
+
+    let walker = DirectoryWalker::new(
+        WalkerConfig {
+            root_paths: vec![tmp.path().to_path_buf()],
+            max_depth: 10,
+            follow_symlinks: false,
+            cross_devices: false,
+            parallelism: 2,
+            excluded_paths: HashSet::new(),
+        },
+        ProtectionRegistry::marker_only(),
+    );
+    let entries = walker.walk().expect("walk synthetic tree");
+    let artifact_roots = entries
+        .iter()
+        .filter(|e| {
+            e.path
+                .file_name()
+                .is_some_and(|n| n == "target" || n == "node_modules" || n == "__pycache__")
+        })
+        .count();
+    assert_eq!(artifact_roots as u64, stats.artifact_roots);
+}
+
+#[test]
+fn bench_scan_synthetic_reports_runs_json() {
+    let result = common::run_cli_case(
+        "bench_scan_synthetic_reports_runs_json",
+        &[
+            "bench",
+            "scan",
+            "--synthetic",
+            "--projects",
+            "8",
+            "--iterations",
+            "2",
+            "--json",
+        ],
+    );
+    assert!(
+        result.status.success(),
+        "log: {}",
+        result.log_path.display()
+    );
+    let payload: Value = serde_json::from_str(result.stdout.trim()).expect("bench JSON");
+    assert_eq!(payload["command"], "bench scan");
+    assert_eq!(payload["runs"].as_array().map(Vec::len), Some(2));
+    assert!(payload["runs"][0]["entries"].as_u64().unwrap_or(0) > 0);
+    assert_eq!(payload["synthetic"]["spec"]["projects"], 8);
+    let root = payload["roots"][0].as_str().expect("root path");
+    assert!(
+        !Path::new(root).exists(),
+        "synthetic tree should be removed"
+    );
+}
+
+#[test]
+fn explain_command_reports_scoring_and_protection_json() {
+    let tmp = tempfile::TempDir::new().expect("tempdir");
+    let project = tmp.path().join("app");
+    let target = common::create_fake_rust_target(&project, Duration::from_hours(72));
+    let kept = tmp.path().join("kept");
+    let kept_target = common::create_fake_rust_target(&kept, Duration::from_hours(72));
+    fs::write(kept.join(".sbh-protect"), "").expect("write marker");
+    let config = tmp.path().join("config.toml");
+    fs::write(
+        &config,
+        format!(
+            "[scanner]\nroot_paths = [{:?}]\nmin_file_age_minutes = 0\n",
+            tmp.path().to_string_lossy()
+        ),
+    )
+    .expect("write config");
+    let config = config.to_string_lossy().into_owned();
+
+    let explain = |name: &str, path: &Path| -> Value {
+        let path = path.to_string_lossy();
+        let args = [
+            "--config", &config, "--json", "explain", &path, "--level", "1",
+        ];
+        let result = common::run_cli_case(name, &args);
+        assert!(
+            result.status.success(),
+            "log: {}",
+            result.log_path.display()
+        );
+        serde_json::from_str(result.stdout.trim()).expect("explain JSON")
+    };
+
+    let payload = explain("explain_command_scores_rust_target", &target);
+    assert_eq!(payload["command"], "explain");
+    assert_eq!(payload["protected_by"], Value::Null);
+    assert_eq!(
+        payload["decision"]["classification"]["category"],
+        "RustTarget"
+    );
+    assert!(payload["decision"]["factor_contributions"].is_array());
+    assert!(payload["verdict"].is_string());
+    assert_eq!(payload["pattern"]["category"], "RustTarget");
+    let settings = payload["config"].as_array().expect("config values");
+    let min_age = settings
+        .iter()
+        .find(|setting| setting["key"] == "scanner.min_file_age_minutes")
+        .expect("min age setting");
+    assert_eq!(min_age["value"], "0");
+    assert!(min_age["default"].is_string(), "{min_age}");
+
+    let payload = explain("explain_command_reports_marker", &kept_target);
+    assert_eq!(payload["candidate"], false);
+    let reason = payload["protected_by"].as_str().expect("protection reason");
+    assert!(reason.contains(".sbh-protect"), "{reason}");
+}
+
+#[test]
+fn command_failures_map_to_documented_exit_codes() {
+    let missing_config = std::env::temp_dir().join("sbh-exit-code-test-missing/config.toml");
+    let missing_config = missing_config.to_string_lossy().into_owned();
+    let cases: [(&str, Vec<&str>, i32); 5] = [
+        (
+            "usage_error_unknown_flag",
+            vec!["scan", "--no-such-flag"],
+            2,
+        ),
+        ("usage_error_missing_arg", vec!["protect"], 2),
+        ("user_error_dashboard_json", vec!["dashboard", "--json"], 1),
+        (
+            "user_error_unknown_help_topic",
+            vec!["help", "no-such-topic"],
+            1,
+        ),
+        (
+            "runtime_error_missing_config",
+            vec!["--config", missing_config.as_str(), "status"],
+            2,
+        ),
+    ];
+
+    for (name, args, expected) in &cases {
+        let result = common::run_cli_case(name, args);
+        assert_eq!(
+            result.status.code(),
+            Some(*expected),
+            "{name}: args={args:?}; stderr={:?}; log={}",
+            result.stderr,
+            result.log_path.display()
+        );
+    }
+}
+
 #[test]
 fn completions_command_generates_shell_script() {
     let result = common::run_cli_case(
@@ -781,6 +1013,7 @@ fn green_pressure_no_deletions() {
     let input = CandidateInput {
         path: env.root().join("project/src/main.rs"),
         size_bytes: 12,
+        disk_bytes: 12,
         age: Duration::from_secs(3600),
         classification: ArtifactClassification::unknown(),
         signals: StructuralSignals::default(),
@@ -929,7 +1162,8 @@ fn scoring_pipeline_ranks_artifacts_above_source() {
     // High-confidence Rust target artifact with strong structural signals.
     let target_input = CandidateInput {
         path: PathBuf::from("/tmp/project/target"),
-        size_bytes: 500_000_000,            // 500 MB
+        size_bytes: 500_000_000, // 500 MB
+        disk_bytes: 500_000_000,
         age: Duration::from_secs(4 * 3600), // 4 hours
         classification: ArtifactClassification {
             pattern_name: "cargo-target".into(),
@@ -953,6 +1187,7 @@ fn scoring_pipeline_ranks_artifacts_above_source() {
     let source_input = CandidateInput {
         path: PathBuf::from("/tmp/project/src/main.rs"),
         size_bytes: 500,
+        disk_bytes: 500,
         age: Duration::from_secs(3600), // 1 hour
         classification: ArtifactClassification::unknown(),
         signals: StructuralSignals::default(),
@@ -1008,6 +1243,7 @@ fn dry_run_deletes_nothing() {
     let candidate = CandidateInput {
         path: artifact.clone(),
         size_bytes: 1024,
+        disk_bytes: 1024,
         age: Duration::from_secs(86400),
         classification: class,
         signals: StructuralSignals {
@@ -1027,6 +1263,8 @@ fn dry_run_deletes_nothing() {
             circuit_breaker_threshold: 3,
             circuit_breaker_cooldown: Duration::from_secs(1),
             check_open_files: false,
+            truncate_categories: Vec::new(),
+            ..DeletionConfig::default()
         },
         None,
     );
@@ -1215,6 +1453,7 @@ fn batch_scoring_ranks_correctly() {
         CandidateInput {
             path: PathBuf::from("/tmp/project/target"),
             size_bytes: 500_000_000,
+            disk_bytes: 500_000_000,
             age: Duration::from_secs(4 * 3600), // 4 hours
             classification: ArtifactClassification {
                 pattern_name: "cargo-target".into(),
@@ -1236,6 +1475,7 @@ fn batch_scoring_ranks_correctly() {
         CandidateInput {
             path: PathBuf::from("/tmp/project/notes.txt"),
             size_bytes: 100,
+            disk_bytes: 100,
             age: Duration::from_secs(2 * 3600), // 2 hours
             classification: ArtifactClassification::unknown(),
             signals: StructuralSignals::default(),
@@ -1282,6 +1522,7 @@ fn e2e_candidate(path: &str, size_gb: u64, age_hours: u64, confidence: f64) -> C
     CandidateInput {
         path: PathBuf::from(path),
         size_bytes: size_gb * 1_073_741_824,
+        disk_bytes: size_gb * 1_073_741_824,
         age: Duration::from_secs(age_hours * 3600),
         classification: ArtifactClassification {
             pattern_name: ".target*".into(),
@@ -1345,6 +1586,7 @@ fn e2e_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             size: 0.70,
             structure: 0.95,
             pressure_multiplier: 1.5,
+            regeneration_cost: 1.0,
         },
         vetoed: false,
         veto_reason: None,
@@ -1356,6 +1598,7 @@ fn e2e_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             combined_confidence: 0.92,
         },
         size_bytes: 3_000_000_000,
+        disk_bytes: 3_000_000_000,
         age: Duration::from_secs(5 * 3600),
         decision: DecisionOutcome {
             action,
@@ -1374,6 +1617,7 @@ fn e2e_scored_candidate(action: DecisionAction, score: f64) -> CandidacyScore {
             }],
             summary: "test".to_string(),
         },
+        inputs: ScoringInputs::default(),
     }
 }
 
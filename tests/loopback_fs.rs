@@ -0,0 +1,385 @@
+//! Loopback filesystem harness: end-to-end runs against a real, tiny filesystem.
+//!
+//! Each test formats a small image, mounts it over a loop device, writes to it
+//! until the kernel returns ENOSPC, and then drives the `sbh` binary against
+//! the full mount. Tempdir tests never see a genuinely full filesystem, so
+//! they cannot catch bugs in how pressure is read, how ballast gets released
+//! when no block is left, or whether cleanup frees space the kernel agrees on.
+//!
+//! Mounting needs root plus `mkfs.<fs>`, `mount`, and `umount`, so the tests
+//! are skipped unless `SBH_LOOPBACK_TESTS=1` is set and the process runs as
+//! root. `SBH_LOOPBACK_FS` picks the filesystem (`ext4` by default, or `xfs`).
+//!
+//! ```text
+//! sudo -E env SBH_LOOPBACK_TESTS=1 cargo test --test loopback_fs -- --nocapture
+//! ```
+
+mod common;
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use serde_json::Value;
+
+const ARTIFACT_AGE: Duration = Duration::from_hours(72);
+const BALLAST_FILES: usize = 4;
+const BALLAST_FILE_BYTES: u64 = 4 << 20;
+const ARTIFACT_FILES: usize = 6;
+const ARTIFACT_FILE_BYTES: usize = 2 << 20;
+
+// ──────────────────── gating ────────────────────
+
+fn loopback_enabled(test: &str) -> bool {
+    if std::env::var("SBH_LOOPBACK_TESTS").is_ok_and(|v| v == "1" || v == "true") {
+        if nix::unistd::geteuid().is_root() {
+            return true;
+        }
+        eprintln!("[{test}] skipped: SBH_LOOPBACK_TESTS is set but loop mounts need root");
+    } else {
+        eprintln!("[{test}] skipped: set SBH_LOOPBACK_TESTS=1 (as root) to run");
+    }
+    false
+}
+
+fn run_tool(program: &str, args: &[&str]) {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .unwrap_or_else(|e| panic!("failed to run {program}: {e}"));
+    assert!(
+        output.status.success(),
+        "{program} {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// ──────────────────── loop mount fixture ────────────────────
+
+/// A freshly formatted filesystem mounted under a tempdir, with a state
+/// directory beside it for `state.json` and the activity logs.
+struct LoopMount {
+    root: tempfile::TempDir,
+    mount: PathBuf,
+    state: PathBuf,
+}
+
+impl LoopMount {
+    fn new() -> Self {
+        let fs_type = std::env::var("SBH_LOOPBACK_FS").unwrap_or_else(|_| "ext4".to_string());
+        // xfs refuses images under 300 MiB.
+        let (size, mkfs_args): (u64, &[&str]) = match fs_type.as_str() {
+            "ext4" => (64 << 20, &["-q", "-F", "-m", "0"]),
+            "xfs" => (320 << 20, &["-q", "-f"]),
+            other => panic!("SBH_LOOPBACK_FS={other} is not supported (use ext4 or xfs)"),
+        };
+
+        let root = tempfile::tempdir().expect("create loopback tempdir");
+        let image = root.path().join("fs.img");
+        File::create(&image)
+            .and_then(|f| f.set_len(size))
+            .expect("create sparse image");
+        let mount = root.path().join("mnt");
+        let state = root.path().join("state");
+        fs::create_dir_all(&mount).expect("create mount point");
+        fs::create_dir_all(&state).expect("create state dir");
+
+        let image_str = image.to_string_lossy();
+        let mount_str = mount.to_string_lossy();
+        let mut args = mkfs_args.to_vec();
+        args.push(&image_str);
+        run_tool(&format!("mkfs.{fs_type}"), &args);
+        run_tool("mount", &["-o", "loop", &image_str, &mount_str]);
+
+        Self { root, mount, state }
+    }
+
+    fn ballast_dir(&self) -> PathBuf {
+        self.mount.join(".sbh-ballast")
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.root.path().join("config.toml")
+    }
+
+    /// Write a config that watches only the loop mount, keeps ballast there,
+    /// and leaves every host filesystem alone.
+    fn write_config(&self) -> PathBuf {
+        let config = format!(
+            r#"[scanner]
+root_paths = ["{mount}"]
+min_file_age_minutes = 0
+
+[ballast]
+file_count = {BALLAST_FILES}
+file_size_bytes = {BALLAST_FILE_BYTES}
+
+[[ballast.pools]]
+path = "{ballast}"
+
+[[pressure.mounts]]
+path = "/"
+ballast = false
+
+[paths]
+state_file = "{state}/state.json"
+sqlite_db = "{state}/activity.sqlite3"
+jsonl_log = "{state}/activity.jsonl"
+ballast_dir = "{ballast}"
+"#,
+            mount = self.mount.display(),
+            ballast = self.ballast_dir().display(),
+            state = self.state.display(),
+        );
+        let path = self.config_path();
+        fs::write(&path, config).expect("write config");
+        path
+    }
+
+    /// Run `sbh` with this mount's config and a HOME inside the fixture.
+    fn sbh(&self, case: &str, args: &[&str]) -> common::CmdResult {
+        let config_path = self.config_path();
+        let config = config_path.to_string_lossy();
+        let home = self.root.path().to_string_lossy();
+        let mut cli_args = vec!["--config", &*config];
+        cli_args.extend_from_slice(args);
+        common::run_cli_case_with_env(case, &cli_args, &[("HOME", &*home)])
+    }
+
+    fn free_bytes(&self) -> u64 {
+        let stats = nix::sys::statvfs::statvfs(&self.mount).expect("statvfs loop mount");
+        stats.blocks_available() * stats.fragment_size()
+    }
+
+    fn ballast_file_count(&self) -> usize {
+        fs::read_dir(self.ballast_dir()).map_or(0, |entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| {
+                    e.file_name()
+                        .to_string_lossy()
+                        .starts_with("SBH_BALLAST_FILE_")
+                })
+                .count()
+        })
+    }
+
+    /// A stale cargo project whose `target/` is the obvious thing to delete.
+    fn create_stale_project(&self) -> PathBuf {
+        let project = self.mount.join("project");
+        fs::create_dir_all(project.join("src")).expect("create project");
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"stale\"\n").expect("manifest");
+        fs::write(project.join("src/main.rs"), "fn main() {}\n").expect("source");
+        let target = common::create_fake_rust_target(&project, ARTIFACT_AGE);
+        let chunk = vec![0xA5_u8; ARTIFACT_FILE_BYTES];
+        for i in 0..ARTIFACT_FILES {
+            fs::write(target.join(format!("debug/deps/libbulk{i}.rlib")), &chunk)
+                .expect("write artifact");
+        }
+        target
+    }
+
+    /// Write filler until the kernel reports ENOSPC; returns bytes written.
+    /// Large writes get close, then block-sized ones take the last blocks.
+    fn fill_until_enospc(&self) -> u64 {
+        let mut file = File::create(self.mount.join("filler.bin")).expect("create filler");
+        let mut written = 0_u64;
+        for chunk_len in [1 << 20, 4 << 10] {
+            let chunk = vec![0_u8; chunk_len];
+            loop {
+                match file
+                    .write(&chunk)
+                    .and_then(|n| file.sync_data().map(|()| n))
+                {
+                    Ok(0) => break,
+                    Ok(n) => written += n as u64,
+                    Err(e) if is_enospc(&e) => break,
+                    Err(e) => panic!("unexpected error filling loop mount: {e}"),
+                }
+            }
+        }
+        assert!(
+            self.write_probe().is_err_and(|e| is_enospc(&e)),
+            "mount should be out of space after filling"
+        );
+        written
+    }
+
+    /// Try to write a small file, as a build would.
+    fn write_probe(&self) -> io::Result<()> {
+        let mut probe = File::create(self.mount.join("probe.bin"))?;
+        probe.write_all(&vec![1_u8; 256 << 10])?;
+        probe.sync_all()
+    }
+}
+
+impl Drop for LoopMount {
+    fn drop(&mut self) {
+        let mount = self.mount.to_string_lossy().into_owned();
+        let unmounted = Command::new("umount")
+            .arg(&mount)
+            .status()
+            .is_ok_and(|s| s.success());
+        if !unmounted {
+            let _ = Command::new("umount").args(["-l", &mount]).status();
+        }
+    }
+}
+
+fn is_enospc(err: &io::Error) -> bool {
+    err.raw_os_error() == Some(libc::ENOSPC)
+}
+
+fn last_json_line(result: &common::CmdResult) -> Value {
+    let line = result
+        .stdout
+        .lines()
+        .rev()
+        .find(|l| l.trim_start().starts_with('{'))
+        .unwrap_or_else(|| panic!("no JSON on stdout; log: {}", result.log_path.display()));
+    serde_json::from_str(line).expect("stdout JSON parses")
+}
+
+// ──────────────────── scenarios ────────────────────
+
+#[test]
+fn enospc_escalates_pressure_releases_ballast_and_cleans_up() {
+    if !loopback_enabled("enospc_escalates_pressure_releases_ballast_and_cleans_up") {
+        return;
+    }
+    let fs = LoopMount::new();
+    fs.write_config();
+
+    let provision = fs.sbh("loopback_provision", &["ballast", "provision", "--json"]);
+    assert!(
+        provision.status.success(),
+        "provision failed; log: {}",
+        provision.log_path.display()
+    );
+    assert_eq!(fs.ballast_file_count(), BALLAST_FILES);
+
+    let target = fs.create_stale_project();
+    fs.fill_until_enospc();
+    let free_when_full = fs.free_bytes();
+
+    let run = fs.sbh("loopback_daemon_once", &["daemon", "--once", "--json"]);
+    assert!(
+        run.status.success(),
+        "daemon --once failed; log: {}",
+        run.log_path.display()
+    );
+    let summary = last_json_line(&run);
+    assert_eq!(summary["level"], "critical", "summary: {summary}");
+    assert_eq!(
+        summary["causing_mount"].as_str().map(PathBuf::from),
+        Some(fs.mount.clone())
+    );
+    assert!(
+        run.stderr.contains("Green -> Critical"),
+        "expected a pressure transition; log: {}",
+        run.log_path.display()
+    );
+
+    assert!(
+        fs.ballast_file_count() < BALLAST_FILES,
+        "ballast should be released under ENOSPC; log: {}",
+        run.log_path.display()
+    );
+    assert!(
+        !target.exists(),
+        "stale target/ should be deleted; log: {}",
+        run.log_path.display()
+    );
+    assert!(
+        summary["deletions"].as_u64() >= Some(1),
+        "summary: {summary}"
+    );
+    assert!(fs.mount.join("project/src/main.rs").exists());
+    assert!(fs.mount.join("project/Cargo.toml").exists());
+
+    let freed = fs.free_bytes() - free_when_full;
+    let artifact_bytes = (ARTIFACT_FILES * ARTIFACT_FILE_BYTES) as u64;
+    assert!(
+        freed >= artifact_bytes,
+        "kernel should report the artifacts' space free again (freed {freed})"
+    );
+    fs.write_probe().expect("writes succeed after cleanup");
+
+    let state: Value = serde_json::from_str(
+        &fs::read_to_string(fs.state.join("state.json")).expect("state.json written"),
+    )
+    .expect("state.json parses");
+    assert!(
+        state["ballast"]["released"].as_u64() >= Some(1),
+        "state: {state}"
+    );
+}
+
+#[test]
+fn check_fails_on_a_full_mount_until_ballast_is_released() {
+    if !loopback_enabled("check_fails_on_a_full_mount_until_ballast_is_released") {
+        return;
+    }
+    let fs = LoopMount::new();
+    fs.write_config();
+    let provision = fs.sbh("loopback_check_provision", &["ballast", "provision"]);
+    assert!(provision.status.success());
+    fs.fill_until_enospc();
+
+    let mount = fs.mount.to_string_lossy().into_owned();
+    let need = BALLAST_FILE_BYTES.to_string();
+    let full = fs.sbh("loopback_check_full", &["check", &mount, "--need", &need]);
+    assert!(
+        !full.status.success(),
+        "check should fail on a full mount; log: {}",
+        full.log_path.display()
+    );
+
+    let count = BALLAST_FILES.to_string();
+    let release = fs.sbh("loopback_check_release", &["ballast", "release", &count]);
+    assert!(
+        release.status.success(),
+        "release failed; log: {}",
+        release.log_path.display()
+    );
+    assert_eq!(fs.ballast_file_count(), 0);
+    assert!(fs.free_bytes() >= BALLAST_FILE_BYTES);
+
+    let relieved = fs.sbh(
+        "loopback_check_relieved",
+        &["check", &mount, "--need", &need],
+    );
+    assert!(
+        relieved.status.success(),
+        "check should pass once ballast is released; log: {}",
+        relieved.log_path.display()
+    );
+}
+
+#[test]
+fn dry_run_on_a_full_mount_deletes_nothing() {
+    if !loopback_enabled("dry_run_on_a_full_mount_deletes_nothing") {
+        return;
+    }
+    let fs = LoopMount::new();
+    fs.write_config();
+    let target = fs.create_stale_project();
+    fs.fill_until_enospc();
+
+    let run = fs.sbh(
+        "loopback_dry_run",
+        &["daemon", "--once", "--dry-run", "--json"],
+    );
+    assert!(
+        run.status.success(),
+        "daemon --once --dry-run failed; log: {}",
+        run.log_path.display()
+    );
+    let summary = last_json_line(&run);
+    assert_eq!(summary["level"], "critical", "summary: {summary}");
+    assert_eq!(summary["dry_run"], true);
+    assert!(target.join("debug/deps/libbulk0.rlib").exists());
+    assert!(fs.write_probe().is_err_and(|e| is_enospc(&e)));
+}
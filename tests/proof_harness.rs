@@ -28,7 +28,7 @@ use storage_ballast_helper::scanner::patterns::{
 };
 use storage_ballast_helper::scanner::scoring::{
     CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome, EvidenceLedger, EvidenceTerm,
-    ScoreFactors, ScoringEngine,
+    ScoreFactors, ScoringEngine, ScoringInputs,
 };
 
 // ════════════════════════════════════════════════════════════
@@ -101,6 +101,7 @@ fn make_candidate_input(
     CandidateInput {
         path: PathBuf::from(path),
         size_bytes: size_gib * 1_073_741_824,
+        disk_bytes: size_gib * 1_073_741_824,
         age: Duration::from_secs(age_hours * 3600),
         classification: ArtifactClassification {
             pattern_name: ".target*".into(),
@@ -152,6 +153,7 @@ fn make_scored(action: DecisionAction, score: f64) -> CandidacyScore {
             size: 0.70,
             structure: 0.95,
             pressure_multiplier: 1.5,
+            regeneration_cost: 1.0,
         },
         vetoed: false,
         veto_reason: None,
@@ -163,6 +165,7 @@ fn make_scored(action: DecisionAction, score: f64) -> CandidacyScore {
             combined_confidence: 0.92,
         },
         size_bytes: 3_000_000_000,
+        disk_bytes: 3_000_000_000,
         age: Duration::from_secs(5 * 3600),
         decision: DecisionOutcome {
             action,
@@ -189,6 +192,7 @@ fn make_scored(action: DecisionAction, score: f64) -> CandidacyScore {
             ],
             summary: "test candidate".to_string(),
         },
+        inputs: ScoringInputs::default(),
     }
 }
 
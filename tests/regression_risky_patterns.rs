@@ -36,6 +36,7 @@ mod tests {
         let input = CandidateInput {
             path,
             size_bytes: 4096,
+            disk_bytes: 4096,
             age: Duration::from_secs(24 * 3600), // Old
             classification,
             signals,
@@ -71,6 +72,7 @@ mod tests {
         let input = CandidateInput {
             path,
             size_bytes: 4096,
+            disk_bytes: 4096,
             age: Duration::from_secs(24 * 3600),
             classification,
             signals,
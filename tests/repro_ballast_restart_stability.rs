@@ -17,7 +17,10 @@ mod tests {
             file_size_bytes: 4096 + 4096,
             replenish_cooldown_minutes: 0,
             auto_provision: true,
+            replenish_forecast_minutes: 60,
+            replenish_incremental: true,
             overrides: BTreeMap::new(),
+            pools: Vec::new(),
         }
     }
 
@@ -15,6 +15,8 @@ mod tests {
             metadata: EntryMetadata {
                 size_bytes: 100,
                 content_size_bytes: 100,
+                disk_bytes: 100,
+                content_disk_bytes: 100,
                 modified: SystemTime::UNIX_EPOCH + Duration::from_secs(mtime_secs),
                 created: None,
                 is_dir: false,
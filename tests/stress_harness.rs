@@ -251,6 +251,7 @@ fn make_candidate(path: &str, size: u64, age_secs: u64) -> CandidateInput {
     CandidateInput {
         path: PathBuf::from(path),
         size_bytes: size,
+        disk_bytes: size,
         age: Duration::from_secs(age_secs),
         classification: ArtifactClassification {
             pattern_name: "target".into(),
@@ -282,6 +283,8 @@ fn make_walk_entry(path: &str, size: u64, depth: usize) -> WalkEntry {
         metadata: EntryMetadata {
             size_bytes: size,
             content_size_bytes: size,
+            disk_bytes: size,
+            content_disk_bytes: size,
             modified: SystemTime::now() - Duration::from_secs(3600),
             created: None,
             is_dir: true,
@@ -1164,6 +1167,8 @@ fn run_scenario_g(seed: u64, iterations: usize) -> ScenarioResult {
                     metadata: EntryMetadata {
                         size_bytes: 1024 * (u64_from_usize(i) + 1),
                         content_size_bytes: 1024 * (u64_from_usize(i) + 1),
+                        disk_bytes: 1024 * (u64_from_usize(i) + 1),
+                        content_disk_bytes: 1024 * (u64_from_usize(i) + 1),
                         modified: SystemTime::now()
                             - Duration::from_secs(3600 * (u64_from_usize(i) + 1)),
                         created: None,
@@ -87,6 +87,7 @@ fn make_candidate(idx: usize, age_hours: u64, size_gib: u64) -> CandidateInput {
     CandidateInput {
         path: PathBuf::from(format!("/data/p{idx}/.target_{}", idx * 100)),
         size_bytes: size_gib * 1_073_741_824,
+        disk_bytes: size_gib * 1_073_741_824,
         age: Duration::from_secs(age_hours * 3600),
         classification: ArtifactClassification {
             pattern_name: ".target*".into(),
@@ -1070,7 +1071,10 @@ fn stress_ballast_lifecycle() {
         file_size_bytes: 1024 * 1024, // 1 MB each (small for test speed)
         replenish_cooldown_minutes: 0,
         auto_provision: true,
+        replenish_forecast_minutes: 60,
+        replenish_incremental: true,
         overrides: std::collections::BTreeMap::default(),
+        pools: Vec::new(),
     };
 
     let mut manager = BallastManager::new(ballast_dir, config).unwrap();